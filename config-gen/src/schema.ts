@@ -10,13 +10,15 @@ export const PRESET_VALUES = [
   "solid",
   "vue",
   "emptysvg",
-  "flutter"
+  "flutter",
+  "css",
+  "datauri"
 ] as const;
 
 export const PresetSchema = z.enum(PRESET_VALUES).meta({
   title: "Preset",
   description:
-    "Icon output preset. 'normal' means plain SVG mode. 'flutter' writes SVGs + a Dart barrel (lib/icons.dart by default). Others are framework presets or an emptysvg placeholder.",
+    "Icon output preset. 'normal' means plain SVG mode. 'flutter' writes SVGs + a Dart barrel (lib/icons.dart by default). 'css' writes SVGs + an icons.css of mask-image utility classes. 'datauri' writes data URI constants (.ts) + a combined icons.ts. Others are framework presets or an emptysvg placeholder.",
   default: DEFAULT_PRESET,
   examples: ["normal", "react", "solid", "emptysvg", "flutter"]
 });
@@ -38,12 +40,249 @@ export const FlutterBarrelClassSchema = z.string().min(1).meta({
 export const SvgViewCommandSchema = z.string().min(1).meta({
   title: "SVG View Command",
   description:
-    "Command used to open an SVG. Supports `%filename%` placeholder, for example: `zed %filename%`.",
+    "Command used to open an SVG. Supports `%filename%` (full path), `%name%`, `%ext%`, `%folder%` and `%date%` placeholders with optional filters like `%name|kebab%`, for example: `zed %filename%`.",
   examples: ["zed %filename%", "code %filename%", "open %filename%"]
 });
 
+export const THEME_VALUES = ["auto", "dark", "light", "high-contrast"] as const;
+
+export const ThemeSchema = z.enum(THEME_VALUES).meta({
+  title: "TUI Theme",
+  description:
+    "Built-in TUI color theme. 'auto' picks dark or light from the terminal background (COLORFGBG). Set NO_COLOR to disable colors entirely.",
+  default: "auto",
+  examples: ["auto", "light", "high-contrast"]
+});
+
+const ColorSchema = z.string().min(1);
+
+export const PaletteSchema = z
+  .object({
+    base_bg: ColorSchema.optional(),
+    panel_bg: ColorSchema.optional(),
+    input_bg: ColorSchema.optional(),
+    tab_bg: ColorSchema.optional(),
+    tab_bg_active: ColorSchema.optional(),
+    row_highlight_bg: ColorSchema.optional(),
+    text: ColorSchema.optional(),
+    muted_text: ColorSchema.optional(),
+    subtle_text: ColorSchema.optional(),
+    accent: ColorSchema.optional(),
+    accent_soft: ColorSchema.optional(),
+    error: ColorSchema.optional(),
+    warn: ColorSchema.optional()
+  })
+  .meta({
+    title: "TUI Palette",
+    description:
+      "Per-color overrides applied on top of `theme`. Values are `#rrggbb`, ANSI color names (e.g. `lightgreen`), or 0-255 indexes.",
+    examples: [{ accent: "#f472b6", row_highlight_bg: "#f472b6" }]
+  });
+
+export const ALIAS_CASE_VALUES = ["pascal", "camel", "constant"] as const;
+
+export const AliasCaseSchema = z.enum(ALIAS_CASE_VALUES).meta({
+  title: "Alias Case",
+  description:
+    "Casing for JS barrel export names: 'pascal' (IconHeart), 'camel' (iconHeart), 'constant' (ICON_HEART).",
+  default: "pascal",
+  examples: ["pascal", "constant"]
+});
+
+export const AliasPrefixSchema = z.string().meta({
+  title: "Alias Prefix",
+  description: "Prepended to the alias in export names. Default: `Icon`. Use an empty string for none.",
+  default: "Icon",
+  examples: ["Icon", ""]
+});
+
+export const AliasSuffixSchema = z.string().meta({
+  title: "Alias Suffix",
+  description: "Appended to the alias in export names, e.g. `Icon` for `HeartIcon`. Default: empty.",
+  default: "",
+  examples: ["Icon"]
+});
+
+export const AliasCollectionPrefixSchema = z.boolean().meta({
+  title: "Alias Collection Prefix",
+  description:
+    "Include the collection in inferred aliases (`lucide:heart` -> `LucideHeart`). When off, the prefixed form is only used if the plain alias already exists.",
+  default: false
+});
+
+const hookCommandSchema = (title: string, when: string) =>
+  z.string().min(1).meta({
+    title,
+    description: `Command run ${when}, once per icon. Supports \`%file%\`, \`%name%\` and \`%folder%\` placeholders with optional filters. Not run through a shell; a non-zero exit from a pre hook aborts the operation.`,
+    examples: ["pnpm icons:codegen", "sh -c 'echo %name% >> icons.log'"]
+  });
+
+export const PreAddCommandSchema = hookCommandSchema("Pre-Add Command", "before an icon file is written");
+export const PostAddCommandSchema = hookCommandSchema("Post-Add Command", "after an icon is added");
+export const PreDeleteCommandSchema = hookCommandSchema("Pre-Delete Command", "before an icon is deleted");
+export const PostDeleteCommandSchema = hookCommandSchema("Post-Delete Command", "after an icon is deleted");
+
+export const ExtendsSchema = z.union([z.string().min(1), z.array(z.string().min(1))]).meta({
+  title: "Extends",
+  description:
+    "Base config(s) to inherit from. Paths starting with `.` or `/` are relative to this file; anything else is resolved from `node_modules` (a package directory uses its `iconmate.config.jsonc`). Keys here override the base.",
+  examples: ["../iconmate.base.jsonc", "@acme/iconmate-config"]
+});
+
+export const ConfirmDeleteSchema = z.boolean().meta({
+  title: "Confirm Delete",
+  description:
+    "Ask before deleting the selected icon in the TUI. Set to false to delete on `d` right away; bulk deletes (`D`) always ask.",
+  default: true
+});
+
+export const AutoOpenAfterAddSchema = z.boolean().meta({
+  title: "Auto Open After Add",
+  description: "Open an icon with the SVG viewer right after it is added from the TUI.",
+  default: false
+});
+
+export const SanitizeSvgSchema = z.boolean().meta({
+  title: "Sanitize SVG",
+  description:
+    "Strip comments, `<?xml ?>` prologs and DOCTYPEs from saved SVGs. JSX and Vue presets always strip them; `--no-sanitize` skips it for one add.",
+  default: true
+});
+
+export const SvgFormatSchema = z.enum(["minified", "pretty"]).meta({
+  title: "SVG Format",
+  description:
+    "Layout of saved SVG/component markup: 'minified' puts it on one line, 'pretty' puts each element on its own indented line. Unset keeps the source's formatting. `--svg-format` overrides it.",
+  examples: ["pretty", "minified"]
+});
+
+export const SvgIndentSchema = z.number().int().min(0).max(8).meta({
+  title: "SVG Indent",
+  description: "Spaces per nesting level when `svg_format` is 'pretty'. Default: 2.",
+  default: 2
+});
+
+export const LineEndingsSchema = z.enum(["lf", "crlf"]).meta({
+  title: "Line Endings",
+  description:
+    "Line endings for icon files and the generated index. Every file also ends with exactly one newline. `--line-endings` overrides it.",
+  default: "lf"
+});
+
+export const EmojiSetSchema = z.string().min(1).meta({
+  title: "Emoji Set",
+  description:
+    "Iconify emoji set that `--icon emoji:🔥` (or a bare emoji) resolves through, e.g. 'noto', 'twemoji', 'openmoji', 'fluent-emoji-flat'. `--emoji-set` overrides it.",
+  default: "noto"
+});
+
+export const IndexHeaderSchema = z.string().meta({
+  title: "Index Header",
+  description:
+    "Comment written at the top of a newly created index.ts, e.g. 'AUTO-GENERATED by iconmate — do not edit'. Lines that aren't comments get a `// ` prefix; later updates keep it in place."
+});
+
+export const IndexFooterSchema = z.string().meta({
+  title: "Index Footer",
+  description: "Comment written at the end of a newly created index.ts. New exports are inserted above it."
+});
+
+export const IndexEslintDisableSchema = z.boolean().meta({
+  title: "Index ESLint Disable",
+  description: "Start a newly created index.ts with `/* eslint-disable */`.",
+  default: false
+});
+
+export const CodeStyleSchema = z
+  .object({
+    quotes: z.enum(["single", "double"]).optional().meta({
+      description: "Quote style for export paths and template imports."
+    }),
+    semicolons: z.boolean().optional().meta({
+      description: "End export lines and template statements with a semicolon."
+    }),
+    indent: z.union([z.literal(2), z.literal(4)]).optional().meta({
+      description: "Spaces per indentation level in component templates. SVG markup keeps `svg_indent`."
+    })
+  })
+  .strict()
+  .meta({
+    title: "Code Style",
+    description:
+      "Quotes, semicolons and indentation for new index.ts exports and component templates. Unset fields keep the existing index.ts style and the built-in templates.",
+    examples: [{ quotes: "double", semicolons: true, indent: 4 }]
+  });
+
+export const IconNamesDtsSchema = z.boolean().meta({
+  title: "Icon Names d.ts",
+  description:
+    "Generate `icon-names.d.ts` next to index.ts with `export type IconName = 'heart' | 'star' | ...`. Once it exists, add, delete and rename keep it up to date.",
+  default: false
+});
+
+export const IMPORT_QUERY_VALUES = ["react", "component", "raw", "url"] as const;
+
+export const ImportQuerySchema = z.enum(IMPORT_QUERY_VALUES).meta({
+  title: "Import Query",
+  description:
+    "Query appended to `.svg` imports in index.ts: 'react' (vite-plugin-svgr, `./heart.svg?react`), 'component' (vite-svg-loader), 'raw' or 'url'. `--import-query` overrides it."
+});
+
+export const SvgrCompatSchema = z.boolean().meta({
+  title: "SVGR Compat",
+  description:
+    "With preset='react', write components the way `svgr --typescript --ref --memo --title-prop` does: `Svg<FileName>` with a `title` prop, `forwardRef` and `memo`.",
+  default: false
+});
+
+export const IconRegistrySchema = z.boolean().meta({
+  title: "Icon Registry",
+  description:
+    "Maintain `icon-registry.ts` (dynamic imports keyed by icon name) and an `Icon.tsx` that lazy-loads from it, for `<Icon name=\"heart\" />`. `Icon.tsx` is only written when missing.",
+  default: false
+});
+
+export const LazyExportsSchema = z.boolean().meta({
+  title: "Lazy Exports",
+  description:
+    "Export icons as `lazy(() => import('./heart'))` (React, Solid) or `defineAsyncComponent(...)` (Vue) so each one is split out of the main bundle. Other presets keep static exports. `--lazy` turns it on per add.",
+  default: false
+});
+
+export const MaxIconSizeSchema = z
+  .union([
+    z.number().int().positive(),
+    z.string().regex(/^\s*\d+(\.\d+)?\s*([bB]|[kKmM]([iI]?[bB])?)?\s*$/)
+  ])
+  .meta({
+    title: "Max Icon Size",
+    description:
+      'Largest icon file `add` writes, in bytes or as a size like "16KB" (1 KB = 1024 bytes). What happens above it is set by `on_oversize`; `iconmate check` lists files already over it.'
+  });
+
+export const OnOversizeSchema = z.enum(["warn", "error"]).meta({
+  title: "On Oversize",
+  description:
+    "What `add` does with an icon over `max_icon_size`: 'warn' writes it with a warning, 'error' refuses unless `--force` is passed.",
+  default: "error"
+});
+
+export const FilenameCaseSchema = z.enum(["kebab", "snake", "camel", "pascal"]).meta({
+  title: "Filename Case",
+  description:
+    "Case for icon filenames `add` infers from the icon or name (`mdi-heart.svg` for 'kebab'), and the default `--case` for `iconmate normalize`. An explicit `--filename` is kept as written."
+});
+
+export const HistoryLogSchema = z.boolean().meta({
+  title: "History Log",
+  description:
+    "Append every add, delete and rename to `.iconmate/log.jsonl` (time, export, file, source and user) for `iconmate log`. Set to false to stop logging.",
+  default: true
+});
+
 export const LocalConfigSchema = z
   .object({
+    extends: ExtendsSchema.optional(),
     folder: z.string().min(1).optional().meta({
       description: "Folder where icons are written.",
       default: DEFAULT_FOLDER,
@@ -52,7 +291,37 @@ export const LocalConfigSchema = z
     preset: PresetSchema.optional(),
     svg_view_cmd: SvgViewCommandSchema.optional(),
     flutter_barrel_file: FlutterBarrelFileSchema.optional(),
-    flutter_barrel_class: FlutterBarrelClassSchema.optional()
+    flutter_barrel_class: FlutterBarrelClassSchema.optional(),
+    theme: ThemeSchema.optional(),
+    palette: PaletteSchema.optional(),
+    alias_case: AliasCaseSchema.optional(),
+    alias_prefix: AliasPrefixSchema.optional(),
+    alias_suffix: AliasSuffixSchema.optional(),
+    alias_collection_prefix: AliasCollectionPrefixSchema.optional(),
+    pre_add_cmd: PreAddCommandSchema.optional(),
+    post_add_cmd: PostAddCommandSchema.optional(),
+    pre_delete_cmd: PreDeleteCommandSchema.optional(),
+    post_delete_cmd: PostDeleteCommandSchema.optional(),
+    confirm_delete: ConfirmDeleteSchema.optional(),
+    auto_open_after_add: AutoOpenAfterAddSchema.optional(),
+    sanitize_svg: SanitizeSvgSchema.optional(),
+    svg_format: SvgFormatSchema.optional(),
+    svg_indent: SvgIndentSchema.optional(),
+    line_endings: LineEndingsSchema.optional(),
+    emoji_set: EmojiSetSchema.optional(),
+    index_header: IndexHeaderSchema.optional(),
+    index_footer: IndexFooterSchema.optional(),
+    index_eslint_disable: IndexEslintDisableSchema.optional(),
+    code_style: CodeStyleSchema.optional(),
+    icon_names_dts: IconNamesDtsSchema.optional(),
+    import_query: ImportQuerySchema.optional(),
+    svgr_compat: SvgrCompatSchema.optional(),
+    icon_registry: IconRegistrySchema.optional(),
+    lazy_exports: LazyExportsSchema.optional(),
+    max_icon_size: MaxIconSizeSchema.optional(),
+    on_oversize: OnOversizeSchema.optional(),
+    filename_case: FilenameCaseSchema.optional(),
+    history_log: HistoryLogSchema.optional()
   })
   .meta({
     id: "IconmateLocalConfig",
@@ -62,7 +331,11 @@ export const LocalConfigSchema = z
 
 export const GlobalConfigSchema = z
   .object({
-    svg_view_cmd: SvgViewCommandSchema.optional()
+    svg_view_cmd: SvgViewCommandSchema.optional(),
+    theme: ThemeSchema.optional(),
+    palette: PaletteSchema.optional(),
+    confirm_delete: ConfirmDeleteSchema.optional(),
+    auto_open_after_add: AutoOpenAfterAddSchema.optional()
   })
   .meta({
     id: "IconmateGlobalConfig",
@@ -1,3 +1,40 @@
+export type IconmateTheme = "auto" | "dark" | "light" | "high-contrast";
+
+/**
+ * TUI palette overrides. Values are `#rrggbb`, ANSI color names
+ * (e.g. `lightgreen`), or 0-255 indexes.
+ */
+export interface IconmatePalette {
+  base_bg?: string;
+  panel_bg?: string;
+  input_bg?: string;
+  tab_bg?: string;
+  tab_bg_active?: string;
+  row_highlight_bg?: string;
+  text?: string;
+  muted_text?: string;
+  subtle_text?: string;
+  accent?: string;
+  accent_soft?: string;
+  error?: string;
+  warn?: string;
+}
+
+/**
+ * Style of generated code. Unset fields keep the existing index.ts style and
+ * the built-in templates.
+ */
+export interface IconmateCodeStyle {
+  quotes?: "single" | "double";
+  semicolons?: boolean;
+  /** Spaces per indentation level in component templates. */
+  indent?: 2 | 4;
+}
+
+export type IconmateAliasCase = "pascal" | "camel" | "constant";
+
+export type IconmateImportQuery = "react" | "component" | "raw" | "url";
+
 export type IconmatePreset =
   | "normal"
   | "react"
@@ -5,12 +42,20 @@ export type IconmatePreset =
   | "solid"
   | "vue"
   | "emptysvg"
-  | "flutter";
+  | "flutter"
+  | "css"
+  | "datauri";
 
 /**
  * Project-level Iconmate config loaded from `iconmate.config.json`.
  */
 export interface IconmateLocalConfig {
+  /**
+   * Base config(s) to inherit from: a path relative to this file, or an npm
+   * package (resolved from `node_modules`). Keys here override the base.
+   */
+  extends?: string | string[];
+
   /**
    * Folder where icons are written.
    * Default: `src/assets/icons` (or `assets/icons` when preset is `flutter`).
@@ -19,7 +64,8 @@ export interface IconmateLocalConfig {
 
   /**
    * Output preset. `normal` means plain `.svg` mode. `flutter` writes SVGs
-   * and a Dart barrel file.
+   * and a Dart barrel file. `css` writes SVGs and an `icons.css` of mask
+   * utility classes. `datauri` writes data URI constants and an `icons.ts`.
    * Default: `"normal"` (auto-switches to `"flutter"` when a Flutter project
    * is detected and no explicit preset is configured).
    */
@@ -27,7 +73,8 @@ export interface IconmateLocalConfig {
 
   /**
    * Command used to open SVG files from the TUI.
-   * Use `%filename%` as the SVG file path placeholder.
+   * Use `%filename%` as the SVG file path placeholder (`%name%`, `%ext%`,
+   * `%folder%`, `%date%` and filters like `%name|kebab%` also work).
    */
   svg_view_cmd?: string;
 
@@ -42,6 +89,169 @@ export interface IconmateLocalConfig {
    * Default: `"AppIcons"`.
    */
   flutter_barrel_class?: string;
+
+  /**
+   * TUI color theme. `auto` picks dark or light from the terminal background.
+   * Set `NO_COLOR` to disable colors entirely.
+   * Default: `"auto"`.
+   */
+  theme?: IconmateTheme;
+
+  /**
+   * Per-color overrides applied on top of `theme`.
+   */
+  palette?: IconmatePalette;
+
+  /**
+   * Casing for JS barrel export names: `IconHeart`, `iconHeart`, `ICON_HEART`.
+   * Default: `"pascal"`.
+   */
+  alias_case?: IconmateAliasCase;
+
+  /**
+   * Prepended to the alias in export names. Default: `"Icon"` (`""` for none).
+   */
+  alias_prefix?: string;
+
+  /**
+   * Appended to the alias in export names, e.g. `"Icon"` for `HeartIcon`.
+   */
+  alias_suffix?: string;
+
+  /**
+   * Include the collection in inferred aliases (`lucide:heart` -> `LucideHeart`).
+   * When off, the prefixed form is only used if the plain alias already exists.
+   * Default: `false`.
+   */
+  alias_collection_prefix?: boolean;
+
+  /**
+   * Commands run once per icon around adds and deletes. `%file%`, `%name%`
+   * and `%folder%` are substituted. A failing pre hook aborts the operation.
+   */
+  pre_add_cmd?: string;
+  post_add_cmd?: string;
+  pre_delete_cmd?: string;
+  post_delete_cmd?: string;
+
+  /**
+   * Ask before deleting the selected icon in the TUI. Bulk deletes always ask.
+   * Default: `true`.
+   */
+  confirm_delete?: boolean;
+
+  /**
+   * Open an icon with the SVG viewer right after adding it from the TUI.
+   * Default: `false`.
+   */
+  auto_open_after_add?: boolean;
+
+  /**
+   * Strip comments, `<?xml ?>` prologs and DOCTYPEs from saved SVGs. JSX and
+   * Vue presets always strip them. Default: `true`.
+   */
+  sanitize_svg?: boolean;
+
+  /**
+   * Layout of saved markup: `minified` (one line) or `pretty` (one element per
+   * line). Unset keeps the source's formatting.
+   */
+  svg_format?: "minified" | "pretty";
+
+  /**
+   * Spaces per nesting level for `svg_format: "pretty"`.
+   * Default: `2`.
+   */
+  svg_indent?: number;
+
+  /**
+   * Line endings for icon files and the generated index.
+   * Default: `"lf"`.
+   */
+  line_endings?: "lf" | "crlf";
+
+  /**
+   * Iconify emoji set that emoji icon sources resolve through.
+   * Default: `"noto"`.
+   */
+  emoji_set?: string;
+
+  /**
+   * Comment at the top of a newly created index.ts.
+   */
+  index_header?: string;
+
+  /**
+   * Comment at the end of a newly created index.ts; exports go above it.
+   */
+  index_footer?: string;
+
+  /**
+   * Start a newly created index.ts with `/* eslint-disable *\/`.
+   * Default: `false`.
+   */
+  index_eslint_disable?: boolean;
+
+  /**
+   * Quotes, semicolons and indentation for new index.ts exports and component
+   * templates.
+   */
+  code_style?: IconmateCodeStyle;
+
+  /**
+   * Generate `icon-names.d.ts` with an `IconName` union of the folder's icons.
+   * Default: `false`.
+   */
+  icon_names_dts?: boolean;
+
+  /**
+   * Query appended to `.svg` imports in index.ts, e.g. `react` for
+   * vite-plugin-svgr (`./heart.svg?react`).
+   */
+  import_query?: IconmateImportQuery;
+
+  /**
+   * With preset `react`, write components the way SVGR does (`title` prop,
+   * `forwardRef`, `memo`).
+   * Default: `false`.
+   */
+  svgr_compat?: boolean;
+
+  /**
+   * Maintain `icon-registry.ts` and a lazy-loading `Icon.tsx` for
+   * `<Icon name="heart" />`.
+   * Default: `false`.
+   */
+  icon_registry?: boolean;
+
+  /**
+   * Export React/Solid icons with `lazy(() => import(...))` and Vue icons with
+   * `defineAsyncComponent`.
+   * Default: `false`.
+   */
+  lazy_exports?: boolean;
+
+  /**
+   * Largest icon file `add` writes: bytes, or a size like `"16KB"`.
+   */
+  max_icon_size?: number | string;
+
+  /**
+   * What `add` does with an icon over `max_icon_size`.
+   * Default: `"error"`.
+   */
+  on_oversize?: "warn" | "error";
+
+  /**
+   * Case for inferred icon filenames and the default for `iconmate normalize`.
+   */
+  filename_case?: "kebab" | "snake" | "camel" | "pascal";
+
+  /**
+   * Record add, delete and rename in `.iconmate/log.jsonl`.
+   * @default true
+   */
+  history_log?: boolean;
 }
 
 /**
@@ -50,7 +260,32 @@ export interface IconmateLocalConfig {
 export interface IconmateGlobalConfig {
   /**
    * Command used to open SVG files from the TUI.
-   * Use `%filename%` as the SVG file path placeholder.
+   * Use `%filename%` as the SVG file path placeholder (`%name%`, `%ext%`,
+   * `%folder%`, `%date%` and filters like `%name|kebab%` also work).
    */
   svg_view_cmd?: string;
+
+  /**
+   * TUI color theme. `auto` picks dark or light from the terminal background.
+   * Set `NO_COLOR` to disable colors entirely.
+   * Default: `"auto"`.
+   */
+  theme?: IconmateTheme;
+
+  /**
+   * Per-color overrides applied on top of `theme`.
+   */
+  palette?: IconmatePalette;
+
+  /**
+   * Ask before deleting the selected icon in the TUI. Bulk deletes always ask.
+   * Default: `true`.
+   */
+  confirm_delete?: boolean;
+
+  /**
+   * Open an icon with the SVG viewer right after adding it from the TUI.
+   * Default: `false`.
+   */
+  auto_open_after_add?: boolean;
 }
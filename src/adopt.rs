@@ -0,0 +1,420 @@
+// `iconmate adopt`: onboard an icons folder that has no index.ts yet.
+//
+// Legacy folders are a pile of `HeartIcon.svg`, `ic_close.svg` and
+// hand-written `.tsx` components imported one by one. `adopt` inventories the
+// folder, infers an export name from each filename (dropping `icon`/`ic`
+// affixes, since `alias_prefix`/`alias_suffix` add their own), and writes an
+// index.ts with one export per icon. index.ts is iconmate's manifest; when
+// the project has no `iconmate.config.jsonc` yet, one is written too, with the
+// preset inferred from the files. Anything it can't index (subfolders,
+// rasters, components without a default export, names that aren't
+// identifiers, duplicates) is reported instead of guessed at. Dry-run by
+// default.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::barrel::BarrelFrame;
+use crate::code_style::CodeStyle;
+use crate::lazy_export::LazyLoader;
+use crate::svg_format::LineEnding;
+use crate::utils::{AliasStyle, ImportQuery, parse_export_line_ts, render_js_export_line};
+
+/// Extensions `adopt` indexes; the same set `sync` keeps in step afterwards.
+const ICON_EXTENSIONS: &[&str] = &["svg", "tsx", "svelte", "vue"];
+const RASTER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "avif", "ico", "bmp"];
+/// Filename words that say "icon" and nothing about which one.
+const ICON_AFFIXES: &[&str] = &["icon", "icons", "ic"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adoption {
+    /// `./heart.svg`
+    pub file_path: String,
+    pub identifier: String,
+    pub rendered_line: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unresolved {
+    /// Folder-relative name; subfolders end with `/`.
+    pub file: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdoptPlan {
+    pub index_location: PathBuf,
+    /// The preset the files point at, for the starter config.
+    pub inferred_preset: &'static str,
+    pub adoptions: Vec<Adoption>,
+    pub unresolved: Vec<Unresolved>,
+}
+
+pub struct AdoptContext<'a> {
+    pub folder: &'a Path,
+    pub alias_style: &'a AliasStyle,
+    pub code_style: &'a CodeStyle,
+    pub import_query: Option<ImportQuery>,
+    pub lazy: Option<LazyLoader>,
+}
+
+pub struct AdoptOptions {
+    pub preset: String,
+    pub barrel_frame: BarrelFrame,
+    pub line_ending: LineEnding,
+    pub icon_names_dts: bool,
+    pub icon_registry: bool,
+}
+
+/// Inventories `ctx.folder` and plans its index.ts. Fails when the folder
+/// already has one: that's `sync`'s job.
+pub fn compute_adopt_plan(ctx: &AdoptContext) -> anyhow::Result<AdoptPlan> {
+    let index_location = ctx.folder.join("index.ts");
+    if index_location.exists() {
+        anyhow::bail!(
+            "{} already exists. Use `iconmate sync` to index new files.",
+            index_location.display()
+        );
+    }
+    if !ctx.folder.is_dir() {
+        anyhow::bail!("{} is not a folder.", ctx.folder.display());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(ctx.folder)
+        .with_context(|| format!("Failed to read icons folder {}", ctx.folder.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        entries.push((name, entry.file_type()?.is_dir()));
+    }
+    entries.sort();
+
+    let mut unresolved = Vec::new();
+    let mut candidates = Vec::new();
+    let bar = crate::progress::Bar::counted("Scanning icons", entries.len());
+    for (name, is_dir) in entries {
+        bar.inc();
+        if is_dir {
+            unresolved.push(Unresolved {
+                file: format!("{name}/"),
+                reason: "subfolder; iconmate folders are flat, adopt it separately with --folder"
+                    .to_string(),
+            });
+            continue;
+        }
+        match unindexable_reason(&ctx.folder.join(&name), &name) {
+            Some(reason) => unresolved.push(Unresolved { file: name, reason }),
+            None => candidates.push(name),
+        }
+    }
+    bar.finish();
+
+    // `heart.svg` next to `heart.tsx` is almost always the component's
+    // source; index the component.
+    let component_stems: HashMap<String, String> = candidates
+        .iter()
+        .filter(|name| !name.to_ascii_lowercase().ends_with(".svg"))
+        .map(|name| (split_name(name).0.to_lowercase(), name.clone()))
+        .collect();
+
+    let mut adoptions = Vec::new();
+    let mut taken: HashMap<String, String> = HashMap::new();
+    for name in &candidates {
+        let (stem, ext) = split_name(name);
+        if ext.eq_ignore_ascii_case(".svg")
+            && let Some(component) = component_stems.get(&stem.to_lowercase())
+        {
+            unresolved.push(Unresolved {
+                file: name.clone(),
+                reason: format!("{component} has the same name; indexing the component"),
+            });
+            continue;
+        }
+        let Some(alias) = infer_alias(stem) else {
+            unresolved.push(Unresolved {
+                file: name.clone(),
+                reason: "no export name in the filename".to_string(),
+            });
+            continue;
+        };
+        let rendered = render_js_export_line(
+            None,
+            ctx.folder,
+            &alias,
+            ctx.alias_style,
+            ctx.code_style,
+            stem,
+            &ImportQuery::import_ext(ctx.import_query, ext),
+        );
+        let Some(entry) = parse_export_line_ts(rendered.trim_end_matches(';'))
+            .or_else(|| parse_export_line_ts(&rendered))
+        else {
+            unresolved.push(Unresolved {
+                file: name.clone(),
+                reason: "could not render an export line for it".to_string(),
+            });
+            continue;
+        };
+        if !is_identifier(&entry.name) {
+            unresolved.push(Unresolved {
+                file: name.clone(),
+                reason: format!(
+                    "{} is not a valid export name; rename the file or set alias_prefix",
+                    entry.name
+                ),
+            });
+            continue;
+        }
+        if let Some(first) = taken.get(&entry.name) {
+            unresolved.push(Unresolved {
+                file: name.clone(),
+                reason: format!("{} is already exported from {first}", entry.name),
+            });
+            continue;
+        }
+        taken.insert(entry.name.clone(), name.clone());
+        let rendered_line = match ctx.lazy {
+            Some(loader) => loader.wrap(&rendered),
+            None => rendered,
+        };
+        adoptions.push(Adoption {
+            file_path: format!("./{name}"),
+            identifier: entry.name,
+            rendered_line,
+        });
+    }
+
+    Ok(AdoptPlan {
+        index_location,
+        inferred_preset: infer_preset(&adoptions),
+        adoptions,
+        unresolved,
+    })
+}
+
+/// Why `name` can't be indexed, or `None` when it can.
+fn unindexable_reason(path: &Path, name: &str) -> Option<String> {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    if RASTER_EXTENSIONS.contains(&extension.as_str()) {
+        return Some("raster image; only SVGs and components are indexed".to_string());
+    }
+    if !ICON_EXTENSIONS.contains(&extension.as_str()) {
+        return Some("not an SVG or component file".to_string());
+    }
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return Some("could not be read as text".to_string()),
+    };
+    match extension.as_str() {
+        "svg" if !contents.contains("<svg") => Some("no <svg> element".to_string()),
+        "tsx" if !contents.contains("export default") => {
+            Some("no default export to re-export".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// `HeartIcon` / `ic_heart` / `icon-heart` -> `Heart`. `None` when nothing
+/// usable is left.
+pub fn infer_alias(stem: &str) -> Option<String> {
+    let kebab = crate::template::apply_filter("kebab", stem)?;
+    let mut words: Vec<&str> = kebab.split('-').filter(|word| !word.is_empty()).collect();
+    if words.len() > 1 && ICON_AFFIXES.contains(&words[0]) {
+        words.remove(0);
+    }
+    if words.len() > 1 && ICON_AFFIXES.contains(&words[words.len() - 1]) {
+        words.pop();
+    }
+    let alias = crate::template::apply_filter("pascal", &words.join("-"))?;
+    (!alias.is_empty()).then_some(alias)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$')
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(idx) => (&name[..idx], &name[idx..]),
+        None => (name, ""),
+    }
+}
+
+/// The preset matching what was indexed: the most common component kind, or
+/// `normal` for plain SVGs. `.tsx` is taken as React.
+fn infer_preset(adoptions: &[Adoption]) -> &'static str {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for adoption in adoptions {
+        let preset = match split_name(&adoption.file_path)
+            .1
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            ".tsx" => "react",
+            ".vue" => "vue",
+            ".svelte" => "svelte",
+            _ => continue,
+        };
+        *counts.entry(preset).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(preset, _)| preset)
+        .unwrap_or("normal")
+}
+
+/// Writes index.ts and the files derived from it.
+pub fn apply_adopt_plan(
+    plan: &AdoptPlan,
+    ctx: &AdoptContext,
+    options: AdoptOptions,
+) -> anyhow::Result<()> {
+    let mut exports = String::new();
+    for adoption in &plan.adoptions {
+        exports.push_str(adoption.rendered_line.trim_end());
+        exports.push('\n');
+    }
+    let mut contents = options.barrel_frame.append("", &exports);
+    if let Some(loader) = ctx.lazy {
+        contents = loader.ensure_import(&contents, &exports);
+    }
+    fs::write(
+        &plan.index_location,
+        options.line_ending.normalize(&contents),
+    )
+    .with_context(|| format!("Failed to write {}", plan.index_location.display()))?;
+
+    if options.preset == "css" {
+        crate::css_mask::write_mask_stylesheet(ctx.folder)?;
+    }
+    if options.preset == "datauri" {
+        crate::datauri::write_datauri_bundle(ctx.folder)?;
+    }
+    crate::css_background::refresh_background_stylesheet_if_present(ctx.folder)?;
+    crate::icon_names::refresh_icon_names(ctx.folder, options.icon_names_dts)?;
+    crate::icon_registry::refresh_icon_registry(ctx.folder, options.icon_registry)?;
+    Ok(())
+}
+
+pub fn render_plan_text(plan: &AdoptPlan, use_color: bool) -> String {
+    let green = if use_color { "\x1b[32m" } else { "" };
+    let yellow = if use_color { "\x1b[33m" } else { "" };
+    let bold = if use_color { "\x1b[1m" } else { "" };
+    let reset = if use_color { "\x1b[0m" } else { "" };
+
+    let mut out = format!("Index: {}\n\n", plan.index_location.display());
+    if plan.adoptions.is_empty() {
+        out.push_str(&format!("{yellow}{bold}No icons to index.{reset}\n\n"));
+    } else {
+        out.push_str(&format!(
+            "{green}{bold}Would export ({}):{reset}\n",
+            plan.adoptions.len()
+        ));
+        for adoption in &plan.adoptions {
+            out.push_str(&format!(
+                "  {green}+ {}{reset} ← {}\n",
+                adoption.identifier, adoption.file_path
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !plan.unresolved.is_empty() {
+        out.push_str(&format!(
+            "{yellow}{bold}Not indexed ({}):{reset}\n",
+            plan.unresolved.len()
+        ));
+        for unresolved in &plan.unresolved {
+            out.push_str(&format!(
+                "  {yellow}! {}{reset} ({})\n",
+                unresolved.file, unresolved.reason
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !plan.adoptions.is_empty() {
+        out.push_str("Run with --apply to write index.ts.\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_aliases_without_icon_affixes() {
+        assert_eq!(infer_alias("HeartIcon").as_deref(), Some("Heart"));
+        assert_eq!(infer_alias("ic_arrow_left").as_deref(), Some("ArrowLeft"));
+        assert_eq!(infer_alias("icon-mdi:heart").as_deref(), Some("MdiHeart"));
+        assert_eq!(infer_alias("icon").as_deref(), Some("Icon"));
+        assert_eq!(infer_alias("--"), None);
+    }
+
+    #[test]
+    fn plans_exports_and_reports_what_it_cannot_index() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let folder = dir.path();
+        let svg = r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"/></svg>"#;
+        fs::write(folder.join("HeartIcon.svg"), svg).unwrap();
+        fs::write(folder.join("heart.svg"), svg).unwrap();
+        fs::write(folder.join("ic_close.svg"), svg).unwrap();
+        fs::write(folder.join("24-hours.svg"), svg).unwrap();
+        fs::write(folder.join("broken.svg"), "not an svg").unwrap();
+        fs::write(folder.join("logo.png"), [0u8; 4]).unwrap();
+        fs::create_dir(folder.join("social")).unwrap();
+
+        let ctx = AdoptContext {
+            folder,
+            alias_style: &AliasStyle {
+                prefix: String::new(),
+                ..AliasStyle::default()
+            },
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
+        };
+        let plan = compute_adopt_plan(&ctx).unwrap();
+        let exported: Vec<(&str, &str)> = plan
+            .adoptions
+            .iter()
+            .map(|a| (a.identifier.as_str(), a.file_path.as_str()))
+            .collect();
+        assert_eq!(
+            exported,
+            vec![("Heart", "./HeartIcon.svg"), ("Close", "./ic_close.svg")]
+        );
+        let unresolved: Vec<&str> = plan.unresolved.iter().map(|u| u.file.as_str()).collect();
+        assert_eq!(
+            unresolved,
+            vec![
+                "broken.svg",
+                "logo.png",
+                "social/",
+                "24-hours.svg",
+                "heart.svg"
+            ]
+        );
+        assert_eq!(plan.inferred_preset, "normal");
+
+        fs::write(folder.join("index.ts"), "").unwrap();
+        assert!(compute_adopt_plan(&ctx).is_err());
+    }
+}
@@ -8,9 +8,14 @@ pub enum AppFocus {
     AddPopup,
     DeletePopup,
     RenamePopup,
+    ReplacePopup,
+    DuplicatePopup,
+    SvgEditPopup,
     HelpPopup,
     IconifySearchPopup,
     SyncPopup,
+    TagPopup,
+    OnboardingPopup,
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +23,16 @@ pub struct IconifyCollectionListItem {
     pub prefix: String,
     pub name: String,
     pub total: Option<u32>,
+    pub details: IconifyCollectionDetails,
+}
+
+/// What the Collections tab's detail pane shows besides the name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IconifyCollectionDetails {
+    pub author: Option<String>,
+    pub license: Option<String>,
+    pub palette: Option<bool>,
+    pub samples: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,11 +40,19 @@ pub struct IconifySearchPayload {
     pub icons: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct IconifyCollectionsPayload {
+    pub collections: Vec<IconifyCollectionListItem>,
+    /// Why the API couldn't be reached, when `collections` is the cached
+    /// copy from an earlier run.
+    pub stale_reason: Option<String>,
+}
+
 #[derive(Debug)]
 pub enum AppEvent {
     IconifyCollectionsLoaded {
         request_id: u64,
-        result: Result<Vec<IconifyCollectionListItem>, String>,
+        result: Result<IconifyCollectionsPayload, String>,
     },
     IconifySearchLoaded {
         request_id: u64,
@@ -41,6 +64,85 @@ pub enum AppEvent {
         prefix: String,
         result: Result<Vec<String>, String>,
     },
+    AddPreviewFetched {
+        request_id: u64,
+        result: Result<String, String>,
+    },
+    /// The Iconify server has no endpoint for `feature`, so the popup turns
+    /// that part off instead of reporting a failed request.
+    IconifyFeatureUnsupported {
+        request_id: u64,
+        feature: crate::iconify::IconifyFeature,
+        message: String,
+    },
+    /// SVGs of the highlighted collection's sample icons, by name.
+    CollectionSamplesFetched {
+        request_id: u64,
+        samples: Vec<(String, Result<String, String>)>,
+    },
+    /// A background re-rank of `source` (the open collection) for `query`.
+    IconFilterRanked {
+        request_id: u64,
+        source: std::sync::Arc<[String]>,
+        query: String,
+        view: crate::views::iconify_search_popup::FilteredView,
+    },
+}
+
+/// The task behind the latest request of one kind (a search, a collection
+/// download, a preview). Starting the next request, cancelling, or dropping
+/// the owning popup aborts it, so superseded network work stops instead of
+/// finishing into an event that would be thrown away.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    request_id: u64,
+    handle: Option<tokio::task::AbortHandle>,
+}
+
+impl InFlight {
+    pub fn start(&mut self, request_id: u64, handle: tokio::task::AbortHandle) {
+        self.cancel();
+        self.request_id = request_id;
+        self.handle = Some(handle);
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+
+    /// Forgets the task once its result for `request_id` has arrived.
+    pub fn finish(&mut self, request_id: u64) {
+        if self.request_id == request_id {
+            self.handle = None;
+        }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// TUI shortcuts for power users, set by `confirm_delete` and
+/// `auto_open_after_add` in the local or global config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuiBehavior {
+    /// Ask before deleting the selected icon. Bulk deletes always ask.
+    pub confirm_delete: bool,
+    /// Open a freshly added icon with the SVG viewer.
+    pub auto_open_after_add: bool,
+}
+
+impl Default for TuiBehavior {
+    fn default() -> Self {
+        Self {
+            confirm_delete: true,
+            auto_open_after_add: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -49,10 +151,61 @@ pub struct AppConfig {
     pub preset: String,
     pub svg_viewer_cmd: Option<String>,
     pub svg_viewer_cmd_source: String,
+    /// Per-extension viewer commands, tried before `svg_viewer_cmd`.
+    pub viewer_cmds: Vec<(String, String)>,
     pub global_config_loaded: bool,
     pub project_config_loaded: bool,
     pub flutter_barrel_file: Option<String>,
     pub flutter_barrel_class: Option<String>,
+    pub alias_style: crate::utils::AliasStyle,
+    pub code_style: crate::code_style::CodeStyle,
+    pub import_query: Option<crate::utils::ImportQuery>,
+    /// `lazy_exports`: component presets get `lazy(() => import(...))` exports.
+    pub lazy_exports: bool,
+    /// `filename_case` for filenames inferred in the add preview.
+    pub filename_case: Option<crate::utils::FilenameCase>,
+    pub hooks: crate::hooks::Hooks,
+    pub behavior: TuiBehavior,
+    /// `line_endings`, barrel header/footer and derived-file settings for
+    /// changes accepted in the sync review.
+    pub sync_options: crate::sync::ApplyOptions,
+    /// Deletes and renames are appended here, unless `history_log` is off.
+    /// Source search in the main view reads it back.
+    pub history: Option<crate::history::HistoryLog>,
+    /// `usage_snippets` template for this preset; the barrel import when
+    /// unset. Shown under the icon list and copied with `y`.
+    pub usage_snippet: Option<String>,
+    /// `preview_colors` for the `p` browser preview and the add preview.
+    pub preview_colors: crate::viewer::PreviewColors,
+}
+
+#[cfg(test)]
+impl AppConfig {
+    /// `folder` and `preset` with every other setting at its default.
+    pub fn for_tests(folder: impl Into<String>, preset: &str) -> Self {
+        Self {
+            folder: folder.into(),
+            preset: preset.to_string(),
+            svg_viewer_cmd: None,
+            svg_viewer_cmd_source: "test".to_string(),
+            viewer_cmds: Vec::new(),
+            global_config_loaded: false,
+            project_config_loaded: false,
+            flutter_barrel_file: None,
+            flutter_barrel_class: None,
+            alias_style: Default::default(),
+            code_style: Default::default(),
+            import_query: None,
+            lazy_exports: false,
+            filename_case: None,
+            hooks: Default::default(),
+            behavior: Default::default(),
+            sync_options: Default::default(),
+            history: None,
+            usage_snippet: None,
+            preview_colors: Default::default(),
+        }
+    }
 }
 
 pub struct App {
@@ -63,6 +216,11 @@ pub struct App {
     pub rx: Receiver<AppEvent>,
 
     pub should_quit: bool,
+    /// The F12 request log overlay.
+    pub show_request_log: bool,
+    /// The last frame was below `tui::MIN_WIDTH` x `tui::MIN_HEIGHT` and
+    /// showed the "terminal too small" screen; keys wait until it's bigger.
+    pub terminal_too_small: bool,
 
     pub selected_index: usize,
 
@@ -70,6 +228,9 @@ pub struct App {
 
     pub filtered_items: Vec<IconEntry>,
 
+    /// `icon-tags.json` of the icons folder.
+    pub tags: crate::tags::IconTags,
+
     pub app_focus: AppFocus,
 
     // Deeper states
@@ -77,9 +238,14 @@ pub struct App {
     pub add_popup_state: Option<crate::views::add_popup::AddPopupState>,
     pub delete_popup_state: Option<crate::views::delete_popup::DeletePopupState>,
     pub rename_popup_state: Option<crate::views::rename_popup::RenamePopupState>,
+    pub replace_popup_state: Option<crate::views::replace_popup::ReplacePopupState>,
+    pub duplicate_popup_state: Option<crate::views::duplicate_popup::DuplicatePopupState>,
+    pub svg_edit_popup_state: Option<crate::views::svg_edit_popup::SvgEditPopupState>,
     pub iconify_search_popup_state:
         Option<crate::views::iconify_search_popup::IconifySearchPopupState>,
     pub sync_popup_state: Option<crate::views::sync_popup::SyncPopupState>,
+    pub tag_popup_state: Option<crate::views::tag_popup::TagPopupState>,
+    pub onboarding_popup_state: Option<crate::views::onboarding_popup::OnboardingPopupState>,
 
     pub next_async_request_id: u64,
 }
@@ -91,19 +257,27 @@ impl App {
             config,
 
             should_quit: false,
+            show_request_log: false,
+            terminal_too_small: false,
             tx,
             rx,
 
             selected_index: 0,
             filtered_items: Vec::new(),
             items: Vec::new(),
+            tags: Default::default(),
 
             app_focus: AppFocus::Main,
             add_popup_state: None,
             delete_popup_state: None,
             rename_popup_state: None,
+            replace_popup_state: None,
+            duplicate_popup_state: None,
+            svg_edit_popup_state: None,
             iconify_search_popup_state: None,
             sync_popup_state: None,
+            tag_popup_state: None,
+            onboarding_popup_state: None,
             next_async_request_id: 0,
             main_state: MainState::new(),
         };
@@ -121,6 +295,14 @@ impl App {
         )
         .unwrap_or_default();
         self.filtered_items = self.items.clone();
+        self.load_icon_sources();
+        self.tags = match crate::tags::IconTags::read(std::path::Path::new(&self.config.folder)) {
+            Ok(tags) => tags,
+            Err(error) => {
+                self.main_state.set_status(format!("{error:#}"), true);
+                Default::default()
+            }
+        };
     }
 
     pub fn update(&mut self) {
@@ -129,17 +311,40 @@ impl App {
         }
 
         self.tick_iconify_search_popup();
+        self.tick_add_popup_preview();
     }
 
     pub fn handlekeys(&mut self, key: Input) {
+        if key.key == tui_textarea::Key::F(12) {
+            self.show_request_log = !self.show_request_log;
+            return;
+        }
         match self.app_focus {
             AppFocus::Main => self.handlekeys_main(key),
             AppFocus::AddPopup => self.handlekeys_add_popup(key),
             AppFocus::DeletePopup => self.handlekeys_delete_popup(key),
             AppFocus::RenamePopup => self.handlekeys_rename_popup(key),
+            AppFocus::ReplacePopup => self.handlekeys_replace_popup(key),
+            AppFocus::DuplicatePopup => self.handlekeys_duplicate_popup(key),
+            AppFocus::SvgEditPopup => self.handlekeys_svg_edit_popup(key),
             AppFocus::HelpPopup => self.handlekeys_help_popup(key),
             AppFocus::IconifySearchPopup => self.handlekeys_iconify_search_popup(key),
             AppFocus::SyncPopup => self.handlekeys_sync_popup(key),
+            AppFocus::TagPopup => self.handlekeys_tag_popup(key),
+            AppFocus::OnboardingPopup => self.handlekeys_onboarding_popup(key),
+        }
+    }
+
+    /// Areas captured by the last frame no longer match the screen: drop them
+    /// so mouse hit-testing and scrolling wait for the next frame, which
+    /// re-clamps every list's scroll offset to its new height.
+    pub fn handle_resize(&mut self) {
+        self.main_state.list_rows_area = None;
+        let count = self.main_item_count();
+        self.selected_index = self.selected_index.min(count.saturating_sub(1));
+        if let Some(state) = self.iconify_search_popup_state.as_mut() {
+            state.collections_list_area = None;
+            state.icons_list_area = None;
         }
     }
 
@@ -150,4 +355,44 @@ impl App {
             _ => {}
         }
     }
+
+    /// A bracketed paste: the whole text at once, into whatever input has
+    /// focus. Views without a text input ignore it.
+    pub fn handle_paste(&mut self, text: &str) {
+        match self.app_focus {
+            AppFocus::Main => self.paste_into_main_search(text),
+            AppFocus::AddPopup => {
+                if let Some(state) = self.add_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            AppFocus::RenamePopup => {
+                if let Some(state) = self.rename_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            AppFocus::ReplacePopup => self.paste_into_replace_popup(text),
+            AppFocus::DuplicatePopup => {
+                if let Some(state) = self.duplicate_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            AppFocus::SvgEditPopup => {
+                if let Some(state) = self.svg_edit_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            AppFocus::TagPopup => {
+                if let Some(state) = self.tag_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            AppFocus::IconifySearchPopup => {
+                if let Some(state) = self.iconify_search_popup_state.as_mut() {
+                    state.paste_text(text);
+                }
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,195 @@
+// App icon generation (`iconmate appicon`).
+//
+// Turns one SVG into the usual favicon/app-icon set: `favicon.svg`,
+// `favicon.ico` (16/32/48 PNGs in an ICO container), `apple-touch-icon.png`,
+// `icon-192.png`, `icon-512.png`, and a padded `icon-maskable-512.png`.
+// Rasterization goes through `crate::raster`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+const ICO_SIZES: &[u32] = &[16, 32, 48];
+const APPLE_TOUCH_SIZE: u32 = 180;
+const MANIFEST_SIZES: &[u32] = &[192, 512];
+const MASKABLE_SIZE: u32 = 512;
+/// Maskable icons must keep their content inside the central 80% safe zone.
+const MASKABLE_PADDING_PERCENT: u32 = 10;
+
+/// Packs already-encoded PNGs into an ICO container. Entries are
+/// `(size, png_bytes)`; sizes of 256 and up are written as 0 per the format.
+pub fn build_ico(images: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    out.extend_from_slice(&1u16.to_le_bytes()); // type: icon
+    out.extend_from_slice(&(images.len() as u16).to_le_bytes());
+
+    let mut offset = 6 + 16 * images.len() as u32;
+    for (size, png) in images {
+        let dim = if *size >= 256 { 0 } else { *size as u8 };
+        out.push(dim); // width
+        out.push(dim); // height
+        out.push(0); // palette colors
+        out.push(0); // reserved
+        out.extend_from_slice(&1u16.to_le_bytes()); // color planes
+        out.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+        out.extend_from_slice(&(png.len() as u32).to_le_bytes());
+        out.extend_from_slice(&offset.to_le_bytes());
+        offset += png.len() as u32;
+    }
+    for (_, png) in images {
+        out.extend_from_slice(png);
+    }
+    out
+}
+
+/// Wraps `svg` on a square `background` with safe-zone padding for maskable
+/// icons. The original root `<svg>` becomes a nested, positioned viewport.
+pub fn maskable_svg(svg: &str, background: &str) -> String {
+    let inner = MASKABLE_PADDING_PERCENT;
+    let extent = 100 - 2 * MASKABLE_PADDING_PERCENT;
+    let positioned = match svg.find("<svg") {
+        Some(start) => {
+            let tag_end = svg[start..]
+                .find('>')
+                .map(|i| start + i)
+                .unwrap_or(svg.len());
+            let open_tag = &svg[start..tag_end];
+            let size_attr =
+                regex::Regex::new(r#"\s(width|height|x|y)\s*=\s*("[^"]*"|'[^']*')"#).unwrap();
+            let cleaned = size_attr.replace_all(open_tag, "");
+            format!(
+                "{}{cleaned} x=\"{inner}\" y=\"{inner}\" width=\"{extent}\" height=\"{extent}\"{}",
+                &svg[..start],
+                &svg[tag_end..]
+            )
+        }
+        None => svg.to_string(),
+    };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 100 100\" width=\"100\" height=\"100\"><rect width=\"100\" height=\"100\" fill=\"{background}\"/>{positioned}</svg>"
+    )
+}
+
+/// `icons` entries for a web app manifest pointing at the generated files.
+pub fn manifest_snippet() -> String {
+    let mut icons: Vec<serde_json::Value> = MANIFEST_SIZES
+        .iter()
+        .map(|size| {
+            serde_json::json!({
+                "src": format!("/icon-{size}.png"),
+                "sizes": format!("{size}x{size}"),
+                "type": "image/png",
+            })
+        })
+        .collect();
+    icons.push(serde_json::json!({
+        "src": format!("/icon-maskable-{MASKABLE_SIZE}.png"),
+        "sizes": format!("{MASKABLE_SIZE}x{MASKABLE_SIZE}"),
+        "type": "image/png",
+        "purpose": "maskable",
+    }));
+    serde_json::to_string_pretty(&serde_json::json!({ "icons": icons }))
+        .expect("manifest snippet should serialize")
+}
+
+/// `<head>` tags referencing the generated files.
+pub fn head_snippet() -> String {
+    [
+        r#"<link rel="icon" href="/favicon.ico" sizes="any">"#,
+        r#"<link rel="icon" href="/favicon.svg" type="image/svg+xml">"#,
+        r#"<link rel="apple-touch-icon" href="/apple-touch-icon.png">"#,
+        r#"<link rel="manifest" href="/manifest.webmanifest">"#,
+    ]
+    .join("\n")
+}
+
+/// Writes the full icon set for `svg` into `out_dir`. Returns written files.
+pub fn generate_app_icons(
+    svg: &str,
+    out_dir: &Path,
+    background: &str,
+) -> anyhow::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+    let mut written = Vec::new();
+
+    let favicon_svg = out_dir.join("favicon.svg");
+    std::fs::write(&favicon_svg, svg)?;
+    written.push(favicon_svg.clone());
+
+    let mut ico_images = Vec::new();
+    for &size in ICO_SIZES {
+        let tmp_png = out_dir.join(format!(".iconmate-favicon-{size}.png"));
+        let result = crate::raster::render_png(&favicon_svg, &tmp_png, size)
+            .and_then(|()| std::fs::read(&tmp_png).map_err(anyhow::Error::from));
+        let _ = std::fs::remove_file(&tmp_png);
+        ico_images.push((size, result?));
+    }
+    let ico_path = out_dir.join("favicon.ico");
+    std::fs::write(&ico_path, build_ico(&ico_images))?;
+    written.push(ico_path);
+
+    let apple_path = out_dir.join("apple-touch-icon.png");
+    crate::raster::render_png(&favicon_svg, &apple_path, APPLE_TOUCH_SIZE)?;
+    written.push(apple_path);
+
+    for &size in MANIFEST_SIZES {
+        let path = out_dir.join(format!("icon-{size}.png"));
+        crate::raster::render_png(&favicon_svg, &path, size)?;
+        written.push(path);
+    }
+
+    let maskable_src = out_dir.join(".iconmate-maskable.svg");
+    std::fs::write(&maskable_src, maskable_svg(svg, background))?;
+    let maskable_path = out_dir.join(format!("icon-maskable-{MASKABLE_SIZE}.png"));
+    let result = crate::raster::render_png(&maskable_src, &maskable_path, MASKABLE_SIZE);
+    let _ = std::fs::remove_file(&maskable_src);
+    result?;
+    written.push(maskable_path);
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ico_header_and_directory_point_at_png_payloads() {
+        let ico = build_ico(&[(16, vec![1, 2, 3]), (256, vec![4, 5])]);
+
+        assert_eq!(&ico[0..6], &[0, 0, 1, 0, 2, 0]);
+        // First entry: 16x16, 3 bytes at offset 6 + 32 = 38.
+        assert_eq!(ico[6], 16);
+        assert_eq!(u32::from_le_bytes(ico[14..18].try_into().unwrap()), 3);
+        assert_eq!(u32::from_le_bytes(ico[18..22].try_into().unwrap()), 38);
+        // Second entry: 256 is encoded as 0, payload follows the first.
+        assert_eq!(ico[22], 0);
+        assert_eq!(u32::from_le_bytes(ico[34..38].try_into().unwrap()), 41);
+        assert_eq!(&ico[38..], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn maskable_svg_pads_and_repositions_original_root() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24"><path d="M0 0"/></svg>"#;
+        let wrapped = maskable_svg(svg, "#112233");
+
+        assert!(
+            wrapped.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100""#)
+        );
+        assert!(wrapped.contains(r##"<rect width="100" height="100" fill="#112233"/>"##));
+        assert!(wrapped.contains(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" x="10" y="10" width="80" height="80">"#
+        ));
+        assert!(!wrapped.contains(r#"width="24""#));
+    }
+
+    #[test]
+    fn manifest_snippet_lists_maskable_icon() {
+        let snippet = manifest_snippet();
+        assert!(snippet.contains("\"src\": \"/icon-192.png\""));
+        assert!(snippet.contains("\"purpose\": \"maskable\""));
+    }
+}
@@ -0,0 +1,108 @@
+// Generated-file header and footer for the JS `index.ts` barrel.
+//
+// `index_header` / `index_footer` in the local config, plus
+// `index_eslint_disable` for a `/* eslint-disable */` pragma on the first
+// line, mark the barrel as generated so linters and reviewers leave it alone.
+// They're written when iconmate creates the file. Later adds insert exports
+// above the footer, and delete, rename and sync only touch export lines, so
+// both survive every update.
+
+pub const ESLINT_DISABLE: &str = "/* eslint-disable */";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BarrelFrame {
+    header: String,
+    footer: String,
+}
+
+/// One `// ` comment per line, leaving lines that already are comments alone.
+fn comment_lines(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty()
+                || trimmed.starts_with("//")
+                || trimmed.starts_with("/*")
+                || trimmed.starts_with('*')
+            {
+                format!("{line}\n")
+            } else {
+                format!("// {line}\n")
+            }
+        })
+        .collect()
+}
+
+impl BarrelFrame {
+    pub fn new(header: Option<&str>, footer: Option<&str>, eslint_disable: bool) -> Self {
+        let mut frame_header = String::new();
+        if eslint_disable {
+            frame_header.push_str(ESLINT_DISABLE);
+            frame_header.push('\n');
+        }
+        frame_header.push_str(&comment_lines(header.unwrap_or_default()));
+        Self {
+            header: frame_header,
+            footer: comment_lines(footer.unwrap_or_default()),
+        }
+    }
+
+    /// Contents of a new barrel holding `exports`.
+    pub fn create(&self, exports: &str) -> String {
+        format!("{}{exports}{}", self.header, self.footer)
+    }
+
+    /// `contents` with `exports` added after the last export, which is above
+    /// the footer when the file ends with it.
+    pub fn append(&self, contents: &str, exports: &str) -> String {
+        if contents.trim().is_empty() {
+            return self.create(exports);
+        }
+        let footer = self.footer.trim_end();
+        let body = contents.trim_end();
+        let (body, tail) = match body.strip_suffix(footer) {
+            Some(before) if !footer.is_empty() => (before, &contents[before.len()..]),
+            _ => (contents, ""),
+        };
+        let mut updated = body.to_string();
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(exports);
+        updated.push_str(tail);
+        updated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_with_header_and_appends_above_the_footer() {
+        let frame = BarrelFrame::new(
+            Some("AUTO-GENERATED by iconmate — do not edit"),
+            Some("// end of icons"),
+            true,
+        );
+        let created = frame.create("export { default as IconA } from './a.svg';\n");
+        assert_eq!(
+            created,
+            "/* eslint-disable */\n// AUTO-GENERATED by iconmate — do not edit\nexport { default as IconA } from './a.svg';\n// end of icons\n"
+        );
+
+        let updated = frame.append(&created, "export { default as IconB } from './b.svg';\n");
+        assert_eq!(
+            updated,
+            "/* eslint-disable */\n// AUTO-GENERATED by iconmate — do not edit\nexport { default as IconA } from './a.svg';\nexport { default as IconB } from './b.svg';\n// end of icons\n"
+        );
+
+        // Files without the footer (or without a frame) just grow at the end.
+        let plain = BarrelFrame::default();
+        assert_eq!(
+            plain.append("export { default as IconA } from './a.svg';", "x;\n"),
+            "export { default as IconA } from './a.svg';\nx;\n"
+        );
+        assert_eq!(frame.append("a;\n", "b;\n"), "a;\nb;\n");
+    }
+}
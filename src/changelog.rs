@@ -0,0 +1,342 @@
+// `iconmate changelog --since <git-ref>`: icon changes as release notes.
+//
+// The barrel is the manifest: its exports at `<ref>` (read with `git show`)
+// are compared with the ones on disk. An export that only exists now is
+// added, one that's gone is removed, a new export name on the same file is a
+// rename, and the same export on another file is a move. Exports on the same
+// file whose contents differ from `<ref>` are updated. Where an added icon
+// came from (`mdi:heart`, a URL) is taken from `.iconmate/log.jsonl` when the
+// history log has it. The result is a Markdown block for the icon package's
+// release notes.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::history::{self, Operation};
+use crate::utils::{IconEntry, icon_file_path};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Added {
+    pub name: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Renamed {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Moved {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Changelog {
+    pub added: Vec<Added>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<Renamed>,
+    pub moved: Vec<Moved>,
+    pub updated: Vec<String>,
+}
+
+impl Changelog {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.moved.is_empty()
+            && self.updated.is_empty()
+    }
+}
+
+/// The barrel and icon files as they were at a git ref.
+pub struct GitSnapshot<'a> {
+    /// Directory `git` runs in; paths passed to `show` are relative to it.
+    pub dir: &'a Path,
+    pub rev: &'a str,
+}
+
+impl GitSnapshot<'_> {
+    /// Fails unless `rev` names a commit.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let output = git(
+            self.dir,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{}^{{commit}}", self.rev),
+            ],
+        )?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("not a git repository") {
+                anyhow::bail!("{} is not inside a git repository.", self.dir.display());
+            }
+            anyhow::bail!("`{}` is not a commit in this repository.", self.rev);
+        }
+        Ok(())
+    }
+
+    /// `path` (relative to `dir`) at `rev`; `None` if it didn't exist.
+    pub fn show(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let spec = format!("{}:./{}", self.rev, path.trim_start_matches("./"));
+        let output = git(self.dir, &["show", &spec])?;
+        Ok(output.status.success().then_some(output.stdout))
+    }
+
+    /// Commit time of `rev` in UTC, in the history log's timestamp format.
+    pub fn commit_time(&self) -> anyhow::Result<String> {
+        let output = Command::new("git")
+            .args([
+                "log",
+                "-1",
+                "--format=%cd",
+                "--date=format-local:%Y-%m-%dT%H:%M:%SZ",
+                self.rev,
+            ])
+            .env("TZ", "UTC")
+            .current_dir(self.dir)
+            .stdin(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("Failed to read the commit time of `{}`.", self.rev);
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<std::process::Output> {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run git: {e}"))
+}
+
+/// Compares the exports at the snapshot (`before`) with the ones on disk
+/// (`after`). `snapshot` and `folder` resolve icon files for the updated
+/// check; `log` adds sources for added icons.
+pub fn compute_changelog(
+    before: &[IconEntry],
+    after: &[IconEntry],
+    folder: &Path,
+    snapshot: &GitSnapshot,
+    log: Option<(&[history::Entry], &str)>,
+) -> anyhow::Result<Changelog> {
+    let key = |entry: &IconEntry| entry.file_path.trim_start_matches("./").to_string();
+    let before_by_name: HashMap<&str, &IconEntry> = before
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry))
+        .collect();
+    let after_names: HashSet<&str> = after.iter().map(|entry| entry.name.as_str()).collect();
+
+    let mut changelog = Changelog::default();
+    let mut added = Vec::new();
+    for entry in after {
+        match before_by_name.get(entry.name.as_str()) {
+            Some(old) if key(old) != key(entry) => changelog.moved.push(Moved {
+                name: entry.name.clone(),
+                from: key(old),
+                to: key(entry),
+            }),
+            Some(_) => {
+                if contents_changed(folder, snapshot, &key(entry))? {
+                    changelog.updated.push(entry.name.clone());
+                }
+            }
+            None => added.push(entry),
+        }
+    }
+
+    // A removed export and an added one on the same file is a rename.
+    let mut removed: Vec<&IconEntry> = before
+        .iter()
+        .filter(|entry| !after_names.contains(entry.name.as_str()))
+        .collect();
+    for entry in added {
+        if let Some(pos) = removed.iter().position(|old| key(old) == key(entry)) {
+            let old = removed.remove(pos);
+            changelog.renamed.push(Renamed {
+                from: old.name.clone(),
+                to: entry.name.clone(),
+            });
+            continue;
+        }
+        changelog.added.push(Added {
+            name: entry.name.clone(),
+            source: log.and_then(|(entries, since)| added_source(entries, since, &entry.name)),
+        });
+    }
+    changelog.removed = removed
+        .into_iter()
+        .map(|entry| entry.name.clone())
+        .collect();
+    Ok(changelog)
+}
+
+fn contents_changed(folder: &Path, snapshot: &GitSnapshot, file: &str) -> anyhow::Result<bool> {
+    // `.tsx` exports are extensionless; diff the file they resolve to.
+    let current = icon_file_path(folder, file);
+    let Ok(now) = std::fs::read(&current) else {
+        return Ok(false);
+    };
+    let relative =
+        crate::utils::folder_relative_path(folder, &current).unwrap_or_else(|| file.to_string());
+    Ok(snapshot.show(&relative)?.is_some_and(|then| then != now))
+}
+
+/// The source of the latest logged add of `name` after `since`.
+fn added_source(entries: &[history::Entry], since: &str, name: &str) -> Option<String> {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.op == Operation::Add && entry.name == name && entry.ts.as_str() > since)
+        .and_then(|entry| entry.source.clone())
+}
+
+pub fn render_markdown(changelog: &Changelog, since: &str) -> String {
+    let mut out = String::from("## Icons\n\n");
+    if changelog.is_empty() {
+        out.push_str(&format!("No icon changes since `{since}`.\n"));
+        return out;
+    }
+    out.push_str(&format!("Changes since `{since}`.\n"));
+    let breaking = changelog.removed.len() + changelog.renamed.len();
+    if breaking > 0 {
+        out.push_str(&format!(
+            "\n**Breaking:** {breaking} export{} removed or renamed.\n",
+            if breaking == 1 { "" } else { "s" }
+        ));
+    }
+
+    let mut section = |title: &str, items: Vec<String>| {
+        if items.is_empty() {
+            return;
+        }
+        out.push_str(&format!("\n### {title}\n\n"));
+        for item in items {
+            out.push_str(&format!("- {item}\n"));
+        }
+    };
+    section(
+        "Added",
+        changelog
+            .added
+            .iter()
+            .map(|added| match &added.source {
+                Some(source) => format!("`{}` ({source})", added.name),
+                None => format!("`{}`", added.name),
+            })
+            .collect(),
+    );
+    section(
+        "Renamed",
+        changelog
+            .renamed
+            .iter()
+            .map(|renamed| format!("`{}` → `{}`", renamed.from, renamed.to))
+            .collect(),
+    );
+    section(
+        "Removed",
+        changelog
+            .removed
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect(),
+    );
+    section(
+        "Updated",
+        changelog
+            .updated
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect(),
+    );
+    section(
+        "Files moved",
+        changelog
+            .moved
+            .iter()
+            .map(|moved| format!("`{}`: `{}` → `{}`", moved.name, moved.from, moved.to))
+            .collect(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, file: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file.to_string(),
+        }
+    }
+
+    #[test]
+    fn classifies_export_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let snapshot = GitSnapshot {
+            dir: dir.path(),
+            rev: "HEAD",
+        };
+        let before = vec![
+            entry("IconFav", "./heart.svg"),
+            entry("IconClose", "./close.svg"),
+            entry("IconStar", "./star.svg"),
+        ];
+        let after = vec![
+            entry("IconHeart", "./heart.svg"),
+            entry("IconStar", "./star-filled.svg"),
+            entry("IconBell", "./bell.svg"),
+        ];
+        let log = vec![history::Entry {
+            ts: "2026-02-01T00:00:00Z".to_string(),
+            op: Operation::Add,
+            name: "IconBell".to_string(),
+            file: "icons/bell.svg".to_string(),
+            to: None,
+            source: Some("mdi:bell".to_string()),
+            user: "alice".to_string(),
+        }];
+
+        let changelog = compute_changelog(
+            &before,
+            &after,
+            dir.path(),
+            &snapshot,
+            Some((&log, "2026-01-01T00:00:00Z")),
+        )
+        .unwrap();
+        assert_eq!(
+            changelog.added,
+            vec![Added {
+                name: "IconBell".to_string(),
+                source: Some("mdi:bell".to_string()),
+            }]
+        );
+        assert_eq!(changelog.removed, vec!["IconClose".to_string()]);
+        assert_eq!(
+            changelog.renamed,
+            vec![Renamed {
+                from: "IconFav".to_string(),
+                to: "IconHeart".to_string(),
+            }]
+        );
+        assert_eq!(changelog.moved[0].to, "star-filled.svg");
+
+        let markdown = render_markdown(&changelog, "v1.0.0");
+        assert!(markdown.contains("**Breaking:** 2 exports removed or renamed"));
+        assert!(markdown.contains("### Added\n\n- `IconBell` (mdi:bell)\n"));
+        assert!(markdown.contains("- `IconFav` → `IconHeart`\n"));
+    }
+}
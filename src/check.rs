@@ -0,0 +1,382 @@
+// `iconmate check`: audits an icons folder for problems that don't break the
+// build but cost users at runtime.
+//
+// The tree-shaking audit reads `index.ts` statement by statement. Exports
+// iconmate writes (static or lazy) are fine; `export *`, default-exported
+// objects, bare `import './x'` and top-level calls either drag every icon
+// into the bundle or make bundlers keep the whole barrel. It also looks at
+// the nearest `package.json`: without `"sideEffects": false`, webpack can't
+// drop unused re-exports at all. With `max_icon_size` set, icon files over
+// the budget are listed too.
+//
+// `--workspace` looks across a monorepo instead: every package below the
+// working directory with an iconmate config contributes its folder, and an
+// export name that means different artwork in two packages is flagged, since
+// `IconClose` then looks different depending on which package rendered it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::config::WorkspaceFolder;
+use crate::size_budget::SizeBudget;
+use crate::utils::icon_file_path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// File the problem is in, as shown to the user.
+    pub file: String,
+    /// 1-based line, when the problem is a statement.
+    pub line: Option<usize>,
+    pub problem: String,
+    pub fix: String,
+}
+
+impl Finding {
+    fn at(file: &str, line: usize, problem: String, fix: &str) -> Self {
+        Finding {
+            file: file.to_string(),
+            line: Some(line),
+            problem,
+            fix: fix.to_string(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let location = match self.line {
+            Some(line) => format!("{}:{line}", self.file),
+            None => self.file.clone(),
+        };
+        format!("{location}: {}\n  fix: {}\n", self.problem, self.fix)
+    }
+}
+
+/// Tree-shaking problems in the statements of `index.ts`.
+pub fn audit_barrel(contents: &str, file: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for statement in crate::js_statements::statements(contents) {
+        let line = statement.line;
+        let compact = statement.code;
+        if crate::utils::parse_export_line_ts(&compact).is_some() {
+            continue;
+        }
+        let preview = if compact.chars().count() > 60 {
+            format!("{}...", compact.chars().take(57).collect::<String>())
+        } else {
+            compact.clone()
+        };
+
+        if compact.starts_with("export * as ") {
+            findings.push(Finding::at(
+                file,
+                line,
+                format!("`{preview}` bundles a whole module into one namespace object, so every icon in it ships"),
+                "re-export the icons by name: `export { default as IconHeart } from './heart.svg';`",
+            ));
+        } else if compact.starts_with("export *") {
+            findings.push(Finding::at(
+                file,
+                line,
+                format!("`{preview}` hides which icons the barrel exports and keeps the whole module unless the package is marked side-effect free"),
+                "re-export the icons by name, or run `iconmate sync --apply` to list the files here",
+            ));
+        } else if compact.starts_with("export default") {
+            findings.push(Finding::at(
+                file,
+                line,
+                format!("`{preview}` default-exports one value, and importing it pulls in every icon it references"),
+                "use named exports and import only the icons you render",
+            ));
+        } else if let Some(rest) = compact.strip_prefix("import ") {
+            if rest.starts_with('\'') || rest.starts_with('"') {
+                findings.push(Finding::at(
+                    file,
+                    line,
+                    format!("`{preview}` is a side-effect import, so bundlers must keep index.ts and everything it loads"),
+                    "move it to your app's entry point",
+                ));
+            }
+        } else if !is_declaration(&compact) {
+            findings.push(Finding::at(
+                file,
+                line,
+                format!("`{preview}` runs when index.ts is imported, so bundlers can't drop the barrel"),
+                "move the call out of index.ts, or mark it with /* @__PURE__ */ if it has no side effects",
+            ));
+        }
+    }
+    findings
+}
+
+fn is_declaration(statement: &str) -> bool {
+    [
+        "export ",
+        "type ",
+        "interface ",
+        "declare ",
+        "const ",
+        "let ",
+        "function ",
+        "class ",
+        "enum ",
+        "\"use ",
+        "'use ",
+    ]
+    .iter()
+    .any(|keyword| statement.starts_with(keyword))
+}
+
+/// Whether the nearest `package.json` above `folder` lets bundlers treat
+/// unused modules as droppable.
+pub fn audit_side_effects(folder: &Path) -> Option<Finding> {
+    let package_path = find_package_json(folder)?;
+    let package: Value =
+        serde_json::from_str(&std::fs::read_to_string(&package_path).ok()?).ok()?;
+    let problem = match package.get("sideEffects") {
+        None => {
+            "no \"sideEffects\" field, so webpack assumes every module (icons included) has side effects and keeps unused re-exports"
+        }
+        Some(Value::Bool(true)) => {
+            "\"sideEffects\": true keeps every re-exported icon, used or not"
+        }
+        Some(_) => return None,
+    };
+    Some(Finding {
+        file: package_path.display().to_string(),
+        line: None,
+        problem: problem.to_string(),
+        fix: "set \"sideEffects\": false, or list the files that do have side effects (e.g. [\"*.css\"])"
+            .to_string(),
+    })
+}
+
+/// Icon files in `folder` over `budget`, largest first.
+pub fn audit_sizes(folder: &Path, preset: &str, budget: &SizeBudget) -> Vec<Finding> {
+    let files = crate::sync::find_js_icon_files(folder, preset).unwrap_or_default();
+    let mut oversized = crate::parallel::map(&files, |name| {
+        let path = folder.join(name);
+        let bytes = std::fs::metadata(&path).ok()?.len();
+        budget.exceeded_by(bytes).then_some((bytes, path))
+    })
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    oversized.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    oversized
+        .into_iter()
+        .map(|(bytes, path)| {
+            Finding {
+                file: path.display().to_string(),
+                line: None,
+                problem: budget.overage(bytes),
+                fix: "optimize it (e.g. svgo), or move it out of the icons folder if it's an illustration".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Export names defined in more than one workspace folder with different
+/// artwork. Files are shown relative to `root`.
+pub fn audit_workspace_aliases(root: &Path, folders: &[WorkspaceFolder]) -> Vec<Finding> {
+    let icons = folders
+        .iter()
+        .filter(|folder| folder.preset != "flutter")
+        .flat_map(|workspace| {
+            crate::utils::get_existing_icons(&workspace.folder.to_string_lossy())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry| {
+                    (
+                        icon_file_path(&workspace.folder, &entry.file_path),
+                        entry.name,
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+    // Reading and normalizing every file is the slow part across packages.
+    let keys = crate::parallel::map(&icons, |(path, _)| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|contents| artwork_key(&contents))
+    });
+
+    // Export name -> (file, artwork key) in each folder that defines it.
+    let mut definitions: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for ((path, name), key) in icons.into_iter().zip(keys) {
+        let Some(key) = key else {
+            continue;
+        };
+        let shown = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .filter(|component| !matches!(component, std::path::Component::CurDir))
+            .collect::<PathBuf>()
+            .to_string_lossy()
+            .replace('\\', "/");
+        definitions.entry(name).or_default().push((shown, key));
+    }
+
+    definitions
+        .into_iter()
+        .filter(|(_, files)| files.iter().any(|(_, key)| *key != files[0].1))
+        .map(|(name, files)| Finding {
+            file: files[0].0.clone(),
+            line: None,
+            problem: format!(
+                "{name} has different artwork across packages: {}",
+                files
+                    .iter()
+                    .map(|(file, _)| file.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            fix: "copy one file over the others so every package renders the same icon, or give the variants distinct names".to_string(),
+        })
+        .collect()
+}
+
+/// Compares what an icon draws, whatever preset wrote it: the SVG inside a
+/// component or data URI module, by path data when it has any.
+fn artwork_key(contents: &str) -> String {
+    let svg = crate::datauri::parse_datauri_module(contents)
+        .and_then(|uri| crate::datauri::decode_svg_data_uri(&uri).ok())
+        .or_else(|| crate::viewer::svg_preview_contents(contents).ok())
+        .unwrap_or_else(|| contents.to_string());
+    crate::dedupe::path_data_key(&svg)
+        .unwrap_or_else(|| svg.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn find_package_json(folder: &Path) -> Option<PathBuf> {
+    let folder = std::path::absolute(folder).ok()?;
+    folder
+        .ancestors()
+        .map(|dir| dir.join("package.json"))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_patterns_that_defeat_tree_shaking() {
+        let barrel = "/* eslint-disable */\nimport { lazy } from 'react';\nimport './icons.css';\nexport { default as IconHeart } from './heart.svg';\nexport const IconStar = lazy(() =>\n  import('./star'));\nexport * from './social';\nexport * as Brand from './brand';\nregisterIcons({\n  heart: true,\n});\nexport default { IconHeart };\n";
+        let lines = audit_barrel(barrel, "index.ts")
+            .into_iter()
+            .map(|finding| finding.line.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![3, 7, 8, 9, 12]);
+    }
+
+    #[test]
+    fn side_effects_field_decides_package_finding() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let icons = dir.path().join("src/icons");
+        std::fs::create_dir_all(&icons).unwrap();
+        std::fs::write(dir.path().join("package.json"), r#"{ "name": "app" }"#).unwrap();
+        assert!(audit_side_effects(&icons).is_some());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "name": "app", "sideEffects": ["*.css"] }"#,
+        )
+        .unwrap();
+        assert!(audit_side_effects(&icons).is_none());
+    }
+
+    #[test]
+    fn lists_icons_over_the_size_budget_largest_first() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.ts"), "x".repeat(4096)).unwrap();
+        std::fs::write(dir.path().join("heart.svg"), "x".repeat(100)).unwrap();
+        std::fs::write(dir.path().join("hero.svg"), "x".repeat(2048)).unwrap();
+        std::fs::write(dir.path().join("map.tsx"), "x".repeat(3072)).unwrap();
+        let budget = SizeBudget {
+            max_bytes: 1024,
+            action: crate::size_budget::OversizeAction::Error,
+        };
+        let findings = audit_sizes(dir.path(), "react", &budget);
+        let files = findings
+            .iter()
+            .map(|finding| Path::new(&finding.file).file_name().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(files, vec!["map.tsx", "hero.svg"]);
+        assert_eq!(findings[1].problem, "2.0 KB, over the 1.0 KB max_icon_size");
+    }
+
+    #[test]
+    fn size_audit_ranks_findings_from_a_parallel_scan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for n in 0..200 {
+            std::fs::write(dir.path().join(format!("icon{n}.svg")), "x".repeat(900 + n)).unwrap();
+        }
+        let budget = SizeBudget {
+            max_bytes: 1024,
+            action: crate::size_budget::OversizeAction::Error,
+        };
+        let sizes = audit_sizes(dir.path(), "normal", &budget)
+            .iter()
+            .map(|finding| std::fs::metadata(&finding.file).unwrap().len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, (1025..1100).rev().collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn flags_aliases_with_different_artwork_across_packages() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let package = |name: &str, preset: &str, files: &[(&str, &str)], index: &str| {
+            let folder = dir.path().join(name).join("icons");
+            std::fs::create_dir_all(&folder).unwrap();
+            for (file, contents) in files {
+                std::fs::write(folder.join(file), contents).unwrap();
+            }
+            std::fs::write(folder.join("index.ts"), index).unwrap();
+            WorkspaceFolder {
+                config: dir.path().join(name).join("iconmate.config.json"),
+                folder,
+                preset: preset.to_string(),
+            }
+        };
+        let folders = vec![
+            package(
+                "web",
+                "normal",
+                &[
+                    (
+                        "close.svg",
+                        "<svg viewBox=\"0 0 24 24\"><path d=\"M1 1L2 2\"/></svg>",
+                    ),
+                    (
+                        "heart.svg",
+                        "<svg viewBox=\"0 0 24 24\"><path d=\"M3 3\"/></svg>",
+                    ),
+                ],
+                "export { default as IconClose } from './close.svg';\nexport { default as IconHeart } from './heart.svg';\n",
+            ),
+            package(
+                "admin",
+                "react",
+                &[
+                    (
+                        "close.tsx",
+                        "export default function Icon(props) {\n  return <svg viewBox=\"0 0 24 24\" {...props}><path d=\"M1,1 L2,2\" /></svg>;\n}",
+                    ),
+                    (
+                        "heart.tsx",
+                        "export default function Icon(props) {\n  return <svg viewBox=\"0 0 24 24\" {...props}><path d=\"M9 9\" /></svg>;\n}",
+                    ),
+                ],
+                "export { default as IconClose } from './close';\nexport { default as IconHeart } from './heart';\n",
+            ),
+        ];
+
+        let findings = audit_workspace_aliases(dir.path(), &folders);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].problem,
+            "IconHeart has different artwork across packages: web/icons/heart.svg, admin/icons/heart.tsx"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+// Fetched-SVG digests (`icon-checksums.json` next to the barrel).
+//
+// Each export remembers the source it was last fetched from (an Iconify name
+// or URL) and the SHA-256 of the SVG as fetched, before preset rewrites.
+// Fetching the same source for that name again — `iconmate replace`, or
+// re-adding a deleted icon — must produce the same bytes or nothing is
+// written; `--expect-sha256 <new hash>` accepts a deliberate upstream change
+// and records it. Inline SVG and data URIs carry their own content and aren't
+// recorded. The file is plain JSON and meant to be committed.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::utils::{_determine_icon_source_type, IconSourceType};
+
+pub const CHECKSUMS_FILE: &str = "icon-checksums.json";
+
+/// Lowercase hex SHA-256, the format `sha256sum` prints.
+pub fn hex_digest(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Fails unless `content` hashes to `expected` (hex, case-insensitive).
+pub fn verify(content: &[u8], expected: &str, label: &str) -> anyhow::Result<()> {
+    let expected = expected.trim().to_ascii_lowercase();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("--expect-sha256 must be 64 hex characters, got '{expected}'.");
+    }
+    let actual = hex_digest(content);
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum mismatch for {label}:\n  expected sha256 {expected}\n  got      sha256 {actual}\nThe icon changed upstream or the download was tampered with; nothing was written."
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checksum {
+    pub source: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IconChecksums {
+    by_name: BTreeMap<String, Checksum>,
+}
+
+impl IconChecksums {
+    /// The folder's digests; empty when it has no checksums file.
+    pub fn read(folder: &Path) -> anyhow::Result<Self> {
+        let path = folder.join(CHECKSUMS_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let by_name = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", path.display()))?;
+        Ok(Self { by_name })
+    }
+
+    pub fn write(&self, folder: &Path) -> anyhow::Result<()> {
+        let path = folder.join(CHECKSUMS_FILE);
+        let mut json = serde_json::to_string_pretty(&self.by_name)?;
+        json.push('\n');
+        std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Checksum> {
+        self.by_name.get(name)
+    }
+}
+
+/// `--expect-sha256` for one icon source, and what fetching it returned:
+/// the SVG, which every `--folder` target of an add reuses instead of
+/// downloading again, and its digest.
+#[derive(Debug, Clone, Default)]
+pub struct Pin {
+    expected: Option<String>,
+    fetched: Option<String>,
+    content: Option<String>,
+}
+
+impl Pin {
+    pub fn new(expect_sha256: Option<&str>) -> Self {
+        Self {
+            expected: expect_sha256.map(str::to_string),
+            ..Self::default()
+        }
+    }
+
+    /// Remembers `content` and its digest, and fails when `--expect-sha256`
+    /// doesn't match it.
+    pub fn check(&mut self, content: &str, label: &str) -> anyhow::Result<()> {
+        if let Some(expected) = &self.expected {
+            verify(content.as_bytes(), expected, label)?;
+        }
+        self.fetched = Some(hex_digest(content.as_bytes()));
+        self.content = Some(content.to_string());
+        Ok(())
+    }
+
+    /// The source as fetched by an earlier `check`.
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
+
+    /// Without `--expect-sha256`, fetching the source `name` was recorded
+    /// with must give the recorded digest.
+    pub fn check_recorded(
+        &self,
+        folder: &Path,
+        name: &str,
+        source: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if self.expected.is_some() {
+            return Ok(());
+        }
+        let (Some(source), Some(fetched)) = (source, &self.fetched) else {
+            return Ok(());
+        };
+        let checksums = IconChecksums::read(folder)?;
+        let Some(recorded) = checksums
+            .get(name)
+            .filter(|checksum| checksum.source == source)
+        else {
+            return Ok(());
+        };
+        if &recorded.sha256 != fetched {
+            anyhow::bail!(
+                "Checksum mismatch for {source}:\n  recorded sha256 {} in {CHECKSUMS_FILE}\n  got      sha256 {fetched}\nThe icon changed upstream or the download was tampered with; nothing was written. Pass --expect-sha256 {fetched} to accept it.",
+                recorded.sha256
+            );
+        }
+        Ok(())
+    }
+
+    /// Records what `name` was fetched from in `folder`'s checksums file.
+    /// Sources that weren't fetched (inline SVG, data URIs) are skipped.
+    pub fn record(&self, folder: &Path, name: &str, source: Option<&str>) -> anyhow::Result<()> {
+        let (Some(source), Some(sha256)) = (source, &self.fetched) else {
+            return Ok(());
+        };
+        if !matches!(
+            _determine_icon_source_type(Some(&source.to_string())),
+            IconSourceType::IconifyName | IconSourceType::Url
+        ) {
+            return Ok(());
+        }
+        let mut checksums = IconChecksums::read(folder)?;
+        checksums.by_name.insert(
+            name.to_string(),
+            Checksum {
+                source: source.to_string(),
+                sha256: sha256.clone(),
+            },
+        );
+        checksums.write(folder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn verify_accepts_uppercase_and_rejects_mismatch() {
+        assert_eq!(hex_digest(b"abc"), ABC);
+        let upper = ABC.to_ascii_uppercase();
+        assert!(verify(b"abc", &upper, "abc").is_ok());
+
+        let err = verify(b"abd", &upper, "mdi:heart").unwrap_err().to_string();
+        assert!(err.contains("Checksum mismatch for mdi:heart"));
+        assert!(verify(b"abc", "abc123", "abc").is_err());
+    }
+
+    #[test]
+    fn records_fetched_sources_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path();
+
+        let mut pin = Pin::new(None);
+        pin.check("abc", "mdi:heart").unwrap();
+        pin.record(folder, "IconHeart", Some("mdi:heart")).unwrap();
+        pin.record(folder, "IconInline", Some("<svg></svg>"))
+            .unwrap();
+
+        let checksums = IconChecksums::read(folder).unwrap();
+        assert_eq!(
+            checksums.get("IconHeart"),
+            Some(&Checksum {
+                source: "mdi:heart".to_string(),
+                sha256: ABC.to_string(),
+            })
+        );
+        assert_eq!(checksums.get("IconInline"), None);
+    }
+
+    #[test]
+    fn refetch_must_match_the_recorded_digest() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path();
+        let mut pin = Pin::new(None);
+        pin.check("abc", "mdi:heart").unwrap();
+        pin.record(folder, "IconHeart", Some("mdi:heart")).unwrap();
+
+        let mut refetch = Pin::new(None);
+        refetch.check("abd", "mdi:heart").unwrap();
+        let err = refetch
+            .check_recorded(folder, "IconHeart", Some("mdi:heart"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Checksum mismatch for mdi:heart"));
+        assert!(err.contains(CHECKSUMS_FILE));
+
+        // A different source, or an explicit new hash, isn't held to the record.
+        assert!(
+            refetch
+                .check_recorded(folder, "IconHeart", Some("lucide:heart"))
+                .is_ok()
+        );
+        let new_hash = hex_digest(b"abd");
+        let mut accepted = Pin::new(Some(&new_hash));
+        accepted.check("abd", "mdi:heart").unwrap();
+        assert!(
+            accepted
+                .check_recorded(folder, "IconHeart", Some("mdi:heart"))
+                .is_ok()
+        );
+        accepted
+            .record(folder, "IconHeart", Some("mdi:heart"))
+            .unwrap();
+        assert_eq!(
+            IconChecksums::read(folder)
+                .unwrap()
+                .get("IconHeart")
+                .unwrap()
+                .sha256,
+            new_hash
+        );
+    }
+}
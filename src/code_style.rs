@@ -0,0 +1,127 @@
+// Code style for generated TypeScript: the `code_style` local config key.
+//
+// `quotes` and `semicolons` apply to new `index.ts` export lines, where they
+// win over the style detected from the existing exports, and to the React,
+// Svelte, Solid and Vue component templates, along with `indent`. The SVG
+// markup inside a component keeps `svg_indent`; only the template around it
+// is restyled.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    Single,
+    Double,
+}
+
+impl Quote {
+    pub const NAMES: &'static [&'static str] = &["single", "double"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "single" => Some(Quote::Single),
+            "double" => Some(Quote::Double),
+            _ => None,
+        }
+    }
+
+    pub fn char(self) -> char {
+        match self {
+            Quote::Single => '\'',
+            Quote::Double => '"',
+        }
+    }
+}
+
+pub const DEFAULT_INDENT: usize = 2;
+pub const INDENT_OPTIONS: &[usize] = &[2, 4];
+
+/// Unset fields keep what the templates (or the existing barrel) use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodeStyle {
+    pub quotes: Option<Quote>,
+    pub semicolons: Option<bool>,
+    pub indent: Option<usize>,
+}
+
+impl CodeStyle {
+    /// `levels` steps of indentation.
+    pub fn indent(&self, levels: usize) -> String {
+        " ".repeat(self.indent.unwrap_or(DEFAULT_INDENT) * levels)
+    }
+
+    /// A built-in component template (2-space indented) in this style. Run it
+    /// before substituting `%svg%` so the markup itself is left alone.
+    pub fn apply_to_template(&self, template: &str) -> String {
+        template
+            .split('\n')
+            .map(|line| self.apply_to_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn apply_to_line(&self, line: &str) -> String {
+        let trimmed = line.trim_start();
+        let depth = line.len() - trimmed.len();
+        let mut body = trimmed.to_string();
+
+        // Only import lines hold JS strings; anything else quoted is markup.
+        let is_import = trimmed.starts_with("import ");
+        if is_import && let Some(quote) = self.quotes {
+            let other = match quote {
+                Quote::Single => '"',
+                Quote::Double => '\'',
+            };
+            body = body.replace(other, &quote.char().to_string());
+        }
+
+        match self.semicolons {
+            Some(false) => {
+                if let Some(stripped) = body.strip_suffix(';') {
+                    body = stripped.to_string();
+                }
+            }
+            Some(true) if !body.ends_with(';') && (is_import || body.ends_with("()")) => {
+                body.push(';');
+            }
+            _ => {}
+        }
+
+        let indent = match self.indent {
+            Some(width) => " ".repeat(depth / DEFAULT_INDENT * width + depth % DEFAULT_INDENT),
+            None => " ".repeat(depth),
+        };
+        format!("{indent}{body}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template;
+
+    #[test]
+    fn restyles_templates_but_not_markup() {
+        let style = CodeStyle {
+            quotes: Some(Quote::Double),
+            semicolons: Some(false),
+            indent: Some(4),
+        };
+        assert_eq!(
+            style.apply_to_template(template::REACT_COMPONENT),
+            "import type { SVGProps } from \"react\"\n\nexport default function Icon(props: SVGProps<SVGSVGElement>) {\n    return (\n%svg%\n    )\n}"
+        );
+
+        let style = CodeStyle {
+            semicolons: Some(true),
+            ..Default::default()
+        };
+        let vue = style.apply_to_template(template::VUE_COMPONENT);
+        assert!(vue.contains("import type { SVGAttributes } from 'vue';\n"));
+        assert!(vue.contains("defineProps<SVGAttributes>();\n"));
+        assert!(vue.contains("<script setup lang=\"ts\">"));
+
+        assert_eq!(
+            CodeStyle::default().apply_to_template(template::SVELTE_COMPONENT),
+            template::SVELTE_COMPONENT
+        );
+    }
+}
@@ -0,0 +1,126 @@
+// Pinned and hidden Iconify collections (`iconmate/collections.json` in the
+// user's config directory).
+//
+// The TUI's Collections tab lists 150+ sets. Pinned ones sort to the top and
+// hidden ones drop out of the list, so the few sets a person actually uses
+// are a keypress away. The choice follows the person rather than the
+// project, so it lives next to the global config instead of in the repo.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+pub const COLLECTION_PREFS_FILE: &str = "collections.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionPrefs {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden: Vec<String>,
+}
+
+/// `$XDG_CONFIG_HOME/iconmate/collections.json`, or under `~/.config`, like
+/// the global config.
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("iconmate").join(COLLECTION_PREFS_FILE))
+}
+
+impl CollectionPrefs {
+    /// The saved prefs; empty when the file doesn't exist yet.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn is_pinned(&self, prefix: &str) -> bool {
+        self.pinned.iter().any(|pinned| pinned == prefix)
+    }
+
+    pub fn is_hidden(&self, prefix: &str) -> bool {
+        self.hidden.iter().any(|hidden| hidden == prefix)
+    }
+
+    /// Pins or unpins `prefix`; returns whether it is pinned now. Pinning a
+    /// hidden collection shows it again.
+    pub fn toggle_pinned(&mut self, prefix: &str) -> bool {
+        let pinned = toggle(&mut self.pinned, prefix);
+        if pinned {
+            self.hidden.retain(|hidden| hidden != prefix);
+        }
+        pinned
+    }
+
+    /// Hides or shows `prefix`; returns whether it is hidden now. Hiding a
+    /// pinned collection unpins it.
+    pub fn toggle_hidden(&mut self, prefix: &str) -> bool {
+        let hidden = toggle(&mut self.hidden, prefix);
+        if hidden {
+            self.pinned.retain(|pinned| pinned != prefix);
+        }
+        hidden
+    }
+}
+
+fn toggle(list: &mut Vec<String>, prefix: &str) -> bool {
+    match list.iter().position(|item| item == prefix) {
+        Some(position) => {
+            list.remove(position);
+            false
+        }
+        None => {
+            list.push(prefix.to_string());
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinning_and_hiding_exclude_each_other_and_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("iconmate").join(COLLECTION_PREFS_FILE);
+        assert_eq!(
+            CollectionPrefs::read(&path).unwrap(),
+            CollectionPrefs::default()
+        );
+
+        let mut prefs = CollectionPrefs::default();
+        assert!(prefs.toggle_hidden("mdi"));
+        assert!(prefs.toggle_pinned("lucide"));
+        assert!(prefs.toggle_pinned("mdi"));
+        assert!(!prefs.is_hidden("mdi"));
+        assert!(prefs.toggle_hidden("lucide"));
+        assert_eq!(prefs.pinned, ["mdi"]);
+        assert_eq!(prefs.hidden, ["lucide"]);
+
+        prefs.write(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\n  \"pinned\": [\n    \"mdi\"\n  ],\n  \"hidden\": [\n    \"lucide\"\n  ]\n}\n"
+        );
+        assert_eq!(CollectionPrefs::read(&path).unwrap(), prefs);
+    }
+}
@@ -1,11 +1,27 @@
 use anyhow::Context;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde_json::{Map, Value};
 use std::path::{Path, PathBuf};
 
-use crate::utils::{PRESETS_OPTIONS, Preset};
+use crate::app_state::TuiBehavior;
+use crate::barrel::BarrelFrame;
+use crate::code_style::{CodeStyle, INDENT_OPTIONS, Quote};
+use crate::config_formats;
+use crate::history::HistoryLog;
+use crate::hooks::{Hook, Hooks};
+use crate::index_writer::IndexStrategy;
+use crate::size_budget::{OversizeAction, SizeBudget, parse_size};
+use crate::svg_format::{LineEnding, SvgFormat, SvgOutput};
+use crate::template;
+use crate::utils::{AliasCase, AliasStyle, FilenameCase, ImportQuery, PRESETS_OPTIONS, Preset};
+use crate::viewer::{PreviewColors, Rgb};
+use crate::views::theme::{PALETTE_KEYS, THEME_NAMES, parse_color};
 
 pub const DEFAULT_FOLDER: &str = "src/assets/icons";
 
+const LOCAL_SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/Blankeos/iconmate/main/iconmatelocal.schema.json";
+
 /// Default SVG folder for a given preset. Flutter's convention is
 /// `assets/icons/` at project root; everything else stays `src/assets/icons`.
 pub fn default_folder_for_preset(preset: &str) -> &'static str {
@@ -23,17 +39,58 @@ struct LocalConfigFile {
     svg_viewer_cmd: Option<String>,
     flutter_barrel_file: Option<String>,
     flutter_barrel_class: Option<String>,
+    theme: Option<String>,
+    palette: Vec<(String, String)>,
+    alias_case: Option<AliasCase>,
+    alias_prefix: Option<String>,
+    alias_suffix: Option<String>,
+    alias_collection_prefix: Option<bool>,
+    hooks: Hooks,
+    confirm_delete: Option<bool>,
+    auto_open_after_add: Option<bool>,
+    sanitize_svg: Option<bool>,
+    svg_format: Option<SvgFormat>,
+    svg_indent: Option<usize>,
+    line_endings: Option<LineEnding>,
+    emoji_set: Option<String>,
+    index_header: Option<String>,
+    index_footer: Option<String>,
+    index_eslint_disable: Option<bool>,
+    code_style: CodeStyle,
+    icon_names_dts: Option<bool>,
+    import_query: Option<ImportQuery>,
+    svgr_compat: Option<bool>,
+    icon_registry: Option<bool>,
+    lazy_exports: Option<bool>,
+    max_icon_size: Option<u64>,
+    on_oversize: Option<OversizeAction>,
+    filename_case: Option<FilenameCase>,
+    history_log: Option<bool>,
+    index_strategy: Option<IndexStrategy>,
+    html_class_hook: Option<String>,
+    usage_snippets: Vec<(String, String)>,
+    viewer_cmds: Vec<(String, String)>,
+    preview_colors: PreviewColors,
 }
 
 #[derive(Debug, Clone, Default)]
 struct GlobalConfigFile {
     svg_viewer_cmd: Option<String>,
+    viewer_cmds: Vec<(String, String)>,
+    theme: Option<String>,
+    palette: Vec<(String, String)>,
+    confirm_delete: Option<bool>,
+    auto_open_after_add: Option<bool>,
+    preview_colors: PreviewColors,
+    request_headers: Vec<crate::http::RequestHeaders>,
 }
 
 #[derive(Debug, Clone)]
 struct LoadedConfigFile<T> {
     path: PathBuf,
     value: T,
+    /// Base configs pulled in through `extends`, nearest first.
+    extends: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -42,10 +99,69 @@ pub struct ResolvedTuiConfig {
     pub preset: String,
     pub svg_viewer_cmd: Option<String>,
     pub svg_viewer_cmd_source: String,
+    /// `viewer_cmds` by file extension: global entries first, then local
+    /// ones on top.
+    pub viewer_cmds: Vec<(String, String)>,
     pub global_config_loaded: bool,
     pub project_config_loaded: bool,
     pub flutter_barrel_file: Option<String>,
     pub flutter_barrel_class: Option<String>,
+    /// TUI theme name (see `views::theme::THEME_NAMES`). Local > global.
+    pub theme: Option<String>,
+    /// Palette overrides: global entries first, then local ones on top.
+    pub palette: Vec<(String, String)>,
+    /// Export naming convention (local config only; CLI flags override it).
+    pub alias_style: AliasStyle,
+    /// Add/delete hook commands (local config only).
+    pub hooks: Hooks,
+    /// `confirm_delete` / `auto_open_after_add`. Local > global.
+    pub behavior: TuiBehavior,
+    /// `sanitize_svg`, `svg_format`, `svg_indent` and `line_endings` (local
+    /// config only; CLI flags override them).
+    pub svg_output: SvgOutput,
+    /// Iconify emoji set for emoji icon sources (local config only).
+    pub emoji_set: String,
+    /// `index_header`, `index_footer` and `index_eslint_disable` (local
+    /// config only).
+    pub barrel_frame: BarrelFrame,
+    /// `code_style` for export lines and component templates (local config
+    /// only).
+    pub code_style: CodeStyle,
+    /// Generate `icon-names.d.ts` on add and sync (local config only).
+    pub icon_names_dts: bool,
+    /// Bundler query for `.svg` imports in `index.ts` (local config only;
+    /// `--import-query` overrides it).
+    pub import_query: Option<ImportQuery>,
+    /// Write React icons the way SVGR does (local config only;
+    /// `--svgr-compat` turns it on per add).
+    pub svgr_compat: bool,
+    /// Maintain `icon-registry.ts` and an `<Icon name>` component on add and
+    /// sync (local config only).
+    pub icon_registry: bool,
+    /// Lazy `index.ts` exports for component presets (local config only;
+    /// `--lazy` turns it on per add).
+    pub lazy_exports: bool,
+    /// `max_icon_size` and `on_oversize` for `add` and `check` (local config
+    /// only; `--force` skips it per add).
+    pub size_budget: Option<SizeBudget>,
+    /// Case for inferred icon filenames and `normalize` (local config only;
+    /// `--filename` and `normalize --case` override it).
+    pub filename_case: Option<FilenameCase>,
+    /// `.iconmate/log.jsonl` for add, delete and rename; `None` when
+    /// `history_log` is off (local config only).
+    pub history: Option<HistoryLog>,
+    /// Barrel language `add` writes: `index.ts`, `index.js`, `icons.dart`
+    /// or `mod.rs` (local config only).
+    pub index_strategy: IndexStrategy,
+    /// `class` attribute value for the html preset's partials (local config
+    /// only).
+    pub html_class_hook: Option<String>,
+    /// `usage_snippets` template for the resolved preset, shown and copied
+    /// in the TUI (local config only).
+    pub usage_snippet: Option<String>,
+    /// `preview_colors` for `currentColor` and the canvas in previews. Local
+    /// > global, per color.
+    pub preview_colors: PreviewColors,
     pub warnings: Vec<String>,
     pub info: Vec<String>,
 }
@@ -65,6 +181,9 @@ pub fn resolve_tui_config(
             "Loaded local config from {}",
             config.path.display()
         ));
+        for base in &config.extends {
+            info.push(format!("Extended base config {}", base.display()));
+        }
     }
     if let Some(config) = &global {
         info.push(format!(
@@ -143,42 +262,340 @@ pub fn resolve_tui_config(
         "Resolved svg_viewer_cmd source: {}",
         svg_viewer_cmd_source
     ));
+    let viewer_cmds = global
+        .iter()
+        .flat_map(|config| config.value.viewer_cmds.iter().cloned())
+        .chain(
+            local
+                .iter()
+                .flat_map(|config| config.value.viewer_cmds.iter().cloned()),
+        )
+        .collect();
+
+    let theme = local
+        .as_ref()
+        .and_then(|config| config.value.theme.clone())
+        .or_else(|| {
+            global
+                .as_ref()
+                .and_then(|config| config.value.theme.clone())
+        });
+    let palette = global
+        .iter()
+        .flat_map(|config| config.value.palette.iter().cloned())
+        .chain(
+            local
+                .iter()
+                .flat_map(|config| config.value.palette.iter().cloned()),
+        )
+        .collect();
+
+    let mut alias_style = AliasStyle::default();
+    if let Some(config) = &local {
+        if let Some(case) = config.value.alias_case {
+            alias_style.case = case;
+        }
+        if let Some(prefix) = &config.value.alias_prefix {
+            alias_style.prefix = prefix.clone();
+        }
+        if let Some(suffix) = &config.value.alias_suffix {
+            alias_style.suffix = suffix.clone();
+        }
+        if let Some(collection_prefix) = config.value.alias_collection_prefix {
+            alias_style.collection_prefix = collection_prefix;
+        }
+    }
+
+    let hooks = local
+        .as_ref()
+        .map(|config| config.value.hooks.clone())
+        .unwrap_or_default();
+
+    let mut behavior = TuiBehavior::default();
+    if let Some(value) = local
+        .as_ref()
+        .and_then(|config| config.value.confirm_delete)
+        .or_else(|| {
+            global
+                .as_ref()
+                .and_then(|config| config.value.confirm_delete)
+        })
+    {
+        behavior.confirm_delete = value;
+    }
+    if let Some(value) = local
+        .as_ref()
+        .and_then(|config| config.value.auto_open_after_add)
+        .or_else(|| {
+            global
+                .as_ref()
+                .and_then(|config| config.value.auto_open_after_add)
+        })
+    {
+        behavior.auto_open_after_add = value;
+    }
+
+    let mut svg_output = SvgOutput::default();
+    if let Some(config) = &local {
+        if let Some(sanitize) = config.value.sanitize_svg {
+            svg_output.sanitize = sanitize;
+        }
+        svg_output.format = config.value.svg_format;
+        if let Some(indent) = config.value.svg_indent {
+            svg_output.indent = indent;
+        }
+        if let Some(line_ending) = config.value.line_endings {
+            svg_output.line_ending = line_ending;
+        }
+    }
+
+    let usage_snippet = local.as_ref().and_then(|config| {
+        config
+            .value
+            .usage_snippets
+            .iter()
+            .find(|(snippet_preset, _)| *snippet_preset == preset)
+            .map(|(_, template)| template.clone())
+    });
+
+    let preview_colors = {
+        let local = local.as_ref().map(|config| config.value.preview_colors);
+        let global = global.as_ref().map(|config| config.value.preview_colors);
+        PreviewColors {
+            foreground: local
+                .and_then(|colors| colors.foreground)
+                .or(global.and_then(|colors| colors.foreground)),
+            background: local
+                .and_then(|colors| colors.background)
+                .or(global.and_then(|colors| colors.background)),
+        }
+    };
 
     Ok(ResolvedTuiConfig {
         folder,
         preset,
         svg_viewer_cmd,
         svg_viewer_cmd_source,
+        viewer_cmds,
         global_config_loaded: global.is_some(),
         project_config_loaded: local.is_some(),
         flutter_barrel_file,
         flutter_barrel_class,
+        theme,
+        palette,
+        alias_style,
+        hooks,
+        behavior,
+        svg_output,
+        emoji_set: local
+            .as_ref()
+            .and_then(|config| config.value.emoji_set.clone())
+            .filter(|set| !set.trim().is_empty())
+            .unwrap_or_else(|| crate::emoji::DEFAULT_EMOJI_SET.to_string()),
+        barrel_frame: local
+            .as_ref()
+            .map(|config| {
+                BarrelFrame::new(
+                    config.value.index_header.as_deref(),
+                    config.value.index_footer.as_deref(),
+                    config.value.index_eslint_disable.unwrap_or(false),
+                )
+            })
+            .unwrap_or_default(),
+        code_style: local
+            .as_ref()
+            .map(|config| config.value.code_style)
+            .unwrap_or_default(),
+        icon_names_dts: local
+            .as_ref()
+            .and_then(|config| config.value.icon_names_dts)
+            .unwrap_or(false),
+        import_query: local.as_ref().and_then(|config| config.value.import_query),
+        svgr_compat: local
+            .as_ref()
+            .and_then(|config| config.value.svgr_compat)
+            .unwrap_or(false),
+        icon_registry: local
+            .as_ref()
+            .and_then(|config| config.value.icon_registry)
+            .unwrap_or(false),
+        lazy_exports: local
+            .as_ref()
+            .and_then(|config| config.value.lazy_exports)
+            .unwrap_or(false),
+        size_budget: local.as_ref().and_then(|config| {
+            Some(SizeBudget {
+                max_bytes: config.value.max_icon_size?,
+                action: config.value.on_oversize.unwrap_or_default(),
+            })
+        }),
+        filename_case: local.as_ref().and_then(|config| config.value.filename_case),
+        history: local
+            .as_ref()
+            .and_then(|config| config.value.history_log)
+            .unwrap_or(true)
+            .then(|| HistoryLog::at(crate::history::LOG_FILE)),
+        index_strategy: local
+            .as_ref()
+            .and_then(|config| config.value.index_strategy)
+            .unwrap_or_default(),
+        html_class_hook: local
+            .as_ref()
+            .and_then(|config| config.value.html_class_hook.clone()),
+        usage_snippet,
+        preview_colors,
         warnings,
         info,
     })
 }
 
+/// Writes a starter `iconmate.config.jsonc` into `dir`, as offered by the
+/// TUI onboarding screen and `iconmate init`. Refuses to overwrite an
+/// existing file.
+pub fn write_local_config(
+    dir: &Path,
+    folder: &str,
+    preset: &str,
+    import_query: Option<ImportQuery>,
+) -> anyhow::Result<PathBuf> {
+    let path = dir.join("iconmate.config.jsonc");
+    if path.exists() {
+        anyhow::bail!("{} already exists", path.display());
+    }
+    let mut contents = format!(
+        "{{\n  \"$schema\": {},\n  \"folder\": {},\n  \"preset\": {}",
+        Value::from(LOCAL_SCHEMA_URL),
+        Value::from(folder),
+        Value::from(preset)
+    );
+    if let Some(query) = import_query {
+        contents.push_str(&format!(
+            ",\n  \"import_query\": {}",
+            Value::from(query.to_str())
+        ));
+    }
+    contents.push_str("\n}\n");
+    std::fs::write(&path, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
 fn load_local_config(
     warnings: &mut Vec<String>,
 ) -> anyhow::Result<Option<LoadedConfigFile<LocalConfigFile>>> {
     let current_dir =
         std::env::current_dir().context("Failed to resolve current working directory")?;
+    load_local_config_in(&current_dir, warnings)
+}
+
+/// An icons folder configured in a package below the workspace root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceFolder {
+    /// The config file that names the folder.
+    pub config: PathBuf,
+    pub folder: PathBuf,
+    pub preset: String,
+}
+
+/// The icons folders configured in `root` and every package below it.
+/// Dependencies, build output and hidden folders aren't searched; configs
+/// that fail to load are reported in `warnings` and skipped.
+pub fn find_workspace_folders(
+    root: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<Vec<WorkspaceFolder>> {
+    let mut folders = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        match load_local_config_in(&dir, warnings) {
+            Ok(Some(config)) => {
+                let preset = config
+                    .value
+                    .preset
+                    .clone()
+                    .unwrap_or_else(|| "normal".to_string());
+                let folder = config
+                    .value
+                    .folder
+                    .clone()
+                    .unwrap_or_else(|| default_folder_for_preset(&preset).to_string());
+                folders.push(WorkspaceFolder {
+                    config: config.path,
+                    folder: dir.join(folder),
+                    preset,
+                });
+            }
+            Ok(None) => {}
+            Err(error) => warnings.push(format!("{error:#}")),
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+                && !name.starts_with('.')
+                && !crate::normalize::SKIPPED_DIRS.contains(&name.as_str())
+            {
+                pending.push(entry.path());
+            }
+        }
+    }
+    folders.sort_by(|a, b| a.config.cmp(&b.config));
+    Ok(folders)
+}
+
+fn load_local_config_in(
+    current_dir: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<Option<LoadedConfigFile<LocalConfigFile>>> {
     let candidates = [
         current_dir.join("iconmate.config.jsonc"),
         current_dir.join("iconmate.config.json"),
         current_dir.join("iconmate.jsonc"),
         current_dir.join("iconmate.json"),
+        current_dir.join("iconmate.toml"),
+        current_dir.join("iconmate.yaml"),
+        current_dir.join("iconmate.yml"),
     ];
 
-    let Some(path) = candidates.into_iter().find(|candidate| candidate.exists()) else {
-        return Ok(None);
+    // Dedicated config files win over package.json's "iconmate" key.
+    let package_json = current_dir.join("package.json");
+    let package_config = if package_json.exists() {
+        // A package.json iconmate can't read shouldn't block every command.
+        read_package_json_config(&package_json).unwrap_or_else(|error| {
+            warnings.push(format!("{error:#}"));
+            None
+        })
+    } else {
+        None
+    };
+
+    let (path, value) = match candidates.into_iter().find(|candidate| candidate.exists()) {
+        Some(path) => {
+            if package_config.is_some() {
+                warnings.push(format!(
+                    "Ignoring \"iconmate\" key in {} because {} takes precedence.",
+                    package_json.display(),
+                    path.display()
+                ));
+            }
+            let value = parse_config_file(&path)?;
+            (path, value)
+        }
+        None => match package_config {
+            Some(value) => (package_json, value),
+            None => return Ok(None),
+        },
     };
 
-    let value = parse_jsonc_file(&path)?;
+    let mut extends = Vec::new();
+    let value = resolve_extends(value, &path, &mut extends)?;
     let parsed = parse_local_value(value, &path, warnings)?;
     Ok(Some(LoadedConfigFile {
         path,
         value: parsed,
+        extends,
     }))
 }
 
@@ -197,6 +614,12 @@ fn load_global_config(
         candidates.push(config_dir.join("iconmate.json"));
         candidates.push(config_dir.join("iconmate").join("config.jsonc"));
         candidates.push(config_dir.join("iconmate").join("config.json"));
+        candidates.push(config_dir.join("iconmate.toml"));
+        candidates.push(config_dir.join("iconmate.yaml"));
+        candidates.push(config_dir.join("iconmate.yml"));
+        candidates.push(config_dir.join("iconmate").join("config.toml"));
+        candidates.push(config_dir.join("iconmate").join("config.yaml"));
+        candidates.push(config_dir.join("iconmate").join("config.yml"));
     }
 
     if let Some(home_dir) = dirs::home_dir() {
@@ -209,23 +632,172 @@ fn load_global_config(
         return Ok(None);
     };
 
-    let value = parse_jsonc_file(&path)?;
+    let value = parse_config_file(&path)?;
     let parsed = parse_global_value(value, &path, warnings)?;
     Ok(Some(LoadedConfigFile {
         path,
         value: parsed,
+        extends: Vec::new(),
     }))
 }
 
-fn parse_jsonc_file(path: &Path) -> anyhow::Result<Value> {
+/// The `"iconmate"` object in package.json, if there is one.
+fn read_package_json_config(path: &Path) -> anyhow::Result<Option<Value>> {
+    let Value::Object(mut package) = parse_config_file(path)? else {
+        return Ok(None);
+    };
+    match package.remove("iconmate") {
+        None => Ok(None),
+        Some(value @ Value::Object(_)) => Ok(Some(value)),
+        Some(_) => anyhow::bail!(
+            "Invalid config at {}: key 'iconmate' must be an object.",
+            path.display()
+        ),
+    }
+}
+
+/// Reads a config file into the shared JSON model, picking the frontend by
+/// extension: `.toml`, `.yaml`/`.yml`, otherwise JSON/JSONC.
+fn parse_config_file(path: &Path) -> anyhow::Result<Value> {
     let raw = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read config file {}", path.display()))?;
-    json5::from_str::<Value>(&raw).with_context(|| {
-        format!(
-            "Invalid config format in {}. Expected JSON/JSONC-compatible object.",
-            path.display()
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => config_formats::parse_toml(&raw).with_context(|| {
+            format!(
+                "Invalid config format in {}. Expected TOML.",
+                path.display()
+            )
+        }),
+        Some("yaml" | "yml") => config_formats::parse_yaml(&raw).with_context(|| {
+            format!(
+                "Invalid config format in {}. Expected YAML.",
+                path.display()
+            )
+        }),
+        _ => json5::from_str::<Value>(&raw).with_context(|| {
+            format!(
+                "Invalid config format in {}. Expected JSON/JSONC-compatible object.",
+                path.display()
+            )
+        }),
+    }
+}
+
+/// Folds `"extends"` (a path or list of paths) into `value`: bases load first,
+/// in order, and the extending config's own keys win. Nested objects such as
+/// `palette` merge key by key. `visited` collects every base loaded, once each.
+fn resolve_extends(value: Value, path: &Path, visited: &mut Vec<PathBuf>) -> anyhow::Result<Value> {
+    resolve_extends_within(value, path, &mut Vec::new(), visited)
+}
+
+/// `ancestors` holds the bases being resolved above `path`; only those make
+/// a cycle, so two bases may share one of their own (a diamond).
+fn resolve_extends_within(
+    value: Value,
+    path: &Path,
+    ancestors: &mut Vec<PathBuf>,
+    visited: &mut Vec<PathBuf>,
+) -> anyhow::Result<Value> {
+    let Value::Object(mut object) = value else {
+        return Ok(value);
+    };
+    let Some(extends) = object.remove("extends") else {
+        return Ok(Value::Object(object));
+    };
+    let specs = match extends {
+        Value::String(spec) => vec![spec],
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(spec) => Ok(spec),
+                _ => Err(invalid_extends(path)),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        _ => return Err(invalid_extends(path)),
+    };
+
+    let own_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut merged = Map::new();
+    for spec in specs {
+        let base_path = resolve_extends_path(&spec, path)?;
+        if base_path == own_path || ancestors.contains(&base_path) {
+            anyhow::bail!(
+                "Invalid config at {}: 'extends' cycle through {}.",
+                path.display(),
+                base_path.display()
+            );
+        }
+        if !visited.contains(&base_path) {
+            visited.push(base_path.clone());
+        }
+        ancestors.push(base_path.clone());
+        let base = resolve_extends_within(
+            parse_config_file(&base_path)?,
+            &base_path,
+            ancestors,
+            visited,
+        );
+        ancestors.pop();
+        let base = as_object(base?, &base_path)?;
+        merge_config_objects(&mut merged, base);
+    }
+    merge_config_objects(&mut merged, object);
+    Ok(Value::Object(merged))
+}
+
+fn invalid_extends(path: &Path) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Invalid config at {}: key 'extends' must be a path or a list of paths.",
+        path.display()
+    )
+}
+
+/// `./base.jsonc` and `/abs/base.json` are files relative to the config;
+/// anything else (`@acme/iconmate-config`, `pkg/iconmate.base.jsonc`) is looked
+/// up in the nearest `node_modules`. A package directory resolves to its
+/// `iconmate.config.jsonc` / `iconmate.config.json`.
+fn resolve_extends_path(spec: &str, from: &Path) -> anyhow::Result<PathBuf> {
+    let config_dir = from.parent().unwrap_or(Path::new("."));
+    let candidate = if spec.starts_with('.') || Path::new(spec).is_absolute() {
+        Some(config_dir.join(spec))
+    } else {
+        config_dir
+            .ancestors()
+            .map(|dir| dir.join("node_modules").join(spec))
+            .find(|candidate| candidate.exists())
+    };
+    let resolved = candidate.and_then(|candidate| {
+        if candidate.is_dir() {
+            ["iconmate.config.jsonc", "iconmate.config.json"]
+                .iter()
+                .map(|name| candidate.join(name))
+                .find(|file| file.is_file())
+        } else {
+            candidate.is_file().then_some(candidate)
+        }
+    });
+    let resolved = resolved.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: can't find extended config '{}'.",
+            from.display(),
+            spec
         )
-    })
+    })?;
+    // Canonical so `./a` and `../pkg/a` are recognized as the same file.
+    Ok(resolved.canonicalize().unwrap_or(resolved))
+}
+
+fn merge_config_objects(into: &mut Map<String, Value>, overrides: Map<String, Value>) {
+    for (key, value) in overrides {
+        match (into.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge_config_objects(existing, nested)
+            }
+            (_, value) => {
+                into.insert(key, value);
+            }
+        }
+    }
 }
 
 fn parse_local_value(
@@ -244,6 +816,41 @@ fn parse_local_value(
             "svg_viewer_cmd",
             "flutter_barrel_file",
             "flutter_barrel_class",
+            "theme",
+            "palette",
+            "alias_case",
+            "alias_prefix",
+            "alias_suffix",
+            "alias_collection_prefix",
+            "pre_add_cmd",
+            "post_add_cmd",
+            "pre_delete_cmd",
+            "post_delete_cmd",
+            "confirm_delete",
+            "auto_open_after_add",
+            "sanitize_svg",
+            "svg_format",
+            "svg_indent",
+            "line_endings",
+            "emoji_set",
+            "index_header",
+            "index_footer",
+            "index_eslint_disable",
+            "code_style",
+            "icon_names_dts",
+            "import_query",
+            "svgr_compat",
+            "icon_registry",
+            "lazy_exports",
+            "max_icon_size",
+            "on_oversize",
+            "filename_case",
+            "history_log",
+            "index_strategy",
+            "html_class_hook",
+            "usage_snippets",
+            "viewer_cmds",
+            "preview_colors",
         ],
         path,
         warnings,
@@ -279,6 +886,50 @@ fn parse_local_value(
     let svg_viewer_cmd = read_svg_viewer_cmd(&object, path, warnings)?;
     let flutter_barrel_file = read_string_field(&object, path, "flutter_barrel_file", false)?;
     let flutter_barrel_class = read_string_field(&object, path, "flutter_barrel_class", false)?;
+    let theme = read_theme(&object, path)?;
+    let palette = read_palette(&object, path, warnings)?;
+    let alias_case = read_alias_case(&object, path)?;
+    let alias_prefix = read_string_field(&object, path, "alias_prefix", true)?;
+    let alias_suffix = read_string_field(&object, path, "alias_suffix", true)?;
+    let alias_collection_prefix = read_bool_field(&object, path, "alias_collection_prefix")?;
+    let hooks = Hooks {
+        pre_add: read_string_field(&object, path, Hook::PreAdd.config_key(), false)?,
+        post_add: read_string_field(&object, path, Hook::PostAdd.config_key(), false)?,
+        pre_delete: read_string_field(&object, path, Hook::PreDelete.config_key(), false)?,
+        post_delete: read_string_field(&object, path, Hook::PostDelete.config_key(), false)?,
+    };
+    let confirm_delete = read_bool_field(&object, path, "confirm_delete")?;
+    let auto_open_after_add = read_bool_field(&object, path, "auto_open_after_add")?;
+    let sanitize_svg = read_bool_field(&object, path, "sanitize_svg")?;
+    let svg_format = read_svg_format(&object, path)?;
+    let svg_indent = read_svg_indent(&object, path)?;
+    let line_endings = read_line_endings(&object, path)?;
+    let emoji_set = read_string_field(&object, path, "emoji_set", false)?;
+    let index_header = read_string_field(&object, path, "index_header", true)?;
+    let index_footer = read_string_field(&object, path, "index_footer", true)?;
+    let index_eslint_disable = read_bool_field(&object, path, "index_eslint_disable")?;
+    let code_style = read_code_style(&object, path, warnings)?;
+    let icon_names_dts = read_bool_field(&object, path, "icon_names_dts")?;
+    let import_query = read_import_query(&object, path)?;
+    let svgr_compat = read_bool_field(&object, path, "svgr_compat")?;
+    let icon_registry = read_bool_field(&object, path, "icon_registry")?;
+    let lazy_exports = read_bool_field(&object, path, "lazy_exports")?;
+    let max_icon_size = read_max_icon_size(&object, path)?;
+    let on_oversize = read_on_oversize(&object, path)?;
+    let filename_case = read_filename_case(&object, path)?;
+    let history_log = read_bool_field(&object, path, "history_log")?;
+    let index_strategy = read_index_strategy(&object, path)?;
+    let html_class_hook = read_string_field(&object, path, "html_class_hook", false)?;
+    let usage_snippets = read_usage_snippets(&object, path, warnings)?;
+    let viewer_cmds = read_viewer_cmds(&object, path)?;
+    let preview_colors = read_preview_colors(&object, path, warnings)?;
+    check_viewer_templates(svg_viewer_cmd.as_deref(), &viewer_cmds, path, warnings);
+    for hook in Hook::ALL {
+        if let Some(command) = hooks.command(hook) {
+            let vars = crate::hooks::HOOK_VARS;
+            warn_template_problems(command, vars, hook.config_key(), path, warnings);
+        }
+    }
 
     Ok(LocalConfigFile {
         folder,
@@ -286,6 +937,38 @@ fn parse_local_value(
         svg_viewer_cmd,
         flutter_barrel_file,
         flutter_barrel_class,
+        theme,
+        palette,
+        alias_case,
+        alias_prefix,
+        alias_suffix,
+        alias_collection_prefix,
+        hooks,
+        confirm_delete,
+        auto_open_after_add,
+        sanitize_svg,
+        svg_format,
+        svg_indent,
+        line_endings,
+        emoji_set,
+        index_header,
+        index_footer,
+        index_eslint_disable,
+        code_style,
+        icon_names_dts,
+        import_query,
+        svgr_compat,
+        icon_registry,
+        lazy_exports,
+        max_icon_size,
+        on_oversize,
+        filename_case,
+        history_log,
+        index_strategy,
+        html_class_hook,
+        usage_snippets,
+        viewer_cmds,
+        preview_colors,
     })
 }
 
@@ -297,13 +980,41 @@ fn parse_global_value(
     let object = as_object(value, path)?;
     warn_unknown_keys(
         &object,
-        &["$schema", "svg_view_cmd", "svg_viewer_cmd"],
+        &[
+            "$schema",
+            "svg_view_cmd",
+            "svg_viewer_cmd",
+            "theme",
+            "palette",
+            "confirm_delete",
+            "auto_open_after_add",
+            "viewer_cmds",
+            "preview_colors",
+            "request_headers",
+        ],
         path,
         warnings,
     );
 
     let svg_viewer_cmd = read_svg_viewer_cmd(&object, path, warnings)?;
-    Ok(GlobalConfigFile { svg_viewer_cmd })
+    let viewer_cmds = read_viewer_cmds(&object, path)?;
+    check_viewer_templates(svg_viewer_cmd.as_deref(), &viewer_cmds, path, warnings);
+    let request_headers = read_request_headers(&object, path)?;
+    let theme = read_theme(&object, path)?;
+    let palette = read_palette(&object, path, warnings)?;
+    let confirm_delete = read_bool_field(&object, path, "confirm_delete")?;
+    let auto_open_after_add = read_bool_field(&object, path, "auto_open_after_add")?;
+    let preview_colors = read_preview_colors(&object, path, warnings)?;
+    Ok(GlobalConfigFile {
+        svg_viewer_cmd,
+        viewer_cmds,
+        theme,
+        palette,
+        confirm_delete,
+        auto_open_after_add,
+        preview_colors,
+        request_headers,
+    })
 }
 
 fn as_object(value: Value, path: &Path) -> anyhow::Result<Map<String, Value>> {
@@ -361,6 +1072,22 @@ fn read_string_field(
     Ok(Some(value.to_string()))
 }
 
+fn read_bool_field(
+    object: &Map<String, Value>,
+    path: &Path,
+    key: &str,
+) -> anyhow::Result<Option<bool>> {
+    match object.get(key) {
+        None => Ok(None),
+        Some(Value::Bool(value)) => Ok(Some(*value)),
+        Some(_) => anyhow::bail!(
+            "Invalid config at {}: key '{}' must be a boolean.",
+            path.display(),
+            key
+        ),
+    }
+}
+
 fn read_svg_viewer_cmd(
     object: &Map<String, Value>,
     path: &Path,
@@ -385,79 +1112,1170 @@ fn read_svg_viewer_cmd(
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn parses_global_svg_viewer_cmd_alias() {
-        let value: Value = serde_json::json!({
-            "svg_viewer_cmd": "code %filename%"
-        });
-        let mut warnings = Vec::new();
-        let parsed =
-            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
-        assert_eq!(parsed.svg_viewer_cmd, Some("code %filename%".to_string()));
-        assert!(warnings.is_empty());
+/// Warns about unknown variables and filters in a config template; they'd
+/// otherwise be left in the output verbatim.
+fn warn_template_problems(
+    template: &str,
+    vars: &[&str],
+    key: &str,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) {
+    for problem in template::check(template, vars) {
+        warnings.push(format!(
+            "Config key '{key}' in {}: {problem}",
+            path.display()
+        ));
     }
+}
 
-    #[test]
-    fn warns_on_unknown_global_key() {
-        let value: Value = serde_json::json!({
-            "svg_view_cmd": "open %filename%",
-            "extra": true
-        });
-        let mut warnings = Vec::new();
-        let _ = parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
-        assert_eq!(warnings.len(), 1);
-        assert!(warnings[0].contains("Ignoring unknown key 'extra'"));
+fn check_viewer_templates(
+    svg_viewer_cmd: Option<&str>,
+    viewer_cmds: &[(String, String)],
+    path: &Path,
+    warnings: &mut Vec<String>,
+) {
+    let vars = crate::viewer::VIEWER_CMD_VARS;
+    if let Some(command) = svg_viewer_cmd {
+        warn_template_problems(command, vars, "svg_viewer_cmd", path, warnings);
+    }
+    for (extension, command) in viewer_cmds {
+        let key = format!("viewer_cmds.{extension}");
+        warn_template_problems(command, vars, &key, path, warnings);
     }
+}
 
-    #[test]
-    fn allows_schema_key_without_warning() {
-        let value: Value = serde_json::json!({
-            "$schema": "https://example.com/iconmate.schema.json",
-            "svg_view_cmd": "open %filename%"
-        });
-        let mut warnings = Vec::new();
-        let parsed =
-            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+/// `{ ".tsx": "code %filename%" }` -> `[("tsx", "code %filename%")]`.
+fn read_viewer_cmds(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let Some(value) = object.get("viewer_cmds") else {
+        return Ok(Vec::new());
+    };
+    let Some(commands) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'viewer_cmds' must be an object of file extensions to commands.",
+            path.display()
+        );
+    };
 
-        assert_eq!(parsed.svg_viewer_cmd, Some("open %filename%".to_string()));
-        assert!(warnings.is_empty());
+    let mut out = Vec::new();
+    for (extension, value) in commands {
+        let Some(command) = value.as_str().filter(|command| !command.trim().is_empty()) else {
+            anyhow::bail!(
+                "Invalid config at {}: viewer_cmds.{} must be a non-empty command.",
+                path.display(),
+                extension
+            );
+        };
+        let extension = extension.trim().trim_start_matches('.').to_lowercase();
+        out.push((extension, command.to_string()));
     }
+    Ok(out)
+}
 
-    #[test]
-    fn allows_schema_key_in_local_config_without_warning() {
-        let value: Value = serde_json::json!({
-            "$schema": "https://example.com/iconmate-local.schema.json",
-            "preset": "normal"
+/// `{ "https://icons.corp.example/": { "Cookie": "sso=..." } }`. Values are
+/// secrets; errors name the header but never repeat its value.
+fn read_request_headers(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Vec<crate::http::RequestHeaders>> {
+    let Some(value) = object.get("request_headers") else {
+        return Ok(Vec::new());
+    };
+    let Some(prefixes) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'request_headers' must be an object of URL prefixes to headers.",
+            path.display()
+        );
+    };
+
+    let mut out = Vec::new();
+    for (url_prefix, headers) in prefixes {
+        // Matched by scheme, host, port and whole path segments, so a
+        // prefix can't carry anything that would loosen that.
+        let valid = reqwest::Url::parse(url_prefix).is_ok_and(|url| {
+            matches!(url.scheme(), "http" | "https")
+                && url.host_str().is_some()
+                && url.username().is_empty()
+                && url.password().is_none()
+                && url.query().is_none()
+                && url.fragment().is_none()
         });
-        let mut warnings = Vec::new();
-        let parsed = parse_local_value(
-            value,
-            Path::new("/tmp/iconmate.config.jsonc"),
-            &mut warnings,
-        )
-        .expect("local config with $schema should parse");
+        if !valid {
+            anyhow::bail!(
+                "Invalid config at {}: request_headers key '{}' must be an http(s) URL prefix (scheme, host and optional path; no credentials, query or fragment).",
+                path.display(),
+                url_prefix
+            );
+        }
+        let Some(headers) = headers.as_object() else {
+            anyhow::bail!(
+                "Invalid config at {}: request_headers.{} must be an object of header names to values.",
+                path.display(),
+                url_prefix
+            );
+        };
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            let Ok(header) = HeaderName::from_bytes(name.trim().as_bytes()) else {
+                anyhow::bail!(
+                    "Invalid config at {}: request_headers.{} has an invalid header name '{}'.",
+                    path.display(),
+                    url_prefix,
+                    name
+                );
+            };
+            let Some(Ok(mut value)) = value.as_str().map(HeaderValue::from_str) else {
+                anyhow::bail!(
+                    "Invalid config at {}: request_headers.{}.{} must be a valid header value string.",
+                    path.display(),
+                    url_prefix,
+                    name
+                );
+            };
+            value.set_sensitive(true);
+            map.insert(header, value);
+        }
+        out.push(crate::http::RequestHeaders {
+            url_prefix: url_prefix.clone(),
+            headers: map,
+        });
+    }
+    Ok(out)
+}
 
-        assert_eq!(parsed.preset.as_deref(), Some("normal"));
-        assert!(warnings.is_empty());
+/// `request_headers` from the global config, for every command's requests.
+/// Only the global config is read: credentials follow the person, not the
+/// project.
+pub fn request_headers() -> anyhow::Result<Vec<crate::http::RequestHeaders>> {
+    let mut warnings = Vec::new();
+    Ok(load_global_config(&mut warnings)?
+        .map(|config| config.value.request_headers)
+        .unwrap_or_default())
+}
+
+fn read_theme(object: &Map<String, Value>, path: &Path) -> anyhow::Result<Option<String>> {
+    let theme = read_string_field(object, path, "theme", false)?;
+    if let Some(value) = theme.as_deref()
+        && !THEME_NAMES.contains(&value)
+    {
+        anyhow::bail!(
+            "Invalid config at {}: key 'theme' must be one of [{}], got '{}'.",
+            path.display(),
+            THEME_NAMES.join(", "),
+            value
+        );
     }
+    Ok(theme)
+}
 
-    #[test]
-    fn validates_local_preset_values() {
-        let value: Value = serde_json::json!({
-            "preset": "invalid"
-        });
-        let mut warnings = Vec::new();
-        let error = parse_local_value(
-            value,
-            Path::new("/tmp/iconmate.config.jsonc"),
-            &mut warnings,
+fn read_alias_case(object: &Map<String, Value>, path: &Path) -> anyhow::Result<Option<AliasCase>> {
+    let Some(value) = read_string_field(object, path, "alias_case", false)? else {
+        return Ok(None);
+    };
+    AliasCase::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'alias_case' must be one of [{}], got '{}'.",
+            path.display(),
+            AliasCase::NAMES.join(", "),
+            value
         )
-        .expect_err("invalid preset should fail validation");
-        assert!(error.to_string().contains("key 'preset' must be one of"));
+    })
+}
+
+fn read_import_query(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Option<ImportQuery>> {
+    let Some(value) = read_string_field(object, path, "import_query", false)? else {
+        return Ok(None);
+    };
+    ImportQuery::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'import_query' must be one of [{}], got '{}'.",
+            path.display(),
+            ImportQuery::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_svg_format(object: &Map<String, Value>, path: &Path) -> anyhow::Result<Option<SvgFormat>> {
+    let Some(value) = read_string_field(object, path, "svg_format", false)? else {
+        return Ok(None);
+    };
+    SvgFormat::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'svg_format' must be one of [{}], got '{}'.",
+            path.display(),
+            SvgFormat::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_line_endings(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Option<LineEnding>> {
+    let Some(value) = read_string_field(object, path, "line_endings", false)? else {
+        return Ok(None);
+    };
+    LineEnding::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'line_endings' must be one of [{}], got '{}'.",
+            path.display(),
+            LineEnding::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_svg_indent(object: &Map<String, Value>, path: &Path) -> anyhow::Result<Option<usize>> {
+    match object.get("svg_indent") {
+        None => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(indent) if indent <= 8 => Ok(Some(indent as usize)),
+            _ => anyhow::bail!(
+                "Invalid config at {}: key 'svg_indent' must be a whole number from 0 to 8.",
+                path.display()
+            ),
+        },
+    }
+}
+
+fn read_max_icon_size(object: &Map<String, Value>, path: &Path) -> anyhow::Result<Option<u64>> {
+    let size = match object.get("max_icon_size") {
+        None => return Ok(None),
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(value)) => parse_size(value),
+        Some(_) => None,
+    };
+    match size {
+        Some(bytes) if bytes > 0 => Ok(Some(bytes)),
+        _ => anyhow::bail!(
+            "Invalid config at {}: key 'max_icon_size' must be a positive byte count or a size like \"16KB\".",
+            path.display()
+        ),
+    }
+}
+
+fn read_on_oversize(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Option<OversizeAction>> {
+    let Some(value) = read_string_field(object, path, "on_oversize", false)? else {
+        return Ok(None);
+    };
+    OversizeAction::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'on_oversize' must be one of [{}], got '{}'.",
+            path.display(),
+            OversizeAction::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_filename_case(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Option<FilenameCase>> {
+    let Some(value) = read_string_field(object, path, "filename_case", false)? else {
+        return Ok(None);
+    };
+    FilenameCase::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'filename_case' must be one of [{}], got '{}'.",
+            path.display(),
+            FilenameCase::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_index_strategy(
+    object: &Map<String, Value>,
+    path: &Path,
+) -> anyhow::Result<Option<IndexStrategy>> {
+    let Some(value) = read_string_field(object, path, "index_strategy", false)? else {
+        return Ok(None);
+    };
+    IndexStrategy::from_str(&value).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid config at {}: key 'index_strategy' must be one of [{}], got '{}'.",
+            path.display(),
+            IndexStrategy::NAMES.join(", "),
+            value
+        )
+    })
+}
+
+fn read_code_style(
+    object: &Map<String, Value>,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<CodeStyle> {
+    let Some(value) = object.get("code_style") else {
+        return Ok(CodeStyle::default());
+    };
+    let Some(code_style) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'code_style' must be an object with 'quotes', 'semicolons' and/or 'indent'.",
+            path.display()
+        );
+    };
+    for key in code_style.keys() {
+        if !["quotes", "semicolons", "indent"].contains(&key.as_str()) {
+            warnings.push(format!(
+                "Ignoring unknown code_style key '{}' in {}",
+                key,
+                path.display()
+            ));
+        }
+    }
+
+    let quotes = match code_style.get("quotes") {
+        None => None,
+        Some(value) => Some(value.as_str().and_then(Quote::from_str).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid config at {}: code_style.quotes must be one of [{}].",
+                path.display(),
+                Quote::NAMES.join(", ")
+            )
+        })?),
+    };
+    let semicolons = match code_style.get("semicolons") {
+        None => None,
+        Some(Value::Bool(value)) => Some(*value),
+        Some(_) => anyhow::bail!(
+            "Invalid config at {}: code_style.semicolons must be a boolean.",
+            path.display()
+        ),
+    };
+    let indent = match code_style.get("indent") {
+        None => None,
+        Some(value) => Some(
+            value
+                .as_u64()
+                .map(|indent| indent as usize)
+                .filter(|indent| INDENT_OPTIONS.contains(indent))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid config at {}: code_style.indent must be 2 or 4.",
+                        path.display()
+                    )
+                })?,
+        ),
+    };
+    Ok(CodeStyle {
+        quotes,
+        semicolons,
+        indent,
+    })
+}
+
+fn read_palette(
+    object: &Map<String, Value>,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let Some(value) = object.get("palette") else {
+        return Ok(Vec::new());
+    };
+    let Some(palette) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'palette' must be an object of color values.",
+            path.display()
+        );
+    };
+
+    let mut out = Vec::new();
+    for (key, value) in palette {
+        if !PALETTE_KEYS.contains(&key.as_str()) {
+            warnings.push(format!(
+                "Ignoring unknown palette key '{}' in {}",
+                key,
+                path.display()
+            ));
+            continue;
+        }
+        let Some(color) = value.as_str().filter(|color| parse_color(color).is_some()) else {
+            anyhow::bail!(
+                "Invalid config at {}: palette.{} must be a color like \"#4ade80\", \"lightgreen\", or \"42\".",
+                path.display(),
+                key
+            );
+        };
+        out.push((key.clone(), color.to_string()));
+    }
+    Ok(out)
+}
+
+fn read_preview_colors(
+    object: &Map<String, Value>,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<PreviewColors> {
+    let Some(value) = object.get("preview_colors") else {
+        return Ok(PreviewColors::default());
+    };
+    let Some(colors) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'preview_colors' must be an object with 'foreground' and/or 'background'.",
+            path.display()
+        );
+    };
+
+    let mut out = PreviewColors::default();
+    for (key, value) in colors {
+        let slot = match key.as_str() {
+            "foreground" => &mut out.foreground,
+            "background" => &mut out.background,
+            _ => {
+                warnings.push(format!(
+                    "Ignoring unknown preview_colors key '{}' in {}",
+                    key,
+                    path.display()
+                ));
+                continue;
+            }
+        };
+        let Some(color) = value.as_str().and_then(Rgb::parse) else {
+            anyhow::bail!(
+                "Invalid config at {}: preview_colors.{} must be a hex color like \"#e5e7eb\".",
+                path.display(),
+                key
+            );
+        };
+        *slot = Some(color);
+    }
+    Ok(out)
+}
+
+fn read_usage_snippets(
+    object: &Map<String, Value>,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let Some(value) = object.get("usage_snippets") else {
+        return Ok(Vec::new());
+    };
+    let Some(snippets) = value.as_object() else {
+        anyhow::bail!(
+            "Invalid config at {}: key 'usage_snippets' must be an object of preset names to templates.",
+            path.display()
+        );
+    };
+
+    let mut out = Vec::new();
+    for (preset, value) in snippets {
+        if Preset::from_str(preset).is_none() {
+            warnings.push(format!(
+                "Ignoring usage snippet for unknown preset '{}' in {}",
+                preset,
+                path.display()
+            ));
+            continue;
+        }
+        let Some(template) = value
+            .as_str()
+            .filter(|template| !template.trim().is_empty())
+        else {
+            anyhow::bail!(
+                "Invalid config at {}: usage_snippets.{} must be a non-empty string.",
+                path.display(),
+                preset
+            );
+        };
+        // A snippet without the icon in it would read the same for every icon.
+        if !["name", "filename", "file"]
+            .iter()
+            .any(|key| template::references(template, key))
+        {
+            anyhow::bail!(
+                "Invalid config at {}: usage_snippets.{} must use %name%, %filename% or %file%.",
+                path.display(),
+                preset
+            );
+        }
+        warn_template_problems(
+            template,
+            crate::views::main::USAGE_SNIPPET_VARS,
+            &format!("usage_snippets.{preset}"),
+            path,
+            warnings,
+        );
+        out.push((preset.clone(), template.to_string()));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_local_config_round_trips() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_local_config(dir.path(), "src/icons", "normal", Some(ImportQuery::React))
+            .unwrap();
+        let value = parse_config_file(&path).unwrap();
+        let mut warnings = Vec::new();
+        let parsed = parse_local_value(value, &path, &mut warnings).unwrap();
+        assert_eq!(parsed.folder.as_deref(), Some("src/icons"));
+        assert_eq!(parsed.preset.as_deref(), Some("normal"));
+        assert_eq!(parsed.import_query, Some(ImportQuery::React));
+        assert!(warnings.is_empty());
+        assert!(write_local_config(dir.path(), "src/icons", "react", None).is_err());
+    }
+
+    #[test]
+    fn parses_global_svg_viewer_cmd_alias() {
+        let value: Value = serde_json::json!({
+            "svg_viewer_cmd": "code %filename%"
+        });
+        let mut warnings = Vec::new();
+        let parsed =
+            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+        assert_eq!(parsed.svg_viewer_cmd, Some("code %filename%".to_string()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn checks_config_templates_upfront() {
+        let mut warnings = Vec::new();
+        parse_local_value(
+            serde_json::json!({
+                "svg_viewer_cmd": "code %file%",
+                "post_add_cmd": "pnpm codegen %name|title%",
+                "viewer_cmds": { "tsx": "zed %filename%" }
+            }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'svg_viewer_cmd'"));
+        assert!(warnings[0].contains("unknown variable %file%"));
+        assert!(warnings[1].contains("'post_add_cmd'"));
+        assert!(warnings[1].contains("unknown filter 'title'"));
+
+        let error = parse_local_value(
+            serde_json::json!({ "usage_snippets": { "react": "<Icon />" } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("usage_snippets.react must use %name%, %filename% or %file%")
+        );
+    }
+
+    #[test]
+    fn parses_request_headers_without_echoing_values() {
+        let path = Path::new("/tmp/iconmate.jsonc");
+        let mut warnings = Vec::new();
+        let parsed = parse_global_value(
+            serde_json::json!({
+                "request_headers": {
+                    "https://icons.corp.example/": {
+                        "Authorization": "Bearer secret-token",
+                        "Cookie": "sso=abc"
+                    }
+                }
+            }),
+            path,
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+        let [rule] = parsed.request_headers.as_slice() else {
+            panic!("expected one prefix");
+        };
+        assert_eq!(rule.url_prefix, "https://icons.corp.example/");
+        assert_eq!(rule.headers["authorization"], "Bearer secret-token");
+        assert!(rule.headers["cookie"].is_sensitive());
+        assert!(!format!("{rule:?}").contains("secret-token"));
+
+        let error = parse_global_value(
+            serde_json::json!({
+                "request_headers": {
+                    "https://icons.corp.example/": { "Authorization": "Bearer secret\ntoken" }
+                }
+            }),
+            path,
+            &mut warnings,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("request_headers.https://icons.corp.example/.Authorization"));
+        assert!(!error.contains("secret"));
+
+        assert!(
+            parse_global_value(
+                serde_json::json!({ "request_headers": { "icons.corp.example": {} } }),
+                path,
+                &mut warnings,
+            )
+            .is_err()
+        );
+        assert!(
+            parse_global_value(
+                serde_json::json!({ "request_headers": { "https://icons.corp.example/?v=1": {} } }),
+                path,
+                &mut warnings,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_viewer_cmds_by_extension() {
+        let value: Value = serde_json::json!({
+            "viewer_cmds": { ".TSX": "code %filename%", "svg": "qlmanage -p" }
+        });
+        let mut warnings = Vec::new();
+        let parsed =
+            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+        assert_eq!(
+            parsed.viewer_cmds,
+            [
+                ("tsx".to_string(), "code %filename%".to_string()),
+                ("svg".to_string(), "qlmanage -p".to_string())
+            ]
+        );
+        assert!(warnings.is_empty());
+
+        let error = parse_local_value(
+            serde_json::json!({ "viewer_cmds": { "tsx": 1 } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("viewer_cmds.tsx must be a non-empty command")
+        );
+    }
+
+    #[test]
+    fn warns_on_unknown_global_key() {
+        let value: Value = serde_json::json!({
+            "svg_view_cmd": "open %filename%",
+            "extra": true
+        });
+        let mut warnings = Vec::new();
+        let _ = parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Ignoring unknown key 'extra'"));
+    }
+
+    #[test]
+    fn allows_schema_key_without_warning() {
+        let value: Value = serde_json::json!({
+            "$schema": "https://example.com/iconmate.schema.json",
+            "svg_view_cmd": "open %filename%"
+        });
+        let mut warnings = Vec::new();
+        let parsed =
+            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+
+        assert_eq!(parsed.svg_viewer_cmd, Some("open %filename%".to_string()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn allows_schema_key_in_local_config_without_warning() {
+        let value: Value = serde_json::json!({
+            "$schema": "https://example.com/iconmate-local.schema.json",
+            "preset": "normal"
+        });
+        let mut warnings = Vec::new();
+        let parsed = parse_local_value(
+            value,
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect("local config with $schema should parse");
+
+        assert_eq!(parsed.preset.as_deref(), Some("normal"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn validates_local_preset_values() {
+        let value: Value = serde_json::json!({
+            "preset": "invalid"
+        });
+        let mut warnings = Vec::new();
+        let error = parse_local_value(
+            value,
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect_err("invalid preset should fail validation");
+        assert!(error.to_string().contains("key 'preset' must be one of"));
+    }
+
+    #[test]
+    fn extends_merges_base_configs_under_local_keys() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let package = dir.path().join("node_modules/@acme/icons");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            package.join("iconmate.config.jsonc"),
+            r#"{ "preset": "react", "palette": { "accent": "red", "error": "red" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("iconmate.base.jsonc"),
+            r#"{ "extends": "@acme/icons", "folder": "src/icons", "alias_prefix": "" }"#,
+        )
+        .unwrap();
+        let local_path = dir.path().join("app/iconmate.config.jsonc");
+        std::fs::create_dir_all(local_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &local_path,
+            r#"{ "extends": ["../iconmate.base.jsonc"], "preset": "solid", "palette": { "accent": "blue" } }"#,
+        )
+        .unwrap();
+
+        let mut chain = Vec::new();
+        let value = resolve_extends(
+            parse_config_file(&local_path).unwrap(),
+            &local_path,
+            &mut chain,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "preset": "solid",
+                "palette": { "accent": "blue", "error": "red" },
+                "folder": "src/icons",
+                "alias_prefix": ""
+            })
+        );
+        assert_eq!(chain.len(), 2);
+
+        std::fs::write(
+            dir.path().join("iconmate.base.jsonc"),
+            r#"{ "extends": "./app/iconmate.config.jsonc" }"#,
+        )
+        .unwrap();
+        let error = resolve_extends(
+            parse_config_file(&local_path).unwrap(),
+            &local_path,
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("'extends' cycle"));
+    }
+
+    #[test]
+    fn extends_allows_two_bases_sharing_one() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("d.jsonc"),
+            r#"{ "preset": "react", "folder": "src/d" }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.jsonc"),
+            r#"{ "extends": "./d.jsonc", "alias_prefix": "B" }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("c.jsonc"),
+            r#"{ "extends": "./d.jsonc", "folder": "src/c" }"#,
+        )
+        .unwrap();
+        let a = dir.path().join("a.jsonc");
+        std::fs::write(&a, r#"{ "extends": ["./b.jsonc", "./c.jsonc"] }"#).unwrap();
+
+        let mut visited = Vec::new();
+        let value = resolve_extends(parse_config_file(&a).unwrap(), &a, &mut visited).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "preset": "react", "folder": "src/c", "alias_prefix": "B" })
+        );
+        assert_eq!(visited.len(), 3);
+    }
+
+    #[test]
+    fn reads_iconmate_key_from_package_json() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let package_json = dir.path().join("package.json");
+        std::fs::write(
+            &package_json,
+            r#"{ "name": "web", "iconmate": { "preset": "react" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            read_package_json_config(&package_json).unwrap(),
+            Some(serde_json::json!({ "preset": "react" }))
+        );
+
+        std::fs::write(&package_json, r#"{ "name": "web" }"#).unwrap();
+        assert_eq!(read_package_json_config(&package_json).unwrap(), None);
+
+        std::fs::write(&package_json, r#"{ "iconmate": "react" }"#).unwrap();
+        assert!(read_package_json_config(&package_json).is_err());
+    }
+
+    #[test]
+    fn parses_hook_commands() {
+        let value: Value = serde_json::json!({
+            "pre_add_cmd": "echo %name%",
+            "post_delete_cmd": "pnpm codegen"
+        });
+        let mut warnings = Vec::new();
+        let parsed = parse_local_value(
+            value,
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(parsed.hooks.pre_add.as_deref(), Some("echo %name%"));
+        assert_eq!(parsed.hooks.post_add, None);
+        assert_eq!(parsed.hooks.post_delete.as_deref(), Some("pnpm codegen"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parses_tui_behavior_toggles_in_both_configs() {
+        let value: Value = serde_json::json!({
+            "confirm_delete": false,
+            "auto_open_after_add": true
+        });
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            value.clone(),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.confirm_delete, Some(false));
+        assert_eq!(local.auto_open_after_add, Some(true));
+        let global =
+            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+        assert_eq!(global.confirm_delete, Some(false));
+        assert!(warnings.is_empty());
+
+        let error = parse_local_value(
+            serde_json::json!({ "confirm_delete": "no" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("confirm_delete"));
+    }
+
+    #[test]
+    fn parses_sanitize_svg_as_local_only_key() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "sanitize_svg": false }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.sanitize_svg, Some(false));
+        assert!(warnings.is_empty());
+
+        parse_global_value(
+            serde_json::json!({ "sanitize_svg": false }),
+            Path::new("/tmp/iconmate.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert!(warnings[0].contains("sanitize_svg"), "{warnings:?}");
+    }
+
+    #[test]
+    fn parses_svg_format_indent_and_line_endings() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "svg_format": "pretty", "svg_indent": 4 }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.svg_format, Some(SvgFormat::Pretty));
+        assert_eq!(local.svg_indent, Some(4));
+        assert_eq!(local.line_endings, None);
+
+        for (value, key) in [
+            (serde_json::json!({ "svg_format": "compact" }), "svg_format"),
+            (serde_json::json!({ "svg_indent": -1 }), "svg_indent"),
+            (serde_json::json!({ "svg_indent": 12 }), "svg_indent"),
+            (serde_json::json!({ "line_endings": "cr" }), "line_endings"),
+        ] {
+            let error = parse_local_value(
+                value,
+                Path::new("/tmp/iconmate.config.jsonc"),
+                &mut warnings,
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains(key), "{error}");
+        }
+    }
+
+    #[test]
+    fn parses_max_icon_size_and_on_oversize() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "max_icon_size": "16KB", "on_oversize": "warn" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.max_icon_size, Some(16 * 1024));
+        assert_eq!(local.on_oversize, Some(OversizeAction::Warn));
+
+        for (value, key) in [
+            (serde_json::json!({ "max_icon_size": 0 }), "max_icon_size"),
+            (
+                serde_json::json!({ "max_icon_size": "big" }),
+                "max_icon_size",
+            ),
+            (serde_json::json!({ "on_oversize": "skip" }), "on_oversize"),
+        ] {
+            let error = parse_local_value(
+                value,
+                Path::new("/tmp/iconmate.config.jsonc"),
+                &mut warnings,
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains(key), "{error}");
+        }
+    }
+
+    #[test]
+    fn parses_filename_case() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "filename_case": "snake" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.filename_case, Some(FilenameCase::Snake));
+
+        let error = parse_local_value(
+            serde_json::json!({ "filename_case": "title" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("key 'filename_case' must be one of")
+        );
+    }
+
+    #[test]
+    fn parses_index_strategy() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "index_strategy": "rust" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(local.index_strategy, Some(IndexStrategy::Rust));
+
+        let error = parse_local_value(
+            serde_json::json!({ "index_strategy": "go" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("key 'index_strategy' must be one of [ts, js, dart, rust]")
+        );
+    }
+
+    #[test]
+    fn parses_usage_snippets_per_preset() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({
+                "usage_snippets": { "react": "<Icon name=\"%filename|kebab%\" />", "angular": "x" }
+            }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(
+            local.usage_snippets,
+            [(
+                "react".to_string(),
+                "<Icon name=\"%filename|kebab%\" />".to_string()
+            )]
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unknown preset 'angular'"));
+
+        let error = parse_local_value(
+            serde_json::json!({ "usage_snippets": { "react": "" } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("usage_snippets.react must be a non-empty string")
+        );
+    }
+
+    #[test]
+    fn parses_code_style_object() {
+        let mut warnings = Vec::new();
+        let local = parse_local_value(
+            serde_json::json!({ "code_style": { "quotes": "double", "indent": 4, "tabs": true } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(
+            local.code_style,
+            CodeStyle {
+                quotes: Some(Quote::Double),
+                semicolons: None,
+                indent: Some(4),
+            }
+        );
+        assert!(warnings.iter().any(|warning| warning.contains("'tabs'")));
+
+        for value in [
+            serde_json::json!({ "code_style": "prettier" }),
+            serde_json::json!({ "code_style": { "quotes": "backtick" } }),
+            serde_json::json!({ "code_style": { "semicolons": "no" } }),
+            serde_json::json!({ "code_style": { "indent": 3 } }),
+        ] {
+            let error = parse_local_value(
+                value,
+                Path::new("/tmp/iconmate.config.jsonc"),
+                &mut warnings,
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains("code_style"), "{error}");
+        }
+    }
+
+    #[test]
+    fn parses_alias_style_keys_allowing_empty_prefix() {
+        let value: Value = serde_json::json!({
+            "alias_case": "pascal",
+            "alias_prefix": "",
+            "alias_suffix": "Icon",
+            "alias_collection_prefix": true
+        });
+        let mut warnings = Vec::new();
+        let parsed = parse_local_value(
+            value,
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(parsed.alias_case, Some(AliasCase::Pascal));
+        assert_eq!(parsed.alias_prefix.as_deref(), Some(""));
+        assert_eq!(parsed.alias_suffix.as_deref(), Some("Icon"));
+        assert_eq!(parsed.alias_collection_prefix, Some(true));
+        assert!(warnings.is_empty());
+
+        let error = parse_local_value(
+            serde_json::json!({ "alias_case": "snake" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect_err("unknown alias case should fail validation");
+        assert!(
+            error
+                .to_string()
+                .contains("key 'alias_case' must be one of")
+        );
+    }
+
+    #[test]
+    fn parses_theme_and_palette_overrides() {
+        let value: Value = serde_json::json!({
+            "theme": "light",
+            "palette": { "accent": "#ff00aa", "bogus": "red" }
+        });
+        let mut warnings = Vec::new();
+        let parsed =
+            parse_global_value(value, Path::new("/tmp/iconmate.jsonc"), &mut warnings).unwrap();
+
+        assert_eq!(parsed.theme.as_deref(), Some("light"));
+        assert_eq!(
+            parsed.palette,
+            vec![("accent".to_string(), "#ff00aa".to_string())]
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unknown palette key 'bogus'"));
+    }
+
+    #[test]
+    fn rejects_unknown_theme_and_invalid_palette_colors() {
+        let mut warnings = Vec::new();
+        let error = parse_local_value(
+            serde_json::json!({ "theme": "solarized" }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect_err("unknown theme should fail validation");
+        assert!(error.to_string().contains("key 'theme' must be one of"));
+
+        let error = parse_local_value(
+            serde_json::json!({ "palette": { "text": "not-a-color" } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect_err("invalid color should fail validation");
+        assert!(error.to_string().contains("palette.text must be a color"));
+    }
+
+    #[test]
+    fn parses_preview_colors() {
+        let mut warnings = Vec::new();
+        let parsed = parse_global_value(
+            serde_json::json!({ "preview_colors": { "foreground": "#fff", "tint": "#000" } }),
+            Path::new("/tmp/iconmate.jsonc"),
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.preview_colors,
+            PreviewColors {
+                foreground: Some(Rgb(255, 255, 255)),
+                background: None,
+            }
+        );
+        assert!(warnings[0].contains("unknown preview_colors key 'tint'"));
+
+        let error = parse_local_value(
+            serde_json::json!({ "preview_colors": { "background": "navy" } }),
+            Path::new("/tmp/iconmate.config.jsonc"),
+            &mut warnings,
+        )
+        .expect_err("named colors should fail validation");
+        assert!(
+            error
+                .to_string()
+                .contains("preview_colors.background must be a hex color")
+        );
     }
 
     #[test]
@@ -0,0 +1,218 @@
+// TOML and YAML frontends for config files.
+//
+// Both parse into the same `serde_json::Value` the JSON/JSONC loader produces,
+// so validation in `config.rs` stays format-agnostic. Parsing is the `toml`
+// and `serde_yaml` crates'; this module only maps their data model onto JSON:
+//
+// - TOML datetimes become their RFC 3339 string.
+// - YAML anchors and aliases resolve, `<<` merge keys apply, and an empty
+//   document is an empty config. Keys must be strings; tags are dropped.
+
+use serde_json::{Map, Value};
+
+pub fn parse_toml(raw: &str) -> anyhow::Result<Value> {
+    let table: toml::Table = toml::from_str(raw)?;
+    Ok(toml_to_json(toml::Value::Table(table)))
+}
+
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(string) => Value::String(string),
+        toml::Value::Integer(integer) => Value::from(integer),
+        toml::Value::Float(float) => Value::from(float),
+        toml::Value::Boolean(boolean) => Value::Bool(boolean),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+pub fn parse_yaml(raw: &str) -> anyhow::Result<Value> {
+    let mut value: serde_yaml::Value = serde_yaml::from_str(raw)?;
+    value.apply_merge()?;
+    match yaml_to_json(value)? {
+        Value::Null => Ok(Value::Object(Map::new())),
+        value => Ok(value),
+    }
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> anyhow::Result<Value> {
+    Ok(match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(boolean) => Value::Bool(boolean),
+        serde_yaml::Value::Number(number) => serde_json::to_value(number)?,
+        serde_yaml::Value::String(string) => Value::String(string),
+        serde_yaml::Value::Sequence(items) => Value::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut object = Map::new();
+            for (key, value) in mapping {
+                let serde_yaml::Value::String(key) = key else {
+                    anyhow::bail!("mapping keys must be strings, got {key:?}");
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_toml_config() {
+        let value = parse_toml(
+            r##"
+# iconmate.toml
+folder = "src/icons" # trailing comment
+preset = 'react'
+alias_collection_prefix = true
+extends = [
+  "../base.toml",
+  "@acme/icons",
+]
+hooks.retries = 3
+
+[palette]
+accent = "#f472b6"
+"row_highlight_bg" = "#f472b6"
+"##,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "folder": "src/icons",
+                "preset": "react",
+                "alias_collection_prefix": true,
+                "extends": ["../base.toml", "@acme/icons"],
+                "hooks": { "retries": 3 },
+                "palette": { "accent": "#f472b6", "row_highlight_bg": "#f472b6" }
+            })
+        );
+        assert!(parse_toml("folder = \"a\"\nfolder = \"b\"").is_err());
+        assert!(parse_toml("folder = unquoted").is_err());
+    }
+
+    #[test]
+    fn parses_toml_arrays_of_tables_and_multiline_strings() {
+        let value = parse_toml(
+            r#"
+index_header = """
+// Generated by iconmate.
+// Do not edit."""
+
+[[workspace.folders]]
+path = "apps/web/icons"
+
+[[workspace.folders]]
+path = "apps/docs/icons"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "index_header": "// Generated by iconmate.\n// Do not edit.",
+                "workspace": {
+                    "folders": [{ "path": "apps/web/icons" }, { "path": "apps/docs/icons" }]
+                }
+            })
+        );
+        let error = parse_toml("[palette]\naccent = \"red\"\n[palette]\n").unwrap_err();
+        assert!(error.to_string().contains("palette"));
+    }
+
+    #[test]
+    fn parses_yaml_config() {
+        let value = parse_yaml(
+            r##"
+---
+# iconmate.yaml
+folder: src/icons
+preset: "react"   # comment
+svg_view_cmd: 'code %filename%'
+alias_prefix: ""
+alias_collection_prefix: true
+extends:
+  - ../base.yaml
+  - '@acme/icons'
+palette:
+  accent: "#f472b6"
+  error: red
+tags: [a, "b c"]
+"##,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "folder": "src/icons",
+                "preset": "react",
+                "svg_view_cmd": "code %filename%",
+                "alias_prefix": "",
+                "alias_collection_prefix": true,
+                "extends": ["../base.yaml", "@acme/icons"],
+                "palette": { "accent": "#f472b6", "error": "red" },
+                "tags": ["a", "b c"]
+            })
+        );
+        assert_eq!(parse_yaml("# empty\n").unwrap(), serde_json::json!({}));
+        assert!(parse_yaml("folder: a\n  nested: b").is_err());
+    }
+
+    #[test]
+    fn parses_yaml_block_scalars_anchors_and_sequences_of_mappings() {
+        let value = parse_yaml(
+            r##"
+index_header: |
+  // Generated by iconmate.
+  // Do not edit.
+usage_snippets:
+  react: >
+    <%name% className="icon" />
+colors: &colors
+  accent: "#f472b6"
+palette:
+  <<: *colors
+  error: red
+preview_colors: *colors
+folders:
+  - path: apps/web/icons
+    preset: react
+  - path: apps/docs/icons
+"##,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "index_header": "// Generated by iconmate.\n// Do not edit.\n",
+                "usage_snippets": { "react": "<%name% className=\"icon\" />\n" },
+                "colors": { "accent": "#f472b6" },
+                "palette": { "accent": "#f472b6", "error": "red" },
+                "preview_colors": { "accent": "#f472b6" },
+                "folders": [
+                    { "path": "apps/web/icons", "preset": "react" },
+                    { "path": "apps/docs/icons" }
+                ]
+            })
+        );
+
+        let error = parse_yaml("? [a, b]\n: c\n").unwrap_err();
+        assert!(error.to_string().contains("mapping keys must be strings"));
+        assert!(parse_yaml("palette: *missing\n").is_err());
+    }
+}
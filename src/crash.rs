@@ -0,0 +1,158 @@
+// Leaving the terminal usable when the TUI dies.
+//
+// The TUI runs in raw mode on the alternate screen. A panic unwinds through
+// `tui::run`'s cleanup guard only after the default hook has printed the
+// message onto the alternate screen, where it vanishes, and a panic in a
+// background task or a SIGTERM / SIGHUP never reaches the guard at all. So
+// while the TUI runs, the panic hook restores the terminal first, then prints
+// the panic, then exits: a TUI left in a cooked terminal can't recover. Unix
+// termination signals restore and exit the same way. Both last only as long
+// as the TUI: `tui::run`'s guard puts the previous hook back and stops the
+// signal watchers.
+//
+// `ICONMATE_CRASH_REPORT=<path>` also writes a report (version, platform,
+// arguments, panic message and backtrace) to that file for bug reports.
+
+use std::backtrace::Backtrace;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub const CRASH_REPORT_ENV: &str = "ICONMATE_CRASH_REPORT";
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static;
+
+/// The installed hook and signal watchers; dropping it puts back the hook
+/// that was there before and stops watching signals.
+pub struct Installed {
+    previous: Arc<PanicHook>,
+    #[cfg(unix)]
+    signals: Vec<tokio::task::JoinHandle<()>>,
+}
+
+/// Installs the panic hook and signal handlers until the returned guard
+/// drops; `restore` puts the terminal back.
+pub fn install(restore: fn()) -> Installed {
+    let previous: Arc<PanicHook> = Arc::from(std::panic::take_hook());
+    let chained = Arc::clone(&previous);
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        chained(info);
+
+        if let Some(path) = report_path() {
+            let message = panic_message(info.payload());
+            let location = info
+                .location()
+                .map(|location| location.to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            let report = render_report(&message, &location, &Backtrace::force_capture());
+            match std::fs::write(&path, report) {
+                Ok(()) => eprintln!("Crash report written to {}", path.display()),
+                Err(error) => {
+                    eprintln!("Could not write crash report {}: {error}", path.display())
+                }
+            }
+        }
+        std::process::exit(101);
+    }));
+
+    Installed {
+        previous,
+        #[cfg(unix)]
+        signals: watch_signals(restore),
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        for task in &self.signals {
+            task.abort();
+        }
+        // `set_hook` panics on a panicking thread; the hook exits before
+        // anything unwinds this far, so that's only a foreign hook's panic.
+        if !std::thread::panicking() {
+            let previous = Arc::clone(&self.previous);
+            std::panic::set_hook(Box::new(move |info| previous(info)));
+        }
+    }
+}
+
+#[cfg(unix)]
+fn watch_signals(restore: fn()) -> Vec<tokio::task::JoinHandle<()>> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    // Outside a runtime (tests) there's nothing to watch with.
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return Vec::new();
+    };
+    let mut tasks = Vec::new();
+    for (kind, code) in [
+        (SignalKind::terminate(), 15),
+        (SignalKind::hangup(), 1),
+        (SignalKind::quit(), 3),
+    ] {
+        let Ok(mut stream) = signal(kind) else {
+            continue;
+        };
+        tasks.push(handle.spawn(async move {
+            if stream.recv().await.is_some() {
+                restore();
+                std::process::exit(128 + code);
+            }
+        }));
+    }
+    tasks
+}
+
+fn report_path() -> Option<PathBuf> {
+    std::env::var_os(CRASH_REPORT_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+fn render_report(message: &str, location: &str, backtrace: &Backtrace) -> String {
+    let args = std::env::args().collect::<Vec<_>>().join(" ");
+    let cwd = std::env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    format!(
+        "iconmate {} crash report\n\nPlatform: {}-{}\nArguments: {args}\nWorking directory: {cwd}\n\nPanic: {message}\nLocation: {location}\n\nBacktrace:\n{backtrace}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_names_the_panic_and_where_it_happened() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new(String::from("index out of bounds"));
+        let message = panic_message(payload.as_ref());
+        let report = render_report(
+            &message,
+            "src/views/add_popup.rs:42:9",
+            &Backtrace::disabled(),
+        );
+
+        assert!(report.starts_with(concat!(
+            "iconmate ",
+            env!("CARGO_PKG_VERSION"),
+            " crash report\n"
+        )));
+        assert!(report.contains("\nPanic: index out of bounds\n"));
+        assert!(report.contains("\nLocation: src/views/add_popup.rs:42:9\n"));
+        assert_eq!(panic_message(&"static str"), "static str");
+    }
+}
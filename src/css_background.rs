@@ -0,0 +1,104 @@
+// Background-image stylesheet (`iconmate css`).
+//
+// For codebases that can't inline SVG (server templates, old jQuery UIs,
+// email), writes an `icons-bg.css` next to the icons with one
+// `.icon-heart { background-image: url("data:...") }` class per SVG export.
+// Unlike the css preset's mask classes, the icon keeps its own colors. Once
+// the file exists, add, delete, rename, replace and sync keep it up to date.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::css_mask::collect_svg_rules;
+use crate::groups::icons_with_groups;
+use crate::utils::svg_to_data_uri;
+
+pub const CSS_BACKGROUND_STYLESHEET: &str = "icons-bg.css";
+
+const GENERATED_HEADER: &str = "/* GENERATED by iconmate — do not edit by hand. */";
+
+/// Renders the stylesheet from `(class_name, svg_contents)` pairs.
+pub fn render_background_stylesheet(rules: &[(String, String)]) -> String {
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push('\n');
+
+    for (class_name, svg) in rules {
+        out.push('\n');
+        out.push_str(&format!(".{class_name} {{\n"));
+        out.push_str("  display: inline-block;\n");
+        out.push_str("  width: 1em;\n");
+        out.push_str("  height: 1em;\n");
+        out.push_str(&format!(
+            "  background-image: url(\"{}\");\n",
+            svg_to_data_uri(svg)
+        ));
+        out.push_str("  background-repeat: no-repeat;\n");
+        out.push_str("  background-position: center;\n");
+        out.push_str("  background-size: contain;\n");
+        out.push_str("}\n");
+    }
+
+    out
+}
+
+/// Rebuilds `<folder>/icons-bg.css` from every `.svg` export of the folder's
+/// barrel, groups included.
+pub fn write_background_stylesheet(folder: &Path) -> anyhow::Result<PathBuf> {
+    let entries = icons_with_groups(folder).unwrap_or_default();
+    let rules = collect_svg_rules(folder, &entries)?;
+
+    let stylesheet_path = folder.join(CSS_BACKGROUND_STYLESHEET);
+    std::fs::write(&stylesheet_path, render_background_stylesheet(&rules))
+        .with_context(|| format!("Failed to write {}", stylesheet_path.display()))?;
+    Ok(stylesheet_path)
+}
+
+/// Regenerates the stylesheet only if the folder already has one.
+pub fn refresh_background_stylesheet_if_present(folder: &Path) -> anyhow::Result<()> {
+    if folder.join(CSS_BACKGROUND_STYLESHEET).exists() {
+        write_background_stylesheet(folder)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_background_classes_and_refreshes_only_when_present() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("heart.svg"), "<svg></svg>").unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
+
+        refresh_background_stylesheet_if_present(dir.path()).unwrap();
+        assert!(!dir.path().join(CSS_BACKGROUND_STYLESHEET).exists());
+
+        let path = write_background_stylesheet(dir.path()).unwrap();
+        let css = std::fs::read_to_string(&path).unwrap();
+        assert!(css.starts_with(GENERATED_HEADER));
+        assert!(css.contains(
+            ".icon-heart {\n  display: inline-block;\n  width: 1em;\n  height: 1em;\n  background-image: url(\"data:image/svg+xml,%3Csvg%3E%3C/svg%3E\");\n"
+        ));
+        assert!(!css.contains("mask-image"));
+
+        std::fs::write(dir.path().join("star.svg"), "<svg/>").unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star.svg';\n",
+        )
+        .unwrap();
+        refresh_background_stylesheet_if_present(dir.path()).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains(".icon-star {")
+        );
+    }
+}
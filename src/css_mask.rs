@@ -0,0 +1,133 @@
+// CSS mask preset support.
+//
+// The `css` preset writes plain SVGs (tracked in `index.ts` like `normal`)
+// and regenerates an `icons.css` stylesheet next to them. Every SVG export
+// gets a class keyed by its alias (`IconHeart` -> `.icon-heart`) that paints
+// `currentColor` through a `mask-image` data URI, for teams that render icons
+// purely via CSS classes.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::utils::{IconEntry, get_existing_icons, svg_to_data_uri};
+
+pub const CSS_MASK_STYLESHEET: &str = "icons.css";
+
+const GENERATED_HEADER: &str = "/* GENERATED by iconmate — do not edit by hand. */";
+
+/// `IconArrowLeft` -> `icon-arrow-left`.
+pub fn class_name_for_alias(alias: &str) -> String {
+    let mut out = String::with_capacity(alias.len() + 4);
+    let mut prev_lower_or_digit = false;
+    for ch in alias.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() && prev_lower_or_digit {
+                out.push('-');
+            }
+            out.push(ch.to_ascii_lowercase());
+            prev_lower_or_digit = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+        } else {
+            if !out.is_empty() && !out.ends_with('-') {
+                out.push('-');
+            }
+            prev_lower_or_digit = false;
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+/// Renders the stylesheet from `(class_name, svg_contents)` pairs.
+pub fn render_mask_stylesheet(rules: &[(String, String)]) -> String {
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push('\n');
+
+    for (class_name, svg) in rules {
+        let uri = svg_to_data_uri(svg);
+        out.push('\n');
+        out.push_str(&format!(".{class_name} {{\n"));
+        out.push_str("  display: inline-block;\n");
+        out.push_str("  width: 1em;\n");
+        out.push_str("  height: 1em;\n");
+        out.push_str("  background-color: currentColor;\n");
+        out.push_str(&format!("  -webkit-mask-image: url(\"{uri}\");\n"));
+        out.push_str(&format!("  mask-image: url(\"{uri}\");\n"));
+        out.push_str("  -webkit-mask-repeat: no-repeat;\n");
+        out.push_str("  mask-repeat: no-repeat;\n");
+        out.push_str("  -webkit-mask-size: 100% 100%;\n");
+        out.push_str("  mask-size: 100% 100%;\n");
+        out.push_str("}\n");
+    }
+
+    out
+}
+
+/// Rebuilds `<folder>/icons.css` from every `.svg` export in `<folder>/index.ts`.
+/// Entries whose file is missing on disk are skipped.
+pub fn write_mask_stylesheet(folder: &Path) -> anyhow::Result<PathBuf> {
+    let folder_str = folder.to_string_lossy();
+    let entries = get_existing_icons(&folder_str).unwrap_or_default();
+    let rules = collect_svg_rules(folder, &entries)?;
+
+    let stylesheet_path = folder.join(CSS_MASK_STYLESHEET);
+    std::fs::write(&stylesheet_path, render_mask_stylesheet(&rules))
+        .with_context(|| format!("Failed to write {}", stylesheet_path.display()))?;
+    Ok(stylesheet_path)
+}
+
+/// `(class_name, svg_contents)` for every `.svg` entry on disk, sorted by
+/// class. Shared with the background-image stylesheet.
+pub fn collect_svg_rules(
+    folder: &Path,
+    entries: &[IconEntry],
+) -> anyhow::Result<Vec<(String, String)>> {
+    let mut rules = Vec::new();
+    for entry in entries {
+        if !entry.file_path.ends_with(".svg") {
+            continue;
+        }
+        let path = folder.join(entry.file_path.trim_start_matches("./"));
+        if !path.exists() {
+            continue;
+        }
+        let svg = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        rules.push((class_name_for_alias(&entry.name), svg));
+    }
+    rules.sort_by(|a, b| a.0.cmp(&b.0));
+    rules.dedup_by(|a, b| a.0 == b.0);
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_name_is_kebab_case_of_alias() {
+        assert_eq!(class_name_for_alias("IconHeart"), "icon-heart");
+        assert_eq!(class_name_for_alias("IconArrowLeft"), "icon-arrow-left");
+        assert_eq!(class_name_for_alias("IconH1Bold"), "icon-h1-bold");
+    }
+
+    #[test]
+    fn writes_stylesheet_for_svg_exports_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("heart.svg"), "<svg></svg>").unwrap();
+        std::fs::write(dir.path().join("star.tsx"), "export default 1").unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star';\n",
+        )
+        .unwrap();
+
+        let path = write_mask_stylesheet(dir.path()).unwrap();
+        let css = std::fs::read_to_string(path).unwrap();
+
+        assert!(css.starts_with(GENERATED_HEADER));
+        assert!(css.contains(".icon-heart {"));
+        assert!(css.contains("mask-image: url(\"data:image/svg+xml,%3Csvg%3E%3C/svg%3E\");"));
+        assert!(!css.contains("icon-star"));
+    }
+}
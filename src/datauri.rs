@@ -0,0 +1,264 @@
+// Data URI preset support.
+//
+// The `datauri` preset writes each icon as a tiny `<stem>.ts` module whose
+// default export is the SVG encoded as a `data:image/svg+xml` URI, and keeps
+// the usual `index.ts` export. It also regenerates a self-contained
+// `icons.ts` with one named constant per icon, for emails, canvas rendering,
+// and other places where components can't be used.
+//
+// Going the other way, `decode_svg_data_uri` reads `data:image/svg+xml` URIs
+// (percent-encoded or base64) given as an icon source, the form browser
+// devtools and some design tools copy icons in.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::utils::{IconEntry, get_existing_icons, icon_file_path, svg_to_data_uri};
+
+pub const DATAURI_BUNDLE: &str = "icons.ts";
+
+const GENERATED_HEADER: &str = "// GENERATED by iconmate — do not edit by hand.";
+const MODULE_PREFIX: &str = "export default ";
+
+/// Strips the XML prolog and collapses whitespace between tags. Comments are
+/// already removed by the add flow.
+pub fn optimize_svg(svg: &str) -> String {
+    let mut content = svg.trim();
+    if content.starts_with("<?xml")
+        && let Some(end) = content.find("?>")
+    {
+        content = content[end + 2..].trim_start();
+    }
+    let re = regex::Regex::new(r">\s+<").unwrap();
+    re.replace_all(content, "><").into_owned()
+}
+
+/// Renders the per-icon module: `export default "data:image/svg+xml,...";`
+pub fn render_datauri_module(svg: &str) -> String {
+    let uri = svg_to_data_uri(&optimize_svg(svg));
+    let literal = serde_json::to_string(&uri).expect("string serialization cannot fail");
+    format!("{MODULE_PREFIX}{literal};\n")
+}
+
+/// Reads the data URI back out of a module written by `render_datauri_module`.
+pub fn parse_datauri_module(contents: &str) -> Option<String> {
+    let literal = contents
+        .trim()
+        .strip_prefix(MODULE_PREFIX)?
+        .trim_end_matches(';');
+    serde_json::from_str::<String>(literal).ok()
+}
+
+/// Renders the combined bundle from `(identifier, data_uri)` pairs.
+pub fn render_datauri_bundle(entries: &[(String, String)]) -> String {
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push('\n');
+    if !entries.is_empty() {
+        out.push('\n');
+    }
+    for (identifier, uri) in entries {
+        let literal = serde_json::to_string(uri).expect("string serialization cannot fail");
+        out.push_str(&format!("export const {identifier} = {literal};\n"));
+    }
+    out
+}
+
+/// Rebuilds `<folder>/icons.ts` from every data URI module exported in
+/// `<folder>/index.ts`. Exports that point at other file types are skipped.
+pub fn write_datauri_bundle(folder: &Path) -> anyhow::Result<PathBuf> {
+    let folder_str = folder.to_string_lossy();
+    let entries = get_existing_icons(&folder_str).unwrap_or_default();
+    let bundle = collect_bundle_entries(folder, &entries)?;
+
+    let bundle_path = folder.join(DATAURI_BUNDLE);
+    std::fs::write(&bundle_path, render_datauri_bundle(&bundle))
+        .with_context(|| format!("Failed to write {}", bundle_path.display()))?;
+    Ok(bundle_path)
+}
+
+fn collect_bundle_entries(
+    folder: &Path,
+    entries: &[IconEntry],
+) -> anyhow::Result<Vec<(String, String)>> {
+    let mut out = Vec::new();
+    for entry in entries {
+        let path = icon_file_path(folder, &entry.file_path);
+        if path.extension().and_then(|ext| ext.to_str()) != Some("ts") || !path.exists() {
+            continue;
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if let Some(uri) = parse_datauri_module(&contents) {
+            out.push((entry.name.clone(), uri));
+        }
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out.dedup_by(|a, b| a.0 == b.0);
+    Ok(out)
+}
+
+/// The SVG document inside a `data:image/svg+xml[;charset=...][;base64],`
+/// URI.
+pub fn decode_svg_data_uri(uri: &str) -> anyhow::Result<String> {
+    let uri = uri.trim();
+    let rest = uri
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+        .map(|_| &uri[5..])
+        .ok_or_else(|| anyhow::anyhow!("Not a data URI"))?;
+    let (meta, payload) = rest
+        .split_once(',')
+        .ok_or_else(|| anyhow::anyhow!("Data URI has no ',' before its payload"))?;
+
+    let mut params = meta.split(';').map(str::trim);
+    let media_type = params.next().unwrap_or_default();
+    if !media_type.eq_ignore_ascii_case("image/svg+xml") {
+        anyhow::bail!(
+            "Only image/svg+xml data URIs are supported, got '{}'",
+            if media_type.is_empty() {
+                "text/plain"
+            } else {
+                media_type
+            }
+        );
+    }
+    let is_base64 = params.any(|param| param.eq_ignore_ascii_case("base64"));
+
+    let bytes = if is_base64 {
+        decode_base64(&percent_decode(payload))
+            .ok_or_else(|| anyhow::anyhow!("Data URI has invalid base64 content"))?
+    } else {
+        percent_decode(payload)
+    };
+    let svg = String::from_utf8(bytes).context("Data URI content is not valid UTF-8")?;
+    if !svg.contains("<svg") {
+        anyhow::bail!("Data URI does not contain an <svg> element");
+    }
+    Ok(svg)
+}
+
+fn percent_decode(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(byte) = value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            index += 3;
+        } else {
+            out.push(bytes[index]);
+            index += 1;
+        }
+    }
+    out
+}
+
+/// Standard or URL-safe alphabet; padding optional, whitespace ignored.
+fn decode_base64(encoded: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in encoded {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            byte if byte.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimizes_prolog_and_inter_tag_whitespace() {
+        let svg = "<?xml version=\"1.0\"?>\n<svg>\n  <path d=\"M0 0\"/>\n</svg>\n";
+        assert_eq!(optimize_svg(svg), "<svg><path d=\"M0 0\"/></svg>");
+    }
+
+    #[test]
+    fn module_round_trips_data_uri() {
+        let module = render_datauri_module("<svg fill=\"#000\"></svg>");
+        assert_eq!(
+            module,
+            "export default \"data:image/svg+xml,%3Csvg fill='%23000'%3E%3C/svg%3E\";\n"
+        );
+        assert_eq!(
+            parse_datauri_module(&module).as_deref(),
+            Some("data:image/svg+xml,%3Csvg fill='%23000'%3E%3C/svg%3E")
+        );
+    }
+
+    #[test]
+    fn decodes_base64_and_percent_encoded_svg_data_uris() {
+        let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg>";
+        assert_eq!(
+            decode_svg_data_uri(
+                "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciPjxwYXRoIGQ9Ik0wIDAiLz48L3N2Zz4="
+            )
+            .unwrap(),
+            svg
+        );
+        assert_eq!(
+            decode_svg_data_uri(
+                "DATA:image/svg+xml;charset=utf-8,%3Csvg xmlns=%22http://www.w3.org/2000/svg%22%3E%3Cpath d=%22M0 0%22/%3E%3C/svg%3E"
+            )
+            .unwrap(),
+            svg
+        );
+        // What `render_datauri_module` writes reads back too.
+        let uri = svg_to_data_uri("<svg fill=\"#000\"></svg>");
+        assert_eq!(
+            decode_svg_data_uri(&uri).unwrap(),
+            "<svg fill='#000'></svg>"
+        );
+
+        assert!(decode_svg_data_uri("data:image/png;base64,iVBORw0KGgo=").is_err());
+        assert!(decode_svg_data_uri("data:image/svg+xml;base64,!!!").is_err());
+    }
+
+    #[test]
+    fn writes_bundle_for_datauri_exports_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("heart.ts"),
+            render_datauri_module("<svg></svg>"),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("star.svg"), "<svg></svg>").unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart';\nexport { default as IconStar } from './star.svg';\n",
+        )
+        .unwrap();
+
+        let path = write_datauri_bundle(dir.path()).unwrap();
+        let bundle = std::fs::read_to_string(path).unwrap();
+
+        assert_eq!(
+            bundle,
+            format!(
+                "{GENERATED_HEADER}\n\nexport const IconHeart = \"data:image/svg+xml,%3Csvg%3E%3C/svg%3E\";\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,470 @@
+// `iconmate dedupe`: merge icons that are the same artwork under different
+// names.
+//
+// Two files are duplicates when their bytes match, or when they have the same
+// extension, `viewBox` and `d` path data (so `IconHeart` and an `IconLove`
+// component that only differs in its name still match). In each group the
+// first export in the barrel (`index.ts`, or whichever `find_barrel` picks) is
+// kept as the canonical icon, unless `--keep` picks another. Every other
+// export is repointed at the canonical file through the barrel's
+// `IndexWriter`, so existing imports of `IconLove` keep working, and a
+// redundant file is deleted once no export points at it. Dry-run by default:
+// the plan is a diff of the barrel plus the files that would go.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::index_writer::IndexWriter;
+use crate::utils::{IconEntry, icon_file_path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Bytes,
+    PathData,
+}
+
+impl MatchKind {
+    fn label(self) -> &'static str {
+        match self {
+            MatchKind::Bytes => "identical bytes",
+            MatchKind::PathData => "same path data",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub kind: MatchKind,
+    /// Export whose file is kept.
+    pub canonical: IconEntry,
+    /// Folder-relative path of the kept file, e.g. `heart.svg`.
+    pub canonical_file: String,
+    /// Exports repointed at the canonical file.
+    pub duplicates: Vec<IconEntry>,
+    /// Folder-relative files deleted once nothing imports them.
+    pub redundant_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineRewrite {
+    /// 1-based line in the barrel.
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DedupePlan {
+    pub index_location: String,
+    pub groups: Vec<DuplicateGroup>,
+    pub rewrites: Vec<LineRewrite>,
+    /// The barrel with every rewrite applied.
+    pub updated_index: String,
+}
+
+impl DedupePlan {
+    pub fn is_clean(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn redundant_files(&self) -> impl Iterator<Item = &String> {
+        self.groups.iter().flat_map(|group| &group.redundant_files)
+    }
+}
+
+struct IconFile {
+    /// Folder-relative path, `/`-separated.
+    relative: String,
+    /// Exports of this file, in barrel order.
+    entries: Vec<IconEntry>,
+    bytes: Vec<u8>,
+}
+
+/// Groups the files the barrel exports by content. `keep` names exports that
+/// should win their group instead of the first one.
+pub fn compute_dedupe_plan(folder: &Path, keep: &[String]) -> anyhow::Result<DedupePlan> {
+    let (writer, index_path) = crate::index_writer::find_barrel(folder);
+    let contents = fs::read_to_string(&index_path)?;
+    let entries = writer.parse(&contents);
+
+    let mut files: Vec<IconFile> = Vec::new();
+    for entry in entries {
+        let Some(relative) = folder_relative(folder, &entry) else {
+            continue;
+        };
+        if let Some(file) = files.iter_mut().find(|file| file.relative == relative) {
+            file.entries.push(entry);
+            continue;
+        }
+        let Ok(bytes) = fs::read(folder.join(&relative)) else {
+            continue;
+        };
+        files.push(IconFile {
+            relative,
+            entries: vec![entry],
+            bytes,
+        });
+    }
+
+    for name in keep {
+        if !files
+            .iter()
+            .any(|file| file.entries.iter().any(|entry| &entry.name == name))
+        {
+            anyhow::bail!(
+                "--keep {name}: no export named {name} in {}.",
+                writer.file_name()
+            );
+        }
+    }
+
+    let mut by_key: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut key_order = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let key = content_key(file);
+        let slot = by_key.entry(key.clone()).or_default();
+        if slot.is_empty() {
+            key_order.push(key);
+        }
+        slot.push(index);
+    }
+
+    let mut groups = Vec::new();
+    for key in key_order {
+        let members = &by_key[&key];
+        if members.len() < 2 {
+            continue;
+        }
+        let kept = members
+            .iter()
+            .copied()
+            .find(|&index| {
+                files[index]
+                    .entries
+                    .iter()
+                    .any(|entry| keep.contains(&entry.name))
+            })
+            .unwrap_or(members[0]);
+        let canonical = &files[kept];
+        let others = members.iter().filter(|&&index| index != kept);
+        let kind = if others
+            .clone()
+            .all(|&index| files[index].bytes == canonical.bytes)
+        {
+            MatchKind::Bytes
+        } else {
+            MatchKind::PathData
+        };
+        groups.push(DuplicateGroup {
+            kind,
+            canonical: canonical.entries[0].clone(),
+            canonical_file: canonical.relative.clone(),
+            duplicates: others
+                .clone()
+                .flat_map(|&index| files[index].entries.iter().cloned())
+                .collect(),
+            redundant_files: others.map(|&index| files[index].relative.clone()).collect(),
+        });
+    }
+
+    let (rewrites, updated_index) = rewrite_index(writer, &contents, &groups);
+
+    // A file is only deleted once every export of it points elsewhere.
+    let still_exported = writer
+        .parse(&updated_index)
+        .iter()
+        .filter_map(|entry| folder_relative(folder, entry))
+        .collect::<Vec<_>>();
+    for group in &mut groups {
+        group
+            .redundant_files
+            .retain(|file| !still_exported.contains(file));
+    }
+
+    Ok(DedupePlan {
+        index_location: index_path.display().to_string(),
+        groups,
+        rewrites,
+        updated_index,
+    })
+}
+
+/// `entry`'s file relative to `folder`, `/`-separated; `None` outside it.
+fn folder_relative(folder: &Path, entry: &IconEntry) -> Option<String> {
+    icon_file_path(folder, &entry.file_path)
+        .strip_prefix(folder)
+        .ok()
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Files only group with files of the same extension: an `.svg` URL and a
+/// `.tsx` component aren't interchangeable exports.
+fn content_key(file: &IconFile) -> String {
+    let extension = Path::new(&file.relative)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("");
+    match path_data_key(&String::from_utf8_lossy(&file.bytes)) {
+        Some(key) => format!("{extension}\npaths\n{key}"),
+        None => format!(
+            "{extension}\nbytes\n{}",
+            crate::checksums::hex_digest(&file.bytes)
+        ),
+    }
+}
+
+/// The `viewBox` and path data of `markup`, ignoring formatting, colors and
+/// everything around the `<svg>`; `None` when it has no `d` attributes.
+pub fn path_data_key(markup: &str) -> Option<String> {
+    let paths = attribute_values(markup, "d");
+    if paths.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}\n{}",
+        attribute_values(markup, "viewBox").join("\n"),
+        paths.join("\n")
+    ))
+}
+
+/// Values of `name="..."` attributes as space-separated tokens, so
+/// `M12,21l-1-1` and `M12 21 l -1 -1` compare equal.
+fn attribute_values(markup: &str, name: &str) -> Vec<String> {
+    let attribute_re = Regex::new(&format!(r#"\s{name}\s*=\s*(?:"([^"]*)"|'([^']*)')"#))
+        .expect("valid attribute regex");
+    let token_re = Regex::new(r"[A-Za-z]|[-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?")
+        .expect("valid path token regex");
+    attribute_re
+        .captures_iter(markup)
+        .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .map(|value| {
+            token_re
+                .find_iter(value.as_str())
+                .map(|token| token.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// Repoints the exports of every redundant file at its group's canonical
+/// file. Only import paths change, so the barrel keeps its line count and
+/// the rewrites are the lines that differ.
+fn rewrite_index(
+    writer: &dyn IndexWriter,
+    contents: &str,
+    groups: &[DuplicateGroup],
+) -> (Vec<LineRewrite>, String) {
+    let mut updated = contents.to_string();
+    for group in groups {
+        for file in &group.redundant_files {
+            if let Some(next) = writer.rename(&updated, file, &group.canonical_file) {
+                updated = next;
+            }
+        }
+    }
+    let rewrites = contents
+        .lines()
+        .zip(updated.lines())
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(index, (before, after))| LineRewrite {
+            line: index + 1,
+            before: before.to_string(),
+            after: after.to_string(),
+        })
+        .collect();
+    (rewrites, updated)
+}
+
+/// Writes the rewritten barrel, deletes the redundant files (and their
+/// rasters), then refreshes the files derived from the barrel.
+pub fn apply_dedupe_plan(folder: &Path, plan: &DedupePlan) -> anyhow::Result<()> {
+    if plan.is_clean() {
+        return Ok(());
+    }
+    fs::write(&plan.index_location, &plan.updated_index)?;
+    for file in plan.redundant_files() {
+        let path = folder.join(file);
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        crate::raster::remove_raster_siblings(&path)?;
+    }
+    crate::gallery::refresh_gallery_if_present(folder, "normal", None, None)?;
+    crate::utils::refresh_derived_files(folder)?;
+    Ok(())
+}
+
+/// The plan as a diff of the barrel plus the files to delete. Colors follow
+/// `sync`: removals red, additions green.
+pub fn render_plan_text(plan: &DedupePlan, use_color: bool) -> String {
+    let green = if use_color { "\x1b[32m" } else { "" };
+    let red = if use_color { "\x1b[31m" } else { "" };
+    let bold = if use_color { "\x1b[1m" } else { "" };
+    let reset = if use_color { "\x1b[0m" } else { "" };
+
+    let mut out = String::new();
+    if plan.is_clean() {
+        out.push_str(&format!("{green}{bold}No duplicate icons.{reset}\n"));
+        return out;
+    }
+
+    for group in &plan.groups {
+        out.push_str(&format!(
+            "{bold}Keep {} ({}){reset}, {}:\n",
+            group.canonical.name,
+            group.canonical_file,
+            group.kind.label()
+        ));
+        for duplicate in &group.duplicates {
+            out.push_str(&format!(
+                "  {} ({}) -> {}\n",
+                duplicate.name, duplicate.file_path, group.canonical_file
+            ));
+        }
+    }
+
+    out.push_str(&format!("\n--- {0}\n+++ {0}\n", plan.index_location));
+    for rewrite in &plan.rewrites {
+        out.push_str(&format!("@@ line {} @@\n", rewrite.line));
+        out.push_str(&format!("{red}-{}{reset}\n", rewrite.before));
+        out.push_str(&format!("{green}+{}{reset}\n", rewrite.after));
+    }
+
+    let files = plan.redundant_files().collect::<Vec<_>>();
+    out.push_str(&format!(
+        "\n{red}{bold}Would delete ({}):{reset}\n",
+        files.len()
+    ));
+    for file in files {
+        out.push_str(&format!("  {red}- {file}{reset}\n"));
+    }
+    out.push_str("\nRun with --apply to rewrite the barrel and delete the redundant files.\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEART: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M12 21l-1-1C5 15 2 12 2 8\"/></svg>\n";
+
+    fn folder_with(files: &[(&str, &str)], index: &str) -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        for (name, contents) in files {
+            fs::write(dir.path().join(name), contents).unwrap();
+        }
+        fs::write(dir.path().join("index.ts"), index).unwrap();
+        dir
+    }
+
+    #[test]
+    fn groups_identical_files_and_repoints_their_exports() {
+        let reformatted = "<svg viewBox=\"0 0 24 24\" xmlns=\"http://www.w3.org/2000/svg\">\n  <path fill=\"red\" d=\"M12,21 l-1-1C5 15 2 12 2 8\"/>\n</svg>\n";
+        let dir = folder_with(
+            &[
+                ("heart.svg", HEART),
+                ("love.svg", HEART),
+                ("like.svg", reformatted),
+                (
+                    "star.svg",
+                    "<svg viewBox=\"0 0 24 24\"><path d=\"M0 0\"/></svg>",
+                ),
+            ],
+            "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star.svg';\nexport { default as IconLove } from './love.svg?react';\nexport { default as IconLike } from './like.svg';\n",
+        );
+
+        let plan = compute_dedupe_plan(dir.path(), &[]).unwrap();
+        assert_eq!(plan.groups.len(), 1);
+        let group = &plan.groups[0];
+        assert_eq!(group.canonical.name, "IconHeart");
+        assert_eq!(group.kind, MatchKind::PathData);
+        assert_eq!(group.redundant_files, vec!["love.svg", "like.svg"]);
+        assert_eq!(
+            plan.updated_index,
+            "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star.svg';\nexport { default as IconLove } from './heart.svg?react';\nexport { default as IconLike } from './heart.svg';\n"
+        );
+        assert_eq!(
+            plan.rewrites.iter().map(|r| r.line).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+
+        apply_dedupe_plan(dir.path(), &plan).unwrap();
+        assert!(dir.path().join("heart.svg").exists());
+        assert!(!dir.path().join("love.svg").exists());
+        assert!(!dir.path().join("like.svg").exists());
+        assert!(compute_dedupe_plan(dir.path(), &[]).unwrap().is_clean());
+    }
+
+    #[test]
+    fn repoints_wrapped_exports_before_deleting_their_file() {
+        let dir = folder_with(
+            &[("heart.svg", HEART), ("love.svg", HEART)],
+            "export { default as IconHeart } from './heart.svg';\nexport {\n  default as IconLove,\n} from './love.svg';\n",
+        );
+
+        let plan = compute_dedupe_plan(dir.path(), &[]).unwrap();
+        assert_eq!(plan.groups[0].redundant_files, vec!["love.svg"]);
+        assert_eq!(
+            plan.updated_index,
+            "export { default as IconHeart } from './heart.svg';\nexport {\n  default as IconLove,\n} from './heart.svg';\n"
+        );
+        assert_eq!(plan.rewrites.len(), 1);
+        assert_eq!(plan.rewrites[0].line, 4);
+
+        apply_dedupe_plan(dir.path(), &plan).unwrap();
+        assert!(!dir.path().join("love.svg").exists());
+        assert!(
+            fs::read_to_string(dir.path().join("index.ts"))
+                .unwrap()
+                .contains("} from './heart.svg';")
+        );
+    }
+
+    #[test]
+    fn dedupes_a_rust_barrel() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("heart.svg"), HEART).unwrap();
+        fs::write(dir.path().join("love.svg"), HEART).unwrap();
+        fs::write(
+            dir.path().join("mod.rs"),
+            "pub const HEART: &str = include_str!(\"heart.svg\");\npub const LOVE: &str = include_str!(\"love.svg\");\n",
+        )
+        .unwrap();
+
+        let plan = compute_dedupe_plan(dir.path(), &[]).unwrap();
+        apply_dedupe_plan(dir.path(), &plan).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("mod.rs")).unwrap(),
+            "pub const HEART: &str = include_str!(\"heart.svg\");\npub const LOVE: &str = include_str!(\"heart.svg\");\n"
+        );
+        assert!(!dir.path().join("love.svg").exists());
+    }
+
+    #[test]
+    fn keep_picks_the_canonical_export() {
+        let dir = folder_with(
+            &[
+                ("heart.tsx", HEART),
+                ("love.tsx", HEART),
+                ("love.svg", HEART),
+            ],
+            "export { default as IconHeart } from './heart';\nexport const IconLove = lazy(() => import('./love'));\nexport { default as IconLoveUrl } from './love.svg';\n",
+        );
+
+        let plan = compute_dedupe_plan(dir.path(), &["IconLove".to_string()]).unwrap();
+        assert_eq!(plan.groups.len(), 1);
+        assert_eq!(plan.groups[0].kind, MatchKind::Bytes);
+        assert_eq!(plan.groups[0].canonical_file, "love.tsx");
+        assert_eq!(
+            plan.rewrites[0].after,
+            "export { default as IconHeart } from './love';"
+        );
+
+        assert!(compute_dedupe_plan(dir.path(), &["IconNope".to_string()]).is_err());
+    }
+}
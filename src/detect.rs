@@ -0,0 +1,222 @@
+// Framework and bundler detection for `iconmate init` and the TUI's first-run
+// onboarding.
+//
+// Looks at the nearest `package.json` (dependencies of every kind) plus
+// `vite.config.*` / `next.config.*` next to it, and maps what it finds to a
+// preset and, for SVG loaders that work by query suffix, an `import_query`.
+// An SVG loader wins over the framework: a React app with vite-plugin-svgr
+// wants plain `.svg` files imported with `?react`, not `.tsx` components.
+// Flutter projects are recognized through `pubspec.yaml`.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::utils::{ImportQuery, Preset};
+
+const VITE_CONFIGS: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.mts",
+    "vite.config.js",
+    "vite.config.mjs",
+    "vite.config.cjs",
+];
+const NEXT_CONFIGS: &[&str] = &["next.config.ts", "next.config.mjs", "next.config.js"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    /// Directory holding the `package.json` (or `pubspec.yaml`).
+    pub root: PathBuf,
+    /// `React`, `Vue`, `Flutter`, ...
+    pub framework: &'static str,
+    /// `Vite` or `webpack`, when one is found.
+    pub bundler: Option<&'static str>,
+    /// The SVG loader that decided the preset, e.g. `vite-plugin-svgr`.
+    pub svg_loader: Option<&'static str>,
+    pub preset: Preset,
+    pub import_query: Option<ImportQuery>,
+}
+
+impl Detection {
+    /// `React + Vite (vite-plugin-svgr)`.
+    pub fn summary(&self) -> String {
+        let mut out = self.framework.to_string();
+        if let Some(bundler) = self.bundler {
+            out.push_str(&format!(" + {bundler}"));
+        }
+        if let Some(loader) = self.svg_loader {
+            out.push_str(&format!(" ({loader})"));
+        }
+        out
+    }
+}
+
+/// Detection for the project `start_dir` belongs to, if it's one we know.
+pub fn detect_project(start_dir: &Path) -> Option<Detection> {
+    if let Some(project) = crate::flutter::detect_flutter_project(start_dir) {
+        return Some(Detection {
+            root: project.root,
+            framework: "Flutter",
+            bundler: None,
+            svg_loader: None,
+            preset: Preset::Flutter,
+            import_query: None,
+        });
+    }
+
+    let root = start_dir
+        .ancestors()
+        .find(|dir| dir.join("package.json").is_file())?;
+    let package_json = std::fs::read_to_string(root.join("package.json")).ok()?;
+    let package: Value = serde_json::from_str(&package_json).ok()?;
+    let vite_config = read_first(root, VITE_CONFIGS);
+    let next_config = read_first(root, NEXT_CONFIGS);
+    detect_from(
+        root,
+        &package,
+        vite_config.as_deref(),
+        next_config.as_deref(),
+    )
+}
+
+fn read_first(root: &Path, names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::fs::read_to_string(root.join(name)).ok())
+}
+
+fn detect_from(
+    root: &Path,
+    package: &Value,
+    vite_config: Option<&str>,
+    next_config: Option<&str>,
+) -> Option<Detection> {
+    let has = |dependency: &str| {
+        ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .any(|field| {
+                package
+                    .get(field)
+                    .and_then(|deps| deps.get(dependency))
+                    .is_some()
+            })
+    };
+    let configured =
+        |config: Option<&str>, needle: &str| config.is_some_and(|c| c.contains(needle));
+
+    let framework = if has("next") {
+        "Next.js"
+    } else if has("react") {
+        "React"
+    } else if has("solid-js") {
+        "Solid"
+    } else if has("nuxt") || has("vue") {
+        "Vue"
+    } else if has("svelte") || has("@sveltejs/kit") {
+        "Svelte"
+    } else {
+        return None;
+    };
+    // Next.js brings its own bundler; it's named as the framework already.
+    let bundler = if framework == "Next.js" {
+        None
+    } else if has("vite") || vite_config.is_some() {
+        Some("Vite")
+    } else if has("webpack") {
+        Some("webpack")
+    } else {
+        None
+    };
+
+    let (svg_loader, preset, import_query) =
+        if has("vite-plugin-svgr") || configured(vite_config, "vite-plugin-svgr") {
+            (
+                Some("vite-plugin-svgr"),
+                Preset::Normal,
+                Some(ImportQuery::React),
+            )
+        } else if has("vite-svg-loader") || configured(vite_config, "vite-svg-loader") {
+            (
+                Some("vite-svg-loader"),
+                Preset::Normal,
+                Some(ImportQuery::Component),
+            )
+        } else if has("vite-plugin-solid-svg") {
+            (Some("vite-plugin-solid-svg"), Preset::Normal, None)
+        } else if has("@svgr/webpack") || configured(next_config, "@svgr/webpack") {
+            (Some("@svgr/webpack"), Preset::Normal, None)
+        } else {
+            let preset = match framework {
+                "Solid" => Preset::Solid,
+                "Vue" => Preset::Vue,
+                "Svelte" => Preset::Svelte,
+                _ => Preset::React,
+            };
+            (None, preset, None)
+        };
+
+    Some(Detection {
+        root: root.to_path_buf(),
+        framework,
+        bundler,
+        svg_loader,
+        preset,
+        import_query,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(package: Value, vite_config: Option<&str>) -> Option<Detection> {
+        detect_from(Path::new("/app"), &package, vite_config, None)
+    }
+
+    #[test]
+    fn svg_loaders_pick_normal_preset_with_their_query() {
+        let detection = detect(
+            serde_json::json!({
+                "dependencies": { "react": "^19.0.0" },
+                "devDependencies": { "vite": "^6.0.0", "vite-plugin-svgr": "^4.0.0" }
+            }),
+            None,
+        )
+        .unwrap();
+        assert_eq!(detection.preset, Preset::Normal);
+        assert_eq!(detection.import_query, Some(ImportQuery::React));
+        assert_eq!(detection.summary(), "React + Vite (vite-plugin-svgr)");
+
+        let detection = detect(
+            serde_json::json!({ "dependencies": { "vue": "^3.0.0" } }),
+            Some("import svgLoader from 'vite-svg-loader'"),
+        )
+        .unwrap();
+        assert_eq!(detection.import_query, Some(ImportQuery::Component));
+        assert_eq!(detection.bundler, Some("Vite"));
+    }
+
+    #[test]
+    fn frameworks_without_a_loader_get_component_presets() {
+        let preset = |deps: Value| detect(serde_json::json!({ "dependencies": deps }), None);
+        assert_eq!(
+            preset(serde_json::json!({ "next": "15", "react": "19" }))
+                .unwrap()
+                .summary(),
+            "Next.js"
+        );
+        assert_eq!(
+            preset(serde_json::json!({ "solid-js": "1" }))
+                .unwrap()
+                .preset,
+            Preset::Solid
+        );
+        assert_eq!(
+            preset(serde_json::json!({ "@sveltejs/kit": "2" }))
+                .unwrap()
+                .preset,
+            Preset::Svelte
+        );
+        assert!(preset(serde_json::json!({ "express": "4" })).is_none());
+    }
+}
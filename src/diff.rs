@@ -0,0 +1,205 @@
+// Line diffs for `replace --diff` and the TUI replace popup.
+//
+// A plain LCS over lines, grouped into unified-diff hunks with three lines of
+// context. Icon files are small (a component is a few dozen lines), so the
+// quadratic table is never a concern; single-line SVGs are split between
+// tags first so a changed path doesn't show up as the whole file.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// `@@ -1,3 +1,4 @@`
+    Hunk(String),
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+const CONTEXT: usize = 3;
+
+/// Lines to compare: a one-line SVG document is broken after each tag.
+fn diff_input(text: &str) -> Vec<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.len() == 1 && lines[0].contains("><") {
+        return lines[0]
+            .replace("><", ">\n<")
+            .lines()
+            .map(str::to_string)
+            .collect();
+    }
+    lines.into_iter().map(str::to_string).collect()
+}
+
+/// The unified diff of `old` and `new`; empty when they match.
+pub fn unified_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = diff_input(old);
+    let new = diff_input(new);
+
+    // lcs[i][j]: length of the LCS of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // (old line, new line, edit) for every line of the walk.
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push((i, j, DiffLine::Context(old[i].clone())));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push((i, j, DiffLine::Removed(old[i].clone())));
+            i += 1;
+        } else {
+            edits.push((i, j, DiffLine::Added(new[j].clone())));
+            j += 1;
+        }
+    }
+
+    let changed = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, line))| !matches!(line, DiffLine::Context(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let Some(&first) = changed.first() else {
+        return Vec::new();
+    };
+
+    // Changes closer than twice the context share a hunk.
+    let mut ranges = vec![(first.saturating_sub(CONTEXT), first)];
+    for &index in &changed[1..] {
+        let last = ranges.last_mut().expect("at least one range");
+        if index - last.1 <= CONTEXT * 2 {
+            last.1 = index;
+        } else {
+            ranges.push((index.saturating_sub(CONTEXT), index));
+        }
+    }
+
+    let mut out = Vec::new();
+    for (start, end) in ranges {
+        let end = (end + CONTEXT).min(edits.len() - 1);
+        let hunk = &edits[start..=end];
+        let old_count = hunk
+            .iter()
+            .filter(|(_, _, line)| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|(_, _, line)| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let (old_start, new_start) = (hunk[0].0 + 1, hunk[0].1 + 1);
+        out.push(DiffLine::Hunk(format!(
+            "@@ -{},{old_count} +{},{new_count} @@",
+            if old_count == 0 {
+                old_start - 1
+            } else {
+                old_start
+            },
+            if new_count == 0 {
+                new_start - 1
+            } else {
+                new_start
+            },
+        )));
+        out.extend(hunk.iter().map(|(_, _, line)| line.clone()));
+    }
+    out
+}
+
+/// `diff -u` style text, with ANSI colors when `use_color`.
+pub fn render_unified(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+    use_color: bool,
+) -> String {
+    let paint = |code: &str, text: String| {
+        if use_color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text
+        }
+    };
+    let mut out = String::new();
+    out.push_str(&paint("1", format!("--- {old_label}")));
+    out.push('\n');
+    out.push_str(&paint("1", format!("+++ {new_label}")));
+    out.push('\n');
+    for line in unified_lines(old, new) {
+        let rendered = match line {
+            DiffLine::Hunk(header) => paint("36", header),
+            DiffLine::Context(text) => format!(" {text}"),
+            DiffLine::Removed(text) => paint("31", format!("-{text}")),
+            DiffLine::Added(text) => paint("32", format!("+{text}")),
+        };
+        out.push_str(&rendered);
+        out.push('\n');
+    }
+    out
+}
+
+/// Reads `render_unified` output (uncolored) back into lines, for the TUI,
+/// which gets the diff from `iconmate replace --dry-run`.
+pub fn parse_unified(text: &str) -> Vec<DiffLine> {
+    text.lines()
+        .filter(|line| !line.starts_with("--- ") && !line.starts_with("+++ "))
+        .filter_map(|line| {
+            if line.starts_with("@@") {
+                Some(DiffLine::Hunk(line.to_string()))
+            } else if let Some(text) = line.strip_prefix('-') {
+                Some(DiffLine::Removed(text.to_string()))
+            } else if let Some(text) = line.strip_prefix('+') {
+                Some(DiffLine::Added(text.to_string()))
+            } else {
+                line.strip_prefix(' ')
+                    .map(|text| DiffLine::Context(text.to_string()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_changes_into_hunks_with_context() {
+        let old = (1..=12).map(|n| format!("line {n}\n")).collect::<String>();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 11\n", "");
+        let diff = render_unified(&old, &new, "a/heart.tsx", "b/heart.tsx", false);
+        assert_eq!(
+            diff,
+            "--- a/heart.tsx\n+++ b/heart.tsx\n@@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n@@ -8,5 +8,4 @@\n line 8\n line 9\n line 10\n-line 11\n line 12\n"
+        );
+        assert_eq!(parse_unified(&diff), unified_lines(&old, &new));
+        assert!(unified_lines(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn splits_one_line_svgs_between_tags() {
+        let old = "<svg viewBox=\"0 0 24 24\"><path d=\"M1 1\"/><circle r=\"2\"/></svg>";
+        let new = "<svg viewBox=\"0 0 24 24\"><path d=\"M2 2\"/><circle r=\"2\"/></svg>";
+        assert_eq!(
+            unified_lines(old, new)[1..],
+            [
+                DiffLine::Context("<svg viewBox=\"0 0 24 24\">".to_string()),
+                DiffLine::Removed("<path d=\"M1 1\"/>".to_string()),
+                DiffLine::Added("<path d=\"M2 2\"/>".to_string()),
+                DiffLine::Context("<circle r=\"2\"/>".to_string()),
+                DiffLine::Context("</svg>".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// Emoji as icon sources.
+//
+// `--icon emoji:🔥` (or just `--icon 🔥`) resolves the character to an icon in
+// one of Iconify's emoji sets, `noto` unless `emoji_set` or `--emoji-set` says
+// otherwise (`twemoji`, `openmoji`, `fluent-emoji-flat`, ...). Emoji sets
+// publish a character map next to their icon list, keyed by the codepoints in
+// hex, so the lookup is one (cached) collection request.
+
+use crate::iconify::IconifyClient;
+
+pub const DEFAULT_EMOJI_SET: &str = "noto";
+const EMOJI_PREFIX: &str = "emoji:";
+
+/// The emoji in `icon_source`, for `emoji:<emoji>` or a bare emoji.
+pub fn emoji_from_icon_source(icon_source: &str) -> Option<&str> {
+    let trimmed = icon_source.trim();
+    let emoji = trimmed.strip_prefix(EMOJI_PREFIX).unwrap_or(trimmed).trim();
+    (!emoji.is_empty() && emoji.chars().all(is_emoji_char) && emoji.chars().any(is_pictograph))
+        .then_some(emoji)
+}
+
+fn is_pictograph(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x3030 | 0x303D
+            | 0x3297 | 0x3299 | 0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139
+            | 0x2194..=0x21AA
+    )
+}
+
+/// Pictographs plus the joiners, selectors and modifiers that build
+/// sequences such as flags, keycaps and skin tones.
+fn is_emoji_char(ch: char) -> bool {
+    is_pictograph(ch)
+        || matches!(ch as u32, 0x200D | 0xFE0F | 0x20E3 | 0xE0020..=0xE007F)
+        || ch.is_ascii_digit()
+        || ch == '#'
+        || ch == '*'
+}
+
+/// Character map keys for `emoji`, most specific first: every codepoint in
+/// lowercase hex joined with `-`, then the same without `fe0f` selectors,
+/// which the sets often leave out.
+fn char_keys(emoji: &str) -> Vec<String> {
+    let key = |skip_selectors: bool| {
+        emoji
+            .chars()
+            .filter(|&ch| !(skip_selectors && ch == '\u{FE0F}'))
+            .map(|ch| format!("{:x}", ch as u32))
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    let mut keys = vec![key(false)];
+    let without_selectors = key(true);
+    if !keys.contains(&without_selectors) {
+        keys.push(without_selectors);
+    }
+    keys
+}
+
+/// `set:name` for `emoji` in the Iconify emoji set `set`.
+pub async fn resolve_emoji(set: &str, emoji: &str) -> anyhow::Result<String> {
+    let client = IconifyClient::from_env()?;
+    let chars = client.collection_chars(set).await?;
+    char_keys(emoji)
+        .iter()
+        .find_map(|key| chars.get(key))
+        .map(|name| format!("{set}:{name}"))
+        .ok_or_else(|| anyhow::anyhow!("No icon for {emoji} in the '{set}' emoji set."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_emoji_sources_and_builds_char_keys() {
+        assert_eq!(emoji_from_icon_source("emoji:🔥"), Some("🔥"));
+        assert_eq!(emoji_from_icon_source(" 🔥 "), Some("🔥"));
+        assert_eq!(emoji_from_icon_source("❤️"), Some("❤️"));
+        assert_eq!(emoji_from_icon_source("mdi:fire"), None);
+        assert_eq!(emoji_from_icon_source("emoji:fire"), None);
+        assert_eq!(emoji_from_icon_source("123"), None);
+
+        assert_eq!(char_keys("🔥"), vec!["1f525"]);
+        assert_eq!(char_keys("❤️"), vec!["2764-fe0f", "2764"]);
+        assert_eq!(char_keys("👍🏽"), vec!["1f44d-1f3fd"]);
+    }
+}
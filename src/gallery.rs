@@ -0,0 +1,178 @@
+// Icon gallery generation (`iconmate gallery`).
+//
+// Writes an `ICONS.md` into the icons folder with a preview, the alias, and
+// the import snippet for every icon in the barrel, so teammates can browse
+// what exists without running the TUI. Once the file exists, add/delete keep
+// it up to date.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::utils::{IconEntry, get_existing_icons_for_preset};
+
+pub const GALLERY_FILE: &str = "ICONS.md";
+
+const GENERATED_HEADER: &str = "<!-- GENERATED by iconmate — do not edit by hand. -->";
+const PREVIEW_SIZE: u32 = 24;
+
+/// How a gallery row tells people to use the icon.
+pub enum ImportStyle<'a> {
+    /// `import { IconHeart } from "<module>";`
+    Js { module: &'a str },
+    /// `AppIcons.heart`
+    Flutter { class: &'a str },
+}
+
+impl<'a> ImportStyle<'a> {
+    pub fn for_preset(
+        preset: &str,
+        module: &'a str,
+        flutter_barrel_class: Option<&'a str>,
+    ) -> Self {
+        if preset == "flutter" {
+            ImportStyle::Flutter {
+                class: flutter_barrel_class.unwrap_or(crate::flutter::DEFAULT_FLUTTER_BARREL_CLASS),
+            }
+        } else {
+            ImportStyle::Js { module }
+        }
+    }
+}
+
+/// The line someone pastes to use `name`.
+pub fn usage_snippet(name: &str, style: &ImportStyle) -> String {
+    match style {
+        ImportStyle::Js { module } => format!("import {{ {name} }} from \"{module}\";"),
+        ImportStyle::Flutter { class } => format!("{class}.{name}"),
+    }
+}
+
+pub fn render_gallery(entries: &[IconEntry], folder_label: &str, style: &ImportStyle) -> String {
+    let mut entries = entries.to_vec();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push_str("\n\n# Icons\n\n");
+    out.push_str(&format!(
+        "{} icon{} in `{folder_label}`.\n\n",
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" }
+    ));
+
+    out.push_str("| Preview | Name | Usage |\n");
+    out.push_str("| :-----: | ---- | ----- |\n");
+    for entry in &entries {
+        let relative = entry.file_path.replace('\\', "/");
+        let relative = relative.trim_start_matches("./");
+        let preview = if relative.ends_with(".svg") {
+            format!(
+                "<img src=\"./{relative}\" width=\"{PREVIEW_SIZE}\" height=\"{PREVIEW_SIZE}\" alt=\"{}\" />",
+                entry.name
+            )
+        } else {
+            "—".to_string()
+        };
+        let usage = format!("`{}`", usage_snippet(&entry.name, style));
+        out.push_str(&format!("| {preview} | `{}` | {usage} |\n", entry.name));
+    }
+
+    out
+}
+
+/// Rebuilds `<folder>/ICONS.md` from the preset's barrel.
+pub fn write_gallery(
+    folder: &Path,
+    preset: &str,
+    flutter_barrel_file: Option<&str>,
+    flutter_barrel_class: Option<&str>,
+) -> anyhow::Result<PathBuf> {
+    let folder_str = folder.to_string_lossy().replace('\\', "/");
+    let entries =
+        get_existing_icons_for_preset(&folder_str, preset, flutter_barrel_file).unwrap_or_default();
+    let module = folder_str.trim_end_matches('/');
+    let style = ImportStyle::for_preset(preset, module, flutter_barrel_class);
+
+    let gallery_path = folder.join(GALLERY_FILE);
+    std::fs::write(&gallery_path, render_gallery(&entries, module, &style))
+        .with_context(|| format!("Failed to write {}", gallery_path.display()))?;
+    Ok(gallery_path)
+}
+
+/// Regenerates the gallery only if the folder already has one.
+pub fn refresh_gallery_if_present(
+    folder: &Path,
+    preset: &str,
+    flutter_barrel_file: Option<&str>,
+    flutter_barrel_class: Option<&str>,
+) -> anyhow::Result<()> {
+    if folder.join(GALLERY_FILE).exists() {
+        write_gallery(folder, preset, flutter_barrel_file, flutter_barrel_class)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, file_path: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_sorted_rows_with_previews_for_svgs_only() {
+        let entries = vec![
+            entry("IconStar", "./star"),
+            entry("IconHeart", "./heart.svg"),
+        ];
+        let md = render_gallery(
+            &entries,
+            "src/assets/icons",
+            &ImportStyle::Js {
+                module: "@/assets/icons",
+            },
+        );
+
+        let heart = md.find("IconHeart").unwrap();
+        let star = md.find("IconStar").unwrap();
+        assert!(heart < star);
+        assert!(md.contains("2 icons in `src/assets/icons`."));
+        assert!(md.contains(
+            "| <img src=\"./heart.svg\" width=\"24\" height=\"24\" alt=\"IconHeart\" /> | `IconHeart` | `import { IconHeart } from \"@/assets/icons\";` |"
+        ));
+        assert!(md.contains("| — | `IconStar` |"));
+    }
+
+    #[test]
+    fn flutter_rows_use_barrel_class_accessors() {
+        let md = render_gallery(
+            &[entry("heart", "heart.svg")],
+            "assets/icons",
+            &ImportStyle::Flutter { class: "AppIcons" },
+        );
+        assert!(md.contains("1 icon in `assets/icons`."));
+        assert!(md.contains("| `heart` | `AppIcons.heart` |"));
+    }
+
+    #[test]
+    fn refresh_is_a_no_op_without_existing_gallery() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
+
+        refresh_gallery_if_present(dir.path(), "normal", None, None).unwrap();
+        assert!(!dir.path().join(GALLERY_FILE).exists());
+
+        write_gallery(dir.path(), "normal", None, None).unwrap();
+        let md = std::fs::read_to_string(dir.path().join(GALLERY_FILE)).unwrap();
+        assert!(md.contains("`IconHeart`"));
+    }
+}
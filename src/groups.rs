@@ -0,0 +1,265 @@
+// Icon groups: subfolders of the icons folder with their own `index.ts`.
+//
+// `iconmate add --group social` saves the icon in `<folder>/social/` and
+// exports it from `social/index.ts`; the root `index.ts` gets
+// `export * from './social';` so every icon is still imported from the icons
+// folder. Groups can nest (`--group brand/social`), and the root re-exports
+// each one directly. Export names stay unique across the whole tree, since
+// two `export *` of the same name would be ambiguous.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::utils::{IconEntry, parse_index_entries};
+
+/// ` social/ ` -> `social`. Segments are plain folder names: no `.`, `..`,
+/// empty parts or absolute paths.
+pub fn validate_group(group: &str) -> anyhow::Result<String> {
+    let normalized = group.trim().replace('\\', "/");
+    let normalized = normalized.trim_matches('/');
+    if normalized.is_empty() {
+        anyhow::bail!("--group can't be empty.");
+    }
+    if normalized
+        .split('/')
+        .any(|segment| segment.trim().is_empty())
+        || Path::new(normalized)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        anyhow::bail!(
+            "--group {group} must be a folder name inside the icons folder, like `social`."
+        );
+    }
+    Ok(normalized.to_string())
+}
+
+/// `export * from './social';` -> `social`. Only local folders count.
+pub fn parse_group_reexport(statement: &str) -> Option<String> {
+    let rest = statement.trim().trim_end_matches(';').trim_end();
+    let rest = rest.strip_prefix("export")?.trim_start();
+    let rest = rest.strip_prefix('*')?.trim_start();
+    let rest = rest.strip_prefix("from")?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let path = rest[1..].strip_suffix(quote)?;
+    let group = path.strip_prefix("./")?;
+    let group = group
+        .strip_suffix("/index.ts")
+        .or_else(|| group.strip_suffix("/index.js"))
+        .or_else(|| group.strip_suffix("/index"))
+        .unwrap_or(group)
+        .trim_end_matches('/');
+    (!group.is_empty()).then(|| group.to_string())
+}
+
+/// Groups re-exported by a barrel, in file order.
+pub fn reexported_groups(contents: &str) -> Vec<String> {
+    crate::js_statements::statements(contents)
+        .iter()
+        .filter_map(|statement| parse_group_reexport(&statement.code))
+        .collect()
+}
+
+/// The re-export of `group`, quoted and terminated like `like`, an export
+/// line already in the barrel's style.
+pub fn reexport_line(group: &str, like: &str) -> String {
+    // The last quote of an export line closes its module path, whatever the
+    // spacing around `from` or `import(`.
+    let quote = match like.rfind(['"', '\'']) {
+        Some(idx) if like[idx..].starts_with('"') => '"',
+        _ => '\'',
+    };
+    let semicolon = if like.trim_end().ends_with(';') {
+        ";"
+    } else {
+        ""
+    };
+    format!("export * from {quote}./{group}{quote}{semicolon}")
+}
+
+/// `./social/heart.svg` -> (`social`, `./heart.svg`), the group and the path
+/// in its barrel. Ungrouped paths come back as they are.
+pub fn split_group(file_path: &str) -> (Option<&str>, String) {
+    let relative = file_path.trim().trim_start_matches("./");
+    match relative.rsplit_once('/') {
+        Some((group, rest)) if !group.is_empty() => (Some(group), format!("./{rest}")),
+        _ => (None, file_path.to_string()),
+    }
+}
+
+/// The root barrel and the barrels of the groups it re-exports, with their
+/// contents. Groups without an `index.ts` are skipped.
+fn read_barrels(root: &Path) -> anyhow::Result<Vec<(Option<String>, PathBuf, String)>> {
+    let root_index = root.join("index.ts");
+    let contents = std::fs::read_to_string(&root_index)
+        .with_context(|| format!("Failed to read {}", root_index.display()))?;
+    let mut barrels = Vec::new();
+    for group in reexported_groups(&contents) {
+        let path = root.join(&group).join("index.ts");
+        if let Ok(group_contents) = std::fs::read_to_string(&path) {
+            barrels.push((Some(group), path, group_contents));
+        }
+    }
+    barrels.insert(0, (None, root_index, contents));
+    Ok(barrels)
+}
+
+/// Every icon of the folder: the root exports, then each group's, with file
+/// paths relative to `root` (`./social/heart.svg`).
+/// Folders with another barrel language have no groups.
+pub fn icons_with_groups(root: &Path) -> anyhow::Result<Vec<IconEntry>> {
+    if !root.join("index.ts").exists() {
+        return crate::utils::get_existing_icons(&root.to_string_lossy());
+    }
+    let mut icons = Vec::new();
+    for (group, _, contents) in read_barrels(root)? {
+        icons.extend(
+            parse_index_entries(&contents)
+                .into_iter()
+                .map(|entry| match &group {
+                    Some(group) => IconEntry {
+                        file_path: format!(
+                            "./{group}/{}",
+                            entry.file_path.trim_start_matches("./")
+                        ),
+                        ..entry
+                    },
+                    None => entry,
+                }),
+        );
+    }
+    Ok(icons)
+}
+
+/// All barrels of the folder back to back, for checks that only look at
+/// export names.
+pub fn tree_index_contents(root: &Path) -> Option<String> {
+    let barrels = read_barrels(root).ok()?;
+    Some(
+        barrels
+            .into_iter()
+            .map(|(_, _, contents)| contents)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// The barrel other than `except` that already exports `name`.
+pub fn find_export(root: &Path, name: &str, except: &Path) -> Option<PathBuf> {
+    read_barrels(root)
+        .ok()?
+        .into_iter()
+        .find(|(_, path, contents)| {
+            path != except
+                && parse_index_entries(contents)
+                    .iter()
+                    .any(|entry| entry.name == name)
+        })
+        .map(|(_, path, _)| path)
+}
+
+/// Adds `export * from './<group>'` to the root barrel unless it's there.
+/// Returns whether the barrel changed.
+pub fn ensure_group_reexport(
+    root: &Path,
+    group: &str,
+    like: &str,
+    barrel_frame: &crate::barrel::BarrelFrame,
+    line_ending: crate::svg_format::LineEnding,
+) -> anyhow::Result<bool> {
+    let root_index = root.join("index.ts");
+    let line = format!("{}\n", reexport_line(group, like));
+    let contents = if root_index.exists() {
+        let contents = std::fs::read_to_string(&root_index)
+            .with_context(|| format!("Failed to read {}", root_index.display()))?;
+        if reexported_groups(&contents).iter().any(|g| g == group) {
+            return Ok(false);
+        }
+        barrel_frame.append(&contents, &line)
+    } else {
+        barrel_frame.create(&line)
+    };
+    std::fs::write(&root_index, line_ending.normalize(&contents))
+        .with_context(|| format!("Failed to write {}", root_index.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_group_names() {
+        assert_eq!(validate_group(" social/ ").unwrap(), "social");
+        assert_eq!(validate_group("brand\\social").unwrap(), "brand/social");
+        assert!(validate_group("").is_err());
+        assert!(validate_group("../social").is_err());
+        assert!(validate_group("brand//social").is_err());
+        assert!(validate_group("/abs").is_ok_and(|group| group == "abs"));
+    }
+
+    #[test]
+    fn parses_group_reexports() {
+        let contents = "export { default as IconHome } from './home.svg';\nexport * from './social';\nexport * from \"./brand/logos/index\"\n// export * from './old';\nexport * from 'lodash';\n";
+        assert_eq!(reexported_groups(contents), ["social", "brand/logos"]);
+        assert_eq!(
+            reexport_line("social", "export { default as IconX } from \"./x.svg\""),
+            "export * from \"./social\""
+        );
+        assert_eq!(
+            reexport_line("social", "export{default as IconX}from\"./x.svg\";"),
+            "export * from \"./social\";"
+        );
+        assert_eq!(
+            reexport_line("social", "export const IconX = lazy(() => import('./x'))"),
+            "export * from './social'"
+        );
+        assert_eq!(
+            split_group("./social/heart.svg"),
+            (Some("social"), "./heart.svg".to_string())
+        );
+        assert_eq!(
+            split_group("./heart.svg"),
+            (None, "./heart.svg".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_icons_of_every_group() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let frame = crate::barrel::BarrelFrame::default();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHome } from './home.svg';\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("social")).unwrap();
+        std::fs::write(
+            dir.path().join("social/index.ts"),
+            "export { default as IconX } from './x.svg';\n",
+        )
+        .unwrap();
+        let like = "export { default as IconX } from './x.svg';";
+        let line_ending = crate::svg_format::LineEnding::default();
+        assert!(ensure_group_reexport(dir.path(), "social", like, &frame, line_ending).unwrap());
+        assert!(!ensure_group_reexport(dir.path(), "social", like, &frame, line_ending).unwrap());
+
+        let icons = icons_with_groups(dir.path()).unwrap();
+        assert_eq!(
+            icons
+                .iter()
+                .map(|icon| (icon.name.as_str(), icon.file_path.as_str()))
+                .collect::<Vec<_>>(),
+            [("IconHome", "./home.svg"), ("IconX", "./social/x.svg")]
+        );
+        assert_eq!(
+            find_export(dir.path(), "IconX", &dir.path().join("index.ts")),
+            Some(dir.path().join("social/index.ts"))
+        );
+        assert_eq!(
+            find_export(dir.path(), "IconX", &dir.path().join("social/index.ts")),
+            None
+        );
+    }
+}
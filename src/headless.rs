@@ -0,0 +1,292 @@
+// Headless TUI driver (`--features headless`).
+//
+// Runs the same `App`, event handling and drawing as `tui::run`, but on a
+// ratatui `TestBackend`: key presses are fed in as crossterm events and the
+// rendered buffer is read back as text. Integration tests use it through
+// `iconmate headless <script>` to cover popup flows, and a bug report can
+// attach a script that reproduces what the reporter saw.
+//
+// Script lines (`#` starts a comment):
+//
+//   press <key>...   keys such as `a`, `enter`, `esc`, `down`, `ctrl+f`, `f12`
+//   type <text>      every character of <text> as a key press
+//   paste <text>     <text> as one bracketed paste; `\n` is a line break
+//   resize <w> <h>   resize the screen, sending the resize event
+//   wait <text>      re-render until <text> is on screen (background fetches)
+//   sleep <ms>       let background work run
+//   expect <text>    fail unless <text> is on screen now
+//   screen           print the current screen
+//
+// The final screen is printed when the script ends or the app quits.
+
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app_state::{App, AppConfig};
+
+/// How long `wait` re-renders before giving up.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+/// About one frame of the real event loop.
+const FRAME: Duration = Duration::from_millis(16);
+
+pub struct Driver {
+    app: App,
+    terminal: Terminal<TestBackend>,
+    quit: bool,
+}
+
+impl Driver {
+    /// Starts the app like `tui::run`, onboarding included.
+    pub fn new(config: AppConfig, width: u16, height: u16) -> anyhow::Result<Self> {
+        let mut app = App::new(config);
+        if app.needs_onboarding() {
+            app.init_onboarding_popup();
+        }
+        Ok(Self {
+            app,
+            terminal: Terminal::new(TestBackend::new(width, height))?,
+            quit: false,
+        })
+    }
+
+    /// Whether ctrl+c or `q` ended the session.
+    pub fn has_quit(&self) -> bool {
+        self.quit || self.app.should_quit
+    }
+
+    /// One key press, e.g. `enter` or `ctrl+f`.
+    pub fn press(&mut self, key: &str) -> anyhow::Result<()> {
+        let event = parse_key(key)?;
+        self.send(event)
+    }
+
+    pub fn type_text(&mut self, text: &str) -> anyhow::Result<()> {
+        text.chars()
+            .try_for_each(|c| self.send(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)))
+    }
+
+    /// A bracketed paste of `text`.
+    pub fn paste(&mut self, text: &str) -> anyhow::Result<()> {
+        self.dispatch(Event::Paste(text.to_string()))
+    }
+
+    /// Resizes the screen and sends the resize event, like a terminal would.
+    pub fn resize(&mut self, width: u16, height: u16) -> anyhow::Result<()> {
+        self.terminal.backend_mut().resize(width, height);
+        self.dispatch(Event::Resize(width, height))
+    }
+
+    fn send(&mut self, event: KeyEvent) -> anyhow::Result<()> {
+        self.dispatch(Event::Key(event))
+    }
+
+    /// Like one turn of `tui::run`: draw, handle the event, update.
+    fn dispatch(&mut self, event: Event) -> anyhow::Result<()> {
+        self.screen()?;
+        if crate::tui::handle_event(&mut self.app, event) {
+            self.quit = true;
+        }
+        self.app.update();
+        Ok(())
+    }
+
+    /// Draws a frame and returns it as text, trailing spaces trimmed.
+    pub fn screen(&mut self) -> anyhow::Result<String> {
+        self.app.update();
+        self.terminal.draw(|f| crate::tui::draw(f, &mut self.app))?;
+        Ok(crate::tui::buffer_rows(self.terminal.backend().buffer())
+            .iter()
+            .map(|row| row.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Re-renders until `text` is on screen, letting background tasks finish.
+    pub async fn wait_for(&mut self, text: &str, timeout: Duration) -> anyhow::Result<()> {
+        let started = Instant::now();
+        loop {
+            let screen = self.screen()?;
+            if screen.contains(text) {
+                return Ok(());
+            }
+            if started.elapsed() > timeout {
+                anyhow::bail!(
+                    "Timed out after {} ms waiting for {text:?}. Screen:\n{screen}",
+                    timeout.as_millis()
+                );
+            }
+            tokio::time::sleep(FRAME).await;
+        }
+    }
+}
+
+/// Parses `a`, `A`, `enter`, `shift+tab`, `ctrl+c`, `alt+x`, `f12`...
+pub fn parse_key(spec: &str) -> anyhow::Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = spec;
+    // A bare `+` is the plus key, not a separator.
+    while let Some((modifier, rest)) = name.split_once('+').filter(|(_, rest)| !rest.is_empty()) {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => anyhow::bail!("Unknown modifier `{modifier}` in key `{spec}`."),
+        };
+        name = rest;
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        _ if name.chars().count() == 1 => KeyCode::Char(name.chars().next().unwrap_or(' ')),
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => anyhow::bail!("Unknown key `{spec}`."),
+        },
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Runs a script (see the top of this file), writing screens to `out`.
+pub async fn run_script(
+    driver: &mut Driver,
+    script: &str,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    for (index, raw) in script.lines().enumerate() {
+        let number = index + 1;
+        let line = raw.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        let argument = argument.trim_end();
+        let result = match command {
+            "press" => argument
+                .split_whitespace()
+                .try_for_each(|key| driver.press(key)),
+            "type" => driver.type_text(argument),
+            "paste" => driver.paste(&argument.replace("\\n", "\n")),
+            "resize" => match argument.split_once(' ').and_then(|(width, height)| {
+                Some((width.parse().ok()?, height.trim().parse().ok()?))
+            }) {
+                Some((width, height)) => driver.resize(width, height),
+                None => Err(anyhow::anyhow!(
+                    "`resize` takes a width and a height, got `{argument}`."
+                )),
+            },
+            "wait" => driver.wait_for(argument, WAIT_TIMEOUT).await,
+            "sleep" => match argument.parse::<u64>() {
+                Ok(ms) => {
+                    tokio::time::sleep(Duration::from_millis(ms)).await;
+                    Ok(())
+                }
+                Err(_) => Err(anyhow::anyhow!(
+                    "`sleep` takes milliseconds, got `{argument}`."
+                )),
+            },
+            "expect" => {
+                let screen = driver.screen()?;
+                if screen.contains(argument) {
+                    Ok(())
+                } else {
+                    Err(anyhow::anyhow!(
+                        "Expected {argument:?} on screen. Screen:\n{screen}"
+                    ))
+                }
+            }
+            "screen" => {
+                writeln!(out, "--- line {number} ---\n{}", driver.screen()?)?;
+                Ok(())
+            }
+            other => Err(anyhow::anyhow!("Unknown command `{other}`.")),
+        };
+        result.with_context(|| format!("Script line {number}: {}", raw.trim()))?;
+        if driver.has_quit() {
+            break;
+        }
+    }
+    writeln!(out, "--- end ---\n{}", driver.screen()?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(folder: &std::path::Path) -> AppConfig {
+        AppConfig::for_tests(folder.to_string_lossy().into_owned(), "normal")
+    }
+
+    #[test]
+    fn parses_key_specs() {
+        assert_eq!(
+            parse_key("ctrl+f").unwrap(),
+            KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(parse_key("shift+tab").unwrap().code, KeyCode::BackTab);
+        assert_eq!(parse_key("F12").unwrap().code, KeyCode::F(12));
+        assert_eq!(parse_key("+").unwrap().code, KeyCode::Char('+'));
+        assert!(parse_key("hyper+x").is_err());
+        assert!(parse_key("f13").is_err());
+    }
+
+    #[tokio::test]
+    async fn script_drives_the_delete_popup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("heart.svg"), "<svg/>").unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
+
+        let mut driver = Driver::new(config(temp_dir.path()), 100, 30).unwrap();
+        let mut out = Vec::new();
+        run_script(
+            &mut driver,
+            "expect IconHeart\npress d\nscreen\npress y\n",
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("--- line 3 ---\n"));
+        assert!(out.contains("--- end ---"));
+        assert!(driver.app.items.is_empty());
+        assert!(!temp_dir.path().join("heart.svg").exists());
+
+        run_script(
+            &mut driver,
+            "press a\npress tab\npaste <svg viewBox=\"0 0 24 24\">\\n<path d=\"M0 0h24v24z\"/>\\n</svg>\nexpect [SVG 24×24\n",
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+
+        let error = run_script(&mut driver, "expect IconHeart\n", &mut Vec::new())
+            .await
+            .unwrap_err();
+        assert!(format!("{error:#}").starts_with("Script line 1: expect IconHeart"));
+    }
+}
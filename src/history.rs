@@ -0,0 +1,353 @@
+// Append-only history of add, delete and rename (`.iconmate/log.jsonl`).
+//
+// Each operation appends one JSON line: when (UTC), what, the export name and
+// file, where an added icon came from, and who ran it (`ICONMATE_USER`, then
+// `git config user.name`, then `$USER`). Commit the file in a shared
+// design-system package and `iconmate log` answers "who added this, and from
+// where?". The log lives next to the local config (the working directory);
+// `"history_log": false` turns it off. Lines are only ever appended, so
+// merges of the file stay trivial.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+pub const LOG_FILE: &str = ".iconmate/log.jsonl";
+
+/// Sources longer than this (raw SVG, data URIs) are summarized.
+const MAX_SOURCE_LEN: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+    Add,
+    Delete,
+    Rename,
+}
+
+impl Operation {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Operation::Add => "add",
+            Operation::Delete => "delete",
+            Operation::Rename => "rename",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// `2026-01-02T03:04:05Z`.
+    pub ts: String,
+    pub op: Operation,
+    /// Export name (`IconHeart`; Flutter: `heart`).
+    pub name: String,
+    /// Icon file, relative to the working directory.
+    pub file: String,
+    /// Rename target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Where an added icon came from: Iconify name, URL, emoji, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub user: String,
+}
+
+impl Entry {
+    fn new(op: Operation, name: &str, file: &Path) -> Self {
+        Entry {
+            ts: now_utc(),
+            op,
+            name: name.to_string(),
+            file: display_path(file),
+            to: None,
+            source: None,
+            user: current_user().to_string(),
+        }
+    }
+
+    pub fn add(name: &str, file: &Path, source: Option<&str>) -> Self {
+        Entry {
+            source: source.map(describe_source),
+            ..Entry::new(Operation::Add, name, file)
+        }
+    }
+
+    pub fn delete(name: &str, file: &Path) -> Self {
+        Entry::new(Operation::Delete, name, file)
+    }
+
+    pub fn rename(name: &str, from: &Path, to: &Path) -> Self {
+        Entry {
+            to: Some(display_path(to)),
+            ..Entry::new(Operation::Rename, name, from)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryLog {
+    path: PathBuf,
+}
+
+impl HistoryLog {
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        HistoryLog { path: path.into() }
+    }
+
+    pub fn append(&self, entry: &Entry) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// `append` for callers whose operation already happened: a log that
+    /// can't be written is a warning, not a failure.
+    pub fn record(&self, entry: Entry) {
+        if let Err(e) = self.append(&entry) {
+            eprintln!("Warning: {e:#}");
+        }
+    }
+
+    /// `sources_by_file` for this log; empty when there's no log yet.
+    pub fn sources(&self) -> HashMap<String, String> {
+        read_log(&self.path)
+            .map(|(entries, _)| sources_by_file(&entries))
+            .unwrap_or_default()
+    }
+}
+
+/// Where each icon file on record came from, keyed by its logged path (see
+/// `display_path`). Renames carry the source along; deletes drop it.
+pub fn sources_by_file(entries: &[Entry]) -> HashMap<String, String> {
+    let mut sources = HashMap::new();
+    for entry in entries {
+        match entry.op {
+            Operation::Add => match &entry.source {
+                Some(source) => {
+                    sources.insert(entry.file.clone(), source.clone());
+                }
+                None => {
+                    sources.remove(&entry.file);
+                }
+            },
+            Operation::Rename => {
+                if let (Some(source), Some(to)) = (sources.remove(&entry.file), &entry.to) {
+                    sources.insert(to.clone(), source);
+                }
+            }
+            Operation::Delete => {
+                sources.remove(&entry.file);
+            }
+        }
+    }
+    sources
+}
+
+/// The log's entries, oldest first. Lines that don't parse (a botched merge)
+/// are skipped and counted.
+pub fn read_log(path: &Path) -> anyhow::Result<(Vec<Entry>, usize)> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok((entries, skipped))
+}
+
+/// One line per entry, newest first:
+/// `2026-01-02 03:04  alice  add     IconHeart  src/icons/heart.svg  (mdi:heart)`.
+pub fn render_log(entries: &[Entry]) -> String {
+    let user_width = entries.iter().map(|e| e.user.len()).max().unwrap_or(0);
+    let name_width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for entry in entries.iter().rev() {
+        let when = entry.ts.get(..16).unwrap_or(&entry.ts).replace('T', " ");
+        let mut line = format!(
+            "{when}  {:<user_width$}  {:<6}  {:<name_width$}  {}",
+            entry.user,
+            entry.op.to_str(),
+            entry.name,
+            entry.file
+        );
+        if let Some(to) = &entry.to {
+            line.push_str(&format!(" → {to}"));
+        }
+        if let Some(source) = &entry.source {
+            line.push_str(&format!("  ({source})"));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn describe_source(source: &str) -> String {
+    let source = source.trim();
+    if source.starts_with('<') {
+        return "inline svg".to_string();
+    }
+    if source.starts_with("data:") {
+        return "data uri".to_string();
+    }
+    if source.len() > MAX_SOURCE_LEN {
+        let cut = (0..=MAX_SOURCE_LEN)
+            .rev()
+            .find(|i| source.is_char_boundary(*i))
+            .unwrap_or(0);
+        return format!("{}…", &source[..cut]);
+    }
+    source.to_string()
+}
+
+/// `src/icons/./heart.svg` -> `src/icons/heart.svg`, `/`-separated, as files
+/// are logged.
+pub fn display_path(path: &Path) -> String {
+    path.components()
+        .filter(|component| !matches!(component, std::path::Component::CurDir))
+        .collect::<PathBuf>()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn current_user() -> &'static str {
+    static USER: OnceLock<String> = OnceLock::new();
+    USER.get_or_init(|| {
+        let from_env = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+        from_env("ICONMATE_USER")
+            .or_else(|| {
+                let output = std::process::Command::new("git")
+                    .args(["config", "user.name"])
+                    .stdin(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .output()
+                    .ok()?;
+                let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                (output.status.success() && !name.is_empty()).then_some(name)
+            })
+            .or_else(|| from_env("USER"))
+            .or_else(|| from_env("USERNAME"))
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+}
+
+fn now_utc() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = crate::template::civil_from_days((secs / 86_400) as i64);
+    let seconds = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_and_reads_back_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = HistoryLog::at(dir.path().join(LOG_FILE));
+        log.append(&Entry::add(
+            "IconHeart",
+            Path::new("src/icons/./heart.svg"),
+            Some("mdi:heart"),
+        ))
+        .unwrap();
+        log.append(&Entry::rename(
+            "IconHeart",
+            Path::new("src/icons/heart.svg"),
+            Path::new("src/icons/love.svg"),
+        ))
+        .unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(LOG_FILE))
+            .unwrap()
+            .write_all(b"<<<<<<< HEAD\n")
+            .unwrap();
+
+        let (entries, skipped) = read_log(&dir.path().join(LOG_FILE)).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].op, Operation::Add);
+        assert_eq!(entries[0].file, "src/icons/heart.svg");
+        assert_eq!(entries[0].source.as_deref(), Some("mdi:heart"));
+        assert_eq!(entries[1].to.as_deref(), Some("src/icons/love.svg"));
+        assert_eq!(entries[0].ts.len(), "2026-01-02T03:04:05Z".len());
+
+        let rendered = render_log(&entries);
+        let first = rendered.lines().next().unwrap();
+        assert!(first.contains("rename  IconHeart  src/icons/heart.svg → src/icons/love.svg"));
+    }
+
+    #[test]
+    fn sources_follow_renames_and_drop_deletes() {
+        let entries = [
+            Entry::add(
+                "IconHeart",
+                Path::new("src/icons/heart.svg"),
+                Some("ph:heart"),
+            ),
+            Entry::rename(
+                "IconHeart",
+                Path::new("src/icons/heart.svg"),
+                Path::new("src/icons/love.svg"),
+            ),
+            Entry::add(
+                "IconHome",
+                Path::new("src/icons/home.svg"),
+                Some("mdi:home"),
+            ),
+            Entry::delete("IconHome", Path::new("src/icons/home.svg")),
+            Entry::add("IconLogo", Path::new("src/icons/logo.svg"), None),
+        ];
+
+        let sources = sources_by_file(&entries);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(
+            sources.get("src/icons/love.svg").map(String::as_str),
+            Some("ph:heart")
+        );
+    }
+
+    #[test]
+    fn summarizes_inline_sources() {
+        assert_eq!(describe_source("<svg viewBox='0 0 1 1'/>"), "inline svg");
+        assert_eq!(
+            describe_source("data:image/svg+xml;base64,AAAA"),
+            "data uri"
+        );
+        assert_eq!(
+            describe_source("https://example.com/a.svg"),
+            "https://example.com/a.svg"
+        );
+    }
+}
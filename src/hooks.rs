@@ -0,0 +1,175 @@
+// Add/delete hooks.
+//
+// `pre_add_cmd`, `post_add_cmd`, `pre_delete_cmd` and `post_delete_cmd` from
+// the local config run once per icon, around the write or removal, so teams
+// can trigger codegen, sprite rebuilds or cache busting. Commands are split
+// like `svg_viewer_cmd` (no shell; wrap in `sh -c '...'` for pipes) and get
+// `%file%`, `%name%` and `%folder%` through the template engine. A failing
+// pre hook aborts the operation before anything is touched.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+
+use crate::template;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreAdd,
+    PostAdd,
+    PreDelete,
+    PostDelete,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 4] = [
+        Hook::PreAdd,
+        Hook::PostAdd,
+        Hook::PreDelete,
+        Hook::PostDelete,
+    ];
+
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            Hook::PreAdd => "pre_add_cmd",
+            Hook::PostAdd => "post_add_cmd",
+            Hook::PreDelete => "pre_delete_cmd",
+            Hook::PostDelete => "post_delete_cmd",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hooks {
+    pub pre_add: Option<String>,
+    pub post_add: Option<String>,
+    pub pre_delete: Option<String>,
+    pub post_delete: Option<String>,
+}
+
+impl Hooks {
+    pub fn command(&self, hook: Hook) -> Option<&str> {
+        match hook {
+            Hook::PreAdd => self.pre_add.as_deref(),
+            Hook::PostAdd => self.post_add.as_deref(),
+            Hook::PreDelete => self.pre_delete.as_deref(),
+            Hook::PostDelete => self.post_delete.as_deref(),
+        }
+    }
+
+    /// Runs `hook` for the icon at `file` exported as `name`, if configured.
+    /// Returns whatever the command printed so CLI callers can pass it on;
+    /// the TUI drops it rather than draw over the screen.
+    pub fn run(&self, hook: Hook, file: &Path, name: &str) -> anyhow::Result<String> {
+        let Some(command_template) = self.command(hook) else {
+            return Ok(String::new());
+        };
+        let key = hook.config_key();
+        let parts = hook_command_parts(command_template, file, name).ok_or_else(|| {
+            anyhow::anyhow!("Could not parse {key}. Check quoting in '{command_template}'.")
+        })?;
+        let Some((executable, args)) = parts.split_first() else {
+            anyhow::bail!("{key} is empty");
+        };
+
+        let output = Command::new(executable)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| format!("Failed to run {key} '{command_template}'."))?;
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = if stderr.trim().is_empty() {
+                stdout.trim()
+            } else {
+                stderr.trim()
+            };
+            anyhow::bail!(
+                "{key} failed for {} ({}){}{}",
+                file.display(),
+                output.status,
+                if detail.is_empty() { "" } else { ": " },
+                detail
+            );
+        }
+        Ok(stdout)
+    }
+
+    /// `run` for the CLI: the hook's output goes to stdout.
+    pub fn run_and_print(&self, hook: Hook, file: &Path, name: &str) -> anyhow::Result<()> {
+        let stdout = self.run(hook, file, name)?;
+        if !stdout.is_empty() {
+            print!("{stdout}");
+        }
+        Ok(())
+    }
+}
+
+/// Variables of hook commands.
+pub const HOOK_VARS: &[&str] = &["file", "name", "folder"];
+
+fn hook_command_parts(command_template: &str, file: &Path, name: &str) -> Option<Vec<String>> {
+    let folder = file
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let vars = template::Vars::new()
+        .with("file", file.to_string_lossy())
+        .with("name", name)
+        .with("folder", folder);
+    let parts = shlex::split(command_template)?;
+    Some(
+        parts
+            .iter()
+            .map(|part| template::render(part, &vars))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_placeholders_per_argument() {
+        let parts = hook_command_parts(
+            "svgo '%file%' --title %name|kebab% --out=%folder%/min",
+            Path::new("src/icons/my heart.svg"),
+            "IconMyHeart",
+        )
+        .unwrap();
+        assert_eq!(
+            parts,
+            [
+                "svgo",
+                "src/icons/my heart.svg",
+                "--title",
+                "icon-my-heart",
+                "--out=src/icons/min"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_hook_reports_its_config_key() {
+        let hooks = Hooks {
+            pre_add: Some("sh -c 'echo nope >&2; exit 3'".to_string()),
+            ..Hooks::default()
+        };
+        let error = hooks
+            .run(Hook::PreAdd, Path::new("icons/heart.svg"), "IconHeart")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("pre_add_cmd failed for icons/heart.svg"));
+        assert!(error.ends_with(": nope"));
+        assert_eq!(
+            hooks
+                .run(Hook::PostAdd, Path::new("icons/heart.svg"), "IconHeart")
+                .unwrap(),
+            ""
+        );
+    }
+}
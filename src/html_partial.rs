@@ -0,0 +1,199 @@
+// HTML partial preset: server-rendered apps (Rails, Django, Laravel) inline
+// SVGs with an include, so `--preset html` writes `_heart.svg.html` partials
+// instead of modules and lists them in `partials.json`, keyed by the partial
+// name:
+//
+//     { "heart": "_heart.svg.html" }
+//
+// `html_class_hook` puts a template expression in the root's `class`
+// attribute (`{{ class }}`, `<%= css_class %>`), so a call site can style the
+// icon it includes. iconmate owns the manifest; it's rewritten on add, delete
+// and rename.
+
+use std::collections::BTreeMap;
+
+use crate::index_writer::IndexWriter;
+use crate::utils::IconEntry;
+
+pub const MANIFEST_FILE: &str = "partials.json";
+
+/// `heart` -> `_heart`, the leading underscore partials use.
+pub fn partial_stem(stem: &str) -> String {
+    if stem.starts_with('_') {
+        stem.to_string()
+    } else {
+        format!("_{stem}")
+    }
+}
+
+/// `<svg viewBox="...">` -> `<svg viewBox="..." class="{{ class }}">`. An
+/// existing class keeps its value, with the hook after it.
+pub fn with_class_hook(svg: &str, hook: &str) -> String {
+    let Some(start) = svg.find("<svg") else {
+        return svg.to_string();
+    };
+    let Some(tag_len) = svg[start..].find('>') else {
+        return svg.to_string();
+    };
+    let tag = &svg[start..start + tag_len];
+    let (head, tail) = tag
+        .strip_suffix('/')
+        .map_or((tag, ""), |head| (head.trim_end(), "/"));
+    let tag = match head.find(" class=\"") {
+        Some(class) => {
+            let value_start = class + " class=\"".len();
+            match head[value_start..].find('"') {
+                Some(value_len) => {
+                    let value_end = value_start + value_len;
+                    let separator = if value_len == 0 { "" } else { " " };
+                    format!(
+                        "{}{separator}{hook}{}{tail}",
+                        &head[..value_end],
+                        &head[value_end..]
+                    )
+                }
+                None => return svg.to_string(),
+            }
+        }
+        None => format!("{head} class=\"{hook}\"{tail}"),
+    };
+    format!("{}{tag}{}", &svg[..start], &svg[start + tag_len..])
+}
+
+pub fn manifest_writer() -> &'static dyn IndexWriter {
+    &ManifestWriter
+}
+
+/// `partials.json`: partial names to file names, sorted by name.
+struct ManifestWriter;
+
+fn parse_manifest(contents: &str) -> BTreeMap<String, String> {
+    serde_json::from_str(contents).unwrap_or_default()
+}
+
+fn render_manifest(partials: &BTreeMap<String, String>) -> String {
+    let mut json = serde_json::to_string_pretty(partials).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+fn file_name_of(path: &str) -> &str {
+    path.trim().trim_start_matches("./")
+}
+
+/// `_heart.svg.html` -> `heart`, the manifest key.
+fn partial_name(file_name: &str) -> &str {
+    let name = file_name_of(file_name).trim_start_matches('_');
+    name.split('.').next().unwrap_or(name)
+}
+
+impl IndexWriter for ManifestWriter {
+    fn file_name(&self) -> &'static str {
+        MANIFEST_FILE
+    }
+
+    fn export_name(&self, name: &str) -> String {
+        crate::template::apply_filter("kebab", name).unwrap_or_else(|| name.to_string())
+    }
+
+    fn render_entry(&self, name: &str, _folder: &str, file_name: &str) -> String {
+        format!(
+            "{}: {}",
+            serde_json::Value::from(name),
+            serde_json::Value::from(file_name)
+        )
+    }
+
+    fn parse(&self, contents: &str) -> Vec<IconEntry> {
+        parse_manifest(contents)
+            .into_iter()
+            .map(|(name, file_name)| IconEntry {
+                name,
+                file_path: format!("./{file_name}"),
+            })
+            .collect()
+    }
+
+    fn create(&self, entry: &str) -> String {
+        self.append("{}", entry)
+    }
+
+    fn append(&self, contents: &str, entry: &str) -> String {
+        let mut partials = parse_manifest(contents);
+        partials.extend(parse_manifest(&format!("{{{entry}}}")));
+        render_manifest(&partials)
+    }
+
+    fn remove(&self, contents: &str, file_path: &str) -> Option<String> {
+        let mut partials = parse_manifest(contents);
+        let count = partials.len();
+        partials.retain(|_, file_name| file_name_of(file_name) != file_name_of(file_path));
+        (partials.len() != count).then(|| render_manifest(&partials))
+    }
+
+    /// The key follows the file, like it does on add.
+    fn rename(&self, contents: &str, from: &str, to: &str) -> Option<String> {
+        let mut partials = parse_manifest(contents);
+        let old_name = partials
+            .iter()
+            .find(|(_, file_name)| file_name_of(file_name) == file_name_of(from))
+            .map(|(name, _)| name.clone())?;
+        partials.remove(&old_name);
+        partials.insert(partial_name(to).to_string(), file_name_of(to).to_string());
+        Some(render_manifest(&partials))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_the_class_hook_to_the_root() {
+        assert_eq!(
+            with_class_hook("<svg viewBox=\"0 0 24 24\"><path/></svg>", "{{ class }}"),
+            "<svg viewBox=\"0 0 24 24\" class=\"{{ class }}\"><path/></svg>"
+        );
+        assert_eq!(
+            with_class_hook("<svg class=\"icon\" />", "<%= css_class %>"),
+            "<svg class=\"icon <%= css_class %>\"/>"
+        );
+        assert_eq!(partial_stem("heart"), "_heart");
+        assert_eq!(partial_stem("_heart"), "_heart");
+    }
+
+    #[test]
+    fn manifest_round_trips() {
+        let writer = manifest_writer();
+        let name = writer.export_name("HeartOutline");
+        assert_eq!(name, "heart-outline");
+        let contents = writer.create(&writer.render_entry(&name, "", "_heart-outline.svg.html"));
+        let contents = writer.append(
+            &contents,
+            &writer.render_entry("bell", "", "_bell.svg.html"),
+        );
+        assert_eq!(
+            contents,
+            "{\n  \"bell\": \"_bell.svg.html\",\n  \"heart-outline\": \"_heart-outline.svg.html\"\n}\n"
+        );
+        assert_eq!(
+            writer
+                .parse(&contents)
+                .into_iter()
+                .map(|entry| entry.file_path)
+                .collect::<Vec<_>>(),
+            ["./_bell.svg.html", "./_heart-outline.svg.html"]
+        );
+
+        let renamed = writer
+            .rename(&contents, "./_bell.svg.html", "_alarm.svg.html")
+            .unwrap();
+        assert!(renamed.contains("\"alarm\": \"_alarm.svg.html\""));
+        assert!(!renamed.contains("\"bell\""));
+        assert_eq!(
+            writer.remove(&renamed, "_heart-outline.svg.html").unwrap(),
+            "{\n  \"alarm\": \"_alarm.svg.html\"\n}\n"
+        );
+        assert_eq!(writer.remove(&contents, "_gone.svg.html"), None);
+    }
+}
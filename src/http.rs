@@ -0,0 +1,404 @@
+// The one HTTP client behind every download: Iconify API calls, icon URLs in
+// `add`, and TUI previews.
+//
+// Sharing a client shares its connection pool, so a TUI session or a batch add
+// reuses connections instead of paying a TLS handshake per icon. Proxies come
+// from the usual `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY` variables, or from
+// `ICONMATE_PROXY` to route iconmate alone. Connection failures, timeouts,
+// `429` and `5xx` answers are retried with exponential backoff
+// (`ICONMATE_HTTP_RETRIES`, default 2, 0 to disable).
+//
+// Requests identify as `iconmate/<version>`, and each one's timing (up to the
+// response headers, retries included) is kept in a short in-memory log. The
+// CLI prints it with `--verbose`; the TUI shows it in the F12 overlay, which
+// helps pin down the slow endpoint of a self-hosted Iconify mirror.
+//
+// `request_headers` in the global config adds static headers (a session
+// cookie, a bearer token) to every request under a URL prefix, for mirrors
+// behind an SSO proxy. Logs name those headers but never print their values.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use reqwest::{Client, IntoUrl, RequestBuilder, Response, StatusCode};
+
+pub const PROXY_ENV: &str = "ICONMATE_PROXY";
+pub const RETRIES_ENV: &str = "ICONMATE_HTTP_RETRIES";
+
+const DEFAULT_RETRIES: u32 = 2;
+const MAX_RETRIES: u32 = 5;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(300);
+pub const USER_AGENT: &str = concat!("iconmate/", env!("CARGO_PKG_VERSION"));
+/// Timings kept for the TUI overlay.
+const RECENT_LIMIT: usize = 32;
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static RECENT: Mutex<VecDeque<RequestTiming>> = Mutex::new(VecDeque::new());
+static REQUEST_HEADERS: Mutex<Vec<RequestHeaders>> = Mutex::new(Vec::new());
+
+/// Print every request's timing to stderr (`--verbose`).
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Headers sent with every request under `url_prefix`: same scheme, host and
+/// port, and a path that continues the prefix's at a `/`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestHeaders {
+    pub url_prefix: String,
+    /// Values are marked sensitive, so `Debug` doesn't print them either.
+    pub headers: HeaderMap,
+}
+
+/// The configured `request_headers` for the rest of the process.
+pub fn set_request_headers(rules: Vec<RequestHeaders>) {
+    if let Ok(mut current) = REQUEST_HEADERS.lock() {
+        *current = rules;
+    }
+}
+
+/// Whether `url` is under `prefix`. `https://icons.corp` covers
+/// `https://icons.corp/x.svg` but not `https://icons.corp.evil.com/`, and
+/// `https://icons.corp/api` covers `/api/x.svg` but not `/apikeys`.
+pub fn url_is_under(prefix: &reqwest::Url, url: &reqwest::Url) -> bool {
+    if prefix.scheme() != url.scheme()
+        || prefix.host_str() != url.host_str()
+        || prefix.port_or_known_default() != url.port_or_known_default()
+    {
+        return false;
+    }
+    let (base, path) = (prefix.path(), url.path());
+    path == base
+        || (base.ends_with('/') && path.starts_with(base))
+        || path
+            .strip_prefix(base)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The configured headers for `url`. Where prefixes overlap, the longer one
+/// wins.
+fn request_headers_for(url: &str) -> HeaderMap {
+    let Ok(url) = reqwest::Url::parse(url) else {
+        return HeaderMap::new();
+    };
+    let mut rules = REQUEST_HEADERS
+        .lock()
+        .map(|rules| {
+            rules
+                .iter()
+                .filter(|rule| {
+                    reqwest::Url::parse(&rule.url_prefix)
+                        .is_ok_and(|prefix| url_is_under(&prefix, &url))
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    rules.sort_by_key(|rule| rule.url_prefix.len());
+    let mut headers = HeaderMap::new();
+    for rule in rules {
+        for (name, value) in &rule.headers {
+            headers.insert(name, value.clone());
+        }
+    }
+    headers
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestTiming {
+    pub method: String,
+    pub url: String,
+    /// `None` when no response came back.
+    pub status: Option<u16>,
+    pub attempts: u32,
+    pub elapsed: Duration,
+    /// Names of the configured headers sent along.
+    pub headers: Vec<String>,
+}
+
+impl std::fmt::Display for RequestTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = self
+            .status
+            .map(|status| status.to_string())
+            .unwrap_or_else(|| "failed".to_string());
+        write!(
+            f,
+            "{} {} {status} {} ms",
+            self.method,
+            self.url,
+            self.elapsed.as_millis()
+        )?;
+        if self.attempts > 1 {
+            write!(f, " ({} attempts)", self.attempts)?;
+        }
+        if !self.headers.is_empty() {
+            let headers = self
+                .headers
+                .iter()
+                .map(|name| format!("{name}: <redacted>"))
+                .collect::<Vec<_>>();
+            write!(f, " [{}]", headers.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The most recent requests, newest last.
+pub fn recent_timings() -> Vec<RequestTiming> {
+    RECENT
+        .lock()
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn record(timing: RequestTiming) {
+    if VERBOSE.load(Ordering::Relaxed) {
+        eprintln!("{timing}");
+    }
+    if let Ok(mut recent) = RECENT.lock() {
+        if recent.len() == RECENT_LIMIT {
+            recent.pop_front();
+        }
+        recent.push_back(timing);
+    }
+}
+
+/// The shared client. Cloning is cheap and keeps the same pool.
+pub fn client() -> Client {
+    CLIENT.get_or_init(build_client).clone()
+}
+
+fn build_client() -> Client {
+    let mut builder = Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(CONNECT_TIMEOUT);
+    if let Some(proxy) = std::env::var(PROXY_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+    {
+        match reqwest::Proxy::all(proxy.trim()) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(error) => eprintln!("Ignoring {PROXY_ENV}: {error}"),
+        }
+    }
+    builder.build().unwrap_or_else(|_| Client::new())
+}
+
+fn retries() -> u32 {
+    std::env::var(RETRIES_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_RETRIES)
+        .min(MAX_RETRIES)
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// Sends `request`, retrying transient failures, and records its timing.
+/// The last attempt's result is returned as is, error status included.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let (method, url) = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .map(|request| (request.method().to_string(), request.url().to_string()))
+        .unwrap_or_default();
+    let headers = request_headers_for(&url);
+    let header_names = headers.keys().map(|name| name.to_string()).collect();
+    let request = request.headers(headers);
+    let started = Instant::now();
+    let (result, attempts) = send_with_retries(request).await;
+    record(RequestTiming {
+        method,
+        url,
+        status: match &result {
+            Ok(response) => Some(response.status().as_u16()),
+            Err(error) => error.status().map(|status| status.as_u16()),
+        },
+        attempts,
+        elapsed: started.elapsed(),
+        headers: header_names,
+    });
+    result
+}
+
+async fn send_with_retries(request: RequestBuilder) -> (reqwest::Result<Response>, u32) {
+    let retries = retries();
+    let mut attempt = 0;
+    loop {
+        let Some(this_try) = request.try_clone().filter(|_| attempt < retries) else {
+            return (request.send().await, attempt + 1);
+        };
+        match this_try.send().await {
+            Ok(response) if !is_retryable_status(response.status()) => {
+                return (Ok(response), attempt + 1);
+            }
+            Err(error) if !is_retryable_error(&error) => return (Err(error), attempt + 1),
+            _ => {}
+        }
+        tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+/// GETs `url` through the shared client, failing on a non-success status.
+pub async fn get(url: impl IntoUrl) -> reqwest::Result<Response> {
+    send(client().get(url)).await?.error_for_status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+
+    /// Serves `responses` in order, one connection each.
+    fn serve(responses: &'static [&'static str]) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/icon.svg", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok() && line.trim() != "" {
+                    if let Some(agent) = line.strip_prefix("user-agent: ") {
+                        assert_eq!(agent.trim(), USER_AGENT);
+                    }
+                    line.clear();
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn prefixes_match_on_host_and_path_boundaries() {
+        let under = |prefix: &str, url: &str| {
+            url_is_under(
+                &reqwest::Url::parse(prefix).unwrap(),
+                &reqwest::Url::parse(url).unwrap(),
+            )
+        };
+        assert!(under("https://icons.corp", "https://icons.corp/heart.svg"));
+        assert!(under(
+            "https://icons.corp/api/",
+            "https://icons.corp/api/heart.svg"
+        ));
+        assert!(under(
+            "https://icons.corp/api",
+            "https://icons.corp/api/heart.svg"
+        ));
+        assert!(under(
+            "https://icons.corp:443/",
+            "https://icons.corp/heart.svg"
+        ));
+        assert!(!under(
+            "https://icons.corp",
+            "https://icons.corp.evil.com/heart.svg"
+        ));
+        assert!(!under("https://icons.corp", "http://icons.corp/heart.svg"));
+        assert!(!under(
+            "https://icons.corp",
+            "https://icons.corp:8443/heart.svg"
+        ));
+        assert!(!under(
+            "https://icons.corp/api",
+            "https://icons.corp/apikeys"
+        ));
+        assert!(!under(
+            "https://icons.corp",
+            "https://evil.com/?https://icons.corp"
+        ));
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_but_not_client_errors() {
+        let url = serve(&[
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n<svg/>",
+        ]);
+        let response = get(&url).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "<svg/>");
+        let timing = recent_timings()
+            .into_iter()
+            .rfind(|timing| timing.url == url)
+            .expect("request should be recorded");
+        assert_eq!((timing.status, timing.attempts), (Some(200), 2));
+        assert!(timing.to_string().starts_with(&format!("GET {url} 200 ")));
+        assert!(timing.to_string().ends_with(" ms (2 attempts)"));
+
+        let url = serve(&[
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n<svg/>",
+        ]);
+        let error = get(&url).await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn sends_configured_headers_without_logging_their_values() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut lines = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok() && line.trim() != "" {
+                lines.push(line.trim().to_lowercase());
+                line.clear();
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            lines
+        });
+
+        let rule = |prefix: &str, name, value| {
+            let mut value = reqwest::header::HeaderValue::from_static(value);
+            value.set_sensitive(true);
+            let mut headers = HeaderMap::new();
+            headers.insert(name, value);
+            RequestHeaders {
+                url_prefix: prefix.to_string(),
+                headers,
+            }
+        };
+        set_request_headers(vec![
+            rule(&format!("{base}api/"), "authorization", "Bearer inner"),
+            rule(&base, "authorization", "Bearer outer"),
+            rule(&base, "cookie", "sso=abc"),
+            rule("https://elsewhere.example/", "x-other", "1"),
+            rule(&format!("{base}ap"), "x-partial", "1"),
+        ]);
+        let url = format!("{base}api/icon.svg");
+        get(&url).await.unwrap();
+
+        let lines = server.join().unwrap();
+        assert!(lines.contains(&"authorization: bearer inner".to_string()));
+        assert!(lines.contains(&"cookie: sso=abc".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("x-other")));
+        assert!(!lines.iter().any(|line| line.starts_with("x-partial")));
+        let timing = recent_timings()
+            .into_iter()
+            .rfind(|timing| timing.url == url)
+            .expect("request should be recorded");
+        assert!(
+            timing
+                .to_string()
+                .ends_with(" ms [authorization: <redacted>, cookie: <redacted>]")
+        );
+    }
+}
@@ -0,0 +1,262 @@
+// On-disk cache for Iconify API responses.
+//
+// Collection metadata runs to megabytes, and it rarely changes. Every cached
+// response keeps the `ETag` / `Last-Modified` validators it came with, and
+// the next request for the same URL sends them back as `If-None-Match` /
+// `If-Modified-Since`; a `304 Not Modified` answer is served from disk. Only
+// responses with a validator are stored, since nothing else could be
+// revalidated.
+//
+// Validators alone can't be trusted for icon sets: a CDN may keep answering
+// `304` for an old copy. Entries for one set (`collection?prefix=mdi`,
+// `mdi.json`, `mdi:home.svg`) are stamped with the set's `lastModified` as
+// last seen (in `sets.json`), and a newer `lastModified`, from a fresh set
+// response or from `/last-modified`, drops every entry stamped before it. An
+// icon removed upstream is then never served from disk.
+//
+// Entries live under `$ICONMATE_CACHE_DIR`, or `iconmate/http` in the OS cache
+// directory. Set `ICONMATE_NO_CACHE` to bypass the cache entirely. The cache is
+// best effort: a read or write failure just means a full download.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::RequestBuilder;
+use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+pub const CACHE_DIR_ENV: &str = "ICONMATE_CACHE_DIR";
+pub const NO_CACHE_ENV: &str = "ICONMATE_NO_CACHE";
+
+/// Each set's `lastModified`, as last seen.
+const SETS_FILE: &str = "sets.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+    /// The Iconify set the response belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+    /// The set's `lastModified` when the response was stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set_modified: Option<u64>,
+}
+
+/// The `lastModified` field of IconifyJSON and the API's set responses.
+#[derive(Deserialize)]
+struct SetStamp {
+    #[serde(rename = "lastModified")]
+    last_modified: Option<u64>,
+}
+
+impl CachedResponse {
+    /// Adds the conditional headers that let the server answer `304`.
+    pub fn revalidate(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = match &self.etag {
+            Some(etag) => request.header(IF_NONE_MATCH, etag),
+            None => request,
+        };
+        match &self.last_modified {
+            Some(last_modified) => request.header(IF_MODIFIED_SINCE, last_modified),
+            None => request,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache configured by the environment, or `None` when disabled or
+    /// when there's no cache directory to use.
+    pub fn from_env() -> Option<Self> {
+        if std::env::var_os(NO_CACHE_ENV).is_some() {
+            return None;
+        }
+        let dir = std::env::var_os(CACHE_DIR_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::cache_dir().map(|dir| dir.join("iconmate").join("http")))?;
+        Some(Self::new(dir))
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}.json",
+            crate::checksums::hex_digest(url.as_bytes())
+        ))
+    }
+
+    pub fn lookup(&self, url: &str) -> Option<CachedResponse> {
+        let contents = fs::read_to_string(self.entry_path(url)).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&contents).ok()?;
+        // Guards against a digest collision, however unlikely.
+        (entry.url == url).then_some(entry)
+    }
+
+    /// Records a `200` response, or drops the entry when the server sent no
+    /// validator to revalidate it with. A response for `set` is stamped with
+    /// the set's `lastModified`: its own, or the one last seen.
+    pub fn store(&self, url: &str, headers: &HeaderMap, body: &str, set: Option<&str>) {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let set_modified = set.and_then(|set| {
+            let stamp = body
+                .trim_start()
+                .starts_with('{')
+                .then(|| serde_json::from_str::<SetStamp>(body).ok())
+                .flatten()
+                .and_then(|stamp| stamp.last_modified);
+            match stamp {
+                Some(last_modified) => {
+                    self.set_modified(set, last_modified);
+                    Some(last_modified)
+                }
+                None => self.set_stamps().get(set).copied(),
+            }
+        });
+        let entry = CachedResponse {
+            url: url.to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+            body: body.to_string(),
+            set: set.map(str::to_string),
+            set_modified,
+        };
+        let path = self.entry_path(url);
+        if entry.etag.is_none() && entry.last_modified.is_none() {
+            let _ = fs::remove_file(path);
+            return;
+        }
+        let _ = write_atomically(&path, &entry);
+    }
+
+    /// The sets with a known `lastModified`, to ask `/last-modified` about.
+    pub fn known_sets(&self) -> Vec<String> {
+        let mut sets = self.set_stamps().into_keys().collect::<Vec<_>>();
+        sets.sort();
+        sets
+    }
+
+    /// Records that `set` was last modified at `last_modified` (a Unix
+    /// timestamp). When that's newer than last seen, every entry stamped
+    /// with an older version of the set, or with none, is dropped.
+    pub fn set_modified(&self, set: &str, last_modified: u64) {
+        let mut stamps = self.set_stamps();
+        if stamps.get(set).is_some_and(|&seen| seen >= last_modified) {
+            return;
+        }
+        stamps.insert(set.to_string(), last_modified);
+        let _ = write_atomically(&self.dir.join(SETS_FILE), &stamps);
+
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let stale = fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_str::<CachedResponse>(&contents).ok())
+                .is_some_and(|entry| {
+                    entry.set.as_deref() == Some(set)
+                        && entry.set_modified.is_none_or(|stamp| stamp < last_modified)
+                });
+            if stale {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    fn set_stamps(&self) -> HashMap<String, u64> {
+        fs::read_to_string(self.dir.join(SETS_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Writes through a temp file so a concurrent reader never sees half an entry.
+fn write_atomically(path: &Path, entry: &impl Serialize) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&temp, serde_json::to_string(entry)?)?;
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use tempfile::TempDir;
+
+    #[test]
+    fn stores_only_revalidatable_responses() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path());
+        let url = "https://api.iconify.design/collections";
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        cache.store(url, &headers, "{}", None);
+        let entry = cache.lookup(url).expect("entry should be cached");
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(entry.last_modified, None);
+        assert_eq!(entry.body, "{}");
+        assert!(cache.lookup("https://api.iconify.design/other").is_none());
+
+        cache.store(url, &HeaderMap::new(), "{\"fresh\":true}", None);
+        assert!(cache.lookup(url).is_none());
+    }
+
+    #[test]
+    fn a_newer_set_drops_entries_stored_for_older_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path());
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        let json = "https://api.iconify.design/mdi.json?icons=home";
+        let svg = "https://api.iconify.design/mdi:home.svg";
+        let other = "https://api.iconify.design/lucide:house.svg";
+
+        cache.store(
+            json,
+            &headers,
+            r#"{"prefix":"mdi","lastModified":100,"icons":{}}"#,
+            Some("mdi"),
+        );
+        cache.store(svg, &headers, "<svg/>", Some("mdi"));
+        cache.store(other, &headers, "<svg/>", Some("lucide"));
+        assert_eq!(cache.lookup(svg).unwrap().set_modified, Some(100));
+        assert_eq!(cache.lookup(other).unwrap().set_modified, None);
+        assert_eq!(cache.known_sets(), ["mdi"]);
+
+        // Not newer: nothing changes.
+        cache.set_modified("mdi", 100);
+        assert!(cache.lookup(json).is_some());
+
+        cache.set_modified("mdi", 200);
+        assert!(cache.lookup(json).is_none());
+        assert!(cache.lookup(svg).is_none());
+        assert!(cache.lookup(other).is_some());
+
+        // The next SVG is stamped with the new version and survives it.
+        cache.store(svg, &headers, "<svg/>", Some("mdi"));
+        cache.set_modified("mdi", 200);
+        assert_eq!(cache.lookup(svg).unwrap().set_modified, Some(200));
+    }
+}
@@ -0,0 +1,117 @@
+// Icon name union (`icon_names_dts` local config key).
+//
+// Writes an `icon-names.d.ts` next to `index.ts` exporting
+// `type IconName = 'heart' | 'star' | ...`, one member per export, so apps
+// can type an `<Icon name>` prop against what's actually in the folder. Names
+// are the import paths without `./` and the extension. With the key set, add
+// and `sync --apply` create the file; once it exists, add/delete/rename keep
+// it up to date.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::utils::IconEntry;
+
+pub const ICON_NAMES_FILE: &str = "icon-names.d.ts";
+
+const GENERATED_HEADER: &str = "// GENERATED by iconmate — do not edit by hand.";
+
+/// `./social/github.svg?react` -> `social/github`.
+pub fn icon_name_for_path(file_path: &str) -> String {
+    let path = file_path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    match path.rsplit_once('.') {
+        Some((stem, ext)) if !ext.contains('/') => stem.to_string(),
+        _ => path.to_string(),
+    }
+}
+
+pub fn render_icon_names(entries: &[IconEntry]) -> String {
+    let mut names = entries
+        .iter()
+        .map(|entry| icon_name_for_path(&entry.file_path))
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push_str("\n\n");
+    if names.is_empty() {
+        out.push_str("export type IconName = never;\n");
+        return out;
+    }
+    out.push_str("export type IconName =\n");
+    for name in &names {
+        let escaped = name.replace('\\', "\\\\").replace('\'', "\\'");
+        out.push_str(&format!("  | '{escaped}'\n"));
+    }
+    out.pop();
+    out.push_str(";\n");
+    out
+}
+
+/// Rebuilds `<folder>/icon-names.d.ts` from `<folder>/index.ts` and its groups.
+pub fn write_icon_names(folder: &Path) -> anyhow::Result<PathBuf> {
+    let entries = crate::groups::icons_with_groups(folder).unwrap_or_default();
+    let path = folder.join(ICON_NAMES_FILE);
+    std::fs::write(&path, render_icon_names(&entries))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Regenerates the file when `enabled`, or when the folder already has one.
+pub fn refresh_icon_names(folder: &Path, enabled: bool) -> anyhow::Result<()> {
+    if enabled || folder.join(ICON_NAMES_FILE).exists() {
+        write_icon_names(folder)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, file_path: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_sorted_union_of_file_stems() {
+        let dts = render_icon_names(&[
+            entry("IconStar", "./star"),
+            entry("IconHeart", "./heart.svg?react"),
+            entry("IconGithub", "./social/github.tsx"),
+        ]);
+        assert_eq!(
+            dts,
+            "// GENERATED by iconmate — do not edit by hand.\n\nexport type IconName =\n  | 'heart'\n  | 'social/github'\n  | 'star';\n"
+        );
+        assert!(render_icon_names(&[]).ends_with("export type IconName = never;\n"));
+    }
+
+    #[test]
+    fn refresh_only_creates_the_file_when_enabled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
+
+        refresh_icon_names(dir.path(), false).unwrap();
+        assert!(!dir.path().join(ICON_NAMES_FILE).exists());
+
+        refresh_icon_names(dir.path(), true).unwrap();
+        std::fs::write(dir.path().join("index.ts"), "").unwrap();
+        refresh_icon_names(dir.path(), false).unwrap();
+        let dts = std::fs::read_to_string(dir.path().join(ICON_NAMES_FILE)).unwrap();
+        assert!(dts.contains("= never;"));
+    }
+}
@@ -0,0 +1,159 @@
+// Single `<Icon name>` component (`icon_registry` local config key).
+//
+// Instead of importing one component per icon, apps render
+// `<Icon name="heart" />`. Two files sit next to `index.ts`:
+// `icon-registry.ts` maps every icon name to a dynamic `import()` of its
+// file, so bundlers split each icon into its own chunk, and `Icon.tsx`
+// lazy-loads from it with `React.lazy`. The registry is regenerated from
+// `index.ts` on add, delete, rename and `sync --apply`; `Icon.tsx` is only
+// written when missing, so it can be restyled by hand. Names match
+// `icon-names.d.ts`. The icons' default exports must be React components:
+// the `react` preset, or `.svg` files behind a component loader such as
+// `import_query: "react"`.
+
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::icon_names::icon_name_for_path;
+use crate::utils::IconEntry;
+
+pub const REGISTRY_FILE: &str = "icon-registry.ts";
+pub const ICON_COMPONENT_FILE: &str = "Icon.tsx";
+
+const GENERATED_HEADER: &str = "// GENERATED by iconmate — do not edit by hand.";
+
+const ICON_COMPONENT: &str =
+    "import { lazy, Suspense, type ComponentType, type SVGProps } from 'react';
+import { iconRegistry, type IconName } from './icon-registry';
+
+type IconProps = SVGProps<SVGSVGElement> & { name: IconName };
+
+const components = new Map<IconName, ComponentType<SVGProps<SVGSVGElement>>>();
+
+function load(name: IconName) {
+  let component = components.get(name);
+  if (!component) {
+    component = lazy(iconRegistry[name]);
+    components.set(name, component);
+  }
+  return component;
+}
+
+export function Icon({ name, ...props }: IconProps) {
+  const Component = load(name);
+  return (
+    <Suspense fallback={null}>
+      <Component {...props} />
+    </Suspense>
+  );
+}
+";
+
+pub fn render_registry(entries: &[IconEntry]) -> String {
+    let mut icons = entries
+        .iter()
+        .map(|entry| {
+            (
+                icon_name_for_path(&entry.file_path),
+                entry.file_path.as_str(),
+            )
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .collect::<Vec<_>>();
+    icons.sort();
+    icons.dedup_by(|a, b| a.0 == b.0);
+
+    let quote = |value: &str| format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"));
+    let mut out = String::new();
+    out.push_str(GENERATED_HEADER);
+    out.push_str("\n\nexport const iconRegistry = {\n");
+    for (name, file_path) in &icons {
+        out.push_str(&format!(
+            "  {}: () => import({}),\n",
+            quote(name),
+            quote(file_path)
+        ));
+    }
+    out.push_str("} as const;\n\nexport type IconName = keyof typeof iconRegistry;\n");
+    out
+}
+
+/// Rebuilds `<folder>/icon-registry.ts` from `<folder>/index.ts` and its
+/// groups, and adds `Icon.tsx` if it isn't there.
+pub fn write_icon_registry(folder: &Path) -> anyhow::Result<()> {
+    let entries = crate::groups::icons_with_groups(folder).unwrap_or_default();
+    let path = folder.join(REGISTRY_FILE);
+    std::fs::write(&path, render_registry(&entries))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let component_path = folder.join(ICON_COMPONENT_FILE);
+    if !component_path.exists() {
+        std::fs::write(&component_path, ICON_COMPONENT)
+            .with_context(|| format!("Failed to write {}", component_path.display()))?;
+    }
+    Ok(())
+}
+
+/// Regenerates the registry when `enabled`, or when the folder already has one.
+pub fn refresh_icon_registry(folder: &Path, enabled: bool) -> anyhow::Result<()> {
+    if enabled || folder.join(REGISTRY_FILE).exists() {
+        write_icon_registry(folder)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, file_path: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_dynamic_imports_keyed_by_name() {
+        assert_eq!(
+            render_registry(&[
+                entry("IconStar", "./star"),
+                entry("IconHeart", "./heart.svg?react"),
+            ]),
+            "// GENERATED by iconmate — do not edit by hand.\n\nexport const iconRegistry = {\n  'heart': () => import('./heart.svg?react'),\n  'star': () => import('./star'),\n} as const;\n\nexport type IconName = keyof typeof iconRegistry;\n"
+        );
+    }
+
+    #[test]
+    fn keeps_a_hand_edited_icon_component() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.ts"), "").unwrap();
+
+        refresh_icon_registry(dir.path(), false).unwrap();
+        assert!(!dir.path().join(REGISTRY_FILE).exists());
+
+        refresh_icon_registry(dir.path(), true).unwrap();
+        assert!(
+            std::fs::read_to_string(dir.path().join(ICON_COMPONENT_FILE))
+                .unwrap()
+                .contains("lazy(iconRegistry[name])")
+        );
+        std::fs::write(dir.path().join(ICON_COMPONENT_FILE), "// mine\n").unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart';\n",
+        )
+        .unwrap();
+        refresh_icon_registry(dir.path(), false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(ICON_COMPONENT_FILE)).unwrap(),
+            "// mine\n"
+        );
+        assert!(
+            std::fs::read_to_string(dir.path().join(REGISTRY_FILE))
+                .unwrap()
+                .contains("'heart': () => import('./heart'),")
+        );
+    }
+}
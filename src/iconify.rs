@@ -1,51 +1,99 @@
+use crate::http_cache::HttpCache;
 use reqwest::{Client, StatusCode, Url};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 
 const DEFAULT_ICONIFY_BASE_URL: &str = "https://api.iconify.design";
 pub const ICONIFY_BASE_URL_ENV: &str = "ICONMATE_ICONIFY_BASE_URL";
 
+/// Alias chains in IconifyJSON longer than this are treated as broken.
+pub const MAX_ALIAS_DEPTH: usize = 8;
+
+pub type BackendFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<String, IconifyError>> + Send + 'a>>;
+
+/// Where `IconifyClient` gets response bodies from: the HTTP API, or local
+/// fixtures under `--mock-api` (see `iconify_mock`). `path` and `query` are
+/// the API's, e.g. `collection` + `prefix=mdi` or `mdi:home.svg`.
+pub trait IconifyBackend: std::fmt::Debug + Send + Sync {
+    fn get<'a>(
+        &'a self,
+        path: &'a str,
+        query: &'a [(String, String)],
+        cacheable: bool,
+    ) -> BackendFuture<'a>;
+
+    /// How the request shows up in error messages.
+    fn endpoint(&self, path: &str, query: &[(String, String)]) -> String;
+
+    /// A stored response, read without a request.
+    fn cached(&self, _path: &str, _query: &[(String, String)]) -> Option<String> {
+        None
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IconifyClient {
-    client: Client,
-    base_url: Url,
+    backend: Arc<dyn IconifyBackend>,
 }
 
 impl IconifyClient {
     pub fn new() -> Result<Self, IconifyError> {
-        Self::from_base_url(DEFAULT_ICONIFY_BASE_URL)
+        Ok(Self::with_backend(HttpBackend::new(
+            DEFAULT_ICONIFY_BASE_URL,
+        )?))
     }
 
+    /// The fixtures backend under `--mock-api` (recording from the API with
+    /// `ICONMATE_MOCK_API_RECORD=1`), then a vendored `@iconify/json`,
+    /// otherwise the (cached) API at `ICONMATE_ICONIFY_BASE_URL`.
     pub fn from_env() -> Result<Self, IconifyError> {
         let base = std::env::var(ICONIFY_BASE_URL_ENV)
             .unwrap_or_else(|_| DEFAULT_ICONIFY_BASE_URL.to_string());
-        Self::from_base_url(&base)
+        match crate::iconify_mock::mock_api_dir() {
+            Some(dir) if crate::iconify_mock::recording() => Ok(Self::with_backend(
+                crate::iconify_mock::RecordingBackend::new(dir, HttpBackend::new(&base)?),
+            )),
+            Some(dir) => Ok(Self::with_backend(
+                crate::iconify_mock::FixtureBackend::new(dir),
+            )),
+            None => match crate::iconify_json::package_dir() {
+                Some(dir) => Ok(Self::with_backend(
+                    crate::iconify_json::IconifyJsonBackend::new(dir),
+                )),
+                None => Ok(Self::with_backend(
+                    HttpBackend::new(&base)?.with_cache(HttpCache::from_env()),
+                )),
+            },
+        }
     }
 
-    pub fn from_base_url(base_url: &str) -> Result<Self, IconifyError> {
-        let normalized = if base_url.ends_with('/') {
-            base_url.to_string()
-        } else {
-            format!("{base_url}/")
-        };
-
-        let base_url = Url::parse(&normalized).map_err(|source| IconifyError::InvalidBaseUrl {
-            base_url: base_url.to_string(),
-            source: source.to_string(),
-        })?;
-
-        Ok(Self {
-            client: Client::new(),
-            base_url,
-        })
+    pub fn with_backend(backend: impl IconifyBackend + 'static) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
     }
 
     pub async fn collections(&self) -> Result<IconifyCollectionsResponse, IconifyError> {
-        let collections: HashMap<String, IconifyCollectionMeta> =
-            self.get_json("collections", &[]).await?;
+        let collections: HashMap<String, IconifyCollectionMeta> = self
+            .get_json("collections", &[])
+            .await
+            .map_err(|error| error.or_unsupported(IconifyFeature::Collections))?;
         Ok(IconifyCollectionsResponse { collections })
     }
 
+    /// The last `collections()` response on disk, read without touching the
+    /// network. Lets the TUI show something when the API is unreachable.
+    pub fn cached_collections(&self) -> Option<IconifyCollectionsResponse> {
+        let body = self.backend.cached("collections", &[])?;
+        let collections = decode_json(&self.backend.endpoint("collections", &[]), &body).ok()?;
+        Some(IconifyCollectionsResponse { collections })
+    }
+
     pub async fn collection(
         &self,
         prefix: &str,
@@ -67,6 +115,24 @@ impl IconifyClient {
         })
     }
 
+    /// The character map of an emoji set: codepoints in hex (`1f525`,
+    /// `2764-fe0f`) to icon names.
+    pub async fn collection_chars(
+        &self,
+        prefix: &str,
+    ) -> Result<HashMap<String, String>, IconifyError> {
+        let response: IconifyCollectionCharsResponse = self
+            .get_json(
+                "collection",
+                &[
+                    ("prefix".to_string(), prefix.to_string()),
+                    ("chars".to_string(), "true".to_string()),
+                ],
+            )
+            .await?;
+        Ok(response.chars)
+    }
+
     pub async fn search(
         &self,
         query: &str,
@@ -84,7 +150,13 @@ impl IconifyClient {
             params.push(("start".to_string(), start.to_string()));
         }
 
-        let mut response: IconifySearchResponse = self.get_json("search", &params).await?;
+        // Search results depend on the query and go stale fast; not cached.
+        let mut response: IconifySearchResponse = async {
+            let body = self.backend.get("search", &params, false).await?;
+            decode_json(&self.backend.endpoint("search", &params), &body)
+        }
+        .await
+        .map_err(|error| error.or_unsupported(IconifyFeature::Search))?;
 
         if !include_collections {
             response.collections = None;
@@ -93,9 +165,23 @@ impl IconifyClient {
         Ok(response)
     }
 
+    /// The icon's SVG. Servers that only host icon data have no `.svg`
+    /// endpoint; the SVG is then built from the icon's JSON, as the API
+    /// would.
     pub async fn svg(&self, prefix_icon: &str) -> Result<String, IconifyError> {
         let path = format!("{prefix_icon}.svg");
-        self.get_text(&path, &[]).await
+        match self.backend.get(&path, &[], true).await {
+            Err(error) if error.is_missing_endpoint() => {
+                let Some((prefix, icon)) = prefix_icon.split_once(':') else {
+                    return Err(error);
+                };
+                match self.icon_json(prefix, icon).await {
+                    Ok(data) => build_svg(&data, icon).ok_or(error),
+                    Err(_) => Err(error),
+                }
+            }
+            response => response,
+        }
     }
 
     pub async fn icon_json(
@@ -118,50 +204,102 @@ impl IconifyClient {
         self.icon_json(prefix, icon).await
     }
 
+    /// The `prefix:icon` that `prefix_icon` renames, when it's an alias kept
+    /// for an old name; `None` for an icon of its own.
+    pub async fn canonical_name(&self, prefix_icon: &str) -> Result<Option<String>, IconifyError> {
+        let (prefix, icon) = prefix_icon
+            .split_once(':')
+            .ok_or_else(|| IconifyError::InvalidIconName(prefix_icon.to_string()))?;
+        let data = self.icon_json(prefix, icon).await?;
+        Ok(canonical_icon_name(&data, icon).map(|name| format!("{prefix}:{name}")))
+    }
+
     async fn get_json<T: DeserializeOwned>(
         &self,
         path: &str,
         query: &[(String, String)],
     ) -> Result<T, IconifyError> {
-        let url = self.build_url(path, query)?;
-        let endpoint = url.to_string();
+        let body = self.backend.get(path, query, true).await?;
+        decode_json(&self.backend.endpoint(path, query), &body)
+    }
+}
 
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(IconifyError::Network)?;
-        let status = response.status();
-        let body = response.text().await.map_err(IconifyError::Network)?;
+/// The Iconify HTTP API at `base_url`.
+#[derive(Debug, Clone)]
+pub struct HttpBackend {
+    client: Client,
+    base_url: Url,
+    /// Revalidated with conditional requests; see `http_cache`.
+    cache: Option<HttpCache>,
+}
 
-        if !status.is_success() {
-            return Err(IconifyError::HttpStatus {
-                status,
-                endpoint,
-                body,
-            });
-        }
+impl HttpBackend {
+    pub fn new(base_url: &str) -> Result<Self, IconifyError> {
+        let normalized = if base_url.ends_with('/') {
+            base_url.to_string()
+        } else {
+            format!("{base_url}/")
+        };
+
+        let base_url = Url::parse(&normalized).map_err(|source| IconifyError::InvalidBaseUrl {
+            base_url: base_url.to_string(),
+            source: source.to_string(),
+        })?;
 
-        serde_json::from_str(&body).map_err(|source| IconifyError::JsonDecode { endpoint, source })
+        Ok(Self {
+            client: crate::http::client(),
+            base_url,
+            cache: None,
+        })
+    }
+
+    pub fn with_cache(mut self, cache: Option<HttpCache>) -> Self {
+        self.cache = cache;
+        self
     }
 
-    async fn get_text(
+    /// GETs `path`, revalidating a cached copy when `cacheable` is set: the
+    /// cached validators go out as conditional headers and a `304` is served
+    /// from disk.
+    async fn get_body(
         &self,
         path: &str,
         query: &[(String, String)],
+        cacheable: bool,
     ) -> Result<String, IconifyError> {
         let url = self.build_url(path, query)?;
         let endpoint = url.to_string();
+        let cache = self.cache.as_ref().filter(|_| cacheable);
+        // The TUI lists collections first thing; a good moment to drop
+        // entries for sets that changed upstream.
+        if path == "collections"
+            && let Some(cache) = cache
+        {
+            self.refresh_set_stamps(cache).await;
+        }
+        let cached = cache.and_then(|cache| cache.lookup(&endpoint));
 
-        let response = self
-            .client
-            .get(url)
-            .send()
+        let mut request = self.client.get(url);
+        if let Some(cached) = &cached {
+            request = cached.revalidate(request);
+        }
+        let response = crate::http::send(request)
             .await
             .map_err(IconifyError::Network)?;
         let status = response.status();
-        let body = response.text().await.map_err(IconifyError::Network)?;
+        if status == StatusCode::NOT_MODIFIED
+            && let Some(cached) = cached
+        {
+            return Ok(cached.body);
+        }
+
+        let headers = response.headers().clone();
+        let body = crate::progress::read_body(
+            response,
+            format!("Fetching {}", path.trim_start_matches('/')),
+        )
+        .await
+        .map_err(IconifyError::Network)?;
 
         if !status.is_success() {
             return Err(IconifyError::HttpStatus {
@@ -171,9 +309,37 @@ impl IconifyClient {
             });
         }
 
+        if let Some(cache) = cache {
+            cache.store(&endpoint, &headers, &body, request_set(path, query));
+        }
         Ok(body)
     }
 
+    /// Asks `/last-modified` about the sets the cache holds. Best effort:
+    /// servers without the endpoint just keep relying on validators.
+    async fn refresh_set_stamps(&self, cache: &HttpCache) {
+        let sets = cache.known_sets();
+        if sets.is_empty() {
+            return;
+        }
+        let query = [("prefixes".to_string(), sets.join(","))];
+        let Ok(url) = self.build_url("last-modified", &query) else {
+            return;
+        };
+        let Ok(response) = crate::http::send(self.client.get(url)).await else {
+            return;
+        };
+        if !response.status().is_success() {
+            return;
+        }
+        let Ok(response) = response.json::<IconifyLastModifiedResponse>().await else {
+            return;
+        };
+        for (set, last_modified) in response.last_modified {
+            cache.set_modified(&set, last_modified);
+        }
+    }
+
     fn build_url(&self, path: &str, query: &[(String, String)]) -> Result<Url, IconifyError> {
         let relative_path = if path.starts_with('/') {
             format!("./{}", path.trim_start_matches('/'))
@@ -200,6 +366,51 @@ impl IconifyClient {
     }
 }
 
+impl IconifyBackend for HttpBackend {
+    fn get<'a>(
+        &'a self,
+        path: &'a str,
+        query: &'a [(String, String)],
+        cacheable: bool,
+    ) -> BackendFuture<'a> {
+        Box::pin(self.get_body(path, query, cacheable))
+    }
+
+    fn endpoint(&self, path: &str, query: &[(String, String)]) -> String {
+        self.build_url(path, query)
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| path.to_string())
+    }
+
+    fn cached(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        let url = self.build_url(path, query).ok()?;
+        Some(self.cache.as_ref()?.lookup(url.as_str())?.body)
+    }
+}
+
+/// The set a request reads from: `collection?prefix=mdi`, `mdi.json` and
+/// `mdi:home.svg` are all `mdi`.
+fn request_set<'a>(path: &'a str, query: &'a [(String, String)]) -> Option<&'a str> {
+    match path {
+        "collection" => query
+            .iter()
+            .find(|(key, _)| key == "prefix")
+            .map(|(_, prefix)| prefix.as_str()),
+        _ => path.strip_suffix(".json").or_else(|| {
+            path.strip_suffix(".svg")?
+                .split_once(':')
+                .map(|(prefix, _)| prefix)
+        }),
+    }
+}
+
+fn decode_json<T: DeserializeOwned>(endpoint: &str, body: &str) -> Result<T, IconifyError> {
+    serde_json::from_str(body).map_err(|source| IconifyError::JsonDecode {
+        endpoint: endpoint.to_string(),
+        source,
+    })
+}
+
 fn merge_collection_icons(
     icons: Vec<String>,
     uncategorized: Option<&Vec<String>>,
@@ -229,6 +440,167 @@ fn merge_collection_icons(
     deduped
 }
 
+/// The viewBox and transforms of an icon, with an alias's on top of its
+/// parent's.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Props {
+    left: Option<f64>,
+    top: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+    rotate: u64,
+    h_flip: bool,
+    v_flip: bool,
+}
+
+impl Props {
+    fn read(value: &Value) -> Self {
+        let number = |key: &str| value.get(key).and_then(Value::as_f64);
+        let flag = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+        Self {
+            left: number("left"),
+            top: number("top"),
+            width: number("width"),
+            height: number("height"),
+            rotate: value.get("rotate").and_then(Value::as_u64).unwrap_or(0),
+            h_flip: flag("hFlip"),
+            v_flip: flag("vFlip"),
+        }
+    }
+
+    /// `self` (the alias) applied over `parent`.
+    fn over(self, parent: Self) -> Self {
+        Self {
+            left: self.left.or(parent.left),
+            top: self.top.or(parent.top),
+            width: self.width.or(parent.width),
+            height: self.height.or(parent.height),
+            rotate: (self.rotate + parent.rotate) % 4,
+            h_flip: self.h_flip != parent.h_flip,
+            v_flip: self.v_flip != parent.v_flip,
+        }
+    }
+}
+
+/// The body of `name`, following aliases, and its combined props.
+fn resolve(set: &Value, name: &str) -> Option<(String, Props)> {
+    let mut props = Props::default();
+    let mut name = name.to_string();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        if let Some(icon) = set.get("icons").and_then(|icons| icons.get(&name)) {
+            let body = icon.get("body")?.as_str()?.to_string();
+            return Some((body, props.over(Props::read(icon))));
+        }
+        let alias = set.get("aliases")?.get(&name)?;
+        props = props.over(Props::read(alias));
+        name = alias.get("parent")?.as_str()?.to_string();
+    }
+    None
+}
+
+/// The icon `name` renames in IconifyJSON data. Only plain aliases (just a
+/// `parent`) count: one that flips, rotates or resizes its parent is an icon
+/// of its own, and the chain stops there.
+pub fn canonical_icon_name(set: &Value, name: &str) -> Option<String> {
+    let mut current = name.to_string();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let is_icon = set
+            .get("icons")
+            .and_then(|icons| icons.get(&current))
+            .is_some();
+        let alias = set
+            .get("aliases")
+            .and_then(|aliases| aliases.get(&current))
+            .and_then(Value::as_object);
+        let parent = match alias {
+            Some(alias) if !is_icon && alias.len() == 1 => {
+                alias.get("parent").and_then(Value::as_str)
+            }
+            _ => None,
+        };
+        match parent {
+            Some(parent) => current = parent.to_string(),
+            None => return (current != name).then_some(current),
+        }
+    }
+    None
+}
+
+/// `name` from IconifyJSON data (a whole set, or a `/<prefix>.json?icons=`
+/// response) as the API's `/<prefix>:<icon>.svg` returns it: the body, or an
+/// alias's parent flipped and rotated as the alias says, in an `<svg>` with
+/// the icon's viewBox.
+pub fn build_svg(set: &Value, name: &str) -> Option<String> {
+    let (mut body, props) = resolve(set, name)?;
+    let props = props.over(Props::read(set));
+    let mut left = props.left.unwrap_or(0.0);
+    let mut top = props.top.unwrap_or(0.0);
+    let mut width = props.width.unwrap_or(16.0);
+    let mut height = props.height.unwrap_or(16.0);
+
+    // Same steps as Iconify's `iconToSVG`.
+    let mut rotate = props.rotate;
+    let mut transforms = Vec::new();
+    if props.h_flip {
+        if props.v_flip {
+            rotate += 2;
+        } else {
+            transforms.push(format!("translate({} {})", width + left, 0.0 - top));
+            transforms.push("scale(-1 1)".to_string());
+            top = 0.0;
+            left = 0.0;
+        }
+    } else if props.v_flip {
+        transforms.push(format!("translate({} {})", 0.0 - left, height + top));
+        transforms.push("scale(1 -1)".to_string());
+        top = 0.0;
+        left = 0.0;
+    }
+    match rotate % 4 {
+        1 => {
+            let center = height / 2.0 + top;
+            transforms.insert(0, format!("rotate(90 {center} {center})"));
+        }
+        2 => transforms.insert(
+            0,
+            format!("rotate(180 {} {})", width / 2.0 + left, height / 2.0 + top),
+        ),
+        3 => {
+            let center = width / 2.0 + left;
+            transforms.insert(0, format!("rotate(-90 {center} {center})"));
+        }
+        _ => {}
+    }
+    if rotate % 2 == 1 {
+        std::mem::swap(&mut left, &mut top);
+        std::mem::swap(&mut width, &mut height);
+    }
+    if !transforms.is_empty() {
+        body = format!("<g transform=\"{}\">{body}</g>", transforms.join(" "));
+    }
+
+    Some(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1em\" height=\"1em\" viewBox=\"{left} {top} {width} {height}\">{body}</svg>"
+    ))
+}
+
+/// Endpoints a self-hosted Iconify server may leave out: many serve only
+/// icon data (`/{prefix}.json`, `/{prefix}/{icon}.svg`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconifyFeature {
+    Collections,
+    Search,
+}
+
+impl std::fmt::Display for IconifyFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IconifyFeature::Collections => write!(f, "collection listing"),
+            IconifyFeature::Search => write!(f, "icon search"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum IconifyError {
     InvalidBaseUrl {
@@ -250,6 +622,50 @@ pub enum IconifyError {
         endpoint: String,
         source: serde_json::Error,
     },
+    /// The server has no endpoint for `feature`.
+    Unsupported {
+        feature: IconifyFeature,
+        endpoint: String,
+    },
+}
+
+impl IconifyError {
+    /// Reads a missing endpoint (`404`, `405`, `501`, or a page that isn't
+    /// JSON at all, like a static host's `index.html`) as the server not
+    /// offering `feature`, rather than as a failed request.
+    fn or_unsupported(self, feature: IconifyFeature) -> Self {
+        match self {
+            IconifyError::HttpStatus { endpoint, .. } if self.is_missing_endpoint() => {
+                IconifyError::Unsupported { feature, endpoint }
+            }
+            IconifyError::JsonDecode { endpoint, source }
+                if source.is_syntax() && source.line() == 1 && source.column() == 1 =>
+            {
+                IconifyError::Unsupported { feature, endpoint }
+            }
+            error => error,
+        }
+    }
+
+    /// A `404`, `405` or `501`: the server has nothing at that path.
+    fn is_missing_endpoint(&self) -> bool {
+        matches!(
+            self,
+            IconifyError::HttpStatus {
+                status: StatusCode::NOT_FOUND
+                    | StatusCode::METHOD_NOT_ALLOWED
+                    | StatusCode::NOT_IMPLEMENTED,
+                ..
+            }
+        )
+    }
+
+    pub fn unsupported_feature(&self) -> Option<IconifyFeature> {
+        match self {
+            IconifyError::Unsupported { feature, .. } => Some(*feature),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for IconifyError {
@@ -279,6 +695,12 @@ impl std::fmt::Display for IconifyError {
                     "failed to parse Iconify response from {endpoint}: {source}"
                 )
             }
+            IconifyError::Unsupported { feature, endpoint } => {
+                write!(
+                    f,
+                    "this Iconify server doesn't offer {feature} ({endpoint} is missing)"
+                )
+            }
         }
     }
 }
@@ -292,6 +714,7 @@ impl std::error::Error for IconifyError {
             IconifyError::Network(source) => Some(source),
             IconifyError::JsonDecode { source, .. } => Some(source),
             IconifyError::HttpStatus { .. } => None,
+            IconifyError::Unsupported { .. } => None,
         }
     }
 }
@@ -339,6 +762,46 @@ impl IconifyCollectionMeta {
 
         fallback.to_string()
     }
+
+    /// `author.name` of the collection's metadata.
+    pub fn author(&self) -> Option<String> {
+        self.extra
+            .get("author")?
+            .get("name")?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// `license.title`, with the SPDX id when it differs: `Apache 2.0 (Apache-2.0)`.
+    pub fn license(&self) -> Option<String> {
+        let license = self.extra.get("license")?;
+        let title = license.get("title").and_then(|title| title.as_str());
+        let spdx = license.get("spdx").and_then(|spdx| spdx.as_str());
+        match (title, spdx) {
+            (Some(title), Some(spdx)) if title != spdx => Some(format!("{title} ({spdx})")),
+            (Some(title), _) => Some(title.to_string()),
+            (None, spdx) => spdx.map(str::to_string),
+        }
+    }
+
+    /// Whether the icons have their own colors rather than `currentColor`.
+    pub fn palette(&self) -> Option<bool> {
+        self.extra.get("palette")?.as_bool()
+    }
+
+    /// A few icon names (without the prefix) the set picks to represent it.
+    pub fn samples(&self) -> Vec<String> {
+        self.extra
+            .get("samples")
+            .and_then(|samples| samples.as_array())
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter_map(|sample| sample.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -361,6 +824,19 @@ struct IconifyCollectionApiResponse {
     pub categories: Option<HashMap<String, Vec<String>>>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct IconifyCollectionCharsResponse {
+    #[serde(default)]
+    pub chars: HashMap<String, String>,
+}
+
+/// `/last-modified?prefixes=`: each set's last change, as a Unix timestamp.
+#[derive(Debug, Clone, Deserialize)]
+struct IconifyLastModifiedResponse {
+    #[serde(rename = "lastModified", default)]
+    last_modified: HashMap<String, u64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -418,7 +894,11 @@ mod tests {
         {
             "mdi": {
                 "name": "Material Design Icons",
-                "total": 7447
+                "total": 7447,
+                "author": {"name": "Pictogrammers", "url": "https://github.com/Templarian/MaterialDesign"},
+                "license": {"title": "Apache 2.0", "spdx": "Apache-2.0"},
+                "samples": ["account-check", "bell-alert-outline"],
+                "palette": false
             },
             "heroicons": {
                 "title": "Heroicons",
@@ -433,9 +913,15 @@ mod tests {
         let mdi = response.get("mdi").expect("mdi should exist");
         assert_eq!(mdi.display_name("mdi"), "Material Design Icons");
         assert_eq!(mdi.total, Some(7447));
+        assert_eq!(mdi.author().as_deref(), Some("Pictogrammers"));
+        assert_eq!(mdi.license().as_deref(), Some("Apache 2.0 (Apache-2.0)"));
+        assert_eq!(mdi.samples(), ["account-check", "bell-alert-outline"]);
+        assert_eq!(mdi.palette(), Some(false));
 
         let heroicons = response.get("heroicons").expect("heroicons should exist");
         assert_eq!(heroicons.display_name("heroicons"), "Heroicons");
+        assert_eq!(heroicons.license(), None);
+        assert!(heroicons.samples().is_empty());
     }
 
     #[test]
@@ -484,4 +970,289 @@ mod tests {
             1
         );
     }
+
+    #[tokio::test]
+    async fn revalidates_cached_responses_with_etag() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut conditional_headers = Vec::new();
+            for response in [
+                "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 12\r\nConnection: close\r\n\r\n<svg>1</svg>",
+                "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n",
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut if_none_match = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("if-none-match")
+                    {
+                        if_none_match = Some(value.trim().to_string());
+                    }
+                }
+                conditional_headers.push(if_none_match);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            conditional_headers
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let client = IconifyClient::with_backend(
+            HttpBackend::new(&base_url)
+                .unwrap()
+                .with_cache(Some(HttpCache::new(temp_dir.path()))),
+        );
+        assert_eq!(client.svg("mdi:home").await.unwrap(), "<svg>1</svg>");
+        assert_eq!(client.svg("mdi:home").await.unwrap(), "<svg>1</svg>");
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![None, Some("\"v1\"".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn listing_collections_drops_entries_for_sets_changed_upstream() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut paths = Vec::new();
+            for body in [r#"{"lastModified":{"mdi":200}}"#, "{}"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                paths.push(request_line.split(' ').nth(1).unwrap().to_string());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            paths
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = HttpCache::new(temp_dir.path());
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::ETAG,
+            reqwest::header::HeaderValue::from_static("\"v1\""),
+        );
+        let svg = format!("{base_url}mdi:home.svg");
+        cache.store(
+            &format!("{base_url}mdi.json?icons=home"),
+            &headers,
+            r#"{"prefix":"mdi","lastModified":100,"icons":{}}"#,
+            request_set("mdi.json", &[]),
+        );
+        cache.store(&svg, &headers, "<svg/>", request_set("mdi:home.svg", &[]));
+        assert!(cache.lookup(&svg).is_some());
+
+        let client = IconifyClient::with_backend(
+            HttpBackend::new(&base_url)
+                .unwrap()
+                .with_cache(Some(cache.clone())),
+        );
+        client.collections().await.unwrap();
+
+        assert_eq!(
+            server.join().unwrap(),
+            ["/last-modified?prefixes=mdi", "/collections"]
+        );
+        assert!(cache.lookup(&svg).is_none());
+    }
+
+    /// A server that only hosts icon data, like many self-hosted mirrors.
+    #[derive(Debug)]
+    struct IconDataOnly;
+
+    impl IconifyBackend for IconDataOnly {
+        fn get<'a>(
+            &'a self,
+            path: &'a str,
+            query: &'a [(String, String)],
+            _cacheable: bool,
+        ) -> BackendFuture<'a> {
+            let endpoint = self.endpoint(path, query);
+            Box::pin(async move {
+                match path {
+                    "collections" => Ok("<!doctype html><title>Icons</title>".to_string()),
+                    "mdi:home.svg" => Ok("<svg/>".to_string()),
+                    _ => Err(IconifyError::HttpStatus {
+                        status: StatusCode::NOT_FOUND,
+                        endpoint,
+                        body: String::new(),
+                    }),
+                }
+            })
+        }
+
+        fn endpoint(&self, path: &str, _query: &[(String, String)]) -> String {
+            format!("https://icons.example.com/{path}")
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_listing_and_search_endpoints_read_as_unsupported() {
+        let client = IconifyClient::with_backend(IconDataOnly);
+
+        let error = client.collections().await.unwrap_err();
+        assert_eq!(
+            error.unsupported_feature(),
+            Some(IconifyFeature::Collections)
+        );
+        assert_eq!(
+            error.to_string(),
+            "this Iconify server doesn't offer collection listing (https://icons.example.com/collections is missing)"
+        );
+        let error = client.search("home", None, None, false).await.unwrap_err();
+        assert_eq!(error.unsupported_feature(), Some(IconifyFeature::Search));
+
+        // Icon data still works, and a missing collection stays a 404.
+        assert_eq!(client.svg("mdi:home").await.unwrap(), "<svg/>");
+        let error = client.collection("nope").await.unwrap_err();
+        assert!(matches!(error, IconifyError::HttpStatus { .. }));
+    }
+
+    /// A server with `/<prefix>.json` but no `.svg` endpoint.
+    #[derive(Debug)]
+    struct JsonOnly;
+
+    impl IconifyBackend for JsonOnly {
+        fn get<'a>(
+            &'a self,
+            path: &'a str,
+            query: &'a [(String, String)],
+            _cacheable: bool,
+        ) -> BackendFuture<'a> {
+            let endpoint = self.endpoint(path, query);
+            Box::pin(async move {
+                match path {
+                    "demo.json" => Ok(r#"{
+                        "prefix": "demo",
+                        "width": 24,
+                        "height": 24,
+                        "icons": {"arrow": {"body": "<path/>"}},
+                        "aliases": {
+                            "arrow-left": {"parent": "arrow", "hFlip": true},
+                            "arrow-up-left": {"parent": "arrow-left", "rotate": 3},
+                            "arrow-turned": {"parent": "arrow", "hFlip": true, "vFlip": true}
+                        }
+                    }"#
+                    .to_string()),
+                    _ => Err(IconifyError::HttpStatus {
+                        status: StatusCode::NOT_FOUND,
+                        endpoint,
+                        body: String::new(),
+                    }),
+                }
+            })
+        }
+
+        fn endpoint(&self, path: &str, _query: &[(String, String)]) -> String {
+            format!("https://icons.example.com/{path}")
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_svgs_from_icon_json_when_the_svg_endpoint_is_missing() {
+        let client = IconifyClient::with_backend(JsonOnly);
+        let svg = |body: &str| {
+            format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1em\" height=\"1em\" viewBox=\"0 0 24 24\">{body}</svg>"
+            )
+        };
+
+        assert_eq!(client.svg("demo:arrow").await.unwrap(), svg("<path/>"));
+        assert_eq!(
+            client.svg("demo:arrow-left").await.unwrap(),
+            svg("<g transform=\"translate(24 0) scale(-1 1)\"><path/></g>")
+        );
+        // Transforms stack along the alias chain, like Iconify's `iconToSVG`.
+        assert_eq!(
+            client.svg("demo:arrow-up-left").await.unwrap(),
+            svg("<g transform=\"rotate(-90 12 12) translate(24 0) scale(-1 1)\"><path/></g>")
+        );
+        assert_eq!(
+            client.svg("demo:arrow-turned").await.unwrap(),
+            svg("<g transform=\"rotate(180 12 12)\"><path/></g>")
+        );
+
+        // An unknown icon keeps the `.svg` endpoint's 404.
+        let error = client.svg("demo:missing").await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Iconify request failed (404 Not Found) for https://icons.example.com/demo:missing.svg"
+        );
+    }
+
+    #[test]
+    fn canonical_names_follow_plain_aliases_only() {
+        let set = serde_json::json!({
+            "icons": {"home": {"body": "<path/>"}},
+            "aliases": {
+                "house": {"parent": "home"},
+                "house-old": {"parent": "house"},
+                "home-flipped": {"parent": "home", "hFlip": true},
+                "home-flipped-old": {"parent": "home-flipped"},
+                "loop": {"parent": "loop"}
+            }
+        });
+        let canonical = |name| canonical_icon_name(&set, name);
+
+        assert_eq!(canonical("house").as_deref(), Some("home"));
+        assert_eq!(canonical("house-old").as_deref(), Some("home"));
+        assert_eq!(
+            canonical("home-flipped-old").as_deref(),
+            Some("home-flipped")
+        );
+        assert_eq!(canonical("home"), None);
+        assert_eq!(canonical("home-flipped"), None);
+        assert_eq!(canonical("missing"), None);
+        assert_eq!(canonical("loop"), None);
+    }
+
+    #[test]
+    fn reads_cached_collections_without_a_request() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        // Nothing listens here; a request would fail.
+        let client = IconifyClient::with_backend(
+            HttpBackend::new("http://127.0.0.1:9/")
+                .unwrap()
+                .with_cache(Some(HttpCache::new(temp_dir.path()))),
+        );
+        assert!(client.cached_collections().is_none());
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::ETAG,
+            reqwest::header::HeaderValue::from_static("\"v1\""),
+        );
+        HttpCache::new(temp_dir.path()).store(
+            "http://127.0.0.1:9/collections",
+            &headers,
+            r#"{"mdi":{"name":"Material Design Icons","total":7000}}"#,
+            None,
+        );
+        let cached = client.cached_collections().expect("cached collections");
+        assert_eq!(cached.collections["mdi"].total, Some(7000));
+    }
 }
@@ -0,0 +1,295 @@
+// `@iconify/json` as an Iconify source: listings and SVGs built from the
+// package on disk, without the network.
+//
+// Projects that already vendor every icon set through `@iconify/json` don't
+// need api.iconify.design. `IconifyClient::from_env` uses this backend when
+// `ICONMATE_ICONIFY_JSON` points at the package, or when
+// `node_modules/@iconify/json` sits in the working directory or one above it
+// (`ICONMATE_ICONIFY_JSON=off` skips the lookup). The package holds:
+//
+//   collections.json    `/collections`
+//   json/<prefix>.json  one IconifyJSON set: `/collection?prefix=`, the
+//                       set's `chars`, `/<prefix>:<icon>.svg` and
+//                       `/<prefix>.json?icons=`
+//
+// SVGs are assembled with `iconify::build_svg`, like the API does. The package has no search index, so `/search` answers 404,
+// which reads as a server without search.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use reqwest::StatusCode;
+use serde_json::{Map, Value, json};
+
+use crate::iconify::{BackendFuture, IconifyBackend, IconifyError, MAX_ALIAS_DEPTH, build_svg};
+
+pub const ICONIFY_JSON_ENV: &str = "ICONMATE_ICONIFY_JSON";
+
+/// Parsed sets, shared by every client in the process: MDI alone is several
+/// megabytes of JSON, and the TUI asks for a handful of its icons at a time.
+static SETS: OnceLock<Mutex<HashMap<PathBuf, Arc<Value>>>> = OnceLock::new();
+
+/// The package from `ICONMATE_ICONIFY_JSON`, else the nearest
+/// `node_modules/@iconify/json` from the working directory up.
+pub fn package_dir() -> Option<PathBuf> {
+    match std::env::var_os(ICONIFY_JSON_ENV) {
+        Some(value) if value == "off" => return None,
+        Some(value) if !value.is_empty() => return Some(PathBuf::from(value)),
+        _ => {}
+    }
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join("node_modules").join("@iconify").join("json"))
+        .find(|package| is_package(package))
+}
+
+/// Whether `dir` looks like the package: a `collections.json` next to `json/`.
+pub fn is_package(dir: &Path) -> bool {
+    dir.join("collections.json").is_file() && dir.join("json").is_dir()
+}
+
+#[derive(Debug, Clone)]
+pub struct IconifyJsonBackend {
+    dir: PathBuf,
+}
+
+impl IconifyJsonBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn respond(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        let param = |key: &str| {
+            query
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        match path {
+            "collections" => std::fs::read_to_string(self.dir.join("collections.json")).ok(),
+            "collection" => {
+                let prefix = param("prefix")?;
+                let set = self.set(prefix)?;
+                if param("chars").is_some() {
+                    let chars = set.get("chars").cloned().unwrap_or_else(|| json!({}));
+                    return Some(json!({ "prefix": prefix, "chars": chars }).to_string());
+                }
+                Some(json!({ "prefix": prefix, "icons": icon_names(&set) }).to_string())
+            }
+            "search" => None,
+            _ => {
+                if let Some(name) = path.strip_suffix(".svg") {
+                    let (prefix, icon) = name.split_once(':')?;
+                    let set = self.set(prefix)?;
+                    return build_svg(&set, icon);
+                }
+                let prefix = path.strip_suffix(".json")?;
+                let set = self.set(prefix)?;
+                icon_json(&set, prefix, param("icons")?.split(',')).map(|value| value.to_string())
+            }
+        }
+    }
+
+    /// `json/<prefix>.json`, parsed once per process.
+    fn set(&self, prefix: &str) -> Option<Arc<Value>> {
+        if prefix.is_empty()
+            || !prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return None;
+        }
+        let path = self.dir.join("json").join(format!("{prefix}.json"));
+        let sets = SETS.get_or_init(Default::default);
+        if let Some(set) = sets.lock().ok()?.get(&path) {
+            return Some(Arc::clone(set));
+        }
+        let set: Arc<Value> =
+            Arc::new(serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()?);
+        sets.lock().ok()?.insert(path, Arc::clone(&set));
+        Some(set)
+    }
+}
+
+impl IconifyBackend for IconifyJsonBackend {
+    fn get<'a>(
+        &'a self,
+        path: &'a str,
+        query: &'a [(String, String)],
+        _cacheable: bool,
+    ) -> BackendFuture<'a> {
+        let response = self
+            .respond(path, query)
+            .ok_or_else(|| IconifyError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                endpoint: self.endpoint(path, query),
+                body: String::new(),
+            });
+        Box::pin(async move { response })
+    }
+
+    fn endpoint(&self, path: &str, query: &[(String, String)]) -> String {
+        let query = query
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        format!(
+            "{}/{path}{}{query} (@iconify/json)",
+            self.dir.display(),
+            if query.is_empty() { "" } else { "?" }
+        )
+    }
+
+    fn cached(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        self.respond(path, query)
+    }
+}
+
+/// Names of the set's icons, sorted, without the hidden ones.
+fn icon_names(set: &Value) -> Vec<String> {
+    let mut names = set
+        .get("icons")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(_, icon)| icon.get("hidden").and_then(Value::as_bool) != Some(true))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// The API's `/<prefix>.json?icons=` subset: the asked-for icons, aliases and
+/// the parents those need, with the set's default size.
+fn icon_json<'a>(set: &Value, prefix: &str, names: impl Iterator<Item = &'a str>) -> Option<Value> {
+    let all_icons = set.get("icons")?;
+    let all_aliases = set.get("aliases");
+    let mut icons = Map::new();
+    let mut aliases = Map::new();
+    for name in names {
+        let mut name = name.to_string();
+        for _ in 0..MAX_ALIAS_DEPTH {
+            if let Some(icon) = all_icons.get(&name) {
+                icons.insert(name, icon.clone());
+                break;
+            }
+            let Some(alias) = all_aliases.and_then(|aliases| aliases.get(&name)) else {
+                break;
+            };
+            aliases.insert(name, alias.clone());
+            let Some(parent) = alias.get("parent").and_then(Value::as_str) else {
+                break;
+            };
+            name = parent.to_string();
+        }
+    }
+    if icons.is_empty() {
+        return None;
+    }
+
+    let mut out = Map::new();
+    out.insert("prefix".to_string(), json!(prefix));
+    out.insert("icons".to_string(), Value::Object(icons));
+    if !aliases.is_empty() {
+        out.insert("aliases".to_string(), Value::Object(aliases));
+    }
+    for key in ["left", "top", "width", "height"] {
+        if let Some(value) = set.get(key) {
+            out.insert(key.to_string(), value.clone());
+        }
+    }
+    Some(Value::Object(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iconify::IconifyClient;
+
+    fn package() -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("json")).unwrap();
+        std::fs::write(
+            dir.path().join("collections.json"),
+            r#"{"demo":{"name":"Demo Icons","total":2,"license":{"title":"MIT","spdx":"MIT"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("json").join("demo.json"),
+            r#"{
+                "prefix": "demo",
+                "width": 24,
+                "height": 24,
+                "icons": {
+                    "arrow": {"body": "<path d=\"M0 0h1\"/>"},
+                    "wide": {"body": "<rect/>", "width": 32},
+                    "old": {"body": "<circle/>", "hidden": true}
+                },
+                "aliases": {
+                    "arrow-left": {"parent": "arrow", "hFlip": true},
+                    "arrow-down": {"parent": "arrow", "rotate": 1}
+                },
+                "chars": {"e001": "arrow"}
+            }"#,
+        )
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn lists_and_builds_icons_from_the_package() {
+        let dir = package();
+        assert!(is_package(dir.path()));
+        let client = IconifyClient::with_backend(IconifyJsonBackend::new(dir.path()));
+
+        let collections = client.collections().await.unwrap();
+        assert_eq!(collections.collections["demo"].total, Some(2));
+        assert_eq!(
+            collections.collections["demo"].license().as_deref(),
+            Some("MIT")
+        );
+        assert_eq!(
+            client.collection("demo").await.unwrap().icons,
+            ["arrow", "wide"]
+        );
+        assert_eq!(
+            client.collection_chars("demo").await.unwrap()["e001"],
+            "arrow"
+        );
+
+        assert_eq!(
+            client.svg("demo:arrow").await.unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1em\" height=\"1em\" viewBox=\"0 0 24 24\"><path d=\"M0 0h1\"/></svg>"
+        );
+        assert_eq!(
+            client.svg("demo:wide").await.unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1em\" height=\"1em\" viewBox=\"0 0 32 24\"><rect/></svg>"
+        );
+        assert!(
+            client
+                .svg("demo:arrow-left")
+                .await
+                .unwrap()
+                .contains("<g transform=\"translate(24 0) scale(-1 1)\"><path")
+        );
+        assert!(
+            client
+                .svg("demo:arrow-down")
+                .await
+                .unwrap()
+                .contains("<g transform=\"rotate(90 12 12)\"><path")
+        );
+
+        let json = client.icon_json("demo", "arrow-left").await.unwrap();
+        assert_eq!(json["aliases"]["arrow-left"]["parent"], "arrow");
+        assert!(json["icons"]["arrow"]["body"].is_string());
+        assert_eq!(json["width"], 24);
+
+        assert!(client.svg("demo:missing").await.is_err());
+        assert!(client.collection("../demo").await.is_err());
+        let error = client.search("arrow", None, None, false).await.unwrap_err();
+        assert!(error.unsupported_feature().is_some());
+    }
+}
@@ -0,0 +1,438 @@
+// `--mock-api <fixtures-dir>`: Iconify responses from local files.
+//
+// Every `IconifyClient` built with `from_env` reads from the fixtures folder
+// instead of the network, so integration tests are deterministic and TUI demos
+// can be recorded offline. The folder mirrors the API:
+//
+//   collections.json        `/collections`; made from the prefix folders if missing
+//   collection/<prefix>.json `/collection?prefix=`; the folder's SVGs if missing
+//   collection/<prefix>.chars.json `/collection?prefix=&chars=true`
+//   search/<query>.json     `/search?query=`; a substring match on names if missing
+//   <prefix>/<icon>.svg     `/<prefix>:<icon>.svg`
+//   <prefix>/<icon>.json    `/<prefix>.json?icons=<icon>`
+//
+// A missing fixture answers 404, like the API does for an unknown icon. The
+// TUI runs `iconmate add` / `replace` as subprocesses; `forward_to` passes the
+// folder on through `ICONMATE_MOCK_API`.
+//
+// With `ICONMATE_MOCK_API_RECORD=1` a missing fixture is fetched from the live
+// API instead and written to its file, and recorded files are replayed from
+// then on: run the test suite once online to capture what it needs, commit
+// the folder, and later runs never touch api.iconify.design. Only recorded
+// files are replayed in this mode, never listings made up from the folders.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use reqwest::StatusCode;
+use serde_json::{Map, Value, json};
+
+use crate::iconify::{BackendFuture, HttpBackend, IconifyBackend, IconifyError};
+
+pub const MOCK_API_ENV: &str = "ICONMATE_MOCK_API";
+pub const RECORD_ENV: &str = "ICONMATE_MOCK_API_RECORD";
+
+/// Iconify's default page size for `/search`.
+const DEFAULT_SEARCH_LIMIT: usize = 64;
+
+static MOCK_API_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Serve Iconify requests from `dir` for the rest of the process (`--mock-api`).
+pub fn set_mock_api_dir(dir: PathBuf) {
+    let _ = MOCK_API_DIR.set(dir);
+}
+
+/// The fixtures folder from `--mock-api` or `ICONMATE_MOCK_API`, if any.
+pub fn mock_api_dir() -> Option<PathBuf> {
+    MOCK_API_DIR.get().cloned().or_else(|| {
+        std::env::var_os(MOCK_API_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// Whether missing fixtures are recorded from the live API.
+pub fn recording() -> bool {
+    std::env::var(RECORD_ENV).is_ok_and(|value| value == "1")
+}
+
+/// Hands the fixtures folder to an `iconmate` subprocess.
+pub fn forward_to(command: &mut Command) {
+    if let Some(dir) = mock_api_dir() {
+        command.env(MOCK_API_ENV, dir);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FixtureBackend {
+    dir: PathBuf,
+}
+
+impl FixtureBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The file a request is recorded in: `mdi/heart.svg` for
+    /// `mdi:heart.svg`, `collection/mdi.json` for `collection?prefix=mdi`.
+    fn fixture_file(&self, path: &str, query: &[(String, String)]) -> Option<PathBuf> {
+        let param = |key: &str| {
+            query
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        match path {
+            "collections" => self.fixture_path(&["collections.json"]),
+            "collection" if param("chars").is_some() => {
+                self.fixture_path(&["collection", &format!("{}.chars.json", param("prefix")?)])
+            }
+            "collection" => {
+                self.fixture_path(&["collection", &format!("{}.json", param("prefix")?)])
+            }
+            "search" => self.fixture_path(&["search", &format!("{}.json", param("query")?)]),
+            _ => {
+                if let Some((prefix, icon)) = path
+                    .strip_suffix(".svg")
+                    .and_then(|name| name.split_once(':'))
+                {
+                    self.fixture_path(&[prefix, &format!("{icon}.svg")])
+                } else {
+                    let prefix = path.strip_suffix(".json")?;
+                    self.fixture_path(&[prefix, &format!("{}.json", param("icons")?)])
+                }
+            }
+        }
+    }
+
+    /// The recorded response, if there is one.
+    fn recorded(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        std::fs::read_to_string(self.fixture_file(path, query)?).ok()
+    }
+
+    fn respond(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        if let Some(body) = self.recorded(path, query) {
+            return Some(body);
+        }
+        let param = |key: &str| {
+            query
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        match path {
+            "collections" => Some(Value::Object(self.collections()).to_string()),
+            "collection" if param("chars").is_none() => {
+                let prefix = param("prefix")?;
+                let icons = self.icons(prefix);
+                (!icons.is_empty()).then(|| json!({ "prefix": prefix, "icons": icons }).to_string())
+            }
+            "search" => {
+                let number = |key: &str| param(key).and_then(|value| value.parse::<usize>().ok());
+                Some(self.search(
+                    param("query")?,
+                    number("limit").unwrap_or(DEFAULT_SEARCH_LIMIT),
+                    number("start").unwrap_or(0),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// `dir` joined with `parts`; `None` if a part would leave it.
+    fn fixture_path(&self, parts: &[&str]) -> Option<PathBuf> {
+        let mut path = self.dir.clone();
+        for part in parts {
+            let mut components = Path::new(part).components();
+            if !matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            ) {
+                return None;
+            }
+            path.push(part);
+        }
+        Some(path)
+    }
+
+    fn read(&self, parts: &[&str]) -> Option<String> {
+        std::fs::read_to_string(self.fixture_path(parts)?).ok()
+    }
+
+    /// Folders holding icons, sorted.
+    fn prefixes(&self) -> Vec<String> {
+        let mut prefixes = std::fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| name != "collection" && name != "search" && !name.starts_with('.'))
+            .collect::<Vec<_>>();
+        prefixes.sort();
+        prefixes
+    }
+
+    /// Icon names in a prefix folder, sorted.
+    fn icons(&self, prefix: &str) -> Vec<String> {
+        let Some(folder) = self.fixture_path(&[prefix]) else {
+            return Vec::new();
+        };
+        let mut icons = std::fs::read_dir(folder)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                (path.extension()? == "svg")
+                    .then(|| path.file_stem()?.to_str().map(str::to_string))?
+            })
+            .collect::<Vec<_>>();
+        icons.sort();
+        icons
+    }
+
+    fn collections(&self) -> Map<String, Value> {
+        if let Some(Ok(Value::Object(collections))) = self
+            .read(&["collections.json"])
+            .map(|body| serde_json::from_str::<Value>(&body))
+        {
+            return collections;
+        }
+        self.prefixes()
+            .into_iter()
+            .map(|prefix| {
+                let total = self.icons(&prefix).len();
+                let meta = json!({ "name": prefix, "total": total });
+                (prefix, meta)
+            })
+            .collect()
+    }
+
+    fn search(&self, query: &str, limit: usize, start: usize) -> String {
+        let needle = query.to_lowercase();
+        let matches = self
+            .prefixes()
+            .into_iter()
+            .flat_map(|prefix| {
+                self.icons(&prefix)
+                    .into_iter()
+                    .map(move |icon| format!("{prefix}:{icon}"))
+            })
+            .filter(|name| name.to_lowercase().contains(&needle))
+            .collect::<Vec<_>>();
+        let icons = matches
+            .iter()
+            .skip(start)
+            .take(limit)
+            .cloned()
+            .collect::<Vec<_>>();
+        let all_collections = self.collections();
+        let collections = icons
+            .iter()
+            .filter_map(|name| name.split_once(':'))
+            .filter_map(|(prefix, _)| {
+                Some((prefix.to_string(), all_collections.get(prefix)?.clone()))
+            })
+            .collect::<Map<_, _>>();
+        json!({
+            "icons": icons,
+            "total": matches.len(),
+            "limit": limit,
+            "start": start,
+            "collections": collections,
+        })
+        .to_string()
+    }
+}
+
+impl IconifyBackend for FixtureBackend {
+    fn get<'a>(
+        &'a self,
+        path: &'a str,
+        query: &'a [(String, String)],
+        _cacheable: bool,
+    ) -> BackendFuture<'a> {
+        let response = self
+            .respond(path, query)
+            .ok_or_else(|| IconifyError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                endpoint: self.endpoint(path, query),
+                body: String::new(),
+            });
+        Box::pin(async move { response })
+    }
+
+    fn endpoint(&self, path: &str, query: &[(String, String)]) -> String {
+        let query = query
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        format!(
+            "{}/{path}{}{query} (mock)",
+            self.dir.display(),
+            if query.is_empty() { "" } else { "?" }
+        )
+    }
+
+    fn cached(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        self.respond(path, query)
+    }
+}
+
+/// Replays recorded fixtures and records the rest from `live`.
+#[derive(Debug, Clone)]
+pub struct RecordingBackend {
+    fixtures: FixtureBackend,
+    live: HttpBackend,
+}
+
+impl RecordingBackend {
+    pub fn new(dir: impl Into<PathBuf>, live: HttpBackend) -> Self {
+        Self {
+            fixtures: FixtureBackend::new(dir),
+            live,
+        }
+    }
+}
+
+impl IconifyBackend for RecordingBackend {
+    fn get<'a>(
+        &'a self,
+        path: &'a str,
+        query: &'a [(String, String)],
+        _cacheable: bool,
+    ) -> BackendFuture<'a> {
+        Box::pin(async move {
+            if let Some(body) = self.fixtures.recorded(path, query) {
+                return Ok(body);
+            }
+            // Bypass the HTTP cache: a fixture must be what the API serves.
+            let body = self.live.get(path, query, false).await?;
+            if let Some(file) = self.fixtures.fixture_file(path, query) {
+                let written = file
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(&file, &body));
+                if let Err(error) = written {
+                    eprintln!("Could not record {}: {error}", file.display());
+                }
+            }
+            Ok(body)
+        })
+    }
+
+    fn endpoint(&self, path: &str, query: &[(String, String)]) -> String {
+        self.live.endpoint(path, query)
+    }
+
+    fn cached(&self, path: &str, query: &[(String, String)]) -> Option<String> {
+        self.fixtures.recorded(path, query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iconify::IconifyClient;
+
+    fn fixtures() -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        for (file, contents) in [
+            ("mdi/heart.svg", "<svg>heart</svg>"),
+            ("mdi/heart-outline.svg", "<svg>outline</svg>"),
+            ("lucide/house.svg", "<svg>house</svg>"),
+            ("search/love.json", r#"{"icons":["mdi:heart"],"total":1}"#),
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[tokio::test]
+    async fn serves_fixtures_and_fills_in_listings() {
+        let dir = fixtures();
+        let client = IconifyClient::with_backend(FixtureBackend::new(dir.path()));
+
+        assert_eq!(client.svg("mdi:heart").await.unwrap(), "<svg>heart</svg>");
+        let collections = client.collections().await.unwrap();
+        assert_eq!(collections.collections["mdi"].total, Some(2));
+        assert_eq!(
+            client.collection("mdi").await.unwrap().icons,
+            vec!["heart", "heart-outline"]
+        );
+
+        let search = client.search("heart", Some(1), None, true).await.unwrap();
+        assert_eq!(
+            (search.icons, search.total),
+            (vec!["mdi:heart".to_string()], 2)
+        );
+        assert!(search.collections.unwrap().contains_key("mdi"));
+        let recorded = client.search("love", None, None, false).await.unwrap();
+        assert_eq!(recorded.icons, vec!["mdi:heart"]);
+    }
+
+    #[tokio::test]
+    async fn missing_fixtures_are_not_found() {
+        let dir = fixtures();
+        let client = IconifyClient::with_backend(FixtureBackend::new(dir.path()));
+
+        for error in [
+            client.svg("mdi:star").await.unwrap_err(),
+            client.svg("..:heart").await.unwrap_err(),
+            client.collection("tabler").await.map(|_| ()).unwrap_err(),
+        ] {
+            assert!(matches!(
+                error,
+                IconifyError::HttpStatus {
+                    status: StatusCode::NOT_FOUND,
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn records_missing_fixtures_then_replays_them() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        // Answers exactly one request; a second one would hang up unanswered.
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 12\r\nConnection: close\r\n\r\n<svg>v</svg>")
+                .unwrap();
+            request_line
+        });
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let client = IconifyClient::with_backend(RecordingBackend::new(
+            dir.path(),
+            HttpBackend::new(&base_url).unwrap(),
+        ));
+        assert_eq!(client.svg("mdi:star").await.unwrap(), "<svg>v</svg>");
+        assert!(server.join().unwrap().starts_with("GET /mdi:star.svg "));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("mdi/star.svg")).unwrap(),
+            "<svg>v</svg>"
+        );
+        // Replayed: nothing listens any more.
+        assert_eq!(client.svg("mdi:star").await.unwrap(), "<svg>v</svg>");
+    }
+}
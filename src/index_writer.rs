@@ -0,0 +1,506 @@
+// Barrel languages (`index_strategy`): which file lists a folder's icons and
+// how it's written.
+//
+// `ts` (the default) and `js` keep the `export { default as IconHeart } from
+// './heart.svg';` barrel in `index.ts` / `index.js`. `dart` writes a
+// `static const String` class to `icons.dart` next to the icons, and `rust`
+// writes a `mod.rs` of `pub const ICON_HEART: &str = include_str!("heart.svg");`
+// so the SVGs are compiled in; the rust preset implies that strategy. Commands
+// that only read or edit a folder (list, delete, rename, the TUI) find
+// whichever barrel is there; only `add` needs the strategy to create one. The
+// flutter preset keeps its own project-level barrel and ignores this setting.
+
+use std::path::{Path, PathBuf};
+
+use crate::utils::IconEntry;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum IndexStrategy {
+    #[default]
+    Ts,
+    Js,
+    Dart,
+    Rust,
+}
+
+impl IndexStrategy {
+    pub const NAMES: &'static [&'static str] = &["ts", "js", "dart", "rust"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "ts" => Some(IndexStrategy::Ts),
+            "js" => Some(IndexStrategy::Js),
+            "dart" => Some(IndexStrategy::Dart),
+            "rust" => Some(IndexStrategy::Rust),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            IndexStrategy::Ts => "ts",
+            IndexStrategy::Js => "js",
+            IndexStrategy::Dart => "dart",
+            IndexStrategy::Rust => "rust",
+        }
+    }
+
+    /// `ts` and `js` share the JS export flow of `add` (presets, lazy
+    /// exports, groups); the others only list `.svg` files.
+    pub fn is_js(self) -> bool {
+        matches!(self, IndexStrategy::Ts | IndexStrategy::Js)
+    }
+
+    pub fn writer(self) -> &'static dyn IndexWriter {
+        match self {
+            IndexStrategy::Ts => &JsWriter {
+                file_name: "index.ts",
+            },
+            IndexStrategy::Js => &JsWriter {
+                file_name: "index.js",
+            },
+            IndexStrategy::Dart => &DartWriter,
+            IndexStrategy::Rust => &RustWriter,
+        }
+    }
+}
+
+/// Reads and writes one language's barrel. File paths are relative to the
+/// icons folder, like `./heart.svg`.
+pub trait IndexWriter {
+    /// The barrel's file name inside the icons folder.
+    fn file_name(&self) -> &'static str;
+
+    /// `IconHeart` -> the identifier in this language's convention.
+    fn export_name(&self, name: &str) -> String;
+
+    /// The barrel entry for `file_name`. `folder` is the icons folder as
+    /// configured, for languages that reference assets from the project root.
+    fn render_entry(&self, name: &str, folder: &str, file_name: &str) -> String;
+
+    fn parse(&self, contents: &str) -> Vec<IconEntry>;
+
+    /// A new barrel holding `entry`.
+    fn create(&self, entry: &str) -> String;
+
+    /// `contents` with `entry` added at the end.
+    fn append(&self, contents: &str, entry: &str) -> String;
+
+    /// `contents` without the entries for `file_path`; `None` when it has none.
+    fn remove(&self, contents: &str, file_path: &str) -> Option<String>;
+
+    /// `contents` without `entries`, each matched by its exact name and
+    /// path so `IconHeart` never takes `IconHeartFill` along; `None` when
+    /// none matched. Barrels with one entry per file match on the path.
+    fn remove_entries(&self, contents: &str, entries: &[IconEntry]) -> Option<String> {
+        let mut updated = None;
+        for entry in entries {
+            let current = updated.as_deref().unwrap_or(contents);
+            if let Some(next) = self.remove(current, &entry.file_path) {
+                updated = Some(next);
+            }
+        }
+        updated
+    }
+
+    /// `contents` with the entry for `from` pointing at `to`; `None` when
+    /// nothing matched.
+    fn rename(&self, contents: &str, from: &str, to: &str) -> Option<String>;
+}
+
+/// The barrel of `folder` and the writer for it: the first of `index.ts`,
+/// `index.js`, `mod.rs`, `partials.json` and `icons.dart` that
+/// exists, else `index.ts`.
+pub fn find_barrel(folder: &Path) -> (&'static dyn IndexWriter, PathBuf) {
+    [
+        IndexStrategy::Ts.writer(),
+        IndexStrategy::Js.writer(),
+        IndexStrategy::Rust.writer(),
+        crate::html_partial::manifest_writer(),
+        IndexStrategy::Dart.writer(),
+    ]
+    .into_iter()
+    .map(|writer| (writer, folder.join(writer.file_name())))
+    .find(|(_, path)| path.is_file())
+    .unwrap_or_else(|| {
+        let writer = IndexStrategy::Ts.writer();
+        (writer, folder.join(writer.file_name()))
+    })
+}
+
+struct JsWriter {
+    file_name: &'static str,
+}
+
+impl IndexWriter for JsWriter {
+    fn file_name(&self) -> &'static str {
+        self.file_name
+    }
+
+    fn export_name(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn render_entry(&self, name: &str, _folder: &str, file_name: &str) -> String {
+        format!("export {{ default as {name} }} from './{file_name}';")
+    }
+
+    fn parse(&self, contents: &str) -> Vec<IconEntry> {
+        crate::utils::parse_index_entries(contents)
+    }
+
+    fn create(&self, entry: &str) -> String {
+        format!("{entry}\n")
+    }
+
+    fn append(&self, contents: &str, entry: &str) -> String {
+        append_line(contents, entry)
+    }
+
+    fn remove(&self, contents: &str, file_path: &str) -> Option<String> {
+        crate::utils::remove_ts_exports(contents, file_path)
+    }
+
+    fn remove_entries(&self, contents: &str, entries: &[IconEntry]) -> Option<String> {
+        crate::utils::remove_ts_exports_where(contents, |export| {
+            entries
+                .iter()
+                .any(|entry| entry.name == export.name && entry.file_path == export.file_path)
+        })
+    }
+
+    fn rename(&self, contents: &str, from: &str, to: &str) -> Option<String> {
+        crate::utils::rename_ts_export_path(contents, from, to)
+    }
+}
+
+/// `icons.dart`: the flutter preset's class shape, one folder per barrel.
+struct DartWriter;
+
+impl IndexWriter for DartWriter {
+    fn file_name(&self) -> &'static str {
+        "icons.dart"
+    }
+
+    fn export_name(&self, name: &str) -> String {
+        crate::template::apply_filter("camel", name).unwrap_or_else(|| name.to_string())
+    }
+
+    fn render_entry(&self, name: &str, folder: &str, file_name: &str) -> String {
+        format!(
+            "  static const String {name} = '{}';",
+            crate::flutter::asset_path_for(folder, file_name)
+        )
+    }
+
+    fn parse(&self, contents: &str) -> Vec<IconEntry> {
+        // Asset paths start at the project root; every entry is in this folder.
+        crate::flutter::parse_dart_barrel(contents)
+            .into_iter()
+            .map(|entry| IconEntry {
+                file_path: format!("./{}", file_name_of(&entry.asset_path)),
+                name: entry.identifier,
+            })
+            .collect()
+    }
+
+    fn create(&self, entry: &str) -> String {
+        let class = crate::flutter::DEFAULT_FLUTTER_BARREL_CLASS;
+        let empty = crate::flutter::render_dart_barrel(class, &[]);
+        let body = empty.strip_suffix("}\n").unwrap_or(&empty);
+        format!("{body}\n{entry}\n}}\n")
+    }
+
+    fn append(&self, contents: &str, entry: &str) -> String {
+        // Entries go before the class's closing brace.
+        match contents.trim_end().strip_suffix('}') {
+            Some(body) => format!("{}\n{entry}\n}}\n", body.trim_end()),
+            None => append_line(contents, entry),
+        }
+    }
+
+    fn remove(&self, contents: &str, file_path: &str) -> Option<String> {
+        let target = file_name_of(file_path);
+        filter_lines(contents, |line| {
+            crate::flutter::parse_dart_barrel(line)
+                .first()
+                .is_some_and(|entry| file_name_of(&entry.asset_path) == target)
+        })
+    }
+
+    fn rename(&self, contents: &str, from: &str, to: &str) -> Option<String> {
+        let (from, to) = (file_name_of(from), file_name_of(to));
+        replace_in_entries(contents, |line| {
+            let entry = crate::flutter::parse_dart_barrel(line).into_iter().next()?;
+            (file_name_of(&entry.asset_path) == from).then(|| {
+                let renamed = format!(
+                    "{}{to}",
+                    &entry.asset_path[..entry.asset_path.len() - from.len()]
+                );
+                line.replacen(&entry.asset_path, &renamed, 1)
+            })
+        })
+    }
+}
+
+/// `mod.rs`: one `include_str!` constant per SVG.
+struct RustWriter;
+
+impl IndexWriter for RustWriter {
+    fn file_name(&self) -> &'static str {
+        "mod.rs"
+    }
+
+    fn export_name(&self, name: &str) -> String {
+        crate::template::apply_filter("constant", name).unwrap_or_else(|| name.to_string())
+    }
+
+    fn render_entry(&self, name: &str, _folder: &str, file_name: &str) -> String {
+        format!("pub const {name}: &str = include_str!(\"{file_name}\");")
+    }
+
+    fn parse(&self, contents: &str) -> Vec<IconEntry> {
+        contents.lines().filter_map(parse_rust_const).collect()
+    }
+
+    fn create(&self, entry: &str) -> String {
+        format!("{entry}\n")
+    }
+
+    fn append(&self, contents: &str, entry: &str) -> String {
+        append_line(contents, entry)
+    }
+
+    fn remove(&self, contents: &str, file_path: &str) -> Option<String> {
+        let target = file_name_of(file_path);
+        filter_lines(contents, |line| {
+            parse_rust_const(line).is_some_and(|entry| file_name_of(&entry.file_path) == target)
+        })
+    }
+
+    fn rename(&self, contents: &str, from: &str, to: &str) -> Option<String> {
+        let (from, to) = (file_name_of(from), file_name_of(to));
+        replace_in_entries(contents, |line| {
+            let entry = parse_rust_const(line)?;
+            (file_name_of(&entry.file_path) == from).then(|| {
+                line.replacen(
+                    &format!("include_str!(\"{from}\")"),
+                    &format!("include_str!(\"{to}\")"),
+                    1,
+                )
+            })
+        })
+    }
+}
+
+/// `pub const ICON_HEART: &str = include_str!("heart.svg");`
+fn parse_rust_const(line: &str) -> Option<IconEntry> {
+    let rest = line.trim().strip_prefix("pub const ")?;
+    let (name, rest) = rest.split_once(':')?;
+    let (_, rest) = rest.split_once('=')?;
+    let path = rest
+        .trim()
+        .strip_prefix("include_str!(\"")?
+        .strip_suffix("\");")?;
+    (!name.trim().is_empty() && !path.is_empty()).then(|| IconEntry {
+        name: name.trim().to_string(),
+        file_path: format!("./{}", path.trim_start_matches("./")),
+    })
+}
+
+fn file_name_of(path: &str) -> &str {
+    let path = path.trim();
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn append_line(contents: &str, line: &str) -> String {
+    let mut out = contents.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(line);
+    out.push('\n');
+    out
+}
+
+/// `contents` without the lines `drop` matches; `None` when none did.
+fn filter_lines(contents: &str, drop: impl Fn(&str) -> bool) -> Option<String> {
+    let kept = contents
+        .lines()
+        .filter(|line| !drop(line))
+        .collect::<Vec<_>>();
+    if kept.len() == contents.lines().count() {
+        return None;
+    }
+    let mut out = kept.join("\n");
+    if contents.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// `contents` with each line `replace` rewrites; `None` when it rewrote none.
+fn replace_in_entries(contents: &str, replace: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut replaced = false;
+    let lines = contents
+        .lines()
+        .map(|line| match replace(line) {
+            Some(updated) => {
+                replaced = true;
+                updated
+            }
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if !replaced {
+        return None;
+    }
+    let mut out = lines.join("\n");
+    if contents.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_and_paths(entries: Vec<IconEntry>) -> Vec<(String, String)> {
+        entries
+            .into_iter()
+            .map(|entry| (entry.name, entry.file_path))
+            .collect()
+    }
+
+    #[test]
+    fn rust_mod_round_trips() {
+        let writer = IndexStrategy::Rust.writer();
+        let name = writer.export_name("IconHeart");
+        assert_eq!(name, "ICON_HEART");
+        let contents = writer.create(&writer.render_entry(&name, "src/icons", "heart.svg"));
+        let contents = writer.append(
+            &contents,
+            &writer.render_entry("ICON_STAR", "src/icons", "star.svg"),
+        );
+        assert_eq!(
+            contents,
+            "pub const ICON_HEART: &str = include_str!(\"heart.svg\");\npub const ICON_STAR: &str = include_str!(\"star.svg\");\n"
+        );
+        assert_eq!(
+            names_and_paths(writer.parse(&contents)),
+            [
+                ("ICON_HEART".to_string(), "./heart.svg".to_string()),
+                ("ICON_STAR".to_string(), "./star.svg".to_string())
+            ]
+        );
+
+        let renamed = writer
+            .rename(&contents, "./heart.svg", "./love.svg")
+            .unwrap();
+        assert!(renamed.contains("include_str!(\"love.svg\")"));
+        assert_eq!(
+            writer.remove(&renamed, "./love.svg").unwrap(),
+            "pub const ICON_STAR: &str = include_str!(\"star.svg\");\n"
+        );
+        assert_eq!(writer.remove(&contents, "./gone.svg"), None);
+    }
+
+    #[test]
+    fn dart_barrel_keeps_its_class() {
+        let writer = IndexStrategy::Dart.writer();
+        let name = writer.export_name("IconHeart");
+        assert_eq!(name, "iconHeart");
+        let contents = writer.create(&writer.render_entry(&name, "assets/icons", "heart.svg"));
+        let contents = writer.append(
+            &contents,
+            &writer.render_entry("iconStar", "assets/icons", "star.svg"),
+        );
+        assert!(contents.ends_with(
+            "  static const String iconHeart = 'assets/icons/heart.svg';\n  static const String iconStar = 'assets/icons/star.svg';\n}\n"
+        ));
+        assert_eq!(
+            names_and_paths(writer.parse(&contents)),
+            [
+                ("iconHeart".to_string(), "./heart.svg".to_string()),
+                ("iconStar".to_string(), "./star.svg".to_string())
+            ]
+        );
+
+        let renamed = writer.rename(&contents, "heart.svg", "love.svg").unwrap();
+        assert!(renamed.contains("iconHeart = 'assets/icons/love.svg'"));
+        let removed = writer.remove(&renamed, "./star.svg").unwrap();
+        assert!(!removed.contains("iconStar"));
+        assert!(removed.ends_with("}\n"));
+    }
+
+    #[test]
+    fn finds_whichever_barrel_exists() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(find_barrel(dir.path()).1, dir.path().join("index.ts"));
+        std::fs::write(dir.path().join("mod.rs"), "").unwrap();
+        let (writer, path) = find_barrel(dir.path());
+        assert_eq!(writer.file_name(), "mod.rs");
+        assert_eq!(path, dir.path().join("mod.rs"));
+        assert_eq!(IndexStrategy::from_str("js"), Some(IndexStrategy::Js));
+        assert_eq!(IndexStrategy::from_str("go"), None);
+    }
+
+    #[test]
+    fn ts_remove_entries_removes_each_selected_line() {
+        let contents = "export { default as IconOne } from './one.svg';\nexport { default as IconTwo } from './two.svg?react';\nexport { default as IconThree } from './three.svg';\n";
+
+        let selected_icons = vec![
+            crate::utils::parse_export_line_ts("export { default as IconOne } from './one.svg';")
+                .expect("line should parse"),
+            crate::utils::parse_export_line_ts(
+                "export { default as IconTwo } from './two.svg?react';",
+            )
+            .expect("line should parse"),
+        ];
+
+        let updated = IndexStrategy::Ts
+            .writer()
+            .remove_entries(contents, &selected_icons)
+            .unwrap();
+
+        assert!(!updated.contains("IconOne"));
+        assert!(!updated.contains("IconTwo"));
+        assert!(updated.contains("IconThree"));
+    }
+
+    #[test]
+    fn ts_remove_entries_matches_overlapping_names_exactly() {
+        let contents = "export { default as IconHeartFill } from './heart-fill.svg';\nexport { default as IconHeart } from './heart.svg';\nexport { default as IconHeartOutline } from './heart.svg';\n";
+        let selected_icons = vec![IconEntry {
+            name: "IconHeart".to_string(),
+            file_path: "./heart.svg".to_string(),
+        }];
+
+        assert_eq!(
+            IndexStrategy::Ts
+                .writer()
+                .remove_entries(contents, &selected_icons)
+                .unwrap(),
+            "export { default as IconHeartFill } from './heart-fill.svg';\nexport { default as IconHeartOutline } from './heart.svg';\n"
+        );
+    }
+
+    #[test]
+    fn ts_remove_entries_removes_selected_from_concatenated_line() {
+        let contents = "export { default as IconOne } from './one.svg';export { default as IconTwo } from './two.svg';\n";
+
+        let selected_icons = vec![
+            crate::utils::parse_export_line_ts("export { default as IconTwo } from './two.svg';")
+                .expect("line should parse"),
+        ];
+
+        let updated = IndexStrategy::Ts
+            .writer()
+            .remove_entries(contents, &selected_icons)
+            .unwrap();
+
+        assert!(updated.contains("IconOne"));
+        assert!(!updated.contains("IconTwo"));
+    }
+}
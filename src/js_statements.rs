@@ -0,0 +1,341 @@
+// Top-level statements of a JS/TS module, for barrels people edit by hand.
+//
+// iconmate writes one `export { default as X } from './x.svg';` per line, but
+// an `index.ts` that has been through a formatter or a teammate may wrap an
+// export over several lines, use double quotes, put two exports on one line,
+// or mark them `export type`. The scanner finds statements wherever they are
+// and keeps their byte range, so edits replace exactly one statement and
+// leave every other byte of the file alone. `default_export` reads the icon
+// a statement re-exports; the `validation` test support in `lib.rs` shares it.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    /// Where the statement sits in the source, including its `;`.
+    pub range: Range<usize>,
+    /// 1-based line the statement starts on.
+    pub line: usize,
+    /// The statement without comments, its `;` or runs of whitespace.
+    pub code: String,
+}
+
+/// Statements end at `;` or at a line break outside brackets, unless the
+/// line obviously continues (`=`, `,`, `=>`, a trailing `from`/`as`, or a
+/// next line starting with `from`). Comments between statements are skipped.
+pub fn statements(source: &str) -> Vec<Statement> {
+    let bytes = source.as_bytes();
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut depth = 0i32;
+    let mut i = 0;
+
+    let push = |range: Range<usize>, out: &mut Vec<Statement>| {
+        let code = compact(&source[range.clone()]);
+        if !code.is_empty() {
+            out.push(Statement {
+                line: source[..range.start].matches('\n').count() + 1,
+                range,
+                code,
+            });
+        }
+    };
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..]
+                    .find('\n')
+                    .map_or(bytes.len(), |offset| i + offset);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |offset| i + 2 + offset + 2);
+                continue;
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                start.get_or_insert(i);
+                i = string_end(bytes, i, quote);
+                end = i;
+                continue;
+            }
+            b'(' | b'{' | b'[' => depth += 1,
+            b')' | b'}' | b']' => depth -= 1,
+            b';' if depth <= 0 => {
+                if let Some(start) = start.take() {
+                    push(start..i + 1, &mut out);
+                }
+                depth = 0;
+                i += 1;
+                continue;
+            }
+            b'\n' => {
+                if depth <= 0
+                    && let Some(statement_start) = start
+                    && !continues(&source[statement_start..end], &source[i..])
+                {
+                    push(statement_start..end, &mut out);
+                    start = None;
+                    depth = 0;
+                }
+                i += 1;
+                continue;
+            }
+            byte if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+        i += source[i..].chars().next().map_or(1, char::len_utf8);
+        end = i;
+    }
+    if let Some(start) = start {
+        push(start..end, &mut out);
+    }
+    out
+}
+
+/// One past the closing quote of the string opening at `open`.
+fn string_end(bytes: &[u8], open: usize, quote: u8) -> usize {
+    let mut i = open + 1;
+    while i < bytes.len() && bytes[i] != quote {
+        if bytes[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    (i + 1).min(bytes.len())
+}
+
+fn continues(statement: &str, rest: &str) -> bool {
+    let trimmed = statement.trim_end();
+    let last_word = trimmed
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .next()
+        .unwrap_or_default();
+    let next = rest.trim_start();
+    trimmed.ends_with(['=', ',', '>', ':'])
+        || matches!(
+            last_word,
+            "export" | "from" | "as" | "default" | "type" | "const"
+        )
+        || (next.starts_with("from")
+            && next[4..].starts_with(|c: char| c.is_whitespace() || c == '\'' || c == '"'))
+}
+
+/// `export {\n  default as X, // note\n} from "./x";` ->
+/// `export { default as X, } from "./x"`. Strings are kept as written.
+pub fn compact(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut pending_space = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let next = match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => text[i..]
+                .find('\n')
+                .map_or(bytes.len(), |offset| i + offset),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => text[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |offset| i + 2 + offset + 2),
+            byte if byte.is_ascii_whitespace() => i + 1,
+            _ => {
+                if pending_space && !out.is_empty() {
+                    out.push(' ');
+                }
+                pending_space = false;
+                let next = match bytes[i] {
+                    quote @ (b'\'' | b'"' | b'`') => string_end(bytes, i, quote),
+                    _ => i + text[i..].chars().next().map_or(1, char::len_utf8),
+                };
+                out.push_str(&text[i..next]);
+                i = next;
+                continue;
+            }
+        };
+        pending_space = true;
+        i = next;
+    }
+    out.strip_suffix(';')
+        .map(str::trim_end)
+        .unwrap_or(&out)
+        .to_string()
+}
+
+/// `(name, path)` of a default re-export, as written by hand: any
+/// whitespace, either quote, `export type { ... }` and `type default as`
+/// modifiers, and type annotations on lazy exports.
+pub fn default_export(line: &str) -> Option<(String, String)> {
+    let line = compact(line);
+    let line = line.as_str();
+
+    // Skip empty lines and comments
+    if line.is_empty() {
+        return None;
+    }
+
+    // Example lines:
+    // export { default as IconGitHub } from "./devicon:github.svg";
+    // export { default as IconGitHub } from './devicon:github.svg';
+    // export type { default as IconGitHub } from './devicon:github.svg';
+    // export const IconGitHub = lazy(() => import('./devicon_github'));
+    if !line.starts_with("export") {
+        return None;
+    }
+    if let Some(rest) = line.strip_prefix("export const ") {
+        return lazy_export(rest, line);
+    }
+
+    let open_brace_idx = line.find('{')?;
+    let close_brace_idx = line[open_brace_idx + 1..].find('}')? + open_brace_idx + 1;
+    let modifier = line["export".len()..open_brace_idx].trim();
+    if !modifier.is_empty() && modifier != "type" {
+        return None;
+    }
+
+    let inside_braces = line[open_brace_idx + 1..close_brace_idx].trim();
+    let mut tokens = inside_braces.split_whitespace().peekable();
+    tokens.next_if_eq(&"type");
+    if tokens.next()? != "default" || tokens.next()? != "as" {
+        return None;
+    }
+
+    let name = tokens.next()?.trim_end_matches(',');
+    if name.is_empty() {
+        return None;
+    }
+
+    let after_brace = line[close_brace_idx + 1..].trim_start();
+    let after_from = after_brace.strip_prefix("from")?.trim_start();
+    let quote_char = after_from.chars().next()?;
+    if quote_char != '"' && quote_char != '\'' {
+        return None;
+    }
+
+    let path_start = quote_char.len_utf8();
+    let path_end = after_from[path_start..].find(quote_char)?;
+    let relative_path = &after_from[path_start..path_start + path_end];
+    let import_path = path_before_query_or_hash(relative_path).trim();
+    if import_path.is_empty() {
+        return None;
+    }
+
+    Some((name.to_string(), import_path.to_string()))
+}
+
+/// `IconHeart = lazy(() => import('./heart'))`, after `export const `.
+fn lazy_export(rest: &str, line: &str) -> Option<(String, String)> {
+    // `=` of the initializer, not of an `=>` in a type annotation.
+    let equals = rest
+        .match_indices('=')
+        .map(|(idx, _)| idx)
+        .find(|&idx| !rest[idx + 1..].starts_with('>'))?;
+    let (name, value) = (&rest[..equals], &rest[equals + 1..]);
+    // `IconHeart: LazyExoticComponent<...>` -> `IconHeart`
+    let name = name.split(':').next().unwrap_or(name).trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    {
+        return None;
+    }
+    let value = value.trim();
+    let (_, arrow) = value.split_once('(')?;
+    if !arrow.trim_start().starts_with("()") || !value.contains("import(") {
+        return None;
+    }
+    let (_, path) = export_specifier(line)?;
+    let import_path = path_before_query_or_hash(path).trim();
+    if import_path.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), import_path.to_string()))
+}
+
+/// Byte offset of the opening quote of the module an export line imports:
+/// `from './heart.svg'`, or `import('./heart')` for lazy exports.
+pub fn export_specifier_start(line: &str) -> Option<usize> {
+    let after = match line.find("import(") {
+        Some(idx) => idx + "import(".len(),
+        None => {
+            let close_brace = line.rfind('}').map_or(0, |idx| idx + 1);
+            close_brace + line[close_brace..].find("from")? + "from".len()
+        }
+    };
+    let offset = line[after..].len() - line[after..].trim_start().len();
+    let start = after + offset;
+    matches!(line[start..].chars().next()?, '\'' | '"').then_some(start)
+}
+
+/// The quote and module path an export line imports.
+pub fn export_specifier(line: &str) -> Option<(char, &str)> {
+    let start = export_specifier_start(line)?;
+    let quote = line[start..].chars().next()?;
+    let path = &line[start + quote.len_utf8()..];
+    let end = path.find(quote)?;
+    Some((quote, &path[..end]))
+}
+
+/// `./heart.svg?react#x` -> `./heart.svg`.
+pub fn path_before_query_or_hash(path: &str) -> &str {
+    let mut end = path.len();
+    if let Some(idx) = path.find('?') {
+        end = end.min(idx);
+    }
+    if let Some(idx) = path.find('#') {
+        end = end.min(idx);
+    }
+    &path[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(source: &str) -> Vec<String> {
+        statements(source)
+            .into_iter()
+            .map(|statement| statement.code)
+            .collect()
+    }
+
+    #[test]
+    fn splits_statements_across_and_within_lines() {
+        let source = "// header\nexport { default as IconA } from './a.svg'; export { default as IconB } from \"./b.svg\"\nexport {\n  default as IconC, // wrapped\n}\n  from './c.svg';\n/* note; not code */\nconst a = 1\n";
+        assert_eq!(
+            codes(source),
+            [
+                "export { default as IconA } from './a.svg'",
+                "export { default as IconB } from \"./b.svg\"",
+                "export { default as IconC, } from './c.svg'",
+                "const a = 1",
+            ]
+        );
+
+        let found = statements(source);
+        assert_eq!(
+            &source[found[0].range.clone()],
+            "export { default as IconA } from './a.svg';"
+        );
+        assert_eq!(
+            &source[found[1].range.clone()],
+            "export { default as IconB } from \"./b.svg\""
+        );
+        assert_eq!(found[2].line, 3);
+        assert!(source[found[2].range.clone()].ends_with("from './c.svg';"));
+    }
+
+    #[test]
+    fn keeps_semicolons_and_comment_markers_inside_strings() {
+        assert_eq!(
+            codes("export { default as IconA } from './a;b//c.svg'"),
+            ["export { default as IconA } from './a;b//c.svg'"]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// Lazy `index.ts` exports (`--lazy`, `lazy_exports` local config key).
+//
+// `export const IconHeart = lazy(() => import('./heart'));` instead of a
+// static re-export, so a huge icons folder doesn't land in the main bundle.
+// React and Solid use their `lazy`, Vue uses `defineAsyncComponent`; the
+// import for it goes at the top of the barrel, below any header comments.
+// Svelte has no lazy component primitive, and `.svg` presets depend on the
+// bundler's loader, so they keep static exports. Lazy and static lines can
+// share a barrel: every reader of `index.ts` understands both.
+
+use crate::utils::Preset;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LazyLoader {
+    React,
+    Solid,
+    Vue,
+}
+
+impl LazyLoader {
+    pub fn for_preset(preset: &Preset) -> Option<Self> {
+        match preset {
+            Preset::React => Some(LazyLoader::React),
+            Preset::Solid => Some(LazyLoader::Solid),
+            Preset::Vue => Some(LazyLoader::Vue),
+            _ => None,
+        }
+    }
+
+    /// The loader for `preset` when `lazy_exports` is on.
+    pub fn configured(enabled: bool, preset: &str) -> Option<Self> {
+        if !enabled {
+            return None;
+        }
+        Preset::from_str(preset).as_ref().and_then(Self::for_preset)
+    }
+
+    fn wrapper(self) -> &'static str {
+        match self {
+            LazyLoader::React | LazyLoader::Solid => "lazy",
+            LazyLoader::Vue => "defineAsyncComponent",
+        }
+    }
+
+    fn module(self) -> &'static str {
+        match self {
+            LazyLoader::React => "react",
+            LazyLoader::Solid => "solid-js",
+            LazyLoader::Vue => "vue",
+        }
+    }
+
+    /// `export { default as IconHeart } from './heart';` ->
+    /// `export const IconHeart = lazy(() => import('./heart'));`, keeping the
+    /// quotes, query and semicolon style of the static line.
+    pub fn wrap(self, export_line: &str) -> String {
+        let line = export_line.trim_end();
+        let Some(entry) = crate::utils::parse_export_line_ts(line) else {
+            return line.to_string();
+        };
+        let Some((_, specifier)) = line.rsplit_once(" from ") else {
+            return line.to_string();
+        };
+        let semicolon = if specifier.ends_with(';') { ";" } else { "" };
+        format!(
+            "export const {} = {}(() => import({})){semicolon}",
+            entry.name,
+            self.wrapper(),
+            specifier.trim_end_matches(';').trim()
+        )
+    }
+
+    /// `contents` with `import { lazy } from 'react';` added below its
+    /// leading comments, unless the barrel already imports it. Quotes and
+    /// semicolon follow `export_line`.
+    pub fn ensure_import(self, contents: &str, export_line: &str) -> String {
+        let already_imported = contents.lines().any(|line| {
+            let line = line.trim();
+            line.starts_with("import ")
+                && line.contains(self.wrapper())
+                && (line.contains(&format!("'{}'", self.module()))
+                    || line.contains(&format!("\"{}\"", self.module())))
+        });
+        if already_imported {
+            return contents.to_string();
+        }
+
+        let export_line = export_line.trim_end();
+        let quote = if export_line.contains("import(\"") {
+            '"'
+        } else {
+            '\''
+        };
+        let semicolon = if export_line.ends_with(';') { ";" } else { "" };
+        let import = format!(
+            "import {{ {} }} from {quote}{}{quote}{semicolon}\n",
+            self.wrapper(),
+            self.module()
+        );
+
+        let mut insert_at = 0;
+        for line in contents.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+                insert_at += line.len();
+            } else {
+                break;
+            }
+        }
+        format!(
+            "{}{import}{}",
+            &contents[..insert_at],
+            &contents[insert_at..]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_static_exports_per_framework() {
+        assert_eq!(
+            LazyLoader::React.wrap("export { default as IconHeart } from './heart';\n"),
+            "export const IconHeart = lazy(() => import('./heart'));"
+        );
+        assert_eq!(
+            LazyLoader::Vue.wrap("export { default as IconHeart } from \"./heart.vue\""),
+            "export const IconHeart = defineAsyncComponent(() => import(\"./heart.vue\"))"
+        );
+        let entry = crate::utils::parse_export_line_ts(
+            "export const IconHeart = lazy(() => import('./heart'))",
+        )
+        .unwrap();
+        assert_eq!(
+            (entry.name.as_str(), entry.file_path.as_str()),
+            ("IconHeart", "./heart")
+        );
+    }
+
+    #[test]
+    fn imports_the_wrapper_once_below_header_comments() {
+        let line = "export const IconHeart = lazy(() => import('./heart'));";
+        let contents =
+            "/* eslint-disable */\n// generated\nexport { default as IconStar } from './star';\n";
+        let updated = LazyLoader::Solid.ensure_import(contents, line);
+        assert_eq!(
+            updated,
+            "/* eslint-disable */\n// generated\nimport { lazy } from 'solid-js';\nexport { default as IconStar } from './star';\n"
+        );
+        assert_eq!(LazyLoader::Solid.ensure_import(&updated, line), updated);
+    }
+}
@@ -1,9 +1,17 @@
 // Core functionality that can be tested
 use anyhow::Result;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-/// Utilities for validating icon operations
+// The binary's barrel statement parser, so the matchers below read an
+// `index.ts` exactly the way iconmate does.
+#[path = "js_statements.rs"]
+#[allow(dead_code)]
+mod js_statements;
+
+/// Test support for iconmate and tools that wrap it: scratch icon folders,
+/// `index.ts` matchers and golden-file snapshots.
 pub mod validation {
     use super::*;
 
@@ -27,16 +35,13 @@ pub mod validation {
         assert!(index_file.exists(), "index.ts should be created");
         assert!(svg_file.exists(), "{}.svg should be created", file_stem);
 
-        // Verify the content of index.ts
+        // Verify the content of index.ts, in either quote style
         let index_content = fs::read_to_string(&index_file)?;
-        let expected_export = format!(
-            "export {{ default as Icon{} }} from './{}.svg';",
-            icon_name, file_stem
-        );
-        if !index_content.contains(&expected_export) {
+        let expected_export = (format!("Icon{}", icon_name), format!("./{}.svg", file_stem));
+        if !index_exports(&index_content).contains(&expected_export) {
             panic!(
-                "index.ts should contain the correct export statement: {}\nActual content:\n{}",
-                expected_export, index_content
+                "index.ts should export {} from '{}'\nActual content:\n{}",
+                expected_export.0, expected_export.1, index_content
             );
         }
 
@@ -66,6 +71,235 @@ pub mod validation {
 
         Ok(())
     }
+
+    /// What an `index.ts` exports, in file order, as `(name, path)`.
+    fn index_exports(contents: &str) -> Vec<(String, String)> {
+        js_statements::statements(contents)
+            .iter()
+            .filter_map(|statement| js_statements::default_export(&statement.code))
+            .collect()
+    }
+
+    /// Asserts `index_path` exports exactly `expected` (`(name, path)`), in
+    /// any order, reading the barrel the way iconmate does.
+    pub fn assert_index_exports(index_path: &Path, expected: &[(&str, &str)]) -> Result<()> {
+        let content = fs::read_to_string(index_path)?;
+        let mut actual = index_exports(&content);
+        let mut expected = expected
+            .iter()
+            .map(|(name, path)| (name.to_string(), path.to_string()))
+            .collect::<Vec<_>>();
+        actual.sort();
+        expected.sort();
+        if actual != expected {
+            panic!(
+                "index.ts exports differ\nexpected: {:?}\nactual:   {:?}\nActual content:\n{}",
+                expected, actual, content
+            );
+        }
+        Ok(())
+    }
+
+    /// Asserts `index_path` has no export named `name`.
+    pub fn assert_index_lacks_export(index_path: &Path, name: &str) -> Result<()> {
+        let content = fs::read_to_string(index_path)?;
+        if index_exports(&content)
+            .iter()
+            .any(|(export, _)| export == name)
+        {
+            panic!(
+                "index.ts should not export {}\nActual content:\n{}",
+                name, content
+            );
+        }
+        Ok(())
+    }
+
+    /// Set to `1` to (re)write golden files instead of comparing against them.
+    pub const UPDATE_SNAPSHOTS_ENV: &str = "ICONMATE_UPDATE_SNAPSHOTS";
+
+    /// Golden-file assertion for generated output (components, barrels). A
+    /// missing golden file is written and the assertion passes, so the first
+    /// run records it; afterwards `actual` must match it, with `\r\n` and a
+    /// trailing newline ignored. Set `ICONMATE_UPDATE_SNAPSHOTS=1` to accept
+    /// new output.
+    pub fn assert_snapshot(golden_path: &Path, actual: &str) -> Result<()> {
+        let normalize = |text: &str| text.replace("\r\n", "\n").trim_end().to_string();
+        let update = std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|value| value == "1");
+        if update || !golden_path.exists() {
+            if let Some(parent) = golden_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(golden_path, format!("{}\n", normalize(actual)))?;
+            return Ok(());
+        }
+
+        let expected = normalize(&fs::read_to_string(golden_path)?);
+        let actual = normalize(actual);
+        if expected != actual {
+            let line = expected
+                .lines()
+                .zip(actual.lines())
+                .position(|(expected, actual)| expected != actual)
+                .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+            panic!(
+                "Snapshot {} differs at line {}\nexpected: {:?}\nactual:   {:?}\nRe-run with {}=1 to accept the new output.",
+                golden_path.display(),
+                line + 1,
+                expected.lines().nth(line).unwrap_or("<end of file>"),
+                actual.lines().nth(line).unwrap_or("<end of file>"),
+                UPDATE_SNAPSHOTS_ENV
+            );
+        }
+        Ok(())
+    }
+
+    /// A scratch project with an icons folder, for driving `iconmate` in
+    /// tests. Made by [`IconFolderBuilder`]; a folder it created under the
+    /// system temp dir is removed on drop.
+    #[derive(Debug)]
+    pub struct IconFolder {
+        root: PathBuf,
+        folder: PathBuf,
+        owned: bool,
+    }
+
+    impl IconFolder {
+        pub fn builder() -> IconFolderBuilder {
+            IconFolderBuilder::default()
+        }
+
+        /// The project root: run `iconmate` from here.
+        pub fn root(&self) -> &Path {
+            &self.root
+        }
+
+        /// The icons folder.
+        pub fn path(&self) -> &Path {
+            &self.folder
+        }
+
+        pub fn index_path(&self) -> PathBuf {
+            self.folder.join("index.ts")
+        }
+
+        /// A file in the icons folder.
+        pub fn read(&self, file: &str) -> Result<String> {
+            Ok(fs::read_to_string(self.folder.join(file))?)
+        }
+    }
+
+    impl Drop for IconFolder {
+        fn drop(&mut self) {
+            if self.owned {
+                let _ = fs::remove_dir_all(&self.root);
+            }
+        }
+    }
+
+    /// Builds an [`IconFolder`]: icon files, `index.ts` exports and an
+    /// optional `iconmate.config.json`.
+    ///
+    /// ```no_run
+    /// use iconmate::validation::IconFolder;
+    ///
+    /// let project = IconFolder::builder()
+    ///     .svg("IconHeart", "heart", "<svg viewBox=\"0 0 24 24\"/>")
+    ///     .config(r#"{ "preset": "normal" }"#)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(project.index_path().exists());
+    /// ```
+    #[derive(Debug, Default)]
+    pub struct IconFolderBuilder {
+        root: Option<PathBuf>,
+        folder: Option<String>,
+        files: Vec<(String, String)>,
+        exports: Vec<(String, String)>,
+        config: Option<String>,
+    }
+
+    impl IconFolderBuilder {
+        /// Build into `root` (kept on drop) instead of a fresh temp dir.
+        pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+            self.root = Some(root.into());
+            self
+        }
+
+        /// Icons folder relative to the root; `src/assets/icons` by default.
+        pub fn folder(mut self, folder: &str) -> Self {
+            self.folder = Some(folder.to_string());
+            self
+        }
+
+        /// A file in the icons folder, without an export.
+        pub fn file(mut self, name: &str, contents: &str) -> Self {
+            self.files.push((name.to_string(), contents.to_string()));
+            self
+        }
+
+        /// An `index.ts` export, e.g. `("IconHeart", "./heart.svg")`.
+        pub fn export(mut self, name: &str, path: &str) -> Self {
+            self.exports.push((name.to_string(), path.to_string()));
+            self
+        }
+
+        /// `<stem>.svg` and its export, the way the `normal` preset writes them.
+        pub fn svg(self, name: &str, stem: &str, svg: &str) -> Self {
+            self.file(&format!("{}.svg", stem), svg)
+                .export(name, &format!("./{}.svg", stem))
+        }
+
+        /// Contents of `iconmate.config.json` at the root.
+        pub fn config(mut self, json: &str) -> Self {
+            self.config = Some(json.to_string());
+            self
+        }
+
+        pub fn build(self) -> Result<IconFolder> {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let (root, owned) = match self.root {
+                Some(root) => (root, false),
+                None => {
+                    let unique = format!(
+                        "iconmate-test-{}-{}",
+                        std::process::id(),
+                        NEXT.fetch_add(1, Ordering::Relaxed)
+                    );
+                    (std::env::temp_dir().join(unique), true)
+                }
+            };
+            let folder = root.join(self.folder.as_deref().unwrap_or("src/assets/icons"));
+            // Owns the root from here, so a failed build cleans up too.
+            let icon_folder = IconFolder {
+                root,
+                folder,
+                owned,
+            };
+            if owned && icon_folder.root.exists() {
+                fs::remove_dir_all(&icon_folder.root)?;
+            }
+            fs::create_dir_all(&icon_folder.folder)?;
+
+            for (name, contents) in &self.files {
+                let path = icon_folder.folder.join(name);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, contents)?;
+            }
+            let index = self
+                .exports
+                .iter()
+                .map(|(name, path)| format!("export {{ default as {} }} from '{}';\n", name, path))
+                .collect::<String>();
+            fs::write(icon_folder.index_path(), index)?;
+            if let Some(config) = &self.config {
+                fs::write(icon_folder.root.join("iconmate.config.json"), config)?;
+            }
+            Ok(icon_folder)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -102,5 +336,61 @@ mod tests {
             &["export { default as IconTest } from './test.svg';"],
         )
         .expect("Index content should be verified");
+
+        // A Prettier-formatted barrel uses double quotes.
+        fs::write(
+            &index_path,
+            "export { default as IconTest } from \"./test.svg\";\n",
+        )
+        .expect("Failed to write index.ts");
+        validation::verify_files_created(&test_folder, "Test", "test")
+            .expect("Double-quoted exports should be verified");
+    }
+
+    #[test]
+    fn icon_folder_builder_and_index_matchers() {
+        let project = validation::IconFolder::builder()
+            .svg("IconHeart", "heart", "<svg>heart</svg>")
+            .export("IconStar", "./star")
+            .file("star.tsx", "export default function IconStar() {}")
+            .config(r#"{ "preset": "react" }"#)
+            .build()
+            .expect("Failed to build icon folder");
+        let root = project.root().to_path_buf();
+        assert!(root.join("iconmate.config.json").exists());
+        assert_eq!(project.read("heart.svg").unwrap(), "<svg>heart</svg>");
+
+        validation::assert_index_exports(
+            &project.index_path(),
+            &[("IconStar", "./star"), ("IconHeart", "./heart.svg")],
+        )
+        .unwrap();
+        validation::assert_index_lacks_export(&project.index_path(), "IconClose").unwrap();
+        fs::write(
+            project.index_path(),
+            "export const IconBell = lazy(() => import(\"./bell.svg?react\"))\nexport {\n  default as IconX,\n} from './x.svg'",
+        )
+        .unwrap();
+        validation::assert_index_exports(
+            &project.index_path(),
+            &[("IconBell", "./bell.svg"), ("IconX", "./x.svg")],
+        )
+        .unwrap();
+
+        drop(project);
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn snapshot_records_then_compares() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let golden = temp_dir.path().join("snapshots/heart.tsx.snap");
+
+        validation::assert_snapshot(&golden, "<svg/>\r\n").unwrap();
+        assert_eq!(fs::read_to_string(&golden).unwrap(), "<svg/>\n");
+        validation::assert_snapshot(&golden, "<svg/>").unwrap();
+
+        let mismatch = std::panic::catch_unwind(|| validation::assert_snapshot(&golden, "<svg />"));
+        assert!(mismatch.is_err());
     }
 }
@@ -1,19 +1,66 @@
+mod adopt;
 mod app_state;
+mod appicon;
+mod barrel;
+mod changelog;
+mod check;
+mod checksums;
+mod code_style;
+mod collection_prefs;
 mod config;
+mod config_formats;
+mod crash;
+mod css_background;
+mod css_mask;
+mod datauri;
+mod dedupe;
+mod detect;
+mod diff;
+mod emoji;
 mod flutter;
+mod gallery;
+mod groups;
+#[cfg(feature = "headless")]
+mod headless;
+mod history;
+mod hooks;
+mod html_partial;
+mod http;
+mod http_cache;
+mod icon_names;
+mod icon_registry;
 mod iconify;
+mod iconify_json;
+mod iconify_mock;
+mod index_writer;
+mod js_statements;
+mod lazy_export;
+mod normalize;
+mod package;
+mod parallel;
+mod progress;
+mod raster;
+mod sanitize;
 mod scroll;
+mod serve;
+mod size_budget;
+mod svg_format;
+mod svg_render;
+mod svgr;
 mod sync;
+mod tags;
+mod template;
 mod tui;
 mod utils;
 mod viewer;
 mod views;
 
+use crate::hooks::Hook;
 use crate::iconify::{IconifyClient, IconifyCollectionResponse, IconifySearchResponse};
 use crate::utils::{
-    _determine_icon_source_type, _icon_source_to_svg, _make_svg_filename, IconEntry,
-    IconSourceType, PRESETS_OPTIONS, Preset, default_name_and_filename_from_icon_source,
-    render_js_export_line,
+    _determine_icon_source_type, _icon_source_to_svg, AliasCase, AliasStyle, FilenameCase,
+    IconEntry, IconSourceType, ImportQuery, PRESETS_OPTIONS, Preset,
+    default_name_and_filename_from_icon_source, make_icon_filename, render_js_export_line,
 };
 use clap::{Parser, Subcommand, ValueEnum};
 use serde::Serialize;
@@ -55,8 +102,29 @@ struct CliArgs {
     /// Flutter preset only: Dart class name in the barrel.
     #[arg(long)]
     flutter_barrel_class: Option<String>,
+
+    /// Never start the TUI; print the icon list instead. Implied when stdin or
+    /// stdout is not a terminal (CI, pipes).
+    #[arg(long)]
+    no_tui: bool,
+
+    /// Hide progress spinners (they are also hidden when stderr is not a
+    /// terminal or `CI` is set).
+    #[arg(long, short = 'q', global = true)]
+    quiet: bool,
+
+    /// Print each HTTP request with its status and timing to stderr.
+    #[arg(long, short = 'v', global = true)]
+    verbose: bool,
+
+    /// Answer Iconify requests (collections, search, SVGs) from a fixtures
+    /// folder instead of the network, for tests and offline demos.
+    #[arg(long, global = true, value_name = "FIXTURES_DIR")]
+    mock_api: Option<PathBuf>,
 }
 
+// Parsed once per run, so `Add`'s many flags aren't worth boxing.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 enum Commands {
     /// Add an icon by specifying its details via command-line arguments.
@@ -66,8 +134,15 @@ enum Commands {
         preset: Option<Preset>,
 
         /// Pathname of the folder where the icon will be saved and index.ts updated.
+        /// Repeat it to add the icon to several folders at once; `folder=preset`
+        /// (e.g. `mobile/icons=flutter`) picks that target's preset.
+        #[arg(long, required = true, value_name = "FOLDER[=PRESET]")]
+        folder: Vec<PathBuf>,
+
+        /// Save the icon in this subfolder of --folder (e.g. "social"), with
+        /// its own index.ts that the folder's index.ts re-exports.
         #[arg(long)]
-        folder: PathBuf,
+        group: Option<String>,
 
         /// The alias for the SVG, used in the index.ts export (e.g., "Chevron").
         /// Optional when --icon is a URL or iconify id — iconmate auto-infers from the icon name.
@@ -89,11 +164,111 @@ enum Commands {
         /// Flutter preset only: Dart class name in the barrel. Default: AppIcons
         #[arg(long)]
         flutter_barrel_class: Option<String>,
+
+        /// Also render the SVG to raster images next to it (e.g. heart.32.png).
+        #[arg(long, value_enum)]
+        raster: Option<raster::RasterFormat>,
+
+        /// Raster sizes in pixels, comma-separated. Default: 16,32,64
+        #[arg(long, value_delimiter = ',', requires = "raster")]
+        sizes: Vec<u32>,
+
+        /// Refuse to write the icon unless the fetched SVG has this SHA-256 (hex).
+        /// Get it with `iconmate iconify get <icon> --format sha256`.
+        #[arg(long, requires = "icon")]
+        expect_sha256: Option<String>,
+
+        /// Export name casing: pascal (IconHeart), camel (iconHeart), constant (ICON_HEART).
+        /// Defaults to `alias_case` from the local config, then pascal.
+        #[arg(long, value_enum)]
+        alias_case: Option<AliasCase>,
+
+        /// Prepended to the alias in the export name. Default: Icon (pass "" for none)
+        #[arg(long)]
+        alias_prefix: Option<String>,
+
+        /// Appended to the alias in the export name (e.g. "Icon" for HeartIcon).
+        #[arg(long)]
+        alias_suffix: Option<String>,
+
+        /// Include the collection in inferred aliases (lucide:heart -> LucideHeart).
+        /// Without it, the prefixed form is only used when the plain alias is taken.
+        #[arg(long)]
+        alias_collection_prefix: bool,
+
+        /// Keep comments, `<?xml ?>` prologs and DOCTYPEs in saved SVGs.
+        /// JSX and Vue presets always strip them.
+        #[arg(long)]
+        no_sanitize: bool,
+
+        /// Lay the saved markup out on one line (minified) or one element per
+        /// line (pretty). Defaults to `svg_format` from the local config, then
+        /// the source's own formatting.
+        #[arg(long, value_enum)]
+        svg_format: Option<svg_format::SvgFormat>,
+
+        /// Spaces per nesting level for `--svg-format pretty`. Default: 2
+        #[arg(long)]
+        svg_indent: Option<usize>,
+
+        /// Line endings for the icon file and index.ts. Defaults to
+        /// `line_endings` from the local config, then lf.
+        #[arg(long, value_enum)]
+        line_endings: Option<svg_format::LineEnding>,
+
+        /// Iconify emoji set for `--icon emoji:🔥` or a bare emoji. Defaults
+        /// to `emoji_set` from the local config, then noto.
+        #[arg(long)]
+        emoji_set: Option<String>,
+
+        /// Bundler query on the index.ts import (`./heart.svg?react`): react
+        /// (vite-plugin-svgr), component (vite-svg-loader), raw or url.
+        /// Defaults to `import_query` from the local config. .svg presets only.
+        #[arg(long, value_enum)]
+        import_query: Option<ImportQuery>,
+
+        /// Write React components the way SVGR does (`Svg<Name>` with a
+        /// `title` prop, `forwardRef` and `memo`), so icons generated before
+        /// migrating don't change. Defaults to `svgr_compat` from the local
+        /// config. React preset only.
+        #[arg(long)]
+        svgr_compat: bool,
+
+        /// Export the icon as `lazy(() => import(...))` (`defineAsyncComponent`
+        /// for Vue) so it's split out of the main bundle. Defaults to
+        /// `lazy_exports` from the local config. React, Solid and Vue presets.
+        #[arg(long)]
+        lazy: bool,
+
+        /// Write the icon even if it's over `max_icon_size` from the local
+        /// config.
+        #[arg(long)]
+        force: bool,
+
+        /// What to do when the export name or file already exists.
+        #[arg(long, value_enum, default_value = "error")]
+        on_conflict: OnConflict,
     },
 
     /// Start an interactive prompt to add icons.
     Tui {},
 
+    /// Write an iconmate.config.jsonc here. Suggests a preset (and import
+    /// query) from package.json, vite.config and next.config, or pubspec.yaml.
+    Init {
+        /// Pathname of the icons folder. Defaults to the preset's default folder.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Preset to use instead of the detected one.
+        #[arg(long)]
+        preset: Option<Preset>,
+
+        /// Take the detected preset without asking.
+        #[arg(long)]
+        auto: bool,
+    },
+
     /// Delete an icon from your collection of icons
     Delete {
         /// Pathname of the folder where all the icons are saved.
@@ -110,17 +285,157 @@ enum Commands {
         #[arg(long = "filename")]
         filenames: Vec<String>,
 
+        /// Delete every icon from an Iconify collection (e.g. "carbon"), matched by
+        /// the default `<prefix>_<icon>` filename. Can be passed multiple times.
+        #[arg(long = "prefix")]
+        prefixes: Vec<String>,
+
         /// Skip the confirmation prompt. Required for non-interactive deletes.
         #[arg(long, short = 'y')]
         yes: bool,
     },
 
+    /// Swap an existing icon's content for a new source. The alias, filename and
+    /// export line stay the same.
+    Replace {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Export name of the icon to replace (e.g. "IconHeart"), or its Dart
+        /// identifier with the flutter preset.
+        #[arg(long)]
+        name: String,
+
+        /// The new source: an iconify id (e.g. "lucide:heart"), a URL, or an SVG.
+        #[arg(long)]
+        icon: String,
+
+        /// Refuse to write the icon unless the fetched SVG has this SHA-256 (hex).
+        #[arg(long)]
+        expect_sha256: Option<String>,
+
+        /// Keep comments, `<?xml ?>` prologs and DOCTYPEs in the new SVG.
+        #[arg(long)]
+        no_sanitize: bool,
+
+        /// Show a diff of the file and ask before overwriting it.
+        #[arg(long)]
+        diff: bool,
+
+        /// Print the diff and exit without writing.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// With --diff, write without asking. Required when not interactive.
+        #[arg(long, short = 'y', requires = "diff")]
+        yes: bool,
+    },
+
     /// List all icons currently exported in the icons folder.
     #[command(visible_alias = "ls")]
     List {
         /// Pathname of the folder where all the icons are saved.
         #[arg(long)]
         folder: Option<PathBuf>,
+
+        /// Only icons tagged with TAG in icon-tags.json. Repeat to require
+        /// several tags.
+        #[arg(long, value_name = "TAG")]
+        tag: Vec<String>,
+    },
+
+    /// Audit the icons folder for barrel patterns that defeat tree shaking.
+    /// Exits with 1 when it finds any.
+    Check {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Scan every icons folder configured below the current directory and
+        /// flag export names with different artwork in different packages.
+        #[arg(long, conflicts_with = "folder")]
+        workspace: bool,
+    },
+
+    /// Write an ICONS.md preview grid of every icon into the icons folder.
+    /// Once it exists, add/delete keep it up to date.
+    Gallery {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+    },
+
+    /// Write an icons-bg.css of `background-image` classes (`.icon-heart`),
+    /// one per SVG, for codebases that can't inline SVG. Once it exists,
+    /// add/delete keep it up to date.
+    Css {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+    },
+
+    /// Reveal the icons folder in Finder, Explorer or the xdg file manager.
+    Open {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+    },
+
+    /// Print an icon in the terminal, drawn with braille characters (or sixel
+    /// graphics) by the same renderer as the TUI's add preview.
+    Preview {
+        /// Export name (e.g. IconHeart) or path to an icon file.
+        target: String,
+
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Width in terminal columns.
+        #[arg(long, default_value_t = 16)]
+        size: usize,
+
+        /// Draw with sixel graphics, for terminals that support them.
+        #[arg(long)]
+        sixel: bool,
+    },
+
+    /// Serve a searchable browser gallery of the icons folder.
+    /// Clicking an icon copies its import statement.
+    Serve {
+        /// Pathname of the folder where all the icons are saved.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Port to listen on.
+        #[arg(long, default_value_t = serve::DEFAULT_SERVE_PORT)]
+        port: u16,
+
+        /// Address to bind. Use 0.0.0.0 to share on your network.
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+
+    /// Generate favicon.ico, apple-touch-icon, and web manifest icons from one SVG.
+    Appicon {
+        /// Icon source: an Iconify name (e.g. "mdi:rocket"), a URL, or raw SVG.
+        #[arg(long)]
+        icon: String,
+
+        /// Output directory, usually your static/public folder.
+        #[arg(long, default_value = "public")]
+        out: PathBuf,
+
+        /// Background color behind the maskable icon's safe zone.
+        #[arg(long, default_value = "#ffffff")]
+        background: String,
+    },
+
+    /// Check `%var%` templates before putting them in a config.
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
     },
 
     /// Query Iconify collections, search results, and raw SVGs.
@@ -149,6 +464,132 @@ enum Commands {
         #[arg(long = "rename", value_name = "OLD=NEW")]
         renames: Vec<String>,
     },
+
+    /// Merge icons whose files are identical (same bytes or same path data):
+    /// other exports are repointed at one canonical file and the redundant
+    /// files deleted. Dry-run by default.
+    Dedupe {
+        /// Pathname of the folder where icons live.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Actually rewrite index.ts and delete files. Without this flag,
+        /// dedupe prints the diff and exits.
+        #[arg(long)]
+        apply: bool,
+
+        /// Export to keep as the canonical icon of its group, instead of the
+        /// first one in index.ts. Repeatable.
+        #[arg(long, value_name = "NAME")]
+        keep: Vec<String>,
+    },
+
+    /// Rename existing icon files to one filename convention and update
+    /// index.ts. Dry-run by default.
+    Normalize {
+        /// Pathname of the folder where icons live.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Filename case. Defaults to `filename_case` from the local config,
+        /// then kebab.
+        #[arg(long = "case", value_enum)]
+        case: Option<FilenameCase>,
+
+        /// Also rewrite imports of the renamed files in source files under the
+        /// project root (the nearest package.json).
+        #[arg(long)]
+        rewrite_imports: bool,
+
+        /// Actually rename files. Without this flag, normalize prints the plan
+        /// and exits.
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Write index.ts for a folder of icons that has none, inferring export
+    /// names from the filenames. Also writes iconmate.config.jsonc when the
+    /// project has no local config. Dry-run by default.
+    Adopt {
+        /// Pathname of the folder where icons live.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Actually write index.ts. Without this flag, adopt prints the plan
+        /// and exits.
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Show the history of adds, deletes and renames from .iconmate/log.jsonl,
+    /// newest first.
+    Log {
+        /// Only entries for this export name (e.g. `IconHeart`).
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Show at most this many entries.
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Print the matching JSON lines as they are stored.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Summarize icons added, removed, renamed and updated since a git ref as
+    /// Markdown for release notes.
+    Changelog {
+        /// Pathname of the folder where icons live.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Git ref to compare against, e.g. the last release tag.
+        #[arg(long, value_name = "GIT_REF")]
+        since: String,
+    },
+    /// Build a publishable npm package (per-icon ES modules, index, types and
+    /// a package.json exports map) from the icons folder.
+    Package {
+        /// Pathname of the folder where icons live.
+        #[arg(long)]
+        folder: Option<PathBuf>,
+
+        /// Folder to write the package into.
+        #[arg(long, default_value = "dist")]
+        out: PathBuf,
+
+        /// Package name. Defaults to the name in ./package.json.
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Drive the TUI without a terminal: run a script of key presses (`press`,
+    /// `type`, `wait`, `expect`, `screen`) and print the rendered screens.
+    #[cfg(feature = "headless")]
+    Headless {
+        /// Script file, or `-` to read it from stdin.
+        script: PathBuf,
+
+        /// Screen width in columns.
+        #[arg(long, default_value_t = 100)]
+        width: u16,
+
+        /// Screen height in rows.
+        #[arg(long, default_value_t = 30)]
+        height: u16,
+    },
+}
+
+/// What `add` does when the export name or target file is already taken.
+#[derive(Clone, Copy, Debug, Default, ValueEnum, PartialEq)]
+enum OnConflict {
+    /// Fail without writing anything.
+    #[default]
+    Error,
+    /// Leave everything as it is and exit successfully, so re-running the
+    /// same add is a no-op.
+    Skip,
 }
 
 #[derive(Clone, Debug, ValueEnum, PartialEq)]
@@ -161,6 +602,26 @@ enum OutputFormat {
 enum GetFormat {
     Svg,
     Json,
+    /// SHA-256 of the SVG, for `add --expect-sha256`.
+    Sha256,
+}
+
+#[derive(Debug, Subcommand)]
+enum TemplateCommands {
+    /// Render a template with sample values and report unknown variables
+    /// and filters.
+    Test {
+        /// The template, e.g. "export { default as %name% } from './%filename%%ext%';".
+        template: String,
+
+        /// Sample icon alias.
+        #[arg(long, default_value = "Heart")]
+        name: String,
+
+        /// Sample Iconify id, for %prefix%, %icon% and %filename%.
+        #[arg(long, default_value = "mdi:heart")]
+        icon: String,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -216,14 +677,48 @@ enum IconifyCommands {
 }
 
 /// Configuration for the icon fetching and saving logic.
+#[derive(Clone)]
 struct AppConfig {
     folder: PathBuf,
+    /// Subfolder of `folder` the icon goes in, re-exported from `folder`.
+    group: Option<String>,
     name: Option<String>,
     icon: Option<String>,
     filename: Option<String>,
     preset: Option<Preset>,
     flutter_barrel_file: Option<PathBuf>,
     flutter_barrel_class: Option<String>,
+    raster: Option<raster::RasterFormat>,
+    raster_sizes: Vec<u32>,
+    /// Hex SHA-256 the fetched SVG must match before anything is written.
+    expect_sha256: Option<String>,
+    alias_style: AliasStyle,
+    hooks: hooks::Hooks,
+    svg_output: svg_format::SvgOutput,
+    barrel_frame: barrel::BarrelFrame,
+    code_style: code_style::CodeStyle,
+    /// Create `icon-names.d.ts` if it isn't there yet.
+    icon_names_dts: bool,
+    /// Create `icon-registry.ts` and `Icon.tsx` if they aren't there yet.
+    icon_registry: bool,
+    /// Appended to `.svg` imports in index.ts.
+    import_query: Option<ImportQuery>,
+    /// Render React components in SVGR's shape.
+    svgr_compat: bool,
+    /// Wrap the export in the preset's lazy loader.
+    lazy_exports: bool,
+    /// Case for filenames inferred from the icon or name.
+    filename_case: Option<FilenameCase>,
+    size_budget: Option<size_budget::SizeBudget>,
+    /// Write icons over `size_budget` anyway.
+    force: bool,
+    on_conflict: OnConflict,
+    /// Where the add is recorded, unless `history_log` is off.
+    history: Option<history::HistoryLog>,
+    /// Barrel language of the icons folder.
+    index_strategy: index_writer::IndexStrategy,
+    /// `class` attribute value for html partials, e.g. `{{ class }}`.
+    html_class_hook: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -394,6 +889,10 @@ async fn run_iconify_command(command: IconifyCommands) -> anyhow::Result<()> {
                     .map_err(iconify_error_to_anyhow)?;
                 print_json(&payload)?;
             }
+            GetFormat::Sha256 => {
+                let svg = client.svg(&icon).await.map_err(iconify_error_to_anyhow)?;
+                println!("{}", crate::checksums::hex_digest(svg.as_bytes()));
+            }
         },
     }
 
@@ -437,179 +936,442 @@ fn resolve_icon_alias(
     Ok((default_name, collection))
 }
 
+/// How far `run_app` goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddStep {
+    /// Render the icon, validate the target exactly like a real add and run
+    /// `pre_add_cmd`, then stop before anything is written.
+    Check,
+    /// Write a target that already passed `Check`, without its pre hook.
+    WriteChecked,
+    /// Validate, run `pre_add_cmd` and write.
+    Write,
+}
+
+impl AddStep {
+    fn writes(self) -> bool {
+        self != AddStep::Check
+    }
+}
+
+/// `add` for every `--folder` target. The icon is fetched once and shared by
+/// every target. With several, each one is checked against that fetch first,
+/// pre hook included, so a conflict or a failing `pre_add_cmd` in one folder
+/// leaves all of them untouched, and writing can't fail on a second download.
+async fn run_app_targets(configs: Vec<AppConfig>) -> anyhow::Result<()> {
+    let mut pin = checksums::Pin::new(
+        configs
+            .first()
+            .and_then(|config| config.expect_sha256.as_deref()),
+    );
+    if configs.len() > 1 {
+        for (index, config) in configs.iter().enumerate() {
+            if configs[..index]
+                .iter()
+                .any(|other| other.folder == config.folder)
+            {
+                anyhow::bail!(
+                    "--folder {} is given more than once.",
+                    config.folder.display()
+                );
+            }
+        }
+        let bar = progress::Bar::counted("Checking folders", configs.len());
+        for config in &configs {
+            run_app(config.clone(), AddStep::Check, &mut pin)
+                .await
+                .map_err(|error| {
+                    anyhow::anyhow!(
+                        "{error:#}\nNothing was written: {} can't take this icon.",
+                        config.folder.display()
+                    )
+                })?;
+            bar.inc();
+        }
+        bar.finish();
+    }
+    let step = if configs.len() > 1 {
+        AddStep::WriteChecked
+    } else {
+        AddStep::Write
+    };
+    for config in configs {
+        run_app(config, step, &mut pin).await?;
+    }
+    Ok(())
+}
+
+/// `web/icons` or `mobile/icons=flutter`. A suffix that isn't a preset name
+/// stays part of the path.
+fn parse_add_target(value: &Path) -> (PathBuf, Option<Preset>) {
+    value
+        .to_str()
+        .and_then(|value| value.rsplit_once('='))
+        .and_then(|(folder, preset)| Some((PathBuf::from(folder), Some(Preset::from_str(preset)?))))
+        .unwrap_or_else(|| (value.to_path_buf(), None))
+}
+
 /// The main logic of the application.
 /// Fetches an icon, saves it, and updates the index (or Dart barrel).
-async fn run_app(config: AppConfig) -> anyhow::Result<()> {
-    let folder_path = &config.folder;
+async fn run_app(config: AppConfig, step: AddStep, pin: &mut checksums::Pin) -> anyhow::Result<()> {
+    let root = &config.folder;
+    let group_folder = config.group.as_ref().map(|group| root.join(group));
+    let folder_path = group_folder.as_ref().unwrap_or(root);
     let effective_preset = config.preset.clone().unwrap_or(Preset::Normal);
 
     // For Flutter, --name may be lowerCamelCase from user; for JS presets
     // PascalCase is conventional. Either way, `resolve_icon_alias` returns the
     // raw string — sanitization per-preset happens below.
-    let (raw_alias, collection_hint) =
+    let (mut raw_alias, collection_hint) =
         resolve_icon_alias(config.name.as_deref(), config.icon.as_deref())?;
+    let alias_inferred = config
+        .name
+        .as_deref()
+        .is_none_or(|name| name.trim().is_empty());
+    let prefixed_alias = collection_hint
+        .as_deref()
+        .filter(|_| alias_inferred)
+        .map(|prefix| crate::utils::collection_prefixed_alias(prefix, &raw_alias));
+    if config.alias_style.collection_prefix
+        && let Some(prefixed) = &prefixed_alias
+    {
+        raw_alias = prefixed.clone();
+    }
 
-    fs::create_dir_all(folder_path)?;
-
-    if matches!(effective_preset, Preset::Flutter) {
-        return run_app_flutter(config, raw_alias, collection_hint).await;
+    if config.group.is_some() && matches!(effective_preset, Preset::Flutter) {
+        anyhow::bail!(
+            "--group needs an index.ts; the flutter preset keeps every icon in one Dart barrel."
+        );
+    }
+    if config.group.is_some() && matches!(effective_preset, Preset::Rust) {
+        anyhow::bail!("--group needs an index.ts; the rust preset keeps every icon in one mod.rs.");
+    }
+    if config.group.is_some() && matches!(effective_preset, Preset::Html) {
+        anyhow::bail!(
+            "--group needs an index.ts; the html preset lists partials in one partials.json."
+        );
+    }
+    if config.group.is_some() && config.index_strategy != index_writer::IndexStrategy::Ts {
+        anyhow::bail!(
+            "--group needs an index.ts, but index_strategy is '{}'.",
+            config.index_strategy.to_str()
+        );
     }
 
-    let icon_alias = raw_alias.clone();
+    if step.writes() {
+        fs::create_dir_all(folder_path)?;
+    }
 
-    // Determine SVG content and filename stem based on a valid combination of arguments.
-    let (svg_content, file_stem_str, ext) = match (&config.icon, effective_preset) {
-        // Case 1: Icon is provided AND the preset is EmptySvg. This is the only mutual exclusivity.
-        (Some(_), Preset::EmptySvg) => {
+    if matches!(effective_preset, Preset::Flutter) {
+        return run_app_flutter(config, raw_alias, collection_hint, step, pin).await;
+    }
+    if matches!(effective_preset, Preset::Rust) {
+        // The preset is `index_strategy = "rust"` with shorter names:
+        // `icons::HEART`, since the module path already says it's an icon.
+        if !matches!(
+            config.index_strategy,
+            index_writer::IndexStrategy::Ts | index_writer::IndexStrategy::Rust
+        ) {
             anyhow::bail!(
-                "The --icon argument cannot be used with the --preset emptysvg. Please provide only one or the other."
+                "--preset rust writes mod.rs, but index_strategy is '{}'.",
+                config.index_strategy.to_str()
             );
         }
+        let writer = index_writer::IndexStrategy::Rust.writer();
+        let export_name = writer.export_name(&raw_alias);
+        return run_app_index_writer(
+            AppConfig {
+                index_strategy: index_writer::IndexStrategy::Rust,
+                ..config
+            },
+            writer,
+            Some(export_name),
+            raw_alias,
+            effective_preset,
+            step,
+            pin,
+        )
+        .await;
+    }
+    if matches!(effective_preset, Preset::Html) {
+        return run_app_index_writer(
+            config,
+            html_partial::manifest_writer(),
+            None,
+            raw_alias,
+            effective_preset,
+            step,
+            pin,
+        )
+        .await;
+    }
+    if !config.index_strategy.is_js() {
+        let writer = config.index_strategy.writer();
+        let export_name = writer.export_name(&config.alias_style.export_name(&raw_alias));
+        return run_app_index_writer(
+            config,
+            writer,
+            Some(export_name),
+            raw_alias,
+            effective_preset,
+            step,
+            pin,
+        )
+        .await;
+    }
 
-        // Case 2: Only a preset is provided.
-        (None, Preset::EmptySvg) => {
-            let content = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24"></svg>"#.to_string();
-            let (file_stem, ext) = _make_svg_filename(
-                config.filename.as_ref(),
-                ".svg",
-                config.icon.as_ref(),
-                &icon_alias,
-            );
-            Ok::<(String, String, &'static str), anyhow::Error>((content, file_stem, ext))
-        }
+    let index_ts_path = folder_path.join(config.index_strategy.writer().file_name());
+    let existing_index = if index_ts_path.exists() {
+        Some(fs::read_to_string(&index_ts_path)?)
+    } else {
+        None
+    };
 
-        // Case 3: React
-        (icon_source, Preset::React) => {
-            let content = _icon_source_to_svg(icon_source, Some("{...props}"), true).await?;
-            let content = format!(
-                "import type {{ SVGProps }} from 'react';\n\nexport default function Icon(props: SVGProps<SVGSVGElement>) {{\n  return (\n{}\n  );\n}}",
-                content
-            );
-            let (file_stem, ext) = _make_svg_filename(
+    // Groups are re-exported with `export *`, so names must be unique across
+    // the whole tree, not just this barrel.
+    let tree_index = groups::tree_index_contents(root);
+    let icon_alias = dedupe_js_alias(
+        tree_index.as_deref().or(existing_index.as_deref()),
+        &raw_alias,
+        prefixed_alias.as_deref(),
+        &config.alias_style,
+    );
+
+    // SVGR names the component after the file, which for React is always .tsx.
+    let svgr_component =
+        (config.svgr_compat && matches!(effective_preset, Preset::React)).then(|| {
+            let (stem, _) = make_icon_filename(
                 config.filename.as_ref(),
                 ".tsx",
                 config.icon.as_ref(),
                 &icon_alias,
+                config.filename_case,
             );
-            Ok::<(String, String, &'static str), anyhow::Error>((content, file_stem, ext))
-        }
+            svgr::component_name(&stem)
+        });
+    let (svg_content, ext) = render_icon_content(
+        &config.icon,
+        &effective_preset,
+        pin,
+        &config.svg_output,
+        &config.code_style,
+        svgr_component.as_deref(),
+    )
+    .await?;
+    let (file_stem_str, ext) = make_icon_filename(
+        config.filename.as_ref(),
+        ext,
+        config.icon.as_ref(),
+        &icon_alias,
+        config.filename_case,
+    );
 
-        // Case 4: Svelte
-        (icon_source, Preset::Svelte) => {
-            let content = _icon_source_to_svg(icon_source, Some("{...props}"), false).await?;
-            let content = format!(
-                "<script lang=\"ts\">\n  import type {{ SVGAttributes }} from 'svelte/elements';\n\n  let {{ ...props }}: SVGAttributes<SVGSVGElement> = $props();\n</script>\n\n{}",
-                content
-            );
-            let (file_stem, ext) = _make_svg_filename(
-                config.filename.as_ref(),
-                ".svelte",
-                config.icon.as_ref(),
-                &icon_alias,
-            );
-            Ok::<(String, String, &'static str), anyhow::Error>((content, file_stem, ext))
-        }
-
-        // Case 5: Solid
-        (icon_source, Preset::Solid) => {
-            let content = _icon_source_to_svg(icon_source, Some("{...props}"), true).await?;
-            let content = format!(
-                "import {{ type JSX }} from 'solid-js';\n\nexport default function Icon(props: JSX.SvgSVGAttributes<SVGSVGElement>) {{\n  return ({});\n}}",
-                content
-            );
-            let (file_stem, ext) = _make_svg_filename(
-                config.filename.as_ref(),
-                ".tsx",
-                config.icon.as_ref(),
-                &icon_alias,
-            );
-            Ok::<(String, String, &'static str), anyhow::Error>((content, file_stem, ext))
-        }
-
-        // Case 6: Vue
-        (icon_source, Preset::Vue) => {
-            let content = _icon_source_to_svg(icon_source, Some("v-bind=\"$props\""), true).await?;
-            let content = format!(
-                "<template>\n  <template>\n    {}\n  </template>\n</template>\n\n<script setup lang=\"ts\">\nimport type {{ SVGAttributes }} from 'vue'\n\ndefineProps<SVGAttributes>()\n</script>",
-                content
-            );
-            let (file_stem, ext) = _make_svg_filename(
-                config.filename.as_ref(),
-                ".vue",
-                config.icon.as_ref(),
-                &icon_alias,
-            );
-            Ok::<(String, String, &'static str), anyhow::Error>((content, file_stem, ext))
-        }
-
-        // Case 7: Only an icon is provided in `normal` mode.
-        (Some(icon_source), Preset::Normal) => {
-            let content = _icon_source_to_svg(&Some(icon_source.clone()), None, false).await?;
-            let (file_stem, ext) = _make_svg_filename(
-                config.filename.as_ref(),
-                ".svg",
-                config.icon.as_ref(),
-                &icon_alias,
-            );
-            Ok((content, file_stem, ext))
-        }
-
-        // Case 8: Normal mode still requires an icon source.
-        (None, Preset::Normal) => {
-            anyhow::bail!("The --icon argument is required when --preset is normal.");
-        }
-
-        // Case 9: Flutter — handled above via run_app_flutter, unreachable here.
-        (_, Preset::Flutter) => unreachable!("Flutter handled in run_app_flutter"),
-    }?;
+    if config.raster.is_some() && ext != ".svg" {
+        anyhow::bail!(
+            "--raster needs an .svg output, but --preset {} writes {} files.",
+            effective_preset.to_str(),
+            ext
+        );
+    }
 
     // The rest of the function can now safely assume it has the content and a filename stem.
     let svg_file_name = format!("{}{}", file_stem_str, ext);
     let svg_file_path = folder_path.join(&svg_file_name);
 
     // Update or create index.ts
-    let index_ts_path = folder_path.join("index.ts");
-    let existing_index = if index_ts_path.exists() {
-        Some(fs::read_to_string(&index_ts_path)?)
-    } else {
-        None
-    };
     let rendered_export_statement = render_js_export_line(
         existing_index.as_deref(),
         folder_path,
         &icon_alias,
+        &config.alias_style,
+        &config.code_style,
         &file_stem_str,
-        ext,
+        &ImportQuery::import_ext(config.import_query, ext),
     );
+    let lazy_loader = config
+        .lazy_exports
+        .then(|| lazy_export::LazyLoader::for_preset(&effective_preset))
+        .flatten();
+    let rendered_export_statement = match lazy_loader {
+        Some(loader) => loader.wrap(&rendered_export_statement),
+        None => rendered_export_statement,
+    };
     let export_line = format!("{}\n", rendered_export_statement);
 
-    if let Some(existing_index) = existing_index.as_deref() {
-        validate_new_export_conflicts(existing_index, &rendered_export_statement, &index_ts_path)?;
+    let export_name = config.alias_style.export_name(&icon_alias);
+    let conflict = existing_index
+        .as_deref()
+        .and_then(|existing_index| {
+            validate_new_export_conflicts(existing_index, &rendered_export_statement, &index_ts_path)
+                .err()
+        })
+        .or_else(|| {
+            groups::find_export(root, &export_name, &index_ts_path).map(|path| {
+                anyhow::anyhow!(
+                    "Icon alias '{}' already exists in {}. Choose a different --name or rename the existing export.",
+                    export_name,
+                    path.display()
+                )
+            })
+        })
+        .or_else(|| {
+            svg_file_path.exists().then(|| {
+                anyhow::anyhow!(
+                    "Target icon file already exists: {}. Choose a different --filename (or --name when filename is omitted).",
+                    svg_file_path.display()
+                )
+            })
+        });
+    if let Some(error) = conflict {
+        if config.on_conflict == OnConflict::Skip {
+            if step.writes() {
+                println!("Skipped {export_name}: {error}");
+            }
+            return Ok(());
+        }
+        return Err(error);
+    }
+    pin.check_recorded(folder_path, &export_name, config.icon.as_deref())?;
+    let file_content = config.svg_output.finish(&svg_content);
+    check_size_budget(&config, &svg_file_name, &file_content, step)?;
+    if step != AddStep::WriteChecked {
+        config
+            .hooks
+            .run_and_print(Hook::PreAdd, &svg_file_path, &export_name)?;
+    }
+    if step == AddStep::Check {
+        return Ok(());
     }
 
-    if svg_file_path.exists() {
-        anyhow::bail!(
-            "Target icon file already exists: {}. Choose a different --filename (or --name when filename is omitted).",
-            svg_file_path.display()
+    fs::write(&svg_file_path, file_content)?;
+    println!("Successfully saved icon to: {}", svg_file_path.display());
+
+    // Like the Flutter barrel: no icon file without its export.
+    if let Err(error) = add_js_export(
+        &index_ts_path,
+        &export_line,
+        &icon_alias,
+        &config.barrel_frame,
+        config.svg_output.line_ending,
+        lazy_loader,
+    ) {
+        let _ = fs::remove_file(&svg_file_path);
+        return Err(error);
+    }
+    pin.record(folder_path, &export_name, config.icon.as_deref())?;
+
+    if let Some(group) = &config.group
+        && groups::ensure_group_reexport(
+            root,
+            group,
+            &rendered_export_statement,
+            &config.barrel_frame,
+            config.svg_output.line_ending,
+        )?
+    {
+        println!(
+            "Re-exported {group} from: {}",
+            root.join("index.ts").display()
         );
     }
 
-    fs::write(&svg_file_path, &svg_content)?;
-    println!("Successfully saved icon to: {}", svg_file_path.display());
+    if matches!(effective_preset, Preset::Css) {
+        let stylesheet_path = crate::css_mask::write_mask_stylesheet(folder_path)?;
+        println!("Updated CSS mask classes in: {}", stylesheet_path.display());
+    }
+
+    if matches!(effective_preset, Preset::DataUri) {
+        let bundle_path = crate::datauri::write_datauri_bundle(folder_path)?;
+        println!("Updated data URI bundle in: {}", bundle_path.display());
+    }
+
+    gallery::refresh_gallery_if_present(root, effective_preset.to_str(), None, None)?;
+    css_background::refresh_background_stylesheet_if_present(root)?;
+    icon_names::refresh_icon_names(root, config.icon_names_dts)?;
+    icon_registry::refresh_icon_registry(root, config.icon_registry)?;
 
+    write_rasters(&config, &svg_file_path)?;
+
+    if let Some(history) = &config.history {
+        history.record(history::Entry::add(
+            &export_name,
+            &svg_file_path,
+            config.icon.as_deref(),
+        ));
+    }
+    config
+        .hooks
+        .run_and_print(Hook::PostAdd, &svg_file_path, &export_name)?;
+
+    Ok(())
+}
+
+/// Swaps an emoji icon source (`emoji:🔥`, or just `🔥`) for the Iconify
+/// name it resolves to in `emoji_set`; other sources pass through.
+async fn resolve_emoji_icon(
+    icon: Option<String>,
+    emoji_set: &str,
+) -> anyhow::Result<Option<String>> {
+    let Some(emoji) = icon.as_deref().and_then(emoji::emoji_from_icon_source) else {
+        return Ok(icon);
+    };
+    let iconify_name = emoji::resolve_emoji(emoji_set, emoji).await?;
+    println!("Resolved {emoji} to {iconify_name}");
+    Ok(Some(iconify_name))
+}
+
+/// Swaps an Iconify name that only renames another icon (an alias a set
+/// keeps for an old name) for the icon it points at, so the canonical name
+/// is fetched and recorded. A failed lookup leaves the source as it was.
+async fn resolve_renamed_icon(icon: Option<String>) -> Option<String> {
+    if !matches!(
+        _determine_icon_source_type(icon.as_ref()),
+        IconSourceType::IconifyName
+    ) {
+        return icon;
+    }
+    let Some(name) = icon
+        .as_deref()
+        .and_then(crate::utils::iconify_name_from_icon_source)
+    else {
+        return icon;
+    };
+    let Ok(client) = IconifyClient::from_env() else {
+        return icon;
+    };
+    match client.canonical_name(&name).await {
+        Ok(Some(canonical)) => {
+            eprintln!(
+                "Warning: {name} is a deprecated alias of {canonical}; adding {canonical} instead."
+            );
+            Some(canonical)
+        }
+        _ => icon,
+    }
+}
+
+fn add_js_export(
+    index_ts_path: &Path,
+    export_line: &str,
+    icon_alias: &str,
+    barrel_frame: &barrel::BarrelFrame,
+    line_ending: svg_format::LineEnding,
+    lazy_loader: Option<lazy_export::LazyLoader>,
+) -> anyhow::Result<()> {
+    let with_lazy_import = |contents: String| match lazy_loader {
+        Some(loader) => loader.ensure_import(&contents, export_line),
+        None => contents,
+    };
     if index_ts_path.exists() {
-        let mut contents = fs::read_to_string(&index_ts_path)?;
+        let contents = fs::read_to_string(index_ts_path)?;
         let export_line_trimmed = export_line.trim_end();
         let export_already_exists = contents
             .lines()
             .any(|line| line.trim_end() == export_line_trimmed);
 
         if !export_already_exists {
-            if !contents.is_empty() && !contents.ends_with('\n') {
-                contents.push('\n');
-            }
-            contents.push_str(&export_line);
-            fs::write(&index_ts_path, contents)?;
+            let contents = with_lazy_import(barrel_frame.append(&contents, export_line));
+            fs::write(index_ts_path, line_ending.normalize(&contents))?;
             println!("Added export to: {}", index_ts_path.display());
         } else {
             println!(
@@ -619,11 +1381,204 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
             );
         }
     } else {
-        let mut file = fs::File::create(&index_ts_path)?;
-        file.write_all(export_line.as_bytes())?;
+        let mut file = fs::File::create(index_ts_path)?;
+        file.write_all(
+            line_ending
+                .normalize(&with_lazy_import(barrel_frame.create(export_line)))
+                .as_bytes(),
+        )?;
         println!("Created and wrote export to: {}", index_ts_path.display());
     }
+    Ok(())
+}
+
+/// File content and extension for `icon` under `preset`, based on a valid
+/// combination of the two. Shared by `add` and `replace`. `output.sanitize`
+/// only matters for presets that tolerate comments; JSX, Vue and data URIs
+/// are always sanitized. Data URIs are always minified. Component templates
+/// follow `code_style`. With `svgr_component`, React output is the SVGR
+/// component of that name instead of the built-in template.
+async fn render_icon_content(
+    icon: &Option<String>,
+    preset: &Preset,
+    pin: &mut checksums::Pin,
+    output: &svg_format::SvgOutput,
+    code_style: &code_style::CodeStyle,
+    svgr_component: Option<&str>,
+) -> anyhow::Result<(String, &'static str)> {
+    match (icon, preset) {
+        // Case 1: Icon is provided AND the preset is EmptySvg. This is the only mutual exclusivity.
+        (Some(_), Preset::EmptySvg) => {
+            anyhow::bail!(
+                "The --icon argument cannot be used with the --preset emptysvg. Please provide only one or the other."
+            );
+        }
+
+        // Case 2: Only a preset is provided.
+        (None, Preset::EmptySvg) => {
+            let content = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24"></svg>"#.to_string();
+            Ok((output.apply(&content), ".svg"))
+        }
+
+        // Case 3a: React, as SVGR would have written it
+        (icon_source, Preset::React) if let Some(component) = svgr_component => {
+            let content =
+                _icon_source_to_svg(icon_source, Some(svgr::ROOT_ATTRIBUTES), true, pin).await?;
+            let mut content = svgr::svg_to_jsx(&output.apply(&content));
+            if output.is_pretty() {
+                let indent = code_style.indent(1);
+                content = format!(
+                    "{indent}{}",
+                    svg_format::indent_continuation(&content, &indent)
+                );
+            }
+            let content = template::render(
+                &code_style.apply_to_template(template::REACT_SVGR_COMPONENT),
+                &template::Vars::new()
+                    .with("component", component)
+                    .with("svg", content),
+            );
+            Ok((content, ".tsx"))
+        }
+
+        // Case 3: React
+        (icon_source, Preset::React) => {
+            let content = _icon_source_to_svg(icon_source, Some("{...props}"), true, pin).await?;
+            let mut content = output.apply(&content);
+            if output.is_pretty() {
+                // The placeholder sits on its own line inside `return (`.
+                let indent = code_style.indent(2);
+                content = format!(
+                    "{indent}{}",
+                    svg_format::indent_continuation(&content, &indent)
+                );
+            }
+            let content = template::render(
+                &code_style.apply_to_template(template::REACT_COMPONENT),
+                &template::Vars::new().with("svg", content),
+            );
+            Ok((content, ".tsx"))
+        }
+
+        // Case 4: Svelte
+        (icon_source, Preset::Svelte) => {
+            let content =
+                _icon_source_to_svg(icon_source, Some("{...props}"), output.sanitize, pin).await?;
+            let content = output.apply(&content);
+            let content = template::render(
+                &code_style.apply_to_template(template::SVELTE_COMPONENT),
+                &template::Vars::new().with("svg", content),
+            );
+            Ok((content, ".svelte"))
+        }
+
+        // Case 5: Solid
+        (icon_source, Preset::Solid) => {
+            let content = _icon_source_to_svg(icon_source, Some("{...props}"), true, pin).await?;
+            let mut content = output.apply(&content);
+            if output.is_pretty() {
+                // `return (%svg%);` wraps onto its own indented block.
+                let indent = code_style.indent(2);
+                content = format!(
+                    "\n{indent}{}\n{}",
+                    svg_format::indent_continuation(&content, &indent),
+                    code_style.indent(1)
+                );
+            }
+            let content = template::render(
+                &code_style.apply_to_template(template::SOLID_COMPONENT),
+                &template::Vars::new().with("svg", content),
+            );
+            Ok((content, ".tsx"))
+        }
 
+        // Case 6: Vue
+        (icon_source, Preset::Vue) => {
+            let content =
+                _icon_source_to_svg(icon_source, Some("v-bind=\"$props\""), true, pin).await?;
+            let mut content = output.apply(&content);
+            if output.is_pretty() {
+                content = svg_format::indent_continuation(&content, &code_style.indent(2));
+            }
+            let content = template::render(
+                &code_style.apply_to_template(template::VUE_COMPONENT),
+                &template::Vars::new().with("svg", content),
+            );
+            Ok((content, ".vue"))
+        }
+
+        // Case 7b: Server-side include; `html_class_hook` is added by the caller.
+        (Some(icon_source), Preset::Html) => {
+            let content =
+                _icon_source_to_svg(&Some(icon_source.clone()), None, output.sanitize, pin).await?;
+            Ok((output.apply(&content), ".svg.html"))
+        }
+
+        // Case 7: Data URI constant
+        (Some(icon_source), Preset::DataUri) => {
+            let content = _icon_source_to_svg(&Some(icon_source.clone()), None, true, pin).await?;
+            let content = crate::datauri::render_datauri_module(&content);
+            Ok((content, ".ts"))
+        }
+
+        // Case 8: Only an icon is provided in `normal` (or `css`/`flutter`/`rust`) mode.
+        (Some(icon_source), Preset::Normal | Preset::Css | Preset::Flutter | Preset::Rust) => {
+            let content =
+                _icon_source_to_svg(&Some(icon_source.clone()), None, output.sanitize, pin).await?;
+            Ok((output.apply(&content), ".svg"))
+        }
+
+        // Case 9: Normal mode still requires an icon source.
+        (None, Preset::Normal) => {
+            anyhow::bail!("The --icon argument is required when --preset is normal.");
+        }
+        (None, Preset::Css) => {
+            anyhow::bail!("The --icon argument is required when --preset is css.");
+        }
+        (None, Preset::DataUri) => {
+            anyhow::bail!("The --icon argument is required when --preset is datauri.");
+        }
+        (None, Preset::Flutter) => {
+            anyhow::bail!("The --icon argument is required for --preset flutter.");
+        }
+        (None, Preset::Rust) => {
+            anyhow::bail!("The --icon argument is required when --preset is rust.");
+        }
+        (None, Preset::Html) => {
+            anyhow::bail!("The --icon argument is required when --preset is html.");
+        }
+    }
+}
+
+/// Holds an icon to `max_icon_size`. Warnings print once, when writing.
+fn check_size_budget(
+    config: &AppConfig,
+    file_name: &str,
+    content: &str,
+    step: AddStep,
+) -> anyhow::Result<()> {
+    let Some(budget) = &config.size_budget else {
+        return Ok(());
+    };
+    if let Some(warning) = budget.check_add(file_name, content.len() as u64, config.force)?
+        && step.writes()
+    {
+        eprintln!("Warning: {warning}.");
+    }
+    Ok(())
+}
+
+/// Renders `--raster` outputs for a freshly written SVG, if requested.
+fn write_rasters(config: &AppConfig, svg_file_path: &Path) -> anyhow::Result<()> {
+    let Some(format) = config.raster else {
+        return Ok(());
+    };
+    let spinner = progress::Spinner::start("Rendering rasters");
+    let written = raster::render_rasters(svg_file_path, format, &config.raster_sizes)?;
+    spinner.finish();
+    for path in written {
+        println!("Rendered raster: {}", path.display());
+    }
     Ok(())
 }
 
@@ -633,6 +1588,8 @@ async fn run_app_flutter(
     config: AppConfig,
     raw_alias: String,
     collection_hint: Option<String>,
+    step: AddStep,
+    pin: &mut checksums::Pin,
 ) -> anyhow::Result<()> {
     let folder_path = &config.folder;
     let folder_str = folder_path.to_string_lossy().replace('\\', "/");
@@ -650,20 +1607,37 @@ async fn run_app_flutter(
     let Some(icon_source) = config.icon.as_ref() else {
         anyhow::bail!("The --icon argument is required for --preset flutter.");
     };
-    let svg_content = _icon_source_to_svg(&Some(icon_source.clone()), None, false).await?;
+    let svg_content = _icon_source_to_svg(
+        &Some(icon_source.clone()),
+        None,
+        config.svg_output.sanitize,
+        pin,
+    )
+    .await?;
+    let svg_content = config.svg_output.apply(&svg_content);
 
     // Resolve SVG filename on disk. Prefer --filename, otherwise derive a
     // snake_case-ish stem from the icon source or name.
-    let (file_stem, ext) = _make_svg_filename(
+    let (file_stem, ext) = make_icon_filename(
         config.filename.as_ref(),
         ".svg",
         config.icon.as_ref(),
         &raw_alias,
+        config.filename_case,
     );
     let file_name = format!("{}{}", file_stem, ext);
     let svg_file_path = folder_path.join(&file_name);
 
     if svg_file_path.exists() {
+        if config.on_conflict == OnConflict::Skip {
+            if step.writes() {
+                println!(
+                    "Skipped {raw_alias}: {} already exists.",
+                    svg_file_path.display()
+                );
+            }
+            return Ok(());
+        }
         anyhow::bail!(
             "Target icon file already exists: {}. Choose a different --filename.",
             svg_file_path.display()
@@ -684,16 +1658,35 @@ async fn run_app_flutter(
 
     let asset_path = crate::flutter::asset_path_for(&folder_str, &file_name);
     let updated = crate::flutter::add_entry(&existing_entries, &identifier, &asset_path)?;
+    pin.check_recorded(folder_path, &identifier, Some(icon_source))?;
+    let file_content = config.svg_output.finish(&svg_content);
+    check_size_budget(&config, &file_name, &file_content, step)?;
+    if step != AddStep::WriteChecked {
+        config
+            .hooks
+            .run_and_print(Hook::PreAdd, &svg_file_path, &identifier)?;
+    }
+    if step == AddStep::Check {
+        return Ok(());
+    }
 
     // Write the SVG first, then the barrel. If the barrel write fails we roll
     // back the SVG so partial state doesn't leak.
-    fs::write(&svg_file_path, &svg_content)?;
+    fs::write(&svg_file_path, file_content)?;
     println!("Successfully saved icon to: {}", svg_file_path.display());
 
     if let Err(err) = crate::flutter::write_barrel(&barrel_path, &barrel_class, &updated) {
         let _ = fs::remove_file(&svg_file_path);
         return Err(err);
     }
+    pin.record(folder_path, &identifier, Some(icon_source))?;
+    gallery::refresh_gallery_if_present(
+        folder_path,
+        "flutter",
+        barrel_path.to_str(),
+        Some(&barrel_class),
+    )?;
+    write_rasters(&config, &svg_file_path)?;
 
     println!(
         "Updated barrel at {}: added {}.{}",
@@ -701,6 +1694,16 @@ async fn run_app_flutter(
         barrel_class,
         identifier
     );
+    if let Some(history) = &config.history {
+        history.record(history::Entry::add(
+            &identifier,
+            &svg_file_path,
+            config.icon.as_deref(),
+        ));
+    }
+    config
+        .hooks
+        .run_and_print(Hook::PostAdd, &svg_file_path, &identifier)?;
 
     if let Some(project) = crate::flutter::detect_flutter_project(
         &std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
@@ -716,6 +1719,185 @@ async fn run_app_flutter(
     Ok(())
 }
 
+/// Add flow for the rust and html presets and the `dart` and `rust` index
+/// strategies: write the SVG and append `export_name` to the folder's
+/// `mod.rs`, `partials.json` or `icons.dart`. Without one the
+/// entry is named after the file, since partials are included by file name.
+async fn run_app_index_writer(
+    config: AppConfig,
+    writer: &dyn index_writer::IndexWriter,
+    export_name: Option<String>,
+    raw_alias: String,
+    preset: Preset,
+    step: AddStep,
+    pin: &mut checksums::Pin,
+) -> anyhow::Result<()> {
+    let folder_path = &config.folder;
+    let index_path = folder_path.join(writer.file_name());
+
+    let (svg_content, ext) = render_icon_content(
+        &config.icon,
+        &preset,
+        pin,
+        &config.svg_output,
+        &config.code_style,
+        None,
+    )
+    .await?;
+    if ext != ".svg" && !matches!(preset, Preset::Html) {
+        anyhow::bail!(
+            "index_strategy '{}' embeds .svg files, but --preset {} writes {} files.",
+            config.index_strategy.to_str(),
+            preset.to_str(),
+            ext
+        );
+    }
+    if config.raster.is_some() && ext != ".svg" {
+        anyhow::bail!(
+            "--raster needs an .svg output, but --preset {} writes {} files.",
+            preset.to_str(),
+            ext
+        );
+    }
+    let svg_content = match (&preset, &config.html_class_hook) {
+        (Preset::Html, Some(hook)) => html_partial::with_class_hook(&svg_content, hook),
+        _ => svg_content,
+    };
+    let (file_stem, ext) = make_icon_filename(
+        config.filename.as_ref(),
+        ext,
+        config.icon.as_ref(),
+        &raw_alias,
+        config.filename_case,
+    );
+    let file_stem = if matches!(preset, Preset::Html) {
+        html_partial::partial_stem(&file_stem)
+    } else {
+        file_stem
+    };
+    let file_name = format!("{file_stem}{ext}");
+    let svg_file_path = folder_path.join(&file_name);
+    let export_name = export_name.unwrap_or_else(|| file_stem.trim_start_matches('_').to_string());
+
+    let existing_index = if index_path.exists() {
+        Some(fs::read_to_string(&index_path)?)
+    } else {
+        None
+    };
+    let taken = existing_index.as_deref().is_some_and(|contents| {
+        writer
+            .parse(contents)
+            .iter()
+            .any(|entry| entry.name == export_name)
+    });
+    let conflict = if taken {
+        Some(anyhow::anyhow!(
+            "Icon alias '{}' already exists in {}. Choose a different --name or rename the existing export.",
+            export_name,
+            index_path.display()
+        ))
+    } else {
+        svg_file_path.exists().then(|| {
+            anyhow::anyhow!(
+                "Target icon file already exists: {}. Choose a different --filename (or --name when filename is omitted).",
+                svg_file_path.display()
+            )
+        })
+    };
+    if let Some(error) = conflict {
+        if config.on_conflict == OnConflict::Skip {
+            if step.writes() {
+                println!("Skipped {export_name}: {error}");
+            }
+            return Ok(());
+        }
+        return Err(error);
+    }
+    pin.check_recorded(folder_path, &export_name, config.icon.as_deref())?;
+    let file_content = config.svg_output.finish(&svg_content);
+    check_size_budget(&config, &file_name, &file_content, step)?;
+    if step != AddStep::WriteChecked {
+        config
+            .hooks
+            .run_and_print(Hook::PreAdd, &svg_file_path, &export_name)?;
+    }
+    if step == AddStep::Check {
+        return Ok(());
+    }
+
+    fs::write(&svg_file_path, file_content)?;
+    println!("Successfully saved icon to: {}", svg_file_path.display());
+
+    let entry = writer.render_entry(
+        &export_name,
+        &folder_path.to_string_lossy().replace('\\', "/"),
+        &file_name,
+    );
+    let contents = match &existing_index {
+        Some(contents) => writer.append(contents, &entry),
+        None => writer.create(&entry),
+    };
+    if let Err(error) = fs::write(
+        &index_path,
+        config.svg_output.line_ending.normalize(&contents),
+    ) {
+        let _ = fs::remove_file(&svg_file_path);
+        return Err(error.into());
+    }
+    pin.record(folder_path, &export_name, config.icon.as_deref())?;
+    println!("Added export to: {}", index_path.display());
+
+    if matches!(preset, Preset::Css) {
+        let stylesheet_path = crate::css_mask::write_mask_stylesheet(folder_path)?;
+        println!("Updated CSS mask classes in: {}", stylesheet_path.display());
+    }
+    write_rasters(&config, &svg_file_path)?;
+
+    if let Some(history) = &config.history {
+        history.record(history::Entry::add(
+            &export_name,
+            &svg_file_path,
+            config.icon.as_deref(),
+        ));
+    }
+    config
+        .hooks
+        .run_and_print(Hook::PostAdd, &svg_file_path, &export_name)?;
+
+    Ok(())
+}
+
+/// An inferred `Heart` that's already exported falls back to `LucideHeart`.
+/// Explicit `--name` values never get here (`prefixed` is `None`), so they
+/// still fail loudly in `validate_new_export_conflicts`.
+fn dedupe_js_alias(
+    existing_index: Option<&str>,
+    alias: &str,
+    prefixed: Option<&str>,
+    alias_style: &AliasStyle,
+) -> String {
+    let (Some(existing_index), Some(prefixed)) = (existing_index, prefixed) else {
+        return alias.to_string();
+    };
+    if prefixed == alias {
+        return alias.to_string();
+    }
+
+    let taken = alias_style.export_name(alias);
+    if collect_icons_from_index_contents(existing_index)
+        .iter()
+        .any(|entry| entry.name == taken)
+    {
+        println!(
+            "'{}' already exists; using '{}' instead.",
+            taken,
+            alias_style.export_name(prefixed)
+        );
+        return prefixed.to_string();
+    }
+    alias.to_string()
+}
+
 fn normalize_export_target(value: &str) -> String {
     value
         .trim()
@@ -788,157 +1970,227 @@ async fn run_prompt_mode(cli: &CliArgs) -> anyhow::Result<()> {
         }
     };
 
-    let icon = match &cli.icon {
-        Some(i) => {
-            println!("> 🚀 Icon: {}", i);
-            Some(i.clone())
-        }
-        None => {
-            if matches!(preset, Some(Preset::EmptySvg)) {
-                None
-            } else {
-                let icon_raw = Text::new(
-                    "🚀 Icon (name like 'heroicons:heart' from https://icones.js.org, full URL, any SVG, or leave empty)\n",
-                )
-                .with_render_config(render_config.clone())
-                .prompt()?;
-                if icon_raw.is_empty() {
-                    None
-                } else {
-                    Some(icon_raw)
-                }
+    let resolved = config::resolve_tui_config(Some(&folder), preset.as_ref())?;
+    let alias_style = resolved.alias_style;
+    let hooks = resolved.hooks;
+    let svg_output = resolved.svg_output;
+    let emoji_set = resolved.emoji_set;
+    let barrel_frame = resolved.barrel_frame;
+    let code_style = resolved.code_style;
+    let icon_names_dts = resolved.icon_names_dts;
+    let icon_registry = resolved.icon_registry;
+    let import_query = resolved.import_query;
+    let svgr_compat = resolved.svgr_compat;
+    let lazy_exports = resolved.lazy_exports;
+    let size_budget = resolved.size_budget;
+    let filename_case = resolved.filename_case;
+    let history = resolved.history;
+    let index_strategy = resolved.index_strategy;
+    let html_class_hook = resolved.html_class_hook;
+
+    // Folder and preset stay fixed for the session; everything below repeats
+    // once per icon until the user says they're done.
+    let mut first_round = true;
+    loop {
+        let icon = match cli.icon.as_ref().filter(|_| first_round) {
+            Some(i) => {
+                println!("> 🚀 Icon: {}", i);
+                Some(i.clone())
             }
-        }
-    };
-
-    let filename = match &cli.filename {
-        Some(f) => {
-            println!(">  Filename: {}", f);
-            Some(f.clone())
-        }
-        None => match _determine_icon_source_type(icon.as_ref()) {
-            IconSourceType::None | IconSourceType::SvgContent => {
-                let f = Text::new(" Filename (without extension like .svg, or leave empty)")
-                    .with_render_config(render_config.clone())
-                    .prompt()?;
-                if f.is_empty() {
-                    // Empty filename is allowed, will use the name instead
-                    println!("  Filename left empty, will use the name as filename...");
+            None => {
+                if matches!(preset, Some(Preset::EmptySvg)) {
                     None
+                } else if let Some(picked) = prompt_iconify_search(&render_config).await? {
+                    Some(picked)
                 } else {
-                    Some(f)
-                }
-            }
-            _ => None,
-        },
-    };
-
-    let inferred_name = icon
-        .as_ref()
-        .and_then(|icon_source| default_name_and_filename_from_icon_source(icon_source))
-        .map(|(name, _)| name);
-
-    let name: Option<String> = match &cli.name {
-        Some(n) => {
-            println!("> ✧ Name: {}", n);
-            Some(n.clone())
-        }
-        None => {
-            let mut prompt = Text::new("✧ Name (leave empty to auto-infer from icon)")
-                .with_render_config(render_config);
-
-            if let Some(default_name) = inferred_name.as_deref() {
-                prompt = prompt.with_default(default_name);
-            }
-
-            let raw = prompt.prompt()?;
-            if raw.trim().is_empty() {
-                None
-            } else {
-                Some(raw)
-            }
-        }
-    };
-
-    let config = AppConfig {
-        folder,
-        name,
-        icon,
-        filename,
-        preset,
-        flutter_barrel_file: cli.flutter_barrel_file.clone(),
-        flutter_barrel_class: cli.flutter_barrel_class.clone(),
-    };
-    run_app(config).await
-}
-
-impl std::fmt::Display for IconEntry {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} — {}", self.name, self.file_path)
-    }
-}
-
-fn collect_icons_from_index_contents(contents: &str) -> Vec<IconEntry> {
-    let mut icons = Vec::new();
-
-    for line in contents.lines() {
-        for statement in line.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() {
-                continue;
+                    let icon_raw = Text::new(
+                        "🚀 Icon (name like 'heroicons:heart' from https://icones.js.org, full URL, any SVG, or leave empty)\n",
+                    )
+                    .with_render_config(render_config.clone())
+                    .prompt()?;
+                    if icon_raw.is_empty() {
+                        None
+                    } else {
+                        Some(icon_raw)
+                    }
+                }
             }
+        };
+        let icon = resolve_renamed_icon(resolve_emoji_icon(icon, &emoji_set).await?).await;
 
-            if let Some(icon_entry) = crate::utils::parse_export_line_ts(statement) {
-                icons.push(icon_entry);
+        let filename = match cli.filename.as_ref().filter(|_| first_round) {
+            Some(f) => {
+                println!(">  Filename: {}", f);
+                Some(f.clone())
             }
+            None => match _determine_icon_source_type(icon.as_ref()) {
+                IconSourceType::None | IconSourceType::SvgContent | IconSourceType::DataUri => {
+                    let f = Text::new(" Filename (without extension like .svg, or leave empty)")
+                        .with_render_config(render_config.clone())
+                        .prompt()?;
+                    if f.is_empty() {
+                        // Empty filename is allowed, will use the name instead
+                        println!("  Filename left empty, will use the name as filename...");
+                        None
+                    } else {
+                        Some(f)
+                    }
+                }
+                _ => None,
+            },
+        };
+        if let Some(filename) = &filename {
+            utils::validate_icon_filename(filename)?;
         }
-    }
 
-    icons
-}
-
-#[cfg(test)]
-fn remove_selected_exports_from_index(contents: &str, selected_icons: &[IconEntry]) -> String {
-    use std::collections::HashSet;
+        let inferred_name = icon
+            .as_ref()
+            .and_then(|icon_source| default_name_and_filename_from_icon_source(icon_source))
+            .map(|(name, _)| name);
 
-    let selected = selected_icons
-        .iter()
-        .map(|icon| (icon.name.clone(), icon.file_path.clone()))
-        .collect::<HashSet<_>>();
+        let name: Option<String> = match cli.name.as_ref().filter(|_| first_round) {
+            Some(n) => {
+                println!("> ✧ Name: {}", n);
+                Some(n.clone())
+            }
+            None => {
+                let mut prompt = Text::new("✧ Name (leave empty to auto-infer from icon)")
+                    .with_render_config(render_config);
 
-    let mut kept_lines = Vec::<String>::new();
-    for line in contents.lines() {
-        let mut parsed_export_in_line = false;
+                if let Some(default_name) = inferred_name.as_deref() {
+                    prompt = prompt.with_default(default_name);
+                }
 
-        for statement in line.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() {
-                continue;
+                let raw = prompt.prompt()?;
+                if raw.trim().is_empty() {
+                    None
+                } else {
+                    Some(raw)
+                }
             }
+        };
 
-            let Some(entry) = crate::utils::parse_export_line_ts(statement) else {
-                continue;
-            };
+        let config = AppConfig {
+            folder: folder.clone(),
+            group: None,
+            name,
+            icon,
+            filename,
+            preset: preset.clone(),
+            flutter_barrel_file: cli.flutter_barrel_file.clone(),
+            flutter_barrel_class: cli.flutter_barrel_class.clone(),
+            raster: None,
+            raster_sizes: Vec::new(),
+            expect_sha256: None,
+            alias_style: alias_style.clone(),
+            hooks: hooks.clone(),
+            svg_output: svg_output.clone(),
+            barrel_frame: barrel_frame.clone(),
+            code_style,
+            icon_names_dts,
+            icon_registry,
+            import_query,
+            svgr_compat,
+            lazy_exports,
+            filename_case,
+            size_budget,
+            force: false,
+            on_conflict: OnConflict::Error,
+            history: history.clone(),
+            index_strategy,
+            html_class_hook: html_class_hook.clone(),
+        };
 
-            parsed_export_in_line = true;
-            if selected.contains(&(entry.name, entry.file_path)) {
-                continue;
-            }
+        let mut pin = checksums::Pin::new(config.expect_sha256.as_deref());
+        // Reported here so the loop can go on; a failed icon doesn't fail the
+        // session.
+        if let Err(error) = run_app(config, AddStep::Write, &mut pin).await {
+            eprintln!("Error: {error:#}");
+        }
+        match inquire::Confirm::new("Add another icon?")
+            .with_render_config(render_config)
+            .with_default(true)
+            .prompt()
+        {
+            Ok(true) => first_round = false,
+            Ok(false)
+            | Err(inquire::InquireError::OperationCanceled)
+            | Err(inquire::InquireError::OperationInterrupted) => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+const PROMPT_SEARCH_LIMIT: u32 = 96;
+
+/// Prompt-mode Iconify search: query -> fuzzy-filterable pick list. Returns
+/// `None` when the user skips search (empty query) or the API is unreachable,
+/// so the caller can fall back to typing a name/URL/SVG.
+async fn prompt_iconify_search(
+    render_config: &inquire::ui::RenderConfig<'static>,
+) -> anyhow::Result<Option<String>> {
+    use inquire::{InquireError, Select, Text};
+
+    let client = match IconifyClient::from_env() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!(
+                "Iconify search unavailable: {}",
+                iconify_error_to_anyhow(error)
+            );
+            return Ok(None);
+        }
+    };
+
+    loop {
+        let query = Text::new("🔎 Search Iconify (leave empty to type a name, URL, or SVG)")
+            .with_render_config(*render_config)
+            .prompt()?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(None);
+        }
 
-            kept_lines.push(format!("{statement};"));
+        let response = match client
+            .search(query, Some(PROMPT_SEARCH_LIMIT), None, false)
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                eprintln!("Iconify search failed: {}", iconify_error_to_anyhow(error));
+                return Ok(None);
+            }
+        };
+        if response.icons.is_empty() {
+            println!("  No icons found for '{query}'. Try another search.");
+            continue;
         }
 
-        if !parsed_export_in_line {
-            kept_lines.push(line.to_string());
+        let label = format!(
+            "🚀 Icon ({} of {} results; type to filter, esc to search again)",
+            response.icons.len(),
+            response.total
+        );
+        match Select::new(&label, response.icons)
+            .with_render_config(*render_config)
+            .with_page_size(12)
+            .prompt()
+        {
+            Ok(icon) => return Ok(Some(icon)),
+            Err(InquireError::OperationCanceled) => continue,
+            Err(error) => return Err(error.into()),
         }
     }
+}
 
-    let mut updated = kept_lines.join("\n");
-    if contents.ends_with('\n') {
-        updated.push('\n');
+impl std::fmt::Display for IconEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} — {}", self.name, self.file_path)
     }
+}
 
-    updated
+fn collect_icons_from_index_contents(contents: &str) -> Vec<IconEntry> {
+    crate::utils::parse_index_entries(contents)
 }
 
 fn resolve_list_folder<'a>(
@@ -948,7 +2200,11 @@ fn resolve_list_folder<'a>(
     command_folder.or(cli.folder.as_ref())
 }
 
-fn run_list_mode(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Result<()> {
+fn run_list_mode(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    tags: &[String],
+) -> anyhow::Result<()> {
     let resolved = config::resolve_tui_config(
         resolve_list_folder(cli, command_folder),
         cli.preset.as_ref(),
@@ -956,38 +2212,44 @@ fn run_list_mode(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Res
 
     let folder = PathBuf::from(&resolved.folder);
 
-    if resolved.preset == "flutter" {
+    let (icons, source) = if resolved.preset == "flutter" {
         let icons = crate::utils::get_existing_icons_for_preset(
             folder.to_string_lossy().as_ref(),
             &resolved.preset,
             resolved.flutter_barrel_file.as_deref(),
         )?;
-        if icons.is_empty() {
-            let barrel = resolved
-                .flutter_barrel_file
-                .unwrap_or_else(|| crate::flutter::DEFAULT_FLUTTER_BARREL_FILE.to_string());
-            println!("No icons found in {}", barrel);
+        let barrel = resolved
+            .flutter_barrel_file
+            .unwrap_or_else(|| crate::flutter::DEFAULT_FLUTTER_BARREL_FILE.to_string());
+        (icons, barrel)
+    } else {
+        let (_, index_path) = index_writer::find_barrel(&folder);
+        if !index_path.exists() {
+            println!("No icons found in {}", index_path.display());
             return Ok(());
         }
-        for icon in icons {
-            println!("{}\t{}", icon.name, icon.file_path);
-        }
-        return Ok(());
-    }
-
-    let index_ts_path = folder.join("index.ts");
-    if !index_ts_path.exists() {
-        println!("No icons found in {}", index_ts_path.display());
+        let icons = groups::icons_with_groups(&folder)?;
+        (icons, index_path.display().to_string())
+    };
+    if icons.is_empty() {
+        println!("No icons found in {}", source);
         return Ok(());
     }
 
-    let icons = crate::utils::get_existing_icons(folder.to_string_lossy().as_ref())?;
-    if icons.is_empty() {
-        println!("No icons found in {}", index_ts_path.display());
+    let icon_tags = if tags.is_empty() {
+        tags::IconTags::default()
+    } else {
+        tags::IconTags::read(&folder)?
+    };
+    let tagged = icons
+        .iter()
+        .filter(|icon| tags.iter().all(|tag| icon_tags.has(&icon.name, tag)))
+        .collect::<Vec<_>>();
+    if tagged.is_empty() {
+        println!("No icons tagged {}", tags.join(", "));
         return Ok(());
     }
-
-    for icon in icons {
+    for icon in tagged {
         println!("{}\t{}", icon.name, icon.file_path);
     }
 
@@ -1002,11 +2264,300 @@ fn resolve_delete_folder<'a>(
     command_folder.or(cli.folder.as_ref())
 }
 
+fn run_check_command(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_list_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`check` audits JS barrels; the flutter preset has none.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    let index_ts_path = folder.join("index.ts");
+    if !index_ts_path.exists() {
+        anyhow::bail!(
+            "No index.ts found in {}. Are you sure this is an icons folder?",
+            folder.display()
+        );
+    }
+
+    // Each audit's findings print as soon as it finishes, so the barrel's show
+    // before the (parallel) scan of every icon file.
+    let mut found = 0;
+    let mut report = |findings: Vec<check::Finding>| {
+        for finding in &findings {
+            print!("{}", finding.render());
+        }
+        found += findings.len();
+    };
+    report(check::audit_barrel(
+        &fs::read_to_string(&index_ts_path)?,
+        &index_ts_path.display().to_string(),
+    ));
+    report(check::audit_side_effects(&folder).into_iter().collect());
+    if let Some(budget) = &resolved.size_budget {
+        report(check::audit_sizes(&folder, &resolved.preset, budget));
+    }
+
+    if found == 0 {
+        println!("No problems found in {}.", folder.display());
+        return Ok(());
+    }
+    println!("\n{found} problem(s) found.");
+    std::process::exit(1);
+}
+
+fn run_workspace_check_command() -> anyhow::Result<()> {
+    let root = std::env::current_dir()?;
+    let mut warnings = Vec::new();
+    let folders = config::find_workspace_folders(&root, &mut warnings)?;
+    for warning in &warnings {
+        eprintln!("Warning: {warning}");
+    }
+    if folders.is_empty() {
+        anyhow::bail!(
+            "No iconmate configs found below {}. Run `check --workspace` from the monorepo root.",
+            root.display()
+        );
+    }
+
+    let findings = check::audit_workspace_aliases(&root, &folders);
+    if findings.is_empty() {
+        println!(
+            "No conflicting export names across {} icon folder(s).",
+            folders.len()
+        );
+        return Ok(());
+    }
+    for finding in &findings {
+        print!("{}", finding.render());
+    }
+    println!("\n{} problem(s) found.", findings.len());
+    std::process::exit(1);
+}
+
+fn run_gallery_command(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_list_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    let folder = PathBuf::from(&resolved.folder);
+    if !folder.is_dir() {
+        anyhow::bail!("Icons folder not found: {}", folder.display());
+    }
+
+    let gallery_path = gallery::write_gallery(
+        &folder,
+        &resolved.preset,
+        resolved.flutter_barrel_file.as_deref(),
+        resolved.flutter_barrel_class.as_deref(),
+    )?;
+    println!("Wrote icon gallery to: {}", gallery_path.display());
+    Ok(())
+}
+
+fn run_css_command(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_list_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`css` reads the folder's barrel; the flutter preset keeps icons in Dart.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    if !folder.is_dir() {
+        anyhow::bail!("Icons folder not found: {}", folder.display());
+    }
+
+    let stylesheet_path = css_background::write_background_stylesheet(&folder)?;
+    println!(
+        "Wrote background-image classes to: {}",
+        stylesheet_path.display()
+    );
+    Ok(())
+}
+
+fn run_open_command(cli: &CliArgs, command_folder: Option<&PathBuf>) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_list_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    let folder = PathBuf::from(&resolved.folder);
+    viewer::open_folder_in_file_manager(&folder)?;
+    println!("Opened {}", folder.display());
+    Ok(())
+}
+
+/// Variables `template test` fills in.
+const TEMPLATE_TEST_VARS: &[&str] = &[
+    "name", "icon", "prefix", "ext", "filename", "file", "folder",
+];
+
+fn run_template_command(cli: &CliArgs, command: &TemplateCommands) -> anyhow::Result<()> {
+    let TemplateCommands::Test {
+        template,
+        name,
+        icon,
+    } = command;
+    let resolved = config::resolve_tui_config(cli.folder.as_ref(), cli.preset.as_ref())?;
+    let ext = Preset::from_str(&resolved.preset)
+        .map(|preset| preset.extension())
+        .unwrap_or(".svg");
+    let filename = template::render(
+        template::ICONIFY_FILENAME,
+        &template::Vars::new().with_iconify_name(icon),
+    );
+    let vars = template::Vars::new()
+        .with("name", name)
+        .with_iconify_name(icon)
+        .with("ext", ext)
+        .with("file", format!("{filename}{ext}"))
+        .with("filename", filename)
+        .with("folder", resolved.folder.as_str());
+
+    let mut problems = template::check(template, TEMPLATE_TEST_VARS);
+    if template::references(template, "filename") && !template::references(template, "ext") {
+        problems.push(format!(
+            "%filename% without %ext%: {} files end in {ext}, so the path would miss it",
+            resolved.preset
+        ));
+    }
+    for problem in &problems {
+        eprintln!("Warning: {problem}");
+    }
+    println!("{}", template::render(template, &vars));
+    Ok(())
+}
+
+/// Pixels per terminal column for `preview --sixel`.
+const SIXEL_PIXELS_PER_COLUMN: usize = 8;
+
+fn run_preview_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    target: &str,
+    size: usize,
+    sixel: bool,
+) -> anyhow::Result<()> {
+    if size == 0 {
+        anyhow::bail!("--size must be at least 1.");
+    }
+    let (path, colors) = if Path::new(target).is_file() {
+        // A file path previews without a project; a broken config only
+        // costs it the configured colors.
+        let colors = config::resolve_tui_config(
+            resolve_list_folder(cli, command_folder),
+            cli.preset.as_ref(),
+        )
+        .map(|resolved| resolved.preview_colors)
+        .unwrap_or_default();
+        (PathBuf::from(target), colors)
+    } else {
+        let resolved = config::resolve_tui_config(
+            resolve_list_folder(cli, command_folder),
+            cli.preset.as_ref(),
+        )?;
+        let entries = utils::get_existing_icons_for_preset(
+            &resolved.folder,
+            &resolved.preset,
+            resolved.flutter_barrel_file.as_deref(),
+        )
+        .unwrap_or_default();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.name == target)
+            .or_else(|| {
+                entries
+                    .iter()
+                    .find(|entry| entry.name.eq_ignore_ascii_case(target))
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No icon named '{target}' in {} and no file at that path.",
+                    resolved.folder
+                )
+            })?;
+        (
+            utils::icon_file_path(Path::new(&resolved.folder), &entry.file_path),
+            resolved.preview_colors,
+        )
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))?;
+    let svg = viewer::svg_preview_contents(&contents)?;
+    // Braille cells are 2x4 dots and about twice as tall as wide, so a square
+    // icon `size` columns wide is `size * 2` dots each way.
+    let pixels = if sixel {
+        size * SIXEL_PIXELS_PER_COLUMN
+    } else {
+        size * 2
+    };
+    let bitmap = svg_render::rasterize(&svg, pixels, pixels)?;
+    if bitmap.is_blank() {
+        anyhow::bail!("Nothing to draw in {}.", path.display());
+    }
+
+    let rgb = |color: viewer::Rgb| [color.0, color.1, color.2];
+    if sixel {
+        println!(
+            "{}",
+            bitmap.to_sixel(colors.foreground.map(rgb), colors.background.map(rgb))
+        );
+    } else {
+        let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout())
+            && std::env::var_os("NO_COLOR").is_none();
+        let mut escape = String::new();
+        if use_color {
+            if let Some(viewer::Rgb(r, g, b)) = colors.foreground {
+                escape.push_str(&format!("\x1b[38;2;{r};{g};{b}m"));
+            }
+            if let Some(viewer::Rgb(r, g, b)) = colors.background {
+                escape.push_str(&format!("\x1b[48;2;{r};{g};{b}m"));
+            }
+        }
+        for line in bitmap.to_braille_lines() {
+            if escape.is_empty() {
+                println!("{line}");
+            } else {
+                println!("{escape}{line}\x1b[0m");
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_serve_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    host: &str,
+    port: u16,
+) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_list_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    let folder = PathBuf::from(&resolved.folder);
+    if !folder.is_dir() {
+        anyhow::bail!("Icons folder not found: {}", folder.display());
+    }
+
+    let ctx = serve::ServeContext {
+        folder,
+        preset: resolved.preset,
+        flutter_barrel_file: resolved.flutter_barrel_file,
+        flutter_barrel_class: resolved.flutter_barrel_class,
+        preview_colors: resolved.preview_colors,
+    };
+    serve::serve(ctx, host, port).await
+}
+
 fn run_delete_flutter(
     folder: &Path,
     resolved: &config::ResolvedTuiConfig,
     names: &[String],
     filenames: &[String],
+    prefixes: &[String],
 ) -> anyhow::Result<()> {
     let barrel_path: PathBuf = resolved
         .flutter_barrel_file
@@ -1044,6 +2595,18 @@ fn run_delete_flutter(
             None => missing.push(format!("filename={filename}")),
         }
     }
+    for prefix in prefixes {
+        let before = to_remove.len();
+        to_remove.extend(
+            entries
+                .iter()
+                .filter(|e| crate::utils::icon_from_collection(&e.asset_path, prefix))
+                .cloned(),
+        );
+        if to_remove.len() == before {
+            missing.push(format!("prefix={prefix}"));
+        }
+    }
 
     if !missing.is_empty() {
         anyhow::bail!("No matching icon(s) found for: {}", missing.join(", "));
@@ -1052,19 +2615,23 @@ fn run_delete_flutter(
     to_remove.sort_by(|a, b| a.identifier.cmp(&b.identifier));
     to_remove.dedup_by(|a, b| a.identifier == b.identifier);
 
+    for entry in &to_remove {
+        let svg_abs = flutter_asset_file(folder, &entry.asset_path);
+        resolved
+            .hooks
+            .run_and_print(Hook::PreDelete, &svg_abs, &entry.identifier)?;
+    }
+
     let mut current = entries;
     for entry in &to_remove {
         let (updated, _) = crate::flutter::remove_entry_by_path(&current, &entry.asset_path);
         current = updated;
 
         // Also delete the SVG on disk if it resolves inside the configured folder.
-        let asset_norm = entry.asset_path.replace('\\', "/");
-        let rel = if !folder_str.is_empty() && asset_norm.starts_with(&format!("{folder_str}/")) {
-            asset_norm[folder_str.len() + 1..].to_string()
-        } else {
-            asset_norm
-        };
-        let svg_abs = folder.join(&rel);
+        let svg_abs = flutter_asset_file(folder, &entry.asset_path);
+        if let Err(e) = raster::remove_raster_siblings(&svg_abs) {
+            eprintln!("Failed to delete rasters for {}: {}", svg_abs.display(), e);
+        }
         if svg_abs.exists() {
             if let Err(e) = fs::remove_file(&svg_abs) {
                 eprintln!("Failed to delete {}: {}", svg_abs.display(), e);
@@ -1072,27 +2639,64 @@ fn run_delete_flutter(
                 eprintln!("Deleted: {}", svg_abs.display());
             }
         }
+        if let Some(history) = &resolved.history {
+            history.record(history::Entry::delete(&entry.identifier, &svg_abs));
+        }
     }
 
     crate::flutter::write_barrel(&barrel_path, &class, &current)?;
+    gallery::refresh_gallery_if_present(folder, "flutter", barrel_path.to_str(), Some(&class))?;
     eprintln!(
         "Updated barrel at {} ({} entr{} removed).",
         barrel_path.display(),
         to_remove.len(),
         if to_remove.len() == 1 { "y" } else { "ies" }
     );
+    for entry in &to_remove {
+        let svg_abs = flutter_asset_file(folder, &entry.asset_path);
+        resolved
+            .hooks
+            .run_and_print(Hook::PostDelete, &svg_abs, &entry.identifier)?;
+    }
     Ok(())
 }
 
+/// The SVG on disk for a barrel `asset_path`, which may or may not carry the
+/// folder prefix.
+fn flutter_asset_file(folder: &Path, asset_path: &str) -> PathBuf {
+    let folder_str = folder.to_string_lossy().replace('\\', "/");
+    let asset_norm = asset_path.replace('\\', "/");
+    let rel = if !folder_str.is_empty() && asset_norm.starts_with(&format!("{folder_str}/")) {
+        asset_norm[folder_str.len() + 1..].to_string()
+    } else {
+        asset_norm
+    };
+    folder.join(rel)
+}
+
 fn apply_deletions(
     folder: &Path,
     _index_ts_path: &Path,
     to_delete: &[IconEntry],
+    hooks: &hooks::Hooks,
+    history: Option<&history::HistoryLog>,
 ) -> anyhow::Result<()> {
+    // Every pre hook runs before anything is removed, so one veto stops the batch.
+    for icon in to_delete {
+        hooks.run_and_print(Hook::PreDelete, &folder.join(&icon.file_path), &icon.name)?;
+    }
+    crate::utils::delete_icon_entries(folder, to_delete)?;
     for icon in to_delete {
         let full_path = folder.join(&icon.file_path);
-        crate::utils::delete_icon_entry(full_path.to_string_lossy().as_ref())?;
         eprintln!("Deleted: {}", full_path.display());
+        if let Some(history) = history {
+            history.record(history::Entry::delete(&icon.name, &full_path));
+        }
+    }
+    gallery::refresh_gallery_if_present(folder, "normal", None, None)?;
+    crate::utils::refresh_derived_files(folder)?;
+    for icon in to_delete {
+        hooks.run_and_print(Hook::PostDelete, &folder.join(&icon.file_path), &icon.name)?;
     }
     Ok(())
 }
@@ -1102,6 +2706,7 @@ fn run_delete_non_interactive(
     command_folder: Option<&PathBuf>,
     names: &[String],
     filenames: &[String],
+    prefixes: &[String],
     yes: bool,
 ) -> anyhow::Result<()> {
     if !yes {
@@ -1117,10 +2722,10 @@ fn run_delete_non_interactive(
     let folder = PathBuf::from(&resolved.folder);
 
     if resolved.preset == "flutter" {
-        return run_delete_flutter(&folder, &resolved, names, filenames);
+        return run_delete_flutter(&folder, &resolved, names, filenames, prefixes);
     }
 
-    let index_ts_path = folder.join("index.ts");
+    let (writer, index_ts_path) = index_writer::find_barrel(&folder);
     if !index_ts_path.exists() {
         anyhow::bail!(
             "No index.ts found in {}. Are you sure this is an icons folder?",
@@ -1129,10 +2734,10 @@ fn run_delete_non_interactive(
     }
 
     let contents = fs::read_to_string(&index_ts_path)?;
-    let icons = collect_icons_from_index_contents(&contents);
+    let icons = writer.parse(&contents);
 
     if icons.is_empty() {
-        println!("No icons found in index.ts");
+        println!("No icons found in {}", writer.file_name());
         return Ok(());
     }
 
@@ -1163,6 +2768,19 @@ fn run_delete_non_interactive(
         }
     }
 
+    for prefix in prefixes {
+        let before = to_delete.len();
+        to_delete.extend(
+            icons
+                .iter()
+                .filter(|i| crate::utils::icon_from_collection(&i.file_path, prefix))
+                .cloned(),
+        );
+        if to_delete.len() == before {
+            missing.push(format!("prefix={prefix}"));
+        }
+    }
+
     if !missing.is_empty() {
         anyhow::bail!("No matching icon(s) found for: {}", missing.join(", "));
     }
@@ -1171,7 +2789,179 @@ fn run_delete_non_interactive(
     to_delete.sort_by(|a, b| a.file_path.cmp(&b.file_path));
     to_delete.dedup_by(|a, b| a.name == b.name && a.file_path == b.file_path);
 
-    apply_deletions(&folder, &index_ts_path, &to_delete)
+    apply_deletions(
+        &folder,
+        &index_ts_path,
+        &to_delete,
+        &resolved.hooks,
+        resolved.history.as_ref(),
+    )
+}
+
+/// How `replace` shows the change before writing it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReplaceReview {
+    None,
+    /// `--diff --yes`: print the diff, then write.
+    Show,
+    /// `--diff`: print the diff and ask.
+    Confirm,
+    /// `--dry-run`: print the diff only.
+    DryRun,
+}
+
+/// `iconmate replace`: overwrite the file behind an existing export from a new
+/// source. Only the content changes; index.ts (or the Dart barrel) is left
+/// alone so every import keeps working.
+async fn run_replace_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    name: &str,
+    icon: &str,
+    expect_sha256: Option<&str>,
+    no_sanitize: bool,
+    review: ReplaceReview,
+) -> anyhow::Result<()> {
+    let resolved = config::resolve_tui_config(
+        resolve_delete_folder(cli, command_folder),
+        cli.preset.as_ref(),
+    )?;
+    let folder = PathBuf::from(&resolved.folder);
+    let preset = Preset::from_str(&resolved.preset)
+        .ok_or_else(|| anyhow::anyhow!("Invalid resolved preset '{}'.", resolved.preset))?;
+
+    let file_path = if matches!(preset, Preset::Flutter) {
+        let barrel_path = resolved
+            .flutter_barrel_file
+            .clone()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(crate::flutter::DEFAULT_FLUTTER_BARREL_FILE));
+        let entries = crate::flutter::read_barrel_entries(&barrel_path)?;
+        let Some(entry) = entries.iter().find(|e| e.identifier == name) else {
+            anyhow::bail!("No icon named '{name}' in {}", barrel_path.display());
+        };
+        flutter_asset_file(&folder, &entry.asset_path)
+    } else {
+        let index_ts_path = folder.join("index.ts");
+        if !index_ts_path.exists() {
+            anyhow::bail!(
+                "No index.ts found in {}. Are you sure this is an icons folder?",
+                folder.display()
+            );
+        }
+        let icons = collect_icons_from_index_contents(&fs::read_to_string(&index_ts_path)?);
+        let Some(entry) = icons.iter().find(|i| i.name == name) else {
+            anyhow::bail!("No icon named '{name}' in {}", index_ts_path.display());
+        };
+        crate::utils::icon_file_path(&folder, &entry.file_path)
+    };
+    if !file_path.exists() {
+        anyhow::bail!(
+            "'{name}' points at {}, which doesn't exist. Run `iconmate sync` first.",
+            file_path.display()
+        );
+    }
+
+    let mut svg_output = resolved.svg_output.clone();
+    svg_output.sanitize &= !no_sanitize;
+    let icon = resolve_emoji_icon(Some(icon.to_string()), &resolved.emoji_set)
+        .await?
+        .unwrap_or_default();
+    // A component SVGR wrote (or `--svgr-compat` did) stays in that shape.
+    let svgr_component = (matches!(preset, Preset::React)
+        && (resolved.svgr_compat
+            || fs::read_to_string(&file_path)
+                .is_ok_and(|existing| existing.contains("forwardRef("))))
+    .then(|| svgr::component_name(&file_path.file_stem().unwrap_or_default().to_string_lossy()));
+    let mut pin = checksums::Pin::new(expect_sha256);
+    let (content, ext) = render_icon_content(
+        &Some(icon.clone()),
+        &preset,
+        &mut pin,
+        &svg_output,
+        &resolved.code_style,
+        svgr_component.as_deref(),
+    )
+    .await?;
+    let existing_ext = file_path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    if existing_ext != ext {
+        anyhow::bail!(
+            "{} is a {} file, but --preset {} writes {} files. Pass the preset it was added with.",
+            file_path.display(),
+            existing_ext,
+            preset.to_str(),
+            ext
+        );
+    }
+
+    pin.check_recorded(&folder, name, Some(icon.as_str()))?;
+
+    let content = svg_output.finish(&content);
+    if review != ReplaceReview::None {
+        let existing = fs::read_to_string(&file_path)?;
+        if existing == content {
+            println!(
+                "{} already matches {icon}; nothing to replace.",
+                file_path.display()
+            );
+            return Ok(());
+        }
+        let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout())
+            && std::env::var_os("NO_COLOR").is_none();
+        let label = file_path.display().to_string();
+        print!(
+            "{}",
+            diff::render_unified(&existing, &content, &label, &label, use_color)
+        );
+        match review {
+            ReplaceReview::DryRun => return Ok(()),
+            ReplaceReview::Confirm => {
+                if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+                    anyhow::bail!(
+                        "Not an interactive terminal; pass --yes (-y) to replace without confirming."
+                    );
+                }
+                let confirmed = inquire::Confirm::new(&format!("Overwrite {label}?"))
+                    .with_default(false)
+                    .prompt()?;
+                if !confirmed {
+                    println!("Left {label} unchanged.");
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fs::write(&file_path, content)?;
+    pin.record(&folder, name, Some(icon.as_str()))?;
+    println!("Replaced {name} with {icon}: {}", file_path.display());
+
+    if folder.join(crate::css_mask::CSS_MASK_STYLESHEET).exists() {
+        crate::css_mask::write_mask_stylesheet(&folder)?;
+    }
+    if folder.join(crate::datauri::DATAURI_BUNDLE).exists() {
+        crate::datauri::write_datauri_bundle(&folder)?;
+    }
+    css_background::refresh_background_stylesheet_if_present(&folder)?;
+    gallery::refresh_gallery_if_present(
+        &folder,
+        preset.to_str(),
+        resolved.flutter_barrel_file.as_deref(),
+        resolved.flutter_barrel_class.as_deref(),
+    )?;
+    if !raster::find_raster_siblings(&file_path).is_empty() {
+        let spinner = progress::Spinner::start("Rendering rasters");
+        let rasters = raster::rerender_raster_siblings(&file_path)?;
+        spinner.finish();
+        for path in rasters {
+            println!("Rendered raster: {}", path.display());
+        }
+    }
+    Ok(())
 }
 
 async fn run_delete_prompt_mode(
@@ -1205,20 +2995,20 @@ async fn run_delete_prompt_mode(
         );
     }
 
-    // Step 2: Check if folder is valid and has index.ts
-    let index_ts_path = folder.join("index.ts");
+    // Step 2: Check if folder is valid and has index.ts (or another barrel)
+    let (writer, index_ts_path) = index_writer::find_barrel(&folder);
     if !index_ts_path.exists() {
         anyhow::bail!(
             "No index.ts found in the specified folder. Are you sure this is an icons folder?"
         );
     }
 
-    // Step 3: Read and parse index.ts
+    // Step 3: Read and parse the barrel
     let contents = fs::read_to_string(&index_ts_path)?;
-    let icons = collect_icons_from_index_contents(&contents);
+    let icons = writer.parse(&contents);
 
     if icons.is_empty() {
-        println!("No icons found in index.ts");
+        println!("No icons found in {}", writer.file_name());
         return Ok(());
     }
 
@@ -1246,7 +3036,13 @@ async fn run_delete_prompt_mode(
         return Ok(());
     }
 
-    apply_deletions(&folder, &index_ts_path, &selected_icons)
+    apply_deletions(
+        &folder,
+        &index_ts_path,
+        &selected_icons,
+        &resolved.hooks,
+        resolved.history.as_ref(),
+    )
 }
 
 fn run_sync_command(
@@ -1286,6 +3082,10 @@ fn run_sync_command(
         flutter_barrel_file,
         flutter_barrel_class: resolved.flutter_barrel_class.as_deref(),
         renames: &rename_map,
+        alias_style: &resolved.alias_style,
+        code_style: &resolved.code_style,
+        import_query: resolved.import_query,
+        lazy: lazy_export::LazyLoader::configured(resolved.lazy_exports, &resolved.preset),
     };
 
     let plan = sync::compute_sync_plan(&ctx)?;
@@ -1307,7 +3107,17 @@ fn run_sync_command(
         );
     }
 
-    let summary = sync::apply_sync_plan(&plan, &ctx, sync::ApplyOptions { prune })?;
+    let summary = sync::apply_sync_plan(
+        &plan,
+        &ctx,
+        sync::ApplyOptions {
+            prune,
+            line_ending: resolved.svg_output.line_ending,
+            barrel_frame: resolved.barrel_frame.clone(),
+            icon_names_dts: resolved.icon_names_dts,
+            icon_registry: resolved.icon_registry,
+        },
+    )?;
     println!(
         "\nApplied: +{} added, -{} removed.",
         summary.added, summary.removed
@@ -1322,104 +3132,771 @@ fn run_sync_command(
     Ok(())
 }
 
+fn run_dedupe_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    apply: bool,
+    keep: &[String],
+) -> anyhow::Result<()> {
+    let folder_override = command_folder.or(cli.folder.as_ref());
+    let resolved = config::resolve_tui_config(folder_override, cli.preset.as_ref())?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`dedupe` works on index.ts barrels; the flutter preset has none.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    let (writer, index_path) = index_writer::find_barrel(&folder);
+    if !index_path.exists() {
+        anyhow::bail!(
+            "No {} found in {}. Are you sure this is an icons folder?",
+            writer.file_name(),
+            folder.display()
+        );
+    }
+
+    let plan = dedupe::compute_dedupe_plan(&folder, keep)?;
+    let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout())
+        && std::env::var_os("NO_COLOR").is_none();
+    if apply && !plan.is_clean() {
+        dedupe::apply_dedupe_plan(&folder, &plan)?;
+        println!(
+            "Merged {} duplicate group(s): repointed {} export(s), deleted {} file(s).",
+            plan.groups.len(),
+            plan.rewrites.len(),
+            plan.redundant_files().count()
+        );
+        return Ok(());
+    }
+    print!("{}", dedupe::render_plan_text(&plan, use_color));
+    Ok(())
+}
+
+fn run_normalize_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    case: Option<FilenameCase>,
+    rewrite_imports: bool,
+    apply: bool,
+) -> anyhow::Result<()> {
+    let folder_override = command_folder.or(cli.folder.as_ref());
+    let resolved = config::resolve_tui_config(folder_override, cli.preset.as_ref())?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`normalize` works on index.ts barrels; the flutter preset has none.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    if !folder.join("index.ts").exists() {
+        anyhow::bail!(
+            "No index.ts found in {}. Are you sure this is an icons folder?",
+            folder.display()
+        );
+    }
+
+    let import_root = if rewrite_imports {
+        let cwd = std::env::current_dir()?;
+        let root = cwd
+            .ancestors()
+            .find(|dir| dir.join("package.json").is_file())
+            .unwrap_or(&cwd)
+            .to_path_buf();
+        Some(root)
+    } else {
+        None
+    };
+    let case = case.or(resolved.filename_case).unwrap_or_default();
+    let plan = normalize::compute_normalize_plan(&folder, case, import_root.as_deref())?;
+    let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout())
+        && std::env::var_os("NO_COLOR").is_none();
+    if !apply || plan.renames.is_empty() {
+        print!("{}", normalize::render_plan_text(&plan, use_color));
+        return Ok(());
+    }
+
+    normalize::apply_normalize_plan(&folder, &plan)?;
+    if let Some(history) = &resolved.history {
+        let entries = crate::utils::get_existing_icons(&resolved.folder)?;
+        for rename in &plan.renames {
+            let to = folder.join(&rename.to);
+            let name = entries
+                .iter()
+                .find(|entry| {
+                    crate::utils::resolve_existing_icon_path(&folder.join(&entry.file_path)) == to
+                })
+                .map(|entry| entry.name.as_str())
+                .unwrap_or_default();
+            history.record(history::Entry::rename(
+                name,
+                &folder.join(&rename.from),
+                &to,
+            ));
+        }
+    }
+    println!(
+        "Renamed {} file(s) to {}-case, rewrote {} import(s).",
+        plan.renames.len(),
+        case.to_str(),
+        plan.imports.len()
+    );
+    if !plan.skipped.is_empty() {
+        println!(
+            "Note: {} file(s) skipped because their new name is taken. Re-run without --apply to see them.",
+            plan.skipped.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_adopt_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    apply: bool,
+) -> anyhow::Result<()> {
+    let folder_override = command_folder.or(cli.folder.as_ref());
+    let resolved = config::resolve_tui_config(folder_override, cli.preset.as_ref())?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`adopt` writes an index.ts barrel; the flutter preset has none.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    // Without a local config the preset comes from the files themselves.
+    let preset_known = resolved.project_config_loaded || cli.preset.is_some();
+    let ctx = adopt::AdoptContext {
+        folder: &folder,
+        alias_style: &resolved.alias_style,
+        code_style: &resolved.code_style,
+        import_query: resolved.import_query,
+        lazy: lazy_export::LazyLoader::configured(resolved.lazy_exports, &resolved.preset),
+    };
+    let plan = adopt::compute_adopt_plan(&ctx)?;
+    let preset = if preset_known {
+        resolved.preset.clone()
+    } else {
+        plan.inferred_preset.to_string()
+    };
+
+    let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout())
+        && std::env::var_os("NO_COLOR").is_none();
+    print!("{}", adopt::render_plan_text(&plan, use_color));
+    if !apply || plan.adoptions.is_empty() {
+        if !resolved.project_config_loaded && !plan.adoptions.is_empty() {
+            println!("Would also write iconmate.config.jsonc (preset: {preset}).");
+        }
+        return Ok(());
+    }
+
+    adopt::apply_adopt_plan(
+        &plan,
+        &ctx,
+        adopt::AdoptOptions {
+            preset: preset.clone(),
+            barrel_frame: resolved.barrel_frame.clone(),
+            line_ending: resolved.svg_output.line_ending,
+            icon_names_dts: resolved.icon_names_dts,
+            icon_registry: resolved.icon_registry,
+        },
+    )?;
+    println!(
+        "\nWrote {} with {} export(s).",
+        plan.index_location.display(),
+        plan.adoptions.len()
+    );
+    if !resolved.project_config_loaded {
+        let cwd = std::env::current_dir()?;
+        let path =
+            config::write_local_config(&cwd, &resolved.folder, &preset, resolved.import_query)?;
+        println!("Wrote {}", path.display());
+    }
+    if !plan.unresolved.is_empty() {
+        println!(
+            "Note: {} file(s) not indexed. Re-run without --apply to see why.",
+            plan.unresolved.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_log_command(name: Option<&str>, limit: Option<usize>, json: bool) -> anyhow::Result<()> {
+    let path = Path::new(history::LOG_FILE);
+    if !path.exists() {
+        println!(
+            "No history yet. {} is written on the next add, delete or rename.",
+            history::LOG_FILE
+        );
+        return Ok(());
+    }
+    let (mut entries, skipped) = history::read_log(path)?;
+    if skipped > 0 {
+        eprintln!(
+            "Warning: skipped {skipped} unreadable line(s) in {}.",
+            history::LOG_FILE
+        );
+    }
+    if let Some(name) = name {
+        entries.retain(|entry| entry.name == name);
+    }
+    if let Some(limit) = limit {
+        entries.drain(..entries.len().saturating_sub(limit));
+    }
+    if json {
+        for entry in entries.iter().rev() {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+    } else {
+        print!("{}", history::render_log(&entries));
+    }
+    Ok(())
+}
+
+fn run_changelog_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    since: &str,
+) -> anyhow::Result<()> {
+    let folder_override = command_folder.or(cli.folder.as_ref());
+    let resolved = config::resolve_tui_config(folder_override, cli.preset.as_ref())?;
+    let folder = PathBuf::from(&resolved.folder);
+    let cwd = std::env::current_dir()?;
+    let icons = changelog::GitSnapshot {
+        dir: if folder.is_dir() { &folder } else { &cwd },
+        rev: since,
+    };
+    icons.verify()?;
+
+    let (before, after) = if resolved.preset == "flutter" {
+        let barrel_path = resolved
+            .flutter_barrel_file
+            .clone()
+            .unwrap_or_else(|| crate::flutter::DEFAULT_FLUTTER_BARREL_FILE.to_string());
+        let barrel = changelog::GitSnapshot {
+            dir: &cwd,
+            rev: since,
+        };
+        let parse = |contents: &str| {
+            crate::flutter::barrel_entries_to_icon_entries(
+                &crate::flutter::parse_dart_barrel(contents),
+                &resolved.folder,
+            )
+        };
+        let before = barrel
+            .show(&barrel_path)?
+            .map(|bytes| parse(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default();
+        let after = std::fs::read_to_string(&barrel_path)
+            .map(|contents| parse(&contents))
+            .unwrap_or_default();
+        (before, after)
+    } else {
+        if !folder.join("index.ts").exists() {
+            anyhow::bail!(
+                "No index.ts found in {}. Are you sure this is an icons folder?",
+                folder.display()
+            );
+        }
+        let before = icons
+            .show("index.ts")?
+            .map(|bytes| crate::utils::parse_index_entries(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default();
+        (before, crate::utils::get_existing_icons(&resolved.folder)?)
+    };
+
+    let log_path = Path::new(history::LOG_FILE);
+    let log = if log_path.exists() {
+        Some((history::read_log(log_path)?.0, icons.commit_time()?))
+    } else {
+        None
+    };
+    let changes = changelog::compute_changelog(
+        &before,
+        &after,
+        &folder,
+        &icons,
+        log.as_ref()
+            .map(|(entries, since)| (entries.as_slice(), since.as_str())),
+    )?;
+    print!("{}", changelog::render_markdown(&changes, since));
+    Ok(())
+}
+
+fn run_package_command(
+    cli: &CliArgs,
+    command_folder: Option<&PathBuf>,
+    out: &Path,
+    name: Option<&str>,
+) -> anyhow::Result<()> {
+    let folder_override = command_folder.or(cli.folder.as_ref());
+    let resolved = config::resolve_tui_config(folder_override, cli.preset.as_ref())?;
+    if resolved.preset == "flutter" {
+        anyhow::bail!("`package` builds npm packages; the Flutter preset isn't supported.");
+    }
+    let folder = PathBuf::from(&resolved.folder);
+    if !folder.join("index.ts").exists() {
+        anyhow::bail!(
+            "No index.ts found in {}. Are you sure this is an icons folder?",
+            folder.display()
+        );
+    }
+
+    let mut meta =
+        package::PackageMeta::read(Path::new("package.json"))?.unwrap_or(package::PackageMeta {
+            name: String::new(),
+            version: "0.0.0".to_string(),
+            description: None,
+            license: None,
+        });
+    if let Some(name) = name {
+        meta.name = name.to_string();
+    }
+    if meta.name.is_empty() {
+        anyhow::bail!(
+            "No package name: pass --name or run from a folder whose package.json has one."
+        );
+    }
+
+    let entries = crate::utils::get_existing_icons(&resolved.folder)?;
+    let built = package::build_package(&folder, &entries, &resolved.preset, &meta)?;
+    package::write_package(out, &built)?;
+    for skipped in &built.skipped {
+        eprintln!("Skipped {}: {}", skipped.name, skipped.reason);
+    }
+    println!(
+        "Packaged {} export{} as {}@{} in {}",
+        built.exports,
+        if built.exports == 1 { "" } else { "s" },
+        meta.name,
+        meta.version,
+        out.display()
+    );
+    Ok(())
+}
+
+fn run_init_command(
+    folder: Option<&PathBuf>,
+    preset: Option<&Preset>,
+    auto: bool,
+) -> anyhow::Result<()> {
+    use inquire::{Select, ui::RenderConfig};
+
+    let cwd = std::env::current_dir()?;
+    let detection = detect::detect_project(&cwd);
+    match &detection {
+        Some(detection) => println!(
+            "Detected {} at {}: suggesting --preset {}{}.",
+            detection.summary(),
+            detection.root.display(),
+            detection.preset.to_str(),
+            detection
+                .import_query
+                .map(|query| format!(" with --import-query {}", query.to_str()))
+                .unwrap_or_default()
+        ),
+        None => println!("No known framework found in package.json or pubspec.yaml."),
+    }
+
+    let suggested = detection
+        .as_ref()
+        .map(|detection| detection.preset.clone())
+        .unwrap_or(Preset::Normal);
+    let preset = match preset {
+        Some(preset) => preset.clone(),
+        None if auto => suggested,
+        None if !should_start_tui(false) => anyhow::bail!(
+            "Not an interactive terminal. Pass --auto to take the suggested preset, or --preset."
+        ),
+        None => {
+            let render_config =
+                RenderConfig::default().with_prompt_prefix(inquire::ui::Styled::new("●"));
+            let starting_cursor = PRESETS_OPTIONS
+                .iter()
+                .position(|option| option.preset == suggested)
+                .unwrap_or(0);
+            Select::new("✦ Preset", PRESETS_OPTIONS.to_vec())
+                .with_render_config(render_config)
+                .with_starting_cursor(starting_cursor)
+                .prompt()?
+                .preset
+        }
+    };
+    let import_query = detection
+        .filter(|detection| detection.preset == preset)
+        .and_then(|detection| detection.import_query);
+    let folder = folder
+        .map(|folder| folder.display().to_string())
+        .unwrap_or_else(|| config::default_folder_for_preset(preset.to_str()).to_string());
+
+    let path = config::write_local_config(&cwd, &folder, preset.to_str(), import_query)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+async fn run_appicon_command(icon: &str, out: &Path, background: &str) -> anyhow::Result<()> {
+    let svg = _icon_source_to_svg(
+        &Some(icon.to_string()),
+        None,
+        true,
+        &mut checksums::Pin::default(),
+    )
+    .await?;
+    let spinner = progress::Spinner::start("Rendering app icons");
+    let written = crate::appicon::generate_app_icons(&svg, out, background)?;
+    spinner.finish();
+    for path in &written {
+        println!("Wrote: {}", path.display());
+    }
+    println!(
+        "\nAdd to your <head>:\n{}\n",
+        crate::appicon::head_snippet()
+    );
+    println!(
+        "Add to manifest.webmanifest:\n{}",
+        crate::appicon::manifest_snippet()
+    );
+    Ok(())
+}
+
+/// The TUI needs a real terminal on both ends; anything else (CI logs, pipes,
+/// `iconmate | less`) gets garbled escape codes.
+fn should_start_tui(no_tui: bool) -> bool {
+    use std::io::IsTerminal;
+
+    !no_tui && std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = CliArgs::parse();
+    progress::set_quiet(args.quiet);
+    http::set_verbose(args.verbose);
+    match config::request_headers() {
+        Ok(rules) => http::set_request_headers(rules),
+        Err(error) => eprintln!("Warning: {error:#}"),
+    }
+    if let Some(dir) = &args.mock_api {
+        let dir = std::fs::canonicalize(dir).map_err(|_| {
+            anyhow::anyhow!("Mock API fixtures folder {} not found.", dir.display())
+        })?;
+        iconify_mock::set_mock_api_dir(dir);
+    }
 
     match args.command {
         Some(Commands::Add {
             folder,
+            group,
             icon,
             name,
             filename,
             preset,
             flutter_barrel_file,
             flutter_barrel_class,
+            raster,
+            sizes,
+            expect_sha256,
+            alias_case,
+            alias_prefix,
+            alias_suffix,
+            alias_collection_prefix,
+            no_sanitize,
+            svg_format,
+            svg_indent,
+            line_endings,
+            emoji_set,
+            import_query,
+            svgr_compat,
+            lazy,
+            force,
+            on_conflict,
         }) => {
-            let resolved = config::resolve_tui_config(Some(&folder), preset.as_ref())?;
-            let config = AppConfig {
-                folder,
-                icon,
-                name,
-                filename,
-                preset: Some(Preset::from_str(&resolved.preset).ok_or_else(|| {
+            let group = group.as_deref().map(groups::validate_group).transpose()?;
+            if let Some(filename) = &filename {
+                utils::validate_icon_filename(filename)?;
+            }
+            let mut icon = icon;
+            let mut configs = Vec::with_capacity(folder.len());
+            for (index, (folder, target_preset)) in folder
+                .iter()
+                .map(|folder| parse_add_target(folder))
+                .enumerate()
+            {
+                let resolved = config::resolve_tui_config(
+                    Some(&folder),
+                    target_preset.as_ref().or(preset.as_ref()),
+                )?;
+                if index == 0 {
+                    icon = resolve_emoji_icon(
+                        icon,
+                        emoji_set.as_deref().unwrap_or(&resolved.emoji_set),
+                    )
+                    .await?;
+                    icon = resolve_renamed_icon(icon).await;
+                }
+                let alias_style = AliasStyle {
+                    case: alias_case.unwrap_or(resolved.alias_style.case),
+                    prefix: alias_prefix.clone().unwrap_or(resolved.alias_style.prefix),
+                    suffix: alias_suffix.clone().unwrap_or(resolved.alias_style.suffix),
+                    collection_prefix: alias_collection_prefix
+                        || resolved.alias_style.collection_prefix,
+                };
+                let target_preset = Preset::from_str(&resolved.preset).ok_or_else(|| {
                     anyhow::anyhow!("Invalid resolved preset '{}'.", resolved.preset)
-                })?),
-                flutter_barrel_file: flutter_barrel_file
-                    .or_else(|| resolved.flutter_barrel_file.map(PathBuf::from)),
-                flutter_barrel_class: flutter_barrel_class.or(resolved.flutter_barrel_class),
-            };
-            run_app(config).await
+                })?;
+                if import_query.is_some() && target_preset.extension() != ".svg" {
+                    anyhow::bail!(
+                        "--import-query needs an .svg output, but --preset {} writes {} files.",
+                        target_preset.to_str(),
+                        target_preset.extension()
+                    );
+                }
+                if lazy && lazy_export::LazyLoader::for_preset(&target_preset).is_none() {
+                    anyhow::bail!(
+                        "--lazy needs --preset react, solid or vue, not {}.",
+                        target_preset.to_str()
+                    );
+                }
+                if svgr_compat && target_preset != Preset::React {
+                    anyhow::bail!(
+                        "--svgr-compat only applies to --preset react, not {}.",
+                        target_preset.to_str()
+                    );
+                }
+                configs.push(AppConfig {
+                    folder,
+                    group: group.clone(),
+                    icon: icon.clone(),
+                    name: name.clone(),
+                    filename: filename.clone(),
+                    preset: Some(target_preset),
+                    flutter_barrel_file: flutter_barrel_file
+                        .clone()
+                        .or_else(|| resolved.flutter_barrel_file.map(PathBuf::from)),
+                    flutter_barrel_class: flutter_barrel_class
+                        .clone()
+                        .or(resolved.flutter_barrel_class),
+                    raster,
+                    raster_sizes: sizes.clone(),
+                    expect_sha256: expect_sha256.clone(),
+                    alias_style,
+                    hooks: resolved.hooks,
+                    svg_output: svg_format::SvgOutput {
+                        sanitize: resolved.svg_output.sanitize && !no_sanitize,
+                        format: svg_format.or(resolved.svg_output.format),
+                        indent: svg_indent.unwrap_or(resolved.svg_output.indent),
+                        line_ending: line_endings.unwrap_or(resolved.svg_output.line_ending),
+                    },
+                    barrel_frame: resolved.barrel_frame,
+                    code_style: resolved.code_style,
+                    icon_names_dts: resolved.icon_names_dts,
+                    icon_registry: resolved.icon_registry,
+                    import_query: import_query.or(resolved.import_query),
+                    svgr_compat: svgr_compat || resolved.svgr_compat,
+                    lazy_exports: lazy || resolved.lazy_exports,
+                    filename_case: resolved.filename_case,
+                    size_budget: resolved.size_budget,
+                    force,
+                    on_conflict,
+                    history: resolved.history,
+                    index_strategy: resolved.index_strategy,
+                    html_class_hook: resolved.html_class_hook,
+                });
+            }
+            run_app_targets(configs).await
         }
         Some(Commands::Tui {}) => run_prompt_mode(&args).await,
+        Some(Commands::Init {
+            ref folder,
+            ref preset,
+            auto,
+        }) => run_init_command(folder.as_ref(), preset.as_ref(), auto),
         Some(Commands::Delete {
             ref folder,
             ref names,
             ref filenames,
+            ref prefixes,
             yes,
         }) => {
-            if !names.is_empty() || !filenames.is_empty() {
-                run_delete_non_interactive(&args, folder.as_ref(), names, filenames, yes)
+            if !names.is_empty() || !filenames.is_empty() || !prefixes.is_empty() {
+                run_delete_non_interactive(&args, folder.as_ref(), names, filenames, prefixes, yes)
             } else {
                 run_delete_prompt_mode(&args, folder.as_ref()).await
             }
         }
-        Some(Commands::List { ref folder }) => run_list_mode(&args, folder.as_ref()),
+        Some(Commands::Replace {
+            ref folder,
+            ref name,
+            ref icon,
+            ref expect_sha256,
+            no_sanitize,
+            diff,
+            dry_run,
+            yes,
+        }) => {
+            let review = if dry_run {
+                ReplaceReview::DryRun
+            } else if diff && !yes {
+                ReplaceReview::Confirm
+            } else if diff {
+                ReplaceReview::Show
+            } else {
+                ReplaceReview::None
+            };
+            run_replace_command(
+                &args,
+                folder.as_ref(),
+                name,
+                icon,
+                expect_sha256.as_deref(),
+                no_sanitize,
+                review,
+            )
+            .await
+        }
+        Some(Commands::List {
+            ref folder,
+            ref tag,
+        }) => run_list_mode(&args, folder.as_ref(), tag),
+        Some(Commands::Check {
+            ref folder,
+            workspace: false,
+        }) => run_check_command(&args, folder.as_ref()),
+        Some(Commands::Check {
+            workspace: true, ..
+        }) => run_workspace_check_command(),
+        Some(Commands::Gallery { ref folder }) => run_gallery_command(&args, folder.as_ref()),
+        Some(Commands::Css { ref folder }) => run_css_command(&args, folder.as_ref()),
+        Some(Commands::Open { ref folder }) => run_open_command(&args, folder.as_ref()),
+        Some(Commands::Preview {
+            ref target,
+            ref folder,
+            size,
+            sixel,
+        }) => run_preview_command(&args, folder.as_ref(), target, size, sixel),
+        Some(Commands::Serve {
+            ref folder,
+            port,
+            ref host,
+        }) => run_serve_command(&args, folder.as_ref(), host, port).await,
+        Some(Commands::Template { ref command }) => run_template_command(&args, command),
         Some(Commands::Iconify { command }) => run_iconify_command(command).await,
+        Some(Commands::Appicon {
+            ref icon,
+            ref out,
+            ref background,
+        }) => run_appicon_command(icon, out, background).await,
         Some(Commands::Sync {
             ref folder,
             apply,
             prune,
             ref renames,
         }) => run_sync_command(&args, folder.as_ref(), apply, prune, renames),
+        Some(Commands::Dedupe {
+            ref folder,
+            apply,
+            ref keep,
+        }) => run_dedupe_command(&args, folder.as_ref(), apply, keep),
+        Some(Commands::Normalize {
+            ref folder,
+            case,
+            rewrite_imports,
+            apply,
+        }) => run_normalize_command(&args, folder.as_ref(), case, rewrite_imports, apply),
+        Some(Commands::Adopt { ref folder, apply }) => {
+            run_adopt_command(&args, folder.as_ref(), apply)
+        }
+        Some(Commands::Log {
+            ref name,
+            limit,
+            json,
+        }) => run_log_command(name.as_deref(), limit, json),
+        Some(Commands::Changelog {
+            ref folder,
+            ref since,
+        }) => run_changelog_command(&args, folder.as_ref(), since),
+        Some(Commands::Package {
+            ref folder,
+            ref out,
+            ref name,
+        }) => run_package_command(&args, folder.as_ref(), out, name.as_deref()),
+        #[cfg(feature = "headless")]
+        Some(Commands::Headless {
+            ref script,
+            width,
+            height,
+        }) => run_headless_command(&args, script, width, height).await,
+        None if !should_start_tui(args.no_tui) => {
+            eprintln!(
+                "Not an interactive terminal; listing icons instead of starting the TUI. See `iconmate --help` for commands."
+            );
+            run_list_mode(&args, None, &[])
+        }
         None => {
-            let resolved = config::resolve_tui_config(args.folder.as_ref(), args.preset.as_ref())?;
-
-            for warning in &resolved.warnings {
-                eprintln!("Warning: {warning}");
-            }
-            for info in &resolved.info {
-                eprintln!("{info}");
-            }
-
-            let config = app_state::AppConfig {
-                folder: resolved.folder,
-                preset: resolved.preset,
-                svg_viewer_cmd: resolved.svg_viewer_cmd,
-                svg_viewer_cmd_source: resolved.svg_viewer_cmd_source,
-                global_config_loaded: resolved.global_config_loaded,
-                project_config_loaded: resolved.project_config_loaded,
-                flutter_barrel_file: resolved.flutter_barrel_file,
-                flutter_barrel_class: resolved.flutter_barrel_class,
-            };
+            let config = tui_app_config(&args)?;
             tui::run(config).await
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn remove_selected_exports_removes_each_selected_line() {
-        let contents = "export { default as IconOne } from './one.svg';\nexport { default as IconTwo } from './two.svg?react';\nexport { default as IconThree } from './three.svg';\n";
-
-        let selected_icons = vec![
-            crate::utils::parse_export_line_ts("export { default as IconOne } from './one.svg';")
-                .expect("line should parse"),
-            crate::utils::parse_export_line_ts(
-                "export { default as IconTwo } from './two.svg?react';",
-            )
-            .expect("line should parse"),
-        ];
+#[cfg(feature = "headless")]
+async fn run_headless_command(
+    args: &CliArgs,
+    script: &Path,
+    width: u16,
+    height: u16,
+) -> anyhow::Result<()> {
+    let script = if script == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        fs::read_to_string(script)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", script.display()))?
+    };
+    let mut driver = headless::Driver::new(tui_app_config(args)?, width, height)?;
+    headless::run_script(&mut driver, &script, &mut std::io::stdout()).await
+}
 
-        let updated = remove_selected_exports_from_index(contents, &selected_icons);
+/// Resolves the TUI's config (and theme) from the CLI args and config files.
+fn tui_app_config(args: &CliArgs) -> anyhow::Result<app_state::AppConfig> {
+    let resolved = config::resolve_tui_config(args.folder.as_ref(), args.preset.as_ref())?;
 
-        assert!(!updated.contains("IconOne"));
-        assert!(!updated.contains("IconTwo"));
-        assert!(updated.contains("IconThree"));
+    for warning in &resolved.warnings {
+        eprintln!("Warning: {warning}");
     }
+    for info in &resolved.info {
+        eprintln!("{info}");
+    }
+
+    views::theme::init(resolved.theme.as_deref(), &resolved.palette);
+    // Spinners and request logs would draw over the alternate screen;
+    // F12 shows the request log inside the TUI instead.
+    progress::set_quiet(true);
+    http::set_verbose(false);
+
+    Ok(app_state::AppConfig {
+        folder: resolved.folder,
+        preset: resolved.preset,
+        svg_viewer_cmd: resolved.svg_viewer_cmd,
+        svg_viewer_cmd_source: resolved.svg_viewer_cmd_source,
+        viewer_cmds: resolved.viewer_cmds,
+        global_config_loaded: resolved.global_config_loaded,
+        project_config_loaded: resolved.project_config_loaded,
+        flutter_barrel_file: resolved.flutter_barrel_file,
+        flutter_barrel_class: resolved.flutter_barrel_class,
+        alias_style: resolved.alias_style,
+        code_style: resolved.code_style,
+        import_query: resolved.import_query,
+        lazy_exports: resolved.lazy_exports,
+        filename_case: resolved.filename_case,
+        hooks: resolved.hooks,
+        behavior: resolved.behavior,
+        sync_options: sync::ApplyOptions {
+            prune: false,
+            line_ending: resolved.svg_output.line_ending,
+            barrel_frame: resolved.barrel_frame,
+            icon_names_dts: resolved.icon_names_dts,
+            icon_registry: resolved.icon_registry,
+        },
+        history: resolved.history,
+        usage_snippet: resolved.usage_snippet,
+        preview_colors: resolved.preview_colors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn collect_icons_reads_multiple_exports_on_same_line() {
@@ -1432,21 +3909,6 @@ mod tests {
         assert!(icons.iter().any(|icon| icon.name == "IconTwo"));
     }
 
-    #[test]
-    fn remove_selected_exports_removes_selected_from_concatenated_line() {
-        let contents = "export { default as IconOne } from './one.svg';export { default as IconTwo } from './two.svg';\n";
-
-        let selected_icons = vec![
-            crate::utils::parse_export_line_ts("export { default as IconTwo } from './two.svg';")
-                .expect("line should parse"),
-        ];
-
-        let updated = remove_selected_exports_from_index(contents, &selected_icons);
-
-        assert!(updated.contains("IconOne"));
-        assert!(!updated.contains("IconTwo"));
-    }
-
     #[test]
     fn resolve_delete_folder_prefers_subcommand_folder() {
         let cli_folder = PathBuf::from("src/assets/icons");
@@ -1460,6 +3922,10 @@ mod tests {
             filename: None,
             flutter_barrel_file: None,
             flutter_barrel_class: None,
+            no_tui: false,
+            quiet: false,
+            verbose: false,
+            mock_api: None,
         };
 
         let resolved = resolve_delete_folder(&cli, Some(&command_folder));
@@ -1478,6 +3944,10 @@ mod tests {
             filename: None,
             flutter_barrel_file: None,
             flutter_barrel_class: None,
+            no_tui: false,
+            quiet: false,
+            verbose: false,
+            mock_api: None,
         };
 
         let resolved = resolve_delete_folder(&cli, None);
@@ -1497,6 +3967,10 @@ mod tests {
             filename: None,
             flutter_barrel_file: None,
             flutter_barrel_class: None,
+            no_tui: false,
+            quiet: false,
+            verbose: false,
+            mock_api: None,
         };
 
         let resolved = resolve_list_folder(&cli, Some(&command_folder));
@@ -1515,12 +3989,36 @@ mod tests {
             filename: None,
             flutter_barrel_file: None,
             flutter_barrel_class: None,
+            no_tui: false,
+            quiet: false,
+            verbose: false,
+            mock_api: None,
         };
 
         let resolved = resolve_list_folder(&cli, None);
         assert_eq!(resolved, Some(&cli_folder));
     }
 
+    #[test]
+    fn dedupe_js_alias_falls_back_to_collection_prefix_when_taken() {
+        let style = AliasStyle::default();
+        let existing = "export { default as IconHeart } from './lucide_heart.svg';\n";
+
+        assert_eq!(
+            dedupe_js_alias(Some(existing), "Heart", Some("MdiHeart"), &style),
+            "MdiHeart"
+        );
+        assert_eq!(
+            dedupe_js_alias(Some(existing), "Star", Some("MdiStar"), &style),
+            "Star"
+        );
+        // Explicit --name: no prefixed candidate, keep the user's choice.
+        assert_eq!(
+            dedupe_js_alias(Some(existing), "Heart", None, &style),
+            "Heart"
+        );
+    }
+
     #[test]
     fn validate_new_export_conflicts_rejects_duplicate_alias() {
         let existing = "export { default as IconHeart } from './heart.svg';\n";
@@ -0,0 +1,486 @@
+// `iconmate normalize`: rename the files of an existing icons folder to one
+// filename convention.
+//
+// Adopting iconmate on a hand-grown folder usually means `HeartIcon.svg`,
+// `mdi:heart.svg` and `arrow_left.svg` side by side. Every file `index.ts`
+// exports is renamed to `filename_case` (kebab by default), with the export
+// lines repointed; export names don't change, so imports through the barrel
+// keep working. With `--rewrite-imports`, source files under the project root
+// that import an icon file directly are updated too: relative specifiers are
+// resolved, aliased ones (`@/assets/icons/HeartIcon.svg`) match on the
+// folder name and filename. Dry-run by default.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use regex::Regex;
+
+use crate::utils::{FilenameCase, icon_file_path, rename_icon_entry};
+
+/// Source files `--rewrite-imports` looks at.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts", "vue", "svelte", "astro", "mdx",
+];
+/// Directories `--rewrite-imports` and `check --workspace` never descend
+/// into, besides hidden ones.
+pub const SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build", "out", "target", "coverage"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    /// Folder-relative, `/`-separated.
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    pub rename: Rename,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRewrite {
+    pub file: PathBuf,
+    /// 1-based.
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NormalizePlan {
+    pub case: FilenameCase,
+    pub renames: Vec<Rename>,
+    pub skipped: Vec<Skipped>,
+    pub imports: Vec<ImportRewrite>,
+}
+
+impl NormalizePlan {
+    pub fn is_clean(&self) -> bool {
+        self.renames.is_empty() && self.skipped.is_empty()
+    }
+}
+
+/// Plans a rename for every file `index.ts` exports whose name doesn't follow
+/// `case`. With `import_root`, also plans the direct imports under it.
+pub fn compute_normalize_plan(
+    folder: &Path,
+    case: FilenameCase,
+    import_root: Option<&Path>,
+) -> anyhow::Result<NormalizePlan> {
+    let entries = crate::utils::get_existing_icons(&folder.to_string_lossy())?;
+
+    let mut seen = HashSet::new();
+    let mut taken = HashSet::new();
+    let mut renames = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        let path = icon_file_path(folder, &entry.file_path);
+        let Some(from) = path
+            .strip_prefix(folder)
+            .ok()
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        else {
+            continue;
+        };
+        if !path.is_file() || !seen.insert(from.clone()) {
+            continue;
+        }
+        let Some(to) = normalized_name(&from, case) else {
+            continue;
+        };
+        if to == from {
+            continue;
+        }
+        let rename = Rename { from, to };
+        let target = folder.join(&rename.to);
+        let reason = if taken.contains(&rename.to.to_lowercase()) {
+            Some(format!("another file is renamed to {}", rename.to))
+        } else if target.exists() && !same_file(&path, &target) {
+            Some(format!("{} already exists", rename.to))
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => skipped.push(Skipped { rename, reason }),
+            None => {
+                taken.insert(rename.to.to_lowercase());
+                renames.push(rename);
+            }
+        }
+    }
+
+    let imports = match import_root {
+        Some(root) => plan_import_rewrites(root, folder, &renames)?,
+        None => Vec::new(),
+    };
+    Ok(NormalizePlan {
+        case,
+        renames,
+        skipped,
+        imports,
+    })
+}
+
+/// `social/HeartIcon.svg` -> `social/heart-icon.svg`. Only the filename
+/// changes; `None` when the stem has nothing to keep.
+fn normalized_name(relative: &str, case: FilenameCase) -> Option<String> {
+    let (dir, file) = match relative.rsplit_once('/') {
+        Some((dir, file)) => (format!("{dir}/"), file),
+        None => (String::new(), relative),
+    };
+    let (stem, extension) = match file.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (file, String::new()),
+    };
+    let stem = case.apply(stem);
+    (!stem.is_empty()).then(|| format!("{dir}{stem}{extension}"))
+}
+
+/// True for `Heart.svg` and `heart.svg` on a case-insensitive filesystem.
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn plan_import_rewrites(
+    root: &Path,
+    folder: &Path,
+    renames: &[Rename],
+) -> anyhow::Result<Vec<ImportRewrite>> {
+    if renames.is_empty() {
+        return Ok(Vec::new());
+    }
+    let folder = lexical_absolute(folder)?;
+    let folder_name = folder
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let by_path: HashMap<PathBuf, &Rename> = renames
+        .iter()
+        .map(|rename| (folder.join(&rename.from), rename))
+        .collect();
+    let specifier_re = Regex::new(
+        r#"(?:\bfrom\s*|\bimport\s*\(\s*|\bimport\s+|\brequire\s*\(\s*)(['"])([^'"\n]+)['"]"#,
+    )
+    .expect("valid import specifier regex");
+
+    let mut files = Vec::new();
+    collect_source_files(&lexical_absolute(root)?, &folder, &mut files)?;
+    files.sort();
+
+    let mut rewrites = Vec::new();
+    for file in files {
+        let Ok(contents) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let dir = file.parent().unwrap_or(Path::new(""));
+        for (index, line) in contents.lines().enumerate() {
+            for captures in specifier_re.captures_iter(line) {
+                let specifier = &captures[2];
+                let (base, suffix) = split_suffix(specifier);
+                let rename = if base.starts_with("./") || base.starts_with("../") {
+                    find_rename(&by_path, &normalize_lexically(&dir.join(base)))
+                } else {
+                    let ends_with = |old: &str| base == old || base.ends_with(&format!("/{old}"));
+                    renames.iter().find(|rename| {
+                        let old = format!("{folder_name}/{}", rename.from);
+                        ends_with(&old)
+                            || has_ts_extension(&rename.from) && ends_with(strip_ts_extension(&old))
+                    })
+                };
+                let Some(rename) = rename else {
+                    continue;
+                };
+                let after = replace_filename(base, &rename.from, &rename.to) + suffix;
+                rewrites.push(ImportRewrite {
+                    file: file.clone(),
+                    line: index + 1,
+                    before: specifier.to_string(),
+                    after,
+                });
+            }
+        }
+    }
+    Ok(rewrites)
+}
+
+fn collect_source_files(dir: &Path, icons: &Path, out: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    if dir == icons {
+        return Ok(());
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect_source_files(&entry.path(), icons, out)?;
+            }
+        } else if file_type.is_file()
+            && Path::new(&name)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension))
+        {
+            out.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// An import may leave out `.tsx` / `.ts`.
+fn find_rename<'a>(by_path: &HashMap<PathBuf, &'a Rename>, target: &Path) -> Option<&'a Rename> {
+    by_path.get(target).copied().or_else(|| {
+        ["tsx", "ts"].iter().find_map(|extension| {
+            let mut with_extension = target.as_os_str().to_owned();
+            with_extension.push(format!(".{extension}"));
+            by_path.get(Path::new(&with_extension)).copied()
+        })
+    })
+}
+
+/// Swaps the old filename at the end of `base` for the new one, keeping a
+/// left-out `.tsx` left out.
+fn replace_filename(base: &str, from: &str, to: &str) -> String {
+    let (from, to) = if base.ends_with(from) {
+        (from, to)
+    } else {
+        (strip_ts_extension(from), strip_ts_extension(to))
+    };
+    let from_file = from.rsplit('/').next().unwrap_or(from);
+    let to_file = to.rsplit('/').next().unwrap_or(to);
+    format!("{}{to_file}", &base[..base.len() - from_file.len()])
+}
+
+fn split_suffix(specifier: &str) -> (&str, &str) {
+    match specifier.find(['?', '#']) {
+        Some(index) => specifier.split_at(index),
+        None => (specifier, ""),
+    }
+}
+
+fn strip_ts_extension(value: &str) -> &str {
+    value
+        .strip_suffix(".tsx")
+        .or_else(|| value.strip_suffix(".ts"))
+        .unwrap_or(value)
+}
+
+fn has_ts_extension(value: &str) -> bool {
+    value.ends_with(".tsx") || value.ends_with(".ts")
+}
+
+fn lexical_absolute(path: &Path) -> anyhow::Result<PathBuf> {
+    Ok(normalize_lexically(&std::path::absolute(path)?))
+}
+
+/// Resolves `.` and `..` without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Renames the files (and their index.ts exports), then rewrites the planned
+/// imports. Case-only renames go through a temporary name so they also work
+/// on case-insensitive filesystems.
+pub fn apply_normalize_plan(folder: &Path, plan: &NormalizePlan) -> anyhow::Result<()> {
+    let folder_str = folder.to_string_lossy();
+    for rename in &plan.renames {
+        if rename.from.eq_ignore_ascii_case(&rename.to) {
+            let temporary = match rename.to.rsplit_once('/') {
+                Some((dir, file)) => format!("{dir}/normalize-tmp-{file}"),
+                None => format!("normalize-tmp-{}", rename.to),
+            };
+            rename_icon_entry(&folder_str, &rename.from, &temporary)?;
+            rename_icon_entry(&folder_str, &temporary, &rename.to)?;
+        } else {
+            rename_icon_entry(&folder_str, &rename.from, &rename.to)?;
+        }
+    }
+
+    let mut by_file: HashMap<&Path, Vec<&ImportRewrite>> = HashMap::new();
+    for rewrite in &plan.imports {
+        by_file.entry(&rewrite.file).or_default().push(rewrite);
+    }
+    for (file, rewrites) in by_file {
+        let contents = fs::read_to_string(file)?;
+        let mut updated = String::with_capacity(contents.len());
+        for (index, line) in contents.split_inclusive('\n').enumerate() {
+            let mut line = line.to_string();
+            for rewrite in rewrites.iter().filter(|rewrite| rewrite.line == index + 1) {
+                for quote in ['\'', '"'] {
+                    line = line.replace(
+                        &format!("{quote}{}{quote}", rewrite.before),
+                        &format!("{quote}{}{quote}", rewrite.after),
+                    );
+                }
+            }
+            updated.push_str(&line);
+        }
+        fs::write(file, updated)?;
+    }
+
+    crate::gallery::refresh_gallery_if_present(folder, "normal", None, None)?;
+    crate::utils::refresh_derived_files(folder)?;
+    Ok(())
+}
+
+pub fn render_plan_text(plan: &NormalizePlan, use_color: bool) -> String {
+    let green = if use_color { "\x1b[32m" } else { "" };
+    let yellow = if use_color { "\x1b[33m" } else { "" };
+    let bold = if use_color { "\x1b[1m" } else { "" };
+    let reset = if use_color { "\x1b[0m" } else { "" };
+
+    let mut out = String::new();
+    if plan.is_clean() {
+        out.push_str(&format!(
+            "{green}{bold}Every icon file is already {}-case.{reset}\n",
+            plan.case.to_str()
+        ));
+        return out;
+    }
+
+    if !plan.renames.is_empty() {
+        out.push_str(&format!(
+            "{green}{bold}Would rename ({}):{reset}\n",
+            plan.renames.len()
+        ));
+        for rename in &plan.renames {
+            out.push_str(&format!(
+                "  {green}{} → {}{reset}\n",
+                rename.from, rename.to
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !plan.imports.is_empty() {
+        out.push_str(&format!(
+            "{green}{bold}Would rewrite imports ({}):{reset}\n",
+            plan.imports.len()
+        ));
+        for rewrite in &plan.imports {
+            out.push_str(&format!(
+                "  {}:{}  {} → {}\n",
+                rewrite.file.display(),
+                rewrite.line,
+                rewrite.before,
+                rewrite.after
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !plan.skipped.is_empty() {
+        out.push_str(&format!(
+            "{yellow}{bold}Skipped ({}):{reset}\n",
+            plan.skipped.len()
+        ));
+        for skipped in &plan.skipped {
+            out.push_str(&format!(
+                "  {yellow}! {} → {}{reset} ({})\n",
+                skipped.rename.from, skipped.rename.to, skipped.reason
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !plan.renames.is_empty() {
+        out.push_str("Run with --apply to rename the files and update index.ts.\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_only_the_filename() {
+        assert_eq!(
+            normalized_name("mdi:heart.svg", FilenameCase::Kebab).as_deref(),
+            Some("mdi-heart.svg")
+        );
+        assert_eq!(
+            normalized_name("social/HeartIcon.tsx", FilenameCase::Snake).as_deref(),
+            Some("social/heart_icon.tsx")
+        );
+        assert_eq!(normalized_name("___.svg", FilenameCase::Kebab), None);
+    }
+
+    #[test]
+    fn plans_renames_collisions_and_direct_imports() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let icons = dir.path().join("src/assets/icons");
+        fs::create_dir_all(&icons).unwrap();
+        for name in [
+            "HeartIcon.svg",
+            "Star.tsx",
+            "star_filled.tsx",
+            "star-filled.tsx",
+        ] {
+            fs::write(icons.join(name), "<svg/>").unwrap();
+        }
+        fs::write(
+            icons.join("index.ts"),
+            "export { default as IconHeart } from './HeartIcon.svg?react';\nexport { default as IconStar } from './Star';\nexport { default as IconStarFilled } from './star_filled';\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("src/App.tsx"),
+            "import Heart from './assets/icons/HeartIcon.svg?url';\nimport Star from '@/assets/icons/Star';\n",
+        )
+        .unwrap();
+
+        let plan = compute_normalize_plan(&icons, FilenameCase::Kebab, Some(dir.path())).unwrap();
+        assert_eq!(
+            plan.renames,
+            vec![
+                Rename {
+                    from: "HeartIcon.svg".to_string(),
+                    to: "heart-icon.svg".to_string()
+                },
+                Rename {
+                    from: "Star.tsx".to_string(),
+                    to: "star.tsx".to_string()
+                },
+            ]
+        );
+        assert_eq!(plan.skipped[0].reason, "star-filled.tsx already exists");
+        assert_eq!(
+            plan.imports
+                .iter()
+                .map(|rewrite| rewrite.after.as_str())
+                .collect::<Vec<_>>(),
+            vec!["./assets/icons/heart-icon.svg?url", "@/assets/icons/star"]
+        );
+
+        apply_normalize_plan(&icons, &plan).unwrap();
+        assert!(icons.join("heart-icon.svg").exists());
+        assert!(icons.join("star.tsx").exists());
+        assert_eq!(
+            fs::read_to_string(icons.join("index.ts")).unwrap(),
+            "export { default as IconHeart } from './heart-icon.svg?react';\nexport { default as IconStar } from './star';\nexport { default as IconStarFilled } from './star_filled';\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("src/App.tsx")).unwrap(),
+            "import Heart from './assets/icons/heart-icon.svg?url';\nimport Star from '@/assets/icons/star';\n"
+        );
+    }
+}
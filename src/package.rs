@@ -0,0 +1,501 @@
+// `iconmate package --out dist/`: a publishable npm package of the icons.
+//
+// Design-system maintainers want to ship `@acme/icons` straight from the
+// icons folder, without a bundler config of their own. The barrel is the
+// manifest: every file `index.ts` exports becomes one ES module under
+// `icons/`, with a `.d.ts` next to it, and `index.js` / `index.d.ts`
+// re-export them under the same names. Component presets are compiled to
+// plain JS around their static markup (`createElement` for React,
+// `solid-js/h` for Solid, `h` for Vue); Svelte components ship as source
+// under the `svelte` export condition, which is how Svelte libraries are
+// published. Every icon is also written as a standalone SVG under `svg/`.
+// The `package.json` `exports` map covers `.`, `./<file>` and `./svg/<file>`.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::utils::{IconEntry, folder_relative_path, icon_file_path};
+
+const GENERATED_HEADER: &str = "// GENERATED by iconmate — do not edit by hand.";
+
+/// Metadata copied into the generated `package.json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageMeta {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub license: Option<String>,
+}
+
+impl PackageMeta {
+    /// `name`, `version`, `description` and `license` of a `package.json`.
+    pub fn read(path: &Path) -> anyhow::Result<Option<PackageMeta>> {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Ok(None);
+        };
+        let json: serde_json::Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let field = |key: &str| json.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        Ok(Some(PackageMeta {
+            name: field("name").unwrap_or_default(),
+            version: field("version").unwrap_or_else(|| "0.0.0".to_string()),
+            description: field("description"),
+            license: field("license"),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    /// `export default "<svg …>"`.
+    Markup,
+    /// `export default "data:image/svg+xml,…"`.
+    DataUri,
+    React,
+    Solid,
+    Vue,
+    Svelte,
+}
+
+impl Flavor {
+    fn for_file(path: &Path, preset: &str) -> Option<Flavor> {
+        match path.extension()?.to_str()? {
+            "svg" => Some(Flavor::Markup),
+            "ts" | "js" => Some(Flavor::DataUri),
+            "tsx" | "jsx" if preset == "solid" => Some(Flavor::Solid),
+            "tsx" | "jsx" => Some(Flavor::React),
+            "vue" => Some(Flavor::Vue),
+            "svelte" => Some(Flavor::Svelte),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageFile {
+    /// Relative to the output folder, `/`-separated.
+    pub path: String,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Package {
+    pub files: Vec<PackageFile>,
+    pub exports: usize,
+    pub skipped: Vec<Skipped>,
+}
+
+/// Builds the package for the barrel `entries` of `folder`.
+pub fn build_package(
+    folder: &Path,
+    entries: &[IconEntry],
+    preset: &str,
+    meta: &PackageMeta,
+) -> anyhow::Result<Package> {
+    let mut package = Package::default();
+    // Module path (`icons/heart.js`) of each file already written.
+    let mut modules: HashMap<String, String> = HashMap::new();
+    let mut index = Vec::new();
+
+    for entry in entries {
+        let path = icon_file_path(folder, &entry.file_path);
+        let skip = |reason: &str| Skipped {
+            name: entry.name.clone(),
+            reason: reason.to_string(),
+        };
+        // Module paths mirror the folder, so a file outside it has no place
+        // in the package.
+        let Some(relative) = folder_relative_path(folder, &path) else {
+            package.skipped.push(skip("outside the icons folder"));
+            continue;
+        };
+
+        if let Some(module) = modules.get(&relative) {
+            index.push((entry.name.clone(), module.clone()));
+            continue;
+        }
+        let Some(flavor) = Flavor::for_file(&path, preset) else {
+            package.skipped.push(skip("not an icon file"));
+            continue;
+        };
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            package.skipped.push(skip("file not found"));
+            continue;
+        };
+        let svg = match standalone_svg(&contents, flavor) {
+            Ok(svg) => svg,
+            Err(reason) => {
+                package.skipped.push(skip(&reason));
+                continue;
+            }
+        };
+
+        let stem = relative
+            .rsplit_once('.')
+            .map_or(relative.as_str(), |(stem, _)| stem);
+        let module = if flavor == Flavor::Svelte {
+            format!("icons/{stem}.svelte")
+        } else {
+            format!("icons/{stem}.js")
+        };
+        let (code, types) = render_module(&entry.name, &contents, &svg, flavor);
+        package.files.push(PackageFile {
+            path: module.clone(),
+            contents: code,
+        });
+        package.files.push(PackageFile {
+            path: format!("{}.d.ts", module.trim_end_matches(".js")),
+            contents: types,
+        });
+        package.files.push(PackageFile {
+            path: format!("svg/{stem}.svg"),
+            contents: format!("{}\n", svg.trim_end()),
+        });
+        modules.insert(relative, module.clone());
+        index.push((entry.name.clone(), module));
+    }
+
+    let index_js = index
+        .iter()
+        .map(|(name, module)| format!("export {{ default as {name} }} from \"./{module}\";\n"))
+        .collect::<String>();
+    package.files.push(PackageFile {
+        path: "index.js".to_string(),
+        contents: format!("{GENERATED_HEADER}\n{index_js}"),
+    });
+    package.files.push(PackageFile {
+        path: "index.d.ts".to_string(),
+        contents: format!("{GENERATED_HEADER}\n{index_js}"),
+    });
+    package.files.push(PackageFile {
+        path: "package.json".to_string(),
+        contents: render_package_json(meta, preset),
+    });
+    package.exports = index.len();
+    Ok(package)
+}
+
+/// The icon as a standalone SVG document, or why it can't be packaged.
+fn standalone_svg(contents: &str, flavor: Flavor) -> Result<String, String> {
+    let svg = match flavor {
+        Flavor::Markup => contents.trim().to_string(),
+        Flavor::DataUri => {
+            let uri = crate::datauri::parse_datauri_module(contents)
+                .ok_or("not a data URI module".to_string())?;
+            crate::datauri::decode_svg_data_uri(&uri).map_err(|e| e.to_string())?
+        }
+        _ => crate::viewer::svg_preview_contents(contents).map_err(|e| e.to_string())?,
+    };
+    if !svg.contains("<svg") {
+        return Err("no <svg> element".to_string());
+    }
+    // What's left of a `{title ? … : null}` (`--svgr-compat`) can't be static.
+    let component = matches!(flavor, Flavor::React | Flavor::Solid | Flavor::Vue);
+    if component && svg.contains('{') {
+        return Err("markup has JSX expressions; only static markup is packaged".to_string());
+    }
+    Ok(svg)
+}
+
+/// The module and its type declarations.
+fn render_module(name: &str, contents: &str, svg: &str, flavor: Flavor) -> (String, String) {
+    let literal = |value: &str| serde_json::to_string(value).expect("string serialization");
+    let string_types = format!("declare const {name}: string;\nexport default {name};\n");
+    let (root, inner) = split_root(svg);
+
+    match flavor {
+        Flavor::Markup => (
+            format!("export default {};\n", literal(svg.trim())),
+            string_types,
+        ),
+        Flavor::DataUri => (crate::datauri::render_datauri_module(svg), string_types),
+        Flavor::React => (
+            format!(
+                "import {{ createElement }} from \"react\";\n\nconst attrs = {};\nconst markup = {};\n\nexport default function {name}(props) {{\n  return createElement(\"svg\", {{ ...attrs, ...props, dangerouslySetInnerHTML: {{ __html: markup }} }});\n}}\n",
+                attrs_object(&root, crate::svgr::jsx_attribute_name),
+                literal(inner),
+            ),
+            format!(
+                "import type {{ ReactElement, SVGProps }} from \"react\";\n\ndeclare function {name}(props: SVGProps<SVGSVGElement>): ReactElement;\nexport default {name};\n"
+            ),
+        ),
+        Flavor::Solid => (
+            format!(
+                "import {{ mergeProps }} from \"solid-js\";\nimport h from \"solid-js/h\";\n\nconst attrs = {};\nconst markup = {};\n\nexport default function {name}(props) {{\n  return h(\"svg\", mergeProps(attrs, props, {{ innerHTML: markup }}));\n}}\n",
+                attrs_object(&root, str::to_string),
+                literal(inner),
+            ),
+            format!(
+                "import type {{ JSX }} from \"solid-js\";\n\ndeclare function {name}(props: JSX.SvgSVGAttributes<SVGSVGElement>): JSX.Element;\nexport default {name};\n"
+            ),
+        ),
+        Flavor::Vue => (
+            format!(
+                "import {{ h }} from \"vue\";\n\nconst attrs = {};\nconst markup = {};\n\nexport default {{\n  name: {},\n  render() {{\n    return h(\"svg\", {{ ...attrs, innerHTML: markup }});\n  }},\n}};\n",
+                attrs_object(&root, str::to_string),
+                literal(inner),
+                literal(name),
+            ),
+            format!(
+                "import type {{ DefineComponent, SVGAttributes }} from \"vue\";\n\ndeclare const {name}: DefineComponent<SVGAttributes>;\nexport default {name};\n"
+            ),
+        ),
+        Flavor::Svelte => (
+            contents.to_string(),
+            format!(
+                "import type {{ Component }} from \"svelte\";\nimport type {{ SVGAttributes }} from \"svelte/elements\";\n\ndeclare const {name}: Component<SVGAttributes<SVGSVGElement>>;\nexport default {name};\n"
+            ),
+        ),
+    }
+}
+
+/// `<svg a="1"><path/></svg>` -> (`[("a", "1")]`, `<path/>`).
+fn split_root(svg: &str) -> (Vec<(String, String)>, &str) {
+    let Some(start) = svg.find("<svg") else {
+        return (Vec::new(), "");
+    };
+    let Some(end) = svg[start..].find('>').map(|end| start + end) else {
+        return (Vec::new(), "");
+    };
+    let tag = &svg[start + 4..end];
+    let attribute_re =
+        regex::Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .expect("valid SVG attribute regex");
+    let attrs = attribute_re
+        .captures_iter(tag)
+        .map(|captures| {
+            let value = captures
+                .get(2)
+                .or(captures.get(3))
+                .map_or("", |m| m.as_str());
+            (captures[1].to_string(), value.to_string())
+        })
+        .collect();
+    if tag.trim_end().ends_with('/') {
+        return (attrs, "");
+    }
+    let inner = svg[end + 1..]
+        .rfind("</svg>")
+        .map_or("", |close| &svg[end + 1..end + 1 + close]);
+    (attrs, inner.trim())
+}
+
+fn attrs_object(attrs: &[(String, String)], rename: fn(&str) -> String) -> String {
+    if attrs.is_empty() {
+        return "{}".to_string();
+    }
+    let fields = attrs
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{}: {}",
+                serde_json::to_string(&rename(name)).expect("string serialization"),
+                serde_json::to_string(value).expect("string serialization")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{ {fields} }}")
+}
+
+/// Written by hand: the order of export conditions matters (`types` first),
+/// and `serde_json` would sort the keys.
+fn render_package_json(meta: &PackageMeta, preset: &str) -> String {
+    let literal = |value: &str| serde_json::to_string(value).expect("string serialization");
+    let mut lines = vec![
+        format!("  \"name\": {},", literal(&meta.name)),
+        format!("  \"version\": {},", literal(&meta.version)),
+    ];
+    if let Some(description) = &meta.description {
+        lines.push(format!("  \"description\": {},", literal(description)));
+    }
+    if let Some(license) = &meta.license {
+        lines.push(format!("  \"license\": {},", literal(license)));
+    }
+    lines.push("  \"type\": \"module\",".to_string());
+    lines.push("  \"sideEffects\": false,".to_string());
+    lines.push("  \"main\": \"./index.js\",".to_string());
+    lines.push("  \"types\": \"./index.d.ts\",".to_string());
+    if preset == "svelte" {
+        lines.push("  \"svelte\": \"./index.js\",".to_string());
+    }
+    lines.push("  \"exports\": {".to_string());
+    if preset == "svelte" {
+        lines.push("    \".\": { \"types\": \"./index.d.ts\", \"svelte\": \"./index.js\", \"default\": \"./index.js\" },".to_string());
+        lines.push("    \"./svg/*\": \"./svg/*.svg\",".to_string());
+        lines.push("    \"./*\": { \"types\": \"./icons/*.svelte.d.ts\", \"svelte\": \"./icons/*.svelte\" },".to_string());
+    } else {
+        lines.push(
+            "    \".\": { \"types\": \"./index.d.ts\", \"default\": \"./index.js\" },".to_string(),
+        );
+        lines.push("    \"./svg/*\": \"./svg/*.svg\",".to_string());
+        lines.push(
+            "    \"./*\": { \"types\": \"./icons/*.d.ts\", \"default\": \"./icons/*.js\" },"
+                .to_string(),
+        );
+    }
+    lines.push("    \"./package.json\": \"./package.json\"".to_string());
+    let peer = match preset {
+        "react" => Some(("react", ">=17")),
+        "solid" => Some(("solid-js", ">=1.6")),
+        "vue" => Some(("vue", ">=3")),
+        "svelte" => Some(("svelte", ">=5")),
+        _ => None,
+    };
+    match peer {
+        Some((dependency, range)) => {
+            lines.push("  },".to_string());
+            lines.push(format!(
+                "  \"peerDependencies\": {{ {}: {} }}",
+                literal(dependency),
+                literal(range)
+            ));
+        }
+        None => lines.push("  }".to_string()),
+    }
+    format!("{{\n{}\n}}\n", lines.join("\n"))
+}
+
+/// Writes `package` into `out`. A non-empty `out` is only replaced when an
+/// earlier `iconmate package` wrote it, so a typo can't wipe a source folder.
+pub fn write_package(out: &Path, package: &Package) -> anyhow::Result<()> {
+    if out.is_dir() && std::fs::read_dir(out)?.next().is_some() {
+        let generated = std::fs::read_to_string(out.join("index.js"))
+            .is_ok_and(|index| index.starts_with(GENERATED_HEADER));
+        if !generated {
+            anyhow::bail!(
+                "{} is not empty and wasn't written by `iconmate package`. Pick another --out.",
+                out.display()
+            );
+        }
+        for stale in ["icons", "svg"] {
+            let dir = out.join(stale);
+            if dir.is_dir() {
+                std::fs::remove_dir_all(&dir)
+                    .with_context(|| format!("Failed to clear {}", dir.display()))?;
+            }
+        }
+    }
+    for file in &package.files {
+        let path = out.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta() -> PackageMeta {
+        PackageMeta {
+            name: "@acme/icons".to_string(),
+            version: "1.2.0".to_string(),
+            description: None,
+            license: Some("MIT".to_string()),
+        }
+    }
+
+    fn entry(name: &str, file: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file.to_string(),
+        }
+    }
+
+    fn file<'a>(package: &'a Package, path: &str) -> &'a str {
+        &package
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap_or_else(|| panic!("{path} not written"))
+            .contents
+    }
+
+    #[test]
+    fn compiles_react_components_to_esm() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("heart.tsx"),
+            "import type { SVGProps } from 'react';\n\nexport default function Icon(props: SVGProps<SVGSVGElement>) {\n  return (\n    <svg viewBox=\"0 0 24 24\" stroke-width=\"2\" {...props}><path d=\"M1 1\" /></svg>\n  );\n}",
+        )
+        .unwrap();
+        let entries = vec![
+            entry("IconHeart", "./heart"),
+            entry("IconLove", "./heart"),
+            entry("IconGone", "./gone"),
+        ];
+
+        let package = build_package(dir.path(), &entries, "react", &meta()).unwrap();
+        let module = file(&package, "icons/heart.js");
+        assert!(module.contains("import { createElement } from \"react\";"));
+        assert!(module.contains("\"strokeWidth\": \"2\""));
+        assert!(module.contains("const markup = \"<path d=\\\"M1 1\\\" />\";"));
+        assert!(module.contains("export default function IconHeart(props)"));
+        assert!(file(&package, "icons/heart.d.ts").contains("SVGProps<SVGSVGElement>"));
+        assert!(file(&package, "svg/heart.svg").contains("xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(
+            file(&package, "index.js")
+                .lines()
+                .skip(1)
+                .collect::<Vec<_>>(),
+            vec![
+                "export { default as IconHeart } from \"./icons/heart.js\";",
+                "export { default as IconLove } from \"./icons/heart.js\";",
+            ]
+        );
+        assert_eq!(package.exports, 2);
+        assert_eq!(package.skipped[0].name, "IconGone");
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(file(&package, "package.json")).unwrap();
+        assert_eq!(manifest["name"], "@acme/icons");
+        assert_eq!(manifest["exports"]["./*"]["default"], "./icons/*.js");
+        assert_eq!(manifest["peerDependencies"]["react"], ">=17");
+    }
+
+    #[test]
+    fn skips_exports_outside_the_folder() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let folder = dir.path().join("icons");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+        let svg = "<svg viewBox=\"0 0 24 24\"><path d=\"M1 1\" /></svg>";
+        std::fs::write(folder.join("heart.svg"), svg).unwrap();
+        std::fs::write(dir.path().join("shared/logo.svg"), svg).unwrap();
+        let entries = vec![
+            entry("IconHeart", "./heart.svg"),
+            entry("IconLogo", "../shared/logo.svg"),
+        ];
+
+        let package = build_package(&folder, &entries, "normal", &meta()).unwrap();
+        assert_eq!(package.exports, 1);
+        assert_eq!(
+            package.skipped,
+            vec![Skipped {
+                name: "IconLogo".to_string(),
+                reason: "outside the icons folder".to_string(),
+            }]
+        );
+        assert!(package.files.iter().all(|file| !file.path.contains("..")));
+    }
+
+    #[test]
+    fn refuses_to_overwrite_foreign_folders() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.ts"), "").unwrap();
+        assert!(write_package(dir.path(), &Package::default()).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+// Order-preserving parallel map for per-icon work over large folders.
+//
+// Design systems can have thousands of icons. `sync` renders an export line
+// (template, barrel style detection, tsconfig lookup) for every orphan file,
+// and `check` stats every icon file and, across a workspace, reads and
+// normalizes each one. That work is independent per file, so it runs on
+// rayon's pool. Results come back in input order, keeping the plan and the
+// rewritten barrel deterministic.
+
+use rayon::prelude::*;
+
+/// `items.iter().map(f).collect()`, spread over the available cores.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    items.par_iter().map(&f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preserves_input_order_for_small_and_large_inputs() {
+        for len in [0, 3, 64, 5_000] {
+            let items: Vec<usize> = (0..len).collect();
+            let doubled = map(&items, |n| n * 2);
+            assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// Progress feedback for slow CLI work (downloads, multi-file renders, batches).
+//
+// Drawn with `indicatif` on stderr: a spinner with an optional byte counter
+// for single downloads and renders, and a counted bar for batch operations
+// (several `--folder` targets, adopt, sync). They share one `MultiProgress`,
+// so a download started inside a batch draws under its bar instead of over
+// it. Bars only draw when stderr is a terminal, so pipes and CI logs stay clean; `--quiet` (or a set `CI`
+// variable) turns them off everywhere, and the TUI disables them while it owns
+// the screen.
+
+use std::io::IsTerminal;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressFinish, ProgressStyle};
+
+const TICK: Duration = Duration::from_millis(80);
+const SPINNER_TEMPLATE: &str = "{spinner} {msg}";
+const BYTES_TEMPLATE: &str = "{spinner} {msg} {bytes}";
+const TOTAL_BYTES_TEMPLATE: &str = "{spinner} {msg} {bytes} / {total_bytes}";
+const COUNTED_TEMPLATE: &str = "{spinner} {msg} [{bar:24}] {pos}/{len}";
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static BARS: LazyLock<MultiProgress> = LazyLock::new(MultiProgress::new);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    !QUIET.load(Ordering::Relaxed)
+        && std::env::var_os("CI").is_none()
+        && std::io::stderr().is_terminal()
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template)
+        .expect("progress templates are valid")
+        .progress_chars("=> ")
+}
+
+/// A bar that draws only when progress is enabled, and clears its line when
+/// finished or dropped.
+fn new_bar(length: Option<u64>, template: &str, message: String) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = match length {
+        Some(length) => ProgressBar::new(length),
+        None => ProgressBar::no_length(),
+    }
+    .with_style(style(template))
+    .with_message(message)
+    .with_finish(ProgressFinish::AndClear);
+    let bar = BARS.add(bar);
+    bar.enable_steady_tick(TICK);
+    bar
+}
+
+/// A live status line with an optional byte counter.
+pub struct Spinner {
+    bar: ProgressBar,
+}
+
+impl Spinner {
+    pub fn start(message: impl Into<String>) -> Self {
+        Self {
+            bar: new_bar(None, SPINNER_TEMPLATE, message.into()),
+        }
+    }
+
+    pub fn set_total(&self, total: Option<u64>) {
+        match total {
+            Some(total) => self.bar.set_length(total),
+            None => self.bar.unset_length(),
+        }
+    }
+
+    pub fn inc(&self, bytes: u64) {
+        if self.bar.position() == 0 {
+            let template = if self.bar.length().is_some() {
+                TOTAL_BYTES_TEMPLATE
+            } else {
+                BYTES_TEMPLATE
+            };
+            self.bar.set_style(style(template));
+        }
+        self.bar.inc(bytes);
+    }
+
+    pub fn finish(self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// `done/total` for a batch: one step per folder, file or icon.
+pub struct Bar {
+    bar: ProgressBar,
+}
+
+impl Bar {
+    /// Nothing is drawn for a single item; the work it wraps has its own
+    /// feedback.
+    pub fn counted(message: impl Into<String>, len: usize) -> Self {
+        let bar = if len > 1 {
+            new_bar(Some(len as u64), COUNTED_TEMPLATE, message.into())
+        } else {
+            ProgressBar::hidden()
+        };
+        Self { bar }
+    }
+
+    pub fn inc(&self) {
+        self.bar.inc(1);
+    }
+
+    pub fn finish(self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Reads a response body chunk by chunk, showing `message` and the byte count.
+pub async fn read_body(
+    mut response: reqwest::Response,
+    message: impl Into<String>,
+) -> reqwest::Result<String> {
+    let spinner = Spinner::start(message);
+    spinner.set_total(response.content_length());
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        spinner.inc(chunk.len() as u64);
+        body.extend_from_slice(&chunk);
+    }
+    spinner.finish();
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_parse() {
+        for template in [
+            SPINNER_TEMPLATE,
+            BYTES_TEMPLATE,
+            TOTAL_BYTES_TEMPLATE,
+            COUNTED_TEMPLATE,
+        ] {
+            assert!(ProgressStyle::with_template(template).is_ok(), "{template}");
+        }
+    }
+
+    #[test]
+    fn bars_stay_hidden_when_quiet() {
+        set_quiet(true);
+        let bar = Bar::counted("Adopting", 3);
+        assert!(bar.bar.is_hidden());
+        bar.inc();
+        assert_eq!(bar.bar.position(), 1);
+        bar.finish();
+        assert!(Spinner::start("Fetching").bar.is_hidden());
+    }
+}
@@ -0,0 +1,381 @@
+// Raster export support.
+//
+// `iconmate add --raster png --sizes 16,32,64` renders the written SVG to
+// square raster images next to it (`heart.svg` -> `heart.16.png`, ...) for
+// favicon/manifest/native-app use. Rendering is `svg_render::render_pixmap`
+// (the `resvg` crate), the same renderer as the terminal previews; WebP output
+// additionally pipes the PNG through `cwebp`.
+//
+// Every raster written is recorded in `rasters.json` next to the SVG (SVG
+// file name -> raster file names), so delete/rename/edit of the SVG carries
+// exactly those along. A hand-made `heart.16.png` that merely looks generated
+// is never touched.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Context;
+use clap::ValueEnum;
+
+pub const DEFAULT_RASTER_SIZES: &[u32] = &[16, 32, 64];
+
+pub const RASTER_MANIFEST: &str = "rasters.json";
+
+/// Overrides the `cwebp` binary used for WebP output.
+const CWEBP_BIN_ENV: &str = "ICONMATE_CWEBP_BIN";
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RasterFormat {
+    #[value(name = "png")]
+    Png,
+    #[value(name = "webp")]
+    Webp,
+}
+
+impl RasterFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RasterFormat::Png => "png",
+            RasterFormat::Webp => "webp",
+        }
+    }
+}
+
+/// `icons/heart.svg` + 32 + png -> `icons/heart.32.png`.
+pub fn raster_path(svg_path: &Path, size: u32, format: RasterFormat) -> PathBuf {
+    let stem = svg_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    svg_path.with_file_name(format!("{stem}.{size}.{}", format.extension()))
+}
+
+/// Renders `svg_path` once per size. Returns the written files.
+pub fn render_rasters(
+    svg_path: &Path,
+    format: RasterFormat,
+    sizes: &[u32],
+) -> anyhow::Result<Vec<PathBuf>> {
+    let sizes = if sizes.is_empty() {
+        DEFAULT_RASTER_SIZES
+    } else {
+        sizes
+    };
+
+    let mut written = Vec::new();
+    for &size in sizes {
+        if size == 0 {
+            anyhow::bail!("Raster sizes must be greater than 0.");
+        }
+        let out_path = raster_path(svg_path, size, format);
+        match format {
+            RasterFormat::Png => render_png(svg_path, &out_path, size)?,
+            RasterFormat::Webp => {
+                let png_path = raster_path(svg_path, size, RasterFormat::Png);
+                let png_existed = png_path.exists();
+                render_png(svg_path, &png_path, size)?;
+                let result = run_cwebp(&png_path, &out_path);
+                if !png_existed {
+                    let _ = std::fs::remove_file(&png_path);
+                }
+                result?;
+            }
+        }
+        written.push(out_path);
+    }
+    record_rasters(svg_path, &written)?;
+    Ok(written)
+}
+
+/// Renders `svg_path` to a `size`x`size` PNG at `out_path`.
+pub fn render_png(svg_path: &Path, out_path: &Path, size: u32) -> anyhow::Result<()> {
+    let svg = std::fs::read_to_string(svg_path)
+        .with_context(|| format!("Failed to read {}", svg_path.display()))?;
+    let pixmap = crate::svg_render::render_pixmap(&svg, size, size, svg_path.parent())
+        .with_context(|| format!("Failed to render {}", svg_path.display()))?;
+    let png = pixmap.encode_png()?;
+    std::fs::write(out_path, png).with_context(|| format!("Failed to write {}", out_path.display()))
+}
+
+fn run_cwebp(png_path: &Path, out_path: &Path) -> anyhow::Result<()> {
+    let bin = std::env::var(CWEBP_BIN_ENV).unwrap_or_else(|_| "cwebp".to_string());
+    let status = Command::new(&bin)
+        .args(["-quiet", "-lossless"])
+        .arg(png_path)
+        .arg("-o")
+        .arg(out_path)
+        .status()
+        .with_context(|| {
+            format!("Failed to run `{bin}`. Install libwebp's `cwebp` or set {CWEBP_BIN_ENV}.")
+        })?;
+    if !status.success() {
+        anyhow::bail!("`{bin}` failed to encode {} ({status})", png_path.display());
+    }
+    Ok(())
+}
+
+/// SVG file name -> the raster file names generated for it, per directory.
+type Manifest = BTreeMap<String, BTreeSet<String>>;
+
+fn read_manifest(dir: &Path) -> anyhow::Result<Manifest> {
+    let path = dir.join(RASTER_MANIFEST);
+    if !path.is_file() {
+        return Ok(Manifest::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("{} is not valid JSON", path.display()))
+}
+
+/// Writes `manifest`, or removes the file when it records nothing.
+fn write_manifest(dir: &Path, manifest: &Manifest) -> anyhow::Result<()> {
+    let path = dir.join(RASTER_MANIFEST);
+    if manifest.is_empty() {
+        if path.is_file() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    let mut json = serde_json::to_string_pretty(manifest)?;
+    json.push('\n');
+    std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The directory and file name `svg_path` is recorded under.
+fn manifest_key(svg_path: &Path) -> Option<(&Path, String)> {
+    let name = svg_path.file_name()?.to_string_lossy().into_owned();
+    Some((svg_path.parent()?, name))
+}
+
+fn record_rasters(svg_path: &Path, rasters: &[PathBuf]) -> anyhow::Result<()> {
+    let Some((dir, key)) = manifest_key(svg_path) else {
+        return Ok(());
+    };
+    let mut manifest = read_manifest(dir)?;
+    manifest.entry(key).or_default().extend(
+        rasters
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned()),
+    );
+    write_manifest(dir, &manifest)
+}
+
+/// Drops the record of `svg_path`, returning the raster names it had.
+fn forget_rasters(svg_path: &Path) -> anyhow::Result<BTreeSet<String>> {
+    let Some((dir, key)) = manifest_key(svg_path) else {
+        return Ok(BTreeSet::new());
+    };
+    let mut manifest = read_manifest(dir)?;
+    let Some(names) = manifest.remove(&key) else {
+        return Ok(BTreeSet::new());
+    };
+    write_manifest(dir, &manifest)?;
+    Ok(names)
+}
+
+/// The rasters recorded for `svg_path` that are still on disk.
+pub fn find_raster_siblings(svg_path: &Path) -> Vec<PathBuf> {
+    let Some((dir, key)) = manifest_key(svg_path) else {
+        return Vec::new();
+    };
+    let Ok(mut manifest) = read_manifest(dir) else {
+        return Vec::new();
+    };
+    manifest
+        .remove(&key)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// For `heart.32.png` and stem `heart`, returns `32.png`.
+fn raster_size_suffix<'a>(file_name: &'a str, stem: &str) -> Option<&'a str> {
+    let rest = file_name.strip_prefix(stem)?.strip_prefix('.')?;
+    let (size, ext) = rest.split_once('.')?;
+    let is_raster_ext =
+        ext == RasterFormat::Png.extension() || ext == RasterFormat::Webp.extension();
+    if size.is_empty() || !size.chars().all(|c| c.is_ascii_digit()) || !is_raster_ext {
+        return None;
+    }
+    Some(rest)
+}
+
+/// Removes every raster generated for `svg_path`, and its record.
+pub fn remove_raster_siblings(svg_path: &Path) -> anyhow::Result<()> {
+    let Some(dir) = svg_path.parent() else {
+        return Ok(());
+    };
+    for name in forget_rasters(svg_path)? {
+        let path = dir.join(name);
+        if path.is_file() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Re-renders every raster already generated for `svg_path`, at the same
+/// sizes and formats, after its content changed. Returns the written files.
+pub fn rerender_raster_siblings(svg_path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let stem = svg_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut written = Vec::new();
+    for path in find_raster_siblings(svg_path) {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some((size, ext)) =
+            raster_size_suffix(name, &stem).and_then(|rest| rest.split_once('.'))
+        else {
+            continue;
+        };
+        let format = if ext == RasterFormat::Webp.extension() {
+            RasterFormat::Webp
+        } else {
+            RasterFormat::Png
+        };
+        let size = size.parse::<u32>().context("Invalid raster size")?;
+        written.extend(render_rasters(svg_path, format, &[size])?);
+    }
+    Ok(written)
+}
+
+/// Moves rasters generated for `old_svg_path` so they follow `new_svg_path`.
+pub fn rename_raster_siblings(old_svg_path: &Path, new_svg_path: &Path) -> anyhow::Result<()> {
+    let (Some(old_dir), Some(old_stem), Some(new_stem)) = (
+        old_svg_path.parent(),
+        old_svg_path.file_stem(),
+        new_svg_path.file_stem(),
+    ) else {
+        return Ok(());
+    };
+    let (old_stem, new_stem) = (old_stem.to_string_lossy(), new_stem.to_string_lossy());
+    let Some(new_parent) = new_svg_path.parent() else {
+        return Ok(());
+    };
+
+    let mut moved = Vec::new();
+    for name in forget_rasters(old_svg_path)? {
+        let path = old_dir.join(&name);
+        let Some(suffix) = raster_size_suffix(&name, &old_stem) else {
+            continue;
+        };
+        if !path.is_file() {
+            continue;
+        }
+        let target = new_parent.join(format!("{new_stem}.{suffix}"));
+        std::fs::rename(&path, &target)
+            .with_context(|| format!("Failed to move {}", path.display()))?;
+        moved.push(target);
+    }
+    if !moved.is_empty() {
+        record_rasters(new_svg_path, &moved)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn raster_path_uses_stem_size_and_extension() {
+        assert_eq!(
+            raster_path(Path::new("icons/heart.svg"), 32, RasterFormat::Png),
+            PathBuf::from("icons/heart.32.png")
+        );
+        assert_eq!(
+            raster_path(Path::new("icons/heart.svg"), 16, RasterFormat::Webp),
+            PathBuf::from("icons/heart.16.webp")
+        );
+    }
+
+    #[test]
+    fn renders_pngs_in_process_and_records_them() {
+        let dir = TempDir::new().unwrap();
+        let svg = dir.path().join("heart.svg");
+        std::fs::write(
+            &svg,
+            r#"<svg viewBox="0 0 4 4"><rect width="4" height="4"/></svg>"#,
+        )
+        .unwrap();
+
+        let written = render_rasters(&svg, RasterFormat::Png, &[16, 32]).unwrap();
+
+        assert_eq!(
+            written,
+            [
+                dir.path().join("heart.16.png"),
+                dir.path().join("heart.32.png")
+            ]
+        );
+        let png = std::fs::read(&written[0]).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(find_raster_siblings(&svg), written);
+    }
+
+    #[test]
+    fn siblings_are_the_recorded_rasters_only() {
+        let dir = TempDir::new().unwrap();
+        for name in ["heart.svg", "heart.16.png", "heart.32.webp", "heart.48.png"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let svg = dir.path().join("heart.svg");
+        record_rasters(
+            &svg,
+            &[
+                dir.path().join("heart.16.png"),
+                dir.path().join("heart.32.webp"),
+                dir.path().join("heart.64.png"),
+            ],
+        )
+        .unwrap();
+
+        let names: Vec<String> = find_raster_siblings(&svg)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["heart.16.png", "heart.32.webp"]);
+
+        remove_raster_siblings(&svg).unwrap();
+        assert!(!dir.path().join("heart.16.png").exists());
+        assert!(!dir.path().join("heart.32.webp").exists());
+        assert!(dir.path().join("heart.48.png").exists());
+        assert!(!dir.path().join(RASTER_MANIFEST).exists());
+    }
+
+    #[test]
+    fn rename_moves_rasters_with_the_svg() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("heart.16.png"), "").unwrap();
+        std::fs::write(dir.path().join("heart.32.png"), "").unwrap();
+        record_rasters(
+            &dir.path().join("heart.svg"),
+            &[
+                dir.path().join("heart.16.png"),
+                dir.path().join("heart.32.png"),
+            ],
+        )
+        .unwrap();
+
+        rename_raster_siblings(&dir.path().join("heart.svg"), &dir.path().join("love.svg"))
+            .unwrap();
+
+        assert!(dir.path().join("love.16.png").exists());
+        assert!(dir.path().join("love.32.png").exists());
+        assert!(!dir.path().join("heart.16.png").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(RASTER_MANIFEST)).unwrap(),
+            "{\n  \"love.svg\": [\n    \"love.16.png\",\n    \"love.32.png\"\n  ]\n}\n"
+        );
+    }
+}
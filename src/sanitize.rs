@@ -0,0 +1,115 @@
+// SVG cleanup applied before an icon is written.
+//
+// Editors and design tools leave comments, `<?xml ?>` prologs and DOCTYPEs in
+// exported SVGs. None of them change how the icon renders, but comments break
+// JSX and some bundler SVG loaders choke on prologs and DOCTYPEs, so they are
+// stripped from every save unless `sanitize_svg` is turned off. CDATA sections
+// (e.g. inside `<style>`) are copied through untouched.
+
+/// Removes comments, processing instructions (including the XML prolog) and
+/// DOCTYPE declarations from `svg`. A construct that sat on its own line takes
+/// the line with it, so the remaining markup keeps its layout.
+pub fn sanitize_svg(svg: &str) -> String {
+    let mut out = String::with_capacity(svg.len());
+    let mut rest = svg;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("<![CDATA[") {
+            let end = rest.find("]]>").map(|end| end + 3).unwrap_or(rest.len());
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let removed_len = if let Some(comment) = rest.strip_prefix("<!--") {
+            comment.find("-->").map(|end| end + 7)
+        } else if rest.starts_with("<?") {
+            rest.find("?>").map(|end| end + 2)
+        } else if rest
+            .get(..9)
+            .is_some_and(|head| head.eq_ignore_ascii_case("<!DOCTYPE"))
+        {
+            doctype_end(rest)
+        } else {
+            None
+        };
+        let Some(removed_len) = removed_len else {
+            out.push('<');
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[removed_len..];
+
+        // Drop the line too if nothing else was on it.
+        let line_start = out.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let after = rest.trim_start_matches([' ', '\t']);
+        if out[line_start..].trim().is_empty()
+            && (after.is_empty() || after.starts_with('\n') || after.starts_with("\r\n"))
+        {
+            out.truncate(line_start);
+            rest = after
+                .strip_prefix("\r\n")
+                .or_else(|| after.strip_prefix('\n'))
+                .unwrap_or(after);
+        }
+    }
+    out.push_str(rest);
+
+    // Whatever preceded the root element was only there for the prolog.
+    if out.trim_start().starts_with('<') {
+        out.trim_start().to_string()
+    } else {
+        out
+    }
+}
+
+/// Length of a `<!DOCTYPE ...>` declaration, including an internal
+/// `[...]` subset, which may contain `>` of its own.
+fn doctype_end(doctype: &str) -> Option<usize> {
+    let mut in_subset = false;
+    let mut quote = None;
+    for (index, ch) in doctype.char_indices() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '[') => in_subset = true,
+            (None, ']') => in_subset = false,
+            (None, '>') if !in_subset => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_prolog_doctype_and_comments_keeping_layout() {
+        let svg = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd" [
+  <!ENTITY ns "http://www.w3.org/2000/svg">
+]>
+<!-- Generator: Sketch -->
+<svg viewBox="0 0 24 24">
+  <!-- outline -->
+  <path d="M0 0h24"/><!-- trailing -->
+</svg>
+"#;
+        assert_eq!(
+            sanitize_svg(svg),
+            "<svg viewBox=\"0 0 24 24\">\n  <path d=\"M0 0h24\"/>\n</svg>\n"
+        );
+    }
+
+    #[test]
+    fn leaves_cdata_and_unterminated_markup_alone() {
+        let svg = "<svg><style><![CDATA[/* <!-- not a comment --> */]]></style><!-- open</svg>";
+        assert_eq!(sanitize_svg(svg), svg);
+    }
+}
@@ -0,0 +1,383 @@
+// Local web preview (`iconmate serve`).
+//
+// A tiny HTTP/1.1 server on top of tokio's TcpListener that renders a
+// searchable grid of the folder's icons in the browser. Clicking a card copies
+// its import statement. Every request re-reads the barrel, so adds/deletes
+// show up on refresh without restarting the server.
+//
+// Routes:
+//   GET /             the gallery page
+//   GET /api/icons    JSON list of `{ name, file, usage, preview }`
+//   GET /files/<rel>  files from the icons folder (previews; SVGs get
+//                     `preview_colors`)
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::gallery::{ImportStyle, usage_snippet};
+use crate::utils::get_existing_icons_for_preset;
+
+pub const DEFAULT_SERVE_PORT: u16 = 4173;
+
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+pub struct ServeContext {
+    pub folder: PathBuf,
+    pub preset: String,
+    pub flutter_barrel_file: Option<String>,
+    pub flutter_barrel_class: Option<String>,
+    /// `preview_colors`, applied to the SVGs the page shows.
+    pub preview_colors: crate::viewer::PreviewColors,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: message.as_bytes().to_vec(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Binds `host:port` and serves until the process is interrupted.
+pub async fn serve(ctx: ServeContext, host: &str, port: u16) -> anyhow::Result<()> {
+    let listener = TcpListener::bind((host, port))
+        .await
+        .with_context(|| format!("Failed to bind {host}:{port}"))?;
+    println!(
+        "Serving {} at http://{}/ (Ctrl+C to stop)",
+        ctx.folder.display(),
+        listener.local_addr()?
+    );
+
+    let ctx = Arc::new(ctx);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &ctx).await {
+                eprintln!("serve: {e}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, ctx: &ServeContext) -> anyhow::Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, target) = (parts.next().unwrap_or_default(), parts.next());
+    let response = match (method, target) {
+        ("GET" | "HEAD", Some(target)) => route(ctx, target),
+        (_, Some(_)) => Response::error(405, "Only GET is supported."),
+        _ => Response::error(400, "Malformed request."),
+    };
+
+    let header = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(header.as_bytes()).await?;
+    if method != "HEAD" {
+        stream.write_all(&response.body).await?;
+    }
+    stream.shutdown().await?;
+    Ok(())
+}
+
+pub fn route(ctx: &ServeContext, target: &str) -> Response {
+    let path = target.split(['?', '#']).next().unwrap_or_default();
+    match path {
+        "/" | "/index.html" => Response::ok("text/html; charset=utf-8", GALLERY_PAGE),
+        "/api/icons" => match icons_json(ctx) {
+            Ok(json) => Response::ok("application/json", json),
+            Err(e) => Response::error(500, &e.to_string()),
+        },
+        _ => match path.strip_prefix("/files/") {
+            Some(rel) => serve_file(ctx, &percent_decode(rel)),
+            None => Response::error(404, "Not found."),
+        },
+    }
+}
+
+fn icons_json(ctx: &ServeContext) -> anyhow::Result<String> {
+    let folder_str = ctx.folder.to_string_lossy().replace('\\', "/");
+    let module = folder_str.trim_end_matches('/');
+    let style = ImportStyle::for_preset(&ctx.preset, module, ctx.flutter_barrel_class.as_deref());
+
+    let mut entries =
+        get_existing_icons_for_preset(&folder_str, &ctx.preset, ctx.flutter_barrel_file.as_deref())
+            .unwrap_or_default();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let icons: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            let file = entry
+                .file_path
+                .replace('\\', "/")
+                .trim_start_matches("./")
+                .to_string();
+            let preview = file.ends_with(".svg").then(|| format!("/files/{file}"));
+            serde_json::json!({
+                "name": entry.name,
+                "file": file,
+                "usage": usage_snippet(&entry.name, &style),
+                "preview": preview,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string(&icons)?)
+}
+
+fn serve_file(ctx: &ServeContext, rel: &str) -> Response {
+    let Some(path) = resolve_served_file(&ctx.folder, rel) else {
+        return Response::error(404, "Not found.");
+    };
+    let content_type = content_type_for(&path);
+    match std::fs::read(&path) {
+        Ok(body) if content_type == "image/svg+xml" => {
+            let svg = String::from_utf8_lossy(&body);
+            Response::ok(content_type, ctx.preview_colors.apply(&svg))
+        }
+        Ok(body) => Response::ok(content_type, body),
+        Err(_) => Response::error(404, "Not found."),
+    }
+}
+
+/// Joins `rel` onto `folder`, refusing anything that could climb out of it.
+pub fn resolve_served_file(folder: &Path, rel: &str) -> Option<PathBuf> {
+    let rel = Path::new(rel);
+    if rel.as_os_str().is_empty()
+        || !rel
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let path = folder.join(rel);
+    path.is_file().then_some(path)
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        _ => "text/plain; charset=utf-8",
+    }
+}
+
+/// Decodes `%XX` escapes; malformed escapes are kept verbatim.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = input.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+const GALLERY_PAGE: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>iconmate</title>
+<style>
+  :root { color-scheme: light dark; font-family: system-ui, sans-serif; }
+  body { margin: 0; padding: 24px; }
+  header { display: flex; gap: 16px; align-items: center; margin-bottom: 20px; }
+  h1 { font-size: 18px; margin: 0; }
+  input { flex: 1; max-width: 360px; padding: 8px 10px; font-size: 14px; border-radius: 6px; border: 1px solid #8884; }
+  #count { opacity: .6; font-size: 13px; }
+  #grid { display: grid; grid-template-columns: repeat(auto-fill, minmax(132px, 1fr)); gap: 12px; }
+  button.card { display: flex; flex-direction: column; align-items: center; gap: 10px; padding: 16px 8px; border: 1px solid #8883; border-radius: 8px; background: none; color: inherit; cursor: pointer; font: inherit; }
+  button.card:hover { border-color: #888; }
+  .preview { width: 32px; height: 32px; display: flex; align-items: center; justify-content: center; opacity: .8; }
+  .preview img { max-width: 100%; max-height: 100%; }
+  .name { font-size: 12px; word-break: break-all; }
+  #toast { position: fixed; bottom: 20px; left: 50%; transform: translateX(-50%); padding: 8px 14px; border-radius: 6px; background: #222; color: #fff; font-size: 13px; opacity: 0; transition: opacity .15s; pointer-events: none; }
+  #toast.show { opacity: 1; }
+</style>
+</head>
+<body>
+<header>
+  <h1>iconmate</h1>
+  <input id="search" type="search" placeholder="Search icons…" autofocus>
+  <span id="count"></span>
+</header>
+<div id="grid"></div>
+<div id="toast"></div>
+<script>
+  const grid = document.getElementById("grid");
+  const search = document.getElementById("search");
+  const count = document.getElementById("count");
+  const toast = document.getElementById("toast");
+  let icons = [];
+
+  function render() {
+    const q = search.value.trim().toLowerCase();
+    const shown = icons.filter((i) => !q || i.name.toLowerCase().includes(q) || i.file.toLowerCase().includes(q));
+    count.textContent = shown.length + " / " + icons.length;
+    grid.replaceChildren(...shown.map((icon) => {
+      const card = document.createElement("button");
+      card.className = "card";
+      card.title = icon.usage;
+      const preview = document.createElement("div");
+      preview.className = "preview";
+      if (icon.preview) {
+        const img = document.createElement("img");
+        img.src = icon.preview;
+        img.alt = icon.name;
+        preview.append(img);
+      } else {
+        preview.textContent = "—";
+      }
+      const name = document.createElement("div");
+      name.className = "name";
+      name.textContent = icon.name;
+      card.append(preview, name);
+      card.addEventListener("click", () => copy(icon.usage));
+      return card;
+    }));
+  }
+
+  async function copy(text) {
+    try {
+      await navigator.clipboard.writeText(text);
+      toast.textContent = "Copied: " + text;
+    } catch {
+      toast.textContent = text;
+    }
+    toast.classList.add("show");
+    clearTimeout(copy.timer);
+    copy.timer = setTimeout(() => toast.classList.remove("show"), 1600);
+  }
+
+  search.addEventListener("input", render);
+  fetch("/api/icons").then((r) => r.json()).then((data) => { icons = data; render(); });
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn context(dir: &TempDir) -> ServeContext {
+        ServeContext {
+            folder: dir.path().to_path_buf(),
+            preset: "normal".to_string(),
+            flutter_barrel_file: None,
+            flutter_barrel_class: None,
+            preview_colors: Default::default(),
+        }
+    }
+
+    #[test]
+    fn api_lists_icons_with_usage_and_preview() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("heart.svg"), "<svg/>").unwrap();
+
+        let response = route(&context(&dir), "/api/icons?t=1");
+        assert_eq!(response.status, 200);
+        let icons: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(icons[0]["name"], "IconHeart");
+        assert_eq!(icons[0]["file"], "heart.svg");
+        assert_eq!(icons[0]["preview"], "/files/heart.svg");
+        assert!(
+            icons[0]["usage"]
+                .as_str()
+                .unwrap()
+                .starts_with("import { IconHeart } from ")
+        );
+
+        let file = route(&context(&dir), "/files/heart.svg");
+        assert_eq!(file.content_type, "image/svg+xml");
+        assert_eq!(file.body, b"<svg/>");
+
+        let ctx = ServeContext {
+            preview_colors: crate::viewer::PreviewColors {
+                foreground: crate::viewer::Rgb::parse("#ffffff"),
+                background: None,
+            },
+            ..context(&dir)
+        };
+        let file = route(&ctx, "/files/heart.svg");
+        assert_eq!(file.body, b"<svg style=\"color:#ffffff;\"/>");
+    }
+
+    #[test]
+    fn served_files_cannot_escape_the_folder() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("icons")).unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "nope").unwrap();
+        let folder = dir.path().join("icons");
+
+        assert!(resolve_served_file(&folder, "../secret.txt").is_none());
+        assert!(resolve_served_file(&folder, "/etc/passwd").is_none());
+        assert!(resolve_served_file(&folder, "").is_none());
+        assert_eq!(percent_decode("..%2Fsecret.txt"), "../secret.txt");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+}
@@ -0,0 +1,124 @@
+// Per-icon size budget (`max_icon_size` / `on_oversize` local config keys).
+//
+// Icons are a few hundred bytes to a few KB; a 200 KB "icon" is almost
+// always an illustration or an export with embedded bitmaps that was pasted
+// into the wrong folder. `add` compares the file it's about to write against
+// the budget and either warns or refuses (`--force` writes it anyway), and
+// `iconmate check` lists the files already over it.
+
+use crate::progress::format_bytes;
+
+/// What `add` does with an icon over `max_icon_size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OversizeAction {
+    Warn,
+    #[default]
+    Error,
+}
+
+impl OversizeAction {
+    pub const NAMES: &'static [&'static str] = &["warn", "error"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(OversizeAction::Warn),
+            "error" => Some(OversizeAction::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeBudget {
+    pub max_bytes: u64,
+    pub action: OversizeAction,
+}
+
+impl SizeBudget {
+    pub fn exceeded_by(&self, bytes: u64) -> bool {
+        bytes > self.max_bytes
+    }
+
+    /// `200.0 KB, over the 16.0 KB max_icon_size`.
+    pub fn overage(&self, bytes: u64) -> String {
+        format!(
+            "{}, over the {} max_icon_size",
+            format_bytes(bytes),
+            format_bytes(self.max_bytes)
+        )
+    }
+
+    /// Checks an icon `add` is about to write. Over the budget, `warn` (or
+    /// `force`) returns the warning to print; `error` fails.
+    pub fn check_add(&self, file: &str, bytes: u64, force: bool) -> anyhow::Result<Option<String>> {
+        if !self.exceeded_by(bytes) {
+            return Ok(None);
+        }
+        let message = format!("{file} is {}", self.overage(bytes));
+        if self.action == OversizeAction::Error && !force {
+            anyhow::bail!(
+                "{message}. Is it an illustration rather than an icon? Pass --force to add it anyway."
+            );
+        }
+        Ok(Some(message))
+    }
+}
+
+/// `2048`, `"2048"`, `"16KB"`, `"1.5 MB"` -> bytes. Units are binary
+/// (1 KB = 1024 bytes), like the sizes iconmate prints.
+pub fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<f64>().ok().filter(|n| n.is_finite())?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes_with_binary_units() {
+        assert_eq!(parse_size("2048"), Some(2048));
+        assert_eq!(parse_size("16KB"), Some(16 * 1024));
+        assert_eq!(parse_size("1.5 mb"), Some(1_572_864));
+        assert_eq!(parse_size("12 parsecs"), None);
+        assert_eq!(parse_size("KB"), None);
+    }
+
+    #[test]
+    fn error_budget_fails_unless_forced() {
+        let budget = SizeBudget {
+            max_bytes: 1024,
+            action: OversizeAction::Error,
+        };
+        assert_eq!(budget.check_add("heart.svg", 1024, false).unwrap(), None);
+        let error = budget.check_add("heart.svg", 4096, false).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("heart.svg is 4.0 KB, over the 1.0 KB max_icon_size.")
+        );
+        assert!(budget.check_add("heart.svg", 4096, true).unwrap().is_some());
+
+        let budget = SizeBudget {
+            action: OversizeAction::Warn,
+            ..budget
+        };
+        assert!(
+            budget
+                .check_add("heart.svg", 4096, false)
+                .unwrap()
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,329 @@
+// Output formatting for saved SVG markup.
+//
+// `--svg-format minified` puts the whole document on one line;
+// `--svg-format pretty` puts every element on its own line, indented by
+// nesting depth. Without either, markup is written the way the source had
+// it. Whitespace inside text nodes is trimmed either way, which is harmless
+// for icons but would matter for `<text>`-heavy illustrations.
+//
+// Output is deterministic: attributes keep their source order (preset
+// attributes such as `{...props}` always land last on the root), line
+// endings are normalized to `line_endings`, and every file ends with exactly
+// one newline, so re-running an add or `sync` never produces a diff.
+
+use clap::ValueEnum;
+
+pub const DEFAULT_SVG_INDENT: usize = 2;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SvgFormat {
+    #[value(name = "minified")]
+    Minified,
+    #[value(name = "pretty")]
+    Pretty,
+}
+
+impl SvgFormat {
+    pub const NAMES: &'static [&'static str] = &["minified", "pretty"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "minified" => Some(SvgFormat::Minified),
+            "pretty" => Some(SvgFormat::Pretty),
+            _ => None,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    #[value(name = "lf")]
+    Lf,
+    #[value(name = "crlf")]
+    Crlf,
+}
+
+impl LineEnding {
+    pub const NAMES: &'static [&'static str] = &["lf", "crlf"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::Crlf),
+            _ => None,
+        }
+    }
+
+    /// Rewrites every line break in `text` as this ending.
+    pub fn normalize(&self, text: &str) -> String {
+        let lf = text.replace("\r\n", "\n");
+        match self {
+            LineEnding::Lf => lf,
+            LineEnding::Crlf => lf.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// How an icon's markup is cleaned up and laid out before it's written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOutput {
+    /// Strip comments, prologs and DOCTYPEs (`sanitize_svg`).
+    pub sanitize: bool,
+    pub format: Option<SvgFormat>,
+    /// Spaces per nesting level for `pretty`.
+    pub indent: usize,
+    pub line_ending: LineEnding,
+}
+
+impl Default for SvgOutput {
+    fn default() -> Self {
+        Self {
+            sanitize: true,
+            format: None,
+            indent: DEFAULT_SVG_INDENT,
+            line_ending: LineEnding::Lf,
+        }
+    }
+}
+
+impl SvgOutput {
+    pub fn apply(&self, svg: &str) -> String {
+        match self.format {
+            None => svg.to_string(),
+            Some(SvgFormat::Minified) => minify(svg),
+            Some(SvgFormat::Pretty) => pretty(svg, self.indent),
+        }
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.format == Some(SvgFormat::Pretty)
+    }
+
+    /// The bytes that go to disk: configured line endings and exactly one
+    /// final newline.
+    pub fn finish(&self, content: &str) -> String {
+        let mut content = content.trim_end().to_string();
+        content.push('\n');
+        self.line_ending.normalize(&content)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(String),
+    Close(String),
+    /// Self-closing elements, comments, CDATA, prologs and DOCTYPEs.
+    Standalone(String),
+    Text(&'a str),
+}
+
+fn tokenize(svg: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = svg;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut tokens, rest);
+            break;
+        };
+        push_text(&mut tokens, &rest[..start]);
+        rest = &rest[start..];
+
+        let verbatim_end = if rest.starts_with("<!--") {
+            Some(rest.find("-->").map(|end| end + 3))
+        } else if rest.starts_with("<![CDATA[") {
+            Some(rest.find("]]>").map(|end| end + 3))
+        } else {
+            None
+        };
+        if let Some(end) = verbatim_end {
+            let end = end.unwrap_or(rest.len());
+            tokens.push(Token::Standalone(rest[..end].to_string()));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = tag_end(rest).map(|end| end + 1).unwrap_or(rest.len());
+        let tag = collapse_whitespace(&rest[..end]);
+        rest = &rest[end..];
+        if let Some(name) = tag.strip_prefix("</") {
+            tokens.push(Token::Close(name.trim_end_matches('>').trim().to_string()));
+        } else if tag.ends_with("/>") || tag.starts_with("<!") || tag.starts_with("<?") {
+            tokens.push(Token::Standalone(tag));
+        } else {
+            tokens.push(Token::Open(tag));
+        }
+    }
+    tokens
+}
+
+fn push_text<'a>(tokens: &mut Vec<Token<'a>>, text: &'a str) {
+    let text = text.trim();
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+}
+
+/// Index of the `>` closing the tag at the start of `tag`, skipping quoted
+/// attribute values.
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, ch) in tag.char_indices() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Folds runs of whitespace outside attribute values into single spaces, so
+/// attributes wrapped over several lines end up on one.
+fn collapse_whitespace(tag: &str) -> String {
+    let mut out = String::with_capacity(tag.len());
+    let mut quote = None;
+    let mut pending_space = false;
+    for ch in tag.chars() {
+        if quote.is_none() && ch.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space {
+            if !matches!(ch, '>') && !out.is_empty() {
+                out.push(' ');
+            }
+            pending_space = false;
+        }
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            _ => {}
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn close_tag(name: &str) -> String {
+    format!("</{name}>")
+}
+
+fn element_name(open: &str) -> &str {
+    open.trim_start_matches('<')
+        .split(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/')
+        .next()
+        .unwrap_or("")
+}
+
+/// The whole document on one line.
+pub fn minify(svg: &str) -> String {
+    tokenize(svg)
+        .into_iter()
+        .map(|token| match token {
+            Token::Open(tag) | Token::Standalone(tag) => tag,
+            Token::Close(name) => close_tag(&name),
+            Token::Text(text) => text.to_string(),
+        })
+        .collect()
+}
+
+/// One element per line, `indent` spaces per level. An element whose only
+/// child is text stays on one line (`<title>Heart</title>`).
+pub fn pretty(svg: &str, indent: usize) -> String {
+    let tokens = tokenize(svg);
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < tokens.len() {
+        let pad = " ".repeat(depth * indent);
+        match &tokens[index] {
+            Token::Open(tag) => {
+                if let (Some(Token::Text(text)), Some(Token::Close(name))) =
+                    (tokens.get(index + 1), tokens.get(index + 2))
+                    && name == element_name(tag)
+                {
+                    lines.push(format!("{pad}{tag}{text}{}", close_tag(name)));
+                    index += 3;
+                    continue;
+                }
+                if let Some(Token::Close(name)) = tokens.get(index + 1)
+                    && name == element_name(tag)
+                {
+                    lines.push(format!("{pad}{tag}{}", close_tag(name)));
+                    index += 2;
+                    continue;
+                }
+                lines.push(format!("{pad}{tag}"));
+                depth += 1;
+            }
+            Token::Close(name) => {
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{}", " ".repeat(depth * indent), close_tag(name)));
+            }
+            Token::Standalone(tag) => lines.push(format!("{pad}{tag}")),
+            Token::Text(text) => lines.push(format!("{pad}{text}")),
+        }
+        index += 1;
+    }
+    lines.join("\n")
+}
+
+/// Prefixes every line after the first with `base`, for markup dropped into
+/// a template at an indented placeholder.
+pub fn indent_continuation(markup: &str, base: &str) -> String {
+    markup.replace('\n', &format!("\n{base}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\"\n     viewBox=\"0 0 24 24\">\n  <title>Heart</title>\n\n  <g fill=\"none\"><path d=\"M0 0h24\"/>\n<circle cx=\"12\" cy=\"12\" r=\"4\"></circle></g>\n</svg>\n";
+
+    #[test]
+    fn minifies_onto_one_line() {
+        assert_eq!(
+            minify(SOURCE),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><title>Heart</title><g fill=\"none\"><path d=\"M0 0h24\"/><circle cx=\"12\" cy=\"12\" r=\"4\"></circle></g></svg>"
+        );
+    }
+
+    #[test]
+    fn pretty_prints_by_depth_with_configurable_indent() {
+        assert_eq!(
+            pretty(SOURCE, 2),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\n  <title>Heart</title>\n  <g fill=\"none\">\n    <path d=\"M0 0h24\"/>\n    <circle cx=\"12\" cy=\"12\" r=\"4\"></circle>\n  </g>\n</svg>"
+        );
+        assert!(pretty(SOURCE, 4).contains("\n        <path d=\"M0 0h24\"/>\n"));
+        // Pretty output is a fixed point, and so is minified output.
+        assert_eq!(pretty(&pretty(SOURCE, 2), 2), pretty(SOURCE, 2));
+        assert_eq!(minify(&pretty(SOURCE, 2)), minify(SOURCE));
+    }
+
+    #[test]
+    fn finish_normalizes_line_endings_and_final_newline() {
+        let lf = SvgOutput::default();
+        assert_eq!(lf.finish("<svg>\r\n</svg>\n\n\n"), "<svg>\n</svg>\n");
+        assert_eq!(lf.finish(&lf.finish("<svg/>")), "<svg/>\n");
+
+        let crlf = SvgOutput {
+            line_ending: LineEnding::Crlf,
+            ..SvgOutput::default()
+        };
+        let once = crlf.finish("<svg>\n  <path/>\r\n</svg>");
+        assert_eq!(once, "<svg>\r\n  <path/>\r\n</svg>\r\n");
+        assert_eq!(crlf.finish(&once), once);
+    }
+
+    #[test]
+    fn keeps_quoted_whitespace_and_jsx_spreads() {
+        let svg = "<svg {...props} class=\"a  b\"><path d=\"M0 0\n L1 1\"/></svg>";
+        assert_eq!(
+            minify(svg),
+            "<svg {...props} class=\"a  b\"><path d=\"M0 0\n L1 1\"/></svg>"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+// SVG rendering, through the `resvg` crate.
+//
+// `render_pixmap` is the one renderer: raster exports (`iconmate add
+// --raster`, app icons) encode its pixels as PNG, and terminal previews
+// threshold them into a 1-bit bitmap that renders as braille characters, or
+// as sixel graphics for `iconmate preview --sixel`. Text isn't rendered; icon
+// sets outline their glyphs.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use resvg::{tiny_skia, usvg};
+
+/// A 1-bit raster, row-major.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = true;
+        }
+    }
+
+    pub fn is_blank(&self) -> bool {
+        !self.pixels.iter().any(|pixel| *pixel)
+    }
+
+    /// One braille character per 2x4 pixel block, so a `w`x`h` bitmap prints
+    /// as `w/2` columns by `h/4` rows.
+    pub fn to_braille_lines(&self) -> Vec<String> {
+        // Dot bits of U+2800, indexed by [y][x] within the block.
+        const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        (0..self.height.div_ceil(4))
+            .map(|row| {
+                (0..self.width.div_ceil(2))
+                    .map(|col| {
+                        let mut bits = 0;
+                        for (dy, dots) in DOTS.iter().enumerate() {
+                            for (dx, dot) in dots.iter().enumerate() {
+                                if self.get(col * 2 + dx, row * 4 + dy) {
+                                    bits |= dot;
+                                }
+                            }
+                        }
+                        char::from_u32(0x2800 + bits).unwrap_or(' ')
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Bitmap {
+    /// A sixel image (DEC graphics, `ESC P ... ESC \\`) with set pixels in
+    /// `foreground` (light gray by default) and the rest in `background`, or
+    /// transparent. Each band of six rows is one line of sixel characters,
+    /// run-length encoded.
+    pub fn to_sixel(&self, foreground: Option<[u8; 3]>, background: Option<[u8; 3]>) -> String {
+        let percent = |rgb: [u8; 3]| rgb.map(|channel| (u32::from(channel) * 100 + 127) / 255);
+        let [r, g, b] = foreground.map(percent).unwrap_or([80, 80, 80]);
+        let mut out = format!(
+            "\x1bP0;1;0q\"1;1;{};{}#1;2;{r};{g};{b}",
+            self.width, self.height
+        );
+        if let Some(background) = background {
+            let [r, g, b] = percent(background);
+            out.push_str(&format!("#2;2;{r};{g};{b}"));
+        } else {
+            out.push_str("#1");
+        }
+        for band in 0..self.height.div_ceil(6) {
+            let rows = (band * 6 + 6).min(self.height) - band * 6;
+            let bits: Vec<u8> = (0..self.width)
+                .map(|x| {
+                    (0..6)
+                        .filter(|dy| self.get(x, band * 6 + dy))
+                        .fold(0u8, |bits, dy| bits | 1 << dy)
+                })
+                .collect();
+            if background.is_some() {
+                let unset: Vec<u8> = bits.iter().map(|bits| !bits & ((1 << rows) - 1)).collect();
+                out.push_str("#2");
+                push_sixel_run(&mut out, &unset);
+                out.push_str("$#1");
+            }
+            push_sixel_run(&mut out, &bits);
+            out.push('-');
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+}
+
+/// One band's sixel characters, runs of four or more as `!<count><char>`.
+fn push_sixel_run(out: &mut String, bits: &[u8]) {
+    let sixels: Vec<char> = bits.iter().map(|bits| char::from(63 + bits)).collect();
+    let mut x = 0;
+    while x < sixels.len() {
+        let run = sixels[x..].iter().take_while(|c| **c == sixels[x]).count();
+        if run > 3 {
+            out.push_str(&format!("!{run}{}", sixels[x]));
+        } else {
+            out.extend(std::iter::repeat_n(sixels[x], run));
+        }
+        x += run;
+    }
+}
+
+/// Renders `svg` into a transparent `width`x`height` pixmap, fitting the
+/// image inside it with the aspect ratio kept. Relative `href`s resolve
+/// against `resources_dir`.
+pub fn render_pixmap(
+    svg: &str,
+    width: u32,
+    height: u32,
+    resources_dir: Option<&Path>,
+) -> anyhow::Result<tiny_skia::Pixmap> {
+    let options = usvg::Options {
+        resources_dir: resources_dir.map(Path::to_path_buf),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&with_svg_namespace(svg), &options)?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| anyhow::anyhow!("Can't render an SVG at {width}x{height}"))?;
+    let size = tree.size();
+    let scale = (width as f32 / size.width()).min(height as f32 / size.height());
+    let transform = tiny_skia::Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        scale,
+        (width as f32 - size.width() * scale) / 2.0,
+        (height as f32 - size.height() * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Rasterizes `svg` into a `width`x`height` bitmap: a pixel is set when the
+/// rendered image covers at least half of it.
+pub fn rasterize(svg: &str, width: usize, height: usize) -> anyhow::Result<Bitmap> {
+    let pixmap = render_pixmap(svg, width as u32, height as u32, None)?;
+    let mut bitmap = Bitmap::new(width, height);
+    for (index, pixel) in pixmap.pixels().iter().enumerate() {
+        if pixel.alpha() >= 128 {
+            bitmap.set(index % width, index / width);
+        }
+    }
+    Ok(bitmap)
+}
+
+/// What the Add popup shows in place of a pasted SVG.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgSummary {
+    pub view_box: (f64, f64, f64, f64),
+    pub shapes: usize,
+}
+
+/// Checks that `svg` is one well-formed `<svg>` document and summarizes it.
+pub fn summarize(svg: &str) -> anyhow::Result<SvgSummary> {
+    let svg = with_svg_namespace(svg);
+    let document = usvg::roxmltree::Document::parse(&svg)?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        anyhow::bail!("Expected <svg>, found <{}>", root.tag_name().name());
+    }
+    let tree = usvg::Tree::from_xmltree(&document, &usvg::Options::default())?;
+    let view_box = root
+        .attribute("viewBox")
+        .map(|raw| {
+            raw.split(|ch: char| ch.is_whitespace() || ch == ',')
+                .filter(|part| !part.is_empty())
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .and_then(Result::ok)
+        .and_then(|numbers| <[f64; 4]>::try_from(numbers).ok())
+        .map(|[x, y, width, height]| (x, y, width, height))
+        .unwrap_or_else(|| {
+            let size = tree.size();
+            (0.0, 0.0, f64::from(size.width()), f64::from(size.height()))
+        });
+    Ok(SvgSummary {
+        view_box,
+        shapes: count_paths(tree.root()),
+    })
+}
+
+/// `svg` with the SVG namespace declared on its root: `usvg` needs it, and
+/// hand-written or JSX-converted markup often leaves it out.
+fn with_svg_namespace(svg: &str) -> Cow<'_, str> {
+    let Ok(document) = usvg::roxmltree::Document::parse(svg) else {
+        return Cow::Borrowed(svg);
+    };
+    let root = document.root_element();
+    if root.tag_name().namespace().is_some() {
+        return Cow::Borrowed(svg);
+    }
+    let at = root.range().start + 1 + root.tag_name().name().len();
+    Cow::Owned(format!(
+        "{} xmlns=\"http://www.w3.org/2000/svg\"{}",
+        &svg[..at],
+        &svg[at..]
+    ))
+}
+
+fn count_paths(group: &usvg::Group) -> usize {
+    group
+        .children()
+        .iter()
+        .map(|node| match node {
+            usvg::Node::Group(group) => count_paths(group),
+            usvg::Node::Path(_) => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ascii(bitmap: &Bitmap) -> Vec<String> {
+        (0..bitmap.height)
+            .map(|y| {
+                (0..bitmap.width)
+                    .map(|x| if bitmap.get(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fills_paths_and_respects_evenodd_holes() {
+        let svg = r#"<svg viewBox="0 0 8 8"><path fill-rule="evenodd" d="M0 0h8v8H0z M2 2h4v4H2z"/></svg>"#;
+        let bitmap = rasterize(svg, 8, 8).unwrap();
+        assert_eq!(
+            ascii(&bitmap),
+            [
+                "########", "########", "##....##", "##....##", "##....##", "##....##", "########",
+                "########"
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_sixel_bands_with_run_lengths() {
+        let svg = r#"<svg viewBox="0 0 8 8"><path d="M0 0h8v2H0z"/></svg>"#;
+        let bitmap = rasterize(svg, 8, 8).unwrap();
+        assert_eq!(
+            bitmap.to_sixel(None, None),
+            "\x1bP0;1;0q\"1;1;8;8#1;2;80;80;80#1!8B-!8?-\x1b\\"
+        );
+        assert_eq!(
+            bitmap.to_sixel(Some([255, 255, 255]), Some([0, 0, 0])),
+            "\x1bP0;1;0q\"1;1;8;8#1;2;100;100;100#2;2;0;0;0#2!8{$#1!8B-#2!8B$#1!8?-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn summarize_accepts_one_svg_and_rejects_broken_markup() {
+        let svg = "<?xml version=\"1.0\"?>\n<!-- icon -->\n<svg viewBox=\"0 0 24 24\">\n  <g><path d=\"M0 0h4v4z\"/></g>\n</svg>\n";
+        let summary = summarize(svg).unwrap();
+        assert_eq!(summary.view_box, (0.0, 0.0, 24.0, 24.0));
+        assert_eq!(summary.shapes, 1);
+
+        for broken in [
+            "<svg><g></svg>",
+            "<svg viewBox=\"0 0 1 1\">",
+            "<svg></svg><svg></svg>",
+            "junk <svg></svg>",
+        ] {
+            assert!(summarize(broken).is_err(), "{broken}");
+        }
+        let error = summarize("<path d=\"M0 0\"/>").unwrap_err().to_string();
+        assert!(error.contains("Expected <svg>"), "{error}");
+    }
+
+    #[test]
+    fn strokes_inherit_from_svg_and_skip_defs() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 8 8" fill="none" stroke="currentColor" stroke-width="2">
+            <defs><rect width="8" height="8" fill="black"/></defs>
+            <!-- a comment with <tags> -->
+            <line x1="0" y1="4" x2="8" y2="4"/>
+        </svg>"#;
+        let bitmap = rasterize(svg, 8, 8).unwrap();
+        let rows = ascii(&bitmap);
+        assert_eq!(rows[3], "########");
+        assert_eq!(rows[4], "########");
+        assert_eq!(rows[0], "........");
+        assert_eq!(rows[7], "........");
+    }
+
+    #[test]
+    fn applies_transforms_and_fits_the_view_box() {
+        let svg = r#"<svg viewBox="0 0 4 4"><g transform="translate(2 2)"><rect width="2" height="2"/></g></svg>"#;
+        let rows = ascii(&rasterize(svg, 4, 4).unwrap());
+        assert_eq!(rows, ["....", "....", "..##", "..##"]);
+
+        let wide = r#"<svg xmlns="http://www.w3.org/2000/svg" width="1em" height="1em" viewBox="0 0 8 4"><rect width="8" height="4"/></svg>"#;
+        let rows = ascii(&rasterize(wide, 4, 4).unwrap());
+        assert_eq!(rows, ["....", "####", "####", "...."]);
+    }
+
+    #[test]
+    fn braille_packs_two_by_four_blocks() {
+        let svg = r#"<svg viewBox="0 0 4 4"><rect width="2" height="4"/></svg>"#;
+        let lines = rasterize(svg, 4, 4).unwrap().to_braille_lines();
+        assert_eq!(lines, ["⣿⠀"]);
+        assert!(rasterize("<div></div>", 4, 4).is_err());
+    }
+}
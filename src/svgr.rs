@@ -0,0 +1,191 @@
+// SVGR-compatible React components (`--svgr-compat`, `svgr_compat` local
+// config key).
+//
+// Teams moving off SVGR want regenerated icons to match what it wrote, so
+// this mirrors `svgr --typescript --ref --memo --title-prop`: a
+// `Svg<FileStem>` arrow component taking `title`/`titleId`, wrapped in
+// `forwardRef` and `memo`, with JSX attribute names (`strokeWidth`,
+// `className`), numeric attributes as expressions (`width={24}`) and
+// `style` strings as objects. Quotes and semicolons follow SVGR's defaults
+// unless `code_style` says otherwise.
+
+use regex::{Captures, Regex};
+
+use crate::template;
+
+/// Attributes `_icon_source_to_svg` appends to the root `<svg>`, in SVGR's order.
+pub const ROOT_ATTRIBUTES: &str = "ref={ref} aria-labelledby={titleId} {...props}";
+
+/// SVGR names components after the file: `mdi_heart.tsx` -> `SvgMdiHeart`.
+pub fn component_name(file_stem: &str) -> String {
+    let pascal = template::apply_filter("pascal", file_stem).unwrap_or_default();
+    format!("Svg{pascal}")
+}
+
+/// Rewrites SVG markup (root attributes already appended) into SVGR's JSX.
+pub fn svg_to_jsx(svg: &str) -> String {
+    let tag_re = Regex::new(r"<[A-Za-z][^>]*>").expect("valid SVG tag regex");
+    let jsx = tag_re
+        .replace_all(svg, |captures: &Captures<'_>| jsx_tag(&captures[0]))
+        .to_string();
+    with_title_prop(&jsx)
+}
+
+fn jsx_tag(tag: &str) -> String {
+    let attribute_re = Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*"([^"]*)""#)
+        .expect("valid SVG attribute regex");
+    attribute_re
+        .replace_all(tag, |captures: &Captures<'_>| {
+            let name = jsx_attribute_name(&captures[1]);
+            let value = &captures[2];
+            if name == "style" {
+                format!("style={{{}}}", style_object(value))
+            } else if is_number(value) {
+                format!("{name}={{{value}}}")
+            } else {
+                format!("{name}=\"{value}\"")
+            }
+        })
+        .to_string()
+}
+
+/// `stroke-width` -> `strokeWidth`, `xlink:href` -> `xlinkHref`; `data-*`
+/// and `aria-*` stay as they are, like React expects.
+pub fn jsx_attribute_name(name: &str) -> String {
+    if name == "class" {
+        return "className".to_string();
+    }
+    if name.starts_with("data-") || name.starts_with("aria-") {
+        return name.to_string();
+    }
+    camel_case(name, &['-', ':'])
+}
+
+fn camel_case(value: &str, separators: &[char]) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut upper = false;
+    for ch in value.chars() {
+        if separators.contains(&ch) {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(ch.to_uppercase());
+            upper = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn is_number(value: &str) -> bool {
+    !value.is_empty()
+        && !value.ends_with('.')
+        && value.parse::<f64>().is_ok_and(f64::is_finite)
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || ch == '.' || ch == '-')
+}
+
+/// `fill:red;stroke-width:2` -> `{ fill: "red", strokeWidth: 2 }`.
+fn style_object(style: &str) -> String {
+    let declarations = style
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(property, value)| (property.trim(), value.trim()))
+        .filter(|(property, value)| !property.is_empty() && !value.is_empty())
+        .map(|(property, value)| {
+            let property = if property.starts_with("--") {
+                format!("\"{property}\"")
+            } else {
+                camel_case(property, &['-'])
+            };
+            if is_number(value) {
+                format!("{property}: {value}")
+            } else {
+                format!("{property}: \"{}\"", value.replace('"', "'"))
+            }
+        })
+        .collect::<Vec<_>>();
+    if declarations.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", declarations.join(", "))
+    }
+}
+
+/// Puts SVGR's `title` expression first inside the root, replacing a
+/// `<title>` the source already had (its text becomes the fallback).
+fn with_title_prop(jsx: &str) -> String {
+    let title_re =
+        Regex::new(r"(?s)\s*<title(?:\s[^>]*)?>(.*?)</title>").expect("valid title regex");
+    let (jsx, existing) = match title_re.captures(jsx) {
+        Some(captures) => {
+            let text = captures[1].trim().to_string();
+            (title_re.replace(jsx, "").to_string(), Some(text))
+        }
+        None => (jsx.to_string(), None),
+    };
+    let expression = match existing {
+        Some(text) => format!(
+            "{{title === undefined ? <title id={{titleId}}>{text}</title> : title ? <title id={{titleId}}>{{title}}</title> : null}}"
+        ),
+        None => "{title ? <title id={titleId}>{title}</title> : null}".to_string(),
+    };
+
+    let Some(root_start) = jsx.find("<svg") else {
+        return jsx;
+    };
+    let Some(root_end) = jsx[root_start..].find('>').map(|end| root_start + end + 1) else {
+        return jsx;
+    };
+    if jsx[..root_end].ends_with("/>") {
+        let open = jsx[..root_end - 2].trim_end();
+        return format!("{open}>{expression}</svg>{}", &jsx[root_end..]);
+    }
+    // Pretty output: the expression gets its own line at the children's depth.
+    let rest = &jsx[root_end..];
+    let separator = match rest.strip_prefix('\n') {
+        Some(children) => {
+            let depth = children.len() - children.trim_start_matches(' ').len();
+            format!("\n{}", " ".repeat(depth))
+        }
+        None => String::new(),
+    };
+    format!("{}{separator}{expression}{rest}", &jsx[..root_end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_components_after_the_file() {
+        assert_eq!(component_name("mdi_heart"), "SvgMdiHeart");
+        assert_eq!(component_name("arrow-left"), "SvgArrowLeft");
+    }
+
+    #[test]
+    fn converts_markup_to_svgr_jsx() {
+        let svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\" class=\"icon\" {ROOT_ATTRIBUTES}><path stroke-width=\"1.5\" xlink:href=\"#a\" data-id=\"x\" style=\"fill:red;stroke-linecap:round\" d=\"M0 0h24\"/></svg>"
+        );
+        assert_eq!(
+            svg_to_jsx(&svg),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width={24} height={24} className=\"icon\" ref={ref} aria-labelledby={titleId} {...props}>{title ? <title id={titleId}>{title}</title> : null}<path strokeWidth={1.5} xlinkHref=\"#a\" data-id=\"x\" style={{ fill: \"red\", strokeLinecap: \"round\" }} d=\"M0 0h24\"/></svg>"
+        );
+    }
+
+    #[test]
+    fn keeps_an_existing_title_as_the_fallback() {
+        let jsx =
+            svg_to_jsx("<svg {...props}>\n  <title>Heart</title>\n  <path d=\"M0 0\"/>\n</svg>");
+        assert_eq!(
+            jsx,
+            "<svg {...props}>\n  {title === undefined ? <title id={titleId}>Heart</title> : title ? <title id={titleId}>{title}</title> : null}\n  <path d=\"M0 0\"/>\n</svg>"
+        );
+        assert_eq!(
+            svg_to_jsx("<svg {...props}/>"),
+            "<svg {...props}>{title ? <title id={titleId}>{title}</title> : null}</svg>"
+        );
+    }
+}
@@ -14,8 +14,15 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
+use crate::barrel::BarrelFrame;
+use crate::code_style::CodeStyle;
 use crate::flutter;
-use crate::utils::{IconEntry, parse_export_line_ts, render_js_export_line};
+use crate::lazy_export::LazyLoader;
+use crate::parallel;
+use crate::svg_format::LineEnding;
+use crate::utils::{
+    AliasStyle, IconEntry, ImportQuery, parse_export_line_ts, render_js_export_line,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Addition {
@@ -37,7 +44,10 @@ fn js_barrel_disk_keys(value: &str) -> Vec<String> {
         .to_string();
 
     let mut keys = vec![basename.clone()];
-    if let Some(stripped) = basename.strip_suffix(".tsx") {
+    if let Some(stripped) = basename
+        .strip_suffix(".tsx")
+        .or_else(|| basename.strip_suffix(".ts"))
+    {
         keys.push(stripped.to_string());
     }
     keys
@@ -82,11 +92,27 @@ pub struct SyncContext<'a> {
     /// User-provided identifier overrides. Keyed by the inferred identifier,
     /// value is the replacement to use instead.
     pub renames: &'a HashMap<String, String>,
+    /// How inferred aliases become export names (JS presets).
+    pub alias_style: &'a AliasStyle,
+    /// Quotes and semicolons for rendered JS exports.
+    pub code_style: &'a CodeStyle,
+    /// Bundler query for `.svg` imports (`import_query`).
+    pub import_query: Option<ImportQuery>,
+    /// Lazy exports for additions (`lazy_exports`), when the preset has a loader.
+    pub lazy: Option<LazyLoader>,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ApplyOptions {
     pub prune: bool,
+    /// Line endings for the rewritten JS barrel (`line_endings`).
+    pub line_ending: LineEnding,
+    /// Header and footer for a JS barrel this apply creates.
+    pub barrel_frame: BarrelFrame,
+    /// Create `icon-names.d.ts` if it isn't there yet.
+    pub icon_names_dts: bool,
+    /// Create `icon-registry.ts` and `Icon.tsx` if they aren't there yet.
+    pub icon_registry: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -101,8 +127,25 @@ pub fn find_svg_files(folder: &Path) -> anyhow::Result<Vec<String>> {
     find_files_with_extensions(folder, &["svg"])
 }
 
-fn find_js_icon_files(folder: &Path) -> anyhow::Result<Vec<String>> {
-    find_files_with_extensions(folder, &["svg", "tsx", "svelte", "vue"])
+pub fn find_js_icon_files(folder: &Path, preset: &str) -> anyhow::Result<Vec<String>> {
+    // The `<Icon>` component that `icon_registry` writes isn't an icon.
+    let is_icon = |name: &String| name != crate::icon_registry::ICON_COMPONENT_FILE;
+    if preset != "datauri" {
+        let mut files = find_files_with_extensions(folder, &["svg", "tsx", "svelte", "vue"])?;
+        files.retain(is_icon);
+        return Ok(files);
+    }
+    // `.ts` files are only icons for the datauri preset; the barrel, the
+    // generated bundle and registry, and declaration files never are.
+    let mut files = find_files_with_extensions(folder, &["svg", "tsx", "ts", "svelte", "vue"])?;
+    files.retain(|name| {
+        is_icon(name)
+            && name != "index.ts"
+            && name != crate::datauri::DATAURI_BUNDLE
+            && name != crate::icon_registry::REGISTRY_FILE
+            && !name.ends_with(".d.ts")
+    });
+    Ok(files)
 }
 
 fn find_files_with_extensions(folder: &Path, extensions: &[&str]) -> anyhow::Result<Vec<String>> {
@@ -168,9 +211,20 @@ fn render_js_addition(
     index_contents: Option<&str>,
     filename: &str,
     alias: &str,
+    alias_style: &AliasStyle,
+    code_style: &CodeStyle,
+    import_query: Option<ImportQuery>,
 ) -> Option<(String, String)> {
     let (stem, ext) = stem_of(filename);
-    let rendered = render_js_export_line(index_contents, folder, alias, stem, ext);
+    let rendered = render_js_export_line(
+        index_contents,
+        folder,
+        alias,
+        alias_style,
+        code_style,
+        stem,
+        &ImportQuery::import_ext(import_query, ext),
+    );
     let entry = parse_export_line_ts(rendered.trim_end_matches(';'))
         .or_else(|| parse_export_line_ts(&rendered))?;
     Some((rendered, entry.name))
@@ -200,25 +254,12 @@ fn compute_js_sync_plan(ctx: &SyncContext) -> anyhow::Result<SyncPlan> {
         None
     };
 
-    let mut entries: Vec<IconEntry> = if let Some(contents) = barrel_contents.as_deref() {
-        let mut out = Vec::new();
-        for line in contents.lines() {
-            for stmt in line.split(';') {
-                let stmt = stmt.trim();
-                if stmt.is_empty() {
-                    continue;
-                }
-                if let Some(entry) = parse_export_line_ts(stmt) {
-                    out.push(entry);
-                }
-            }
-        }
-        out
-    } else {
-        Vec::new()
-    };
+    let mut entries: Vec<IconEntry> = barrel_contents
+        .as_deref()
+        .map(crate::utils::parse_index_entries)
+        .unwrap_or_default();
 
-    let files_on_disk = find_js_icon_files(ctx.folder)?;
+    let files_on_disk = find_js_icon_files(ctx.folder, ctx.preset)?;
     let disk_set: HashSet<String> = files_on_disk
         .iter()
         .flat_map(|filename| js_barrel_disk_keys(filename))
@@ -238,24 +279,42 @@ fn compute_js_sync_plan(ctx: &SyncContext) -> anyhow::Result<SyncPlan> {
     let mut additions = Vec::new();
     let mut collisions = Vec::new();
 
-    for filename in &files_on_disk {
-        if js_barrel_disk_keys(filename)
-            .iter()
-            .any(|key| barrel_paths.contains_key(key))
-        {
-            continue;
-        }
+    let orphan_files: Vec<&String> = files_on_disk
+        .iter()
+        .filter(|filename| {
+            !js_barrel_disk_keys(filename)
+                .iter()
+                .any(|key| barrel_paths.contains_key(key))
+        })
+        .collect();
+    // Rendering is the per-file cost on big folders; collisions depend on
+    // earlier additions, so they're resolved in order afterwards.
+    let bar = crate::progress::Bar::counted("Indexing icons", orphan_files.len());
+    let rendered_orphans = parallel::map(&orphan_files, |filename| {
+        bar.inc();
         let (stem, _) = stem_of(filename);
         let inferred_alias = pascal_case(stem);
         if inferred_alias.is_empty() {
-            continue;
+            return None;
         }
-        let Some((rendered, full_name)) = render_js_addition(
+        render_js_addition(
             ctx.folder,
             barrel_contents.as_deref(),
             filename,
             &inferred_alias,
-        ) else {
+            ctx.alias_style,
+            ctx.code_style,
+            ctx.import_query,
+        )
+        .map(|(line, name)| match ctx.lazy {
+            Some(loader) => (loader.wrap(&line), name),
+            None => (line, name),
+        })
+    });
+    bar.finish();
+
+    for (filename, rendered) in orphan_files.into_iter().zip(rendered_orphans) {
+        let Some((rendered, full_name)) = rendered else {
             continue;
         };
 
@@ -456,15 +515,17 @@ fn apply_js(
     }
 
     if !plan.additions.is_empty() {
-        if !contents.is_empty() && !contents.ends_with('\n') {
-            contents.push('\n');
-        }
+        let mut exports = String::new();
         for addition in &plan.additions {
             if let Some(line) = &addition.rendered_line {
-                contents.push_str(line.trim_end());
-                contents.push('\n');
+                exports.push_str(line.trim_end());
+                exports.push('\n');
             }
         }
+        contents = options.barrel_frame.append(&contents, &exports);
+        if let Some(loader) = ctx.lazy {
+            contents = loader.ensure_import(&contents, &exports);
+        }
         summary.added = plan.additions.len();
     }
 
@@ -472,8 +533,17 @@ fn apply_js(
         if let Some(parent) = barrel_path.parent() {
             fs::create_dir_all(parent).ok();
         }
-        fs::write(&barrel_path, contents)?;
+        fs::write(&barrel_path, options.line_ending.normalize(&contents))?;
+        if ctx.preset == "css" {
+            crate::css_mask::write_mask_stylesheet(ctx.folder)?;
+        }
+        if ctx.preset == "datauri" {
+            crate::datauri::write_datauri_bundle(ctx.folder)?;
+        }
     }
+    crate::css_background::refresh_background_stylesheet_if_present(ctx.folder)?;
+    crate::icon_names::refresh_icon_names(ctx.folder, options.icon_names_dts)?;
+    crate::icon_registry::refresh_icon_registry(ctx.folder, options.icon_registry)?;
 
     Ok(summary)
 }
@@ -484,33 +554,10 @@ fn remove_js_exports(contents: &str, to_remove: &[IconEntry]) -> String {
         .map(|e| (e.name.clone(), normalize_js_path(&e.file_path)))
         .collect();
 
-    let mut kept: Vec<String> = Vec::new();
-    for line in contents.lines() {
-        let mut parsed_any = false;
-        for stmt in line.split(';') {
-            let stmt = stmt.trim();
-            if stmt.is_empty() {
-                continue;
-            }
-            let Some(entry) = parse_export_line_ts(stmt) else {
-                continue;
-            };
-            parsed_any = true;
-            let key = (entry.name, normalize_js_path(&entry.file_path));
-            if remove_set.contains(&key) {
-                continue;
-            }
-            kept.push(format!("{stmt};"));
-        }
-        if !parsed_any {
-            kept.push(line.to_string());
-        }
-    }
-    let mut out = kept.join("\n");
-    if contents.ends_with('\n') {
-        out.push('\n');
-    }
-    out
+    crate::utils::remove_ts_exports_where(contents, |entry| {
+        remove_set.contains(&(entry.name.clone(), normalize_js_path(&entry.file_path)))
+    })
+    .unwrap_or_else(|| contents.to_string())
 }
 
 fn apply_flutter(
@@ -663,6 +710,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert!(plan.is_clean(), "{}", render_plan_text(&plan, false));
@@ -688,11 +739,45 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert!(plan.is_clean(), "{}", render_plan_text(&plan, false));
     }
 
+    #[test]
+    fn datauri_orphan_ts_module_is_addition_but_barrel_and_bundle_are_not() {
+        let tmp = TempDir::new().unwrap();
+        let folder = tmp.path();
+        write_file(&folder.join("heart.ts"), "export default \"data:\";\n");
+        write_file(&folder.join("icons.ts"), "// GENERATED\n");
+        write_file(&folder.join("index.ts"), "");
+
+        let renames = HashMap::new();
+        let ctx = SyncContext {
+            folder,
+            preset: "datauri",
+            flutter_barrel_file: None,
+            flutter_barrel_class: None,
+            renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
+        };
+        let plan = compute_sync_plan(&ctx).unwrap();
+
+        assert_eq!(plan.additions.len(), 1);
+        assert_eq!(plan.additions[0].file_path, "./heart.ts");
+        assert_eq!(
+            plan.additions[0].rendered_line.as_deref(),
+            Some("export { default as IconHeart } from './heart';")
+        );
+    }
+
     #[test]
     fn js_orphan_tsx_file_is_addition_with_extensionless_import_when_tsconfig_disallows_it() {
         let tmp = TempDir::new().unwrap();
@@ -710,6 +795,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
 
@@ -740,6 +829,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert_eq!(plan.additions.len(), 1);
@@ -748,6 +841,38 @@ mod tests {
         assert!(plan.removals.is_empty());
     }
 
+    #[test]
+    fn js_large_folder_plan_keeps_sorted_order_and_in_plan_collisions() {
+        let tmp = TempDir::new().unwrap();
+        let folder = tmp.path();
+        for n in 0..500 {
+            write_file(&folder.join(format!("icon-{n:04}.svg")), "<svg/>");
+        }
+        write_file(&folder.join("index.ts"), "");
+
+        let renames = HashMap::new();
+        let ctx = SyncContext {
+            folder,
+            preset: "react",
+            flutter_barrel_file: None,
+            flutter_barrel_class: None,
+            renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
+        };
+        let plan = compute_sync_plan(&ctx).unwrap();
+        assert_eq!(plan.additions.len(), 500);
+        let expected: Vec<String> = (0..500).map(|n| format!("IconIcon{n:04}")).collect();
+        let identifiers: Vec<String> = plan
+            .additions
+            .iter()
+            .map(|addition| addition.identifier.clone())
+            .collect();
+        assert_eq!(identifiers, expected);
+    }
+
     #[test]
     fn js_detects_orphan_entry_as_removal() {
         let tmp = TempDir::new().unwrap();
@@ -765,6 +890,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert_eq!(plan.removals.len(), 1);
@@ -790,6 +919,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert_eq!(
@@ -819,6 +952,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert!(
@@ -855,6 +992,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         let summary = apply_sync_plan(&plan, &ctx, ApplyOptions::default()).unwrap();
@@ -882,6 +1023,10 @@ mod tests {
             flutter_barrel_file: None,
             flutter_barrel_class: None,
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
 
@@ -892,7 +1037,15 @@ mod tests {
         assert!(still.contains("IconHeart"));
 
         // With --prune: removed.
-        let summary = apply_sync_plan(&plan, &ctx, ApplyOptions { prune: true }).unwrap();
+        let summary = apply_sync_plan(
+            &plan,
+            &ctx,
+            ApplyOptions {
+                prune: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
         assert_eq!(summary.removed, 1);
         let pruned = fs::read_to_string(folder.join("index.ts")).unwrap();
         assert!(!pruned.contains("IconHeart"));
@@ -923,6 +1076,10 @@ mod tests {
             flutter_barrel_file: Some(&barrel),
             flutter_barrel_class: Some("AppIcons"),
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         assert_eq!(plan.additions.len(), 1);
@@ -954,6 +1111,10 @@ mod tests {
             flutter_barrel_file: Some(&barrel),
             flutter_barrel_class: Some("AppIcons"),
             renames: &renames,
+            alias_style: &AliasStyle::default(),
+            code_style: &CodeStyle::default(),
+            import_query: None,
+            lazy: None,
         };
         let plan = compute_sync_plan(&ctx).unwrap();
         let summary = apply_sync_plan(&plan, &ctx, ApplyOptions::default()).unwrap();
@@ -0,0 +1,161 @@
+// Icon tags (`icon-tags.json` next to `index.ts`).
+//
+// Free-form labels such as `navigation` or `brand` that group icons by what
+// they're for, so a large folder can be filtered by purpose: `#navigation` in
+// the TUI search, `iconmate list --tag navigation` on the command line. The
+// TUI's `t` key edits them. Tags are keyed by export name, which survives a
+// file rename; entries for icons no longer in the barrel are dropped the next
+// time the file is written. The file is plain JSON and meant to be committed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::utils::IconEntry;
+
+pub const TAGS_FILE: &str = "icon-tags.json";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IconTags {
+    by_name: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl IconTags {
+    /// The folder's tags; empty when it has no tags file.
+    pub fn read(folder: &Path) -> anyhow::Result<Self> {
+        let path = folder.join(TAGS_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let by_name = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", path.display()))?;
+        Ok(Self { by_name })
+    }
+
+    /// Writes the tags of the icons in `icons`, dropping the rest. Removes the
+    /// file when nothing is tagged.
+    pub fn write(&self, folder: &Path, icons: &[IconEntry]) -> anyhow::Result<()> {
+        let path = folder.join(TAGS_FILE);
+        let kept = self
+            .by_name
+            .iter()
+            .filter(|(name, tags)| !tags.is_empty() && icons.iter().any(|icon| &icon.name == *name))
+            .collect::<BTreeMap<_, _>>();
+        if kept.is_empty() {
+            if path.is_file() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(());
+        }
+        let mut json = serde_json::to_string_pretty(&kept)?;
+        json.push('\n');
+        std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, name: &str) -> impl Iterator<Item = &str> {
+        self.by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    pub fn has(&self, name: &str, tag: &str) -> bool {
+        self.by_name
+            .get(name)
+            .is_some_and(|tags| tags.contains(&normalize_tag(tag)))
+    }
+
+    /// Replaces an icon's tags; empty or repeated tags are dropped.
+    pub fn set(&mut self, name: &str, tags: impl IntoIterator<Item = String>) {
+        let tags = tags
+            .into_iter()
+            .map(|tag| normalize_tag(&tag))
+            .filter(|tag| !tag.is_empty())
+            .collect::<BTreeSet<_>>();
+        if tags.is_empty() {
+            self.by_name.remove(name);
+        } else {
+            self.by_name.insert(name.to_string(), tags);
+        }
+    }
+
+    /// Every tag in use, sorted.
+    pub fn all(&self) -> BTreeSet<&str> {
+        self.by_name
+            .values()
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// `  Social Media ` -> `social-media`: lowercase, no `#`, `-` between words.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// `nav, social media,#brand` -> `["nav", "social-media", "brand"]`.
+pub fn parse_tag_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(normalize_tag)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon(name: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: format!("./{}.svg", name.to_lowercase()),
+        }
+    }
+
+    #[test]
+    fn round_trips_and_drops_icons_that_are_gone() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut tags = IconTags::default();
+        tags.set("IconHome", parse_tag_list("Navigation, #brand"));
+        tags.set("IconGone", parse_tag_list("navigation"));
+        tags.write(dir.path(), &[icon("IconHome")]).unwrap();
+
+        let read = IconTags::read(dir.path()).unwrap();
+        assert_eq!(
+            read.get("IconHome").collect::<Vec<_>>(),
+            ["brand", "navigation"]
+        );
+        assert!(read.has("IconHome", "#Navigation"));
+        assert!(read.get("IconGone").next().is_none());
+        assert_eq!(
+            read.all().into_iter().collect::<Vec<_>>(),
+            ["brand", "navigation"]
+        );
+
+        let mut cleared = read.clone();
+        cleared.set("IconHome", Vec::new());
+        cleared.write(dir.path(), &[icon("IconHome")]).unwrap();
+        assert!(!dir.path().join(TAGS_FILE).exists());
+        assert_eq!(IconTags::read(dir.path()).unwrap(), IconTags::default());
+    }
+
+    #[test]
+    fn normalizes_tags() {
+        assert_eq!(normalize_tag("  Social Media "), "social-media");
+        assert_eq!(
+            parse_tag_list("nav,, social media,#Brand"),
+            ["nav", "social-media", "brand"]
+        );
+    }
+}
@@ -0,0 +1,320 @@
+// `%var%` template expansion.
+//
+// Every generated string with placeholders goes through here: export lines,
+// default filenames, preset component wrappers, and the user's
+// `svg_viewer_cmd`. A token is `%key%` or `%key|filter|filter...%`.
+// Tokens that don't resolve (unknown key or filter) are left verbatim, so a
+// stray `%` or a shell variable like `%APPDATA%` survives untouched.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const FILTERS: &[&str] = &[
+    "lower", "upper", "kebab", "snake", "constant", "pascal", "camel",
+];
+
+/// `./heart.svg`-style barrel export; `%name%` is the full export name.
+pub const EXPORT_LINE: &str = "export { default as %name% } from './%filename%%ext%';";
+/// Default file stem for an Iconify id (`mdi:heart` -> `mdi_heart`).
+pub const ICONIFY_FILENAME: &str = "%prefix%_%icon%";
+/// Default file stem when there's no Iconify id to go on.
+pub const ALIAS_FILENAME: &str = "%name|lower%";
+
+pub const REACT_COMPONENT: &str = "import type { SVGProps } from 'react';\n\nexport default function Icon(props: SVGProps<SVGSVGElement>) {\n  return (\n%svg%\n  );\n}";
+/// `--svgr-compat`: what `svgr --typescript --ref --memo --title-prop` writes.
+pub const REACT_SVGR_COMPONENT: &str = "import * as React from \"react\";\nimport type { SVGProps } from \"react\";\nimport { Ref, forwardRef, memo } from \"react\";\ninterface SVGRProps {\n  title?: string;\n  titleId?: string;\n}\nconst %component% = (\n  { title, titleId, ...props }: SVGProps<SVGSVGElement> & SVGRProps,\n  ref: Ref<SVGSVGElement>\n) => (\n%svg%\n);\nconst ForwardRef = forwardRef(%component%);\nconst Memo = memo(ForwardRef);\nexport default Memo;";
+pub const SVELTE_COMPONENT: &str = "<script lang=\"ts\">\n  import type { SVGAttributes } from 'svelte/elements';\n\n  let { ...props }: SVGAttributes<SVGSVGElement> = $props();\n</script>\n\n%svg%";
+pub const SOLID_COMPONENT: &str = "import { type JSX } from 'solid-js';\n\nexport default function Icon(props: JSX.SvgSVGAttributes<SVGSVGElement>) {\n  return (%svg%);\n}";
+pub const VUE_COMPONENT: &str = "<template>\n  <template>\n    %svg%\n  </template>\n</template>\n\n<script setup lang=\"ts\">\nimport type { SVGAttributes } from 'vue'\n\ndefineProps<SVGAttributes>()\n</script>";
+
+/// Values available to a template. Besides whatever is set, `%pascal%` and
+/// `%kebab%` derive from `name`, and `%date%` is today's UTC date.
+#[derive(Default)]
+pub struct Vars {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Vars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.values.retain(|(existing, _)| *existing != key);
+        self.values.push((key, value.into()));
+        self
+    }
+
+    /// `prefix` and `icon` from an Iconify id; `icon` alone otherwise.
+    pub fn with_iconify_name(self, iconify_name: &str) -> Self {
+        match iconify_name.split_once(':') {
+            Some((prefix, icon)) => self.with("prefix", prefix).with("icon", icon),
+            None => self.with("prefix", "").with("icon", iconify_name),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        if let Some((_, value)) = self.values.iter().find(|(existing, _)| *existing == key) {
+            return Some(value.clone());
+        }
+        match key {
+            "pascal" | "kebab" => apply_filter(key, &self.get("name")?),
+            "date" => Some(today_utc()),
+            _ => None,
+        }
+    }
+}
+
+pub fn render(template: &str, vars: &Vars) -> String {
+    render_with(template, |token| expand(token, vars))
+}
+
+/// Whether `template` has a well-formed `%key...%` token.
+pub fn references(template: &str, key: &str) -> bool {
+    let mut found = false;
+    render_with(template, |token| {
+        let mut parts = token.split('|').map(str::trim);
+        if parts.next() == Some(key) && parts.all(|filter| FILTERS.contains(&filter)) {
+            found = true;
+        }
+        None
+    });
+    found
+}
+
+/// Problems with the `%var%` tokens of a template that is rendered with
+/// `vars` (plus `%date%`, and `%pascal%` / `%kebab%` when `name` is one of
+/// them): unknown variables and filters. Only lowercase tokens count, so
+/// `%APPDATA%` and a lone `100%` pass.
+pub fn check(template: &str, vars: &[&str]) -> Vec<String> {
+    let mut problems = Vec::new();
+    render_with(template, |token| {
+        let looks_like_token = token.split('|').map(str::trim).all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+        if !looks_like_token {
+            return None;
+        }
+        let mut parts = token.split('|').map(str::trim);
+        let key = parts.next()?;
+        let derived = matches!(key, "pascal" | "kebab") && vars.contains(&"name");
+        if !vars.contains(&key) && !derived && key != "date" {
+            problems.push(format!(
+                "unknown variable %{key}% (available: {})",
+                vars.iter()
+                    .map(|var| format!("%{var}%"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        for filter in parts.filter(|filter| !FILTERS.contains(filter)) {
+            problems.push(format!(
+                "unknown filter '{filter}' in %{token}% (available: {})",
+                FILTERS.join(", ")
+            ));
+        }
+        Some(String::new())
+    });
+    problems
+}
+
+fn render_with(template: &str, mut resolve: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(end) = after.find('%')
+            && let Some(value) = resolve(&after[..end])
+        {
+            out.push_str(&value);
+            rest = &after[end + 1..];
+            continue;
+        }
+        out.push('%');
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn expand(token: &str, vars: &Vars) -> Option<String> {
+    let mut parts = token.split('|').map(str::trim);
+    let mut value = vars.get(parts.next()?)?;
+    for filter in parts {
+        value = apply_filter(filter, &value)?;
+    }
+    Some(value)
+}
+
+pub fn apply_filter(filter: &str, value: &str) -> Option<String> {
+    let words = || words(value).into_iter();
+    let capitalized = |word: String| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => {
+                first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase()
+            }
+            None => String::new(),
+        }
+    };
+    Some(match filter {
+        "lower" => value.to_lowercase(),
+        "upper" => value.to_uppercase(),
+        "kebab" => words()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("-"),
+        "snake" => words()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        "constant" => words()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        "pascal" => words().map(capitalized).collect(),
+        "camel" => words()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    w.to_lowercase()
+                } else {
+                    capitalized(w)
+                }
+            })
+            .collect(),
+        _ => return None,
+    })
+}
+
+/// Splits on non-alphanumerics and lower/digit -> upper transitions:
+/// `chevron-right`, `chevron_right` and `ChevronRight` all give
+/// `["chevron"/"Chevron", "right"/"Right"]`.
+fn words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for chunk in value.split(|c: char| !c.is_alphanumeric()) {
+        let mut current = String::new();
+        let mut prev: Option<char> = None;
+        for ch in chunk.chars() {
+            if let Some(p) = prev
+                && (p.is_lowercase() || p.is_ascii_digit())
+                && ch.is_uppercase()
+            {
+                words.push(std::mem::take(&mut current));
+            }
+            current.push(ch);
+            prev = Some(ch);
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
+
+fn today_utc() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Days since 1970-01-01 -> (year, month, day), proleptic Gregorian.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_variables_and_filters() {
+        let vars = &["name", "file"];
+        assert!(check("code %file% # %name|kebab% %pascal% %date%", vars).is_empty());
+        assert!(check("%APPDATA%\\viewer.exe 100% %Name%", vars).is_empty());
+        assert_eq!(
+            check("open %filename% %name|title%", vars),
+            [
+                "unknown variable %filename% (available: %name%, %file%)",
+                "unknown filter 'title' in %name|title% (available: lower, upper, kebab, snake, constant, pascal, camel)"
+            ]
+        );
+        assert_eq!(check("%kebab%", &["file"]).len(), 1);
+    }
+
+    #[test]
+    fn expands_variables_filters_and_derived_names() {
+        let vars = Vars::new()
+            .with("name", "ChevronRight")
+            .with("folder", "src/icons")
+            .with("date", "2025-01-02")
+            .with_iconify_name("mdi:chevron-right");
+
+        assert_eq!(
+            render(
+                "%prefix%/%icon|pascal% %kebab% %pascal% %name|snake|upper% %folder% %date%",
+                &vars
+            ),
+            "mdi/ChevronRight chevron-right ChevronRight CHEVRON_RIGHT src/icons 2025-01-02"
+        );
+        assert_eq!(render("%icon|camel%", &vars), "chevronRight");
+        assert_eq!(render("%icon|constant%", &vars), "CHEVRON_RIGHT");
+    }
+
+    #[test]
+    fn leaves_unknown_tokens_and_stray_percents_alone() {
+        let vars = Vars::new().with("filename", "a.svg");
+        assert_eq!(
+            render("%APPDATA%\\zed 100% %filename|nope% %filename%", &vars),
+            "%APPDATA%\\zed 100% %filename|nope% a.svg"
+        );
+        assert!(references("open %filename|lower%", "filename"));
+        assert!(!references("open %filename|nope%", "filename"));
+    }
+
+    #[test]
+    fn built_in_templates_match_previous_output() {
+        let vars = Vars::new()
+            .with("name", "IconHeart")
+            .with("filename", "mdi_heart")
+            .with("ext", ".svg");
+        assert_eq!(
+            render(EXPORT_LINE, &vars),
+            "export { default as IconHeart } from './mdi_heart.svg';"
+        );
+        assert_eq!(
+            render(
+                ICONIFY_FILENAME,
+                &Vars::new().with_iconify_name("mdi:heart")
+            ),
+            "mdi_heart"
+        );
+        // Substituted values are not re-scanned for tokens.
+        let svg = Vars::new().with("svg", "<svg width=\"100%\">%name%</svg>");
+        assert!(
+            render(SOLID_COMPONENT, &svg).contains("return (<svg width=\"100%\">%name%</svg>);")
+        );
+    }
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+}
@@ -3,63 +3,73 @@ use crate::{
     views::main::render_main_view,
 };
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::{Event, KeyEventKind};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Wrap};
 use ratatui::{Frame, Terminal, backend::CrosstermBackend, layout::Constraint};
 use std::{io, time::Duration};
 use tui_textarea::{Input, Key};
 
-struct TerminalCleanupGuard;
+/// Restores the terminal, then (dropping `_crash`) the previous panic hook.
+struct TerminalCleanupGuard {
+    _crash: crate::crash::Installed,
+}
 
 impl Drop for TerminalCleanupGuard {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
-        let mut stdout = io::stdout();
-        let _ = execute!(stdout, LeaveAlternateScreen, DisableMouseCapture);
+        restore_terminal();
     }
 }
 
+/// Leaves raw mode and the alternate screen; safe to call more than once.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let mut stdout = io::stdout();
+    let _ = execute!(
+        stdout,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    );
+}
+
 pub async fn run(config: AppConfig) -> Result<(), anyhow::Error> {
+    let crash = crate::crash::install(restore_terminal);
     enable_raw_mode()?;
-    let _cleanup = TerminalCleanupGuard;
+    let _cleanup = TerminalCleanupGuard { _crash: crash };
 
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // With bracketed paste a pasted SVG arrives as one `Event::Paste`
+    // instead of thousands of key presses.
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new(config);
+    if app.needs_onboarding() {
+        app.init_onboarding_popup();
+    }
 
     loop {
-        terminal.draw(|f| ui(f, &mut app))?;
+        terminal.draw(|f| draw(f, &mut app))?;
 
         if ratatui::crossterm::event::poll(Duration::from_millis(16))? {
             let event = ratatui::crossterm::event::read()?;
-            match event {
-                Event::Key(key_event) => {
-                    if matches!(app.app_focus, AppFocus::AddPopup) {
-                        app.handle_key_event_add_popup(key_event);
-                        continue;
-                    }
-
-                    let input: Input = key_event.into();
-                    if matches!(
-                        input,
-                        Input {
-                            key: Key::Char('c'),
-                            ctrl: true,
-                            ..
-                        }
-                    ) {
-                        break;
-                    }
-                    app.handlekeys(input);
-                }
-                Event::Mouse(mouse_event) => app.handle_mouse(mouse_event),
-                _ => {}
+            if handle_event(&mut app, event) {
+                break;
             }
         }
 
@@ -74,6 +84,117 @@ pub async fn run(config: AppConfig) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Below this the layouts don't fit; a "terminal too small" screen is drawn.
+pub const MIN_WIDTH: u16 = 50;
+pub const MIN_HEIGHT: u16 = 14;
+
+/// One terminal event; `true` means quit (ctrl+c outside the add popup).
+pub fn handle_event(app: &mut App, event: Event) -> bool {
+    match event {
+        // Windows reports releases too; acting on them would type every
+        // character, IME-composed ones included, twice.
+        Event::Key(key_event) if key_event.kind == KeyEventKind::Release => {}
+        Event::Key(key_event) => {
+            if app.terminal_too_small {
+                // Nothing the keys would change is on screen.
+                return key_event.code == ratatui::crossterm::event::KeyCode::Char('c')
+                    && key_event
+                        .modifiers
+                        .contains(ratatui::crossterm::event::KeyModifiers::CONTROL);
+            }
+            if matches!(app.app_focus, AppFocus::AddPopup) {
+                app.handle_key_event_add_popup(key_event);
+                return false;
+            }
+
+            let input: Input = key_event.into();
+            if matches!(
+                input,
+                Input {
+                    key: Key::Char('c'),
+                    ctrl: true,
+                    ..
+                }
+            ) {
+                return true;
+            }
+            app.handlekeys(input);
+        }
+        Event::Mouse(mouse_event) if !app.terminal_too_small => app.handle_mouse(mouse_event),
+        Event::Paste(text) if !app.terminal_too_small => app.handle_paste(&text),
+        Event::Resize(_, _) => app.handle_resize(),
+        _ => {}
+    }
+    false
+}
+
+/// A full frame: the main view, the focused popup and overlays.
+pub fn draw(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    app.terminal_too_small = area.width < MIN_WIDTH || area.height < MIN_HEIGHT;
+    if app.terminal_too_small {
+        render_too_small(f);
+        return;
+    }
+    ui(f, app);
+    if crate::views::theme::is_monochrome() {
+        crate::views::theme::apply_monochrome(f.buffer_mut());
+    }
+}
+
+/// The rows of a drawn buffer as text. The cell after a wide character is
+/// covered by it and skipped, as a terminal would.
+#[cfg(any(test, feature = "headless"))]
+pub fn buffer_rows(buffer: &ratatui::buffer::Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (0..area.height)
+        .map(|y| {
+            let mut row = String::new();
+            let mut covered = 0;
+            for x in 0..area.width {
+                let symbol = buffer[(x, y)].symbol();
+                if covered > 0 {
+                    covered -= 1;
+                    continue;
+                }
+                covered = crate::views::layout::display_width(symbol).saturating_sub(1);
+                row.push_str(symbol);
+            }
+            row
+        })
+        .collect()
+}
+
+fn render_too_small(f: &mut Frame) {
+    let area = f.area();
+    let palette = crate::views::theme::palette();
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(palette.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("{} x {}", area.width, area.height)),
+        Line::from(Span::styled(
+            format!("Needs {MIN_WIDTH} x {MIN_HEIGHT}; resize, or ctrl+c to quit"),
+            Style::default().fg(palette.muted_text),
+        )),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let body = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        body,
+    );
+}
+
 fn ui(f: &mut Frame, app: &mut App) {
     let area = f.area();
     let layout = ratatui::layout::Layout::default()
@@ -91,11 +212,58 @@ fn ui(f: &mut Frame, app: &mut App) {
         AppFocus::AddPopup => crate::views::add_popup::render_add_popup(f, app),
         AppFocus::DeletePopup => crate::views::delete_popup::render_delete_popup(f, app),
         AppFocus::RenamePopup => crate::views::rename_popup::render_rename_popup(f, app),
+        AppFocus::ReplacePopup => crate::views::replace_popup::render_replace_popup(f, app),
+        AppFocus::DuplicatePopup => crate::views::duplicate_popup::render_duplicate_popup(f, app),
         AppFocus::HelpPopup => crate::views::help_popup::render_help_popup(f, app),
         AppFocus::IconifySearchPopup => {
             crate::views::iconify_search_popup::render_iconify_search_popup(f, app)
         }
+        AppFocus::SvgEditPopup => crate::views::svg_edit_popup::render_svg_edit_popup(f, app),
         AppFocus::SyncPopup => crate::views::sync_popup::render_sync_popup(f, app),
+        AppFocus::TagPopup => crate::views::tag_popup::render_tag_popup(f, app),
+        AppFocus::OnboardingPopup => {
+            crate::views::onboarding_popup::render_onboarding_popup(f, app)
+        }
         _ => {}
     }
+
+    if app.show_request_log {
+        crate::views::request_log::render_request_log(f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn render(app: &mut App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        buffer_rows(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn small_terminal_shows_notice_and_ignores_keys_until_resized() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = App::new(AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        let q = Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+
+        let small = render(&mut app, 30, 8);
+        assert!(small.contains("Terminal too small"));
+        assert!(small.contains("30 x 8"));
+        assert!(!handle_event(&mut app, q.clone()));
+        assert!(!app.should_quit);
+
+        app.selected_index = 5;
+        handle_event(&mut app, Event::Resize(MIN_WIDTH, MIN_HEIGHT));
+        assert_eq!(app.selected_index, 0);
+        assert!(!render(&mut app, MIN_WIDTH, MIN_HEIGHT).contains("Terminal too small"));
+        handle_event(&mut app, q);
+        assert!(app.should_quit);
+    }
 }
@@ -4,7 +4,10 @@ use reqwest::Url;
 use serde_json::Value;
 use std::path::Path;
 
+use crate::code_style::CodeStyle;
 use crate::iconify::IconifyClient;
+use crate::js_statements::{export_specifier, export_specifier_start, path_before_query_or_hash};
+use crate::template;
 
 #[derive(ValueEnum, Clone, Debug, PartialEq, Hash)]
 pub enum Preset {
@@ -35,6 +38,22 @@ pub enum Preset {
     /// Flutter (Dart barrel)
     #[value(name = "flutter")]
     Flutter,
+
+    /// SVG + generated CSS mask utility classes
+    #[value(name = "css")]
+    Css,
+
+    /// TypeScript constant holding the SVG as a data URI
+    #[value(name = "datauri")]
+    DataUri,
+
+    /// SVG + `include_str!` constants in a generated `mod.rs`
+    #[value(name = "rust")]
+    Rust,
+
+    /// SVG partial for server-side includes + a `partials.json` manifest
+    #[value(name = "html")]
+    Html,
 }
 
 impl Preset {
@@ -47,6 +66,24 @@ impl Preset {
             Preset::Solid => "solid",
             Preset::Vue => "vue",
             Preset::Flutter => "flutter",
+            Preset::Css => "css",
+            Preset::DataUri => "datauri",
+            Preset::Rust => "rust",
+            Preset::Html => "html",
+        }
+    }
+
+    /// Extension (with the dot) of the file this preset writes.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Preset::Normal | Preset::EmptySvg | Preset::Flutter | Preset::Css | Preset::Rust => {
+                ".svg"
+            }
+            Preset::React | Preset::Solid => ".tsx",
+            Preset::Svelte => ".svelte",
+            Preset::Vue => ".vue",
+            Preset::DataUri => ".ts",
+            Preset::Html => ".svg.html",
         }
     }
 
@@ -59,9 +96,188 @@ impl Preset {
             "solid" => Some(Preset::Solid),
             "vue" => Some(Preset::Vue),
             "flutter" => Some(Preset::Flutter),
+            "css" => Some(Preset::Css),
+            "datauri" => Some(Preset::DataUri),
+            "rust" => Some(Preset::Rust),
+            "html" => Some(Preset::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Bundler query appended to `.svg` imports in `index.ts`
+/// (`./heart.svg?react`), so the import resolves to a component or string
+/// instead of a URL.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ImportQuery {
+    /// `?react`, for vite-plugin-svgr.
+    #[value(name = "react")]
+    React,
+
+    /// `?component`, for vite-svg-loader.
+    #[value(name = "component")]
+    Component,
+
+    /// `?raw`, the markup as a string (Vite, webpack asset/source).
+    #[value(name = "raw")]
+    Raw,
+
+    /// `?url`, the asset URL.
+    #[value(name = "url")]
+    Url,
+}
+
+impl ImportQuery {
+    pub const NAMES: &'static [&'static str] = &["react", "component", "raw", "url"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "react" => Some(ImportQuery::React),
+            "component" => Some(ImportQuery::Component),
+            "raw" => Some(ImportQuery::Raw),
+            "url" => Some(ImportQuery::Url),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        &self.suffix()[1..]
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            ImportQuery::React => "?react",
+            ImportQuery::Component => "?component",
+            ImportQuery::Raw => "?raw",
+            ImportQuery::Url => "?url",
+        }
+    }
+
+    /// The `ext` an export line imports: `.svg?react` with a query, `ext`
+    /// as-is otherwise. Only `.svg` files take a query.
+    pub fn import_ext(query: Option<Self>, ext: &str) -> String {
+        match query {
+            Some(query) if ext == ".svg" => format!("{ext}{}", query.suffix()),
+            _ => ext.to_string(),
+        }
+    }
+}
+
+pub const DEFAULT_ALIAS_PREFIX: &str = "Icon";
+
+/// How the export name is built from the alias: `IconHeart`, `iconHeart`,
+/// or `ICON_HEART`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum AliasCase {
+    /// Prefix + alias + suffix as written (`IconHeart`, `HeartIcon`).
+    #[default]
+    #[value(name = "pascal")]
+    Pascal,
+
+    /// lowerCamelCase (`iconHeart`).
+    #[value(name = "camel")]
+    Camel,
+
+    /// SCREAMING_SNAKE_CASE (`ICON_HEART`).
+    #[value(name = "constant")]
+    Constant,
+}
+
+impl AliasCase {
+    pub const NAMES: &'static [&'static str] = &["pascal", "camel", "constant"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "pascal" => Some(AliasCase::Pascal),
+            "camel" => Some(AliasCase::Camel),
+            "constant" => Some(AliasCase::Constant),
+            _ => None,
+        }
+    }
+}
+
+/// Filename convention for icon files (`filename_case`, `normalize --case`).
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum FilenameCase {
+    /// `mdi-heart.svg`.
+    #[default]
+    #[value(name = "kebab")]
+    Kebab,
+
+    /// `mdi_heart.svg`.
+    #[value(name = "snake")]
+    Snake,
+
+    /// `mdiHeart.svg`.
+    #[value(name = "camel")]
+    Camel,
+
+    /// `MdiHeart.svg`.
+    #[value(name = "pascal")]
+    Pascal,
+}
+
+impl FilenameCase {
+    pub const NAMES: &'static [&'static str] = &["kebab", "snake", "camel", "pascal"];
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "kebab" => Some(FilenameCase::Kebab),
+            "snake" => Some(FilenameCase::Snake),
+            "camel" => Some(FilenameCase::Camel),
+            "pascal" => Some(FilenameCase::Pascal),
             _ => None,
         }
     }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            FilenameCase::Kebab => "kebab",
+            FilenameCase::Snake => "snake",
+            FilenameCase::Camel => "camel",
+            FilenameCase::Pascal => "pascal",
+        }
+    }
+
+    /// `mdi:Heart Outline` -> `mdi-heart-outline`. Anything that isn't a
+    /// letter or digit (colons, dots, spaces) only separates words.
+    pub fn apply(self, stem: &str) -> String {
+        template::apply_filter(self.to_str(), stem).unwrap_or_else(|| stem.to_string())
+    }
+}
+
+/// Team naming convention for JS barrel exports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AliasStyle {
+    pub case: AliasCase,
+    pub prefix: String,
+    pub suffix: String,
+    /// Always infer `LucideHeart` rather than `Heart` from `lucide:heart`.
+    pub collection_prefix: bool,
+}
+
+impl Default for AliasStyle {
+    fn default() -> Self {
+        Self {
+            case: AliasCase::Pascal,
+            prefix: DEFAULT_ALIAS_PREFIX.to_string(),
+            suffix: String::new(),
+            collection_prefix: false,
+        }
+    }
+}
+
+impl AliasStyle {
+    /// `Heart` -> the identifier written to `index.ts`.
+    pub fn export_name(&self, alias: &str) -> String {
+        let joined = format!("{}{}{}", self.prefix, alias, self.suffix);
+        let filter = match self.case {
+            AliasCase::Pascal => return joined,
+            AliasCase::Camel => "camel",
+            AliasCase::Constant => "constant",
+        };
+        template::apply_filter(filter, &joined).unwrap_or(joined)
+    }
 }
 
 /// A helper struct that pairs a preset with its human-readable description
@@ -106,6 +322,22 @@ pub const PRESETS_OPTIONS: &[PresetOption] = &[
         preset: Preset::Flutter,
         description: "Outputs SVGs + a Dart barrel (lib/icons.dart)",
     },
+    PresetOption {
+        preset: Preset::Css,
+        description: "Outputs SVGs + CSS mask utility classes (icons.css)",
+    },
+    PresetOption {
+        preset: Preset::DataUri,
+        description: "Outputs a data URI constant (.ts) + a combined icons.ts",
+    },
+    PresetOption {
+        preset: Preset::Rust,
+        description: "Outputs SVGs + include_str! constants (mod.rs)",
+    },
+    PresetOption {
+        preset: Preset::Html,
+        description: "Outputs HTML partials (_name.svg.html) + a partials.json manifest",
+    },
 ];
 
 /// helper function to create a centered rect using up certain maximum dimensions `r`
@@ -150,7 +382,7 @@ struct JsExportStyle {
     quote: char,
     semicolon: bool,
     leading_dot_slash: bool,
-    include_tsx_extension: bool,
+    include_ts_extension: bool,
 }
 
 impl Default for JsExportStyle {
@@ -159,7 +391,7 @@ impl Default for JsExportStyle {
             quote: '\'',
             semicolon: true,
             leading_dot_slash: true,
-            include_tsx_extension: false,
+            include_ts_extension: false,
         }
     }
 }
@@ -167,13 +399,16 @@ impl Default for JsExportStyle {
 /// Reconcile a rendered JS barrel export with the local barrel style and TS config.
 ///
 /// Existing `index.ts` lines win for quote/semicolon/`./` style and, when present,
-/// `.tsx` extension style. `.svg` imports keep their extension. `.tsx` imports keep
-/// the extension only when the nearest tsconfig enables
-/// `compilerOptions.allowImportingTsExtensions`; otherwise `.tsx` is stripped.
+/// `.tsx`/`.ts` extension style, except where `code_style` sets quotes or
+/// semicolons. `.svg` imports keep their extension. `.tsx`/`.ts`
+/// imports keep the extension only when the nearest tsconfig enables
+/// `compilerOptions.allowImportingTsExtensions`; otherwise it is stripped.
+/// A `?query` or `#hash` on the rendered import is kept.
 pub fn format_js_export_for_barrel(
     rendered_line: &str,
     existing_barrel_contents: Option<&str>,
     ts_extension_policy: TsExtensionPolicy,
+    code_style: &CodeStyle,
 ) -> String {
     let Some(entry) = parse_export_line_ts(rendered_line) else {
         return rendered_line.trim_end().to_string();
@@ -182,16 +417,25 @@ pub fn format_js_export_for_barrel(
     let existing_style = existing_barrel_contents.and_then(detect_js_export_style);
     let fallback_style = detect_js_export_style(rendered_line);
     let mut style = existing_style.or(fallback_style).unwrap_or_default();
+    if let Some(quote) = code_style.quotes {
+        style.quote = quote.char();
+    }
+    if let Some(semicolons) = code_style.semicolons {
+        style.semicolon = semicolons;
+    }
 
-    style.include_tsx_extension = match ts_extension_policy {
+    style.include_ts_extension = match ts_extension_policy {
         TsExtensionPolicy::Strip => false,
         TsExtensionPolicy::Allow => existing_style
-            .map(|style| style.include_tsx_extension)
-            .or_else(|| fallback_style.map(|style| style.include_tsx_extension))
+            .map(|style| style.include_ts_extension)
+            .or_else(|| fallback_style.map(|style| style.include_ts_extension))
             .unwrap_or(false),
     };
 
-    let import_path = apply_js_import_path_style(&entry.file_path, style);
+    let suffix = raw_export_path(rendered_line)
+        .map(|path| &path[path_before_query_or_hash(path).len()..])
+        .unwrap_or("");
+    let import_path = apply_js_import_path_style(&format!("{}{suffix}", entry.file_path), style);
     format!(
         "export {{ default as {} }} from {}{}{}{}",
         entry.name,
@@ -206,81 +450,45 @@ pub fn render_js_export_line(
     index_contents: Option<&str>,
     folder: &Path,
     alias: &str,
+    alias_style: &AliasStyle,
+    code_style: &CodeStyle,
     file_stem: &str,
     ext: &str,
 ) -> String {
-    let rendered = format!(
-        "export {{ default as Icon{} }} from './{}{}';",
-        alias, file_stem, ext
+    let rendered = template::render(
+        template::EXPORT_LINE,
+        &template::Vars::new()
+            .with("name", alias_style.export_name(alias))
+            .with("filename", file_stem)
+            .with("ext", ext),
     );
     format_js_export_for_barrel(
         &rendered,
         index_contents,
         TsExtensionPolicy::from_tsconfig_near(folder),
+        code_style,
     )
 }
 
 fn detect_js_export_style(contents: &str) -> Option<JsExportStyle> {
-    for line in contents.lines() {
-        for stmt in line.split_inclusive(';') {
-            let trimmed = stmt.trim();
-            if trimmed.is_empty() || parse_export_line_ts(trimmed).is_none() {
-                continue;
-            }
-
-            let path = raw_export_path(trimmed)?;
-            return Some(JsExportStyle {
-                quote: quote_after_from(trimmed).unwrap_or('\''),
-                semicolon: trimmed.ends_with(';'),
-                leading_dot_slash: path.starts_with("./"),
-                include_tsx_extension: path_before_query_or_hash(path).ends_with(".tsx"),
-            });
-        }
-
-        let trimmed = line.trim();
-        if !trimmed.is_empty() && parse_export_line_ts(trimmed).is_some() {
-            let path = raw_export_path(trimmed)?;
-            return Some(JsExportStyle {
-                quote: quote_after_from(trimmed).unwrap_or('\''),
-                semicolon: trimmed.ends_with(';'),
-                leading_dot_slash: path.starts_with("./"),
-                include_tsx_extension: path_before_query_or_hash(path).ends_with(".tsx"),
-            });
-        }
-    }
-    None
+    let statement = crate::js_statements::statements(contents)
+        .into_iter()
+        .find(|statement| parse_export_line_ts(&statement.code).is_some())?;
+    let path = raw_export_path(&statement.code)?;
+    Some(JsExportStyle {
+        quote: quote_after_from(&statement.code).unwrap_or('\''),
+        semicolon: contents[statement.range].ends_with(';'),
+        leading_dot_slash: path.starts_with("./"),
+        include_ts_extension: has_ts_extension(path_before_query_or_hash(path)),
+    })
 }
 
 fn quote_after_from(line: &str) -> Option<char> {
-    let from_idx = line.find("from")?;
-    line[from_idx + "from".len()..]
-        .trim_start()
-        .chars()
-        .next()
-        .filter(|c| *c == '\'' || *c == '"')
+    export_specifier(line).map(|(quote, _)| quote)
 }
 
 fn raw_export_path(line: &str) -> Option<&str> {
-    let from_idx = line.find("from")?;
-    let after_from = line[from_idx + "from".len()..].trim_start();
-    let quote = after_from.chars().next()?;
-    if quote != '\'' && quote != '"' {
-        return None;
-    }
-    let start = quote.len_utf8();
-    let end = after_from[start..].find(quote)?;
-    Some(&after_from[start..start + end])
-}
-
-fn path_before_query_or_hash(path: &str) -> &str {
-    let mut end = path.len();
-    if let Some(idx) = path.find('?') {
-        end = end.min(idx);
-    }
-    if let Some(idx) = path.find('#') {
-        end = end.min(idx);
-    }
-    &path[..end]
+    export_specifier(line).map(|(_, path)| path)
 }
 
 fn apply_js_import_path_style(path: &str, style: JsExportStyle) -> String {
@@ -292,8 +500,8 @@ fn apply_js_import_path_style(path: &str, style: JsExportStyle) -> String {
         None => (without_prefix, ""),
     };
 
-    let base = if !style.include_tsx_extension && base.ends_with(".tsx") {
-        &base[..base.len() - ".tsx".len()]
+    let base = if !style.include_ts_extension {
+        strip_ts_extension(base)
     } else {
         base
     };
@@ -344,6 +552,8 @@ pub enum IconSourceType {
     Url,
     /// Raw SVG content
     SvgContent,
+    /// A `data:image/svg+xml` URI, plain or base64
+    DataUri,
     /// None provided
     None,
 }
@@ -388,12 +598,15 @@ fn to_pascal_case(input: &str) -> String {
 }
 
 fn safe_default_filename_from_iconify_name(iconify_name: &str) -> String {
-    iconify_name.replace(':', "_")
+    template::render(
+        template::ICONIFY_FILENAME,
+        &template::Vars::new().with_iconify_name(iconify_name),
+    )
 }
 
 pub fn iconify_name_from_icon_source(icon_source: &str) -> Option<String> {
     let trimmed = icon_source.trim();
-    if trimmed.is_empty() || trimmed.trim_start().starts_with("<svg") {
+    if trimmed.is_empty() || trimmed.starts_with('<') {
         return None;
     }
 
@@ -408,16 +621,23 @@ pub fn iconify_name_from_icon_source(icon_source: &str) -> Option<String> {
     let host = url.host_str()?.to_ascii_lowercase();
 
     if host.contains("icones.js.org") {
+        let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
         for (key, value) in url.query_pairs() {
             if key == "icon" || key == "i" {
                 let candidate = decode_icon_candidate(value.as_ref());
                 if is_iconify_name(&candidate) {
                     return Some(candidate);
                 }
+                // `/collection/lucide?icon=heart` leaves the prefix to the path.
+                if segments.len() >= 2 && segments[0] == "collection" {
+                    let candidate = format!("{}:{candidate}", decode_icon_candidate(segments[1]));
+                    if is_iconify_name(&candidate) {
+                        return Some(candidate);
+                    }
+                }
             }
         }
 
-        let segments: Vec<_> = url.path_segments()?.collect();
         if segments.len() >= 4 && segments[0] == "collection" && segments[2] == "icon" {
             let candidate = format!(
                 "{}:{}",
@@ -441,6 +661,29 @@ pub fn iconify_name_from_icon_source(icon_source: &str) -> Option<String> {
         }
     }
 
+    // Icon pages: `https://icon-sets.iconify.design/mdi/home/`.
+    if host.contains("icon-sets.iconify.design") {
+        let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        if let [prefix, icon, ..] = segments.as_slice() {
+            let candidate = format!(
+                "{}:{}",
+                decode_icon_candidate(prefix),
+                decode_icon_candidate(icon)
+            );
+            if is_iconify_name(&candidate) {
+                return Some(candidate);
+            }
+        }
+        for (key, value) in url.query_pairs() {
+            if key == "icon" {
+                let candidate = decode_icon_candidate(value.as_ref());
+                if is_iconify_name(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+
     if host.contains("api.iconify.design") {
         let path = url.path().trim_start_matches('/').trim_end_matches('/');
 
@@ -478,6 +721,25 @@ pub fn iconify_name_from_icon_source(icon_source: &str) -> Option<String> {
     None
 }
 
+/// `lucide` + `Heart` -> `LucideHeart`.
+pub fn collection_prefixed_alias(prefix: &str, alias: &str) -> String {
+    format!("{}{}", to_pascal_case(prefix), alias)
+}
+
+/// Whether an icon file came from the Iconify collection `prefix`, going by
+/// its default filename (`carbon_heart.svg`, or the older `carbon:heart.svg`).
+/// Works on index paths (`./carbon_heart.svg`) and barrel asset paths alike.
+pub fn icon_from_collection(file_path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim().trim_end_matches(':');
+    if prefix.is_empty() {
+        return false;
+    }
+    let basename = file_path.rsplit(['/', '\\']).next().unwrap_or(file_path);
+    basename
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.starts_with('_') || rest.starts_with(':'))
+}
+
 pub fn default_name_and_filename_from_icon_source(icon_source: &str) -> Option<(String, String)> {
     let iconify_name = iconify_name_from_icon_source(icon_source)?;
     let icon_name = iconify_name
@@ -499,10 +761,22 @@ pub fn default_name_and_filename_from_icon_source(icon_source: &str) -> Option<(
 pub fn _determine_icon_source_type(icon_source: Option<&String>) -> IconSourceType {
     match icon_source {
         Some(icon) => {
-            if icon.trim_start().starts_with("<svg") {
+            // Exported files may open with a prolog, DOCTYPE or comment.
+            let markup = icon.trim_start();
+            if markup.starts_with('<') && markup.contains("<svg") {
                 IconSourceType::SvgContent
+            } else if markup
+                .get(..5)
+                .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+            {
+                IconSourceType::DataUri
             } else if icon.starts_with("http://") || icon.starts_with("https://") {
-                IconSourceType::Url
+                // Icon pages (Icones, Iconify) name an icon; they aren't SVGs.
+                if iconify_name_from_icon_source(icon).is_some() {
+                    IconSourceType::IconifyName
+                } else {
+                    IconSourceType::Url
+                }
             } else {
                 IconSourceType::IconifyName
             }
@@ -511,48 +785,68 @@ pub fn _determine_icon_source_type(icon_source: Option<&String>) -> IconSourceTy
     }
 }
 
+/// The SVG an icon source names, as fetched or decoded.
+async fn fetch_icon_source(icon_source: &String) -> anyhow::Result<String> {
+    Ok(match _determine_icon_source_type(Some(icon_source)) {
+        IconSourceType::SvgContent => {
+            // Already an SVG document
+            icon_source.clone()
+        }
+        IconSourceType::DataUri => crate::datauri::decode_svg_data_uri(icon_source)?,
+        IconSourceType::IconifyName => {
+            let iconify_name =
+                iconify_name_from_icon_source(icon_source).unwrap_or_else(|| icon_source.clone());
+            let client = IconifyClient::from_env()?;
+            client.svg(&iconify_name).await?
+        }
+        IconSourceType::Url => {
+            // Already a full URL
+            let icon_url = Url::parse(icon_source)?;
+            println!("Fetching icon from: {}", icon_url);
+
+            let response = crate::http::get(icon_url).await?;
+            crate::progress::read_body(response, "Downloading icon").await?
+        }
+        IconSourceType::None => r#"<svg></svg>"#.to_string(),
+    })
+}
+
 /// Util: Converts any icon_source into an SVG
 pub async fn _icon_source_to_svg(
     icon_source: &Option<String>,
     append_attribute: Option<&'static str>,
-    remove_comments: bool,
+    sanitize: bool,
+    pin: &mut crate::checksums::Pin,
 ) -> anyhow::Result<String> {
     // If icon_source is missing, return a minimal SVG (Note: rust skill issue idk how else to just reuse the last clause in the match below)
     let Some(icon_source) = icon_source else {
         return Ok(r#"<svg></svg>"#.to_string());
     };
 
-    let mut content = match _determine_icon_source_type(Some(icon_source)) {
-        IconSourceType::SvgContent => {
-            // Already an SVG document
-            icon_source.clone()
-        }
-        IconSourceType::IconifyName => {
-            let client = IconifyClient::from_env()?;
-            client.svg(icon_source).await?
-        }
-        IconSourceType::Url => {
-            if let Some(iconify_name) = iconify_name_from_icon_source(icon_source) {
-                let client = IconifyClient::from_env()?;
-                client.svg(&iconify_name).await?
-            } else {
-                // Already a full URL
-                let icon_url = Url::parse(icon_source)?;
-                println!("Fetching icon from: {}", icon_url);
-
-                // Fetch the SVG content
-                let client = reqwest::Client::new();
-                let response = client.get(icon_url).send().await?.error_for_status()?;
-                response.text().await?
-            }
-        }
-        IconSourceType::None => {
-            return Ok(r#"<svg></svg>"#.to_string());
+    // Pin the source as fetched, before any preset rewrites. Later targets
+    // of the same add reuse the first fetch.
+    let mut content = match pin.content() {
+        Some(content) => content.to_string(),
+        None => {
+            let content = fetch_icon_source(icon_source).await?;
+            let label = match _determine_icon_source_type(Some(icon_source)) {
+                IconSourceType::SvgContent => "the inline SVG",
+                IconSourceType::DataUri => "the data URI",
+                _ => icon_source.as_str(),
+            };
+            pin.check(&content, label)?;
+            content
         }
     };
 
     // -- Transformations if applicable ---
 
+    // 0. SVG copied out of a JSX component (`className`, `strokeWidth={2}`,
+    // `{...props}`) becomes plain SVG; component presets add their own props.
+    if crate::viewer::looks_like_jsx(&content) {
+        content = crate::viewer::jsx_to_svg(&content);
+    }
+
     // 1. Append attribute (i.e. for jsx,svelte,vue)
     if let Some(attr) = append_attribute {
         // Find the first occurrence of "<svg" and append the attribute right before the closing ">"
@@ -566,17 +860,35 @@ pub async fn _icon_source_to_svg(
         }
     }
 
-    // 2. Remove Comments
-    if remove_comments {
-        // Remove HTML comments from SVG content
-        // This regex matches <!-- ... --> including any content in between
-        let re = regex::Regex::new(r"<!--.*?-->").unwrap();
-        content = re.replace_all(&content, "").to_string();
+    // 2. Strip comments, prologs and DOCTYPEs
+    if sanitize {
+        content = crate::sanitize::sanitize_svg(&content);
     }
 
     Ok(content)
 }
 
+/// Encodes an SVG document as a `data:image/svg+xml` URI. Only the characters
+/// that break CSS `url("...")` values are escaped, which keeps the output far
+/// smaller than base64.
+pub fn svg_to_data_uri(svg: &str) -> String {
+    let collapsed = svg.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut encoded = String::with_capacity(collapsed.len());
+    for ch in collapsed.chars() {
+        match ch {
+            '"' => encoded.push('\''),
+            '%' => encoded.push_str("%25"),
+            '#' => encoded.push_str("%23"),
+            '<' => encoded.push_str("%3C"),
+            '>' => encoded.push_str("%3E"),
+            '{' => encoded.push_str("%7B"),
+            '}' => encoded.push_str("%7D"),
+            _ => encoded.push(ch),
+        }
+    }
+    format!("data:image/svg+xml,{encoded}")
+}
+
 /// Util: Reused in all cases, for appending the filename of svg, i.e. add .tsx or .svg or .svelte.
 /// Returns a file_stem and an ext
 pub fn _make_svg_filename(
@@ -595,11 +907,11 @@ pub fn _make_svg_filename(
                 .unwrap_or(icon.clone()),
             IconSourceType::Url => iconify_name_from_icon_source(icon)
                 .map(|iconify_name| safe_default_filename_from_iconify_name(&iconify_name))
-                .unwrap_or_else(|| name_from_cli.to_string().to_lowercase()),
-            _ => name_from_cli.to_string().to_lowercase(),
+                .unwrap_or_else(|| alias_filename(name_from_cli)),
+            _ => alias_filename(name_from_cli),
         }
     } else {
-        name_from_cli.to_string().to_lowercase()
+        alias_filename(name_from_cli)
     };
 
     if stem.ends_with(ext) {
@@ -609,27 +921,75 @@ pub fn _make_svg_filename(
     }
 }
 
+/// Rejects a typed filename that is absolute, climbs out with `..`, or has no stem.
+pub fn validate_icon_filename(value: &str) -> anyhow::Result<()> {
+    let normalized = value.trim().replace('\\', "/");
+    if normalized.is_empty() {
+        anyhow::bail!("Filename can't be empty.");
+    }
+    let bytes = normalized.as_bytes();
+    let has_drive = bytes.len() >= 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes.len() == 2 || bytes[2] == b'/');
+    if normalized.starts_with('/') || has_drive || Path::new(value.trim()).is_absolute() {
+        anyhow::bail!("Filename '{value}' is an absolute path; give one inside the icons folder.");
+    }
+    if normalized.split('/').any(|segment| segment.trim() == "..") {
+        anyhow::bail!("Filename '{value}' leaves the icons folder; '..' isn't allowed.");
+    }
+    let last = normalized.rsplit('/').next().unwrap_or_default();
+    if last.split('.').next().unwrap_or_default().trim().is_empty() {
+        anyhow::bail!("Filename '{value}' has no name before its extension.");
+    }
+    Ok(())
+}
+
+/// `_make_svg_filename` with `filename_case` applied to inferred stems. An
+/// explicit `--filename` is kept as written.
+pub fn make_icon_filename(
+    stem_from_cli: Option<&String>,
+    ext: &'static str,
+    icon_source: Option<&String>,
+    name_from_cli: &str,
+    case: Option<FilenameCase>,
+) -> (String, &'static str) {
+    let (stem, ext) = _make_svg_filename(stem_from_cli, ext, icon_source, name_from_cli);
+    match case {
+        // A stem from the alias is lowercased already; case the alias itself
+        // so `HeartOutline` keeps its word boundaries.
+        Some(case) if stem_from_cli.is_none() => {
+            let source = if stem == alias_filename(name_from_cli) {
+                name_from_cli
+            } else {
+                &stem
+            };
+            (case.apply(source), ext)
+        }
+        _ => (stem, ext),
+    }
+}
+
+fn alias_filename(alias: &str) -> String {
+    template::render(
+        template::ALIAS_FILENAME,
+        &template::Vars::new().with("name", alias),
+    )
+}
+
 // Util for tui view in add.
 pub fn filename_from_preset(file_name: Option<String>, preset: Option<Preset>) -> String {
     if let Some(preset) = preset {
-        let ext = match preset {
-            Preset::Normal => "svg",
-            Preset::EmptySvg => "svg",
-            Preset::React => "tsx",
-            Preset::Svelte => "svelte",
-            Preset::Solid => "tsx",
-            Preset::Vue => "vue",
-            Preset::Flutter => "svg",
-        };
+        let ext = preset.extension();
 
         if let Some(name) = file_name {
             if name.contains('.') {
                 return name;
             } else {
-                return format!("{}.{}", name, ext);
+                return format!("{name}{ext}");
             }
         } else {
-            return format!("component.{}", ext);
+            return format!("component{ext}");
         }
     }
 
@@ -642,7 +1002,7 @@ pub fn filename_from_preset(file_name: Option<String>, preset: Option<Preset>) -
 
 /// Preset-aware dispatcher. For `flutter`, parses the Dart barrel file
 /// (defaults to `lib/icons.dart` if `flutter_barrel_path` is None). For every
-/// other preset, parses `<folder>/index.ts`.
+/// other preset, parses `<folder>/index.ts` and the groups it re-exports.
 pub fn get_existing_icons_for_preset(
     folder_path: &str,
     preset: &str,
@@ -659,189 +1019,155 @@ pub fn get_existing_icons_for_preset(
             folder_path,
         ));
     }
-    get_existing_icons(folder_path)
+    crate::groups::icons_with_groups(std::path::Path::new(folder_path))
 }
 
 /// Util: Reads a file line-by-line and extracts every icon entry that matches
 /// the template used by the current project.
 /// Returns a vector of `IconEntry` with the export alias and import file path.
 pub fn get_existing_icons(folder_path: &str) -> anyhow::Result<Vec<IconEntry>> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-    use std::path::Path;
+    let (writer, index_path) = crate::index_writer::find_barrel(std::path::Path::new(folder_path));
+    let contents = std::fs::read_to_string(&index_path)?;
+    Ok(writer.parse(&contents))
+}
 
-    let index_path = Path::new(folder_path).join("index.ts"); // FUTURE: for flutter suport, make sure to configure this + the parsing of it.
+/// The exports of an `index.ts`, in file order. Exports may be wrapped over
+/// several lines, share a line, or sit between comments and other code.
+pub fn parse_index_entries(contents: &str) -> Vec<IconEntry> {
+    crate::js_statements::statements(contents)
+        .iter()
+        .filter_map(|statement| parse_export_line_ts(&statement.code))
+        .collect()
+}
 
-    let file = File::open(&index_path)?;
+/// For parsing a single export statement in typescript; see
+/// [`crate::js_statements::default_export`].
+pub fn parse_export_line_ts(line: &str) -> Option<IconEntry> {
+    crate::js_statements::default_export(line)
+        .map(|(name, file_path)| IconEntry { name, file_path })
+}
 
-    let reader = BufReader::new(file);
+// FUTURE:
+// pub fn _parse_export_line_dart(line: &str) -> Option<IconEntry> {}
 
-    let mut icons = Vec::new();
+/// Deletes `entries` of the barrel in `folder`: their exports in one pass
+/// over the barrel, each matched by exact name and path, then their files
+/// and rasters. A file another export still points at is kept.
+pub fn delete_icon_entries(folder: &Path, entries: &[IconEntry]) -> anyhow::Result<()> {
+    use std::fs;
 
-    for line in reader.lines() {
-        let line = line?;
+    let (writer, index_path) = crate::index_writer::find_barrel(folder);
+    let mut remaining = Vec::new();
+    if index_path.exists() {
+        let contents = fs::read_to_string(&index_path)?;
+        let updated = writer.remove_entries(&contents, entries);
+        if let Some(updated) = &updated {
+            fs::write(&index_path, updated)?;
+        }
+        remaining = writer.parse(updated.as_deref().unwrap_or(&contents));
+    }
 
-        // Skip empty lines and comments
-        if line.trim().is_empty() || line.trim_start().starts_with("//") {
+    for entry in entries {
+        if remaining
+            .iter()
+            .any(|other| icon_relative_paths_match(&other.file_path, &entry.file_path))
+        {
             continue;
         }
-
-        // Parse one or more exports per physical line.
-        // This keeps discovery resilient if exports were accidentally concatenated.
-        for statement in line.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() || statement.starts_with("//") {
-                continue;
-            }
-
-            if let Some(icon_entry) = parse_export_line_ts(statement) {
-                icons.push(icon_entry);
-            }
+        let path = icon_file_path(folder, &entry.file_path);
+        if path.exists() {
+            fs::remove_file(&path)?;
         }
+        crate::raster::remove_raster_siblings(&path)?;
     }
-
-    Ok(icons)
+    Ok(())
 }
 
-/// For parsing a single export line in typescript.
-pub fn parse_export_line_ts(line: &str) -> Option<IconEntry> {
-    let line = line.trim();
-
-    // Skip empty lines and comments
-    if line.is_empty() || line.trim_start().starts_with("//") {
-        return None;
+/// Rewrites the files `folder` builds from its icons, the ones it already
+/// has: the mask and background stylesheets, data-URI bundle, name list and
+/// registry.
+pub fn refresh_derived_files(folder: &Path) -> anyhow::Result<()> {
+    if folder.join(crate::css_mask::CSS_MASK_STYLESHEET).exists() {
+        crate::css_mask::write_mask_stylesheet(folder)?;
     }
-
-    // Example lines:
-    // export { default as IconGitHub } from "./devicon:github.svg";
-    // export { default as IconGitHub } from './devicon:github.svg';
-    if !line.starts_with("export") {
-        return None;
+    if folder.join(crate::datauri::DATAURI_BUNDLE).exists() {
+        crate::datauri::write_datauri_bundle(folder)?;
     }
+    crate::css_background::refresh_background_stylesheet_if_present(folder)?;
+    crate::icon_names::refresh_icon_names(folder, false)?;
+    crate::icon_registry::refresh_icon_registry(folder, false)?;
+    Ok(())
+}
 
-    let open_brace_idx = line.find('{')?;
-    let close_brace_idx = line[open_brace_idx + 1..].find('}')? + open_brace_idx + 1;
-
-    let inside_braces = line[open_brace_idx + 1..close_brace_idx].trim();
-    let mut tokens = inside_braces.split_whitespace();
-    if tokens.next()? != "default" || tokens.next()? != "as" {
-        return None;
-    }
+/// An `index.ts` without the exports of `relative_path`; `None` when it has
+/// none.
+pub fn remove_ts_exports(contents: &str, relative_path: &str) -> Option<String> {
+    let normalized_relative_path = normalize_icon_relative_path(relative_path);
+    remove_ts_exports_where(contents, |entry| {
+        icon_relative_paths_match(&entry.file_path, &normalized_relative_path)
+    })
+}
 
-    let name = tokens.next()?.trim_end_matches(',');
-    if name.is_empty() {
-        return None;
+/// An `index.ts` without the exports `should_remove` picks; `None` when it
+/// picks none. A statement that fills its lines (with at most a trailing
+/// `//` comment) takes them along; one sharing a line leaves the rest of the
+/// line. Everything else is kept byte for byte.
+pub fn remove_ts_exports_where(
+    contents: &str,
+    mut should_remove: impl FnMut(&IconEntry) -> bool,
+) -> Option<String> {
+    let mut removals = Vec::new();
+    for statement in crate::js_statements::statements(contents) {
+        let Some(entry) = parse_export_line_ts(&statement.code) else {
+            continue;
+        };
+        if should_remove(&entry) {
+            removals.push(statement_removal_range(contents, statement.range));
+        }
     }
-
-    let after_brace = line[close_brace_idx + 1..].trim_start();
-    let after_from = after_brace.strip_prefix("from")?.trim_start();
-    let quote_char = after_from.chars().next()?;
-    if quote_char != '"' && quote_char != '\'' {
+    if removals.is_empty() {
         return None;
     }
 
-    let path_start = quote_char.len_utf8();
-    let path_end = after_from[path_start..].find(quote_char)?;
-    let relative_path = &after_from[path_start..path_start + path_end];
-    let mut import_path_end = relative_path.len();
-    if let Some(index) = relative_path.find('?') {
-        import_path_end = import_path_end.min(index);
-    }
-    if let Some(index) = relative_path.find('#') {
-        import_path_end = import_path_end.min(index);
+    let mut updated = String::with_capacity(contents.len());
+    let mut kept_from = 0;
+    for range in removals {
+        let start = range.start.max(kept_from);
+        updated.push_str(&contents[kept_from..start]);
+        kept_from = kept_from.max(range.end);
     }
-    let import_path = relative_path[..import_path_end].trim();
-    if import_path.is_empty() {
-        return None;
-    }
-
-    Some(IconEntry {
-        name: name.to_string(),
-        file_path: import_path.to_string(),
-    })
+    updated.push_str(&contents[kept_from..]);
+    Some(updated)
 }
 
-// FUTURE:
-// pub fn _parse_export_line_dart(line: &str) -> Option<IconEntry> {}
-
-/// Deletes an IconEntry based on its file path
-pub fn delete_icon_entry(file_path: &str) -> anyhow::Result<()> {
-    use std::fs;
-    use std::path::Path;
-
-    let path = Path::new(file_path);
-    let resolved_path = resolve_existing_icon_path(path);
-
-    // Delete the icon file when present. We still continue to clean index.ts
-    // if the file is already missing (stale export entry).
-    if resolved_path.exists() {
-        fs::remove_file(&resolved_path)?;
-    }
-
-    // Find the parent folder and index.ts
-    if let Some(parent) = resolved_path.parent().or_else(|| path.parent()) {
-        let index_path = parent.join("index.ts");
-
-        if index_path.exists() {
-            // Read the current index.ts
-            let contents = fs::read_to_string(&index_path)?;
-
-            // Generate the file path relative to the parent folder
-            let relative_path = resolved_path
-                .strip_prefix(parent)
-                .ok()
-                .and_then(|value| value.to_str())
-                .unwrap_or(file_path);
-
-            // Create the normalized relative path for comparison
-            let normalized_relative_path = normalize_icon_relative_path(relative_path);
-            // Remove all lines that export this file
-            let mut lines_to_keep = Vec::<String>::new();
-            let mut found_export = false;
-
-            for line in contents.lines() {
-                let mut parsed_export_in_line = false;
-
-                for statement in line.split(';') {
-                    let statement = statement.trim();
-                    if statement.is_empty() {
-                        continue;
-                    }
-
-                    let Some(entry) = parse_export_line_ts(statement) else {
-                        continue;
-                    };
-
-                    parsed_export_in_line = true;
-                    let should_remove =
-                        icon_relative_paths_match(&entry.file_path, &normalized_relative_path);
-
-                    if should_remove {
-                        found_export = true;
-                        continue;
-                    }
-
-                    lines_to_keep.push(format!("{statement};"));
-                }
-
-                if !parsed_export_in_line {
-                    lines_to_keep.push(line.to_string());
-                }
-            }
-
-            if found_export {
-                // Write the updated content back
-                let mut updated_content = lines_to_keep.join("\n");
-                if contents.ends_with('\n') {
-                    updated_content.push('\n');
-                }
-                fs::write(&index_path, updated_content)?;
-                // println!("Updated index.ts");
-            }
-        }
-    }
-
-    Ok(())
+/// The bytes to drop with the statement at `range`: its whole lines when
+/// nothing else is on them, otherwise the statement and the spaces after
+/// it (or before it, at the end of a line).
+fn statement_removal_range(
+    contents: &str,
+    range: std::ops::Range<usize>,
+) -> std::ops::Range<usize> {
+    let line_start = contents[..range.start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = contents[range.end..]
+        .find('\n')
+        .map_or(contents.len(), |idx| range.end + idx);
+    let before = &contents[line_start..range.start];
+    let after = contents[range.end..line_end].trim();
+    let after_is_blank = after.is_empty() || after.starts_with("//");
+
+    if before.trim().is_empty() && after_is_blank {
+        return line_start..(line_end + 1).min(contents.len());
+    }
+    let is_space = |c: char| c == ' ' || c == '\t';
+    if after.is_empty() {
+        let start = range.start - (before.len() - before.trim_end_matches(is_space).len());
+        return start..range.end;
+    }
+    let trailing = contents[range.end..line_end].len()
+        - contents[range.end..line_end]
+            .trim_start_matches(is_space)
+            .len();
+    range.start..range.end + trailing
 }
 
 fn normalize_icon_relative_path(value: &str) -> String {
@@ -854,14 +1180,83 @@ fn normalize_icon_relative_path(value: &str) -> String {
         .to_string()
 }
 
-fn strip_tsx_extension(value: &str) -> &str {
-    value.strip_suffix(".tsx").unwrap_or(value)
+/// `.tsx` and `.ts` imports may be written without their extension.
+fn strip_ts_extension(value: &str) -> &str {
+    value
+        .strip_suffix(".tsx")
+        .or_else(|| value.strip_suffix(".ts"))
+        .unwrap_or(value)
+}
+
+fn has_ts_extension(value: &str) -> bool {
+    value.ends_with(".tsx") || value.ends_with(".ts")
 }
 
 fn icon_relative_paths_match(left: &str, right: &str) -> bool {
     let left = normalize_icon_relative_path(left);
     let right = normalize_icon_relative_path(right);
-    left == right || strip_tsx_extension(&left) == strip_tsx_extension(&right)
+    left == right || strip_ts_extension(&left) == strip_ts_extension(&right)
+}
+
+/// The file behind an export's `file_path`, resolved the same way by list,
+/// delete, rename and open: folder-relative paths (`./heart.svg`,
+/// `social/heart.svg`) are joined onto `folder`, absolute ones are kept, `.`
+/// and `..` segments are folded away, and an extensionless import finds its
+/// `.tsx`/`.ts` file.
+pub fn icon_file_path(folder: &Path, file_path: &str) -> std::path::PathBuf {
+    let file_path = Path::new(file_path.trim());
+    let joined = if file_path.is_absolute() {
+        file_path.to_path_buf()
+    } else {
+        folder.join(file_path)
+    };
+    resolve_existing_icon_path(&lexically_normalized(&joined))
+}
+
+/// `path` in the folder-relative form barrels are matched in: `/`-separated,
+/// without `./`. When one side is absolute and the other isn't, or one goes
+/// through a symlink, both are compared by their real location instead.
+/// `None` when `path` is outside `folder`.
+pub fn folder_relative_path(folder: &Path, path: &Path) -> Option<String> {
+    let relative = |folder: &Path, path: &Path| {
+        path.strip_prefix(folder)
+            .ok()
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+            .filter(|relative| !relative.is_empty())
+    };
+    relative(&lexically_normalized(folder), &lexically_normalized(path)).or_else(|| {
+        let folder = std::fs::canonicalize(folder).ok()?;
+        // The file may be gone already (a stale export); its folder isn't.
+        let path = std::fs::canonicalize(path).ok().or_else(|| {
+            Some(
+                std::fs::canonicalize(path.parent()?)
+                    .ok()?
+                    .join(path.file_name()?),
+            )
+        })?;
+        relative(&folder, &path)
+    })
+}
+
+/// `src/icons/./social/../heart.svg` -> `src/icons/heart.svg`, without
+/// touching the filesystem. `.` alone becomes the empty path, so joining
+/// and stripping it are no-ops.
+fn lexically_normalized(path: &Path) -> std::path::PathBuf {
+    use std::path::Component;
+
+    let mut out = std::path::PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
 }
 
 pub fn resolve_existing_icon_path(path: &Path) -> std::path::PathBuf {
@@ -869,12 +1264,11 @@ pub fn resolve_existing_icon_path(path: &Path) -> std::path::PathBuf {
         return path.to_path_buf();
     }
 
-    let tsx_path = path.with_extension("tsx");
-    if tsx_path.exists() {
-        tsx_path
-    } else {
-        path.to_path_buf()
-    }
+    ["tsx", "ts"]
+        .iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|candidate| candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
 }
 
 fn split_import_path_suffix(value: &str) -> (&str, &str) {
@@ -893,16 +1287,12 @@ fn split_import_path_suffix(value: &str) -> (&str, &str) {
     }
 }
 
-fn replace_import_path_in_export_statement(
+pub fn replace_import_path_in_export_statement(
     statement: &str,
     current_relative_path: &str,
     new_relative_path: &str,
 ) -> Option<String> {
-    let from_pos = statement.find("from ")?;
-    let path_start_search = from_pos + "from ".len();
-    let statement_after_from = &statement[path_start_search..];
-    let first_quote_offset = statement_after_from.find(['"', '\''])?;
-    let first_quote_idx = path_start_search + first_quote_offset;
+    let first_quote_idx = export_specifier_start(statement)?;
     let quote_char = statement.as_bytes()[first_quote_idx] as char;
     let path_start_idx = first_quote_idx + 1;
     let second_quote_offset = statement[path_start_idx..].find(quote_char)?;
@@ -915,13 +1305,12 @@ fn replace_import_path_in_export_statement(
     }
 
     let with_dot_prefix = matched_base_path.starts_with("./");
-    let matched_uses_extensionless_tsx =
-        Path::new(&normalize_icon_relative_path(matched_base_path))
-            .extension()
-            .is_none()
-            && new_relative_path.ends_with(".tsx");
-    let replacement_base = if matched_uses_extensionless_tsx {
-        strip_tsx_extension(new_relative_path)
+    let matched_uses_extensionless_ts = Path::new(&normalize_icon_relative_path(matched_base_path))
+        .extension()
+        .is_none()
+        && has_ts_extension(new_relative_path);
+    let replacement_base = if matched_uses_extensionless_ts {
+        strip_ts_extension(new_relative_path)
     } else {
         new_relative_path
     };
@@ -941,13 +1330,46 @@ fn replace_import_path_in_export_statement(
     ))
 }
 
+/// An `index.ts` with the exports of `current_relative_path` pointing at
+/// `new_relative_path`; `None` when nothing matched. Only the import path
+/// changes; the rest of the file is kept byte for byte.
+pub fn rename_ts_export_path(
+    contents: &str,
+    current_relative_path: &str,
+    new_relative_path: &str,
+) -> Option<String> {
+    let mut updated = String::with_capacity(contents.len());
+    let mut kept_from = 0;
+    for statement in crate::js_statements::statements(contents) {
+        if parse_export_line_ts(&statement.code).is_none() {
+            continue;
+        }
+        let Some(updated_statement) = replace_import_path_in_export_statement(
+            &contents[statement.range.clone()],
+            current_relative_path,
+            new_relative_path,
+        ) else {
+            continue;
+        };
+        updated.push_str(&contents[kept_from..statement.range.start]);
+        updated.push_str(&updated_statement);
+        kept_from = statement.range.end;
+    }
+
+    if kept_from == 0 {
+        return None;
+    }
+    updated.push_str(&contents[kept_from..]);
+    Some(updated)
+}
+
 pub fn rename_icon_entry(
     folder_path: &str,
     current_file_path: &str,
     new_file_path_input: &str,
 ) -> anyhow::Result<()> {
     use std::fs;
-    use std::path::{Component, Path};
+    use std::path::Path;
 
     let requested_current_relative_path = normalize_icon_relative_path(current_file_path);
     let current_relative_path = requested_current_relative_path.clone();
@@ -955,30 +1377,13 @@ pub fn rename_icon_entry(
         anyhow::bail!("Current icon path is empty");
     }
 
+    validate_icon_filename(new_file_path_input)?;
     let mut new_relative_path = normalize_icon_relative_path(new_file_path_input);
-    if new_relative_path.is_empty() {
-        anyhow::bail!("New filename cannot be empty");
-    }
-
-    let new_path = Path::new(&new_relative_path);
-    if new_path.is_absolute() {
-        anyhow::bail!("Please provide a relative filename");
-    }
-    if new_path
-        .components()
-        .any(|component| matches!(component, Component::ParentDir))
-    {
-        anyhow::bail!("Parent directory traversals are not allowed");
-    }
 
     let folder = Path::new(folder_path);
-    let current_abs_path =
-        resolve_existing_icon_path(&folder.join(&requested_current_relative_path));
-    let current_relative_path = current_abs_path
-        .strip_prefix(folder)
-        .ok()
-        .and_then(|value| value.to_str())
-        .map(normalize_icon_relative_path)
+    let current_abs_path = icon_file_path(folder, &requested_current_relative_path);
+    let current_relative_path = folder_relative_path(folder, &current_abs_path)
+        .map(|value| normalize_icon_relative_path(&value))
         .unwrap_or(requested_current_relative_path);
     if !current_abs_path.exists() {
         anyhow::bail!("Icon file not found: {}", current_abs_path.display());
@@ -1002,67 +1407,33 @@ pub fn rename_icon_entry(
         anyhow::bail!("Target file already exists: {}", new_abs_path.display());
     }
 
-    let index_path = folder.join("index.ts");
+    let (writer, index_path) = crate::index_writer::find_barrel(folder);
+    let index_name = writer.file_name();
     if !index_path.exists() {
-        anyhow::bail!("No index.ts found in folder: {}", folder.display());
+        anyhow::bail!("No {index_name} found in folder: {}", folder.display());
     }
 
     let index_contents = fs::read_to_string(&index_path)?;
-    let mut replaced_count = 0usize;
-    let mut updated_lines = Vec::<String>::new();
-    for line in index_contents.lines() {
-        let mut parsed_export_in_line = false;
-
-        for statement in line.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() {
-                continue;
-            }
-
-            if parse_export_line_ts(statement).is_none() {
-                continue;
-            }
-
-            parsed_export_in_line = true;
-
-            if let Some(updated_statement) = replace_import_path_in_export_statement(
-                statement,
-                &current_relative_path,
-                &new_relative_path,
-            ) {
-                updated_lines.push(format!("{updated_statement};"));
-                replaced_count += 1;
-            } else {
-                updated_lines.push(format!("{statement};"));
-            }
-        }
-
-        if !parsed_export_in_line {
-            updated_lines.push(line.to_string());
-        }
-    }
-
-    if replaced_count == 0 {
+    let Some(updated_index) =
+        writer.rename(&index_contents, &current_relative_path, &new_relative_path)
+    else {
         anyhow::bail!(
-            "Could not find an export path for '{}' in index.ts",
+            "Could not find an export path for '{}' in {index_name}",
             current_file_path
         );
-    }
+    };
 
     if let Some(parent) = new_abs_path.parent() {
         fs::create_dir_all(parent)?;
     }
 
     fs::rename(&current_abs_path, &new_abs_path)?;
+    crate::raster::rename_raster_siblings(&current_abs_path, &new_abs_path)?;
 
-    let mut updated_index = updated_lines.join("\n");
-    if index_contents.ends_with('\n') {
-        updated_index.push('\n');
-    }
     if let Err(write_error) = fs::write(&index_path, updated_index) {
         let _ = fs::rename(&new_abs_path, &current_abs_path);
         anyhow::bail!(
-            "Failed to update index.ts after rename: {}. Rolled back file rename.",
+            "Failed to update {index_name} after rename: {}. Rolled back file rename.",
             write_error
         );
     }
@@ -1070,11 +1441,111 @@ pub fn rename_icon_entry(
     Ok(())
 }
 
+/// The file `duplicate_icon_entry` writes for `new_name`: next to
+/// `current_file_path`, with its extension and a stem from the alias in
+/// `filename_case` (lowercased when unset, like `add`).
+pub fn duplicate_filename(
+    current_file_path: &str,
+    new_name: &str,
+    case: Option<FilenameCase>,
+) -> String {
+    let current = normalize_icon_relative_path(current_file_path);
+    let stem = match case {
+        Some(case) => case.apply(new_name.trim()),
+        None => alias_filename(new_name.trim()),
+    };
+    let file_name = match Path::new(&current).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => format!("{stem}.{ext}"),
+        None => stem,
+    };
+    match current.rfind('/') {
+        Some(slash) => format!("{}{file_name}", &current[..=slash]),
+        None => file_name,
+    }
+}
+
+/// Copies the icon at `current_file_path` to `new_file_path` and exports the
+/// copy as `new_name` from the folder's barrel, in the barrel's own style.
+/// Returns the folder-relative path of the copy.
+pub fn duplicate_icon_entry(
+    folder_path: &str,
+    current_file_path: &str,
+    new_name: &str,
+    new_file_path: &str,
+    code_style: &CodeStyle,
+) -> anyhow::Result<String> {
+    use std::fs;
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        anyhow::bail!("Please enter a name for the copy.");
+    }
+    validate_icon_filename(new_file_path)?;
+    let new_relative_path = normalize_icon_relative_path(new_file_path);
+
+    let folder = Path::new(folder_path);
+    let current_abs_path = icon_file_path(folder, current_file_path);
+    if !current_abs_path.exists() {
+        anyhow::bail!("Icon file not found: {}", current_abs_path.display());
+    }
+    let new_abs_path = folder.join(&new_relative_path);
+    if new_abs_path.exists() {
+        anyhow::bail!("Target file already exists: {}", new_abs_path.display());
+    }
+
+    let (writer, index_path) = crate::index_writer::find_barrel(folder);
+    let index_name = writer.file_name();
+    if !index_path.exists() {
+        anyhow::bail!("No {index_name} found in folder: {}", folder.display());
+    }
+    let index_contents = fs::read_to_string(&index_path)?;
+    let export_name = writer.export_name(new_name);
+    if writer
+        .parse(&index_contents)
+        .iter()
+        .any(|entry| entry.name == export_name)
+    {
+        anyhow::bail!("Icon alias '{export_name}' already exists in {index_name}.");
+    }
+
+    let mut entry = writer.render_entry(&export_name, folder_path, &new_relative_path);
+    if matches!(index_name, "index.ts" | "index.js") {
+        entry = format_js_export_for_barrel(
+            &entry,
+            Some(&index_contents),
+            TsExtensionPolicy::from_tsconfig_near(folder),
+            code_style,
+        );
+    }
+    let updated_index = writer.append(&index_contents, &entry);
+
+    if let Some(parent) = new_abs_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&current_abs_path, &new_abs_path)?;
+    if let Err(write_error) = fs::write(&index_path, updated_index) {
+        let _ = fs::remove_file(&new_abs_path);
+        anyhow::bail!(
+            "Failed to update {index_name} after copying: {}. Removed the copy.",
+            write_error
+        );
+    }
+
+    Ok(new_relative_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn entry(name: &str, file_path: &str) -> IconEntry {
+        IconEntry {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+
     #[test]
     fn parses_iconify_name_from_plain_value() {
         assert_eq!(
@@ -1107,6 +1578,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_iconify_name_from_icon_page_urls() {
+        for (url, expected) in [
+            (
+                "https://icones.js.org/collection/lucide?icon=lucide:heart",
+                "lucide:heart",
+            ),
+            (
+                "https://icones.js.org/collection/lucide?icon=heart",
+                "lucide:heart",
+            ),
+            ("https://icon-sets.iconify.design/mdi/home/", "mdi:home"),
+            (
+                "https://icon-sets.iconify.design/mdi/?icon-filter=home&icon=mdi:home-outline",
+                "mdi:home-outline",
+            ),
+        ] {
+            assert_eq!(
+                iconify_name_from_icon_source(url).as_deref(),
+                Some(expected),
+                "{url}"
+            );
+            assert_eq!(
+                _determine_icon_source_type(Some(&url.to_string())),
+                IconSourceType::IconifyName,
+                "{url}"
+            );
+        }
+        assert_eq!(
+            _determine_icon_source_type(Some(&"https://example.com/heart.svg".to_string())),
+            IconSourceType::Url
+        );
+        assert_eq!(
+            iconify_name_from_icon_source("https://icon-sets.iconify.design/mdi/"),
+            None
+        );
+    }
+
     #[test]
     fn derives_name_and_filename_defaults() {
         assert_eq!(
@@ -1120,6 +1629,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn data_uri_escapes_css_breaking_characters() {
+        let uri = svg_to_data_uri("<svg fill=\"#000\">\n  <path d=\"M0 0\"/>\n</svg>");
+        assert_eq!(
+            uri,
+            "data:image/svg+xml,%3Csvg fill='%23000'%3E %3Cpath d='M0 0'/%3E %3C/svg%3E"
+        );
+    }
+
     #[test]
     fn makes_safe_default_filename_for_iconify_names() {
         let icon = "lucide:check".to_string();
@@ -1130,6 +1648,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filename_case_applies_to_inferred_stems_only() {
+        let icon = "lucide:check".to_string();
+        let svg = "<svg/>".to_string();
+        let filename = "My_File".to_string();
+        let case = Some(FilenameCase::Kebab);
+
+        assert_eq!(
+            make_icon_filename(None, ".svg", Some(&icon), "Check", case).0,
+            "lucide-check"
+        );
+        assert_eq!(
+            make_icon_filename(None, ".svg", Some(&svg), "HeartOutline", case).0,
+            "heart-outline"
+        );
+        assert_eq!(
+            make_icon_filename(Some(&filename), ".svg", Some(&svg), "Heart", case).0,
+            "My_File"
+        );
+    }
+
+    #[test]
+    fn matches_icons_by_collection_prefix() {
+        assert!(icon_from_collection("./carbon_heart.svg", "carbon"));
+        assert!(icon_from_collection(
+            "assets/icons/carbon_heart.svg",
+            "carbon:"
+        ));
+        assert!(icon_from_collection("./carbon:heart.svg", "carbon"));
+        assert!(!icon_from_collection("./carbon-extra_heart.svg", "carbon"));
+        assert!(!icon_from_collection("./carbonheart.svg", "carbon"));
+        assert!(!icon_from_collection("./mdi_carbon.svg", "carbon"));
+        assert!(!icon_from_collection("./carbon_heart.svg", ""));
+    }
+
     #[test]
     fn parses_typescript_export_with_double_quotes() {
         let parsed =
@@ -1148,6 +1701,7 @@ mod tests {
             "export { default as IconHeart } from './heart.svg';",
             Some(existing),
             TsExtensionPolicy::Strip,
+            &CodeStyle::default(),
         );
 
         assert_eq!(
@@ -1156,12 +1710,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn formats_js_export_like_a_prettier_barrel() {
+        // Prettier wraps long exports and keeps double quotes; a header
+        // comment with single quotes comes first.
+        let existing = "// Don't edit: 'generated'\nexport {\n  default as IconGithubWithAVeryLongName,\n} from \"./github.svg\";\n";
+
+        let formatted = format_js_export_for_barrel(
+            "export { default as IconHeart } from './heart.svg';",
+            Some(existing),
+            TsExtensionPolicy::Strip,
+            &CodeStyle::default(),
+        );
+
+        assert_eq!(
+            formatted,
+            "export { default as IconHeart } from \"./heart.svg\";"
+        );
+    }
+
+    #[test]
+    fn code_style_overrides_existing_barrel_quotes_and_semicolons() {
+        let existing = "export { default as IconGithub } from './check.svg';\n";
+
+        let formatted = format_js_export_for_barrel(
+            "export { default as IconHeart } from './heart.svg';",
+            Some(existing),
+            TsExtensionPolicy::Strip,
+            &CodeStyle {
+                quotes: Some(crate::code_style::Quote::Double),
+                semicolons: Some(false),
+                indent: None,
+            },
+        );
+
+        assert_eq!(
+            formatted,
+            "export { default as IconHeart } from \"./heart.svg\""
+        );
+    }
+
     #[test]
     fn strips_tsx_extension_when_tsconfig_does_not_allow_it() {
         let formatted = format_js_export_for_barrel(
             "export { default as IconHeart } from './heart.tsx';",
             None,
             TsExtensionPolicy::Strip,
+            &CodeStyle::default(),
         );
 
         assert_eq!(formatted, "export { default as IconHeart } from './heart';");
@@ -1173,6 +1768,7 @@ mod tests {
             "export { default as IconHeart } from './heart.tsx';",
             None,
             TsExtensionPolicy::Allow,
+            &CodeStyle::default(),
         );
 
         assert_eq!(
@@ -1189,6 +1785,7 @@ mod tests {
             "export { default as IconHeart } from './heart.tsx';",
             Some(existing),
             TsExtensionPolicy::Allow,
+            &CodeStyle::default(),
         );
 
         assert_eq!(formatted, "export { default as IconHeart } from './heart';");
@@ -1200,6 +1797,7 @@ mod tests {
             "export { default as IconHeart } from './heart.svg';",
             None,
             TsExtensionPolicy::Strip,
+            &CodeStyle::default(),
         );
 
         assert_eq!(
@@ -1222,7 +1820,15 @@ mod tests {
         )
         .expect("tsconfig should be written");
 
-        let formatted = render_js_export_line(None, temp_dir.path(), "Heart", "heart", ".tsx");
+        let formatted = render_js_export_line(
+            None,
+            temp_dir.path(),
+            "Heart",
+            &AliasStyle::default(),
+            &CodeStyle::default(),
+            "heart",
+            ".tsx",
+        );
 
         assert_eq!(
             formatted,
@@ -1230,6 +1836,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn import_query_survives_barrel_style_reconciliation() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let existing = "export { default as IconGithub } from \"./check.svg\"\n";
+
+        let formatted = render_js_export_line(
+            Some(existing),
+            temp_dir.path(),
+            "Heart",
+            &AliasStyle::default(),
+            &CodeStyle::default(),
+            "heart",
+            &ImportQuery::import_ext(Some(ImportQuery::React), ".svg"),
+        );
+
+        assert_eq!(
+            formatted,
+            "export { default as IconHeart } from \"./heart.svg?react\""
+        );
+        assert_eq!(
+            ImportQuery::import_ext(Some(ImportQuery::Raw), ".tsx"),
+            ".tsx"
+        );
+    }
+
+    #[test]
+    fn alias_style_builds_export_names() {
+        let style = |case, prefix: &str, suffix: &str| AliasStyle {
+            case,
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+            collection_prefix: false,
+        };
+
+        assert_eq!(
+            AliasStyle::default().export_name("ArrowLeft"),
+            "IconArrowLeft"
+        );
+        assert_eq!(
+            style(AliasCase::Pascal, "", "Icon").export_name("Heart"),
+            "HeartIcon"
+        );
+        assert_eq!(
+            style(AliasCase::Camel, "Icon", "").export_name("ArrowLeft"),
+            "iconArrowLeft"
+        );
+        assert_eq!(
+            style(AliasCase::Constant, "Icon", "").export_name("ArrowLeft"),
+            "ICON_ARROW_LEFT"
+        );
+        assert_eq!(
+            style(AliasCase::Constant, "", "").export_name("H1Bold"),
+            "H1_BOLD"
+        );
+    }
+
     #[test]
     fn parses_typescript_export_with_single_quotes() {
         let parsed =
@@ -1262,6 +1924,49 @@ mod tests {
         assert_eq!(parsed.file_path, "./mdi:github.svg");
     }
 
+    #[test]
+    fn parses_hand_edited_barrel_exports() {
+        let contents = "/* eslint-disable */\nexport type { type default as IconType } from \"./type.svg\"\nexport{default as IconTight}from'./tight.svg';\texport { default as IconTab } from './tab.svg'\nexport {\n  default as IconWrapped, // keep\n} from './wrapped.svg';\nexport const IconLazy: LazyExoticComponent<() => JSX.Element> = lazy(() => import('./lazy'));\nexport * from './other';\n";
+        assert_eq!(
+            parse_index_entries(contents)
+                .into_iter()
+                .map(|entry| (entry.name, entry.file_path))
+                .collect::<Vec<_>>(),
+            [
+                ("IconType", "./type.svg"),
+                ("IconTight", "./tight.svg"),
+                ("IconTab", "./tab.svg"),
+                ("IconWrapped", "./wrapped.svg"),
+                ("IconLazy", "./lazy"),
+            ]
+            .map(|(name, path)| (name.to_string(), path.to_string()))
+        );
+    }
+
+    #[test]
+    fn edits_hand_edited_barrels_without_touching_other_text() {
+        let contents = "// Icons, sorted by hand\nexport { default as IconA } from \"./a.svg\"; export { default as IconB } from \"./b.svg\" // b\nexport {\n    default as IconC,\n} from './c.svg';\nexport  *  from   './other';   // untouched\n";
+
+        assert_eq!(
+            remove_ts_exports(contents, "b.svg").unwrap(),
+            "// Icons, sorted by hand\nexport { default as IconA } from \"./a.svg\"; // b\nexport {\n    default as IconC,\n} from './c.svg';\nexport  *  from   './other';   // untouched\n"
+        );
+        assert_eq!(
+            remove_ts_exports(contents, "c.svg").unwrap(),
+            "// Icons, sorted by hand\nexport { default as IconA } from \"./a.svg\"; export { default as IconB } from \"./b.svg\" // b\nexport  *  from   './other';   // untouched\n"
+        );
+        assert_eq!(
+            remove_ts_exports(contents, "a.svg").unwrap(),
+            "// Icons, sorted by hand\nexport { default as IconB } from \"./b.svg\" // b\nexport {\n    default as IconC,\n} from './c.svg';\nexport  *  from   './other';   // untouched\n"
+        );
+        assert_eq!(remove_ts_exports(contents, "other"), None);
+
+        assert_eq!(
+            rename_ts_export_path(contents, "c.svg", "see.svg").unwrap(),
+            contents.replace("'./c.svg'", "'./see.svg'")
+        );
+    }
+
     #[test]
     fn renames_file_and_updates_index_path() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
@@ -1334,6 +2039,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn renames_lazy_export_path() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let icons_folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
+        std::fs::write(icons_folder.join("foo.tsx"), "").expect("old file should be created");
+
+        let index_path = icons_folder.join("index.ts");
+        std::fs::write(
+            &index_path,
+            "import { lazy } from 'react';\nexport const IconFoo = lazy(() => import('./foo'));\n",
+        )
+        .expect("index.ts should be created");
+
+        rename_icon_entry(icons_folder.to_string_lossy().as_ref(), "./foo", "bar")
+            .expect("rename should succeed");
+
+        let index_contents =
+            std::fs::read_to_string(&index_path).expect("index.ts should be readable");
+        assert_eq!(
+            index_contents,
+            "import { lazy } from 'react';\nexport const IconFoo = lazy(() => import('./bar'));\n"
+        );
+    }
+
     #[test]
     fn renames_file_and_preserves_import_suffix() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
@@ -1392,7 +2122,7 @@ mod tests {
     }
 
     #[test]
-    fn delete_icon_entry_preserves_trailing_newline() {
+    fn delete_icon_entries_preserves_trailing_newline() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
         std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
@@ -1409,7 +2139,7 @@ mod tests {
         )
         .expect("index.ts should be created");
 
-        delete_icon_entry(remove_file.to_string_lossy().as_ref())
+        delete_icon_entries(&icons_folder, &[entry("IconRemove", "./remove.svg")])
             .expect("delete should remove icon entry");
 
         let updated_index =
@@ -1423,7 +2153,7 @@ mod tests {
     }
 
     #[test]
-    fn delete_icon_entry_removes_only_exact_export_path() {
+    fn delete_icon_entries_removes_only_exact_export_path() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
         std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
@@ -1440,7 +2170,7 @@ mod tests {
         )
         .expect("index.ts should be created");
 
-        delete_icon_entry(remove_file.to_string_lossy().as_ref())
+        delete_icon_entries(&icons_folder, &[entry("IconRemove", "./remove.svg")])
             .expect("delete should remove only the exact icon entry");
 
         let updated_index =
@@ -1450,32 +2180,143 @@ mod tests {
     }
 
     #[test]
-    fn delete_icon_entry_handles_absolute_dot_segment_path() {
+    fn validates_typed_icon_filenames() {
+        for ok in [
+            "heart",
+            "heart.svg",
+            "mdi:heart.svg",
+            "social/heart",
+            "./heart",
+        ] {
+            assert!(validate_icon_filename(ok).is_ok(), "{ok}");
+        }
+        for (bad, message) in [
+            ("", "can't be empty"),
+            ("   ", "can't be empty"),
+            ("../heart", "leaves the icons folder"),
+            ("social\\..\\..\\heart", "leaves the icons folder"),
+            ("/etc/heart", "is an absolute path"),
+            ("C:\\icons\\heart", "is an absolute path"),
+            (".svg", "has no name"),
+            ("social/", "has no name"),
+        ] {
+            let error = validate_icon_filename(bad).unwrap_err().to_string();
+            assert!(error.contains(message), "{bad}: {error}");
+        }
+    }
+
+    #[test]
+    fn rename_rejects_targets_outside_the_folder() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
-        std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
+        std::fs::create_dir_all(&icons_folder).unwrap();
+        std::fs::write(icons_folder.join("heart.svg"), "<svg></svg>").unwrap();
+        std::fs::write(
+            icons_folder.join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
+        )
+        .unwrap();
 
-        let remove_file = icons_folder.join("remove.svg");
-        std::fs::write(&remove_file, "<svg></svg>").expect("remove icon should be created");
+        for target in ["../heart", "/tmp/heart", ".svg"] {
+            assert!(
+                rename_icon_entry(&icons_folder.to_string_lossy(), "./heart.svg", target).is_err()
+            );
+        }
+        assert!(icons_folder.join("heart.svg").exists());
+        assert!(!temp_dir.path().join("heart.svg").exists());
+    }
 
-        let index_path = icons_folder.join("index.ts");
+    #[test]
+    fn resolves_export_paths_against_the_folder() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let icons_folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(icons_folder.join("social")).unwrap();
+        std::fs::write(icons_folder.join("heart.tsx"), "export default 1;").unwrap();
+
+        assert_eq!(
+            icon_file_path(&icons_folder, "./heart"),
+            icons_folder.join("heart.tsx")
+        );
+        assert_eq!(
+            icon_file_path(&icons_folder, "./social/../star.svg"),
+            icons_folder.join("star.svg")
+        );
+        let absolute = icons_folder.join("social").join("bell.svg");
+        assert_eq!(
+            icon_file_path(&icons_folder, &absolute.to_string_lossy()),
+            absolute
+        );
+
+        assert_eq!(
+            folder_relative_path(&icons_folder, &icons_folder.join("./social/bell.svg")),
+            Some("social/bell.svg".to_string())
+        );
+        assert_eq!(
+            folder_relative_path(Path::new("."), Path::new("./heart.svg")),
+            Some("heart.svg".to_string())
+        );
+        assert_eq!(
+            folder_relative_path(
+                &icons_folder.join("social"),
+                &icons_folder.join("heart.tsx")
+            ),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn renames_through_a_symlinked_folder() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let icons_folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(&icons_folder).unwrap();
+        std::fs::write(icons_folder.join("heart.svg"), "<svg></svg>").unwrap();
         std::fs::write(
-            &index_path,
-            "export { default as IconRemove } from './remove.svg';\n",
+            icons_folder.join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\n",
         )
-        .expect("index.ts should be created");
+        .unwrap();
+        let link = temp_dir.path().join("linked-icons");
+        std::os::unix::fs::symlink(&icons_folder, &link).unwrap();
 
-        let absolute_with_dot = icons_folder.join("./remove.svg");
-        delete_icon_entry(absolute_with_dot.to_string_lossy().as_ref())
-            .expect("delete should accept absolute paths with dot segments");
+        // The folder is opened through the link, the file is named by its real path.
+        let real_file = std::fs::canonicalize(icons_folder.join("heart.svg")).unwrap();
+        assert_eq!(
+            folder_relative_path(&link, &real_file),
+            Some("heart.svg".to_string())
+        );
+        rename_icon_entry(
+            &link.to_string_lossy(),
+            &real_file.to_string_lossy(),
+            "love",
+        )
+        .expect("rename should match the export through the link");
 
-        let updated_index =
-            std::fs::read_to_string(&index_path).expect("index.ts should be readable");
-        assert!(!updated_index.contains("IconRemove"));
+        assert!(icons_folder.join("love.svg").exists());
+        assert_eq!(
+            std::fs::read_to_string(icons_folder.join("index.ts")).unwrap(),
+            "export { default as IconHeart } from './love.svg';\n"
+        );
     }
 
+    #[cfg(windows)]
     #[test]
-    fn delete_icon_entry_updates_index_even_if_file_is_missing() {
+    fn folder_relative_paths_use_forward_slashes() {
+        assert_eq!(
+            folder_relative_path(
+                Path::new(r"C:\project\icons"),
+                Path::new(r"C:\project\icons\.\social\heart.svg")
+            ),
+            Some("social/heart.svg".to_string())
+        );
+        assert_eq!(
+            icon_file_path(Path::new(r"C:\project\icons"), "./social/../heart.svg"),
+            Path::new(r"C:\project\icons\heart.svg")
+        );
+    }
+
+    #[test]
+    fn delete_icon_entries_updates_index_even_if_file_is_missing() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
         std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
@@ -1489,8 +2330,15 @@ mod tests {
         )
         .expect("index.ts should be created");
 
-        delete_icon_entry(missing_file.to_string_lossy().as_ref())
-            .expect("delete should remove stale export when file is missing");
+        delete_icon_entries(
+            &icons_folder,
+            &[entry(
+                "IconDarkTheme24Regular",
+                "./fluent:dark-theme-24-regular.svg",
+            )],
+        )
+        .expect("delete should remove stale export when file is missing");
+        assert!(!missing_file.exists());
 
         let updated_index =
             std::fs::read_to_string(&index_path).expect("index.ts should be readable");
@@ -1499,7 +2347,7 @@ mod tests {
     }
 
     #[test]
-    fn delete_icon_entry_removes_target_from_concatenated_export_line() {
+    fn delete_icon_entries_removes_target_from_concatenated_export_line() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
         std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
@@ -1516,7 +2364,7 @@ mod tests {
         )
         .expect("index.ts should be created");
 
-        delete_icon_entry(remove_file.to_string_lossy().as_ref())
+        delete_icon_entries(&icons_folder, &[entry("IconRemove", "./remove.svg")])
             .expect("delete should remove export from concatenated line");
 
         let updated_index =
@@ -1567,7 +2415,89 @@ mod tests {
     }
 
     #[test]
-    fn delete_icon_entry_resolves_extensionless_tsx_export() {
+    fn duplicates_next_to_the_source_in_the_barrels_style() {
+        assert_eq!(
+            duplicate_filename("./social/heart.svg", "IconHeartFilled", None),
+            "social/iconheartfilled.svg"
+        );
+        assert_eq!(
+            duplicate_filename("heart.tsx", "HeartFilled", Some(FilenameCase::Kebab)),
+            "heart-filled.tsx"
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path();
+        std::fs::write(
+            folder.join("mod.rs"),
+            "pub const HEART: &str = include_str!(\"heart.svg\");\n",
+        )
+        .unwrap();
+        std::fs::write(folder.join("heart.svg"), "<svg/>").unwrap();
+
+        let copied = duplicate_icon_entry(
+            &folder.to_string_lossy(),
+            "heart.svg",
+            "HeartFilled",
+            "heart_filled.svg",
+            &CodeStyle::default(),
+        )
+        .unwrap();
+        assert_eq!(copied, "heart_filled.svg");
+        assert!(folder.join("heart_filled.svg").is_file());
+        let names = crate::index_writer::IndexStrategy::Rust
+            .writer()
+            .parse(&std::fs::read_to_string(folder.join("mod.rs")).unwrap())
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["HEART", "HEART_FILLED"]);
+
+        let error = duplicate_icon_entry(
+            &folder.to_string_lossy(),
+            "heart.svg",
+            "heart_filled",
+            "heart-again.svg",
+            &CodeStyle::default(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+        assert!(!folder.join("heart-again.svg").exists());
+    }
+
+    #[test]
+    fn delete_icon_entries_removes_exactly_the_selected_exports() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let folder = temp_dir.path();
+        for file in ["heart.svg", "heart-fill.svg", "star.svg"] {
+            std::fs::write(folder.join(file), "<svg></svg>").expect("icon should be created");
+        }
+        std::fs::write(
+            folder.join("index.ts"),
+            "export { default as IconHeartFill } from './heart-fill.svg';\nexport { default as IconHeart } from './heart.svg';\nexport { default as IconStarAlias } from './star.svg';\nexport { default as IconStar } from './star.svg';\n",
+        )
+        .expect("index.ts should be created");
+
+        delete_icon_entries(
+            folder,
+            &[
+                entry("IconHeart", "./heart.svg"),
+                entry("IconStar", "./star.svg"),
+            ],
+        )
+        .expect("delete should succeed");
+
+        assert_eq!(
+            std::fs::read_to_string(folder.join("index.ts")).unwrap(),
+            "export { default as IconHeartFill } from './heart-fill.svg';\nexport { default as IconStarAlias } from './star.svg';\n"
+        );
+        assert!(!folder.join("heart.svg").exists());
+        assert!(folder.join("heart-fill.svg").exists());
+        // IconStarAlias still imports it.
+        assert!(folder.join("star.svg").exists());
+    }
+
+    #[test]
+    fn delete_icon_entries_resolves_extensionless_tsx_export() {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let icons_folder = temp_dir.path().join("icons");
         std::fs::create_dir_all(&icons_folder).expect("icons folder should be created");
@@ -1581,7 +2511,7 @@ mod tests {
         )
         .expect("index.ts should be created");
 
-        delete_icon_entry(icons_folder.join("heart").to_string_lossy().as_ref())
+        delete_icon_entries(&icons_folder, &[entry("IconHeart", "./heart")])
             .expect("delete should resolve heart.tsx");
 
         assert!(!remove_file.exists());
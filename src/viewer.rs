@@ -7,6 +7,8 @@ use std::hash::{Hash, Hasher};
 use std::path::Path;
 use std::process::{Command, Stdio};
 
+use crate::template;
+
 #[derive(Debug, Clone)]
 pub enum OpenSvgOutcome {
     OpenedWithCustomCommand,
@@ -15,12 +17,31 @@ pub enum OpenSvgOutcome {
     OpenedWithWebPreview(String),
 }
 
-fn svg_preview_contents(contents: &str) -> anyhow::Result<String> {
+/// The `<svg>` element of an icon file (SVG or component) as a standalone SVG.
+pub fn svg_preview_contents(contents: &str) -> anyhow::Result<String> {
     let svg = extract_svg_fragment(contents)
         .ok_or_else(|| anyhow::anyhow!("No <svg> element found in selected icon."))?;
     Ok(ensure_svg_xmlns(&sanitize_svg_for_browser(svg)))
 }
 
+/// Whether `svg` was copied out of JSX rather than an SVG file: `className`,
+/// camelCase presentation attributes, or `{...}` expressions.
+pub fn looks_like_jsx(svg: &str) -> bool {
+    svg.contains("={")
+        || Regex::new(r"\{\s*\.\.\.")
+            .expect("valid JSX spread regex")
+            .is_match(svg)
+        || JSX_SVG_ATTRIBUTE_REPLACEMENTS
+            .iter()
+            .any(|(jsx, _)| svg.contains(jsx))
+}
+
+/// Turns JSX `<svg>` markup back into a standalone SVG document, with the
+/// same rewrites as the browser preview.
+pub fn jsx_to_svg(svg: &str) -> String {
+    ensure_svg_xmlns(&sanitize_svg_for_browser(svg))
+}
+
 fn extract_svg_fragment(contents: &str) -> Option<&str> {
     let full_svg = Regex::new(r"(?is)<svg\b[^>]*>.*?</svg>").ok()?;
     if let Some(found) = full_svg.find(contents) {
@@ -182,7 +203,83 @@ const JSX_SVG_ATTRIBUTE_REPLACEMENTS: &[(&str, &str)] = &[
     ("className=", "class="),
 ];
 
-pub fn preview_svg_in_browser(svg_path: &Path) -> anyhow::Result<()> {
+/// An `#rrggbb` color from `preview_colors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// `#rrggbb` or `#rgb`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let hex = value.trim().strip_prefix('#')?;
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        match hex.len() {
+            6 => Some(Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => {
+                let short = |i: usize| channel(&hex[i..=i]).map(|value| value * 17);
+                Some(Rgb(short(0)?, short(1)?, short(2)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// `preview_colors`: what `currentColor` and the canvas are in previews, so
+/// monochrome icons aren't drawn black on a dark background. Icon files
+/// themselves are never changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PreviewColors {
+    pub foreground: Option<Rgb>,
+    pub background: Option<Rgb>,
+}
+
+impl PreviewColors {
+    /// `svg` with its root element styled: `color` for `currentColor`
+    /// and a `background`. A `style` the icon already has still wins.
+    pub fn apply(&self, svg: &str) -> String {
+        let mut declarations = String::new();
+        if let Some(foreground) = self.foreground {
+            declarations.push_str(&format!("color:{foreground};"));
+        }
+        if let Some(background) = self.background {
+            declarations.push_str(&format!("background:{background};"));
+        }
+        if declarations.is_empty() {
+            return svg.to_string();
+        }
+        let Some(root) = Regex::new(r"(?is)<svg\b[^>]*>")
+            .expect("valid SVG root regex")
+            .find(svg)
+        else {
+            return svg.to_string();
+        };
+        let style = Regex::new(r#"(?i)\sstyle\s*=\s*["']"#).expect("valid style regex");
+        let insert_at;
+        let insertion;
+        if let Some(existing) = style.find(root.as_str()) {
+            insert_at = root.start() + existing.end();
+            insertion = declarations;
+        } else {
+            insert_at = root.start() + "<svg".len();
+            insertion = format!(" style=\"{declarations}\"");
+        }
+        format!("{}{insertion}{}", &svg[..insert_at], &svg[insert_at..])
+    }
+}
+
+pub fn preview_svg_in_browser(svg_path: &Path, colors: &PreviewColors) -> anyhow::Result<()> {
     let resolved_path = crate::utils::resolve_existing_icon_path(svg_path);
     let svg_path = resolved_path.as_path();
 
@@ -192,7 +289,7 @@ pub fn preview_svg_in_browser(svg_path: &Path) -> anyhow::Result<()> {
 
     let contents = fs::read_to_string(svg_path)
         .with_context(|| format!("Failed to read icon file {}", svg_path.display()))?;
-    let preview_svg = svg_preview_contents(&contents)?;
+    let preview_svg = colors.apply(&svg_preview_contents(&contents)?);
     let preview_path = preview_file_path(svg_path, &preview_svg);
 
     fs::write(&preview_path, preview_svg)
@@ -204,9 +301,39 @@ pub fn preview_svg_in_browser(svg_path: &Path) -> anyhow::Result<()> {
         .with_context(|| format!("Failed to open preview SVG {}", preview_path.display()))
 }
 
+/// The command for `path`: the `viewer_cmds` entry with the longest matching
+/// extension (`.svg.html` before `.html`; later entries win a tie), else
+/// `svg_viewer_cmd`.
+pub fn viewer_cmd_for<'a>(
+    path: &Path,
+    svg_viewer_cmd: Option<&'a str>,
+    viewer_cmds: &'a [(String, String)],
+) -> Option<&'a str> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    viewer_cmds
+        .iter()
+        .filter(|(extension, _)| {
+            file_name
+                .strip_suffix(extension.as_str())
+                .is_some_and(|stem| stem.ends_with('.'))
+        })
+        .max_by_key(|(extension, _)| extension.len())
+        .map(|(_, command)| command.as_str())
+        .or(svg_viewer_cmd)
+}
+
+/// Opens an icon file: the viewer command for its extension, then the OS
+/// viewer, then the Iconify web preview. `source` is where the icon came
+/// from, as recorded in the history log, so renamed files and component
+/// outputs still get a web preview.
 pub fn open_svg_with_fallback(
     svg_path: &Path,
     svg_viewer_cmd: Option<&str>,
+    viewer_cmds: &[(String, String)],
+    source: Option<&str>,
 ) -> anyhow::Result<OpenSvgOutcome> {
     let resolved_path = crate::utils::resolve_existing_icon_path(svg_path);
     let svg_path = resolved_path.as_path();
@@ -216,6 +343,7 @@ pub fn open_svg_with_fallback(
     }
 
     let mut errors = Vec::<String>::new();
+    let svg_viewer_cmd = viewer_cmd_for(svg_path, svg_viewer_cmd, viewer_cmds);
 
     if let Some(command_template) = svg_viewer_cmd {
         match open_with_custom_command(command_template, svg_path) {
@@ -224,7 +352,7 @@ pub fn open_svg_with_fallback(
         }
     }
 
-    match open_with_os_default(svg_path) {
+    match open_with_os_default(&os_viewer_path(svg_path)) {
         Ok(()) => {
             if svg_viewer_cmd.is_some() {
                 return Ok(OpenSvgOutcome::OpenedWithOsDefaultAfterCustomFailure);
@@ -234,7 +362,7 @@ pub fn open_svg_with_fallback(
         Err(error) => errors.push(format!("OS default open failed: {error}")),
     }
 
-    if let Some(web_preview_url) = iconify_web_preview_url(svg_path) {
+    if let Some(web_preview_url) = iconify_web_preview_url(svg_path, source) {
         open_url_in_browser(&web_preview_url).with_context(|| {
             format!(
                 "Failed to open web preview URL after local open failures: {}",
@@ -268,12 +396,11 @@ fn open_with_custom_command(command_template: &str, svg_path: &Path) -> anyhow::
     }
 
     let file_name = svg_path.to_string_lossy().to_string();
+    let vars = viewer_template_vars(svg_path);
     let mut used_placeholder = false;
     for part in &mut parts {
-        if part.contains("%filename%") {
-            *part = part.replace("%filename%", &file_name);
-            used_placeholder = true;
-        }
+        used_placeholder |= template::references(part, "filename");
+        *part = template::render(part, &vars);
     }
 
     if !used_placeholder {
@@ -285,6 +412,30 @@ fn open_with_custom_command(command_template: &str, svg_path: &Path) -> anyhow::
         .with_context(|| format!("Failed to run svg_viewer_cmd '{}'.", command_template))
 }
 
+/// Variables of `svg_viewer_cmd` and `viewer_cmds`.
+pub const VIEWER_CMD_VARS: &[&str] = &["filename", "name", "ext", "folder"];
+
+/// `%filename%` is the full path; `%name%`, `%ext%` and `%folder%` are its parts.
+fn viewer_template_vars(svg_path: &Path) -> template::Vars {
+    let stem = svg_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = svg_path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let folder = svg_path
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    template::Vars::new()
+        .with("filename", svg_path.to_string_lossy())
+        .with("name", stem)
+        .with("ext", ext)
+        .with("folder", folder)
+}
+
 fn spawn_background(executable: &str, args: &[String]) -> anyhow::Result<()> {
     Command::new(executable)
         .args(args)
@@ -340,13 +491,76 @@ fn open_with_os_default(svg_path: &Path) -> anyhow::Result<()> {
     }
 }
 
-fn iconify_web_preview_url(svg_path: &Path) -> Option<String> {
-    let stem = svg_path.file_stem()?.to_string_lossy();
-    let icon_name = crate::utils::iconify_name_from_icon_source(stem.as_ref())?;
+/// What the OS viewer gets: an SVG file as is, or the inline `<svg>` of a
+/// component (`.tsx`, `.svelte`, `.vue`) rendered to a temp file, so it shows
+/// the icon rather than its source. Falls back to the file itself.
+fn os_viewer_path(svg_path: &Path) -> std::path::PathBuf {
+    let is_svg = svg_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    if is_svg {
+        return svg_path.to_path_buf();
+    }
+    let Some(preview_svg) = fs::read_to_string(svg_path)
+        .ok()
+        .and_then(|contents| svg_preview_contents(&contents).ok())
+    else {
+        return svg_path.to_path_buf();
+    };
+    let preview_path = preview_file_path(svg_path, &preview_svg);
+    match fs::write(&preview_path, preview_svg) {
+        Ok(()) => preview_path,
+        Err(_) => svg_path.to_path_buf(),
+    }
+}
+
+/// The Iconify name (`mdi:heart`) of the icon at `svg_path`: from its
+/// recorded `source` when that names an Iconify icon, otherwise from an
+/// Iconify-named file stem.
+pub fn iconify_icon_name(svg_path: &Path, source: Option<&str>) -> Option<String> {
+    source
+        .and_then(crate::utils::iconify_name_from_icon_source)
+        .or_else(|| {
+            let stem = svg_path.file_stem()?.to_string_lossy();
+            crate::utils::iconify_name_from_icon_source(stem.as_ref())
+        })
+}
+
+/// The Iconify API URL of the icon, named as in [`iconify_icon_name`].
+fn iconify_web_preview_url(svg_path: &Path, source: Option<&str>) -> Option<String> {
+    let icon_name = iconify_icon_name(svg_path, source)?;
     let encoded = icon_name.replace(':', "%3A");
     Some(format!("https://api.iconify.design/{encoded}.svg"))
 }
 
+/// Shows `folder` in Finder, Explorer or the xdg file manager.
+pub fn open_folder_in_file_manager(folder: &Path) -> anyhow::Result<()> {
+    if !folder.is_dir() {
+        anyhow::bail!("Icons folder not found: {}", folder.display());
+    }
+    let folder_name = folder.to_string_lossy().to_string();
+
+    #[cfg(target_os = "macos")]
+    {
+        spawn_background("open", &[folder_name]).context("Failed to open folder via open")
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        spawn_background("xdg-open", &[folder_name]).context("Failed to open folder via xdg-open")
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        spawn_background("explorer", &[folder_name]).context("Failed to open folder via explorer")
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        anyhow::bail!("Unsupported OS for file manager opener")
+    }
+}
+
 pub fn open_url_in_browser(url: &str) -> anyhow::Result<()> {
     #[cfg(target_os = "macos")]
     {
@@ -384,15 +598,74 @@ mod tests {
     fn builds_iconify_web_preview_url_from_iconify_stem() {
         let path = Path::new("/tmp/mdi:heart.svg");
         assert_eq!(
-            iconify_web_preview_url(path),
+            iconify_web_preview_url(path, None),
             Some("https://api.iconify.design/mdi%3Aheart.svg".to_string())
         );
     }
 
+    #[test]
+    fn picks_the_viewer_command_by_extension() {
+        let viewer_cmds = vec![
+            ("tsx".to_string(), "code %filename%".to_string()),
+            ("html".to_string(), "firefox".to_string()),
+            ("svg.html".to_string(), "vim".to_string()),
+            ("tsx".to_string(), "zed".to_string()),
+        ];
+        let pick = |file: &str| viewer_cmd_for(Path::new(file), Some("qlmanage -p"), &viewer_cmds);
+        assert_eq!(pick("/icons/Heart.TSX"), Some("zed"));
+        assert_eq!(pick("/icons/_heart.svg.html"), Some("vim"));
+        assert_eq!(pick("/icons/page.html"), Some("firefox"));
+        assert_eq!(pick("/icons/heart.svg"), Some("qlmanage -p"));
+        assert_eq!(pick("/icons/xtsx"), Some("qlmanage -p"));
+        assert_eq!(viewer_cmd_for(Path::new("a.svg"), None, &[]), None);
+    }
+
+    #[test]
+    fn builds_web_preview_url_from_recorded_source() {
+        let path = Path::new("/tmp/IconHeart.tsx");
+        assert_eq!(iconify_web_preview_url(path, None), None);
+        assert_eq!(
+            iconify_web_preview_url(
+                path,
+                Some("https://icones.js.org/collection/mdi?icon=heart")
+            ),
+            Some("https://api.iconify.design/mdi%3Aheart.svg".to_string())
+        );
+        assert_eq!(
+            iconify_web_preview_url(Path::new("/tmp/lucide:x.svg"), Some("<svg/>")),
+            Some("https://api.iconify.design/lucide%3Ax.svg".to_string())
+        );
+    }
+
+    #[test]
+    fn renders_component_files_to_svg_for_the_os_viewer() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let svg = dir.path().join("heart.svg");
+        fs::write(&svg, "<svg/>").unwrap();
+        assert_eq!(os_viewer_path(&svg), svg);
+
+        let component = dir.path().join("Heart.tsx");
+        fs::write(
+            &component,
+            "export default () => <svg viewBox=\"0 0 24 24\" strokeWidth={2}><path d=\"M0 0\" /></svg>;",
+        )
+        .unwrap();
+        let preview = os_viewer_path(&component);
+        assert_eq!(preview.extension().unwrap(), "svg");
+        let rendered = fs::read_to_string(&preview).unwrap();
+        assert!(rendered.contains("stroke-width=\"2\""));
+        assert!(rendered.contains("xmlns=\"http://www.w3.org/2000/svg\""));
+        fs::remove_file(preview).unwrap();
+
+        let data_uri = dir.path().join("heart.ts");
+        fs::write(&data_uri, "export default 'data:image/svg+xml,...';").unwrap();
+        assert_eq!(os_viewer_path(&data_uri), data_uri);
+    }
+
     #[test]
     fn returns_none_for_non_iconify_stem() {
         let path = Path::new("/tmp/logo.svg");
-        assert_eq!(iconify_web_preview_url(path), None);
+        assert_eq!(iconify_web_preview_url(path, None), None);
     }
 
     #[test]
@@ -418,6 +691,19 @@ export default function Icon(props: SVGProps<SVGSVGElement>) {
         assert!(!svg.contains("strokeWidth"));
     }
 
+    #[test]
+    fn converts_pasted_jsx_svg_to_standalone_svg() {
+        let jsx = r#"<svg {...props} className="size-4" strokeWidth={2} viewBox="0 0 24 24"><path strokeLinecap="round" d="M0 0" /></svg>"#;
+        assert!(looks_like_jsx(jsx));
+        assert_eq!(
+            jsx_to_svg(jsx),
+            r#"<svg class="size-4" stroke-width="2" viewBox="0 0 24 24" xmlns="http://www.w3.org/2000/svg"><path stroke-linecap="round" d="M0 0" /></svg>"#
+        );
+
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><clipPath id="a"><path stroke-width="2" d="M0 0"/></clipPath></svg>"#;
+        assert!(!looks_like_jsx(svg));
+    }
+
     #[test]
     fn sanitizes_svelte_and_vue_dynamic_bindings() {
         let svelte = r#"
@@ -449,4 +735,22 @@ export default function Icon(props: SVGProps<SVGSVGElement>) {
         let error = svg_preview_contents("export default null").unwrap_err();
         assert!(error.to_string().contains("No <svg> element"));
     }
+
+    #[test]
+    fn preview_colors_style_the_root_svg() {
+        let colors = PreviewColors {
+            foreground: Rgb::parse("#E5E7EB"),
+            background: Rgb::parse("#123"),
+        };
+        assert_eq!(
+            colors.apply(r#"<svg viewBox="0 0 24 24"><path fill="currentColor"/></svg>"#),
+            r#"<svg style="color:#e5e7eb;background:#112233;" viewBox="0 0 24 24"><path fill="currentColor"/></svg>"#
+        );
+        assert_eq!(
+            colors.apply(r#"<svg style="color:red"><g style="x"/></svg>"#),
+            r#"<svg style="color:#e5e7eb;background:#112233;color:red"><g style="x"/></svg>"#
+        );
+        assert_eq!(PreviewColors::default().apply("<svg/>"), "<svg/>");
+        assert_eq!(Rgb::parse("e5e7eb"), None);
+    }
 }
@@ -1,10 +1,13 @@
+use std::path::Path;
 use std::process::Command;
+use std::time::{Duration, Instant};
 
-use crate::app_state::{App, AppFocus};
-use crate::utils::{PRESETS_OPTIONS, Preset, PresetOption, popup_area};
+use crate::app_state::{App, AppEvent, AppFocus, InFlight};
+use crate::utils::{AliasCase, AliasStyle, PRESETS_OPTIONS, Preset, PresetOption, popup_area};
+use crate::views::layout::single_line;
 use ratatui::Frame;
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
-use ratatui::layout::{Alignment, Constraint};
+use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, ListItem, Paragraph};
@@ -18,14 +21,22 @@ const FILENAME_FIELD_IDX: usize = 2;
 const NAME_FIELD_IDX: usize = 3;
 const SUBMIT_FIELD_IDX: usize = 4;
 
+/// Wait for typing to pause before fetching a preview.
+const PREVIEW_DEBOUNCE_MS: u64 = 400;
+/// Preview raster size: 12x6 braille cells, roughly square on screen.
+const PREVIEW_PIXELS: usize = 24;
+/// Preview column width, border included.
+const PREVIEW_WIDTH: u16 = 16;
+
 /// Preview the call-site shape the user will see once this icon is added.
 /// For Flutter, show `AppIcons.chevronRight` using the configured class and
-/// the name normalized to lowerCamelCase. For every other preset, keep the
-/// existing `<Icon{} />` hint (the JS presets all consume it via an `Icon` prefix).
+/// the name normalized to lowerCamelCase. For every other preset, show the
+/// export name the configured alias style produces (`<IconHeart />` by default).
 fn usage_hint_for_preset(
     preset: Option<&Preset>,
     raw_name: &str,
     flutter_barrel_class: Option<&str>,
+    alias_style: &AliasStyle,
 ) -> String {
     if matches!(preset, Some(Preset::Flutter)) {
         let class = flutter_barrel_class.unwrap_or(crate::flutter::DEFAULT_FLUTTER_BARREL_CLASS);
@@ -37,9 +48,86 @@ fn usage_hint_for_preset(
             Err(_) => format!("usage: {}.{{}}", class),
         }
     } else if raw_name.trim().is_empty() {
-        String::from("usage: <Icon{} />")
+        match alias_style.case {
+            AliasCase::Pascal => format!(
+                "usage: <{}{{}}{} />",
+                alias_style.prefix, alias_style.suffix
+            ),
+            _ => format!("usage: <{} />", alias_style.export_name("Name")),
+        }
     } else {
-        format!("usage: <Icon{} />", raw_name)
+        format!("usage: <{} />", alias_style.export_name(raw_name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AddPreview {
+    Empty,
+    Loading,
+    Ready(Vec<String>),
+    Failed(String),
+}
+
+pub fn preview_from_svg(svg: &str) -> AddPreview {
+    match crate::svg_render::rasterize(svg, PREVIEW_PIXELS, PREVIEW_PIXELS) {
+        Ok(bitmap) if bitmap.is_blank() => AddPreview::Failed("Nothing to draw".to_string()),
+        Ok(bitmap) => AddPreview::Ready(bitmap.to_braille_lines()),
+        Err(error) => AddPreview::Failed(error.to_string()),
+    }
+}
+
+/// Fetches the SVG behind a typed-in icon source without the CLI's progress
+/// output, which would draw over the TUI.
+async fn fetch_preview_svg(source: &str) -> anyhow::Result<String> {
+    if let Some(iconify_name) = crate::utils::iconify_name_from_icon_source(source) {
+        let client = crate::iconify::IconifyClient::from_env()?;
+        return Ok(client.svg(&iconify_name).await?);
+    }
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let response = crate::http::get(source).await?;
+        return Ok(response.text().await?);
+    }
+    anyhow::bail!("Not an Iconify name, URL or SVG")
+}
+
+/// Full-size editor for multi-line SVG markup, drawn over the Add popup.
+#[derive(Debug)]
+pub struct SvgEditorState {
+    pub textarea: TextArea<'static>,
+    pub error: Option<String>,
+}
+
+/// What a failed `iconmate add` run printed, for the popup's status line.
+pub fn add_command_error(output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !stderr.is_empty() {
+        stderr
+    } else if !stdout.is_empty() {
+        stdout
+    } else {
+        "Failed to add icon".to_string()
+    }
+}
+
+/// Short description of a collapsed SVG for the icon source field.
+fn svg_chip_label(svg: &str) -> String {
+    let lines = svg.lines().count();
+    let size = if svg.len() >= 1024 {
+        format!("{:.1} KB", svg.len() as f64 / 1024.0)
+    } else {
+        format!("{} B", svg.len())
+    };
+    match crate::svg_render::summarize(svg) {
+        Ok(summary) => {
+            let (_, _, width, height) = summary.view_box;
+            format!(
+                "[SVG {width}×{height} · {} shape{} · {lines} lines · {size}]",
+                summary.shapes,
+                if summary.shapes == 1 { "" } else { "s" },
+            )
+        }
+        Err(_) => format!("[SVG · {lines} lines · {size}]"),
     }
 }
 
@@ -62,6 +150,26 @@ pub struct AddPopupState {
     pub preset_filter: String,
     pub status_message: Option<String>,
     pub status_is_error: bool,
+
+    pub preview: AddPreview,
+    /// Icon source the preview belongs to (or is being fetched for).
+    preview_source: String,
+    preview_deadline: Option<Instant>,
+    preview_request_id: u64,
+    /// The preview download, aborted when the source changes or the popup
+    /// closes.
+    preview_task: InFlight,
+    /// The export file as of opening the popup, so the export line preview
+    /// matches its quote/semicolon style without rereading it every frame.
+    index_contents: Option<String>,
+    /// The group the icon goes in: the one selected in the tree when the
+    /// popup opened, if it has its own `index.ts`.
+    pub group: Option<String>,
+
+    /// Multi-line SVG that stands in for the icon source text; the field
+    /// shows a summary chip while this is set.
+    pub pasted_svg: Option<String>,
+    pub svg_editor: Option<SvgEditorState>,
 }
 impl AddPopupState {
     fn is_paste_shortcut(input: &Input) -> bool {
@@ -69,13 +177,125 @@ impl AddPopupState {
     }
 
     fn paste_into_current_input(&mut self) -> bool {
-        if let Ok(mut ctx) = arboard::Clipboard::new() {
-            if let Ok(text) = ctx.get_text() {
-                self.inputs[self.current_input].insert_str(&text);
-                return true;
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            Ok(text) => {
+                self.paste_text(&text);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Inserts pasted text (bracketed paste or the clipboard) into the focused
+    /// field. Multi-line text in the icon field is taken as SVG markup.
+    pub fn paste_text(&mut self, text: &str) {
+        if let Some(editor) = self.svg_editor.as_mut() {
+            editor.textarea.insert_str(text);
+            editor.error = None;
+            return;
+        }
+        match self.current_input {
+            ICON_FIELD_IDX if text.trim().contains('\n') => self.accept_multiline_svg(text.trim()),
+            ICON_FIELD_IDX => {
+                // A one-line source replaces a collapsed SVG.
+                self.pasted_svg = None;
+                self.inputs[ICON_FIELD_IDX].insert_str(text.trim());
+            }
+            PRESET_FIELD_IDX => {
+                self.inputs[PRESET_FIELD_IDX].insert_str(single_line(text));
+                self.refilter_presets();
             }
+            SUBMIT_FIELD_IDX => return,
+            index => {
+                self.inputs[index].insert_str(single_line(text));
+            }
+        }
+        self.clear_status();
+    }
+
+    /// The icon source as submitted: the collapsed SVG if there is one,
+    /// otherwise the field's text.
+    fn icon_source(&self) -> String {
+        match &self.pasted_svg {
+            Some(svg) => svg.clone(),
+            None => self.inputs[ICON_FIELD_IDX]
+                .lines()
+                .join("\n")
+                .trim()
+                .to_string(),
+        }
+    }
+
+    /// Collapses valid SVG markup into the icon field's chip. Anything that
+    /// doesn't validate opens in the editor with the error so it can be fixed.
+    fn accept_multiline_svg(&mut self, svg: &str) {
+        let svg = if crate::viewer::looks_like_jsx(svg) {
+            crate::viewer::jsx_to_svg(svg)
+        } else {
+            svg.to_string()
+        };
+        match crate::svg_render::summarize(&svg) {
+            Ok(_) => {
+                self.pasted_svg = Some(svg.clone());
+                self.inputs[ICON_FIELD_IDX] = TextArea::default();
+                self.svg_editor = None;
+                self.apply_icon_based_defaults();
+            }
+            Err(error) => self.open_svg_editor(&svg, Some(error.to_string())),
+        }
+    }
+
+    fn open_svg_editor(&mut self, svg: &str, error: Option<String>) {
+        let mut textarea = TextArea::from(svg.lines().map(str::to_string));
+        textarea.set_cursor_line_style(Style::default());
+        textarea
+            .set_line_number_style(Style::default().fg(crate::views::theme::palette().subtle_text));
+        self.svg_editor = Some(SvgEditorState { textarea, error });
+    }
+
+    fn handlekeys_svg_editor(&mut self, input: Input) {
+        let Some(editor) = self.svg_editor.as_mut() else {
+            return;
+        };
+        match input {
+            Input { key: Key::Esc, .. } => self.svg_editor = None,
+            Input {
+                key: Key::Char('s'),
+                ctrl: true,
+                ..
+            } => {
+                let svg = editor.textarea.lines().join("\n").trim().to_string();
+                if svg.is_empty() {
+                    self.pasted_svg = None;
+                    self.svg_editor = None;
+                } else {
+                    self.accept_multiline_svg(&svg);
+                }
+            }
+            input if Self::is_paste_shortcut(&input) => {
+                self.paste_into_current_input();
+            }
+            input => {
+                editor.textarea.input(input);
+                editor.error = None;
+            }
+        }
+    }
+
+    /// Keys for the icon field while it shows a collapsed SVG chip.
+    fn handlekeys_svg_chip(&mut self, input: Input) {
+        match input.key {
+            Key::Tab => self.move_focus(input.shift),
+            Key::Enter => {
+                let svg = self.pasted_svg.clone().unwrap_or_default();
+                self.open_svg_editor(&svg, None);
+            }
+            Key::Backspace | Key::Delete => {
+                self.pasted_svg = None;
+                self.clear_status();
+            }
+            _ => {}
         }
-        false
     }
 
     fn clear_status(&mut self) {
@@ -88,9 +308,28 @@ impl AddPopupState {
         self.status_is_error = true;
     }
 
+    fn set_status_info(&mut self, message: String) {
+        self.status_message = Some(message);
+        self.status_is_error = false;
+    }
+
+    /// Clears the per-icon fields after a successful add so the next icon can
+    /// be entered straight away. The preset stays as selected.
+    fn reset_for_next_icon(&mut self) {
+        for index in [ICON_FIELD_IDX, FILENAME_FIELD_IDX, NAME_FIELD_IDX] {
+            self.inputs[index] = TextArea::default();
+        }
+        self.icon = None;
+        self.filename = None;
+        self.name = None;
+        self.pasted_svg = None;
+        self.current_input = ICON_FIELD_IDX;
+        self.sync_cursor(ICON_FIELD_IDX);
+    }
+
     fn apply_icon_based_defaults(&mut self) {
-        let icon_raw = self.inputs[ICON_FIELD_IDX].lines().join("\n");
-        let icon_source = icon_raw.trim();
+        let icon_raw = self.icon_source();
+        let icon_source = icon_raw.as_str();
         if icon_source.is_empty() {
             return;
         }
@@ -122,14 +361,80 @@ impl AddPopupState {
         }
     }
 
+    fn effective_preset(&self) -> Preset {
+        self.preset
+            .clone()
+            .or_else(|| {
+                self.presets_filtered
+                    .get(self.preset_index)
+                    .map(|option| option.preset.clone())
+            })
+            .unwrap_or(Preset::Normal)
+    }
+
+    /// Where the icon would be written and the line it would add to the
+    /// export file, mirroring `iconmate add` (minus alias dedupe).
+    fn resolved_target(&self, config: &crate::app_state::AppConfig) -> Option<(String, String)> {
+        let preset = self.effective_preset();
+        let icon = self.icon_source();
+        let filename = self.inputs[FILENAME_FIELD_IDX]
+            .lines()
+            .join("")
+            .trim()
+            .to_string();
+        let mut name = self.inputs[NAME_FIELD_IDX]
+            .lines()
+            .join("")
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            name = crate::utils::default_name_and_filename_from_icon_source(&icon)?.0;
+        }
+
+        let icon = (!icon.is_empty()).then_some(icon);
+        let filename = (!filename.is_empty()).then_some(filename);
+        let (stem, ext) = crate::utils::make_icon_filename(
+            filename.as_ref(),
+            preset.extension(),
+            icon.as_ref(),
+            &name,
+            config.filename_case,
+        );
+        let folder = &Path::new(&config.folder).join(self.group.as_deref().unwrap_or_default());
+        let path = folder.join(format!("{stem}{ext}")).display().to_string();
+
+        let line = if matches!(preset, Preset::Flutter) {
+            let barrel = config
+                .flutter_barrel_file
+                .as_deref()
+                .unwrap_or(crate::flutter::DEFAULT_FLUTTER_BARREL_FILE);
+            format!("+ {barrel}")
+        } else {
+            let line = crate::utils::render_js_export_line(
+                self.index_contents.as_deref(),
+                folder,
+                &name,
+                &config.alias_style,
+                &config.code_style,
+                &stem,
+                &crate::utils::ImportQuery::import_ext(config.import_query, ext),
+            );
+            match crate::lazy_export::LazyLoader::configured(config.lazy_exports, &config.preset) {
+                Some(loader) => loader.wrap(&line),
+                None => line,
+            }
+        };
+        Some((path, line))
+    }
+
     fn sync_cursor(&mut self, index: usize) {
         for (_i, textarea) in self.inputs.iter_mut().enumerate() {
             // Stay
             if index == _i {
                 textarea.set_cursor_style(
                     Style::default()
-                        .bg(crate::views::theme::ACCENT)
-                        .fg(crate::views::theme::BASE_BG),
+                        .bg(crate::views::theme::palette().accent)
+                        .fg(crate::views::theme::palette().base_bg),
                 );
                 continue;
             }
@@ -152,7 +457,18 @@ impl AddPopupState {
                 }
             }
             ICON_FIELD_IDX => {
-                self.icon = Some(self.inputs[ICON_FIELD_IDX].lines().join("\n"));
+                // Terminals without bracketed paste (see `paste_text`) type a
+                // pasted SVG out key by key, so collapse it once focus leaves
+                // the field.
+                let typed = self.inputs[ICON_FIELD_IDX].lines().join("\n");
+                if self.pasted_svg.is_none()
+                    && self.inputs[ICON_FIELD_IDX].lines().len() > 1
+                    && crate::svg_render::summarize(typed.trim()).is_ok()
+                {
+                    self.pasted_svg = Some(typed.trim().to_string());
+                    self.inputs[ICON_FIELD_IDX] = TextArea::default();
+                }
+                self.icon = Some(self.icon_source());
                 self.apply_icon_based_defaults();
             }
             FILENAME_FIELD_IDX => {
@@ -190,7 +506,7 @@ impl AddPopupState {
     pub fn handlekeys_preset_input(&mut self, input: Input) {
         if self.current_input == PRESET_FIELD_IDX {
             if Self::is_paste_shortcut(&input) && self.paste_into_current_input() {
-                self.preset_filter = self.inputs[PRESET_FIELD_IDX].lines().join("\n");
+                return;
             }
 
             match input.key {
@@ -224,21 +540,7 @@ impl AddPopupState {
                         if !Self::is_paste_shortcut(&input) {
                             self.inputs[PRESET_FIELD_IDX].input(input);
                         }
-                        self.preset_filter = self.inputs[PRESET_FIELD_IDX].lines().join("\n");
-
-                        self.presets_filtered = PRESETS_OPTIONS
-                            .iter()
-                            .filter(|opt| {
-                                let filter = self.preset_filter.to_lowercase();
-                                filter.is_empty()
-                                    || opt.preset.to_str().contains(&filter)
-                                    || opt.description.to_lowercase().contains(&filter)
-                            })
-                            .cloned()
-                            .collect();
-                        if self.preset_index >= self.presets_filtered.len() {
-                            self.preset_index = 0;
-                        }
+                        self.refilter_presets();
                         self.clear_status();
                         return;
                     }
@@ -247,6 +549,24 @@ impl AddPopupState {
         }
     }
 
+    fn refilter_presets(&mut self) {
+        self.preset_filter = self.inputs[PRESET_FIELD_IDX].lines().join("\n");
+
+        self.presets_filtered = PRESETS_OPTIONS
+            .iter()
+            .filter(|opt| {
+                let filter = self.preset_filter.to_lowercase();
+                filter.is_empty()
+                    || opt.preset.to_str().contains(&filter)
+                    || opt.description.to_lowercase().contains(&filter)
+            })
+            .cloned()
+            .collect();
+        if self.preset_index >= self.presets_filtered.len() {
+            self.preset_index = 0;
+        }
+    }
+
     pub fn handlekeys_text_area(&mut self, input: Input) {
         if Self::is_paste_shortcut(&input) && self.paste_into_current_input() {
             self.clear_status();
@@ -305,6 +625,13 @@ impl App {
             .position(|option| option.preset == configured_preset)
             .unwrap_or(0);
 
+        let group = self
+            .selected_group()
+            .filter(|_| self.config.preset != "flutter");
+        let index_path = Path::new(&self.config.folder)
+            .join(group.as_deref().unwrap_or_default())
+            .join("index.ts");
+
         self.app_focus = AppFocus::AddPopup;
         self.add_popup_state = Some(AddPopupState {
             // folder: None,
@@ -326,6 +653,16 @@ impl App {
             current_input: 0,
             status_message: None,
             status_is_error: false,
+
+            preview: AddPreview::Empty,
+            preview_source: String::new(),
+            preview_deadline: None,
+            preview_request_id: 0,
+            preview_task: InFlight::default(),
+            index_contents: std::fs::read_to_string(index_path).ok(),
+            group,
+            pasted_svg: None,
+            svg_editor: None,
         });
 
         // Unused: Set default value for folder input
@@ -350,7 +687,7 @@ impl App {
     }
 
     fn submit_add_popup(&mut self) -> Result<(), String> {
-        let (preset, icon, filename, name) = {
+        let (preset, icon, filename, name, group) = {
             let Some(state) = self.add_popup_state.as_mut() else {
                 return Err("Add popup is not initialized".to_string());
             };
@@ -370,11 +707,7 @@ impl App {
                     }
                 })
                 .unwrap_or(Preset::Normal);
-            let icon = state.inputs[ICON_FIELD_IDX]
-                .lines()
-                .join("\n")
-                .trim()
-                .to_string();
+            let icon = state.icon_source();
             let filename = state.inputs[FILENAME_FIELD_IDX]
                 .lines()
                 .join("")
@@ -399,7 +732,7 @@ impl App {
                 }
             }
 
-            (preset, icon, filename, name)
+            (preset, icon, filename, name, state.group.clone())
         };
 
         if name.is_empty() {
@@ -415,6 +748,7 @@ impl App {
         }
 
         let mut command = Command::new(std::env::current_exe().map_err(|error| error.to_string())?);
+        crate::iconify_mock::forward_to(&mut command);
         command
             .arg("add")
             .arg("--folder")
@@ -432,6 +766,13 @@ impl App {
             command.arg("--filename").arg(filename);
         }
 
+        if let Some(group) = group
+            .as_deref()
+            .filter(|_| !matches!(preset, Preset::Flutter))
+        {
+            command.arg("--group").arg(group);
+        }
+
         if matches!(preset, Preset::Flutter) {
             if let Some(barrel_file) = self.config.flutter_barrel_file.as_deref() {
                 command.arg("--flutter-barrel-file").arg(barrel_file);
@@ -443,22 +784,114 @@ impl App {
 
         let output = command.output().map_err(|error| error.to_string())?;
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            let message = if !stderr.is_empty() {
-                stderr
-            } else if !stdout.is_empty() {
-                stdout
-            } else {
-                "Failed to add icon".to_string()
-            };
-            return Err(message);
+            return Err(add_command_error(&output));
         }
 
         self.init_icons();
-        self.app_focus = AppFocus::Main;
-        self.add_popup_state = None;
+        if let Some(state) = self.add_popup_state.as_mut() {
+            state.index_contents = std::fs::read_to_string(
+                Path::new(&self.config.folder)
+                    .join(state.group.as_deref().unwrap_or_default())
+                    .join("index.ts"),
+            )
+            .ok();
+        }
+        let mut status = format!("Added {name}. Add another, or esc to close.");
+        if self.config.behavior.auto_open_after_add
+            && let Err(error) = self.open_added_icon(&name)
+        {
+            status = format!("Added {name}, but failed to open it: {error}");
+        }
+        if let Some(state) = self.add_popup_state.as_mut() {
+            state.reset_for_next_icon();
+            state.set_status_info(status);
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the preview in step with the icon source field: raw SVG renders
+    /// right away, names and URLs are fetched once typing pauses.
+    pub fn tick_add_popup_preview(&mut self) {
+        let Some(state) = self.add_popup_state.as_mut() else {
+            return;
+        };
+        let source = state.icon_source();
+
+        if source != state.preview_source {
+            state.preview_source = source.clone();
+            // Drop whatever is in flight for the old source.
+            state.preview_request_id = 0;
+            state.preview_task.cancel();
+            state.preview_deadline = None;
+            state.preview = match crate::utils::_determine_icon_source_type(Some(&source)) {
+                _ if source.is_empty() => AddPreview::Empty,
+                crate::utils::IconSourceType::SvgContent => preview_from_svg(&source),
+                crate::utils::IconSourceType::DataUri => {
+                    match crate::datauri::decode_svg_data_uri(&source) {
+                        Ok(svg) => preview_from_svg(&svg),
+                        Err(error) => AddPreview::Failed(error.to_string()),
+                    }
+                }
+                _ => {
+                    state.preview_deadline =
+                        Some(Instant::now() + Duration::from_millis(PREVIEW_DEBOUNCE_MS));
+                    AddPreview::Loading
+                }
+            };
+            return;
+        }
+
+        if state
+            .preview_deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            state.preview_deadline = None;
+            let request_id = self.next_request_id();
+            let tx = self.tx.clone();
+            let task = tokio::spawn(async move {
+                let result = fetch_preview_svg(&source)
+                    .await
+                    .map_err(|error| error.to_string());
+                let _ = tx.send(AppEvent::AddPreviewFetched { request_id, result });
+            });
+            if let Some(state) = self.add_popup_state.as_mut() {
+                state.preview_request_id = request_id;
+                state.preview_task.start(request_id, task.abort_handle());
+            }
+        }
+    }
+
+    pub fn handle_add_preview_loaded(&mut self, request_id: u64, result: Result<String, String>) {
+        let Some(state) = self.add_popup_state.as_mut() else {
+            return;
+        };
+        if request_id != state.preview_request_id {
+            return;
+        }
+        state.preview_task.finish(request_id);
+        state.preview = match result {
+            Ok(svg) => preview_from_svg(&svg),
+            Err(error) => AddPreview::Failed(error),
+        };
+    }
 
+    fn open_added_icon(&self, name: &str) -> anyhow::Result<()> {
+        let item = self
+            .items
+            .iter()
+            .find(|item| item.name == name)
+            .ok_or_else(|| anyhow::anyhow!("{name} is not in the export file"))?;
+        let path = crate::utils::icon_file_path(
+            std::path::Path::new(&self.config.folder),
+            &item.file_path,
+        );
+        crate::viewer::open_svg_with_fallback(
+            &path,
+            self.config.svg_viewer_cmd.as_deref(),
+            &self.config.viewer_cmds,
+            self.recorded_source(&path).as_deref(),
+        )?;
         Ok(())
     }
 
@@ -479,10 +912,19 @@ impl App {
         }
 
         if let Some(state) = self.add_popup_state.as_mut() {
+            if state.svg_editor.is_some() {
+                state.handlekeys_svg_editor(input);
+                return;
+            }
             let _input = input.clone();
 
             match state.current_input {
                 PRESET_FIELD_IDX => state.handlekeys_preset_input(_input),
+                ICON_FIELD_IDX if state.pasted_svg.is_some() => state.handlekeys_svg_chip(_input),
+                ICON_FIELD_IDX if _input.ctrl && matches!(_input.key, Key::Char('e')) => {
+                    let svg = state.icon_source();
+                    state.open_svg_editor(&svg, None);
+                }
                 ICON_FIELD_IDX => state.handlekeys_text_area(_input),
                 SUBMIT_FIELD_IDX => match _input.key {
                     Key::Tab => state.move_focus(_input.shift),
@@ -502,11 +944,88 @@ impl App {
     }
 }
 
+fn render_preview(
+    f: &mut Frame,
+    area: Rect,
+    preview: &AddPreview,
+    colors: &crate::viewer::PreviewColors,
+) {
+    use ratatui::style::Modifier;
+
+    let block = Block::default()
+        .title("Preview")
+        .title_style(
+            Style::default()
+                .fg(crate::views::theme::palette().muted_text)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().bg(crate::views::theme::palette().input_bg));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let subtle = Style::default().fg(crate::views::theme::palette().subtle_text);
+    let body = match preview {
+        AddPreview::Ready(lines) => Paragraph::new(
+            lines
+                .iter()
+                .map(|line| Line::from(line.clone()))
+                .collect::<Vec<_>>(),
+        )
+        .alignment(Alignment::Center)
+        .style(preview_style(colors)),
+        AddPreview::Loading => Paragraph::new("Loading…").style(subtle),
+        AddPreview::Empty => Paragraph::new("Fill in an icon source").style(subtle),
+        AddPreview::Failed(error) => Paragraph::new(format!("No preview: {error}"))
+            .style(Style::default().fg(crate::views::theme::palette().warn)),
+    };
+    f.render_widget(body.wrap(ratatui::widgets::Wrap { trim: true }), inner);
+}
+
+/// The drawn icon in `preview_colors`, else in the theme's text color.
+pub fn preview_style(colors: &crate::viewer::PreviewColors) -> Style {
+    let rgb = |color: crate::viewer::Rgb| ratatui::style::Color::Rgb(color.0, color.1, color.2);
+    let style = Style::default().fg(colors
+        .foreground
+        .map(rgb)
+        .unwrap_or(crate::views::theme::palette().text));
+    match colors.background {
+        Some(background) => style.bg(rgb(background)),
+        None => style,
+    }
+}
+
+/// Draws `hint` on the last row of a single-line field (title, input, spare).
+fn render_field_hint(f: &mut Frame, field_area: Rect, hint: String) {
+    if field_area.height < 3 {
+        return;
+    }
+    let hint_area = Rect {
+        y: field_area.y + field_area.height - 1,
+        height: 1,
+        ..field_area
+    };
+    f.render_widget(
+        Paragraph::new(hint).style(Style::default().fg(crate::views::theme::palette().subtle_text)),
+        hint_area,
+    );
+}
+
 pub fn render_add_popup(f: &mut Frame, app: &mut App) {
     use ratatui::style::Modifier;
 
-    let area = popup_area(f.area(), 78, 29);
-    let body_area = crate::views::theme::render_popup_shell(f, area, "Add Icon");
+    let area = popup_area(f.area(), 78, 32);
+    let title = match app
+        .add_popup_state
+        .as_ref()
+        .and_then(|state| state.group.as_deref())
+    {
+        Some(group) => format!("Add Icon to {group}/"),
+        None => "Add Icon".to_string(),
+    };
+    let body_area = crate::views::theme::render_popup_shell(f, area, &title);
+    // Narrow popups can't fit a right-aligned hint next to the field title, so
+    // the hint moves to the spare row under the input instead.
+    let stack_hints = crate::views::layout::is_narrow(area);
 
     let layout = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
@@ -515,9 +1034,9 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
             Constraint::Length(1),
             Constraint::Length(4),
             Constraint::Length(1),
-            Constraint::Length(3),
+            Constraint::Length(7),
             Constraint::Length(1),
-            Constraint::Length(3),
+            Constraint::Length(2),
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
@@ -526,11 +1045,36 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
         .split(body_area);
     let preset_area = layout[0];
     let icon_area = layout[2];
-    let filename_area = layout[4];
-    let name_area = layout[6];
+    let resolved_area = layout[6];
     let submit_area = layout[8];
     let footer_area = layout[10];
 
+    // Filename and name stack on the left; the preview takes the right
+    // column unless the popup is too narrow to spare it.
+    let show_preview = !stack_hints;
+    let columns = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints(if show_preview {
+            vec![
+                Constraint::Min(0),
+                Constraint::Length(2),
+                Constraint::Length(PREVIEW_WIDTH),
+            ]
+        } else {
+            vec![Constraint::Min(0)]
+        })
+        .split(layout[4]);
+    let fields = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .split(columns[0]);
+    let filename_area = fields[0];
+    let name_area = fields[2];
+
     if let Some(state) = &mut app.add_popup_state {
         let labels: Vec<String> = vec![
             String::from("Preset"),
@@ -541,11 +1085,14 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
 
         let field_theme = |active: bool| {
             if active {
-                (crate::views::theme::INPUT_BG, crate::views::theme::ACCENT)
+                (
+                    crate::views::theme::palette().input_bg,
+                    crate::views::theme::palette().accent,
+                )
             } else {
                 (
-                    crate::views::theme::INPUT_BG,
-                    crate::views::theme::MUTED_TEXT,
+                    crate::views::theme::palette().input_bg,
+                    crate::views::theme::palette().muted_text,
                 )
             }
         };
@@ -572,7 +1119,7 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
         if !has_presets {
             items = vec![
                 ListItem::new("No presets found")
-                    .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT)),
+                    .style(Style::default().fg(crate::views::theme::palette().subtle_text)),
             ];
         }
 
@@ -610,7 +1157,7 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
                     .title(labels[PRESET_FIELD_IDX].clone())
                     .title(
                         Line::from(preset_filter_hint)
-                            .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT))
+                            .style(Style::default().fg(crate::views::theme::palette().subtle_text))
                             .alignment(Alignment::Right),
                     )
                     .title_style(
@@ -618,13 +1165,17 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
                             .fg(preset_title)
                             .add_modifier(Modifier::BOLD),
                     )
-                    .style(Style::default().bg(preset_bg).fg(crate::views::theme::TEXT)),
+                    .style(
+                        Style::default()
+                            .bg(preset_bg)
+                            .fg(crate::views::theme::palette().text),
+                    ),
             )
             .highlight_symbol("  ")
             .highlight_style(
                 Style::default()
-                    .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-                    .fg(crate::views::theme::BASE_BG)
+                    .bg(crate::views::theme::palette().row_highlight_bg)
+                    .fg(crate::views::theme::palette().base_bg)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -634,35 +1185,74 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
         let icon_block = Block::default()
             .title(labels[ICON_FIELD_IDX].clone())
             .title_style(Style::default().fg(icon_title).add_modifier(Modifier::BOLD))
-            .style(Style::default().bg(icon_bg).fg(crate::views::theme::TEXT));
-        state.inputs[ICON_FIELD_IDX].set_block(icon_block);
-        state.inputs[ICON_FIELD_IDX].set_cursor_line_style(Style::default());
-        f.render_widget(&state.inputs[ICON_FIELD_IDX], icon_area);
+            .style(
+                Style::default()
+                    .bg(icon_bg)
+                    .fg(crate::views::theme::palette().text),
+            );
+        let icon_hint = if state.pasted_svg.is_some() {
+            "enter edit · backspace clear"
+        } else {
+            "ctrl+e multi-line"
+        };
+        let icon_block = if stack_hints {
+            icon_block
+        } else {
+            icon_block.title(
+                Line::from(icon_hint)
+                    .style(Style::default().fg(crate::views::theme::palette().subtle_text))
+                    .alignment(Alignment::Right),
+            )
+        };
+        if let Some(svg) = &state.pasted_svg {
+            let chip_style = if state.current_input == ICON_FIELD_IDX {
+                Style::default()
+                    .bg(crate::views::theme::palette().row_highlight_bg)
+                    .fg(crate::views::theme::palette().base_bg)
+            } else {
+                Style::default().fg(crate::views::theme::palette().accent)
+            };
+            f.render_widget(
+                Paragraph::new(Line::from(Span::styled(svg_chip_label(svg), chip_style)))
+                    .block(icon_block),
+                icon_area,
+            );
+        } else {
+            state.inputs[ICON_FIELD_IDX].set_block(icon_block);
+            state.inputs[ICON_FIELD_IDX].set_cursor_line_style(Style::default());
+            f.render_widget(&state.inputs[ICON_FIELD_IDX], icon_area);
+        }
 
         let (filename_bg, filename_title) = field_theme(state.current_input == FILENAME_FIELD_IDX);
-        let filename_block = Block::default()
+        let filename_hint = crate::utils::filename_from_preset(
+            Some(state.inputs[FILENAME_FIELD_IDX].lines().join("")),
+            state.preset.clone(),
+        );
+        let mut filename_block = Block::default()
             .title(labels[FILENAME_FIELD_IDX].clone())
             .title_style(
                 Style::default()
                     .fg(filename_title)
                     .add_modifier(Modifier::BOLD),
             )
-            .title(
-                Line::from(crate::utils::filename_from_preset(
-                    Some(state.inputs[FILENAME_FIELD_IDX].lines().join("")),
-                    state.preset.clone(),
-                ))
-                .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT))
-                .alignment(Alignment::Right),
-            )
             .style(
                 Style::default()
                     .bg(filename_bg)
-                    .fg(crate::views::theme::TEXT),
+                    .fg(crate::views::theme::palette().text),
             );
+        if !stack_hints {
+            filename_block = filename_block.title(
+                Line::from(filename_hint.clone())
+                    .style(Style::default().fg(crate::views::theme::palette().subtle_text))
+                    .alignment(Alignment::Right),
+            );
+        }
         state.inputs[FILENAME_FIELD_IDX].set_block(filename_block);
         state.inputs[FILENAME_FIELD_IDX].set_cursor_line_style(Style::default());
         f.render_widget(&state.inputs[FILENAME_FIELD_IDX], filename_area);
+        if stack_hints {
+            render_field_hint(f, filename_area, filename_hint);
+        }
 
         let name_value = state.inputs[NAME_FIELD_IDX].lines().join("");
         let (name_bg, name_title) = field_theme(state.current_input == NAME_FIELD_IDX);
@@ -670,29 +1260,55 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
             state.preset.as_ref(),
             &name_value,
             app.config.flutter_barrel_class.as_deref(),
+            &app.config.alias_style,
         );
-        let name_block = Block::default()
+        let mut name_block = Block::default()
             .title(format!("{}", labels[NAME_FIELD_IDX]))
             .title_style(Style::default().fg(name_title).add_modifier(Modifier::BOLD))
-            .title(
-                Line::from(usage_hint)
-                    .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT))
+            .style(
+                Style::default()
+                    .bg(name_bg)
+                    .fg(crate::views::theme::palette().text),
+            );
+        if !stack_hints {
+            name_block = name_block.title(
+                Line::from(usage_hint.clone())
+                    .style(Style::default().fg(crate::views::theme::palette().subtle_text))
                     .alignment(Alignment::Right),
-            )
-            .style(Style::default().bg(name_bg).fg(crate::views::theme::TEXT));
+            );
+        }
         state.inputs[NAME_FIELD_IDX].set_block(name_block);
         state.inputs[NAME_FIELD_IDX].set_cursor_line_style(Style::default());
         f.render_widget(&state.inputs[NAME_FIELD_IDX], name_area);
+        if stack_hints {
+            render_field_hint(f, name_area, usage_hint);
+        }
+
+        if show_preview {
+            render_preview(f, columns[2], &state.preview, &app.config.preview_colors);
+        }
+
+        if let Some((path, line)) = state.resolved_target(&app.config) {
+            let muted = Style::default().fg(crate::views::theme::palette().muted_text);
+            let subtle = Style::default().fg(crate::views::theme::palette().subtle_text);
+            f.render_widget(
+                Paragraph::new(vec![
+                    Line::from(vec![Span::styled("→ ", subtle), Span::styled(path, muted)]),
+                    Line::from(vec![Span::styled("  ", subtle), Span::styled(line, subtle)]),
+                ]),
+                resolved_area,
+            );
+        }
 
         let submit_focused = state.current_input == SUBMIT_FIELD_IDX;
         let submit_style = if submit_focused {
             Style::default()
-                .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-                .fg(crate::views::theme::BASE_BG)
+                .bg(crate::views::theme::palette().row_highlight_bg)
+                .fg(crate::views::theme::palette().base_bg)
                 .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
-                .fg(crate::views::theme::ACCENT)
+                .fg(crate::views::theme::palette().accent)
                 .add_modifier(Modifier::BOLD)
         };
         let submit_button =
@@ -702,9 +1318,9 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
 
         let footer = if let Some(message) = &state.status_message {
             let color = if state.status_is_error {
-                crate::views::theme::ERROR
+                crate::views::theme::palette().error
             } else {
-                crate::views::theme::MUTED_TEXT
+                crate::views::theme::palette().muted_text
             };
             Paragraph::new(message.clone())
                 .alignment(Alignment::Left)
@@ -719,25 +1335,112 @@ pub fn render_add_popup(f: &mut Frame, app: &mut App) {
             .alignment(Alignment::Left)
         };
         f.render_widget(footer, footer_area);
+
+        if let Some(editor) = state.svg_editor.as_mut() {
+            render_svg_editor(f, editor);
+        }
     }
 }
 
+fn render_svg_editor(f: &mut Frame, editor: &mut SvgEditorState) {
+    let area = popup_area(f.area(), 90, 28);
+    let body_area = crate::views::theme::render_popup_shell(f, area, "Edit SVG");
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    editor.textarea.set_block(
+        Block::default().style(
+            Style::default()
+                .bg(crate::views::theme::palette().input_bg)
+                .fg(crate::views::theme::palette().text),
+        ),
+    );
+    editor.textarea.set_cursor_style(
+        Style::default()
+            .bg(crate::views::theme::palette().accent)
+            .fg(crate::views::theme::palette().base_bg),
+    );
+    f.render_widget(&editor.textarea, layout[0]);
+
+    let footer = match &editor.error {
+        Some(error) => Paragraph::new(format!("Invalid SVG: {error}"))
+            .style(Style::default().fg(crate::views::theme::palette().error)),
+        None => Paragraph::new(crate::views::theme::shortcut_line(&[
+            ("Apply", "ctrl+s"),
+            ("Cancel", "esc"),
+            ("Paste", "cmd/ctrl+v"),
+        ])),
+    };
+    f.render_widget(footer, layout[2]);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::crossterm::event::Event;
     use tempfile::TempDir;
 
     fn test_config(folder: String) -> crate::app_state::AppConfig {
-        crate::app_state::AppConfig {
-            folder,
-            preset: "normal".to_string(),
-            svg_viewer_cmd: None,
-            svg_viewer_cmd_source: "test".to_string(),
-            global_config_loaded: false,
-            project_config_loaded: false,
-            flutter_barrel_file: None,
-            flutter_barrel_class: None,
+        crate::app_state::AppConfig::for_tests(folder, "normal")
+    }
+
+    #[test]
+    fn reset_for_next_icon_keeps_preset_and_focuses_icon_field() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let mut app = App::new(test_config(temp_dir.path().to_string_lossy().into_owned()));
+        app.init_add_popup_with_icon_source("mdi:heart");
+
+        let state = app
+            .add_popup_state
+            .as_mut()
+            .expect("add popup should be initialized");
+        state.preset = Some(Preset::React);
+        state.reset_for_next_icon();
+
+        assert_eq!(state.preset, Some(Preset::React));
+        assert_eq!(state.current_input, ICON_FIELD_IDX);
+        for index in [ICON_FIELD_IDX, FILENAME_FIELD_IDX, NAME_FIELD_IDX] {
+            assert!(state.inputs[index].lines().join("").is_empty());
         }
+        assert!(state.icon.is_none() && state.name.is_none());
+    }
+
+    #[test]
+    fn bracketed_paste_collapses_svg_at_once_and_keeps_wide_text_intact() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let mut app = App::new(test_config(temp_dir.path().to_string_lossy().into_owned()));
+        app.init_add_popup();
+        let state = app.add_popup_state.as_mut().unwrap();
+        state.current_input = ICON_FIELD_IDX;
+        state.sync_cursor(ICON_FIELD_IDX);
+
+        let svg = "<svg viewBox=\"0 0 24 24\">\n  <path d=\"M0 0h24v24z\"/>\n</svg>\n";
+        crate::tui::handle_event(&mut app, Event::Paste(svg.to_string()));
+        let state = app.add_popup_state.as_mut().unwrap();
+        assert_eq!(state.pasted_svg.as_deref(), Some(svg.trim()));
+
+        state.current_input = NAME_FIELD_IDX;
+        state.sync_cursor(NAME_FIELD_IDX);
+        crate::tui::handle_event(&mut app, Event::Paste("心形图标\n".to_string()));
+        let state = app.add_popup_state.as_ref().unwrap();
+        assert_eq!(state.inputs[NAME_FIELD_IDX].lines(), ["心形图标"]);
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30))
+            .expect("test terminal should be created");
+        terminal
+            .draw(|f| crate::tui::draw(f, &mut app))
+            .expect("frame should draw");
+        let row = crate::tui::buffer_rows(terminal.backend().buffer())
+            .into_iter()
+            .find(|row| row.contains('心'))
+            .expect("pasted name should be on screen");
+        assert!(row.contains("心形图标"), "wide text was split: {row:?}");
     }
 
     #[test]
@@ -836,4 +1539,95 @@ mod tests {
             SUBMIT_FIELD_IDX
         );
     }
+
+    #[test]
+    fn raw_svg_previews_immediately_and_shows_resolved_target() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(&folder).expect("icons folder should be created");
+
+        let mut app = App::new(test_config(folder.to_string_lossy().into_owned()));
+        app.init_add_popup();
+        let state = app.add_popup_state.as_mut().unwrap();
+        state.inputs[ICON_FIELD_IDX] =
+            TextArea::from([r#"<svg viewBox="0 0 24 24"><rect width="24" height="24"/></svg>"#]);
+        state.inputs[NAME_FIELD_IDX] = TextArea::from(["Heart"]);
+
+        app.tick_add_popup_preview();
+
+        let state = app.add_popup_state.as_ref().unwrap();
+        assert!(matches!(state.preview, AddPreview::Ready(_)));
+        let (path, line) = state
+            .resolved_target(&app.config)
+            .expect("target should resolve once a name is set");
+        assert!(path.ends_with(".svg"), "unexpected path {path}");
+        assert!(line.contains("IconHeart"), "unexpected export line {line}");
+    }
+
+    #[test]
+    fn multi_line_svg_collapses_to_chip_when_leaving_icon_field() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let mut app = App::new(test_config(temp_dir.path().to_string_lossy().into_owned()));
+        app.init_add_popup();
+        let state = app.add_popup_state.as_mut().unwrap();
+        state.current_input = ICON_FIELD_IDX;
+        state.inputs[ICON_FIELD_IDX] = TextArea::from([
+            r#"<svg viewBox="0 0 24 24">"#,
+            r#"  <path d="M0 0h24v24z"/>"#,
+            "</svg>",
+        ]);
+
+        app.handlekeys_add_popup(Input {
+            key: Key::Tab,
+            ..Default::default()
+        });
+
+        let state = app.add_popup_state.as_mut().unwrap();
+        let svg = state.pasted_svg.clone().expect("svg should be collapsed");
+        assert_eq!(svg.lines().count(), 3);
+        assert_eq!(state.icon_source(), svg);
+        assert!(svg_chip_label(&svg).starts_with("[SVG 24×24 · 1 shape · 3 lines"));
+
+        state.current_input = ICON_FIELD_IDX;
+        app.handlekeys_add_popup(Input {
+            key: Key::Backspace,
+            ..Default::default()
+        });
+        assert!(app.add_popup_state.as_ref().unwrap().pasted_svg.is_none());
+    }
+
+    #[test]
+    fn invalid_svg_opens_editor_until_it_validates() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let mut app = App::new(test_config(temp_dir.path().to_string_lossy().into_owned()));
+        app.init_add_popup();
+        let state = app.add_popup_state.as_mut().unwrap();
+        state.current_input = ICON_FIELD_IDX;
+        state.accept_multiline_svg("<svg viewBox=\"0 0 24 24\">\n<g>\n</svg>");
+
+        let editor = state.svg_editor.as_mut().expect("editor should open");
+        assert!(
+            editor
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("expected 'g' tag")
+        );
+        assert!(state.pasted_svg.is_none());
+
+        let editor = state.svg_editor.as_mut().unwrap();
+        editor.textarea = TextArea::from(["<svg viewBox=\"0 0 24 24\">", "<g></g>", "</svg>"]);
+        app.handlekeys_add_popup(Input {
+            key: Key::Char('s'),
+            ctrl: true,
+            ..Default::default()
+        });
+
+        let state = app
+            .add_popup_state
+            .as_ref()
+            .expect("popup should stay open");
+        assert!(state.svg_editor.is_none());
+        assert!(state.pasted_svg.is_some());
+    }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::app_state::{App, AppFocus};
 use crate::utils::popup_area;
@@ -42,24 +42,79 @@ use tui_textarea::{Input, Key};
 pub struct DeletePopupState {
     pub selected_index: usize, // For yes or no only
 
-    pub item_to_delete: Option<crate::utils::IconEntry>,
+    pub items_to_delete: Vec<crate::utils::IconEntry>,
+    /// Set for a bulk delete: the search the items were filtered by.
+    pub bulk_filter: Option<String>,
 }
 
 impl App {
     pub fn init_delete_popup(&mut self) {
         self.app_focus = AppFocus::DeletePopup;
 
-        if let Some(item_to_delete) = self.filtered_items.get(self.selected_index) {
+        // On a folder row of the tree: every icon under it, always confirmed.
+        if let Some(crate::views::main::MainRow::Folder { path, .. }) =
+            self.main_rows().into_iter().nth(self.selected_index)
+        {
+            let prefix = format!("./{path}/");
             self.delete_popup_state = Some(DeletePopupState {
                 selected_index: 0,
-                item_to_delete: Some(item_to_delete.clone()),
-            });
-        } else {
-            self.delete_popup_state = Some(DeletePopupState {
-                selected_index: 0,
-                item_to_delete: None,
+                items_to_delete: self
+                    .items
+                    .iter()
+                    .filter(|item| item.file_path.starts_with(&prefix))
+                    .cloned()
+                    .collect(),
+                bulk_filter: Some(prefix),
             });
+            return;
+        }
+
+        self.delete_popup_state = Some(DeletePopupState {
+            selected_index: 0,
+            items_to_delete: self.selected_item().cloned().into_iter().collect(),
+            bulk_filter: None,
+        });
+
+        if !self.config.behavior.confirm_delete {
+            let name = self
+                .delete_popup_state
+                .as_ref()
+                .and_then(|state| state.items_to_delete.first())
+                .map(|item| item.name.clone());
+            self.main_state.clear_status();
+            self.perform_delete_action();
+            self.close_delete_popup();
+            if let Some(name) = name
+                && !self.main_state.status_is_error
+            {
+                self.main_state.set_status(format!("Deleted {name}"), false);
+            }
+        }
+    }
+
+    /// Delete everything the current search matches. Search `carbon:` first to
+    /// drop a whole collection.
+    pub fn init_bulk_delete_popup(&mut self) {
+        let query = self.main_state.search_items_value.trim().to_string();
+        if query.is_empty() {
+            self.main_state.set_status(
+                "Filter the list first (e.g. search `carbon:`) to bulk delete".to_string(),
+                true,
+            );
+            return;
+        }
+        if self.filtered_items.is_empty() {
+            self.main_state
+                .set_status(format!("No icons match '{query}'"), true);
+            return;
         }
+
+        self.app_focus = AppFocus::DeletePopup;
+        self.delete_popup_state = Some(DeletePopupState {
+            selected_index: 0,
+            items_to_delete: self.filtered_items.clone(),
+            bulk_filter: Some(query),
+        });
     }
 
     fn close_delete_popup(&mut self) {
@@ -68,39 +123,119 @@ impl App {
     }
 
     fn perform_delete_action(&mut self) {
-        // Remove the item from the items vector
-        if let Some(state) = &self.delete_popup_state {
-            if let Some(item) = &state.item_to_delete {
-                if let Some(pos) = self.items.iter().position(|i| i.name == item.name) {
-                    self.items.remove(pos);
-                }
+        let items = self
+            .delete_popup_state
+            .as_ref()
+            .map(|state| state.items_to_delete.clone())
+            .unwrap_or_default();
+        let folder = std::path::PathBuf::from(&self.config.folder);
+        for item in &items {
+            if let Err(e) = self.config.hooks.run(
+                crate::hooks::Hook::PreDelete,
+                &folder.join(&item.file_path),
+                &item.name,
+            ) {
+                self.main_state.set_status(format!("{e:#}"), true);
+                return;
+            }
+        }
+        if let Err(e) = self.delete_items(&items) {
+            self.main_state.set_status(format!("{e:#}"), true);
+            self.init_icons();
+            return;
+        }
 
-                let abs_file_path = std::path::Path::new(&self.config.folder).join(&item.file_path);
+        if let Err(e) = crate::gallery::refresh_gallery_if_present(
+            std::path::Path::new(&self.config.folder),
+            &self.config.preset,
+            self.config.flutter_barrel_file.as_deref(),
+            self.config.flutter_barrel_class.as_deref(),
+        ) {
+            eprintln!("Failed to update {}: {}", crate::gallery::GALLERY_FILE, e);
+        }
+        if self.config.preset != "flutter"
+            && let Err(e) = crate::utils::refresh_derived_files(&folder)
+        {
+            eprintln!("Failed to update generated files: {e:#}");
+        }
 
-                if self.config.preset == "flutter" {
-                    if let Err(e) = perform_flutter_delete(
-                        &self.config.folder,
-                        self.config.flutter_barrel_file.as_deref(),
-                        self.config.flutter_barrel_class.as_deref(),
-                        &item.file_path,
-                    ) {
-                        eprintln!("Failed to update Dart barrel: {}", e);
-                    }
-                    if abs_file_path.exists() {
-                        if let Err(e) = std::fs::remove_file(&abs_file_path) {
-                            eprintln!("Failed to delete {}: {}", abs_file_path.display(), e);
-                        }
+        // Re-initialize icons from disk to ensure consistency
+        self.init_icons();
+        for item in &items {
+            if let Err(e) = self.config.hooks.run(
+                crate::hooks::Hook::PostDelete,
+                &folder.join(&item.file_path),
+                &item.name,
+            ) {
+                self.main_state.set_status(format!("{e:#}"), true);
+                return;
+            }
+        }
+        if self
+            .delete_popup_state
+            .as_ref()
+            .is_some_and(|state| state.bulk_filter.is_some())
+        {
+            self.update_filtered_items_main();
+            self.main_state
+                .set_status(format!("Deleted {} icons", items.len()), false);
+        }
+    }
+
+    /// Removes `items` from their barrels in one pass per barrel, each
+    /// matched by name and path; a file another export still imports stays.
+    fn delete_items(&self, items: &[crate::utils::IconEntry]) -> anyhow::Result<()> {
+        let root = Path::new(&self.config.folder);
+        if self.config.preset == "flutter" {
+            for item in items {
+                let abs_file_path = crate::utils::icon_file_path(root, &item.file_path);
+                perform_flutter_delete(
+                    &self.config.folder,
+                    self.config.flutter_barrel_file.as_deref(),
+                    self.config.flutter_barrel_class.as_deref(),
+                    &item.file_path,
+                )?;
+                crate::raster::remove_raster_siblings(&abs_file_path)?;
+                if abs_file_path.exists() {
+                    std::fs::remove_file(&abs_file_path)?;
+                }
+            }
+        } else {
+            // A grouped icon goes through its group's barrel.
+            let mut by_folder: Vec<(PathBuf, Vec<crate::utils::IconEntry>)> = Vec::new();
+            for item in items {
+                let (folder, file_path) = match crate::groups::split_group(&item.file_path) {
+                    (Some(group), file_path) if root.join(group).join("index.ts").is_file() => {
+                        (root.join(group), file_path)
                     }
-                } else if let Err(e) =
-                    crate::utils::delete_icon_entry(abs_file_path.to_str().unwrap_or(""))
+                    _ => (root.to_path_buf(), item.file_path.clone()),
+                };
+                let entry = crate::utils::IconEntry {
+                    name: item.name.clone(),
+                    file_path,
+                };
+                match by_folder
+                    .iter_mut()
+                    .find(|(existing, _)| *existing == folder)
                 {
-                    eprintln!("Failed to delete icon file: {}", e);
+                    Some((_, entries)) => entries.push(entry),
+                    None => by_folder.push((folder, vec![entry])),
                 }
             }
+            for (folder, entries) in &by_folder {
+                crate::utils::delete_icon_entries(folder, entries)?;
+            }
         }
 
-        // Re-initialize icons from disk to ensure consistency
-        self.init_icons();
+        if let Some(history) = &self.config.history {
+            for item in items {
+                history.record(crate::history::Entry::delete(
+                    &item.name,
+                    &crate::utils::icon_file_path(root, &item.file_path),
+                ));
+            }
+        }
+        Ok(())
     }
 
     pub fn handlekeys_delete_popup(&mut self, input: Input) {
@@ -158,61 +293,91 @@ pub fn render_delete_popup(f: &mut Frame, app: &mut App) {
         .split(body_area);
 
     if let Some(state) = &mut app.delete_popup_state {
-        let icon_name = state
-            .item_to_delete
-            .as_ref()
-            .map(|item| item.name.as_str())
-            .unwrap_or("this icon");
-        let prompt = Paragraph::new(format!("Delete '{icon_name}'?"))
+        let prompt_text = match (&state.bulk_filter, state.items_to_delete.as_slice()) {
+            (Some(filter), items) => format!(
+                "Delete {} icon{} matching '{filter}'?",
+                items.len(),
+                if items.len() == 1 { "" } else { "s" }
+            ),
+            (None, [item, ..]) => format!("Delete '{}'?", item.name),
+            (None, []) => "Delete 'this icon'?".to_string(),
+        };
+        let prompt = Paragraph::new(prompt_text)
             .alignment(Alignment::Left)
             .style(
                 Style::default()
-                    .fg(crate::views::theme::TEXT)
+                    .fg(crate::views::theme::palette().text)
                     .add_modifier(Modifier::BOLD),
             );
         f.render_widget(prompt, layout[0]);
 
+        if state.bulk_filter.is_some() {
+            let names = state
+                .items_to_delete
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            f.render_widget(
+                Paragraph::new(names)
+                    .style(Style::default().fg(crate::views::theme::palette().muted_text)),
+                layout[1],
+            );
+        }
+
         let action_line = if state.selected_index == 0 {
             Line::from(vec![
                 Span::styled(
                     " Delete ",
                     Style::default()
-                        .bg(crate::views::theme::ERROR)
-                        .fg(crate::views::theme::BASE_BG)
+                        .bg(crate::views::theme::palette().error)
+                        .fg(crate::views::theme::palette().base_bg)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
-                Span::styled("y", Style::default().fg(crate::views::theme::MUTED_TEXT)),
+                Span::styled(
+                    "y",
+                    Style::default().fg(crate::views::theme::palette().muted_text),
+                ),
                 Span::raw("     "),
                 Span::styled(
                     "Cancel",
                     Style::default()
-                        .fg(crate::views::theme::SUBTLE_TEXT)
+                        .fg(crate::views::theme::palette().subtle_text)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
-                Span::styled("n", Style::default().fg(crate::views::theme::MUTED_TEXT)),
+                Span::styled(
+                    "n",
+                    Style::default().fg(crate::views::theme::palette().muted_text),
+                ),
             ])
         } else {
             Line::from(vec![
                 Span::styled(
                     "Delete",
                     Style::default()
-                        .fg(crate::views::theme::ACCENT_SOFT)
+                        .fg(crate::views::theme::palette().accent_soft)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
-                Span::styled("y", Style::default().fg(crate::views::theme::MUTED_TEXT)),
+                Span::styled(
+                    "y",
+                    Style::default().fg(crate::views::theme::palette().muted_text),
+                ),
                 Span::raw("     "),
                 Span::styled(
                     " Cancel ",
                     Style::default()
-                        .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-                        .fg(crate::views::theme::BASE_BG)
+                        .bg(crate::views::theme::palette().row_highlight_bg)
+                        .fg(crate::views::theme::palette().base_bg)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
-                Span::styled("n", Style::default().fg(crate::views::theme::MUTED_TEXT)),
+                Span::styled(
+                    "n",
+                    Style::default().fg(crate::views::theme::palette().muted_text),
+                ),
             ])
         };
         f.render_widget(
@@ -221,3 +386,106 @@ pub fn render_delete_popup(f: &mut Frame, app: &mut App) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppConfig;
+
+    fn app_in(folder: &Path, preset: &str) -> App {
+        App::new(AppConfig::for_tests(
+            folder.to_string_lossy().into_owned(),
+            preset,
+        ))
+    }
+
+    fn press(app: &mut App, key: Key) {
+        app.handlekeys(Input {
+            key,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn deleting_one_alias_keeps_the_other_and_its_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHome } from './home.svg';\nexport { default as IconHouse } from './home.svg';\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("home.svg"), "<svg/>").unwrap();
+        let mut app = app_in(temp_dir.path(), "normal");
+
+        assert_eq!(app.selected_item().unwrap().name, "IconHome");
+        press(&mut app, Key::Char('d'));
+        assert_eq!(app.app_focus, AppFocus::DeletePopup);
+        press(&mut app, Key::Char('y'));
+
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("index.ts")).unwrap(),
+            "export { default as IconHouse } from './home.svg';\n"
+        );
+        assert!(temp_dir.path().join("home.svg").exists());
+        assert_eq!(
+            app.items
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>(),
+            ["IconHouse"]
+        );
+    }
+
+    #[test]
+    fn deleting_rewrites_the_datauri_bundle() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["heart", "star"] {
+            std::fs::write(
+                temp_dir.path().join(format!("{name}.ts")),
+                crate::datauri::render_datauri_module("<svg></svg>"),
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart';\nexport { default as IconStar } from './star';\n",
+        )
+        .unwrap();
+        crate::datauri::write_datauri_bundle(temp_dir.path()).unwrap();
+        let mut app = app_in(temp_dir.path(), "datauri");
+
+        assert_eq!(app.selected_item().unwrap().name, "IconHeart");
+        press(&mut app, Key::Char('d'));
+        press(&mut app, Key::Char('y'));
+
+        let bundle =
+            std::fs::read_to_string(temp_dir.path().join(crate::datauri::DATAURI_BUNDLE)).unwrap();
+        assert!(!bundle.contains("IconHeart"));
+        assert!(bundle.contains("IconStar"));
+    }
+
+    #[test]
+    fn deleting_rewrites_the_mask_stylesheet() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["heart", "star"] {
+            std::fs::write(temp_dir.path().join(format!("{name}.svg")), "<svg></svg>").unwrap();
+        }
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star.svg';\n",
+        )
+        .unwrap();
+        crate::css_mask::write_mask_stylesheet(temp_dir.path()).unwrap();
+        let mut app = app_in(temp_dir.path(), "css");
+
+        assert_eq!(app.selected_item().unwrap().name, "IconHeart");
+        press(&mut app, Key::Char('d'));
+        press(&mut app, Key::Char('y'));
+
+        let css =
+            std::fs::read_to_string(temp_dir.path().join(crate::css_mask::CSS_MASK_STYLESHEET))
+                .unwrap();
+        assert!(!css.contains(".icon-heart {"));
+        assert!(css.contains(".icon-star {"));
+    }
+}
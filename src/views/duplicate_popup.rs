@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::app_state::{App, AppFocus};
+use crate::utils::popup_area;
+use crate::views::theme;
+
+#[derive(Debug)]
+pub struct DuplicatePopupState {
+    pub item_to_duplicate: Option<crate::utils::IconEntry>,
+    pub name_input: TextArea<'static>,
+    pub status_message: Option<String>,
+}
+
+impl DuplicatePopupState {
+    pub fn paste_text(&mut self, text: &str) {
+        self.name_input
+            .insert_str(crate::views::layout::single_line(text));
+        self.status_message = None;
+    }
+
+    fn new_name(&self) -> String {
+        self.name_input.lines().join("").trim().to_string()
+    }
+}
+
+impl App {
+    pub fn init_duplicate_popup(&mut self) {
+        self.app_focus = AppFocus::DuplicatePopup;
+
+        let item_to_duplicate = self.selected_item().cloned();
+        let mut name_input = TextArea::default();
+        if let Some(item) = &item_to_duplicate {
+            name_input.insert_str(&item.name);
+        }
+        name_input.set_cursor_style(
+            Style::default()
+                .bg(theme::palette().accent)
+                .fg(theme::palette().base_bg),
+        );
+
+        self.duplicate_popup_state = Some(DuplicatePopupState {
+            item_to_duplicate,
+            name_input,
+            status_message: None,
+        });
+    }
+
+    fn close_duplicate_popup(&mut self) {
+        self.app_focus = AppFocus::Main;
+        self.duplicate_popup_state = None;
+    }
+
+    fn submit_duplicate_popup(&mut self) -> Result<(), String> {
+        let Some(state) = self.duplicate_popup_state.as_ref() else {
+            return Err("Duplicate popup is not initialized".to_string());
+        };
+        let Some(item) = state.item_to_duplicate.clone() else {
+            return Err("No icon selected to duplicate.".to_string());
+        };
+        if self.config.preset == "flutter" {
+            return Err("Duplicating isn't supported for the flutter preset.".to_string());
+        }
+        let new_name = state.new_name();
+        if new_name == item.name {
+            return Err("Please enter a new name for the copy.".to_string());
+        }
+
+        // A grouped icon is copied inside its group's folder and barrel.
+        let root = Path::new(&self.config.folder);
+        let (group, file_path) = match crate::groups::split_group(&item.file_path) {
+            (Some(group), file_path) if root.join(group).join("index.ts").is_file() => {
+                (Some(group), file_path)
+            }
+            _ => (None, item.file_path.clone()),
+        };
+        let folder = root.join(group.unwrap_or_default());
+
+        let new_file_path =
+            crate::utils::duplicate_filename(&file_path, &new_name, self.config.filename_case);
+        let new_file_path = crate::utils::duplicate_icon_entry(
+            &folder.to_string_lossy(),
+            &file_path,
+            &new_name,
+            &new_file_path,
+            &self.config.code_style,
+        )
+        .map_err(|error| error.to_string())?;
+        crate::css_background::refresh_background_stylesheet_if_present(root)
+            .map_err(|error| error.to_string())?;
+        crate::icon_names::refresh_icon_names(root, false).map_err(|error| error.to_string())?;
+        crate::icon_registry::refresh_icon_registry(root, false)
+            .map_err(|error| error.to_string())?;
+
+        if let Some(history) = &self.config.history {
+            let source = history
+                .sources()
+                .remove(&crate::history::display_path(&folder.join(&file_path)));
+            history.record(crate::history::Entry::add(
+                &new_name,
+                &folder.join(&new_file_path),
+                source.as_deref(),
+            ));
+        }
+
+        self.init_icons();
+        self.close_duplicate_popup();
+        self.update_filtered_items_main();
+        self.select_icon_named(&new_name);
+        self.main_state
+            .set_status(format!("Duplicated {} as {new_name}", item.name), false);
+        Ok(())
+    }
+
+    pub fn handlekeys_duplicate_popup(&mut self, input: Input) {
+        match input.key {
+            Key::Esc => self.close_duplicate_popup(),
+            Key::Enter => {
+                if let Err(error) = self.submit_duplicate_popup()
+                    && let Some(state) = self.duplicate_popup_state.as_mut()
+                {
+                    state.status_message = Some(error);
+                }
+            }
+            _ => {
+                if let Some(state) = self.duplicate_popup_state.as_mut() {
+                    state.name_input.input(input);
+                    state.status_message = None;
+                }
+            }
+        }
+    }
+}
+
+pub fn render_duplicate_popup(f: &mut Frame, app: &mut App) {
+    let area = popup_area(f.area(), 74, 16);
+    let body_area = theme::render_popup_shell(f, area, "Duplicate Icon");
+
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    let filename_case = app.config.filename_case;
+    let Some(state) = app.duplicate_popup_state.as_mut() else {
+        return;
+    };
+
+    let header = match &state.item_to_duplicate {
+        Some(item) => format!("Alias: {}\nFile: {}", item.name, item.file_path),
+        None => "No icon selected".to_string(),
+    };
+    f.render_widget(
+        Paragraph::new(header)
+            .alignment(Alignment::Left)
+            .style(Style::default().fg(theme::palette().muted_text)),
+        layout[0],
+    );
+
+    let input_block = Block::default()
+        .title("New alias")
+        .title_style(
+            Style::default()
+                .fg(theme::palette().accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().fg(theme::palette().text));
+    state.name_input.set_block(input_block);
+    state.name_input.set_cursor_line_style(Style::default());
+    f.render_widget(&state.name_input, layout[2]);
+
+    let new_name = state.new_name();
+    let tip = match &state.item_to_duplicate {
+        Some(item) if !new_name.is_empty() && new_name != item.name => format!(
+            "Copies to {} and exports it from the same barrel.",
+            crate::utils::duplicate_filename(&item.file_path, &new_name, filename_case)
+        ),
+        _ => {
+            "Type a new alias for the copy, e.g. add Filled for a variant to hand-edit.".to_string()
+        }
+    };
+    f.render_widget(
+        Paragraph::new(tip)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(theme::palette().subtle_text)),
+        layout[4],
+    );
+
+    let footer = match &state.status_message {
+        Some(message) => Paragraph::new(message.clone())
+            .alignment(Alignment::Left)
+            .style(Style::default().fg(theme::palette().error)),
+        None => Paragraph::new(theme::shortcut_line(&[
+            ("Duplicate", "enter"),
+            ("Cancel", "esc"),
+        ]))
+        .alignment(Alignment::Left),
+    };
+    f.render_widget(footer, layout[5]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppConfig;
+
+    #[test]
+    fn copies_the_selected_icon_under_a_new_alias() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHome } from \"./home.svg\"\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("home.svg"), "<svg>home</svg>").unwrap();
+        let mut app = App::new(AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        let press = |app: &mut App, key| {
+            app.handlekeys(Input {
+                key,
+                ..Default::default()
+            })
+        };
+
+        press(&mut app, Key::Char('c'));
+        assert_eq!(app.app_focus, AppFocus::DuplicatePopup);
+        press(&mut app, Key::Enter);
+        assert_eq!(
+            app.duplicate_popup_state
+                .as_ref()
+                .and_then(|state| state.status_message.as_deref()),
+            Some("Please enter a new name for the copy.")
+        );
+
+        for c in "Filled".chars() {
+            press(&mut app, Key::Char(c));
+        }
+        press(&mut app, Key::Enter);
+
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("iconhomefilled.svg")).unwrap(),
+            "<svg>home</svg>"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("index.ts")).unwrap(),
+            "export { default as IconHome } from \"./home.svg\"\nexport { default as IconHomeFilled } from \"./iconhomefilled.svg\"\n"
+        );
+        assert_eq!(app.selected_item().unwrap().name, "IconHomeFilled");
+
+        // A taken alias is refused without touching the folder.
+        press(&mut app, Key::Char('c'));
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Enter);
+        assert_eq!(app.app_focus, AppFocus::DuplicatePopup);
+        assert!(!temp_dir.path().join("iconhome.svg").exists());
+    }
+}
@@ -46,15 +46,15 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
                     "○ "
                 },
                 Style::default().fg(if app.config.global_config_loaded {
-                    crate::views::theme::ACCENT
+                    crate::views::theme::palette().accent
                 } else {
-                    crate::views::theme::SUBTLE_TEXT
+                    crate::views::theme::palette().subtle_text
                 }),
             ),
             Span::styled(
                 "Global config",
                 Style::default()
-                    .fg(crate::views::theme::TEXT)
+                    .fg(crate::views::theme::palette().text)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -66,15 +66,15 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
                     "○ "
                 },
                 Style::default().fg(if app.config.project_config_loaded {
-                    crate::views::theme::ACCENT
+                    crate::views::theme::palette().accent
                 } else {
-                    crate::views::theme::SUBTLE_TEXT
+                    crate::views::theme::palette().subtle_text
                 }),
             ),
             Span::styled(
                 "Local config",
                 Style::default()
-                    .fg(crate::views::theme::TEXT)
+                    .fg(crate::views::theme::palette().text)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -91,6 +91,6 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
         app.config.svg_viewer_cmd_source
     ))
     .alignment(Alignment::Left)
-    .style(Style::default().fg(crate::views::theme::MUTED_TEXT));
+    .style(Style::default().fg(crate::views::theme::palette().muted_text));
     f.render_widget(status, layout[2]);
 }
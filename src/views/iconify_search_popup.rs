@@ -1,5 +1,8 @@
 use std::{
     borrow::Cow,
+    path::PathBuf,
+    process::Command,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -17,14 +20,29 @@ use ratatui::{
 use tui_textarea::{Input, Key, TextArea};
 
 use crate::{
-    app_state::{App, AppEvent, AppFocus, IconifyCollectionListItem, IconifySearchPayload},
-    iconify::IconifyClient,
+    app_state::{
+        App, AppEvent, AppFocus, IconifyCollectionDetails, IconifyCollectionListItem,
+        IconifyCollectionsPayload, IconifySearchPayload, InFlight,
+    },
+    collection_prefs::CollectionPrefs,
+    iconify::{IconifyClient, IconifyError, IconifyFeature},
     scroll,
     utils::popup_area,
+    views::add_popup::{AddPreview, preview_from_svg},
 };
 
 const SEARCH_DEBOUNCE_MS: u64 = 280;
 const SEARCH_LIMIT: u32 = 80;
+/// Collections at least this big are filtered on a worker thread, so a
+/// keystroke never waits on a full re-rank (MDI alone has 7k+ icons).
+const BACKGROUND_FILTER_MIN_ICONS: usize = 2_000;
+/// Sample icons drawn in the Collections tab's detail pane.
+const SAMPLE_COUNT: usize = 3;
+/// Braille columns of one drawn sample.
+const SAMPLE_COLUMNS: usize = 12;
+/// The detail pane only opens next to a list at least this wide.
+const DETAILS_WIDTH: u16 = 42;
+const DETAILS_MIN_LIST_WIDTH: u16 = 40;
 
 #[derive(Debug, Clone)]
 struct FuzzyCandidate<'a> {
@@ -38,12 +56,12 @@ impl AsRef<str> for FuzzyCandidate<'_> {
     }
 }
 
-fn fuzzy_rank_indices(query: &str, candidates: Vec<FuzzyCandidate<'_>>) -> Vec<usize> {
+fn fuzzy_rank_indices<'a>(
+    query: &str,
+    candidates: impl Iterator<Item = FuzzyCandidate<'a>>,
+) -> Vec<usize> {
     if query.trim().is_empty() {
-        return candidates
-            .into_iter()
-            .map(|candidate| candidate.index)
-            .collect();
+        return candidates.map(|candidate| candidate.index).collect();
     }
 
     let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
@@ -57,39 +75,97 @@ fn fuzzy_rank_indices(query: &str, candidates: Vec<FuzzyCandidate<'_>>) -> Vec<u
         .collect()
 }
 
-fn fuzzy_filter_collections(
-    collections: &[IconifyCollectionListItem],
-    query: &str,
-) -> Vec<IconifyCollectionListItem> {
-    let candidates = collections
-        .iter()
-        .enumerate()
-        .map(|(index, item)| FuzzyCandidate {
-            index,
-            haystack: Cow::Owned(format!("{} {}", item.prefix, item.name)),
-        })
-        .collect::<Vec<_>>();
+fn collection_haystack(item: &IconifyCollectionListItem) -> Cow<'_, str> {
+    Cow::Owned(format!("{} {}", item.prefix, item.name))
+}
 
-    fuzzy_rank_indices(query, candidates)
-        .into_iter()
-        .map(|index| collections[index].clone())
-        .collect()
+fn icon_haystack(icon: &str) -> Cow<'_, str> {
+    Cow::Borrowed(icon)
 }
 
-fn fuzzy_filter_icons(icons: &[String], query: &str) -> Vec<String> {
-    let candidates = icons
-        .iter()
-        .enumerate()
-        .map(|(index, icon)| FuzzyCandidate {
+/// Whether every match for `next` is also a match for `previous`, so
+/// filtering can start from the previous results. Holds when a plain query
+/// only grows at the end; nucleo's `!`, `^`, `$`, `'` and `\` operators can
+/// change meaning as the query grows, so those always rescan.
+fn query_narrows(previous: &str, next: &str) -> bool {
+    !previous.is_empty()
+        && next.starts_with(previous)
+        && !next.contains(['!', '^', '$', '\'', '\\'])
+}
+
+/// Indices into a backing list, ranked against the query that produced them.
+/// Filtering never copies the items themselves, and typing another character
+/// only rescores the current matches instead of the whole list.
+#[derive(Debug, Clone, Default)]
+pub struct FilteredView {
+    query: String,
+    indices: Vec<usize>,
+}
+
+impl FilteredView {
+    /// Every item of a backing list of `len`, in order.
+    fn all(len: usize) -> Self {
+        Self {
+            query: String::new(),
+            indices: (0..len).collect(),
+        }
+    }
+
+    fn refilter<'a, T>(
+        &mut self,
+        items: &'a [T],
+        query: &str,
+        haystack: impl Fn(&'a T) -> Cow<'a, str>,
+    ) {
+        let query = query.trim();
+        if query.is_empty() {
+            *self = Self::all(items.len());
+            return;
+        }
+
+        let to_candidate = |index: usize| FuzzyCandidate {
             index,
-            haystack: Cow::Borrowed(icon.as_str()),
-        })
-        .collect::<Vec<_>>();
+            haystack: haystack(&items[index]),
+        };
+        self.indices = if query_narrows(&self.query, query) {
+            let previous = std::mem::take(&mut self.indices);
+            fuzzy_rank_indices(query, previous.into_iter().map(to_candidate))
+        } else {
+            fuzzy_rank_indices(query, (0..items.len()).map(to_candidate))
+        };
+        self.query = query.to_string();
+    }
 
-    fuzzy_rank_indices(query, candidates)
-        .into_iter()
-        .map(|index| icons[index].clone())
-        .collect()
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Drops the rows `keep` rejects and moves the ones `first` picks to the
+    /// top, each part keeping its ranking.
+    fn arrange(&mut self, keep: impl Fn(usize) -> bool, first: impl Fn(usize) -> bool) {
+        self.indices.retain(|&index| keep(index));
+        self.indices.sort_by_key(|&index| !first(index));
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// The backing-list index shown at `position`.
+    pub fn get(&self, position: usize) -> Option<usize> {
+        self.indices.get(position).copied()
+    }
+
+    /// Backing-list indices for `len` rows starting at `offset`.
+    pub fn window(&self, offset: usize, len: usize) -> &[usize] {
+        let start = offset.min(self.indices.len());
+        let end = offset.saturating_add(len).min(self.indices.len());
+        &self.indices[start..end]
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -113,26 +189,97 @@ pub struct IconifySearchPopupState {
     pub icons_list_area: Option<Rect>,
 
     pub all_collections: Vec<IconifyCollectionListItem>,
-    pub filtered_collections: Vec<IconifyCollectionListItem>,
+    /// Rows of the Collections tab, as indices into `all_collections`.
+    pub visible_collections: FilteredView,
     pub search_icons: Vec<String>,
-    pub collection_icons: Vec<String>,
+    /// Shared with the filter worker, which ranks it without a copy.
+    pub collection_icons: Arc<[String]>,
     pub collection_icons_prefix: Option<String>,
-    pub visible_icons: Vec<String>,
+    /// Rows of the Icons tab, as indices into `icon_source()`.
+    pub visible_icons: FilteredView,
     pub selected_collection_filter: Option<String>,
 
     pub pending_search_query: Option<String>,
     pub debounce_deadline: Option<Instant>,
+    /// Set when a big collection needs re-filtering; `tick` hands it to the
+    /// worker while the previous results stay on screen, dimmed and
+    /// unselectable.
+    pub pending_icon_filter: bool,
 
     pub latest_collections_request_id: u64,
     pub latest_search_request_id: u64,
     pub latest_collection_icons_request_id: u64,
-
+    pub latest_icon_filter_request_id: u64,
+
+    /// Aborted when superseded or when the popup closes.
+    pub collections_task: InFlight,
+    pub search_task: InFlight,
+    pub collection_icons_task: InFlight,
+
+    /// The collections came from the on-disk cache because the API was
+    /// unreachable.
+    pub collections_stale: bool,
+    /// The server has no `/collections` (or `/search`); the Collections tab
+    /// is hidden (or typing no longer searches).
+    pub collections_unsupported: bool,
+    pub search_unsupported: bool,
     pub is_loading_collections: bool,
     pub is_loading_search: bool,
     pub is_loading_collection_icons: bool,
+    pub is_filtering_icons: bool,
 
     pub status_message: Option<String>,
     pub status_is_error: bool,
+
+    /// Icons toggled with space, in the order they were marked. Marks
+    /// survive switching collections.
+    pub marked_icons: Vec<String>,
+    /// Enter was pressed once with icons marked; the next enter adds them.
+    pub confirming_add: bool,
+    /// Marked icons being added, one per tick so the status can count them.
+    pub adding_marked: Option<MarkedAdds>,
+
+    pub collection_prefs: CollectionPrefs,
+    /// Where pin/hide changes are saved; `None` keeps them for this session.
+    pub collection_prefs_path: Option<PathBuf>,
+    pub show_hidden_collections: bool,
+
+    /// The highlighted collection whose samples the detail pane draws.
+    pub samples_prefix: Option<String>,
+    pub samples: Vec<(String, AddPreview)>,
+    /// Samples are fetched once the selection rests until this moment.
+    pub samples_deadline: Option<Instant>,
+    pub latest_samples_request_id: u64,
+    pub samples_task: InFlight,
+}
+
+/// A batch of marked icons and how far adding them has got.
+#[derive(Debug, Clone, Default)]
+pub struct MarkedAdds {
+    pub icon_names: Vec<String>,
+    pub group: Option<String>,
+    pub added: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+impl MarkedAdds {
+    fn done(&self) -> usize {
+        self.added.len() + self.failed.len()
+    }
+
+    fn next_icon(&self) -> Option<&String> {
+        self.icon_names.get(self.done())
+    }
+
+    fn progress_message(&self) -> Option<String> {
+        self.next_icon().map(|icon_name| {
+            format!(
+                "Adding {} of {}: {icon_name}…",
+                self.done() + 1,
+                self.icon_names.len()
+            )
+        })
+    }
 }
 
 impl IconifySearchPopupState {
@@ -148,51 +295,157 @@ impl IconifySearchPopupState {
             collections_list_area: None,
             icons_list_area: None,
             all_collections: Vec::new(),
-            filtered_collections: Vec::new(),
+            visible_collections: FilteredView::default(),
             search_icons: Vec::new(),
-            collection_icons: Vec::new(),
+            collection_icons: Arc::from([]),
             collection_icons_prefix: None,
-            visible_icons: Vec::new(),
+            visible_icons: FilteredView::default(),
             selected_collection_filter: None,
             pending_search_query: None,
             debounce_deadline: None,
+            pending_icon_filter: false,
             latest_collections_request_id: 0,
             latest_search_request_id: 0,
             latest_collection_icons_request_id: 0,
+            latest_icon_filter_request_id: 0,
+            collections_task: InFlight::default(),
+            search_task: InFlight::default(),
+            collection_icons_task: InFlight::default(),
+            collections_stale: false,
+            collections_unsupported: false,
+            search_unsupported: false,
             is_loading_collections: false,
             is_loading_search: false,
             is_loading_collection_icons: false,
+            is_filtering_icons: false,
             status_message: None,
             status_is_error: false,
+            marked_icons: Vec::new(),
+            confirming_add: false,
+            adding_marked: None,
+            collection_prefs: CollectionPrefs::default(),
+            collection_prefs_path: None,
+            show_hidden_collections: false,
+            samples_prefix: None,
+            samples: Vec::new(),
+            samples_deadline: None,
+            latest_samples_request_id: 0,
+            samples_task: InFlight::default(),
         }
     }
 
-    fn active_collections(&self) -> &[IconifyCollectionListItem] {
-        if self.search_value.trim().is_empty() {
-            &self.all_collections
-        } else {
-            &self.filtered_collections
+    fn visible_collection(&self, position: usize) -> Option<&IconifyCollectionListItem> {
+        self.visible_collections
+            .get(position)
+            .map(|index| &self.all_collections[index])
+    }
+
+    /// The list the Icons tab filters: the open collection once it has
+    /// loaded, otherwise the remote search results.
+    fn icon_source(&self) -> &[String] {
+        match &self.selected_collection_filter {
+            Some(prefix) if self.collection_icons_prefix.as_deref() == Some(prefix.as_str()) => {
+                &self.collection_icons
+            }
+            Some(_) => &[],
+            None => &self.search_icons,
         }
     }
 
+    fn visible_icon(&self, position: usize) -> Option<&String> {
+        self.visible_icons
+            .get(position)
+            .map(|index| &self.icon_source()[index])
+    }
+
+    /// Pinned collections first, hidden ones left out unless shown.
     fn refresh_filtered_collections(&mut self) {
-        let query = self.search_value.trim();
-        if query.is_empty() {
-            self.filtered_collections.clear();
-            return;
+        let query = self.search_value.clone();
+        self.visible_collections
+            .refilter(&self.all_collections, &query, collection_haystack);
+        let collections = &self.all_collections;
+        let prefs = &self.collection_prefs;
+        let show_hidden = self.show_hidden_collections;
+        self.visible_collections.arrange(
+            |index| show_hidden || !prefs.is_hidden(&collections[index].prefix),
+            |index| prefs.is_pinned(&collections[index].prefix),
+        );
+    }
+
+    /// Re-sorts after a pin/hide change and keeps `prefix` selected when it's
+    /// still listed.
+    fn rearrange_collections(&mut self, prefix: &str) {
+        self.visible_collections.clear();
+        self.refresh_filtered_collections();
+        if let Some(position) = (0..self.visible_collections.len()).find(|&position| {
+            self.visible_collection(position)
+                .is_some_and(|item| item.prefix == prefix)
+        }) {
+            self.selected_collection_index = position;
+        }
+        self.clamp_collection_selection();
+    }
+
+    fn save_collection_prefs(&mut self) {
+        if let Some(path) = &self.collection_prefs_path
+            && let Err(error) = self.collection_prefs.write(path)
+        {
+            self.set_status(format!("{error:#}"), true);
         }
+    }
+
+    fn toggle_selected_collection_pin(&mut self) {
+        let Some(prefix) = self.selected_collection_prefix() else {
+            return;
+        };
+        let message = if self.collection_prefs.toggle_pinned(&prefix) {
+            format!("Pinned {prefix}")
+        } else {
+            format!("Unpinned {prefix}")
+        };
+        self.set_status(message, false);
+        self.rearrange_collections(&prefix);
+        self.save_collection_prefs();
+    }
+
+    fn toggle_selected_collection_hidden(&mut self) {
+        let Some(prefix) = self.selected_collection_prefix() else {
+            return;
+        };
+        let message = if self.collection_prefs.toggle_hidden(&prefix) {
+            format!("Hid {prefix}; ctrl+t shows hidden collections")
+        } else {
+            format!("Unhid {prefix}")
+        };
+        self.set_status(message, false);
+        self.rearrange_collections(&prefix);
+        self.save_collection_prefs();
+    }
 
-        self.filtered_collections = fuzzy_filter_collections(&self.all_collections, query);
+    fn toggle_show_hidden_collections(&mut self) {
+        self.show_hidden_collections = !self.show_hidden_collections;
+        let prefix = self.selected_collection_prefix().unwrap_or_default();
+        self.rearrange_collections(&prefix);
+        let hidden = self.collection_prefs.hidden.len();
+        let message = if self.show_hidden_collections {
+            format!("Showing {hidden} hidden collection(s)")
+        } else {
+            format!("Hiding {hidden} collection(s)")
+        };
+        self.set_status(message, false);
     }
 
     fn sync_search_dispatch_state(&mut self) {
         self.pending_search_query = None;
         self.debounce_deadline = None;
+        // Whatever is in flight was for the previous query.
+        self.search_task.cancel();
 
         let query = self.search_value.trim().to_string();
         if query.is_empty()
             || self.active_tab != IconifySearchTab::Icons
             || self.selected_collection_filter.is_some()
+            || self.search_unsupported
         {
             self.is_loading_search = false;
             return;
@@ -206,21 +459,38 @@ impl IconifySearchPopupState {
     fn clear_search_input(&mut self) {
         self.search_textarea = TextArea::default();
         self.search_value.clear();
-        self.filtered_collections.clear();
         self.search_icons.clear();
+        self.visible_icons.clear();
+        self.refresh_filtered_collections();
         self.selected_icon_index = 0;
         self.sync_search_dispatch_state();
         self.clear_status();
     }
 
     fn selected_collection_prefix(&self) -> Option<String> {
-        self.active_collections()
-            .get(self.selected_collection_index)
+        self.visible_collection(self.selected_collection_index)
             .map(|item| item.prefix.clone())
     }
 
+    /// `None` while the rows on screen are for an older query.
     fn selected_icon_name(&self) -> Option<String> {
-        self.visible_icons.get(self.selected_icon_index).cloned()
+        if self.icon_results_stale() {
+            return None;
+        }
+        self.visible_icon(self.selected_icon_index).cloned()
+    }
+
+    /// A re-filter is queued or running, so the rows on screen don't match
+    /// the query yet.
+    fn icon_results_stale(&self) -> bool {
+        self.pending_icon_filter || self.is_filtering_icons
+    }
+
+    /// An exact `prefix:name` (or icon page URL) typed into the search box.
+    /// With nothing in the list to pick, enter fetches it directly, which
+    /// still works when the collection listing or search is unavailable.
+    fn typed_icon_name(&self) -> Option<String> {
+        crate::utils::iconify_name_from_icon_source(&self.search_value)
     }
 
     fn set_status(&mut self, message: String, is_error: bool) {
@@ -233,8 +503,103 @@ impl IconifySearchPopupState {
         self.status_is_error = false;
     }
 
+    fn toggle_selected_mark(&mut self) {
+        let Some(icon_name) = self.selected_icon_name() else {
+            self.set_status("No icon selected.".to_string(), true);
+            return;
+        };
+        match self.marked_icons.iter().position(|icon| *icon == icon_name) {
+            Some(position) => {
+                self.marked_icons.remove(position);
+            }
+            None => self.marked_icons.push(icon_name),
+        }
+        match self.marked_icons.len() {
+            0 => self.clear_status(),
+            count => self.set_status(
+                format!("{count} marked; enter adds them with inferred names."),
+                false,
+            ),
+        }
+    }
+
+    /// Keeps the detail pane on the highlighted collection. Returns the
+    /// samples to fetch once the selection has rested for a moment, so
+    /// scrolling through the list doesn't fire a request per row.
+    fn follow_highlighted_collection(&mut self) -> Option<(String, Vec<String>)> {
+        if self.active_tab != IconifySearchTab::Collections {
+            return None;
+        }
+        let highlighted = self
+            .visible_collection(self.selected_collection_index)
+            .map(|item| (item.prefix.clone(), item.details.samples.clone()));
+        let prefix = highlighted.as_ref().map(|(prefix, _)| prefix.clone());
+        if prefix != self.samples_prefix {
+            self.samples_task.cancel();
+            self.samples_prefix = prefix;
+            self.samples = highlighted
+                .map(|(_, samples)| {
+                    samples
+                        .into_iter()
+                        .take(SAMPLE_COUNT)
+                        .map(|name| (name, AddPreview::Loading))
+                        .collect()
+                })
+                .unwrap_or_default();
+            self.samples_deadline = (!self.samples.is_empty())
+                .then(|| Instant::now() + Duration::from_millis(SEARCH_DEBOUNCE_MS));
+            return None;
+        }
+
+        if self
+            .samples_deadline
+            .is_none_or(|deadline| Instant::now() < deadline)
+        {
+            return None;
+        }
+        self.samples_deadline = None;
+        let names = self.samples.iter().map(|(name, _)| name.clone()).collect();
+        Some((self.samples_prefix.clone()?, names))
+    }
+
+    fn handle_unsupported_feature(
+        &mut self,
+        request_id: u64,
+        feature: IconifyFeature,
+        message: String,
+    ) {
+        match feature {
+            IconifyFeature::Collections => {
+                if request_id != self.latest_collections_request_id {
+                    return;
+                }
+                self.collections_task.finish(request_id);
+                self.is_loading_collections = false;
+                self.collections_unsupported = true;
+                self.active_tab = IconifySearchTab::Icons;
+                self.sync_search_dispatch_state();
+            }
+            IconifyFeature::Search => {
+                if request_id != self.latest_search_request_id {
+                    return;
+                }
+                self.search_task.finish(request_id);
+                self.is_loading_search = false;
+                self.search_unsupported = true;
+            }
+        }
+        let hint = if self.collections_unsupported && self.search_unsupported {
+            "type an exact name like mdi:home and press enter"
+        } else if self.collections_unsupported {
+            "search, or type an exact name like mdi:home"
+        } else {
+            "open a collection, or type an exact name like mdi:home"
+        };
+        self.set_status(format!("{message} ({hint})"), false);
+    }
+
     fn clamp_collection_selection(&mut self) {
-        let len = self.active_collections().len();
+        let len = self.visible_collections.len();
         if len == 0 {
             self.selected_collection_index = 0;
         } else if self.selected_collection_index >= len {
@@ -251,6 +616,12 @@ impl IconifySearchPopupState {
         }
     }
 
+    pub fn paste_text(&mut self, text: &str) {
+        self.search_textarea
+            .insert_str(crate::views::layout::single_line(text));
+        self.update_search_value();
+    }
+
     fn update_search_value(&mut self) {
         self.search_value = self.search_textarea.lines().join("");
 
@@ -260,43 +631,40 @@ impl IconifySearchPopupState {
 
         self.refresh_filtered_collections();
 
-        let query = self.search_value.trim().to_string();
-        if query.is_empty() {
-            self.filtered_collections.clear();
+        if self.selected_collection_filter.is_none() {
+            // Remote results belong to the previous query.
             self.search_icons.clear();
-            self.refresh_visible_icons();
-            self.sync_search_dispatch_state();
-            return;
+            self.visible_icons.clear();
         }
-
-        self.search_icons.clear();
         self.refresh_visible_icons();
         self.sync_search_dispatch_state();
     }
 
+    /// Re-filters the Icons tab for the current query. Callers that replace
+    /// the backing list (`icon_source()`) clear `visible_icons` first, so
+    /// filtering doesn't narrow stale results.
     fn refresh_visible_icons(&mut self) {
-        if let Some(prefix) = &self.selected_collection_filter {
-            if self.collection_icons_prefix.as_deref() != Some(prefix.as_str()) {
-                self.visible_icons.clear();
-                self.clamp_icon_selection();
+        self.pending_icon_filter = false;
+        if self.selected_collection_filter.is_some() {
+            let query = self.search_value.clone();
+            if !query.trim().is_empty() && self.icon_source().len() >= BACKGROUND_FILTER_MIN_ICONS {
+                self.pending_icon_filter = true;
                 return;
             }
-
-            let query = self.search_value.trim();
-            if query.is_empty() {
-                self.visible_icons = self.collection_icons.clone();
-            } else {
-                self.visible_icons = fuzzy_filter_icons(&self.collection_icons, query);
-            }
+            self.is_filtering_icons = false;
+            let mut view = std::mem::take(&mut self.visible_icons);
+            view.refilter(self.icon_source(), &query, |icon| icon_haystack(icon));
+            self.visible_icons = view;
         } else {
-            self.visible_icons = self.search_icons.clone();
+            // Remote results are already ranked for the query.
+            self.visible_icons = FilteredView::all(self.search_icons.len());
         }
 
         self.clamp_icon_selection();
     }
 
     fn move_collection_selection(&mut self, delta: i32) {
-        let len = self.active_collections().len();
+        let len = self.visible_collections.len();
         if len == 0 {
             self.selected_collection_index = 0;
             self.collections_scroll_offset = 0;
@@ -342,23 +710,52 @@ impl IconifySearchPopupState {
     }
 }
 
+/// The event for a finished request: `IconifyFeatureUnsupported` when the
+/// server lacks the endpoint, otherwise what `loaded` builds.
+fn iconify_event<T>(
+    request_id: u64,
+    result: Result<T, IconifyError>,
+    loaded: impl FnOnce(Result<T, String>) -> AppEvent,
+) -> AppEvent {
+    match result {
+        Err(error) => match error.unsupported_feature() {
+            Some(feature) => AppEvent::IconifyFeatureUnsupported {
+                request_id,
+                feature,
+                message: error.to_string(),
+            },
+            None => loaded(Err(error.to_string())),
+        },
+        Ok(value) => loaded(Ok(value)),
+    }
+}
+
 enum PopupAction {
     None,
     Close,
     OpenCollection(String),
     FillAddPopup(String),
     OpenIconInBrowser(String),
+    AddIcons(Vec<String>),
 }
 
 impl App {
-    fn next_request_id(&mut self) -> u64 {
+    pub fn next_request_id(&mut self) -> u64 {
         self.next_async_request_id = self.next_async_request_id.saturating_add(1);
         self.next_async_request_id
     }
 
     pub fn init_iconify_search_popup(&mut self) {
         self.app_focus = AppFocus::IconifySearchPopup;
-        self.iconify_search_popup_state = Some(IconifySearchPopupState::new());
+        let mut state = IconifySearchPopupState::new();
+        state.collection_prefs_path = crate::collection_prefs::default_path();
+        if let Some(path) = &state.collection_prefs_path {
+            match CollectionPrefs::read(path) {
+                Ok(prefs) => state.collection_prefs = prefs,
+                Err(error) => state.set_status(format!("{error:#}"), true),
+            }
+        }
+        self.iconify_search_popup_state = Some(state);
         self.request_iconify_collections();
     }
 
@@ -371,8 +768,19 @@ impl App {
         let mut action = PopupAction::None;
 
         if let Some(state) = self.iconify_search_popup_state.as_mut() {
+            if state.adding_marked.is_some() {
+                return;
+            }
+            // Any key other than enter backs out of the add confirmation.
+            let confirming = std::mem::take(&mut state.confirming_add);
+            if confirming {
+                state.clear_status();
+            }
             match input.key {
+                Key::Esc if confirming => {}
                 Key::Esc => action = PopupAction::Close,
+                // Without a listing there is nothing to switch to.
+                Key::Tab if state.collections_unsupported => {}
                 Key::Tab => {
                     match state.active_tab {
                         IconifySearchTab::Collections => {
@@ -380,6 +788,7 @@ impl App {
                         }
                         IconifySearchTab::Icons => {
                             state.selected_collection_filter = None;
+                            state.collection_icons_task.cancel();
                             state.clear_search_input();
                             state.active_tab = IconifySearchTab::Collections;
                             state.is_loading_collection_icons = false;
@@ -401,16 +810,55 @@ impl App {
                         if let Some(prefix) = state.selected_collection_prefix() {
                             state.clear_search_input();
                             action = PopupAction::OpenCollection(prefix);
+                        } else if let Some(icon_name) = state.typed_icon_name() {
+                            action = PopupAction::FillAddPopup(icon_name);
                         }
                     }
+                    IconifySearchTab::Icons if confirming => {
+                        action = PopupAction::AddIcons(state.marked_icons.clone());
+                    }
+                    IconifySearchTab::Icons if !state.marked_icons.is_empty() => {
+                        let count = state.marked_icons.len();
+                        state.confirming_add = true;
+                        state.set_status(
+                            format!(
+                                "Add {count} icon{} with inferred names? enter to add, esc to go back",
+                                if count == 1 { "" } else { "s" }
+                            ),
+                            false,
+                        );
+                    }
                     IconifySearchTab::Icons => {
-                        if let Some(icon_name) = state.selected_icon_name() {
+                        if let Some(icon_name) = state
+                            .selected_icon_name()
+                            .or_else(|| state.typed_icon_name())
+                        {
                             action = PopupAction::FillAddPopup(icon_name);
                         } else {
                             state.set_status("No icon selected.".to_string(), true);
                         }
                     }
                 },
+                Key::Char('p')
+                    if input.ctrl && state.active_tab == IconifySearchTab::Collections =>
+                {
+                    state.toggle_selected_collection_pin();
+                }
+                Key::Char('x')
+                    if input.ctrl && state.active_tab == IconifySearchTab::Collections =>
+                {
+                    state.toggle_selected_collection_hidden();
+                }
+                Key::Char('t')
+                    if input.ctrl && state.active_tab == IconifySearchTab::Collections =>
+                {
+                    state.toggle_show_hidden_collections();
+                }
+                Key::Char(' ')
+                    if state.active_tab == IconifySearchTab::Icons && !input.ctrl && !input.alt =>
+                {
+                    state.toggle_selected_mark();
+                }
                 Key::Char('o') if input.ctrl => {
                     if state.active_tab == IconifySearchTab::Icons {
                         if let Some(icon_name) = state.selected_icon_name() {
@@ -438,9 +886,110 @@ impl App {
             PopupAction::OpenIconInBrowser(icon_name) => {
                 self.open_icon_browser_preview(icon_name);
             }
+            PopupAction::AddIcons(icon_names) => self.start_marked_adds(icon_names),
         }
     }
 
+    /// Queues the marked icons for `tick`, which adds one per frame under the
+    /// name inferred from it, like the Add popup does when its name field is
+    /// left empty. Keys are ignored until the batch is done.
+    fn start_marked_adds(&mut self, icon_names: Vec<String>) {
+        let group = self
+            .selected_group()
+            .filter(|_| self.config.preset != "flutter");
+        let Some(state) = self.iconify_search_popup_state.as_mut() else {
+            return;
+        };
+        let adds = MarkedAdds {
+            icon_names,
+            group,
+            ..MarkedAdds::default()
+        };
+        if let Some(message) = adds.progress_message() {
+            state.set_status(message, false);
+        }
+        state.adding_marked = Some(adds);
+    }
+
+    /// Adds the next marked icon; after the last, closes the popup and
+    /// reports the batch.
+    fn add_next_marked_icon(&mut self) {
+        let Some(mut adds) = self
+            .iconify_search_popup_state
+            .as_mut()
+            .and_then(|state| state.adding_marked.take())
+        else {
+            return;
+        };
+        if let Some(icon_name) = adds.next_icon().cloned() {
+            match self.add_iconify_icon(&icon_name, adds.group.as_deref()) {
+                Ok(name) => adds.added.push(name),
+                Err(error) => adds.failed.push(format!("{icon_name} ({error})")),
+            }
+        }
+        if let Some(message) = adds.progress_message()
+            && let Some(state) = self.iconify_search_popup_state.as_mut()
+        {
+            state.set_status(message, false);
+            state.adding_marked = Some(adds);
+            return;
+        }
+
+        self.close_iconify_search_popup();
+        self.init_icons();
+        self.update_filtered_items_main();
+        if let Some(name) = adds.added.first() {
+            self.select_icon_named(name);
+        }
+        let total = adds.icon_names.len();
+        let mut message = format!(
+            "Added {} of {total} icon{}",
+            adds.added.len(),
+            if total == 1 { "" } else { "s" }
+        );
+        if !adds.failed.is_empty() {
+            message.push_str(&format!("; failed: {}", adds.failed.join(", ")));
+        }
+        self.main_state.set_status(message, !adds.failed.is_empty());
+    }
+
+    fn add_iconify_icon(&self, icon_name: &str, group: Option<&str>) -> Result<String, String> {
+        let Some((name, _)) = crate::utils::default_name_and_filename_from_icon_source(icon_name)
+        else {
+            return Err("can't infer a name".to_string());
+        };
+
+        let mut command = Command::new(std::env::current_exe().map_err(|error| error.to_string())?);
+        crate::iconify_mock::forward_to(&mut command);
+        command
+            .arg("add")
+            .arg("--folder")
+            .arg(&self.config.folder)
+            .arg("--name")
+            .arg(&name)
+            .arg("--preset")
+            .arg(&self.config.preset)
+            .arg("--icon")
+            .arg(icon_name);
+        if let Some(group) = group {
+            command.arg("--group").arg(group);
+        }
+        if self.config.preset == "flutter" {
+            if let Some(barrel_file) = self.config.flutter_barrel_file.as_deref() {
+                command.arg("--flutter-barrel-file").arg(barrel_file);
+            }
+            if let Some(barrel_class) = self.config.flutter_barrel_class.as_deref() {
+                command.arg("--flutter-barrel-class").arg(barrel_class);
+            }
+        }
+
+        let output = command.output().map_err(|error| error.to_string())?;
+        if !output.status.success() {
+            return Err(crate::views::add_popup::add_command_error(&output));
+        }
+        Ok(name)
+    }
+
     pub fn handle_mouse_iconify_search_popup(
         &mut self,
         mouse: ratatui::crossterm::event::MouseEvent,
@@ -456,7 +1005,7 @@ impl App {
                 let Some(area) = state.collections_list_area else {
                     return;
                 };
-                let len = state.active_collections().len();
+                let len = state.visible_collections.len();
                 let height = area.height as usize;
 
                 match mouse.kind {
@@ -522,6 +1071,15 @@ impl App {
     }
 
     pub fn tick_iconify_search_popup(&mut self) {
+        if self
+            .iconify_search_popup_state
+            .as_ref()
+            .is_some_and(|state| state.adding_marked.is_some())
+        {
+            self.add_next_marked_icon();
+            return;
+        }
+
         let query_to_dispatch = self.iconify_search_popup_state.as_ref().and_then(|state| {
             if state.active_tab != IconifySearchTab::Icons
                 || state.selected_collection_filter.is_some()
@@ -540,30 +1098,148 @@ impl App {
         if let Some(query) = query_to_dispatch {
             self.dispatch_iconify_search(query);
         }
+
+        if self
+            .iconify_search_popup_state
+            .as_ref()
+            .is_some_and(|state| state.pending_icon_filter)
+        {
+            self.dispatch_icon_filter();
+        }
+
+        if let Some((prefix, names)) = self
+            .iconify_search_popup_state
+            .as_mut()
+            .and_then(IconifySearchPopupState::follow_highlighted_collection)
+        {
+            self.dispatch_collection_samples(prefix, names);
+        }
+    }
+
+    fn dispatch_collection_samples(&mut self, prefix: String, names: Vec<String>) {
+        let request_id = self.next_request_id();
+        let Some(state) = self.iconify_search_popup_state.as_mut() else {
+            return;
+        };
+        state.latest_samples_request_id = request_id;
+
+        let tx = self.tx.clone();
+        let task = tokio::spawn(async move {
+            let mut samples = Vec::with_capacity(names.len());
+            match IconifyClient::from_env() {
+                Ok(client) => {
+                    for name in names {
+                        let svg = client
+                            .svg(&format!("{prefix}:{name}"))
+                            .await
+                            .map_err(|error| error.to_string());
+                        samples.push((name, svg));
+                    }
+                }
+                Err(error) => {
+                    samples.extend(names.into_iter().map(|name| (name, Err(error.to_string()))));
+                }
+            }
+            let _ = tx.send(AppEvent::CollectionSamplesFetched {
+                request_id,
+                samples,
+            });
+        });
+        state.samples_task.start(request_id, task.abort_handle());
+    }
+
+    /// Ranks the open collection against the current query on a blocking
+    /// worker. Narrowing starts from the results on screen, which are still
+    /// valid for any query that extends theirs.
+    fn dispatch_icon_filter(&mut self) {
+        let request_id = self.next_request_id();
+        let Some(state) = self.iconify_search_popup_state.as_mut() else {
+            return;
+        };
+        state.pending_icon_filter = false;
+        state.latest_icon_filter_request_id = request_id;
+        state.is_filtering_icons = true;
+
+        let source = Arc::clone(&state.collection_icons);
+        let query = state.search_value.trim().to_string();
+        let mut view = state.visible_icons.clone();
+        let tx = self.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            view.refilter(&source, &query, |icon| icon_haystack(icon));
+            let _ = tx.send(AppEvent::IconFilterRanked {
+                request_id,
+                source,
+                query,
+                view,
+            });
+        });
     }
 
     pub fn handle_app_event(&mut self, event: AppEvent) {
         match event {
+            AppEvent::AddPreviewFetched { request_id, result } => {
+                self.handle_add_preview_loaded(request_id, result);
+            }
+            AppEvent::IconifyFeatureUnsupported {
+                request_id,
+                feature,
+                message,
+            } => {
+                if let Some(state) = self.iconify_search_popup_state.as_mut() {
+                    state.handle_unsupported_feature(request_id, feature, message);
+                }
+            }
+            AppEvent::CollectionSamplesFetched {
+                request_id,
+                samples,
+            } => {
+                if let Some(state) = self.iconify_search_popup_state.as_mut()
+                    && request_id == state.latest_samples_request_id
+                {
+                    state.samples_task.finish(request_id);
+                    state.samples = samples
+                        .into_iter()
+                        .map(|(name, result)| {
+                            let preview = match result {
+                                Ok(svg) => preview_from_svg(&svg),
+                                Err(error) => AddPreview::Failed(error),
+                            };
+                            (name, preview)
+                        })
+                        .collect();
+                }
+            }
             AppEvent::IconifyCollectionsLoaded { request_id, result } => {
                 if let Some(state) = self.iconify_search_popup_state.as_mut() {
                     if request_id != state.latest_collections_request_id {
                         return;
                     }
+                    state.collections_task.finish(request_id);
 
                     state.is_loading_collections = false;
 
                     match result {
-                        Ok(items) => {
-                            state.all_collections = items;
+                        Ok(payload) => {
+                            state.all_collections = payload.collections;
+                            state.collections_stale = payload.stale_reason.is_some();
+                            state.visible_collections.clear();
                             state.refresh_filtered_collections();
                             state.clamp_collection_selection();
-                            if state.all_collections.is_empty() {
+                            if let Some(reason) = payload.stale_reason {
+                                state.set_status(
+                                    format!("Offline, showing cached collections: {reason}"),
+                                    true,
+                                );
+                            } else if state.all_collections.is_empty() {
                                 state.set_status("No Iconify collections found.".to_string(), true);
                             } else if !state.is_loading_search {
                                 state.clear_status();
                             }
                         }
-                        Err(error) => state.set_status(error, true),
+                        Err(error) => state.set_status(
+                            format!("{error} (type an exact name like mdi:home and press enter)"),
+                            true,
+                        ),
                     }
                 }
             }
@@ -576,6 +1252,7 @@ impl App {
                     if request_id != state.latest_search_request_id {
                         return;
                     }
+                    state.search_task.finish(request_id);
 
                     if query != state.search_value.trim() {
                         return;
@@ -593,11 +1270,11 @@ impl App {
                     match result {
                         Ok(payload) => {
                             state.search_icons = payload.icons;
+                            state.visible_icons.clear();
                             state.clamp_collection_selection();
                             state.refresh_visible_icons();
 
-                            if state.search_icons.is_empty()
-                                && state.active_collections().is_empty()
+                            if state.search_icons.is_empty() && state.visible_collections.is_empty()
                             {
                                 state.set_status(
                                     "No matching icons or collections.".to_string(),
@@ -609,12 +1286,38 @@ impl App {
                         }
                         Err(error) => {
                             state.search_icons.clear();
+                            state.visible_icons.clear();
                             state.refresh_visible_icons();
                             state.set_status(error, true);
                         }
                     }
                 }
             }
+            AppEvent::IconFilterRanked {
+                request_id,
+                source,
+                query,
+                view,
+            } => {
+                if let Some(state) = self.iconify_search_popup_state.as_mut() {
+                    if request_id != state.latest_icon_filter_request_id
+                        || !Arc::ptr_eq(&source, &state.collection_icons)
+                        || query != state.search_value.trim()
+                    {
+                        return;
+                    }
+
+                    state.is_filtering_icons = false;
+                    state.visible_icons = view;
+                    state.clamp_icon_selection();
+                    if state.visible_icons.is_empty() {
+                        state
+                            .set_status("No matching icons in this collection.".to_string(), false);
+                    } else {
+                        state.clear_status();
+                    }
+                }
+            }
             AppEvent::IconifyCollectionIconsLoaded {
                 request_id,
                 prefix,
@@ -624,18 +1327,21 @@ impl App {
                     if request_id != state.latest_collection_icons_request_id {
                         return;
                     }
+                    state.collection_icons_task.finish(request_id);
 
                     state.is_loading_collection_icons = false;
 
                     match result {
                         Ok(icons) => {
                             state.collection_icons_prefix = Some(prefix);
-                            state.collection_icons = icons;
+                            state.collection_icons = icons.into();
+                            state.visible_icons.clear();
                             state.refresh_visible_icons();
 
                             if state.collection_icons.is_empty() {
                                 state.set_status("No icons in this collection.".to_string(), false);
                             } else if !state.search_value.trim().is_empty()
+                                && !state.pending_icon_filter
                                 && state.visible_icons.is_empty()
                             {
                                 state.set_status(
@@ -647,8 +1353,9 @@ impl App {
                             }
                         }
                         Err(error) => {
-                            state.collection_icons.clear();
+                            state.collection_icons = Arc::from([]);
                             state.collection_icons_prefix = None;
+                            state.visible_icons.clear();
                             state.refresh_visible_icons();
                             state.set_status(error, true);
                         }
@@ -670,13 +1377,16 @@ impl App {
         state.set_status("Loading collections...".to_string(), false);
 
         let tx = self.tx.clone();
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             let result = async {
-                let client = IconifyClient::from_env().map_err(|error| error.to_string())?;
-                let response = client
-                    .collections()
-                    .await
-                    .map_err(|error| error.to_string())?;
+                let client = IconifyClient::from_env()?;
+                let (response, stale_reason) = match client.collections().await {
+                    Ok(response) => (response, None),
+                    Err(error) => match client.cached_collections() {
+                        Some(response) => (response, Some(error.to_string())),
+                        None => return Err(error),
+                    },
+                };
 
                 let mut collections: Vec<IconifyCollectionListItem> = response
                     .collections
@@ -684,17 +1394,31 @@ impl App {
                     .map(|(prefix, meta)| IconifyCollectionListItem {
                         name: meta.display_name(&prefix),
                         total: meta.total,
+                        details: IconifyCollectionDetails {
+                            author: meta.author(),
+                            license: meta.license(),
+                            palette: meta.palette(),
+                            samples: meta.samples(),
+                        },
                         prefix,
                     })
                     .collect();
 
                 collections.sort_by(|a, b| a.prefix.cmp(&b.prefix));
-                Ok::<Vec<IconifyCollectionListItem>, String>(collections)
+                Ok::<IconifyCollectionsPayload, IconifyError>(IconifyCollectionsPayload {
+                    collections,
+                    stale_reason,
+                })
             }
             .await;
 
-            let _ = tx.send(AppEvent::IconifyCollectionsLoaded { request_id, result });
+            let _ = tx.send(iconify_event(request_id, result, |result| {
+                AppEvent::IconifyCollectionsLoaded { request_id, result }
+            }));
         });
+        state
+            .collections_task
+            .start(request_id, task.abort_handle());
     }
 
     fn dispatch_iconify_search(&mut self, query: String) {
@@ -718,26 +1442,28 @@ impl App {
         state.is_loading_search = true;
 
         let tx = self.tx.clone();
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             let result = async {
-                let client = IconifyClient::from_env().map_err(|error| error.to_string())?;
+                let client = IconifyClient::from_env()?;
                 let response = client
                     .search(&query, Some(SEARCH_LIMIT), None, false)
-                    .await
-                    .map_err(|error| error.to_string())?;
+                    .await?;
 
-                Ok::<IconifySearchPayload, String>(IconifySearchPayload {
+                Ok::<IconifySearchPayload, IconifyError>(IconifySearchPayload {
                     icons: response.icons,
                 })
             }
             .await;
 
-            let _ = tx.send(AppEvent::IconifySearchLoaded {
-                request_id,
-                query,
-                result,
-            });
+            let _ = tx.send(iconify_event(request_id, result, |result| {
+                AppEvent::IconifySearchLoaded {
+                    request_id,
+                    query,
+                    result,
+                }
+            }));
         });
+        state.search_task.start(request_id, task.abort_handle());
     }
 
     fn open_collection_icons(&mut self, prefix: String) {
@@ -747,12 +1473,14 @@ impl App {
             state.active_tab = IconifySearchTab::Icons;
             state.selected_collection_filter = Some(prefix.clone());
             state.selected_icon_index = 0;
+            state.visible_icons.clear();
             state.refresh_visible_icons();
             state.sync_search_dispatch_state();
 
             should_fetch = state.collection_icons_prefix.as_deref() != Some(prefix.as_str());
             if should_fetch {
-                state.collection_icons.clear();
+                state.collection_icons = Arc::from([]);
+                state.visible_icons.clear();
                 state.refresh_visible_icons();
             }
         }
@@ -772,7 +1500,7 @@ impl App {
         state.set_status(format!("Loading icons for collection '{prefix}'..."), false);
 
         let tx = self.tx.clone();
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             let result = async {
                 let client = IconifyClient::from_env().map_err(|error| error.to_string())?;
                 let response = client
@@ -796,6 +1524,9 @@ impl App {
                 result,
             });
         });
+        state
+            .collection_icons_task
+            .start(request_id, task.abort_handle());
     }
 
     fn open_icon_browser_preview(&mut self, icon_name: String) {
@@ -828,7 +1559,9 @@ impl App {
     }
 }
 
-fn icones_collection_url(icon_name: &str) -> Option<String> {
+/// `mdi:heart` -> its page on icones.js.org, with the collection's other
+/// variants and its license.
+pub fn icones_collection_url(icon_name: &str) -> Option<String> {
     let (prefix, _) = icon_name.split_once(':')?;
     Some(format!(
         "https://icones.js.org/collection/{prefix}?icon={icon_name}"
@@ -843,6 +1576,7 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
 
     let area = popup_area(f.area(), 92, 23);
     let body_area = crate::views::theme::render_popup_shell(f, area, "Iconify Search");
+    let preview_colors = app.config.preview_colors;
 
     let inner = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
@@ -864,15 +1598,15 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
         .title("Search")
         .title_style(
             Style::default()
-                .fg(crate::views::theme::MUTED_TEXT)
+                .fg(crate::views::theme::palette().muted_text)
                 .add_modifier(Modifier::BOLD),
         )
-        .style(Style::default().fg(crate::views::theme::TEXT));
+        .style(Style::default().fg(crate::views::theme::palette().text));
     state.search_textarea.set_block(search_block);
     state.search_textarea.set_cursor_style(
         Style::default()
-            .bg(crate::views::theme::ACCENT)
-            .fg(crate::views::theme::BASE_BG),
+            .bg(crate::views::theme::palette().accent)
+            .fg(crate::views::theme::palette().base_bg),
     );
     state
         .search_textarea
@@ -881,44 +1615,62 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
 
     let collections_style = if state.active_tab == IconifySearchTab::Collections {
         Style::default()
-            .bg(crate::views::theme::TAB_BG_ACTIVE)
-            .fg(crate::views::theme::BASE_BG)
+            .bg(crate::views::theme::palette().tab_bg_active)
+            .fg(crate::views::theme::palette().base_bg)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
-            .bg(crate::views::theme::TAB_BG)
-            .fg(crate::views::theme::MUTED_TEXT)
+            .bg(crate::views::theme::palette().tab_bg)
+            .fg(crate::views::theme::palette().muted_text)
     };
     let icons_style = if state.active_tab == IconifySearchTab::Icons {
         Style::default()
-            .bg(crate::views::theme::TAB_BG_ACTIVE)
-            .fg(crate::views::theme::BASE_BG)
+            .bg(crate::views::theme::palette().tab_bg_active)
+            .fg(crate::views::theme::palette().base_bg)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
-            .bg(crate::views::theme::TAB_BG)
-            .fg(crate::views::theme::MUTED_TEXT)
+            .bg(crate::views::theme::palette().tab_bg)
+            .fg(crate::views::theme::palette().muted_text)
     };
 
-    let mut tabs_spans = vec![
-        Span::styled(" Collections ", collections_style),
-        Span::styled(" | ", Style::default().fg(crate::views::theme::SUBTLE_TEXT)),
-        Span::styled(" Icons ", icons_style),
-    ];
+    let mut tabs_spans = if state.collections_unsupported {
+        vec![Span::styled(" Icons ", icons_style)]
+    } else {
+        vec![
+            Span::styled(" Collections ", collections_style),
+            Span::styled(
+                " | ",
+                Style::default().fg(crate::views::theme::palette().subtle_text),
+            ),
+            Span::styled(" Icons ", icons_style),
+        ]
+    };
     if let Some(prefix) = &state.selected_collection_filter {
         tabs_spans.push(Span::raw("  "));
         tabs_spans.push(Span::styled(
             format!("collection: {prefix}"),
-            Style::default().fg(crate::views::theme::SUBTLE_TEXT),
+            Style::default().fg(crate::views::theme::palette().subtle_text),
         ));
     }
 
     let tabs = Paragraph::new(Line::from(tabs_spans)).alignment(Alignment::Left);
     f.render_widget(tabs, inner[1]);
 
+    let (list_area, details_area) = if state.active_tab == IconifySearchTab::Collections
+        && inner[3].width >= DETAILS_WIDTH + DETAILS_MIN_LIST_WIDTH
+    {
+        let columns = ratatui::layout::Layout::default()
+            .direction(ratatui::layout::Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(DETAILS_WIDTH)])
+            .split(inner[3]);
+        (columns[0], Some(columns[1]))
+    } else {
+        (inner[3], None)
+    };
+
     // The List block has a title which consumes the top row; subtract it so
     // scroll math + mouse hit-testing use the actual item-rows area.
-    let list_area = inner[3];
     let list_rows_area = Rect {
         x: list_area.x,
         y: list_area.y.saturating_add(1),
@@ -928,46 +1680,7 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
     let list_visible_height = list_rows_area.height as usize;
     match state.active_tab {
         IconifySearchTab::Collections => {
-            let (items, col_len, col_is_empty): (Vec<ListItem>, usize, bool) = {
-                let collection_items = state.active_collections();
-                let col_len = collection_items.len();
-                let col_is_empty = collection_items.is_empty();
-                let items = if col_is_empty {
-                    vec![ListItem::new(Line::from(Span::styled(
-                        "No collections",
-                        Style::default().fg(crate::views::theme::SUBTLE_TEXT),
-                    )))]
-                } else {
-                    collection_items
-                        .iter()
-                        .map(|item| {
-                            let total_label = match item.total {
-                                Some(total) => format!("{total}"),
-                                None => "-".to_string(),
-                            };
-                            let line = Line::from(vec![
-                                Span::styled(
-                                    format!("{: <10}", item.prefix),
-                                    Style::default()
-                                        .fg(crate::views::theme::ACCENT_SOFT)
-                                        .add_modifier(Modifier::BOLD),
-                                ),
-                                Span::styled(
-                                    format!(" {total_label: <6}"),
-                                    Style::default().fg(crate::views::theme::MUTED_TEXT),
-                                ),
-                                Span::styled(
-                                    item.name.clone(),
-                                    Style::default().fg(crate::views::theme::TEXT),
-                                ),
-                            ]);
-                            ListItem::new(line)
-                        })
-                        .collect()
-                };
-                (items, col_len, col_is_empty)
-            };
-
+            let col_len = state.visible_collections.len();
             crate::scroll::clamp_offset(
                 &mut state.collections_scroll_offset,
                 col_len,
@@ -980,63 +1693,111 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
             );
             state.collections_list_area = Some(list_rows_area);
 
+            // Only the rows on screen are built; big filtered lists stay
+            // index-only.
+            let items: Vec<ListItem> = if state.visible_collections.is_empty() {
+                vec![ListItem::new(Line::from(Span::styled(
+                    "No collections",
+                    Style::default().fg(crate::views::theme::palette().subtle_text),
+                )))]
+            } else {
+                state
+                    .visible_collections
+                    .window(state.collections_scroll_offset, list_visible_height)
+                    .iter()
+                    .map(|&index| {
+                        let item = &state.all_collections[index];
+                        let total_label = match item.total {
+                            Some(total) => format!("{total}"),
+                            None => "-".to_string(),
+                        };
+                        let marker = if state.collection_prefs.is_pinned(&item.prefix) {
+                            "★ "
+                        } else if state.collection_prefs.is_hidden(&item.prefix) {
+                            "· "
+                        } else {
+                            "  "
+                        };
+                        let line = Line::from(vec![
+                            Span::styled(
+                                marker,
+                                Style::default().fg(crate::views::theme::palette().accent),
+                            ),
+                            Span::styled(
+                                format!("{: <10}", item.prefix),
+                                Style::default()
+                                    .fg(crate::views::theme::palette().accent_soft)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!(" {total_label: <6}"),
+                                Style::default().fg(crate::views::theme::palette().muted_text),
+                            ),
+                            Span::styled(
+                                item.name.clone(),
+                                Style::default().fg(crate::views::theme::palette().text),
+                            ),
+                        ]);
+                        ListItem::new(line)
+                    })
+                    .collect()
+            };
+
             let mut list_state = ratatui::widgets::ListState::default();
-            if !col_is_empty {
-                *list_state.offset_mut() = state.collections_scroll_offset;
+            if col_len > 0 {
                 let in_view = list_visible_height > 0
                     && state.selected_collection_index >= state.collections_scroll_offset
                     && state.selected_collection_index
                         < state.collections_scroll_offset + list_visible_height;
                 if in_view {
-                    list_state.select(Some(state.selected_collection_index));
+                    list_state.select(Some(
+                        state.selected_collection_index - state.collections_scroll_offset,
+                    ));
                 }
             }
 
             let list = List::new(items)
                 .block(
                     Block::default()
-                        .title("Collections")
+                        .title(if state.collections_stale {
+                            Line::from(vec![
+                                Span::raw("Collections "),
+                                Span::styled(
+                                    " stale data ",
+                                    Style::default()
+                                        .bg(crate::views::theme::palette().error)
+                                        .fg(crate::views::theme::palette().base_bg),
+                                ),
+                            ])
+                        } else {
+                            Line::from("Collections")
+                        })
                         .title_style(
                             Style::default()
-                                .fg(crate::views::theme::MUTED_TEXT)
+                                .fg(crate::views::theme::palette().muted_text)
                                 .add_modifier(Modifier::BOLD),
                         )
                         .style(
                             Style::default()
-                                .bg(crate::views::theme::PANEL_BG)
-                                .fg(crate::views::theme::TEXT),
+                                .bg(crate::views::theme::palette().panel_bg)
+                                .fg(crate::views::theme::palette().text),
                         ),
                 )
                 .highlight_symbol("  ")
                 .highlight_style(
                     Style::default()
-                        .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-                        .fg(crate::views::theme::BASE_BG)
+                        .bg(crate::views::theme::palette().row_highlight_bg)
+                        .fg(crate::views::theme::palette().base_bg)
                         .add_modifier(Modifier::BOLD),
                 );
-            f.render_stateful_widget(list, inner[3], &mut list_state);
+            f.render_stateful_widget(list, list_area, &mut list_state);
+
+            if let Some(area) = details_area {
+                render_collection_details(f, area, state, &preview_colors);
+            }
         }
         IconifySearchTab::Icons => {
-            let items: Vec<ListItem> = if state.visible_icons.is_empty() {
-                vec![ListItem::new(Line::from(Span::styled(
-                    "No icons",
-                    Style::default().fg(crate::views::theme::SUBTLE_TEXT),
-                )))]
-            } else {
-                state
-                    .visible_icons
-                    .iter()
-                    .map(|icon| {
-                        ListItem::new(Line::from(Span::styled(
-                            icon.clone(),
-                            Style::default().fg(crate::views::theme::TEXT),
-                        )))
-                    })
-                    .collect()
-            };
-
             let icons_len = state.visible_icons.len();
-            let icons_is_empty = state.visible_icons.is_empty();
             crate::scroll::clamp_offset(
                 &mut state.icons_scroll_offset,
                 icons_len,
@@ -1049,14 +1810,42 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
             );
             state.icons_list_area = Some(list_rows_area);
 
+            let items: Vec<ListItem> = if state.visible_icons.is_empty() {
+                vec![ListItem::new(Line::from(Span::styled(
+                    "No icons",
+                    Style::default().fg(crate::views::theme::palette().subtle_text),
+                )))]
+            } else {
+                let icons = state.icon_source();
+                let row_color = if state.icon_results_stale() {
+                    crate::views::theme::palette().subtle_text
+                } else {
+                    crate::views::theme::palette().text
+                };
+                state
+                    .visible_icons
+                    .window(state.icons_scroll_offset, list_visible_height)
+                    .iter()
+                    .map(|&index| {
+                        let marked = state.marked_icons.contains(&icons[index]);
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                if marked { "● " } else { "  " },
+                                Style::default().fg(crate::views::theme::palette().accent),
+                            ),
+                            Span::styled(icons[index].clone(), Style::default().fg(row_color)),
+                        ]))
+                    })
+                    .collect()
+            };
+
             let mut list_state = ratatui::widgets::ListState::default();
-            if !icons_is_empty {
-                *list_state.offset_mut() = state.icons_scroll_offset;
+            if icons_len > 0 && !state.icon_results_stale() {
                 let in_view = list_visible_height > 0
                     && state.selected_icon_index >= state.icons_scroll_offset
                     && state.selected_icon_index < state.icons_scroll_offset + list_visible_height;
                 if in_view {
-                    list_state.select(Some(state.selected_icon_index));
+                    list_state.select(Some(state.selected_icon_index - state.icons_scroll_offset));
                 }
             }
 
@@ -1072,20 +1861,20 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
                         .title(title)
                         .title_style(
                             Style::default()
-                                .fg(crate::views::theme::MUTED_TEXT)
+                                .fg(crate::views::theme::palette().muted_text)
                                 .add_modifier(Modifier::BOLD),
                         )
                         .style(
                             Style::default()
-                                .bg(crate::views::theme::PANEL_BG)
-                                .fg(crate::views::theme::TEXT),
+                                .bg(crate::views::theme::palette().panel_bg)
+                                .fg(crate::views::theme::palette().text),
                         ),
                 )
                 .highlight_symbol("  ")
                 .highlight_style(
                     Style::default()
-                        .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-                        .fg(crate::views::theme::BASE_BG)
+                        .bg(crate::views::theme::palette().row_highlight_bg)
+                        .fg(crate::views::theme::palette().base_bg)
                         .add_modifier(Modifier::BOLD),
                 );
             f.render_stateful_widget(list, inner[3], &mut list_state);
@@ -1096,6 +1885,8 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
         Some("Loading collection icons...")
     } else if state.is_loading_search {
         Some("Searching Iconify...")
+    } else if state.is_filtering_icons {
+        Some("Filtering icons...")
     } else if state.is_loading_collections {
         Some("Loading collections...")
     } else {
@@ -1107,9 +1898,9 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
         .or_else(|| state.status_message.clone())
         .unwrap_or_default();
     let status_color = if state.status_is_error {
-        crate::views::theme::ERROR
+        crate::views::theme::palette().error
     } else {
-        crate::views::theme::MUTED_TEXT
+        crate::views::theme::palette().muted_text
     };
     let status = Paragraph::new(status_message)
         .alignment(Alignment::Left)
@@ -1119,13 +1910,27 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
     let help_line = if state.active_tab == IconifySearchTab::Collections {
         crate::views::theme::shortcut_line(&[
             ("Open", "enter"),
+            ("Pin", "ctrl+p"),
+            ("Hide", "ctrl+x"),
+            ("Show hidden", "ctrl+t"),
             ("Switch", "tab"),
             ("Move", "up/down"),
             ("Close", "esc"),
         ])
+    } else if !state.marked_icons.is_empty() {
+        let add_label = format!("Add {} marked", state.marked_icons.len());
+        crate::views::theme::shortcut_line(&[
+            (add_label.as_str(), "enter"),
+            ("Mark", "space"),
+            ("Preview", "ctrl+o"),
+            ("Move", "up/down"),
+            ("Switch", "tab"),
+            ("Close", "esc"),
+        ])
     } else {
         crate::views::theme::shortcut_line(&[
             ("Use icon", "enter"),
+            ("Mark", "space"),
             ("Preview", "ctrl+o"),
             ("Move", "up/down"),
             ("Switch", "tab"),
@@ -1136,6 +1941,108 @@ pub fn render_iconify_search_popup(f: &mut Frame, app: &mut App) {
     f.render_widget(help, inner[5]);
 }
 
+/// License, author and a few drawn samples of the highlighted collection, to
+/// judge it before opening it.
+fn render_collection_details(
+    f: &mut Frame,
+    area: Rect,
+    state: &IconifySearchPopupState,
+    colors: &crate::viewer::PreviewColors,
+) {
+    use ratatui::{
+        style::Modifier,
+        text::{Line, Span},
+    };
+
+    let block = Block::default()
+        .title("Details")
+        .title_style(
+            Style::default()
+                .fg(crate::views::theme::palette().muted_text)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().bg(crate::views::theme::palette().input_bg));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(item) = state.visible_collection(state.selected_collection_index) else {
+        return;
+    };
+    let label = Style::default().fg(crate::views::theme::palette().muted_text);
+    let text = Style::default().fg(crate::views::theme::palette().text);
+    let field = |name: &'static str, value: Option<String>| {
+        Line::from(vec![
+            Span::styled(format!("{name: <9}"), label),
+            Span::styled(value.unwrap_or_else(|| "-".to_string()), text),
+        ])
+    };
+    let details = &item.details;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            item.name.clone(),
+            text.add_modifier(Modifier::BOLD),
+        )),
+        field("Author", details.author.clone()),
+        field("License", details.license.clone()),
+        field(
+            "Colors",
+            details.palette.map(|palette| {
+                if palette {
+                    "multicolor".to_string()
+                } else {
+                    "currentColor".to_string()
+                }
+            }),
+        ),
+        field("Icons", item.total.map(|total| total.to_string())),
+        Line::default(),
+    ];
+    if state.samples_prefix.as_deref() == Some(item.prefix.as_str()) {
+        let style = crate::views::add_popup::preview_style(colors);
+        lines.extend(
+            sample_lines(&state.samples)
+                .into_iter()
+                .map(|line| Line::from(Span::styled(line, style))),
+        );
+        lines.push(Line::from(Span::styled(
+            state
+                .samples
+                .iter()
+                .map(|(name, _)| pad_sample(&name.chars().take(SAMPLE_COLUMNS).collect::<String>()))
+                .collect::<Vec<_>>()
+                .join(" "),
+            label,
+        )));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// The samples' braille drawings side by side, one string per text row.
+fn sample_lines(samples: &[(String, AddPreview)]) -> Vec<String> {
+    let columns: Vec<Vec<String>> = samples
+        .iter()
+        .map(|(_, preview)| match preview {
+            AddPreview::Ready(lines) => lines.clone(),
+            AddPreview::Loading => vec!["…".to_string()],
+            AddPreview::Empty | AddPreview::Failed(_) => vec!["×".to_string()],
+        })
+        .collect();
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+    (0..height)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| pad_sample(column.get(row).map(String::as_str).unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+fn pad_sample(text: &str) -> String {
+    format!("{text: <SAMPLE_COLUMNS$}")
+}
+
 #[cfg(any())]
 mod tests {
     use super::{
@@ -1150,16 +2057,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let folder = temp_dir.path().join("icons");
 
-        let config = AppConfig {
-            folder: folder.to_string_lossy().into_owned(),
-            preset: "normal".to_string(),
-            svg_viewer_cmd: None,
-            svg_viewer_cmd_source: "test".to_string(),
-            global_config_loaded: false,
-            project_config_loaded: false,
-            flutter_barrel_file: None,
-            flutter_barrel_class: None,
-        };
+        let config = AppConfig::for_tests(folder.to_string_lossy().into_owned(), "normal");
 
         App::new(config)
     }
@@ -1185,11 +2083,13 @@ mod tests {
                 prefix: "lucide".to_string(),
                 name: "Lucide".to_string(),
                 total: Some(100),
+                details: Default::default(),
             },
             IconifyCollectionListItem {
                 prefix: "mdi".to_string(),
                 name: "Material Design Icons".to_string(),
                 total: Some(200),
+                details: Default::default(),
             },
         ];
 
@@ -1326,7 +2226,7 @@ mod tests {
     }
 }
 /*
-                        .fg(crate::views::theme::ACCENT)
+                        .fg(crate::views::theme::palette().accent)
                         .add_modifier(Modifier::BOLD),
                 );
             f.render_stateful_widget(list, inner[3], &mut list_state);
@@ -1335,7 +2235,7 @@ mod tests {
             let items: Vec<ListItem> = if state.visible_icons.is_empty() {
                 vec![ListItem::new(Line::from(Span::styled(
                     "No icons",
-                    Style::default().fg(crate::views::theme::SUBTLE_TEXT),
+                    Style::default().fg(crate::views::theme::palette().subtle_text),
                 )))]
             } else {
                 state
@@ -1344,7 +2244,7 @@ mod tests {
                     .map(|icon| {
                         ListItem::new(Line::from(Span::styled(
                             icon.clone(),
-                            Style::default().fg(crate::views::theme::TEXT),
+                            Style::default().fg(crate::views::theme::palette().text),
                         )))
                     })
                     .collect()
@@ -1367,19 +2267,19 @@ mod tests {
                         .title(title)
                         .title_style(
                             Style::default()
-                                .fg(crate::views::theme::MUTED_TEXT)
+                                .fg(crate::views::theme::palette().muted_text)
                                 .add_modifier(Modifier::BOLD),
                         )
                         .style(
                             Style::default()
-                                .bg(crate::views::theme::PANEL_BG)
-                                .fg(crate::views::theme::TEXT),
+                                .bg(crate::views::theme::palette().panel_bg)
+                                .fg(crate::views::theme::palette().text),
                         ),
                 )
                 .highlight_symbol("  ")
                 .highlight_style(
                     Style::default()
-                        .fg(crate::views::theme::ACCENT)
+                        .fg(crate::views::theme::palette().accent)
                         .add_modifier(Modifier::BOLD),
                 );
             f.render_stateful_widget(list, inner[3], &mut list_state);
@@ -1401,9 +2301,9 @@ mod tests {
         .or_else(|| state.status_message.clone())
         .unwrap_or_default();
     let status_color = if state.status_is_error {
-        crate::views::theme::ERROR
+        crate::views::theme::palette().error
     } else {
-        crate::views::theme::MUTED_TEXT
+        crate::views::theme::palette().muted_text
     };
     let status = Paragraph::new(status_message)
         .alignment(Alignment::Left)
@@ -1435,10 +2335,13 @@ mod tests {
 #[cfg(test)]
 mod tests {
     use super::{
-        IconifySearchPopupState, IconifySearchTab, fuzzy_filter_collections, fuzzy_filter_icons,
-        icones_collection_url,
+        AddPreview, BACKGROUND_FILTER_MIN_ICONS, FilteredView, IconifySearchPopupState,
+        IconifySearchTab, collection_haystack, icon_haystack, icones_collection_url, query_narrows,
+        sample_lines,
     };
+    use crate::app_state::AppEvent;
     use crate::app_state::{App, AppConfig, AppFocus, IconifyCollectionListItem};
+    use std::sync::Arc;
     use tempfile::TempDir;
     use tui_textarea::{Input, Key};
 
@@ -1446,16 +2349,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("temp dir should be created");
         let folder = temp_dir.path().join("icons");
 
-        let config = AppConfig {
-            folder: folder.to_string_lossy().into_owned(),
-            preset: "normal".to_string(),
-            svg_viewer_cmd: None,
-            svg_viewer_cmd_source: "test".to_string(),
-            global_config_loaded: false,
-            project_config_loaded: false,
-            flutter_barrel_file: None,
-            flutter_barrel_class: None,
-        };
+        let config = AppConfig::for_tests(folder.to_string_lossy().into_owned(), "normal");
 
         App::new(config)
     }
@@ -1480,17 +2374,20 @@ mod tests {
                 prefix: "lucide".to_string(),
                 name: "Lucide Icons".to_string(),
                 total: Some(100),
+                details: Default::default(),
             },
             IconifyCollectionListItem {
                 prefix: "mdi".to_string(),
                 name: "Material Design Icons".to_string(),
                 total: Some(100),
+                details: Default::default(),
             },
         ];
 
-        let filtered = fuzzy_filter_collections(&collections, "lcd");
+        let mut filtered = FilteredView::default();
+        filtered.refilter(&collections, "lcd", collection_haystack);
         assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].prefix, "lucide");
+        assert_eq!(collections[filtered.get(0).unwrap()].prefix, "lucide");
     }
 
     #[test]
@@ -1501,8 +2398,36 @@ mod tests {
             "lucide:home".to_string(),
         ];
 
-        let filtered = fuzzy_filter_icons(&icons, "bn");
-        assert_eq!(filtered, vec!["lucide:bean".to_string()]);
+        let mut filtered = FilteredView::default();
+        filtered.refilter(&icons, "bn", |icon| icon_haystack(icon));
+        assert_eq!(filtered.window(0, 10), &[0]);
+    }
+
+    #[test]
+    fn typing_narrows_previous_matches_and_matches_a_full_rescan() {
+        let icons: Vec<String> = (0..7_000)
+            .map(|n| format!("mdi:icon-{n}-{}", ["arrow", "bean", "home"][n % 3]))
+            .collect();
+
+        let mut incremental = FilteredView::default();
+        for query in ["a", "ar", "arr", "arrow 12"] {
+            incremental.refilter(&icons, query, |icon| icon_haystack(icon));
+            let mut rescan = FilteredView::default();
+            rescan.refilter(&icons, query, |icon| icon_haystack(icon));
+            assert_eq!(
+                incremental.window(0, usize::MAX),
+                rescan.window(0, usize::MAX)
+            );
+        }
+        assert!(incremental.len() <= icons.len() / 3);
+        assert!(incremental.window(5, 3).len() <= 3);
+
+        incremental.refilter(&icons, "", |icon| icon_haystack(icon));
+        assert_eq!(incremental.len(), icons.len());
+
+        assert!(query_narrows("arr", "arro"));
+        assert!(!query_narrows("arro", "arr"));
+        assert!(!query_narrows("arr", "arr !x"));
     }
 
     #[test]
@@ -1518,7 +2443,8 @@ mod tests {
             "lucide:bean".to_string(),
             "lucide:beaker".to_string(),
             "lucide:home".to_string(),
-        ];
+        ]
+        .into();
         state.refresh_visible_icons();
         app.iconify_search_popup_state = Some(state);
 
@@ -1538,7 +2464,89 @@ mod tests {
         assert_eq!(state.search_value, "bn");
         assert!(state.pending_search_query.is_none());
         assert!(!state.is_loading_search);
-        assert_eq!(state.visible_icons, vec!["lucide:bean".to_string()]);
+        assert_eq!(state.visible_icons.len(), 1);
+        assert_eq!(state.selected_icon_name().as_deref(), Some("lucide:bean"));
+    }
+
+    #[test]
+    fn big_collection_filters_on_worker_and_drops_stale_results() {
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+
+        let icons: Arc<[String]> = (0..BACKGROUND_FILTER_MIN_ICONS)
+            .map(|n| format!("mdi:{}-{n}", ["bean", "home"][n % 2]))
+            .collect();
+        let mut state = IconifySearchPopupState::new();
+        state.active_tab = IconifySearchTab::Icons;
+        state.selected_collection_filter = Some("mdi".to_string());
+        state.collection_icons_prefix = Some("mdi".to_string());
+        state.collection_icons = Arc::clone(&icons);
+        state.refresh_visible_icons();
+        app.iconify_search_popup_state = Some(state);
+
+        app.handlekeys_iconify_search_popup(Input {
+            key: Key::Char('b'),
+            ..Default::default()
+        });
+        let state = app.iconify_search_popup_state.as_mut().unwrap();
+        // The keystroke doesn't rank anything; the old rows stay until the
+        // worker answers, but can't be picked.
+        assert!(state.pending_icon_filter);
+        assert_eq!(state.visible_icons.len(), icons.len());
+        assert_eq!(state.selected_icon_name(), None);
+
+        state.pending_icon_filter = false;
+        state.latest_icon_filter_request_id = 7;
+        let mut view = FilteredView::default();
+        view.refilter(&icons, "b", |icon| icon_haystack(icon));
+        for (request_id, query) in [(6, "b"), (7, "x")] {
+            app.handle_app_event(AppEvent::IconFilterRanked {
+                request_id,
+                source: Arc::clone(&icons),
+                query: query.to_string(),
+                view: FilteredView::default(),
+            });
+        }
+        assert_eq!(
+            app.iconify_search_popup_state
+                .as_ref()
+                .unwrap()
+                .visible_icons
+                .len(),
+            icons.len()
+        );
+
+        app.handle_app_event(AppEvent::IconFilterRanked {
+            request_id: 7,
+            source: Arc::clone(&icons),
+            query: "b".to_string(),
+            view,
+        });
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert_eq!(state.visible_icons.len(), icons.len() / 2);
+        assert_eq!(state.selected_icon_name().as_deref(), Some("mdi:bean-0"));
+    }
+
+    #[tokio::test]
+    async fn superseded_and_orphaned_requests_are_aborted() {
+        let pending = || tokio::spawn(std::future::pending::<()>());
+
+        let mut state = IconifySearchPopupState::new();
+        let first = pending();
+        state.collection_icons_task.start(1, first.abort_handle());
+        let second = pending();
+        state.collection_icons_task.start(2, second.abort_handle());
+        assert!(first.await.unwrap_err().is_cancelled());
+
+        // A query change cancels the remote search for the old query.
+        let search = pending();
+        state.search_task.start(3, search.abort_handle());
+        state.sync_search_dispatch_state();
+        assert!(search.await.unwrap_err().is_cancelled());
+
+        // Closing the popup drops the state and everything still in flight.
+        drop(state);
+        assert!(second.await.unwrap_err().is_cancelled());
     }
 
     #[test]
@@ -1563,6 +2571,300 @@ mod tests {
         assert!(state.is_loading_search);
     }
 
+    #[test]
+    fn offline_popup_shows_cached_collections_and_accepts_exact_names() {
+        use crate::app_state::IconifyCollectionsPayload;
+
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+        let mut state = IconifySearchPopupState::new();
+        state.latest_collections_request_id = 7;
+        app.iconify_search_popup_state = Some(state);
+
+        app.handle_app_event(AppEvent::IconifyCollectionsLoaded {
+            request_id: 7,
+            result: Ok(IconifyCollectionsPayload {
+                collections: vec![IconifyCollectionListItem {
+                    prefix: "lucide".to_string(),
+                    name: "Lucide".to_string(),
+                    total: Some(1500),
+                    details: Default::default(),
+                }],
+                stale_reason: Some("Iconify network error: offline".to_string()),
+            }),
+        });
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(state.collections_stale);
+        assert_eq!(state.visible_collections.len(), 1);
+        assert!(state.status_is_error);
+
+        for ch in "mdi:home".chars() {
+            app.handlekeys_iconify_search_popup(Input {
+                key: Key::Char(ch),
+                ..Default::default()
+            });
+        }
+        assert!(
+            app.iconify_search_popup_state
+                .as_ref()
+                .unwrap()
+                .visible_collections
+                .is_empty()
+        );
+        app.handlekeys_iconify_search_popup(Input {
+            key: Key::Enter,
+            ..Default::default()
+        });
+
+        assert_eq!(app.app_focus, AppFocus::AddPopup);
+        let add_state = app.add_popup_state.as_ref().expect("add popup should open");
+        assert_eq!(add_state.inputs[1].lines(), ["mdi:home"]);
+    }
+
+    #[test]
+    fn space_marks_icons_and_enter_asks_before_adding_them() {
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+        let mut state = IconifySearchPopupState::new();
+        state.active_tab = IconifySearchTab::Icons;
+        state.selected_collection_filter = Some("lucide".to_string());
+        state.collection_icons_prefix = Some("lucide".to_string());
+        state.collection_icons = vec![
+            "lucide:bean".to_string(),
+            "lucide:beaker".to_string(),
+            "lucide:home".to_string(),
+        ]
+        .into();
+        state.refresh_visible_icons();
+        app.iconify_search_popup_state = Some(state);
+        let press = |app: &mut App, key| {
+            app.handlekeys_iconify_search_popup(Input {
+                key,
+                ..Default::default()
+            })
+        };
+
+        press(&mut app, Key::Char(' '));
+        press(&mut app, Key::Down);
+        press(&mut app, Key::Down);
+        press(&mut app, Key::Char(' '));
+        press(&mut app, Key::Up);
+        press(&mut app, Key::Char(' '));
+        press(&mut app, Key::Char(' '));
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert_eq!(state.marked_icons, ["lucide:bean", "lucide:home"]);
+        assert_eq!(state.search_value, "");
+
+        press(&mut app, Key::Enter);
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(state.confirming_add);
+        assert!(
+            state
+                .status_message
+                .as_deref()
+                .unwrap()
+                .starts_with("Add 2 icons")
+        );
+
+        // Esc backs out of the confirmation and keeps the marks.
+        press(&mut app, Key::Esc);
+        assert_eq!(app.app_focus, AppFocus::IconifySearchPopup);
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(!state.confirming_add);
+        assert!(state.status_message.is_none());
+        assert_eq!(state.marked_icons.len(), 2);
+
+        // Confirming queues the batch for `tick` and counts it in the status;
+        // keys wait until it's done.
+        press(&mut app, Key::Enter);
+        press(&mut app, Key::Enter);
+        press(&mut app, Key::Esc);
+        assert_eq!(app.app_focus, AppFocus::IconifySearchPopup);
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert_eq!(
+            state.adding_marked.as_ref().unwrap().icon_names,
+            ["lucide:bean", "lucide:home"]
+        );
+        assert_eq!(
+            state.status_message.as_deref(),
+            Some("Adding 1 of 2: lucide:bean…")
+        );
+    }
+
+    #[test]
+    fn pinned_collections_sort_first_and_hidden_ones_drop_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let prefs_path = temp_dir.path().join("collections.json");
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+        let mut state = IconifySearchPopupState::new();
+        state.collection_prefs_path = Some(prefs_path.clone());
+        state.all_collections = ["lucide", "mdi", "ph"]
+            .into_iter()
+            .map(|prefix| IconifyCollectionListItem {
+                prefix: prefix.to_string(),
+                name: format!("{prefix} icons"),
+                total: Some(10),
+                details: Default::default(),
+            })
+            .collect();
+        state.refresh_filtered_collections();
+        app.iconify_search_popup_state = Some(state);
+        let press = |app: &mut App, key, ctrl| {
+            app.handlekeys_iconify_search_popup(Input {
+                key,
+                ctrl,
+                ..Default::default()
+            })
+        };
+        let prefixes = |app: &App| {
+            let state = app.iconify_search_popup_state.as_ref().unwrap();
+            (0..state.visible_collections.len())
+                .map(|position| state.visible_collection(position).unwrap().prefix.clone())
+                .collect::<Vec<_>>()
+        };
+
+        press(&mut app, Key::Down, false);
+        press(&mut app, Key::Down, false);
+        press(&mut app, Key::Char('p'), true);
+        assert_eq!(prefixes(&app), ["ph", "lucide", "mdi"]);
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert_eq!(state.selected_collection_prefix().as_deref(), Some("ph"));
+
+        press(&mut app, Key::Down, false);
+        press(&mut app, Key::Char('x'), true);
+        assert_eq!(prefixes(&app), ["ph", "mdi"]);
+        assert_eq!(
+            std::fs::read_to_string(&prefs_path).unwrap(),
+            "{\n  \"pinned\": [\n    \"ph\"\n  ],\n  \"hidden\": [\n    \"lucide\"\n  ]\n}\n"
+        );
+
+        // Pinned ones still lead a filtered list.
+        press(&mut app, Key::Char('t'), true);
+        for ch in "icons".chars() {
+            press(&mut app, Key::Char(ch), false);
+        }
+        assert_eq!(prefixes(&app), ["ph", "lucide", "mdi"]);
+    }
+
+    #[test]
+    fn detail_pane_fetches_samples_once_the_selection_rests() {
+        use crate::app_state::IconifyCollectionDetails;
+
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+        let mut state = IconifySearchPopupState::new();
+        state.all_collections = vec![IconifyCollectionListItem {
+            prefix: "mdi".to_string(),
+            name: "Material Design Icons".to_string(),
+            total: Some(7447),
+            details: IconifyCollectionDetails {
+                license: Some("Apache 2.0".to_string()),
+                samples: ["home", "bell", "star", "heart"]
+                    .map(str::to_string)
+                    .to_vec(),
+                ..Default::default()
+            },
+        }];
+        state.refresh_filtered_collections();
+
+        assert_eq!(state.follow_highlighted_collection(), None);
+        assert_eq!(state.samples_prefix.as_deref(), Some("mdi"));
+        assert_eq!(state.samples.len(), 3);
+        assert!(state.samples_deadline.is_some());
+
+        state.samples_deadline = Some(std::time::Instant::now());
+        let (prefix, names) = state.follow_highlighted_collection().unwrap();
+        assert_eq!(prefix, "mdi");
+        assert_eq!(names, ["home", "bell", "star"]);
+        assert_eq!(state.follow_highlighted_collection(), None);
+
+        state.latest_samples_request_id = 4;
+        app.iconify_search_popup_state = Some(state);
+        app.handle_app_event(AppEvent::CollectionSamplesFetched {
+            request_id: 4,
+            samples: vec![
+                (
+                    "home".to_string(),
+                    Ok(
+                        r#"<svg viewBox="0 0 24 24"><rect width="24" height="24"/></svg>"#
+                            .to_string(),
+                    ),
+                ),
+                ("bell".to_string(), Err("offline".to_string())),
+            ],
+        });
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(matches!(state.samples[0].1, AddPreview::Ready(_)));
+        let lines = sample_lines(&state.samples);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], format!("{} ×{}", "⣿".repeat(12), " ".repeat(11)));
+        assert_eq!(lines[1], format!("{} {}", "⣿".repeat(12), " ".repeat(12)));
+    }
+
+    #[test]
+    fn servers_without_listing_or_search_fall_back_to_exact_names() {
+        use crate::iconify::IconifyFeature;
+
+        let mut app = test_app();
+        app.app_focus = AppFocus::IconifySearchPopup;
+        let mut state = IconifySearchPopupState::new();
+        state.latest_collections_request_id = 3;
+        state.is_loading_collections = true;
+        app.iconify_search_popup_state = Some(state);
+        let press = |app: &mut App, key| {
+            app.handlekeys_iconify_search_popup(Input {
+                key,
+                ..Default::default()
+            })
+        };
+
+        app.handle_app_event(AppEvent::IconifyFeatureUnsupported {
+            request_id: 3,
+            feature: IconifyFeature::Collections,
+            message: "this Iconify server doesn't offer collection listing".to_string(),
+        });
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert_eq!(state.active_tab, IconifySearchTab::Icons);
+        assert!(!state.is_loading_collections);
+        assert!(!state.status_is_error);
+        press(&mut app, Key::Tab);
+        assert_eq!(
+            app.iconify_search_popup_state.as_ref().unwrap().active_tab,
+            IconifySearchTab::Icons
+        );
+
+        for ch in "mdi:home".chars() {
+            press(&mut app, Key::Char(ch));
+        }
+        let state = app.iconify_search_popup_state.as_mut().unwrap();
+        assert_eq!(state.pending_search_query.as_deref(), Some("mdi:home"));
+        state.latest_search_request_id = 4;
+        app.handle_app_event(AppEvent::IconifyFeatureUnsupported {
+            request_id: 4,
+            feature: IconifyFeature::Search,
+            message: "this Iconify server doesn't offer icon search".to_string(),
+        });
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(state.search_unsupported);
+        assert!(
+            state
+                .status_message
+                .as_deref()
+                .unwrap()
+                .ends_with("(type an exact name like mdi:home and press enter)")
+        );
+
+        press(&mut app, Key::Backspace);
+        press(&mut app, Key::Char('e'));
+        let state = app.iconify_search_popup_state.as_ref().unwrap();
+        assert!(state.pending_search_query.is_none());
+        assert!(!state.is_loading_search);
+
+        press(&mut app, Key::Enter);
+        assert_eq!(app.app_focus, AppFocus::AddPopup);
+    }
+
     #[test]
     fn j_and_k_type_into_search_input() {
         let mut app = test_app();
@@ -0,0 +1,84 @@
+use ratatui::layout::Rect;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Below this many columns views switch to their compact layout.
+pub const COMPACT_WIDTH: u16 = 80;
+/// Below this many rows the main view drops its logo header.
+pub const COMPACT_HEIGHT: u16 = 24;
+
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < COMPACT_WIDTH
+}
+
+pub fn is_short(area: Rect) -> bool {
+    area.height < COMPACT_HEIGHT
+}
+
+/// Terminal columns `text` takes up; CJK and emoji take two each.
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Drops the first `offset` columns of `text`, marking the cut with `…`.
+/// A wide character straddling the cut is dropped whole.
+pub fn scroll_text(text: &str, offset: usize) -> String {
+    if offset == 0 {
+        return text.to_string();
+    }
+    let mut skipped = 0;
+    let rest: String = text
+        .chars()
+        .skip_while(|c| {
+            let skip = skipped <= offset;
+            skipped += c.width().unwrap_or(0);
+            skip
+        })
+        .collect();
+    format!("…{rest}")
+}
+
+/// Largest useful horizontal offset for `texts` (keeps the last char visible).
+pub fn max_scroll_offset<'a>(texts: impl IntoIterator<Item = &'a str>) -> usize {
+    texts
+        .into_iter()
+        .map(|text| display_width(text).saturating_sub(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Pasted text for a one-line input: line breaks become spaces and the ends
+/// are trimmed.
+pub fn single_line(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_text_marks_hidden_prefix() {
+        assert_eq!(scroll_text("./icons/heart.svg", 0), "./icons/heart.svg");
+        assert_eq!(scroll_text("./icons/heart.svg", 7), "…heart.svg");
+        assert_eq!(scroll_text("abc", 10), "…");
+        assert_eq!(scroll_text("./アイコン/heart.svg", 3), "…イコン/heart.svg");
+        assert_eq!(scroll_text("./アイコン/heart.svg", 4), "…コン/heart.svg");
+    }
+
+    #[test]
+    fn max_offset_uses_longest_text() {
+        assert_eq!(max_scroll_offset(["ab", "abcdef"]), 5);
+        assert_eq!(max_scroll_offset([]), 0);
+        assert_eq!(max_scroll_offset(["心形"]), 3);
+    }
+
+    #[test]
+    fn single_line_joins_pasted_lines() {
+        assert_eq!(single_line("  mdi:heart\n"), "mdi:heart");
+        assert_eq!(single_line("heart\r\n  outline"), "heart outline");
+    }
+}
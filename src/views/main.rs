@@ -8,6 +8,7 @@ use ratatui::{
     style::Style,
     widgets::{Block, Paragraph},
 };
+use std::collections::{BTreeSet, HashMap};
 use tui_textarea::{Input, Key, TextArea};
 
 use crate::{
@@ -28,12 +29,58 @@ impl AsRef<str> for HomeSearchCandidate {
     }
 }
 
-fn fuzzy_filter_home_items(items: &[IconEntry], query: &str) -> Vec<IconEntry> {
-    let query = query.trim();
+/// The collection named by a `carbon:` search, if that's what the query is.
+pub fn collection_filter(query: &str) -> Option<&str> {
+    let prefix = query.trim().strip_suffix(':')?;
+    (!prefix.is_empty() && !prefix.contains([':', ' '])).then_some(prefix)
+}
+
+/// Filters the icon list by `query`. `sources` (file path -> where the icon
+/// came from, empty unless source search is on) is matched too, so renamed
+/// icons are still found by their Iconify name or collection. `#tag` words
+/// keep only icons with that tag.
+fn fuzzy_filter_home_items(
+    items: &[IconEntry],
+    query: &str,
+    sources: &HashMap<String, String>,
+    tags: &crate::tags::IconTags,
+) -> Vec<IconEntry> {
+    let (tag_filters, words): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|word| word.len() > 1 && word.starts_with('#'));
+    let tagged;
+    let items = if tag_filters.is_empty() {
+        items
+    } else {
+        tagged = items
+            .iter()
+            .filter(|item| tag_filters.iter().all(|tag| tags.has(&item.name, tag)))
+            .cloned()
+            .collect::<Vec<_>>();
+        &tagged
+    };
+    let query = words.join(" ");
+    let query = query.as_str();
     if query.is_empty() {
         return items.to_vec();
     }
 
+    // `carbon:` is an exact collection filter rather than a fuzzy query, so a
+    // bulk delete from it can't catch icons that merely look similar.
+    if let Some(prefix) = collection_filter(query) {
+        return items
+            .iter()
+            .filter(|item| {
+                crate::utils::icon_from_collection(&item.file_path, prefix)
+                    || sources
+                        .get(&item.file_path)
+                        .and_then(|source| crate::utils::iconify_name_from_icon_source(source))
+                        .is_some_and(|name| name.split_once(':').is_some_and(|(p, _)| p == prefix))
+            })
+            .cloned()
+            .collect();
+    }
+
     let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
     let mut matcher = Matcher::new(Config::DEFAULT);
 
@@ -42,7 +89,10 @@ fn fuzzy_filter_home_items(items: &[IconEntry], query: &str) -> Vec<IconEntry> {
         .enumerate()
         .map(|(index, item)| HomeSearchCandidate {
             index,
-            haystack: format!("{} {}", item.name, item.file_path),
+            haystack: match sources.get(&item.file_path) {
+                Some(source) => format!("{} {} {source}", item.name, item.file_path),
+                None => format!("{} {}", item.name, item.file_path),
+            },
         })
         .collect::<Vec<_>>();
 
@@ -55,6 +105,107 @@ fn fuzzy_filter_home_items(items: &[IconEntry], query: &str) -> Vec<IconEntry> {
         .collect()
 }
 
+/// One row of the main list: a folder of the icon tree, or an icon (an index
+/// into the list being shown).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MainRow {
+    Folder {
+        path: String,
+        depth: usize,
+        icons: usize,
+        collapsed: bool,
+    },
+    Icon {
+        index: usize,
+        depth: usize,
+    },
+}
+
+/// Rows for `items`. Icons in subfolders (`./social/heart.svg`) sit under
+/// collapsible folder rows, after the icons of the folder above; folders keep
+/// the order they first appear in. A search shows its matches flat, in rank
+/// order.
+fn main_rows(items: &[IconEntry], searching: bool, collapsed: &BTreeSet<String>) -> Vec<MainRow> {
+    let folders = items
+        .iter()
+        .map(|item| {
+            crate::groups::split_group(&item.file_path)
+                .0
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    if searching || folders.iter().all(|folder| folder.is_empty()) {
+        return (0..items.len())
+            .map(|index| MainRow::Icon { index, depth: 0 })
+            .collect();
+    }
+    let mut rows = Vec::new();
+    push_folder_rows(
+        &folders,
+        &(0..items.len()).collect::<Vec<_>>(),
+        "",
+        0,
+        collapsed,
+        &mut rows,
+    );
+    rows
+}
+
+/// The icons directly in `folder`, then each subfolder's row and, unless it
+/// is collapsed, its own rows one level deeper.
+fn push_folder_rows(
+    folders: &[&str],
+    indices: &[usize],
+    folder: &str,
+    depth: usize,
+    collapsed: &BTreeSet<String>,
+    rows: &mut Vec<MainRow>,
+) {
+    let mut children: Vec<&str> = Vec::new();
+    for &index in indices {
+        let path = folders[index];
+        if path == folder {
+            rows.push(MainRow::Icon { index, depth });
+            continue;
+        }
+        let rest = if folder.is_empty() {
+            path
+        } else {
+            &path[folder.len() + 1..]
+        };
+        let child = rest.split('/').next().unwrap_or(rest);
+        if !children.contains(&child) {
+            children.push(child);
+        }
+    }
+    for child in children {
+        let path = if folder.is_empty() {
+            child.to_string()
+        } else {
+            format!("{folder}/{child}")
+        };
+        let inside = indices
+            .iter()
+            .copied()
+            .filter(|&index| {
+                folders[index] == path || folders[index].starts_with(&format!("{path}/"))
+            })
+            .collect::<Vec<_>>();
+        let is_collapsed = collapsed.contains(&path);
+        rows.push(MainRow::Folder {
+            path: path.clone(),
+            depth,
+            icons: inside.len(),
+            collapsed: is_collapsed,
+        });
+        if !is_collapsed {
+            push_folder_rows(folders, &inside, &path, depth + 1, collapsed, rows);
+        }
+    }
+}
+
+const FILE_SCROLL_STEP: isize = 4;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MainStateFocus {
     Normal,
@@ -74,6 +225,20 @@ pub struct MainState {
     /// Area of the data-rows (excluding header) for the main list, captured at render time.
     /// Used for mouse hit-testing and viewport sizing.
     pub list_rows_area: Option<Rect>,
+
+    /// Characters hidden from the start of the File column (←/→ to scroll).
+    pub file_scroll_offset: usize,
+    /// Largest offset that still shows something, captured at render time.
+    pub file_scroll_max: usize,
+
+    /// Source search (tab while searching): also match where each icon came
+    /// from, per the history log.
+    pub search_sources: bool,
+    /// Icon file path -> source, loaded when source search is turned on.
+    pub icon_sources: HashMap<String, String>,
+
+    /// Folders of the icon tree folded to one row (enter/space toggles).
+    pub collapsed_folders: BTreeSet<String>,
 }
 
 impl MainState {
@@ -86,27 +251,105 @@ impl MainState {
             search_textarea: TextArea::default(),
             list_scroll_offset: 0,
             list_rows_area: None,
+            file_scroll_offset: 0,
+            file_scroll_max: 0,
+            search_sources: false,
+            icon_sources: HashMap::new(),
+            collapsed_folders: BTreeSet::new(),
         }
     }
 
-    fn set_status(&mut self, message: String, is_error: bool) {
+    pub fn set_status(&mut self, message: String, is_error: bool) {
         self.status_message = Some(message);
         self.status_is_error = is_error;
     }
 
-    fn clear_status(&mut self) {
+    pub fn clear_status(&mut self) {
         self.status_message = None;
         self.status_is_error = false;
     }
 }
 
+/// Variables of `usage_snippets` templates.
+pub const USAGE_SNIPPET_VARS: &[&str] = &["name", "filename", "file", "folder"];
+
 impl App {
-    fn main_item_count(&self) -> usize {
+    /// The icons the main list shows: all of them, or the search's matches.
+    fn main_items(&self) -> &[IconEntry] {
         if self.main_state.search_items_value.is_empty() {
-            self.items.len()
+            &self.items
         } else {
-            self.filtered_items.len()
+            &self.filtered_items
+        }
+    }
+
+    pub fn main_rows(&self) -> Vec<MainRow> {
+        main_rows(
+            self.main_items(),
+            !self.main_state.search_items_value.is_empty(),
+            &self.main_state.collapsed_folders,
+        )
+    }
+
+    pub fn main_item_count(&self) -> usize {
+        self.main_rows().len()
+    }
+
+    /// The icon on the selected row; `None` on a folder row.
+    pub fn selected_item(&self) -> Option<&IconEntry> {
+        match self.main_rows().get(self.selected_index)? {
+            MainRow::Icon { index, .. } => self.main_items().get(*index),
+            MainRow::Folder { .. } => None,
+        }
+    }
+
+    /// Moves the selection to the icon exported as `name`, if it's listed.
+    pub fn select_icon_named(&mut self, name: &str) {
+        let items = self.main_items();
+        if let Some(row) = self.main_rows().iter().position(|row| {
+            matches!(row, MainRow::Icon { index, .. }
+                if items.get(*index).is_some_and(|item| item.name == name))
+        }) {
+            self.selected_index = row;
+        }
+    }
+
+    /// The group new icons go in from here: the selected folder, or the
+    /// selected icon's, when it has its own `index.ts`. Folders without one
+    /// are exported from the barrel above them.
+    pub fn selected_group(&self) -> Option<String> {
+        let folder = match self.main_rows().get(self.selected_index)? {
+            MainRow::Folder { path, .. } => path.clone(),
+            MainRow::Icon { index, .. } => {
+                let item = self.main_items().get(*index)?;
+                crate::groups::split_group(&item.file_path).0?.to_string()
+            }
+        };
+        let root = std::path::Path::new(&self.config.folder);
+        let mut group = folder.as_str();
+        loop {
+            if root.join(group).join("index.ts").is_file() {
+                return Some(group.to_string());
+            }
+            group = group.rsplit_once('/')?.0;
+        }
+    }
+
+    /// Folds or unfolds the selected folder row. False on an icon row.
+    fn toggle_selected_folder(&mut self) -> bool {
+        let Some(MainRow::Folder { path, .. }) =
+            self.main_rows().into_iter().nth(self.selected_index)
+        else {
+            return false;
+        };
+        let collapsed = &mut self.main_state.collapsed_folders;
+        if !collapsed.remove(&path) {
+            collapsed.insert(path);
         }
+        let len = self.main_item_count();
+        let height = self.main_visible_height();
+        scroll::clamp_offset(&mut self.main_state.list_scroll_offset, len, height);
+        true
     }
 
     fn main_visible_height(&self) -> usize {
@@ -148,20 +391,54 @@ impl App {
         self.ensure_main_selection_visible();
     }
 
-    fn update_filtered_items_main(&mut self) {
-        self.filtered_items =
-            fuzzy_filter_home_items(&self.items, &self.main_state.search_items_value);
-        if self.filtered_items.is_empty() {
-            self.selected_index = 0;
-        } else if self.selected_index >= self.filtered_items.len() {
-            self.selected_index = self.filtered_items.len().saturating_sub(1);
-        }
+    pub fn update_filtered_items_main(&mut self) {
+        self.filtered_items = fuzzy_filter_home_items(
+            &self.items,
+            &self.main_state.search_items_value,
+            &self.main_state.icon_sources,
+            &self.tags,
+        );
         let len = self.main_item_count();
+        self.selected_index = self.selected_index.min(len.saturating_sub(1));
         let height = self.main_visible_height();
         scroll::clamp_offset(&mut self.main_state.list_scroll_offset, len, height);
         self.ensure_main_selection_visible();
     }
 
+    /// Turns source search on or off and re-filters.
+    pub fn toggle_main_search_sources(&mut self) {
+        self.main_state.search_sources = !self.main_state.search_sources;
+        self.load_icon_sources();
+        self.update_filtered_items_main();
+    }
+
+    /// Reads the history log for source search, keyed by the icon list's file
+    /// paths. Empty while source search is off.
+    pub fn load_icon_sources(&mut self) {
+        let state = &mut self.main_state;
+        state.icon_sources.clear();
+        if !state.search_sources {
+            return;
+        }
+        let Some(history) = &self.config.history else {
+            state.set_status(
+                "No history log to search sources in (history_log is off)".to_string(),
+                true,
+            );
+            return;
+        };
+        let logged = history.sources();
+        let folder = std::path::Path::new(&self.config.folder);
+        state.icon_sources = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let key = crate::history::display_path(&folder.join(&item.file_path));
+                Some((item.file_path.clone(), logged.get(&key)?.clone()))
+            })
+            .collect();
+    }
+
     pub fn handle_mouse_main(&mut self, mouse: ratatui::crossterm::event::MouseEvent) {
         use ratatui::crossterm::event::{MouseButton, MouseEventKind};
 
@@ -210,6 +487,7 @@ impl App {
             }
             Key::Up => self.move_main_selection_up(),
             Key::Down => self.move_main_selection_down(),
+            Key::Tab => self.toggle_main_search_sources(),
             _ => {
                 self.main_state.search_textarea.input(input.clone());
                 self.main_state.search_items_value =
@@ -219,6 +497,16 @@ impl App {
         }
     }
 
+    /// Pasting while the list has focus starts a search for the text.
+    pub fn paste_into_main_search(&mut self, text: &str) {
+        self.main_state.main_state_focus = MainStateFocus::Search;
+        self.main_state
+            .search_textarea
+            .insert_str(crate::views::layout::single_line(text));
+        self.main_state.search_items_value = self.main_state.search_textarea.lines().join("");
+        self.update_filtered_items_main();
+    }
+
     fn handlekeys_main_normal(&mut self, input: &Input) {
         match input.key {
             Key::Char('q') => self.should_quit = true,
@@ -231,9 +519,40 @@ impl App {
             Key::Char('d') => {
                 self.init_delete_popup();
             }
+            Key::Char('D') => {
+                self.init_bulk_delete_popup();
+            }
             Key::Char('r') => {
                 self.init_rename_popup();
             }
+            Key::Char('R') => {
+                self.init_replace_popup();
+            }
+            Key::Char('t') => {
+                self.init_tag_popup();
+            }
+            Key::Char('c') => {
+                self.init_duplicate_popup();
+            }
+            Key::Char('e') => {
+                self.init_svg_edit_popup();
+            }
+            Key::Char('O') => match self.open_selected_folder() {
+                Ok(folder) => self
+                    .main_state
+                    .set_status(format!("Opened {}", folder.display()), false),
+                Err(error) => self
+                    .main_state
+                    .set_status(format!("Failed to open folder: {}", error), true),
+            },
+            Key::Char('o') if input.ctrl => match self.open_selected_icon_on_icones() {
+                Ok(url) => self
+                    .main_state
+                    .set_status(format!("Opened Icones page: {url}"), false),
+                Err(error) => self
+                    .main_state
+                    .set_status(format!("Failed to open Icones page: {}", error), true),
+            },
             Key::Char('o') => match self.open_selected_icon() {
                 Ok(crate::viewer::OpenSvgOutcome::OpenedWithCustomCommand) => {
                     self.main_state.clear_status()
@@ -263,6 +582,7 @@ impl App {
                     .main_state
                     .set_status(format!("Failed to preview icon: {}", error), true),
             },
+            Key::Char('y') => self.copy_selected_usage_snippet(),
             Key::Char('/') => {
                 self.main_state.main_state_focus = MainStateFocus::Search;
             }
@@ -272,40 +592,140 @@ impl App {
             Key::Char('S') => {
                 self.init_sync_popup();
             }
+            Key::Enter | Key::Char(' ') => {
+                self.toggle_selected_folder();
+            }
             Key::Up | Key::Char('k') => self.move_main_selection_up(),
             Key::Down | Key::Char('j') => self.move_main_selection_down(),
+            Key::Left | Key::Char('h') => self.scroll_main_file_column(-FILE_SCROLL_STEP),
+            Key::Right | Key::Char('l') => self.scroll_main_file_column(FILE_SCROLL_STEP),
             _ => {}
         }
     }
 
+    fn scroll_main_file_column(&mut self, delta: isize) {
+        let state = &mut self.main_state;
+        state.file_scroll_offset = state
+            .file_scroll_offset
+            .saturating_add_signed(delta)
+            .min(state.file_scroll_max);
+    }
+
     pub fn open_selected_icon(&self) -> anyhow::Result<crate::viewer::OpenSvgOutcome> {
         let absolute_path = self.selected_icon_path()?;
-        crate::viewer::open_svg_with_fallback(&absolute_path, self.config.svg_viewer_cmd.as_deref())
+        crate::viewer::open_svg_with_fallback(
+            &absolute_path,
+            self.config.svg_viewer_cmd.as_deref(),
+            &self.config.viewer_cmds,
+            self.recorded_source(&absolute_path).as_deref(),
+        )
+    }
+
+    /// The icones.js.org page of the selected icon, found from the source the
+    /// history log recorded or an Iconify-named file.
+    fn selected_icones_url(&self) -> anyhow::Result<String> {
+        let path = self.selected_icon_path()?;
+        let source = self.recorded_source(&path);
+        let icon_name =
+            crate::viewer::iconify_icon_name(&path, source.as_deref()).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} has no recorded Iconify source.",
+                    crate::history::display_path(&path)
+                )
+            })?;
+        crate::views::iconify_search_popup::icones_collection_url(&icon_name)
+            .ok_or_else(|| anyhow::anyhow!("'{icon_name}' is not an Iconify icon name."))
+    }
+
+    pub fn open_selected_icon_on_icones(&self) -> anyhow::Result<String> {
+        let url = self.selected_icones_url()?;
+        crate::viewer::open_url_in_browser(&url)?;
+        Ok(url)
+    }
+
+    /// Opens the icons folder in the file manager, or the selected subfolder
+    /// when a folder row is selected.
+    pub fn open_selected_folder(&self) -> anyhow::Result<std::path::PathBuf> {
+        let mut folder = std::path::PathBuf::from(&self.config.folder);
+        if self.selected_item().is_none()
+            && let Some(group) = self.selected_group()
+        {
+            folder.push(group);
+        }
+        crate::viewer::open_folder_in_file_manager(&folder)?;
+        Ok(folder)
+    }
+
+    /// Where the icon at `path` came from, per the history log.
+    pub fn recorded_source(&self, path: &std::path::Path) -> Option<String> {
+        let history = self.config.history.as_ref()?;
+        history
+            .sources()
+            .remove(&crate::history::display_path(path))
     }
 
     pub fn preview_selected_icon(&self) -> anyhow::Result<()> {
         let absolute_path = self.selected_icon_path()?;
-        crate::viewer::preview_svg_in_browser(&absolute_path)
+        crate::viewer::preview_svg_in_browser(&absolute_path, &self.config.preview_colors)
     }
 
-    fn selected_icon_path(&self) -> anyhow::Result<std::path::PathBuf> {
-        use std::path::Path;
+    /// How the selected icon is used: the preset's `usage_snippets` template,
+    /// or the barrel import the gallery shows.
+    pub fn selected_usage_snippet(&self) -> Option<String> {
+        use crate::gallery::{ImportStyle, usage_snippet};
+        use crate::template::{Vars, render};
 
-        let item_list = if self.main_state.search_items_value.is_empty() {
-            &self.items
-        } else {
-            &self.filtered_items
+        let item = self.selected_item()?;
+        let folder = self.config.folder.replace('\\', "/");
+        let folder = folder.trim_end_matches('/');
+        let Some(template) = &self.config.usage_snippet else {
+            let style = ImportStyle::for_preset(
+                &self.config.preset,
+                folder,
+                self.config.flutter_barrel_class.as_deref(),
+            );
+            return Some(usage_snippet(&item.name, &style));
+        };
+
+        let file = item.file_path.replace('\\', "/");
+        let file = file.trim_start_matches("./");
+        let file_name = file.rsplit('/').next().unwrap_or(file);
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        let vars = Vars::new()
+            .with("name", &item.name)
+            .with("filename", stem)
+            .with("file", file)
+            .with("folder", folder);
+        Some(render(template, &vars))
+    }
+
+    fn copy_selected_usage_snippet(&mut self) {
+        let Some(snippet) = self.selected_usage_snippet() else {
+            self.main_state
+                .set_status("No icon selected.".to_string(), true);
+            return;
         };
-        let item = item_list
-            .get(self.selected_index)
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(&snippet)) {
+            Ok(()) => self
+                .main_state
+                .set_status(format!("Copied {snippet}"), false),
+            Err(error) => self
+                .main_state
+                .set_status(format!("Failed to copy usage snippet: {error}"), true),
+        }
+    }
+
+    pub fn selected_icon_path(&self) -> anyhow::Result<std::path::PathBuf> {
+        use std::path::Path;
+
+        let item = self
+            .selected_item()
             .ok_or_else(|| anyhow::anyhow!("No icon selected."))?;
 
-        let file_path = Path::new(&item.file_path);
-        Ok(if file_path.is_absolute() {
-            file_path.to_path_buf()
-        } else {
-            Path::new(&self.config.folder).join(file_path)
-        })
+        Ok(crate::utils::icon_file_path(
+            Path::new(&self.config.folder),
+            &item.file_path,
+        ))
     }
 
     pub fn handlekeys_main(&mut self, input: Input) {
@@ -319,47 +739,65 @@ impl App {
 pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
     use ratatui::{
         style::Modifier,
+        text::{Line, Span},
         widgets::{Cell, Row, Table},
     };
 
     let main_state = &mut app.main_state;
     let is_searching = main_state.main_state_focus == MainStateFocus::Search;
+    let narrow = crate::views::layout::is_narrow(area);
+    // Small terminals collapse the logo + tagline into a one-line top bar.
+    let compact_header = narrow || crate::views::layout::is_short(area);
 
     let main_chunks = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
-            Constraint::Length(1),
+            Constraint::Length(if compact_header { 1 } else { 3 }),
+            Constraint::Length(if compact_header { 0 } else { 1 }),
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Min(0),
             Constraint::Length(1),
+            Constraint::Length(1),
         ])
         .split(area);
 
-    let ascii_art = "░▀█▀░█▀▀░█▀█░█▀█░█▄█░█▀█░▀█▀░█▀▀░\n\
-         ░░█░░█░░░█░█░█░█░█░█░█▀█░░█░░█▀▀░\n\
-         ░▀▀▀░▀▀▀░▀▀▀░▀░▀░▀░▀░▀░▀░░▀░░▀▀▀░";
-    f.render_widget(
-        Paragraph::new(ascii_art)
-            .style(Style::default().fg(crate::views::theme::ACCENT))
-            .alignment(Alignment::Center),
-        main_chunks[0],
-    );
+    if compact_header {
+        f.render_widget(
+            Paragraph::new("iconmate")
+                .style(
+                    Style::default()
+                        .fg(crate::views::theme::palette().accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .alignment(Alignment::Center),
+            main_chunks[0],
+        );
+    } else {
+        let ascii_art = "░▀█▀░█▀▀░█▀█░█▀█░█▄█░█▀█░▀█▀░█▀▀░\n\
+             ░░█░░█░░░█░█░█░█░█░█░█▀█░░█░░█▀▀░\n\
+             ░▀▀▀░▀▀▀░▀▀▀░▀░▀░▀░▀░▀░▀░░▀░░▀▀▀░";
+        f.render_widget(
+            Paragraph::new(ascii_art)
+                .style(Style::default().fg(crate::views::theme::palette().accent))
+                .alignment(Alignment::Center),
+            main_chunks[0],
+        );
 
-    f.render_widget(
-        Paragraph::new("Add svg icons to your js apps without any dependencies")
-            .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT))
-            .alignment(Alignment::Center),
-        main_chunks[1],
-    );
+        f.render_widget(
+            Paragraph::new("Add svg icons to your js apps without any dependencies")
+                .style(Style::default().fg(crate::views::theme::palette().subtle_text))
+                .alignment(Alignment::Center),
+            main_chunks[1],
+        );
+    }
 
     let status_text = main_state.status_message.clone().unwrap_or_default();
     let status_color = if main_state.status_is_error {
-        crate::views::theme::ERROR
+        crate::views::theme::palette().error
     } else {
-        crate::views::theme::SUBTLE_TEXT
+        crate::views::theme::palette().subtle_text
     };
     f.render_widget(
         Paragraph::new(status_text)
@@ -373,14 +811,18 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
         .constraints([
             Constraint::Length(10),
             Constraint::Fill(1),
-            Constraint::Length(8),
+            Constraint::Length(if is_searching { 16 } else { 8 }),
         ])
         .split(main_chunks[3]);
 
     f.render_widget(
-        Paragraph::new("Search /")
-            .style(Style::default().fg(crate::views::theme::MUTED_TEXT))
-            .alignment(Alignment::Left),
+        Paragraph::new(if main_state.search_sources {
+            "Sources /"
+        } else {
+            "Search /"
+        })
+        .style(Style::default().fg(crate::views::theme::palette().muted_text))
+        .alignment(Alignment::Left),
         search_chunks[0],
     );
 
@@ -388,16 +830,16 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
         main_state.search_textarea.set_block(Block::default());
         main_state.search_textarea.set_cursor_style(
             Style::default()
-                .bg(crate::views::theme::ACCENT)
-                .fg(crate::views::theme::BASE_BG),
+                .bg(crate::views::theme::palette().accent)
+                .fg(crate::views::theme::palette().base_bg),
         );
         main_state
             .search_textarea
             .set_cursor_line_style(Style::default());
         f.render_widget(&main_state.search_textarea, search_chunks[1]);
         f.render_widget(
-            Paragraph::new("enter")
-                .style(Style::default().fg(crate::views::theme::MUTED_TEXT))
+            Paragraph::new("tab src · enter")
+                .style(Style::default().fg(crate::views::theme::palette().muted_text))
                 .alignment(Alignment::Right),
             search_chunks[2],
         );
@@ -408,9 +850,9 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
             main_state.search_items_value.clone()
         };
         let search_color = if main_state.search_items_value.is_empty() {
-            crate::views::theme::MUTED_TEXT
+            crate::views::theme::palette().muted_text
         } else {
-            crate::views::theme::TEXT
+            crate::views::theme::palette().text
         };
         f.render_widget(
             Paragraph::new(search_display)
@@ -426,47 +868,115 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
         &app.filtered_items
     };
     let show_no_results = !main_state.search_items_value.is_empty() && item_list.is_empty();
+    let list_rows = main_rows(
+        item_list,
+        !main_state.search_items_value.is_empty(),
+        &main_state.collapsed_folders,
+    );
 
-    let header_cells = ["Name", "File"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(crate::views::theme::MUTED_TEXT)));
-    let header = Row::new(header_cells).style(Style::default().fg(crate::views::theme::TEXT));
+    // Keep the horizontal offset valid for whatever list is showing now.
+    let file_scroll_max = crate::views::layout::max_scroll_offset(
+        item_list.iter().map(|item| item.file_path.as_str()),
+    );
+    main_state.file_scroll_offset = main_state.file_scroll_offset.min(file_scroll_max);
+    main_state.file_scroll_max = file_scroll_max;
+    let file_scroll = main_state.file_scroll_offset;
+
+    let file_header = if file_scroll > 0 { "File ←" } else { "File" };
+    let header_cells = ["Name", file_header].into_iter().map(|h| {
+        Cell::from(h).style(Style::default().fg(crate::views::theme::palette().muted_text))
+    });
+    let header =
+        Row::new(header_cells).style(Style::default().fg(crate::views::theme::palette().text));
 
     let rows: Vec<Row> = if show_no_results {
         vec![Row::new(vec![
             Cell::from("No icons match your search")
-                .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT)),
+                .style(Style::default().fg(crate::views::theme::palette().subtle_text)),
             Cell::from(""),
         ])]
     } else {
-        item_list
+        list_rows
             .iter()
-            .map(|item| {
-                Row::new(vec![
-                    Cell::from(item.name.as_str())
-                        .style(Style::default().fg(crate::views::theme::TEXT)),
-                    Cell::from(item.file_path.as_str())
-                        .style(Style::default().fg(crate::views::theme::MUTED_TEXT)),
-                ])
+            .map(|row| match row {
+                MainRow::Folder {
+                    path,
+                    depth,
+                    icons,
+                    collapsed,
+                } => {
+                    let name = path.rsplit('/').next().unwrap_or(path);
+                    Row::new(vec![
+                        Cell::from(Line::from(vec![
+                            Span::styled(
+                                format!(
+                                    "{}{} {name}/",
+                                    "  ".repeat(*depth),
+                                    if *collapsed { "▸" } else { "▾" }
+                                ),
+                                Style::default()
+                                    .fg(crate::views::theme::palette().accent)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!(" {icons}"),
+                                Style::default().fg(crate::views::theme::palette().subtle_text),
+                            ),
+                        ])),
+                        Cell::from(crate::views::layout::scroll_text(
+                            &format!("./{path}/"),
+                            file_scroll,
+                        ))
+                        .style(Style::default().fg(crate::views::theme::palette().muted_text)),
+                    ])
+                }
+                MainRow::Icon { index, depth } => {
+                    let item = &item_list[*index];
+                    let mut name = vec![Span::styled(
+                        format!("{}{}", "  ".repeat(*depth), item.name),
+                        Style::default().fg(crate::views::theme::palette().text),
+                    )];
+                    for tag in app.tags.get(&item.name) {
+                        name.push(Span::styled(
+                            format!(" #{tag}"),
+                            Style::default().fg(crate::views::theme::palette().subtle_text),
+                        ));
+                    }
+                    Row::new(vec![
+                        Cell::from(Line::from(name)),
+                        Cell::from(crate::views::layout::scroll_text(
+                            &item.file_path,
+                            file_scroll,
+                        ))
+                        .style(Style::default().fg(crate::views::theme::palette().muted_text)),
+                    ])
+                }
             })
             .collect()
     };
 
     let has_rows = !rows.is_empty();
-    let table = Table::new(
-        rows,
-        [Constraint::Percentage(50), Constraint::Percentage(50)],
-    )
-    .header(header)
-    .block(Block::default())
-    .column_spacing(2)
-    .highlight_symbol("  ")
-    .row_highlight_style(
-        Style::default()
-            .bg(crate::views::theme::ROW_HIGHLIGHT_BG)
-            .fg(crate::views::theme::BASE_BG)
-            .add_modifier(Modifier::BOLD),
-    );
+    let has_folders = list_rows
+        .iter()
+        .any(|row| matches!(row, MainRow::Folder { .. }));
+    // File paths are usually longer than names; give them more room when
+    // columns are scarce.
+    let column_widths = if narrow {
+        [Constraint::Percentage(40), Constraint::Percentage(60)]
+    } else {
+        [Constraint::Percentage(50), Constraint::Percentage(50)]
+    };
+    let table = Table::new(rows, column_widths)
+        .header(header)
+        .block(Block::default())
+        .column_spacing(2)
+        .highlight_symbol("  ")
+        .row_highlight_style(
+            Style::default()
+                .bg(crate::views::theme::palette().row_highlight_bg)
+                .fg(crate::views::theme::palette().base_bg)
+                .add_modifier(Modifier::BOLD),
+        );
 
     let table_area = main_chunks[5];
     // Data rows area excludes the 1-row header.
@@ -477,7 +987,7 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
         height: table_area.height.saturating_sub(1),
     };
     let visible_height = rows_area.height as usize;
-    let list_len = if show_no_results { 0 } else { item_list.len() };
+    let list_len = if show_no_results { 0 } else { list_rows.len() };
 
     // Clamp offset to valid range if the viewport just changed size.
     crate::scroll::clamp_offset(&mut main_state.list_scroll_offset, list_len, visible_height);
@@ -503,32 +1013,74 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
     }
     f.render_stateful_widget(table, table_area, &mut state);
 
-    let shortcuts = crate::views::theme::shortcut_line(&[
-        ("Add", "a"),
-        ("Iconify", "i"),
-        ("Delete", "d"),
-        ("Rename", "r"),
-        ("Open", "o"),
-        ("Preview", "p"),
-        ("Sync", "S"),
-        ("Help", "?"),
-        ("Quit", "q"),
-    ]);
-    let version_label = format!("v{}", env!("CARGO_PKG_VERSION"));
+    if let Some(snippet) = app.selected_usage_snippet() {
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    "Usage  ",
+                    Style::default().fg(crate::views::theme::palette().muted_text),
+                ),
+                Span::styled(
+                    snippet,
+                    Style::default().fg(crate::views::theme::palette().subtle_text),
+                ),
+            ])),
+            main_chunks[6],
+        );
+    }
+
+    let shortcuts = if narrow {
+        crate::views::theme::shortcut_line(&[
+            ("Add", "a"),
+            ("Iconify", "i"),
+            ("Delete", "d"),
+            ("Help", "?"),
+            ("Quit", "q"),
+        ])
+    } else {
+        let mut shortcuts = vec![
+            ("Add", "a"),
+            ("Iconify", "i"),
+            ("Delete", "d"),
+            ("Delete all", "D"),
+            ("Rename", "r"),
+            ("Replace", "R"),
+            ("Duplicate", "c"),
+            ("Edit", "e"),
+            ("Tag", "t"),
+            ("Open", "o"),
+            ("Folder", "O"),
+            ("Icones", "ctrl+o"),
+            ("Preview", "p"),
+            ("Copy", "y"),
+            ("Sync", "S"),
+            ("Help", "?"),
+            ("Quit", "q"),
+        ];
+        if has_folders {
+            shortcuts.insert(0, ("Fold", "enter"));
+        }
+        crate::views::theme::shortcut_line(&shortcuts)
+    };
+    let version_label = if narrow {
+        String::new()
+    } else {
+        format!("v{}", env!("CARGO_PKG_VERSION"))
+    };
     let footer_layout = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Horizontal)
         .constraints([
             Constraint::Min(0),
             Constraint::Length(version_label.chars().count() as u16 + 1),
         ])
-        .split(main_chunks[6]);
+        .split(main_chunks[7]);
     f.render_widget(
         Paragraph::new(shortcuts).alignment(Alignment::Left),
         footer_layout[0],
     );
     f.render_widget(
         Paragraph::new(version_label)
-            .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT))
+            .style(Style::default().fg(crate::views::theme::palette().subtle_text))
             .alignment(Alignment::Right),
         footer_layout[1],
     );
@@ -536,8 +1088,10 @@ pub fn render_main_view(f: &mut Frame, area: Rect, app: &mut App) {
 
 #[cfg(test)]
 mod tests {
-    use super::fuzzy_filter_home_items;
+    use super::{MainRow, fuzzy_filter_home_items, main_rows};
+    use crate::tags::IconTags;
     use crate::utils::IconEntry;
+    use std::collections::HashMap;
 
     fn sample_items() -> Vec<IconEntry> {
         vec![
@@ -558,25 +1112,380 @@ mod tests {
 
     #[test]
     fn home_search_matches_fuzzy_name_query() {
-        let filtered = fuzzy_filter_home_items(&sample_items(), "ihrt");
+        let filtered = fuzzy_filter_home_items(
+            &sample_items(),
+            "ihrt",
+            &HashMap::new(),
+            &IconTags::default(),
+        );
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].name, "IconHeart");
     }
 
     #[test]
     fn home_search_matches_fuzzy_file_path_query() {
-        let filtered = fuzzy_filter_home_items(&sample_items(), "lchrt");
+        let filtered = fuzzy_filter_home_items(
+            &sample_items(),
+            "lchrt",
+            &HashMap::new(),
+            &IconTags::default(),
+        );
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].name, "IconHeart");
     }
 
+    #[test]
+    fn home_search_filters_exactly_by_collection() {
+        let mut items = sample_items();
+        items.push(IconEntry {
+            name: "IconLucideLab".to_string(),
+            file_path: "./mdi_lucide-lab.svg".to_string(),
+        });
+        let filtered =
+            fuzzy_filter_home_items(&items, "lucide:", &HashMap::new(), &IconTags::default());
+        assert_eq!(filtered.len(), 3);
+        assert!(
+            filtered
+                .iter()
+                .all(|item| item.file_path.starts_with("./lucide:"))
+        );
+        assert!(
+            fuzzy_filter_home_items(&items, "mdi:", &HashMap::new(), &IconTags::default()).len()
+                == 1
+        );
+    }
+
+    #[test]
+    fn home_search_matches_sources_of_renamed_icons() {
+        let mut items = sample_items();
+        items.push(IconEntry {
+            name: "IconFavorite".to_string(),
+            file_path: "./favorite.svg".to_string(),
+        });
+        let sources = HashMap::from([("./favorite.svg".to_string(), "ph:heart-fill".to_string())]);
+
+        assert!(
+            fuzzy_filter_home_items(&items, "phosphor", &HashMap::new(), &IconTags::default())
+                .is_empty()
+        );
+        assert!(
+            fuzzy_filter_home_items(&items, "ph:", &HashMap::new(), &IconTags::default())
+                .is_empty()
+        );
+
+        let by_collection = fuzzy_filter_home_items(&items, "ph:", &sources, &IconTags::default());
+        assert_eq!(by_collection.len(), 1);
+        assert_eq!(by_collection[0].name, "IconFavorite");
+        let by_name = fuzzy_filter_home_items(&items, "heart-fill", &sources, &IconTags::default());
+        assert_eq!(by_name[0].name, "IconFavorite");
+    }
+
+    #[test]
+    fn home_search_filters_by_hash_tags() {
+        let mut tags = IconTags::default();
+        tags.set("IconHeart", vec!["social".to_string()]);
+        tags.set(
+            "IconHouse",
+            vec!["navigation".to_string(), "social".to_string()],
+        );
+
+        let social = fuzzy_filter_home_items(&sample_items(), "#social", &HashMap::new(), &tags);
+        assert_eq!(social.len(), 2);
+        let both = fuzzy_filter_home_items(
+            &sample_items(),
+            "#social #Navigation",
+            &HashMap::new(),
+            &tags,
+        );
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].name, "IconHouse");
+        let narrowed =
+            fuzzy_filter_home_items(&sample_items(), "#social hrt", &HashMap::new(), &tags);
+        assert_eq!(narrowed.len(), 1);
+        assert_eq!(narrowed[0].name, "IconHeart");
+    }
+
+    #[test]
+    fn tab_in_search_turns_on_source_search_from_history_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("favorite.svg"), "<svg/>").unwrap();
+        std::fs::write(
+            folder.join("index.ts"),
+            "export { default as IconFavorite } from './favorite.svg';\n",
+        )
+        .unwrap();
+        let history = crate::history::HistoryLog::at(temp_dir.path().join("log.jsonl"));
+        history
+            .append(&crate::history::Entry::add(
+                "IconHeart",
+                &folder.join("heart.svg"),
+                Some("ph:heart"),
+            ))
+            .unwrap();
+        history
+            .append(&crate::history::Entry::rename(
+                "IconHeart",
+                &folder.join("heart.svg"),
+                &folder.join("favorite.svg"),
+            ))
+            .unwrap();
+
+        let mut app = crate::app_state::App::new(crate::app_state::AppConfig {
+            history: Some(history),
+            ..crate::app_state::AppConfig::for_tests(
+                folder.to_string_lossy().into_owned(),
+                "normal",
+            )
+        });
+        for c in "/ph:".chars() {
+            app.handlekeys(tui_textarea::Input {
+                key: tui_textarea::Key::Char(c),
+                ..Default::default()
+            });
+        }
+        assert!(app.filtered_items.is_empty());
+
+        app.handlekeys(tui_textarea::Input {
+            key: tui_textarea::Key::Tab,
+            ..Default::default()
+        });
+        assert!(app.main_state.search_sources);
+        assert_eq!(app.filtered_items.len(), 1);
+        assert!(render_to_text(&mut app, 100, 30).contains("Sources /"));
+    }
+
+    #[test]
+    fn icones_page_comes_from_the_recorded_source() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let folder = temp_dir.path().join("icons");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(
+            folder.join("index.ts"),
+            "export { default as IconFavorite } from './favorite.svg';\nexport { default as IconLogo } from './logo.svg';\n",
+        )
+        .unwrap();
+        let history = crate::history::HistoryLog::at(temp_dir.path().join("log.jsonl"));
+        history
+            .append(&crate::history::Entry::add(
+                "IconFavorite",
+                &folder.join("favorite.svg"),
+                Some("https://icones.js.org/collection/ph?icon=ph:heart"),
+            ))
+            .unwrap();
+
+        let mut app = crate::app_state::App::new(crate::app_state::AppConfig {
+            history: Some(history),
+            ..crate::app_state::AppConfig::for_tests(
+                folder.to_string_lossy().into_owned(),
+                "normal",
+            )
+        });
+        assert_eq!(
+            app.selected_icones_url().unwrap(),
+            "https://icones.js.org/collection/ph?icon=ph:heart"
+        );
+
+        // A hand-made logo has no upstream page.
+        app.selected_index = 1;
+        app.handlekeys(tui_textarea::Input {
+            key: tui_textarea::Key::Char('o'),
+            ctrl: true,
+            ..Default::default()
+        });
+        let status = app.main_state.status_message.clone().unwrap_or_default();
+        assert!(
+            status.contains("has no recorded Iconify source"),
+            "{status}"
+        );
+    }
+
     #[test]
     fn home_search_keeps_all_items_for_empty_query() {
         let items = sample_items();
-        let filtered = fuzzy_filter_home_items(&items, "   ");
+        let filtered =
+            fuzzy_filter_home_items(&items, "   ", &HashMap::new(), &IconTags::default());
         assert_eq!(filtered.len(), items.len());
         assert_eq!(filtered[0].name, items[0].name);
         assert_eq!(filtered[1].name, items[1].name);
         assert_eq!(filtered[2].name, items[2].name);
     }
+
+    #[test]
+    fn tree_rows_nest_subfolders_and_fold() {
+        let items = [
+            "./home.svg",
+            "./social/x.svg",
+            "./brand/logo.svg",
+            "./social/github.svg",
+            "./brand/social/y.svg",
+        ]
+        .into_iter()
+        .map(|file_path| IconEntry {
+            name: "Icon".to_string(),
+            file_path: file_path.to_string(),
+        })
+        .collect::<Vec<_>>();
+        let folder = |path: &str, depth, icons, collapsed| MainRow::Folder {
+            path: path.to_string(),
+            depth,
+            icons,
+            collapsed,
+        };
+        let icon = |index, depth| MainRow::Icon { index, depth };
+
+        assert_eq!(
+            main_rows(&items, false, &Default::default()),
+            [
+                icon(0, 0),
+                folder("social", 0, 2, false),
+                icon(1, 1),
+                icon(3, 1),
+                folder("brand", 0, 2, false),
+                icon(2, 1),
+                folder("brand/social", 1, 1, false),
+                icon(4, 2),
+            ]
+        );
+        assert_eq!(
+            main_rows(&items, false, &["brand".to_string()].into()),
+            [
+                icon(0, 0),
+                folder("social", 0, 2, false),
+                icon(1, 1),
+                icon(3, 1),
+                folder("brand", 0, 2, true),
+            ]
+        );
+        // Search results stay flat, in rank order.
+        assert_eq!(main_rows(&items[..2], true, &Default::default()).len(), 2);
+    }
+
+    #[test]
+    fn folder_rows_fold_and_scope_add_and_delete() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHome } from './home.svg';\nexport * from './social';\n",
+        )
+        .unwrap();
+        std::fs::create_dir(temp_dir.path().join("social")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("social/index.ts"),
+            "export { default as IconX } from './x.svg';\nexport { default as IconGithub } from './github.svg';\n",
+        )
+        .unwrap();
+        let mut app = crate::app_state::App::new(crate::app_state::AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        let press = |app: &mut crate::app_state::App, key| {
+            app.handlekeys(tui_textarea::Input {
+                key,
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(app.main_item_count(), 4);
+        press(&mut app, tui_textarea::Key::Down);
+        assert!(app.selected_item().is_none());
+        assert_eq!(app.selected_group().as_deref(), Some("social"));
+        press(&mut app, tui_textarea::Key::Enter);
+        assert_eq!(app.main_item_count(), 2);
+        let screen = render_to_text(&mut app, 100, 30);
+        assert!(screen.contains("▸ social/ 2"), "{screen}");
+
+        press(&mut app, tui_textarea::Key::Char('a'));
+        assert_eq!(
+            app.add_popup_state.as_ref().unwrap().group.as_deref(),
+            Some("social")
+        );
+        press(&mut app, tui_textarea::Key::Esc);
+
+        press(&mut app, tui_textarea::Key::Char('d'));
+        let state = app.delete_popup_state.as_ref().unwrap();
+        assert_eq!(
+            state
+                .items_to_delete
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>(),
+            ["IconX", "IconGithub"]
+        );
+    }
+
+    fn render_to_text(app: &mut crate::app_state::App, width: u16, height: u16) -> String {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| super::render_main_view(f, f.area(), app))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn usage_snippet_follows_the_preset_template() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHeartOutline } from './heart-outline.svg';\n",
+        )
+        .unwrap();
+        let mut config = crate::app_state::AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "react",
+        );
+        let mut app = crate::app_state::App::new(config.clone());
+        let folder = config.folder.replace('\\', "/");
+        assert_eq!(
+            app.selected_usage_snippet(),
+            Some(format!("import {{ IconHeartOutline }} from \"{folder}\";"))
+        );
+
+        config.usage_snippet = Some("<Icon name=\"%filename|kebab%\" /> // %name%".to_string());
+        app.config = config;
+        assert_eq!(
+            app.selected_usage_snippet().as_deref(),
+            Some("<Icon name=\"heart-outline\" /> // IconHeartOutline")
+        );
+        let screen = render_to_text(&mut app, 100, 30);
+        assert!(
+            screen.contains("Usage  <Icon name=\"heart-outline\" />"),
+            "{screen}"
+        );
+    }
+
+    #[test]
+    fn narrow_terminal_collapses_header_and_scrolls_file_column() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = crate::app_state::App::new(crate::app_state::AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        app.items = sample_items();
+
+        let wide = render_to_text(&mut app, 100, 30);
+        assert!(wide.contains("░▀█▀░"));
+
+        let narrow = render_to_text(&mut app, 60, 20);
+        assert!(!narrow.contains("░▀█▀░"));
+        assert!(narrow.lines().next().unwrap().contains("iconmate"));
+
+        app.handlekeys_main(tui_textarea::Input {
+            key: tui_textarea::Key::Right,
+            ..Default::default()
+        });
+        let scrolled = render_to_text(&mut app, 60, 20);
+        assert!(scrolled.contains("File ←"));
+        assert!(scrolled.contains("…ide:heart.svg"));
+    }
 }
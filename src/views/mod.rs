@@ -1,8 +1,15 @@
 pub mod add_popup;
 pub mod delete_popup;
+pub mod duplicate_popup;
 pub mod help_popup;
 pub mod iconify_search_popup;
+pub mod layout;
 pub mod main;
+pub mod onboarding_popup;
 pub mod rename_popup;
+pub mod replace_popup;
+pub mod request_log;
+pub mod svg_edit_popup;
 pub mod sync_popup;
+pub mod tag_popup;
 pub mod theme;
@@ -0,0 +1,469 @@
+use std::path::Path;
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::app_state::{App, AppFocus};
+use crate::utils::{PRESETS_OPTIONS, popup_area};
+use crate::views::theme;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OnboardingStep {
+    Preset,
+    Folder,
+    Config,
+}
+
+#[derive(Debug)]
+pub struct OnboardingPopupState {
+    pub step: OnboardingStep,
+    pub preset_index: usize,
+    pub folder_input: TextArea<'static>,
+    /// The user typed a folder (or passed `--folder`), so switching presets
+    /// must not reset it to the preset's default.
+    pub folder_customized: bool,
+    pub create_config: bool,
+    pub status_message: Option<String>,
+    /// What `package.json` and friends suggest; its preset starts selected.
+    pub detection: Option<crate::detect::Detection>,
+}
+
+impl OnboardingPopupState {
+    fn selected_preset(&self) -> &'static str {
+        PRESETS_OPTIONS[self.preset_index].preset.to_str()
+    }
+
+    /// The detected `import_query`, as long as its preset is still selected.
+    fn import_query(&self) -> Option<crate::utils::ImportQuery> {
+        self.detection
+            .as_ref()
+            .filter(|detection| detection.preset.to_str() == self.selected_preset())
+            .and_then(|detection| detection.import_query)
+    }
+
+    fn folder(&self) -> String {
+        self.folder_input.lines().join("").trim().to_string()
+    }
+
+    fn set_folder(&mut self, folder: &str) {
+        let mut folder_input = TextArea::from([folder.to_string()]);
+        folder_input.move_cursor(tui_textarea::CursorMove::End);
+        folder_input.set_cursor_style(
+            Style::default()
+                .bg(theme::palette().accent)
+                .fg(theme::palette().base_bg),
+        );
+        self.folder_input = folder_input;
+    }
+}
+
+impl App {
+    /// First run: no local config and nothing at the icons folder yet.
+    pub fn needs_onboarding(&self) -> bool {
+        !self.config.project_config_loaded && !Path::new(&self.config.folder).exists()
+    }
+
+    pub fn init_onboarding_popup(&mut self) {
+        let detection = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| crate::detect::detect_project(&cwd));
+        self.init_onboarding_popup_with(detection);
+    }
+
+    fn init_onboarding_popup_with(&mut self, detection: Option<crate::detect::Detection>) {
+        // Only a plain `normal` is worth second-guessing; `--preset` and the
+        // Flutter autodetect already picked something specific.
+        let suggested = detection
+            .as_ref()
+            .filter(|_| self.config.preset == "normal")
+            .map(|detection| detection.preset.to_str())
+            .unwrap_or(&self.config.preset);
+        let preset_index = PRESETS_OPTIONS
+            .iter()
+            .position(|option| option.preset.to_str() == suggested)
+            .unwrap_or(0);
+        let mut state = OnboardingPopupState {
+            step: OnboardingStep::Preset,
+            preset_index,
+            folder_input: TextArea::default(),
+            folder_customized: self.config.folder
+                != crate::config::default_folder_for_preset(&self.config.preset),
+            create_config: true,
+            status_message: None,
+            detection,
+        };
+        state.set_folder(&self.config.folder);
+
+        self.onboarding_popup_state = Some(state);
+        self.app_focus = AppFocus::OnboardingPopup;
+    }
+
+    fn close_onboarding_popup(&mut self) {
+        self.onboarding_popup_state = None;
+        self.app_focus = AppFocus::Main;
+    }
+
+    fn finish_onboarding(&mut self) -> anyhow::Result<String> {
+        let Some(state) = self.onboarding_popup_state.as_ref() else {
+            anyhow::bail!("Onboarding is not initialized");
+        };
+        let folder = state.folder();
+        let preset = state.selected_preset().to_string();
+        let import_query = state.import_query();
+
+        std::fs::create_dir_all(&folder)
+            .map_err(|error| anyhow::anyhow!("Failed to create {folder}: {error}"))?;
+        let mut message = format!("Created {folder}");
+        if state.create_config {
+            let cwd = std::env::current_dir()?;
+            let path = crate::config::write_local_config(&cwd, &folder, &preset, import_query)?;
+            message.push_str(&format!(" and {}", path.display()));
+            self.config.project_config_loaded = true;
+            self.config.import_query = import_query;
+        }
+
+        self.config.folder = folder;
+        self.config.preset = preset;
+        self.init_icons();
+        self.update_filtered_items_main();
+        Ok(message)
+    }
+
+    pub fn handlekeys_onboarding_popup(&mut self, input: Input) {
+        let Some(state) = self.onboarding_popup_state.as_mut() else {
+            self.app_focus = AppFocus::Main;
+            return;
+        };
+        state.status_message = None;
+
+        match state.step {
+            OnboardingStep::Preset => match input.key {
+                Key::Esc | Key::Char('q') => self.close_onboarding_popup(),
+                Key::Up | Key::Char('k') => {
+                    state.preset_index = state.preset_index.saturating_sub(1);
+                }
+                Key::Down | Key::Char('j') => {
+                    state.preset_index = (state.preset_index + 1).min(PRESETS_OPTIONS.len() - 1);
+                }
+                Key::Enter | Key::Tab => {
+                    if !state.folder_customized {
+                        let folder =
+                            crate::config::default_folder_for_preset(state.selected_preset());
+                        state.set_folder(folder);
+                    }
+                    state.step = OnboardingStep::Folder;
+                }
+                _ => {}
+            },
+            OnboardingStep::Folder => match input.key {
+                Key::Esc => state.step = OnboardingStep::Preset,
+                Key::Enter | Key::Tab => {
+                    if state.folder().is_empty() {
+                        state.status_message = Some("Please enter a folder.".to_string());
+                    } else {
+                        state.step = OnboardingStep::Config;
+                    }
+                }
+                _ => {
+                    if state.folder_input.input(input) {
+                        state.folder_customized = true;
+                    }
+                }
+            },
+            OnboardingStep::Config => match input.key {
+                Key::Esc => state.step = OnboardingStep::Folder,
+                Key::Char('y') => state.create_config = true,
+                Key::Char('n') => state.create_config = false,
+                Key::Left | Key::Right | Key::Char(' ') | Key::Char('h') | Key::Char('l') => {
+                    state.create_config = !state.create_config;
+                }
+                Key::Enter => match self.finish_onboarding() {
+                    Ok(message) => {
+                        self.close_onboarding_popup();
+                        self.main_state.set_status(message, false);
+                    }
+                    Err(error) => {
+                        if let Some(state) = self.onboarding_popup_state.as_mut() {
+                            state.status_message = Some(format!("{error:#}"));
+                        }
+                    }
+                },
+                _ => {}
+            },
+        }
+    }
+}
+
+pub fn render_onboarding_popup(f: &mut Frame, app: &mut App) {
+    let area = popup_area(f.area(), 72, 24);
+    let body_area = theme::render_popup_shell(f, area, "Welcome to iconmate");
+
+    let Some(state) = app.onboarding_popup_state.as_mut() else {
+        return;
+    };
+
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    let text = Style::default().fg(theme::palette().text);
+    let muted = Style::default().fg(theme::palette().muted_text);
+    let subtle = Style::default().fg(theme::palette().subtle_text);
+    let accent_bold = Style::default()
+        .fg(theme::palette().accent)
+        .add_modifier(Modifier::BOLD);
+
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                "No iconmate config or icons folder found here yet.",
+                text,
+            )),
+            Line::from(Span::styled(
+                match &state.detection {
+                    Some(detection) => format!(
+                        "Detected {}; its preset is preselected.",
+                        detection.summary()
+                    ),
+                    None => "Pick a preset and folder to get started.".to_string(),
+                },
+                muted,
+            )),
+        ])
+        .alignment(Alignment::Left),
+        layout[0],
+    );
+
+    let (step_number, step_label) = match state.step {
+        OnboardingStep::Preset => (1, "Framework preset"),
+        OnboardingStep::Folder => (2, "Icons folder"),
+        OnboardingStep::Config => (3, "Config file"),
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("Step {step_number}/3 "), subtle),
+            Span::styled(step_label, accent_bold),
+        ])),
+        layout[2],
+    );
+
+    match state.step {
+        OnboardingStep::Preset => {
+            let lines = PRESETS_OPTIONS
+                .iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    let selected = index == state.preset_index;
+                    let name_style = if selected {
+                        Style::default()
+                            .bg(theme::palette().row_highlight_bg)
+                            .fg(theme::palette().base_bg)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        text
+                    };
+                    Line::from(vec![
+                        Span::styled(if selected { "› " } else { "  " }, accent_bold),
+                        Span::styled(format!(" {:<10}", option.preset.to_str()), name_style),
+                        Span::styled(format!("  {}", option.description), muted),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            f.render_widget(Paragraph::new(lines), layout[4]);
+        }
+        OnboardingStep::Folder => {
+            let input_block = Block::default()
+                .title("Folder where icons are written")
+                .title_style(accent_bold)
+                .style(text);
+            state.folder_input.set_block(input_block);
+            state.folder_input.set_cursor_line_style(Style::default());
+            let input_area = ratatui::layout::Rect {
+                height: layout[4].height.min(3),
+                ..layout[4]
+            };
+            f.render_widget(&state.folder_input, input_area);
+        }
+        OnboardingStep::Config => {
+            let choice = |label: &'static str, active: bool| {
+                if active {
+                    Span::styled(
+                        format!(" {label} "),
+                        Style::default()
+                            .bg(theme::palette().row_highlight_bg)
+                            .fg(theme::palette().base_bg)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Span::styled(format!(" {label} "), subtle)
+                }
+            };
+            f.render_widget(
+                Paragraph::new(vec![
+                    Line::from(Span::styled(
+                        "Save these choices to iconmate.config.jsonc?",
+                        text,
+                    )),
+                    Line::from(vec![
+                        Span::styled("preset ", muted),
+                        Span::styled(state.selected_preset(), text),
+                        Span::styled("  folder ", muted),
+                        Span::styled(state.folder(), text),
+                        Span::styled(
+                            if state.import_query().is_some() {
+                                "  import_query "
+                            } else {
+                                ""
+                            },
+                            muted,
+                        ),
+                        Span::styled(
+                            state.import_query().map_or("", |query| query.to_str()),
+                            text,
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        choice("Yes", state.create_config),
+                        Span::raw(" "),
+                        Span::styled("y", muted),
+                        Span::raw("     "),
+                        choice("No", !state.create_config),
+                        Span::raw(" "),
+                        Span::styled("n", muted),
+                    ]),
+                ]),
+                layout[4],
+            );
+        }
+    }
+
+    let footer = if let Some(message) = &state.status_message {
+        Paragraph::new(message.clone()).style(Style::default().fg(theme::palette().error))
+    } else {
+        let shortcuts: &[(&str, &str)] = match state.step {
+            OnboardingStep::Preset => &[("Select", "↑/↓"), ("Next", "enter"), ("Skip", "esc")],
+            OnboardingStep::Folder => &[("Next", "enter"), ("Back", "esc")],
+            OnboardingStep::Config => &[("Toggle", "←/→"), ("Finish", "enter"), ("Back", "esc")],
+        };
+        Paragraph::new(theme::shortcut_line(shortcuts))
+    };
+    f.render_widget(footer.alignment(Alignment::Left), layout[5]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: Key) -> Input {
+        Input {
+            key,
+            ..Default::default()
+        }
+    }
+
+    fn test_app(folder: String) -> App {
+        App::new(crate::app_state::AppConfig::for_tests(folder, "normal"))
+    }
+
+    #[test]
+    fn onboarding_creates_folder_with_chosen_preset() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let folder = temp_dir.path().join("icons");
+        let mut app = test_app(folder.to_string_lossy().into_owned());
+        assert!(app.needs_onboarding());
+
+        app.init_onboarding_popup();
+        app.handlekeys_onboarding_popup(key(Key::Down));
+        app.handlekeys_onboarding_popup(key(Key::Down));
+        app.handlekeys_onboarding_popup(key(Key::Enter));
+        assert_eq!(
+            app.onboarding_popup_state.as_ref().unwrap().folder(),
+            folder.to_string_lossy(),
+            "an explicit folder survives the preset change"
+        );
+        app.handlekeys_onboarding_popup(key(Key::Enter));
+        app.handlekeys_onboarding_popup(key(Key::Char('n')));
+        app.handlekeys_onboarding_popup(key(Key::Enter));
+
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert!(app.onboarding_popup_state.is_none());
+        assert!(folder.is_dir());
+        assert_eq!(app.config.preset, "react");
+        assert!(!app.config.project_config_loaded);
+        assert!(!app.needs_onboarding());
+    }
+
+    #[test]
+    fn detected_preset_is_preselected_with_its_import_query() {
+        let mut app = test_app(crate::config::DEFAULT_FOLDER.to_string());
+        app.init_onboarding_popup_with(Some(crate::detect::Detection {
+            root: std::path::PathBuf::from("/app"),
+            framework: "Vue",
+            bundler: Some("Vite"),
+            svg_loader: Some("vite-svg-loader"),
+            preset: crate::utils::Preset::Normal,
+            import_query: Some(crate::utils::ImportQuery::Component),
+        }));
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 26)).unwrap();
+        terminal
+            .draw(|f| render_onboarding_popup(f, &mut app))
+            .unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Detected Vue + Vite (vite-svg-loader)"));
+
+        let state = app.onboarding_popup_state.as_ref().unwrap();
+        assert_eq!(
+            state.import_query(),
+            Some(crate::utils::ImportQuery::Component)
+        );
+        app.handlekeys_onboarding_popup(key(Key::Down));
+        let state = app.onboarding_popup_state.as_ref().unwrap();
+        assert_eq!(state.import_query(), None);
+    }
+
+    #[test]
+    fn preset_switch_updates_default_folder_until_edited() {
+        let mut app = test_app(crate::config::DEFAULT_FOLDER.to_string());
+        app.init_onboarding_popup();
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 26)).unwrap();
+        terminal
+            .draw(|f| render_onboarding_popup(f, &mut app))
+            .unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Step 1/3"));
+        assert!(screen.contains("datauri"));
+        for _ in 0..6 {
+            app.handlekeys_onboarding_popup(key(Key::Down));
+        }
+        app.handlekeys_onboarding_popup(key(Key::Enter));
+        let state = app.onboarding_popup_state.as_ref().unwrap();
+        assert_eq!(state.selected_preset(), "flutter");
+        assert_eq!(state.folder(), crate::flutter::DEFAULT_FLUTTER_FOLDER);
+
+        app.handlekeys_onboarding_popup(key(Key::Char('x')));
+        app.handlekeys_onboarding_popup(key(Key::Esc));
+        app.handlekeys_onboarding_popup(key(Key::Up));
+        app.handlekeys_onboarding_popup(key(Key::Enter));
+        let state = app.onboarding_popup_state.as_ref().unwrap();
+        assert_eq!(state.selected_preset(), "vue");
+        assert_eq!(
+            state.folder(),
+            format!("{}x", crate::flutter::DEFAULT_FLUTTER_FOLDER)
+        );
+    }
+}
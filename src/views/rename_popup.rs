@@ -13,28 +13,13 @@ fn perform_flutter_rename(
     current_file_path: &str,
     new_file_input: &str,
 ) -> anyhow::Result<()> {
-    use std::path::Component;
-
     let current_rel = current_file_path.trim().to_string();
     if current_rel.is_empty() {
         anyhow::bail!("Current icon path is empty");
     }
 
+    crate::utils::validate_icon_filename(new_file_input)?;
     let mut new_rel = new_file_input.trim().to_string();
-    if new_rel.is_empty() {
-        anyhow::bail!("New filename cannot be empty");
-    }
-
-    let new_path_check = Path::new(&new_rel);
-    if new_path_check.is_absolute() {
-        anyhow::bail!("Please provide a relative filename");
-    }
-    if new_path_check
-        .components()
-        .any(|component| matches!(component, Component::ParentDir))
-    {
-        anyhow::bail!("Parent directory traversals are not allowed");
-    }
 
     // Preserve existing extension if the user typed just a bare name.
     if Path::new(&new_rel).extension().is_none() {
@@ -85,6 +70,7 @@ fn perform_flutter_rename(
         let _ = std::fs::rename(&new_abs, &current_abs);
         return Err(err);
     }
+    crate::raster::rename_raster_siblings(&current_abs, &new_abs)?;
 
     Ok(())
 }
@@ -108,13 +94,19 @@ impl RenamePopupState {
     }
 
     fn paste_into_input(&mut self) -> bool {
-        if let Ok(mut clipboard) = arboard::Clipboard::new() {
-            if let Ok(text) = clipboard.get_text() {
-                self.filename_input.insert_str(&text);
-                return true;
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            Ok(text) => {
+                self.paste_text(&text);
+                true
             }
+            Err(_) => false,
         }
-        false
+    }
+
+    pub fn paste_text(&mut self, text: &str) {
+        self.filename_input
+            .insert_str(crate::views::layout::single_line(text));
+        self.clear_status();
     }
 
     fn clear_status(&mut self) {
@@ -132,7 +124,7 @@ impl App {
     pub fn init_rename_popup(&mut self) {
         self.app_focus = AppFocus::RenamePopup;
 
-        let item_to_rename = self.filtered_items.get(self.selected_index).cloned();
+        let item_to_rename = self.selected_item().cloned();
         let mut filename_input = TextArea::default();
 
         if let Some(item) = &item_to_rename {
@@ -146,8 +138,8 @@ impl App {
 
         filename_input.set_cursor_style(
             Style::default()
-                .bg(crate::views::theme::ACCENT)
-                .fg(crate::views::theme::BASE_BG),
+                .bg(crate::views::theme::palette().accent)
+                .fg(crate::views::theme::palette().base_bg),
         );
 
         self.rename_popup_state = Some(RenamePopupState {
@@ -177,6 +169,19 @@ impl App {
             return Err("Please enter a new filename.".to_string());
         }
 
+        // A grouped icon is renamed inside its group's folder and barrel.
+        let root = Path::new(&self.config.folder);
+        let (group, file_path) = match crate::groups::split_group(&item.file_path) {
+            (Some(group), file_path)
+                if self.config.preset != "flutter"
+                    && root.join(group).join("index.ts").is_file() =>
+            {
+                (Some(group), file_path)
+            }
+            _ => (None, item.file_path.clone()),
+        };
+        let folder = root.join(group.unwrap_or_default());
+
         if self.config.preset == "flutter" {
             perform_flutter_rename(
                 &self.config.folder,
@@ -187,8 +192,21 @@ impl App {
             )
             .map_err(|error| error.to_string())?;
         } else {
-            crate::utils::rename_icon_entry(&self.config.folder, &item.file_path, &new_filename)
+            crate::utils::rename_icon_entry(&folder.to_string_lossy(), &file_path, &new_filename)
+                .map_err(|error| error.to_string())?;
+            crate::css_background::refresh_background_stylesheet_if_present(root)
                 .map_err(|error| error.to_string())?;
+            crate::icon_names::refresh_icon_names(root, false)
+                .map_err(|error| error.to_string())?;
+            crate::icon_registry::refresh_icon_registry(root, false)
+                .map_err(|error| error.to_string())?;
+        }
+        if let Some(history) = &self.config.history {
+            history.record(crate::history::Entry::rename(
+                &item.name,
+                &folder.join(file_path),
+                &folder.join(&new_filename),
+            ));
         }
 
         self.init_icons();
@@ -249,17 +267,17 @@ pub fn render_rename_popup(f: &mut Frame, app: &mut App) {
         };
         let status_paragraph = Paragraph::new(status)
             .alignment(Alignment::Left)
-            .style(Style::default().fg(crate::views::theme::MUTED_TEXT));
+            .style(Style::default().fg(crate::views::theme::palette().muted_text));
         f.render_widget(status_paragraph, layout[0]);
 
         let input_block = Block::default()
             .title("New filename")
             .title_style(
                 Style::default()
-                    .fg(crate::views::theme::ACCENT)
+                    .fg(crate::views::theme::palette().accent)
                     .add_modifier(Modifier::BOLD),
             )
-            .style(Style::default().fg(crate::views::theme::TEXT));
+            .style(Style::default().fg(crate::views::theme::palette().text));
         state.filename_input.set_block(input_block);
         state.filename_input.set_cursor_line_style(Style::default());
         f.render_widget(&state.filename_input, layout[2]);
@@ -269,14 +287,14 @@ pub fn render_rename_popup(f: &mut Frame, app: &mut App) {
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true })
-        .style(Style::default().fg(crate::views::theme::SUBTLE_TEXT));
+        .style(Style::default().fg(crate::views::theme::palette().subtle_text));
         f.render_widget(tip, layout[4]);
 
         let footer = if let Some(message) = &state.status_message {
             let color = if state.status_is_error {
-                crate::views::theme::ERROR
+                crate::views::theme::palette().error
             } else {
-                crate::views::theme::MUTED_TEXT
+                crate::views::theme::palette().muted_text
             };
             Paragraph::new(message.clone())
                 .alignment(Alignment::Left)
@@ -0,0 +1,325 @@
+use std::process::Command;
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::app_state::{App, AppFocus};
+use crate::diff::DiffLine;
+use crate::utils::popup_area;
+use crate::views::theme;
+
+#[derive(Debug)]
+pub enum ReplaceStep {
+    /// Typing the new source.
+    Source,
+    /// The diff of the selected icon's file against the new source, waiting
+    /// for `y` / `n`.
+    Review { lines: Vec<DiffLine>, scroll: usize },
+}
+
+#[derive(Debug)]
+pub struct ReplacePopupState {
+    pub item_to_replace: Option<crate::utils::IconEntry>,
+    pub source_input: TextArea<'static>,
+    pub step: ReplaceStep,
+    pub status_message: Option<String>,
+    pub status_is_error: bool,
+}
+
+impl ReplacePopupState {
+    fn source(&self) -> String {
+        self.source_input.lines().join("").trim().to_string()
+    }
+
+    fn scroll_review(&mut self, delta: isize) {
+        if let ReplaceStep::Review { lines, scroll } = &mut self.step {
+            *scroll = scroll
+                .saturating_add_signed(delta)
+                .min(lines.len().saturating_sub(1));
+        }
+    }
+}
+
+impl App {
+    pub fn init_replace_popup(&mut self) {
+        self.app_focus = AppFocus::ReplacePopup;
+
+        let mut source_input = TextArea::default();
+        source_input.set_cursor_style(
+            Style::default()
+                .bg(theme::palette().accent)
+                .fg(theme::palette().base_bg),
+        );
+        self.replace_popup_state = Some(ReplacePopupState {
+            item_to_replace: self.selected_item().cloned(),
+            source_input,
+            step: ReplaceStep::Source,
+            status_message: None,
+            status_is_error: false,
+        });
+    }
+
+    fn close_replace_popup(&mut self) {
+        self.app_focus = AppFocus::Main;
+        self.replace_popup_state = None;
+    }
+
+    /// Runs `iconmate replace` for the selected icon, like the add popup runs
+    /// `iconmate add`, and returns its stdout.
+    fn run_replace(&self, source: &str, dry_run: bool) -> Result<String, String> {
+        let Some(item) = self
+            .replace_popup_state
+            .as_ref()
+            .and_then(|state| state.item_to_replace.as_ref())
+        else {
+            return Err("No icon selected to replace.".to_string());
+        };
+        let mut command = Command::new(std::env::current_exe().map_err(|error| error.to_string())?);
+        crate::iconify_mock::forward_to(&mut command);
+        command
+            .arg("--preset")
+            .arg(&self.config.preset)
+            .arg("replace")
+            .arg("--folder")
+            .arg(&self.config.folder)
+            .arg("--name")
+            .arg(&item.name)
+            .arg("--icon")
+            .arg(source);
+        if dry_run {
+            command.arg("--dry-run");
+        }
+
+        let output = command.output().map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(if stderr.is_empty() {
+                "Failed to replace icon".to_string()
+            } else {
+                stderr
+            });
+        }
+        Ok(stdout)
+    }
+
+    fn review_replace_popup(&mut self) -> Result<(), String> {
+        let source = self
+            .replace_popup_state
+            .as_ref()
+            .map(ReplacePopupState::source)
+            .unwrap_or_default();
+        if source.is_empty() {
+            return Err("Enter an iconify name, URL or SVG to replace the icon with.".to_string());
+        }
+        let lines = crate::diff::parse_unified(&self.run_replace(&source, true)?);
+        if let Some(state) = self.replace_popup_state.as_mut() {
+            if lines.is_empty() {
+                state.status_message = Some("The icon already matches that source.".to_string());
+                state.status_is_error = false;
+            } else {
+                state.step = ReplaceStep::Review { lines, scroll: 0 };
+                state.status_message = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn confirm_replace_popup(&mut self) -> Result<(), String> {
+        let Some(state) = self.replace_popup_state.as_ref() else {
+            return Err("Replace popup is not initialized".to_string());
+        };
+        let source = state.source();
+        let name = state
+            .item_to_replace
+            .as_ref()
+            .map(|item| item.name.clone())
+            .unwrap_or_default();
+        self.run_replace(&source, false)?;
+        self.init_icons();
+        self.close_replace_popup();
+        self.main_state
+            .set_status(format!("Replaced {name} with {source}"), false);
+        Ok(())
+    }
+
+    /// Pasted sources go in as typed; multi-line SVG markup stays on one
+    /// line, which `iconmate replace` accepts all the same.
+    pub fn paste_into_replace_popup(&mut self, text: &str) {
+        if let Some(state) = self.replace_popup_state.as_mut()
+            && matches!(state.step, ReplaceStep::Source)
+        {
+            state
+                .source_input
+                .insert_str(crate::views::layout::single_line(text));
+            state.status_message = None;
+        }
+    }
+
+    pub fn handlekeys_replace_popup(&mut self, input: Input) {
+        let Some(state) = self.replace_popup_state.as_mut() else {
+            return;
+        };
+        let reviewing = matches!(state.step, ReplaceStep::Review { .. });
+        let result = match (reviewing, input.key) {
+            (_, Key::Esc) => {
+                self.close_replace_popup();
+                return;
+            }
+            (false, Key::Enter) => self.review_replace_popup(),
+            (false, _) => {
+                state.source_input.input(input);
+                state.status_message = None;
+                Ok(())
+            }
+            (true, Key::Char('y') | Key::Enter) => self.confirm_replace_popup(),
+            (true, Key::Char('n')) => {
+                state.step = ReplaceStep::Source;
+                Ok(())
+            }
+            (true, Key::Down | Key::Char('j')) => {
+                state.scroll_review(1);
+                Ok(())
+            }
+            (true, Key::Up | Key::Char('k')) => {
+                state.scroll_review(-1);
+                Ok(())
+            }
+            (true, _) => Ok(()),
+        };
+        if let Err(error) = result
+            && let Some(state) = self.replace_popup_state.as_mut()
+        {
+            state.status_message = Some(error);
+            state.status_is_error = true;
+        }
+    }
+}
+
+fn diff_to_lines(lines: &[DiffLine]) -> Vec<Line<'static>> {
+    lines
+        .iter()
+        .map(|line| match line {
+            DiffLine::Hunk(header) => Line::from(Span::styled(
+                header.clone(),
+                Style::default().fg(theme::palette().subtle_text),
+            )),
+            DiffLine::Context(text) => Line::from(Span::styled(
+                format!(" {text}"),
+                Style::default().fg(theme::palette().muted_text),
+            )),
+            DiffLine::Removed(text) => Line::from(Span::styled(
+                format!("-{text}"),
+                Style::default().fg(theme::palette().error),
+            )),
+            DiffLine::Added(text) => Line::from(Span::styled(
+                format!("+{text}"),
+                Style::default().fg(theme::palette().accent),
+            )),
+        })
+        .collect()
+}
+
+pub fn render_replace_popup(f: &mut Frame, app: &mut App) {
+    let area = popup_area(f.area(), 90, 26);
+    let body_area = theme::render_popup_shell(f, area, "Replace Icon");
+
+    let Some(state) = app.replace_popup_state.as_mut() else {
+        return;
+    };
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    let header = match &state.item_to_replace {
+        Some(item) => format!("Alias: {}\nFile: {}", item.name, item.file_path),
+        None => "No icon selected".to_string(),
+    };
+    f.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme::palette().muted_text)),
+        layout[0],
+    );
+
+    let shortcuts = match &state.step {
+        ReplaceStep::Source => {
+            let input_block = Block::default()
+                .title("New source (iconify name, URL or SVG)")
+                .title_style(
+                    Style::default()
+                        .fg(theme::palette().accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().fg(theme::palette().text));
+            state.source_input.set_block(input_block);
+            state.source_input.set_cursor_line_style(Style::default());
+            let input_area = ratatui::layout::Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
+                .split(layout[2])[0];
+            f.render_widget(&state.source_input, input_area);
+            theme::shortcut_line(&[("Show diff", "enter"), ("Cancel", "esc")])
+        }
+        ReplaceStep::Review { lines, scroll } => {
+            f.render_widget(
+                Paragraph::new(diff_to_lines(lines))
+                    .alignment(Alignment::Left)
+                    .scroll((*scroll as u16, 0)),
+                layout[2],
+            );
+            theme::shortcut_line(&[
+                ("Overwrite", "y"),
+                ("Edit source", "n"),
+                ("Scroll", "j/k"),
+                ("Cancel", "esc"),
+            ])
+        }
+    };
+
+    let footer = match &state.status_message {
+        Some(message) => {
+            Paragraph::new(message.clone()).style(Style::default().fg(if state.status_is_error {
+                theme::palette().error
+            } else {
+                theme::palette().muted_text
+            }))
+        }
+        None => Paragraph::new(shortcuts),
+    };
+    f.render_widget(footer, layout[3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_keep_their_markers() {
+        let rendered = diff_to_lines(&[
+            DiffLine::Hunk("@@ -1,1 +1,1 @@".to_string()),
+            DiffLine::Removed("<path d=\"M1 1\"/>".to_string()),
+            DiffLine::Added("<path d=\"M2 2\"/>".to_string()),
+        ]);
+        let text = rendered
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            text,
+            vec![
+                "@@ -1,1 +1,1 @@",
+                "-<path d=\"M1 1\"/>",
+                "+<path d=\"M2 2\"/>"
+            ]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph};
+
+use crate::http::RequestTiming;
+use crate::views::layout::{display_width, scroll_text};
+
+const OVERLAY_WIDTH: u16 = 84;
+const OVERLAY_ROWS: usize = 10;
+
+/// One row per request, newest first: status, time, then the URL cut from
+/// the left so the path (the part that differs) stays visible.
+fn request_log_lines(timings: &[RequestTiming], width: usize) -> Vec<(String, String)> {
+    timings
+        .iter()
+        .rev()
+        .take(OVERLAY_ROWS)
+        .map(|timing| {
+            let status = timing
+                .status
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| "ERR".to_string());
+            let mut head = format!("{status:<4}{:>5} ms  ", timing.elapsed.as_millis());
+            if timing.attempts > 1 {
+                head = format!("{head}x{} ", timing.attempts);
+            }
+            let room = width.saturating_sub(display_width(&head));
+            let url = &timing.url;
+            let url_width = display_width(url);
+            let url = if url_width > room && room > 1 {
+                scroll_text(url, url_width - room)
+            } else {
+                url.clone()
+            };
+            (head, url)
+        })
+        .collect()
+}
+
+/// Recent HTTP requests in the bottom-right corner, drawn over whatever is
+/// on screen without taking focus. Toggled with F12.
+pub fn render_request_log(f: &mut Frame) {
+    let area = f.area();
+    let width = OVERLAY_WIDTH.min(area.width);
+    let height = (OVERLAY_ROWS as u16 + 2).min(area.height);
+    let rect = Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height - height,
+        width,
+        height,
+    };
+
+    let palette = crate::views::theme::palette();
+    let timings = crate::http::recent_timings();
+    let lines: Vec<Line> = if timings.is_empty() {
+        vec![Line::from(Span::styled(
+            "No requests yet",
+            Style::default().fg(palette.subtle_text),
+        ))]
+    } else {
+        request_log_lines(&timings, width.saturating_sub(2) as usize)
+            .into_iter()
+            .map(|(head, url)| {
+                let failed =
+                    head.starts_with("ERR") || head.starts_with('4') || head.starts_with('5');
+                Line::from(vec![
+                    Span::styled(
+                        head,
+                        Style::default().fg(if failed {
+                            palette.error
+                        } else {
+                            palette.accent_soft
+                        }),
+                    ),
+                    Span::styled(url, Style::default().fg(palette.text)),
+                ])
+            })
+            .collect()
+    };
+
+    f.render_widget(Clear, rect);
+    let block = Block::bordered()
+        .title(" Requests (F12) ")
+        .title_style(
+            Style::default()
+                .fg(palette.muted_text)
+                .add_modifier(Modifier::BOLD),
+        )
+        .border_style(Style::default().fg(palette.subtle_text))
+        .style(Style::default().bg(palette.panel_bg));
+    f.render_widget(Paragraph::new(lines).block(block), rect);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn lists_newest_first_and_keeps_the_end_of_long_urls() {
+        let timing = |url: &str, status, attempts| RequestTiming {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            status,
+            attempts,
+            elapsed: Duration::from_millis(42),
+            headers: Vec::new(),
+        };
+        let timings = vec![
+            timing("https://api.iconify.design/collections", Some(200), 1),
+            timing(
+                "https://icons.example.com/mirror/api/collection?prefix=mdi",
+                None,
+                3,
+            ),
+        ];
+
+        let lines = request_log_lines(&timings, 40);
+        assert_eq!(lines[0].0, "ERR    42 ms  x3 ");
+        assert_eq!(lines[0].1, "…/collection?prefix=mdi");
+        assert_eq!(
+            lines[1],
+            (
+                "200    42 ms  ".to_string(),
+                "…conify.design/collections".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::Frame;
+use ratatui::layout::Constraint;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Paragraph};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::app_state::{App, AppFocus};
+use crate::utils::popup_area;
+use crate::views::theme;
+
+/// Tag names, `>`/`/>` and attribute names, highlighted through the
+/// textarea's search so markup reads at a glance.
+const MARKUP_PATTERN: &str = r"</?[A-Za-z][\w:.-]*|/?>|[A-Za-z_:][\w:.-]*=";
+
+/// Quick edits to one icon's markup (a fill, the viewBox) without leaving
+/// the TUI. Saving validates the SVG first.
+#[derive(Debug)]
+pub struct SvgEditPopupState {
+    pub item_to_edit: crate::utils::IconEntry,
+    pub path: PathBuf,
+    pub textarea: TextArea<'static>,
+    pub error: Option<String>,
+}
+
+impl SvgEditPopupState {
+    pub fn paste_text(&mut self, text: &str) {
+        self.textarea.insert_str(text);
+        self.error = None;
+    }
+
+    fn svg(&self) -> String {
+        self.textarea.lines().join("\n")
+    }
+}
+
+impl App {
+    pub fn init_svg_edit_popup(&mut self) {
+        let Some(item) = self.selected_item().cloned() else {
+            return;
+        };
+        let path = match self.selected_icon_path() {
+            Ok(path) => path,
+            Err(error) => {
+                self.main_state.set_status(format!("{error:#}"), true);
+                return;
+            }
+        };
+        let is_svg = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        if !is_svg {
+            self.main_state.set_status(
+                format!(
+                    "Only .svg files can be edited here; open {} with o.",
+                    item.file_path
+                ),
+                true,
+            );
+            return;
+        }
+        let svg = match std::fs::read_to_string(&path) {
+            Ok(svg) => svg,
+            Err(error) => {
+                self.main_state
+                    .set_status(format!("Can't read {}: {error}", path.display()), true);
+                return;
+            }
+        };
+
+        let mut textarea = TextArea::from(svg.lines().map(str::to_string));
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_line_number_style(Style::default().fg(theme::palette().subtle_text));
+        textarea.set_search_style(Style::default().fg(theme::palette().accent));
+        let _ = textarea.set_search_pattern(MARKUP_PATTERN);
+
+        self.app_focus = AppFocus::SvgEditPopup;
+        self.svg_edit_popup_state = Some(SvgEditPopupState {
+            item_to_edit: item,
+            path,
+            textarea,
+            error: None,
+        });
+    }
+
+    fn close_svg_edit_popup(&mut self) {
+        self.app_focus = AppFocus::Main;
+        self.svg_edit_popup_state = None;
+    }
+
+    fn save_svg_edit_popup(&mut self) -> Result<(), String> {
+        let Some(state) = self.svg_edit_popup_state.as_ref() else {
+            return Err("SVG editor is not initialized".to_string());
+        };
+        let svg = state.svg();
+        crate::svg_render::summarize(&svg).map_err(|error| format!("Invalid SVG: {error}"))?;
+        let path = state.path.clone();
+        let name = state.item_to_edit.name.clone();
+
+        std::fs::write(&path, format!("{}\n", svg.trim_end()))
+            .map_err(|error| error.to_string())?;
+        self.refresh_after_svg_edit(&path)
+            .map_err(|error| format!("{error:#}"))?;
+
+        self.close_svg_edit_popup();
+        self.main_state.set_status(format!("Saved {name}"), false);
+        Ok(())
+    }
+
+    /// The files built from icon contents, when the folder has them.
+    fn refresh_after_svg_edit(&self, path: &Path) -> anyhow::Result<()> {
+        let folder = Path::new(&self.config.folder);
+        crate::utils::refresh_derived_files(folder)?;
+        crate::gallery::refresh_gallery_if_present(
+            folder,
+            &self.config.preset,
+            self.config.flutter_barrel_file.as_deref(),
+            self.config.flutter_barrel_class.as_deref(),
+        )?;
+        crate::raster::rerender_raster_siblings(path)?;
+        Ok(())
+    }
+
+    pub fn handlekeys_svg_edit_popup(&mut self, input: Input) {
+        match input {
+            Input { key: Key::Esc, .. } => self.close_svg_edit_popup(),
+            Input {
+                key: Key::Char('s'),
+                ctrl: true,
+                ..
+            } => {
+                if let Err(error) = self.save_svg_edit_popup()
+                    && let Some(state) = self.svg_edit_popup_state.as_mut()
+                {
+                    state.error = Some(error);
+                }
+            }
+            input => {
+                if let Some(state) = self.svg_edit_popup_state.as_mut() {
+                    state.textarea.input(input);
+                    state.error = None;
+                }
+            }
+        }
+    }
+}
+
+pub fn render_svg_edit_popup(f: &mut Frame, app: &mut App) {
+    let Some(state) = app.svg_edit_popup_state.as_mut() else {
+        return;
+    };
+    let area = popup_area(f.area(), 90, 28);
+    let title = format!("Edit {}", state.item_to_edit.file_path);
+    let body_area = theme::render_popup_shell(f, area, &title);
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    state.textarea.set_block(
+        Block::default().style(
+            Style::default()
+                .bg(theme::palette().input_bg)
+                .fg(theme::palette().text),
+        ),
+    );
+    state.textarea.set_cursor_style(
+        Style::default()
+            .bg(theme::palette().accent)
+            .fg(theme::palette().base_bg)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(&state.textarea, layout[0]);
+
+    let footer = match &state.error {
+        Some(error) => {
+            Paragraph::new(error.clone()).style(Style::default().fg(theme::palette().error))
+        }
+        None => Paragraph::new(theme::shortcut_line(&[
+            ("Save", "ctrl+s"),
+            ("Cancel", "esc"),
+        ])),
+    };
+    f.render_widget(footer, layout[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppConfig;
+
+    #[test]
+    fn saves_only_markup_that_validates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHome } from './home.svg';\nexport { default as IconStar } from './star.tsx';\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("home.svg"),
+            "<svg viewBox=\"0 0 24 24\">\n<path fill=\"red\"/>\n</svg>\n",
+        )
+        .unwrap();
+        let mut app = App::new(AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        let press = |app: &mut App, key, ctrl| {
+            app.handlekeys(Input {
+                key,
+                ctrl,
+                ..Default::default()
+            })
+        };
+
+        press(&mut app, Key::Char('e'), false);
+        assert_eq!(app.app_focus, AppFocus::SvgEditPopup);
+
+        // Break the markup: `</svg>` becomes `</sv`.
+        press(&mut app, Key::Down, false);
+        press(&mut app, Key::Down, false);
+        press(&mut app, Key::End, false);
+        press(&mut app, Key::Backspace, false);
+        press(&mut app, Key::Backspace, false);
+        press(&mut app, Key::Char('s'), true);
+        let state = app.svg_edit_popup_state.as_ref().unwrap();
+        assert!(state.error.as_deref().unwrap().starts_with("Invalid SVG"));
+        assert!(
+            std::fs::read_to_string(temp_dir.path().join("home.svg"))
+                .unwrap()
+                .ends_with("</svg>\n")
+        );
+
+        press(&mut app, Key::Char('g'), false);
+        press(&mut app, Key::Char('>'), false);
+        press(&mut app, Key::Up, false);
+        press(&mut app, Key::Home, false);
+        for _ in 0.."<path fill=\"".len() {
+            press(&mut app, Key::Right, false);
+        }
+        for _ in 0.."red".len() {
+            press(&mut app, Key::Delete, false);
+        }
+        for c in "currentColor".chars() {
+            press(&mut app, Key::Char(c), false);
+        }
+        press(&mut app, Key::Char('s'), true);
+
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("home.svg")).unwrap(),
+            "<svg viewBox=\"0 0 24 24\">\n<path fill=\"currentColor\"/>\n</svg>\n"
+        );
+        assert_eq!(
+            app.main_state.status_message.as_deref(),
+            Some("Saved IconHome")
+        );
+
+        // Components aren't plain SVG; the editor stays closed for them.
+        press(&mut app, Key::Char('j'), false);
+        press(&mut app, Key::Char('e'), false);
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert!(
+            app.main_state
+                .status_message
+                .as_deref()
+                .unwrap()
+                .contains("Only .svg")
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use tui_textarea::{Input, Key};
@@ -15,14 +15,87 @@ use crate::views::theme;
 
 #[derive(Debug)]
 pub enum SyncPopupState {
-    Plan(SyncPlan),
+    Review(SyncReview),
     Error(String),
 }
 
+/// Per-item accept/skip over a sync plan, like `git add -p`: additions first,
+/// then removals. Collisions are shown but can't be accepted.
+#[derive(Debug)]
+pub struct SyncReview {
+    pub plan: SyncPlan,
+    pub cursor: usize,
+    /// `None` until decided; undecided items are skipped on write.
+    pub decisions: Vec<Option<bool>>,
+}
+
+impl SyncReview {
+    pub fn new(plan: SyncPlan) -> Self {
+        let decisions = vec![None; plan.additions.len() + plan.removals.len()];
+        Self {
+            plan,
+            cursor: 0,
+            decisions,
+        }
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.decisions.len()
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        if self.decisions.is_empty() {
+            return;
+        }
+        let last = self.decisions.len() - 1;
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// Decides the item under the cursor and moves to the next one.
+    pub fn decide(&mut self, accept: bool) {
+        if let Some(decision) = self.decisions.get_mut(self.cursor) {
+            *decision = Some(accept);
+            self.move_cursor(1);
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(decision) = self.decisions.get_mut(self.cursor) {
+            *decision = Some(!decision.unwrap_or(false));
+        }
+    }
+
+    /// Accepts every item not decided yet.
+    pub fn accept_rest(&mut self) {
+        for decision in self.decisions.iter_mut().filter(|d| d.is_none()) {
+            *decision = Some(true);
+        }
+    }
+
+    /// The plan narrowed to accepted items, without collisions.
+    pub fn accepted_plan(&self) -> SyncPlan {
+        let accepted = |index: usize| self.decisions[index] == Some(true);
+        let offset = self.plan.additions.len();
+        SyncPlan {
+            preset: self.plan.preset.clone(),
+            barrel_location: self.plan.barrel_location.clone(),
+            additions: (self.plan.additions.iter().enumerate())
+                .filter(|(i, _)| accepted(*i))
+                .map(|(_, addition)| addition.clone())
+                .collect(),
+            removals: (self.plan.removals.iter().enumerate())
+                .filter(|(i, _)| accepted(offset + i))
+                .map(|(_, removal)| removal.clone())
+                .collect(),
+            collisions: Vec::new(),
+        }
+    }
+}
+
 impl App {
     pub fn init_sync_popup(&mut self) {
         let state = match build_sync_plan(&self.config) {
-            Ok(plan) => SyncPopupState::Plan(plan),
+            Ok(plan) => SyncPopupState::Review(SyncReview::new(plan)),
             Err(err) => SyncPopupState::Error(format!("Failed to compute sync plan:\n{err}")),
         };
         self.sync_popup_state = Some(state);
@@ -30,17 +103,77 @@ impl App {
     }
 
     pub fn handlekeys_sync_popup(&mut self, input: Input) {
-        match input.key {
-            Key::Esc | Key::Char('q') | Key::Char('S') | Key::Char('s') => {
-                self.sync_popup_state = None;
-                self.app_focus = AppFocus::Main;
+        let review = match self.sync_popup_state.as_mut() {
+            Some(SyncPopupState::Review(review)) => Some(review),
+            _ => None,
+        };
+        match (input.key, review) {
+            (Key::Esc | Key::Char('q') | Key::Char('S') | Key::Char('s'), _) => {
+                self.close_sync_popup();
             }
+            (Key::Up | Key::Char('k'), Some(review)) => review.move_cursor(-1),
+            (Key::Down | Key::Char('j'), Some(review)) => review.move_cursor(1),
+            (Key::Char('y'), Some(review)) => review.decide(true),
+            (Key::Char('n'), Some(review)) => review.decide(false),
+            (Key::Char(' '), Some(review)) => review.toggle(),
+            (Key::Char('a'), Some(review)) => review.accept_rest(),
+            (Key::Enter, Some(_)) => self.apply_sync_review(),
             _ => {}
         }
     }
+
+    fn close_sync_popup(&mut self) {
+        self.sync_popup_state = None;
+        self.app_focus = AppFocus::Main;
+    }
+
+    fn apply_sync_review(&mut self) {
+        let Some(SyncPopupState::Review(review)) = self.sync_popup_state.as_ref() else {
+            return;
+        };
+        let plan = review.accepted_plan();
+        self.close_sync_popup();
+        if plan.additions.is_empty() && plan.removals.is_empty() {
+            self.main_state
+                .set_status("Nothing accepted; index unchanged".to_string(), false);
+            return;
+        }
+
+        let mut options = self.config.sync_options.clone();
+        options.prune = !plan.removals.is_empty();
+        let result = with_sync_context(&self.config, |ctx| {
+            sync::apply_sync_plan(&plan, ctx, options)
+        });
+        match result {
+            Ok(summary) => {
+                if let Err(e) = crate::gallery::refresh_gallery_if_present(
+                    Path::new(&self.config.folder),
+                    &self.config.preset,
+                    self.config.flutter_barrel_file.as_deref(),
+                    self.config.flutter_barrel_class.as_deref(),
+                ) {
+                    eprintln!("Failed to update {}: {}", crate::gallery::GALLERY_FILE, e);
+                }
+                self.init_icons();
+                self.main_state.set_status(
+                    format!(
+                        "Synced: +{} added, -{} removed",
+                        summary.added, summary.removed
+                    ),
+                    false,
+                );
+            }
+            Err(e) => self
+                .main_state
+                .set_status(format!("Sync failed: {e:#}"), true),
+        }
+    }
 }
 
-fn build_sync_plan(config: &crate::app_state::AppConfig) -> anyhow::Result<SyncPlan> {
+fn with_sync_context<T>(
+    config: &crate::app_state::AppConfig,
+    f: impl FnOnce(&sync::SyncContext) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
     let folder = PathBuf::from(&config.folder);
     let barrel_file = config.flutter_barrel_file.as_deref().map(Path::new);
     let renames: HashMap<String, String> = HashMap::new();
@@ -51,8 +184,16 @@ fn build_sync_plan(config: &crate::app_state::AppConfig) -> anyhow::Result<SyncP
         flutter_barrel_file: barrel_file,
         flutter_barrel_class: config.flutter_barrel_class.as_deref(),
         renames: &renames,
+        alias_style: &config.alias_style,
+        code_style: &config.code_style,
+        import_query: config.import_query,
+        lazy: crate::lazy_export::LazyLoader::configured(config.lazy_exports, &config.preset),
     };
-    sync::compute_sync_plan(&ctx)
+    f(&ctx)
+}
+
+fn build_sync_plan(config: &crate::app_state::AppConfig) -> anyhow::Result<SyncPlan> {
+    with_sync_context(config, sync::compute_sync_plan)
 }
 
 pub fn render_sync_popup(f: &mut Frame, app: &App) {
@@ -70,17 +211,18 @@ pub fn render_sync_popup(f: &mut Frame, app: &App) {
             Constraint::Length(2),
             Constraint::Length(1),
             Constraint::Min(0),
+            Constraint::Length(1),
         ])
         .split(body_area);
 
     let description = Paragraph::new(vec![
         Line::from(Span::styled(
             "Checks drift if you edited your icons folder manually without iconmate.",
-            Style::default().fg(theme::MUTED_TEXT),
+            Style::default().fg(theme::palette().muted_text),
         )),
         Line::from(Span::styled(
-            "Read-only in the TUI — run `iconmate sync --apply` to write.",
-            Style::default().fg(theme::SUBTLE_TEXT),
+            "Accept or skip each change; only accepted ones are written.",
+            Style::default().fg(theme::palette().subtle_text),
         )),
     ])
     .alignment(Alignment::Left);
@@ -89,27 +231,51 @@ pub fn render_sync_popup(f: &mut Frame, app: &App) {
     match state {
         SyncPopupState::Error(msg) => {
             let body = Paragraph::new(msg.clone())
-                .style(Style::default().fg(theme::ERROR))
+                .style(Style::default().fg(theme::palette().error))
                 .alignment(Alignment::Left);
             f.render_widget(body, layout[2]);
+            let footer = Paragraph::new(theme::shortcut_line(&[("Close", "esc")]));
+            f.render_widget(footer, layout[3]);
         }
-        SyncPopupState::Plan(plan) => {
-            let body = Paragraph::new(plan_to_lines(plan)).alignment(Alignment::Left);
+        SyncPopupState::Review(review) => {
+            let (lines, cursor_line) = review_to_lines(review);
+            // Keep the cursor row in view on long plans.
+            let height = layout[2].height as usize;
+            let scroll = cursor_line
+                .filter(|line| *line >= height)
+                .map(|line| line + 1 - height)
+                .unwrap_or(0);
+            let body = Paragraph::new(lines)
+                .alignment(Alignment::Left)
+                .scroll((scroll as u16, 0));
             f.render_widget(body, layout[2]);
+
+            let footer = if review.item_count() == 0 {
+                theme::shortcut_line(&[("Close", "esc")])
+            } else {
+                theme::shortcut_line(&[
+                    ("Accept", "y"),
+                    ("Skip", "n"),
+                    ("Toggle", "space"),
+                    ("Accept rest", "a"),
+                    ("Write", "enter"),
+                    ("Cancel", "esc"),
+                ])
+            };
+            f.render_widget(Paragraph::new(footer), layout[3]);
         }
     }
 }
 
-const ADD_COLOR: Color = theme::ACCENT;
-const PRUNE_COLOR: Color = theme::ERROR;
-const WARN_COLOR: Color = Color::Rgb(250, 204, 21);
-
-fn plan_to_lines(plan: &SyncPlan) -> Vec<Line<'static>> {
-    let text = Style::default().fg(theme::TEXT);
-    let muted = Style::default().fg(theme::MUTED_TEXT);
-    let subtle = Style::default().fg(theme::SUBTLE_TEXT);
+/// The review's lines, and which of them holds the cursor.
+fn review_to_lines(review: &SyncReview) -> (Vec<Line<'static>>, Option<usize>) {
+    let plan = &review.plan;
+    let text = Style::default().fg(theme::palette().text);
+    let muted = Style::default().fg(theme::palette().muted_text);
+    let subtle = Style::default().fg(theme::palette().subtle_text);
 
     let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut cursor_line = None;
     lines.push(Line::from(vec![
         Span::styled("Barrel: ", muted),
         Span::styled(plan.barrel_location.clone(), text),
@@ -124,25 +290,49 @@ fn plan_to_lines(plan: &SyncPlan) -> Vec<Line<'static>> {
         lines.push(Line::from(Span::styled(
             "● It's clean and synced!",
             Style::default()
-                .fg(theme::ACCENT)
+                .fg(theme::palette().accent)
                 .add_modifier(Modifier::BOLD),
         )));
-        return lines;
+        return (lines, None);
     }
 
+    let marker = |index: usize| match review.decisions[index] {
+        Some(true) => "[y]",
+        Some(false) => "[n]",
+        None => "[ ]",
+    };
+    let row_style = |index: usize, color| {
+        if index == review.cursor {
+            Style::default()
+                .bg(theme::palette().row_highlight_bg)
+                .fg(theme::palette().base_bg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        }
+    };
+
     if !plan.additions.is_empty() {
         lines.push(Line::from(Span::styled(
-            format!("Would add ({}):", plan.additions.len()),
-            Style::default().fg(ADD_COLOR).add_modifier(Modifier::BOLD),
+            format!("Add ({}):", plan.additions.len()),
+            Style::default()
+                .fg(theme::palette().accent)
+                .add_modifier(Modifier::BOLD),
         )));
-        for a in &plan.additions {
+        for (index, a) in plan.additions.iter().enumerate() {
+            if index == review.cursor {
+                cursor_line = Some(lines.len());
+            }
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("  + {:<24}", a.identifier),
-                    Style::default().fg(ADD_COLOR),
+                    format!("  {} + {:<24}", marker(index), a.identifier),
+                    row_style(index, theme::palette().accent),
                 ),
                 Span::styled(" → ", muted),
-                Span::styled(a.file_path.clone(), Style::default().fg(ADD_COLOR)),
+                Span::styled(
+                    a.file_path.clone(),
+                    Style::default().fg(theme::palette().accent),
+                ),
                 Span::styled("  (orphan file)", subtle),
             ]));
         }
@@ -151,19 +341,27 @@ fn plan_to_lines(plan: &SyncPlan) -> Vec<Line<'static>> {
 
     if !plan.removals.is_empty() {
         lines.push(Line::from(Span::styled(
-            format!("Would prune ({}):", plan.removals.len()),
+            format!("Prune ({}):", plan.removals.len()),
             Style::default()
-                .fg(PRUNE_COLOR)
+                .fg(theme::palette().error)
                 .add_modifier(Modifier::BOLD),
         )));
-        for r in &plan.removals {
+        let offset = plan.additions.len();
+        for (i, r) in plan.removals.iter().enumerate() {
+            let index = offset + i;
+            if index == review.cursor {
+                cursor_line = Some(lines.len());
+            }
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("  - {:<24}", r.identifier),
-                    Style::default().fg(PRUNE_COLOR),
+                    format!("  {} - {:<24}", marker(index), r.identifier),
+                    row_style(index, theme::palette().error),
                 ),
                 Span::styled(" → ", muted),
-                Span::styled(r.file_path.clone(), Style::default().fg(PRUNE_COLOR)),
+                Span::styled(
+                    r.file_path.clone(),
+                    Style::default().fg(theme::palette().error),
+                ),
                 Span::styled("  (file missing)", subtle),
             ]));
         }
@@ -173,43 +371,111 @@ fn plan_to_lines(plan: &SyncPlan) -> Vec<Line<'static>> {
     if !plan.collisions.is_empty() {
         lines.push(Line::from(Span::styled(
             format!("Collisions ({}):", plan.collisions.len()),
-            Style::default().fg(WARN_COLOR).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme::palette().warn)
+                .add_modifier(Modifier::BOLD),
         )));
         for c in &plan.collisions {
             lines.push(Line::from(vec![
                 Span::styled(
                     format!("  ! {}", c.inferred_identifier),
-                    Style::default().fg(WARN_COLOR),
+                    Style::default().fg(theme::palette().warn),
                 ),
                 Span::styled(" collides with ", muted),
                 Span::styled(
                     format!("`{}`", c.conflicting_identifier),
-                    Style::default().fg(WARN_COLOR),
+                    Style::default().fg(theme::palette().warn),
                 ),
                 Span::styled(format!(" (from {})", c.file_path), subtle),
             ]));
         }
-        lines.push(Line::from(""));
-    }
-
-    if !plan.additions.is_empty() || !plan.removals.is_empty() {
         lines.push(Line::from(Span::styled(
-            "Run with --apply to write additions.",
+            "Collisions are left out. Resolve them with `iconmate sync --rename <inferred>=<newName>`, or rename the SVG on disk.",
             muted,
         )));
-        if !plan.removals.is_empty() {
-            lines.push(Line::from(Span::styled(
-                "Run with --apply --prune to also remove orphan entries.",
-                muted,
-            )));
+    }
+
+    (lines, cursor_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{Addition, Removal};
+
+    fn key(key: Key) -> Input {
+        Input {
+            key,
+            ..Default::default()
         }
     }
-    if !plan.collisions.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "Resolve collisions with --rename <inferred>=<newName>, or rename the SVG on disk.",
-            muted,
-        )));
+
+    fn test_app(folder: String) -> App {
+        App::new(crate::app_state::AppConfig {
+            project_config_loaded: true,
+            ..crate::app_state::AppConfig::for_tests(folder, "normal")
+        })
     }
 
-    lines
+    #[test]
+    fn review_keeps_only_accepted_items() {
+        let plan = SyncPlan {
+            preset: "normal".to_string(),
+            barrel_location: "index.ts".to_string(),
+            additions: ["heart", "star"]
+                .iter()
+                .map(|name| Addition {
+                    file_path: format!("./{name}.svg"),
+                    identifier: name.to_string(),
+                    rendered_line: None,
+                })
+                .collect(),
+            removals: vec![Removal {
+                identifier: "IconGone".to_string(),
+                file_path: "./gone.svg".to_string(),
+            }],
+            collisions: Vec::new(),
+        };
+        let mut review = SyncReview::new(plan);
+        review.decide(true);
+        review.decide(false);
+        review.toggle();
+        review.move_cursor(5);
+        assert_eq!(review.cursor, 2);
+
+        let accepted = review.accepted_plan();
+        assert_eq!(accepted.additions.len(), 1);
+        assert_eq!(accepted.additions[0].identifier, "heart");
+        assert_eq!(accepted.removals.len(), 1);
+    }
+
+    #[test]
+    fn enter_writes_accepted_additions_only() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let folder = temp_dir.path();
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#;
+        std::fs::write(folder.join("heart.svg"), svg).unwrap();
+        std::fs::write(folder.join("star.svg"), svg).unwrap();
+        std::fs::write(
+            folder.join("index.ts"),
+            "export { default as IconGone } from './gone.svg';\n",
+        )
+        .unwrap();
+
+        let mut app = test_app(folder.to_string_lossy().to_string());
+        app.init_sync_popup();
+        app.handlekeys_sync_popup(key(Key::Char('y')));
+        app.handlekeys_sync_popup(key(Key::Char('n')));
+        app.handlekeys_sync_popup(key(Key::Enter));
+
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert_eq!(
+            std::fs::read_to_string(folder.join("index.ts")).unwrap(),
+            "export { default as IconGone } from './gone.svg';\nexport { default as IconHeart } from './heart.svg';\n"
+        );
+        assert_eq!(
+            app.main_state.status_message.as_deref(),
+            Some("Synced: +1 added, -0 removed")
+        );
+    }
 }
@@ -0,0 +1,224 @@
+use std::path::Path;
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::app_state::{App, AppFocus};
+use crate::utils::popup_area;
+use crate::views::theme;
+
+#[derive(Debug)]
+pub struct TagPopupState {
+    pub item_to_tag: Option<crate::utils::IconEntry>,
+    pub tags_input: TextArea<'static>,
+    pub status_message: Option<String>,
+}
+
+impl TagPopupState {
+    pub fn paste_text(&mut self, text: &str) {
+        self.tags_input
+            .insert_str(crate::views::layout::single_line(text));
+        self.status_message = None;
+    }
+}
+
+impl App {
+    pub fn init_tag_popup(&mut self) {
+        self.app_focus = AppFocus::TagPopup;
+
+        let item_to_tag = self.selected_item().cloned();
+        let mut tags_input = TextArea::default();
+        if let Some(item) = &item_to_tag {
+            tags_input.insert_str(self.tags.get(&item.name).collect::<Vec<_>>().join(", "));
+        }
+        tags_input.set_cursor_style(
+            Style::default()
+                .bg(theme::palette().accent)
+                .fg(theme::palette().base_bg),
+        );
+
+        self.tag_popup_state = Some(TagPopupState {
+            item_to_tag,
+            tags_input,
+            status_message: None,
+        });
+    }
+
+    fn close_tag_popup(&mut self) {
+        self.app_focus = AppFocus::Main;
+        self.tag_popup_state = None;
+    }
+
+    fn submit_tag_popup(&mut self) -> Result<(), String> {
+        let Some(state) = self.tag_popup_state.as_ref() else {
+            return Err("Tag popup is not initialized".to_string());
+        };
+        let Some(item) = state.item_to_tag.clone() else {
+            return Err("No icon selected to tag.".to_string());
+        };
+        let tags = crate::tags::parse_tag_list(&state.tags_input.lines().join(""));
+
+        let mut updated = self.tags.clone();
+        updated.set(&item.name, tags);
+        updated
+            .write(Path::new(&self.config.folder), &self.items)
+            .map_err(|error| format!("{error:#}"))?;
+        self.tags = updated;
+
+        self.close_tag_popup();
+        self.update_filtered_items_main();
+        let tags = self.tags.get(&item.name).collect::<Vec<_>>();
+        let message = if tags.is_empty() {
+            format!("Cleared tags of {}", item.name)
+        } else {
+            format!("Tagged {} with {}", item.name, tags.join(", "))
+        };
+        self.main_state.set_status(message, false);
+        Ok(())
+    }
+
+    pub fn handlekeys_tag_popup(&mut self, input: Input) {
+        match input.key {
+            Key::Esc => self.close_tag_popup(),
+            Key::Enter => {
+                if let Err(error) = self.submit_tag_popup()
+                    && let Some(state) = self.tag_popup_state.as_mut()
+                {
+                    state.status_message = Some(error);
+                }
+            }
+            _ => {
+                if let Some(state) = self.tag_popup_state.as_mut() {
+                    state.tags_input.input(input);
+                    state.status_message = None;
+                }
+            }
+        }
+    }
+}
+
+pub fn render_tag_popup(f: &mut Frame, app: &mut App) {
+    let area = popup_area(f.area(), 74, 16);
+    let body_area = theme::render_popup_shell(f, area, "Edit Tags");
+
+    let layout = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .split(body_area);
+
+    let in_use = app.tags.all().into_iter().collect::<Vec<_>>().join(", ");
+    let Some(state) = app.tag_popup_state.as_mut() else {
+        return;
+    };
+
+    let header = match &state.item_to_tag {
+        Some(item) => format!("Alias: {}\nFile: {}", item.name, item.file_path),
+        None => "No icon selected".to_string(),
+    };
+    f.render_widget(
+        Paragraph::new(header)
+            .alignment(Alignment::Left)
+            .style(Style::default().fg(theme::palette().muted_text)),
+        layout[0],
+    );
+
+    let input_block = Block::default()
+        .title("Tags (comma-separated)")
+        .title_style(
+            Style::default()
+                .fg(theme::palette().accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().fg(theme::palette().text));
+    state.tags_input.set_block(input_block);
+    state.tags_input.set_cursor_line_style(Style::default());
+    f.render_widget(&state.tags_input, layout[2]);
+
+    let tip = if in_use.is_empty() {
+        "Search #tag in the icon list to filter by tag.".to_string()
+    } else {
+        format!("In use: {in_use}")
+    };
+    f.render_widget(
+        Paragraph::new(tip)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(theme::palette().subtle_text)),
+        layout[4],
+    );
+
+    let footer = match &state.status_message {
+        Some(message) => Paragraph::new(message.clone())
+            .alignment(Alignment::Left)
+            .style(Style::default().fg(theme::palette().error)),
+        None => Paragraph::new(theme::shortcut_line(&[
+            ("Save", "enter"),
+            ("Cancel", "esc"),
+        ]))
+        .alignment(Alignment::Left),
+    };
+    f.render_widget(footer, layout[5]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppConfig;
+
+    #[test]
+    fn saves_tags_for_the_selected_icon_and_filters_by_them() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.ts"),
+            "export { default as IconHome } from './home.svg';\nexport { default as IconStar } from './star.svg';\n",
+        )
+        .unwrap();
+        let mut app = App::new(AppConfig::for_tests(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "normal",
+        ));
+        let press = |app: &mut App, key| {
+            app.handlekeys(Input {
+                key,
+                ..Default::default()
+            })
+        };
+
+        press(&mut app, Key::Char('t'));
+        assert_eq!(app.app_focus, AppFocus::TagPopup);
+        for c in "Navigation, top bar".chars() {
+            press(&mut app, Key::Char(c));
+        }
+        press(&mut app, Key::Enter);
+
+        assert_eq!(app.app_focus, AppFocus::Main);
+        assert_eq!(
+            app.main_state.status_message.as_deref(),
+            Some("Tagged IconHome with navigation, top-bar")
+        );
+        let written = crate::tags::IconTags::read(temp_dir.path()).unwrap();
+        assert!(written.has("IconHome", "top-bar"));
+
+        for c in "/#navigation".chars() {
+            press(&mut app, Key::Char(c));
+        }
+        assert_eq!(app.filtered_items.len(), 1);
+        assert_eq!(app.filtered_items[0].name, "IconHome");
+
+        // Reopening shows the saved tags for editing.
+        press(&mut app, Key::Enter);
+        press(&mut app, Key::Char('t'));
+        let state = app.tag_popup_state.as_ref().unwrap();
+        assert_eq!(state.tags_input.lines(), ["navigation, top-bar"]);
+    }
+}
@@ -1,33 +1,223 @@
+use std::sync::OnceLock;
+
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Clear, Paragraph},
 };
 
-pub const BASE_BG: Color = Color::Rgb(10, 14, 22);
-pub const PANEL_BG: Color = Color::Reset;
-pub const INPUT_BG: Color = Color::Reset;
-pub const LOGO_GREEN: Color = Color::Rgb(74, 222, 128);
-pub const TAB_BG: Color = Color::Reset;
-pub const TAB_BG_ACTIVE: Color = LOGO_GREEN;
-pub const ROW_HIGHLIGHT_BG: Color = LOGO_GREEN;
-
-pub const TEXT: Color = Color::Rgb(236, 240, 246);
-pub const MUTED_TEXT: Color = Color::Rgb(143, 155, 177);
-pub const SUBTLE_TEXT: Color = Color::Rgb(110, 124, 149);
-pub const ACCENT: Color = LOGO_GREEN;
-pub const ACCENT_SOFT: Color = LOGO_GREEN;
-pub const ERROR: Color = Color::Rgb(248, 113, 113);
+/// Names accepted by the `theme` config key. `auto` picks `dark` or `light`
+/// from the terminal background.
+pub const THEME_NAMES: &[&str] = &["auto", "dark", "light", "high-contrast"];
+
+/// Keys accepted by the `palette` config object.
+pub const PALETTE_KEYS: &[&str] = &[
+    "base_bg",
+    "panel_bg",
+    "input_bg",
+    "tab_bg",
+    "tab_bg_active",
+    "row_highlight_bg",
+    "text",
+    "muted_text",
+    "subtle_text",
+    "accent",
+    "accent_soft",
+    "error",
+    "warn",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub base_bg: Color,
+    pub panel_bg: Color,
+    pub input_bg: Color,
+    pub tab_bg: Color,
+    pub tab_bg_active: Color,
+    pub row_highlight_bg: Color,
+    pub text: Color,
+    pub muted_text: Color,
+    pub subtle_text: Color,
+    pub accent: Color,
+    pub accent_soft: Color,
+    pub error: Color,
+    pub warn: Color,
+}
+
+const LOGO_GREEN: Color = Color::Rgb(74, 222, 128);
+
+impl Palette {
+    pub const DARK: Palette = Palette {
+        base_bg: Color::Rgb(10, 14, 22),
+        panel_bg: Color::Reset,
+        input_bg: Color::Reset,
+        tab_bg: Color::Reset,
+        tab_bg_active: LOGO_GREEN,
+        row_highlight_bg: LOGO_GREEN,
+        text: Color::Rgb(236, 240, 246),
+        muted_text: Color::Rgb(143, 155, 177),
+        subtle_text: Color::Rgb(110, 124, 149),
+        accent: LOGO_GREEN,
+        accent_soft: LOGO_GREEN,
+        error: Color::Rgb(248, 113, 113),
+        warn: Color::Rgb(250, 204, 21),
+    };
+
+    pub const LIGHT: Palette = Palette {
+        base_bg: Color::Rgb(255, 255, 255),
+        panel_bg: Color::Reset,
+        input_bg: Color::Reset,
+        tab_bg: Color::Reset,
+        tab_bg_active: Color::Rgb(22, 163, 74),
+        row_highlight_bg: Color::Rgb(22, 163, 74),
+        text: Color::Rgb(17, 24, 39),
+        muted_text: Color::Rgb(75, 85, 99),
+        subtle_text: Color::Rgb(107, 114, 128),
+        accent: Color::Rgb(22, 163, 74),
+        accent_soft: Color::Rgb(21, 128, 61),
+        error: Color::Rgb(220, 38, 38),
+        warn: Color::Rgb(161, 98, 7),
+    };
+
+    pub const HIGH_CONTRAST: Palette = Palette {
+        base_bg: Color::Black,
+        panel_bg: Color::Reset,
+        input_bg: Color::Reset,
+        tab_bg: Color::Reset,
+        tab_bg_active: Color::LightCyan,
+        row_highlight_bg: Color::LightCyan,
+        text: Color::White,
+        muted_text: Color::White,
+        subtle_text: Color::Gray,
+        accent: Color::LightCyan,
+        accent_soft: Color::LightCyan,
+        error: Color::LightRed,
+        warn: Color::Yellow,
+    };
+
+    pub fn named(name: &str) -> Option<Palette> {
+        match name {
+            "dark" => Some(Palette::DARK),
+            "light" => Some(Palette::LIGHT),
+            "high-contrast" => Some(Palette::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+
+    /// Overrides one slot by its `PALETTE_KEYS` name. Returns false for
+    /// unknown keys.
+    pub fn set(&mut self, key: &str, color: Color) -> bool {
+        let slot = match key {
+            "base_bg" => &mut self.base_bg,
+            "panel_bg" => &mut self.panel_bg,
+            "input_bg" => &mut self.input_bg,
+            "tab_bg" => &mut self.tab_bg,
+            "tab_bg_active" => &mut self.tab_bg_active,
+            "row_highlight_bg" => &mut self.row_highlight_bg,
+            "text" => &mut self.text,
+            "muted_text" => &mut self.muted_text,
+            "subtle_text" => &mut self.subtle_text,
+            "accent" => &mut self.accent,
+            "accent_soft" => &mut self.accent_soft,
+            "error" => &mut self.error,
+            "warn" => &mut self.warn,
+            _ => return false,
+        };
+        *slot = color;
+        true
+    }
+}
+
+/// Parses `#rrggbb`, ANSI names (`red`, `lightblue`, ...), or a 0-255 index.
+pub fn parse_color(value: &str) -> Option<Color> {
+    value.trim().parse::<Color>().ok()
+}
+
+struct ActiveTheme {
+    palette: Palette,
+    monochrome: bool,
+}
+
+static ACTIVE_THEME: OnceLock<ActiveTheme> = OnceLock::new();
+
+/// Colors for the current session. Falls back to `dark` before `init`.
+pub fn palette() -> &'static Palette {
+    ACTIVE_THEME
+        .get()
+        .map(|theme| &theme.palette)
+        .unwrap_or(&Palette::DARK)
+}
+
+/// True when `NO_COLOR` is set; frames are rendered without any color.
+pub fn is_monochrome() -> bool {
+    ACTIVE_THEME.get().is_some_and(|theme| theme.monochrome)
+}
+
+/// Selects the session theme. `overrides` are already-validated
+/// `(palette key, color)` pairs applied on top of the named theme.
+pub fn init(theme: Option<&str>, overrides: &[(String, String)]) {
+    let name = resolve_theme_name(theme, std::env::var("COLORFGBG").ok().as_deref());
+    let mut palette = Palette::named(name).unwrap_or(Palette::DARK);
+    for (key, value) in overrides {
+        if let Some(color) = parse_color(value) {
+            palette.set(key, color);
+        }
+    }
+    let monochrome = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let _ = ACTIVE_THEME.set(ActiveTheme {
+        palette,
+        monochrome,
+    });
+}
+
+/// Maps `auto` (or no setting) to `dark`/`light` using `COLORFGBG`
+/// (`"<fg>;<bg>"`, set by rxvt, Konsole, iTerm2 and others). Unknown
+/// backgrounds stay dark.
+pub fn resolve_theme_name(theme: Option<&str>, colorfgbg: Option<&str>) -> &'static str {
+    match theme {
+        Some("dark") => "dark",
+        Some("light") => "light",
+        Some("high-contrast") => "high-contrast",
+        _ => {
+            let background = colorfgbg
+                .and_then(|value| value.rsplit(';').next())
+                .and_then(|bg| bg.trim().parse::<u8>().ok());
+            match background {
+                Some(7 | 9..=15) => "light",
+                _ => "dark",
+            }
+        }
+    }
+}
+
+/// Strips every color from a rendered frame. Cells that relied on a
+/// background for emphasis (selected rows, tabs, buttons) become reversed so
+/// they stay visible.
+pub fn apply_monochrome(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
 
 pub fn render_popup_shell(f: &mut Frame, area: Rect, title: &str) -> Rect {
     f.render_widget(Clear, area);
     f.render_widget(
-        Block::bordered().border_style(Style::default().fg(SUBTLE_TEXT)),
+        Block::bordered().border_style(Style::default().fg(palette().subtle_text)),
         area,
     );
 
+    // Narrow terminals get one column of padding instead of two; short ones
+    // lose the blank row between the header and the body.
+    let padding = if super::layout::is_narrow(area) { 1 } else { 2 };
+    let body_gap = if super::layout::is_short(area) { 0 } else { 1 };
+
     let frame = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
@@ -39,27 +229,31 @@ pub fn render_popup_shell(f: &mut Frame, area: Rect, title: &str) -> Rect {
         ])
         .split(area);
 
-    let title_width = title.chars().count().saturating_add(1) as u16;
+    let title_width = crate::views::layout::display_width(title).saturating_add(1) as u16;
     let header = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(2),
+            Constraint::Length(padding),
             Constraint::Length(title_width),
             Constraint::Min(0),
             Constraint::Length(3),
-            Constraint::Length(2),
+            Constraint::Length(padding),
         ])
         .split(frame[1]);
 
     f.render_widget(
-        Paragraph::new(title).style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD)),
+        Paragraph::new(title).style(
+            Style::default()
+                .fg(palette().text)
+                .add_modifier(Modifier::BOLD),
+        ),
         header[1],
     );
     f.render_widget(
         Paragraph::new("esc")
             .style(
                 Style::default()
-                    .fg(ACCENT_SOFT)
+                    .fg(palette().accent_soft)
                     .add_modifier(Modifier::BOLD),
             )
             .alignment(Alignment::Right),
@@ -68,15 +262,15 @@ pub fn render_popup_shell(f: &mut Frame, area: Rect, title: &str) -> Rect {
 
     let body_with_gap = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .constraints([Constraint::Length(body_gap), Constraint::Min(0)])
         .split(frame[2]);
 
     Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(2),
+            Constraint::Length(padding),
             Constraint::Min(0),
-            Constraint::Length(2),
+            Constraint::Length(padding),
         ])
         .split(body_with_gap[1])[1]
 }
@@ -91,17 +285,60 @@ pub fn shortcut_line(items: &[(&str, &str)]) -> Line<'static> {
         spans.push(Span::styled(
             (*label).to_string(),
             Style::default()
-                .fg(ACCENT_SOFT)
+                .fg(palette().accent_soft)
                 .add_modifier(Modifier::BOLD),
         ));
         if !key.is_empty() {
             spans.push(Span::raw(" "));
             spans.push(Span::styled(
                 (*key).to_string(),
-                Style::default().fg(MUTED_TEXT),
+                Style::default().fg(palette().muted_text),
             ));
         }
     }
 
     Line::from(spans)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn auto_theme_follows_colorfgbg_background() {
+        assert_eq!(resolve_theme_name(None, Some("0;15")), "light");
+        assert_eq!(
+            resolve_theme_name(Some("auto"), Some("15;default;0")),
+            "dark"
+        );
+        assert_eq!(resolve_theme_name(Some("auto"), None), "dark");
+        assert_eq!(
+            resolve_theme_name(Some("high-contrast"), Some("0;15")),
+            "high-contrast"
+        );
+    }
+
+    #[test]
+    fn palette_overrides_by_key() {
+        let mut palette = Palette::DARK;
+        assert!(palette.set("accent", parse_color("#010203").unwrap()));
+        assert_eq!(palette.accent, Color::Rgb(1, 2, 3));
+        assert!(!palette.set("nope", Color::Red));
+        assert_eq!(parse_color("lightgreen"), Some(Color::LightGreen));
+    }
+
+    #[test]
+    fn monochrome_reverses_background_highlights() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer[(0, 0)].set_fg(Color::Red).set_bg(Color::Green);
+        buffer[(1, 0)].set_fg(Color::Red);
+
+        apply_monochrome(&mut buffer);
+
+        assert_eq!(buffer[(0, 0)].bg, Color::Reset);
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(buffer[(1, 0)].fg, Color::Reset);
+        assert!(!buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+    }
+}
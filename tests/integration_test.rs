@@ -1,6 +1,10 @@
 use std::process::Command;
 use tempfile::TempDir;
 
+/// Recorded Iconify responses, so tests that add `heroicons:heart` don't need
+/// the network. Run with `ICONMATE_MOCK_API_RECORD=1` to record new ones.
+const ICONIFY_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/iconify");
+
 #[test]
 fn test_add_command_creates_folder_and_files() {
     // Create a temporary directory for testing
@@ -22,6 +26,7 @@ fn test_add_command_creates_folder_and_files() {
             "Heart",
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("Failed to execute command");
 
@@ -91,6 +96,7 @@ fn test_add_command_with_existing_folder() {
             "Heart",
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("Failed to execute command");
 
@@ -426,6 +432,7 @@ fn test_add_command_preset_normal_with_icon() {
             "Heart",
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("Failed to execute command");
 
@@ -538,6 +545,48 @@ fn test_list_command_uses_default_folder_when_no_flag_is_passed() {
     );
 }
 
+#[test]
+fn test_list_command_filters_by_tag() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHome } from './home.svg';\nexport { default as IconArrow } from './arrow.svg';\nexport { default as IconStar } from './star.svg';\n",
+    )
+    .expect("Failed to write index.ts");
+    std::fs::write(
+        test_folder.join("icon-tags.json"),
+        r#"{ "IconHome": ["navigation"], "IconArrow": ["navigation", "arrows"] }"#,
+    )
+    .expect("Failed to write icon-tags.json");
+
+    let list = |tags: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_iconmate"));
+        command.args(["list", "--folder", test_folder.to_str().unwrap()]);
+        for tag in tags {
+            command.args(["--tag", tag]);
+        }
+        let output = command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    assert_eq!(
+        list(&["navigation"]),
+        "IconHome\t./home.svg\nIconArrow\t./arrow.svg\n"
+    );
+    assert_eq!(list(&["Navigation", "#arrows"]), "IconArrow\t./arrow.svg\n");
+    assert_eq!(list(&["brand"]), "No icons tagged brand\n");
+}
+
 #[test]
 fn test_list_command_reports_no_icons_when_index_is_missing() {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
@@ -584,6 +633,7 @@ fn test_flutter_preset_add_creates_barrel_and_svg() {
             barrel_path.to_str().unwrap(),
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("Failed to execute command");
 
@@ -691,6 +741,7 @@ fn test_flutter_preset_add_infers_name_from_iconify_id() {
             barrel_path.to_str().unwrap(),
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("Failed to execute command");
 
@@ -728,6 +779,7 @@ fn test_flutter_preset_add_uses_collection_prefix_on_collision() {
             barrel_path.to_str().unwrap(),
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("first add should run");
     assert!(first.status.success());
@@ -747,6 +799,7 @@ fn test_flutter_preset_add_uses_collection_prefix_on_collision() {
             barrel_path.to_str().unwrap(),
         ])
         .current_dir(temp_dir.path())
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
         .output()
         .expect("second add should run");
     assert!(
@@ -762,3 +815,2210 @@ fn test_flutter_preset_add_uses_collection_prefix_on_collision() {
         "collision fallback should produce mdiHeart: got {contents}"
     );
 }
+
+#[test]
+fn test_css_preset_add_writes_svg_and_mask_stylesheet() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+
+    let output = Command::new(binary_path)
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "css",
+            "--icon",
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><path d="M0 0h24v24H0z"/></svg>"#,
+            "--name",
+            "Square",
+            "--filename",
+            "square",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(test_folder.join("square.svg").exists());
+    let css = std::fs::read_to_string(test_folder.join("icons.css"))
+        .expect("icons.css should be created");
+    assert!(css.contains(".icon-square {"), "got {css}");
+    assert!(css.contains("mask-image: url(\"data:image/svg+xml,%3Csvg"));
+}
+
+#[test]
+fn test_datauri_preset_add_writes_module_barrel_and_bundle() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+
+    let output = Command::new(binary_path)
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "datauri",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <!-- comment -->\n  <path d=\"M0 0\"/>\n</svg>",
+            "--name",
+            "Dot",
+            "--filename",
+            "dot",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let module = std::fs::read_to_string(test_folder.join("dot.ts")).expect("dot.ts");
+    assert_eq!(
+        module,
+        "export default \"data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg'%3E%3Cpath d='M0 0'/%3E%3C/svg%3E\";\n"
+    );
+
+    let index = std::fs::read_to_string(test_folder.join("index.ts")).expect("index.ts");
+    assert_eq!(index, "export { default as IconDot } from './dot';\n");
+
+    let bundle = std::fs::read_to_string(test_folder.join("icons.ts")).expect("icons.ts");
+    assert!(bundle.contains("export const IconDot = \"data:image/svg+xml,%3Csvg"));
+}
+
+#[test]
+fn test_add_command_raster_rejects_non_svg_preset() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+
+    let output = Command::new(binary_path)
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "react",
+            "--icon",
+            "<svg></svg>",
+            "--name",
+            "Blank",
+            "--raster",
+            "png",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--raster needs an .svg output"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!test_folder.join("blank.tsx").exists());
+}
+
+#[test]
+fn test_add_command_rejects_filenames_outside_the_folder() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    for (filename, message) in [
+        ("../../escape", "leaves the icons folder"),
+        ("/tmp/escape", "is an absolute path"),
+        (".svg", "has no name"),
+    ] {
+        let output = Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                "<svg></svg>",
+                "--name",
+                "Escape",
+                "--filename",
+                filename,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "{filename} should be rejected");
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains(message),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    assert!(!temp_dir.path().join("src/escape.svg").exists());
+    assert!(!test_folder.exists());
+}
+
+#[test]
+fn test_default_command_lists_icons_when_not_a_tty() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\n",
+    )
+    .expect("Failed to write index.ts");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    // `output()` pipes stdout, so this is never a TTY; `--no-tui` forces the
+    // same path explicitly.
+    for args in [vec![], vec!["--no-tui"]] {
+        let output = Command::new(binary_path)
+            .args(&args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("IconHeart\t./heart.svg"),
+            "stdout should list icons instead of starting the TUI"
+        );
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("Not an interactive terminal"),
+            "stderr should explain the fallback"
+        );
+    }
+}
+
+#[test]
+fn test_add_command_expect_sha256_guards_write() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="M0 0h24v24H0z"/></svg>"#;
+    let good = "209818985549f01bc3cd049ba1b25f08fb1d67ce0765e5afe3e550a699e70c97";
+    let bad = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |hash: &str| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                svg,
+                "--name",
+                "Square",
+                "--filename",
+                "square",
+                "--expect-sha256",
+                hash,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(bad);
+    assert!(!output.status.success(), "mismatched hash should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Checksum mismatch"));
+    assert!(!test_folder.join("square.svg").exists());
+
+    let output = run(good);
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(test_folder.join("square.svg").exists());
+}
+
+#[test]
+fn test_delete_command_keeps_icons_whose_names_contain_the_deleted_name() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+
+    for file in ["heart.svg", "heart-fill.svg"] {
+        std::fs::write(test_folder.join(file), "<svg></svg>").expect("Failed to write svg");
+    }
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeartFill } from './heart-fill.svg';\nexport { default as IconHeart } from './heart.svg';\n",
+    )
+    .expect("Failed to write index.ts");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "delete",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--name",
+            "IconHeart",
+            "-y",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!test_folder.join("heart.svg").exists());
+    assert!(test_folder.join("heart-fill.svg").exists());
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconHeartFill } from './heart-fill.svg';\n"
+    );
+}
+
+#[test]
+fn test_delete_command_removes_every_icon_from_a_collection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+
+    for file in ["carbon_heart.svg", "carbon_star.svg", "mdi_star.svg"] {
+        std::fs::write(test_folder.join(file), "<svg></svg>").expect("Failed to write svg");
+    }
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './carbon_heart.svg';\nexport { default as IconStar } from './carbon_star.svg';\nexport { default as IconMdiStar } from './mdi_star.svg';\n",
+    )
+    .expect("Failed to write index.ts");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |prefix: &str| {
+        Command::new(binary_path)
+            .args([
+                "delete",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--prefix",
+                prefix,
+                "-y",
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("carbon");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!test_folder.join("carbon_heart.svg").exists());
+    assert!(!test_folder.join("carbon_star.svg").exists());
+    assert!(test_folder.join("mdi_star.svg").exists());
+    let index = std::fs::read_to_string(test_folder.join("index.ts")).unwrap();
+    assert_eq!(
+        index.trim(),
+        "export { default as IconMdiStar } from './mdi_star.svg';"
+    );
+
+    let output = run("carbon");
+    assert!(!output.status.success(), "no matches should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("prefix=carbon"));
+}
+
+#[test]
+fn test_replace_command_swaps_content_and_keeps_export() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+
+    let index = "export { default as IconHeart } from './mdi_heart.svg';\n";
+    std::fs::write(test_folder.join("index.ts"), index).expect("Failed to write index.ts");
+    std::fs::write(test_folder.join("mdi_heart.svg"), "<svg>old</svg>")
+        .expect("Failed to write svg");
+
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><path d="M0 0h24v24H0z"/></svg>"#;
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |name: &str| {
+        Command::new(binary_path)
+            .args([
+                "replace",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--name",
+                name,
+                "--icon",
+                svg,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("IconHeart");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let content = std::fs::read_to_string(test_folder.join("mdi_heart.svg")).unwrap();
+    assert!(
+        content.contains("M0 0h24v24H0z"),
+        "content should be replaced"
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        index
+    );
+
+    let output = run("IconStar");
+    assert!(!output.status.success(), "unknown name should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No icon named 'IconStar'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_add_command_runs_pre_and_post_add_hooks() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "post_add_cmd": "touch %folder%/added-%name%" }"#,
+    )
+    .expect("Failed to write config");
+
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"></svg>"#;
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |name: &str| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                svg,
+                "--name",
+                name,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("Square");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(test_folder.join("added-IconSquare").exists());
+
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "pre_add_cmd": "false" }"#,
+    )
+    .expect("Failed to write config");
+    let output = run("Circle");
+    assert!(!output.status.success(), "failing pre hook should abort");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pre_add_cmd failed"));
+    assert!(!test_folder.join("circle.svg").exists());
+}
+
+#[test]
+fn test_list_command_reads_folder_from_package_json() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let icons_folder = temp_dir.path().join("app/icons");
+    std::fs::create_dir_all(&icons_folder).expect("Failed to create icons folder");
+    std::fs::write(
+        icons_folder.join("index.ts"),
+        "export { default as IconHouse } from './house.svg';\n",
+    )
+    .expect("Failed to write index.ts");
+    std::fs::write(
+        temp_dir.path().join("package.json"),
+        r#"{ "name": "web", "iconmate": { "folder": "app/icons" } }"#,
+    )
+    .expect("Failed to write package.json");
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let output = Command::new(binary_path)
+        .args(["list"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("IconHouse\t./house.svg"),
+        "stdout should include icon from the package.json folder"
+    );
+}
+
+#[test]
+fn test_add_command_sanitizes_svg_unless_opted_out() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    let svg = "<?xml version=\"1.0\"?>\n<!-- Generator: Sketch -->\n<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <!-- outline -->\n  <path d=\"M0 0\"/>\n</svg>";
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |name: &str, extra: &[&str]| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                svg,
+                "--name",
+                name,
+                "--filename",
+                &name.to_lowercase(),
+            ])
+            .args(extra)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("Clean", &[]);
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let clean = std::fs::read_to_string(test_folder.join("clean.svg")).unwrap();
+    assert_eq!(
+        clean,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <path d=\"M0 0\"/>\n</svg>\n"
+    );
+
+    let output = run("Raw", &["--no-sanitize"]);
+    assert!(output.status.success());
+    let raw = std::fs::read_to_string(test_folder.join("raw.svg")).unwrap();
+    assert_eq!(raw, format!("{svg}\n"));
+}
+
+#[test]
+fn test_add_command_converts_jsx_svg_pastes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    let jsx = r#"<svg {...props} className="size-4" strokeWidth={2} viewBox="0 0 24 24"><path strokeLinecap="round" d="M0 0"/></svg>"#;
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |preset: &str, name: &str| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--preset",
+                preset,
+                "--icon",
+                jsx,
+                "--name",
+                name,
+                "--filename",
+                &name.to_lowercase(),
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("normal", "Plain");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = std::fs::read_to_string(test_folder.join("plain.svg")).unwrap();
+    assert_eq!(
+        svg,
+        "<svg class=\"size-4\" stroke-width=\"2\" viewBox=\"0 0 24 24\" xmlns=\"http://www.w3.org/2000/svg\"><path stroke-linecap=\"round\" d=\"M0 0\"/></svg>\n"
+    );
+
+    let output = run("react", "Component");
+    assert!(output.status.success());
+    let component = std::fs::read_to_string(test_folder.join("component.tsx")).unwrap();
+    assert_eq!(component.matches("{...props}").count(), 1);
+    assert!(!component.contains("className=\"size-4\""));
+}
+
+#[test]
+fn test_add_command_accepts_base64_data_uri() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--icon",
+            "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciPjxwYXRoIGQ9Ik0wIDAiLz48L3N2Zz4=",
+            "--name",
+            "Pasted",
+            "--filename",
+            "pasted",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("pasted.svg")).unwrap(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg>\n"
+    );
+}
+
+#[test]
+fn test_add_command_is_idempotent_with_on_conflict_skip() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "line_endings": "crlf" }"#,
+    )
+    .unwrap();
+    let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <path d=\"M0 0\"/>\n</svg>";
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |extra: &[&str]| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                svg,
+                "--name",
+                "Dot",
+            ])
+            .args(extra)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+    let snapshot = || {
+        (
+            std::fs::read(test_folder.join("dot.svg")).unwrap(),
+            std::fs::read(test_folder.join("index.ts")).unwrap(),
+        )
+    };
+
+    let output = run(&[]);
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let first = snapshot();
+    assert_eq!(
+        String::from_utf8_lossy(&first.0),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\r\n  <path d=\"M0 0\"/>\r\n</svg>\r\n"
+    );
+    assert!(String::from_utf8_lossy(&first.1).ends_with("';\r\n"));
+
+    let output = run(&[]);
+    assert!(!output.status.success());
+
+    let output = run(&["--on-conflict", "skip"]);
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Skipped IconDot"));
+    assert_eq!(snapshot(), first);
+}
+
+#[test]
+fn test_add_command_fans_out_to_every_folder_or_none() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let web = temp_dir.path().join("web/icons");
+    let app = temp_dir.path().join("app/icons");
+    let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg>";
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let run = |name: &str| {
+        Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                web.to_str().unwrap(),
+                "--folder",
+                &format!("{}=react", app.display()),
+                "--icon",
+                svg,
+                "--name",
+                name,
+                "--filename",
+                &name.to_lowercase(),
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("Dot");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(web.join("dot.svg").exists());
+    assert!(app.join("dot.tsx").exists());
+    assert!(
+        std::fs::read_to_string(web.join("index.ts"))
+            .unwrap()
+            .contains("export { default as IconDot } from './dot.svg';")
+    );
+    assert!(
+        std::fs::read_to_string(app.join("index.ts"))
+            .unwrap()
+            .contains("export { default as IconDot } from './dot';")
+    );
+
+    // `Ring` is free in web/icons but its file is taken in app/icons.
+    std::fs::write(app.join("ring.tsx"), "taken").unwrap();
+    let output = run("Ring");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Nothing was written"));
+    assert!(!web.join("ring.svg").exists());
+    assert!(
+        !std::fs::read_to_string(web.join("index.ts"))
+            .unwrap()
+            .contains("Ring")
+    );
+
+    // Every target's pre hook runs before the first write.
+    std::fs::remove_file(app.join("ring.tsx")).unwrap();
+    std::fs::write(app.join("blocked"), "").unwrap();
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "pre_add_cmd": "test ! -e %folder%/blocked" }"#,
+    )
+    .unwrap();
+    let output = run("Ring");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pre_add_cmd failed"));
+    assert!(!web.join("ring.svg").exists());
+    assert!(!app.join("ring.tsx").exists());
+}
+
+#[test]
+fn test_add_command_fetches_once_for_every_folder() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let web = temp_dir.path().join("web/icons");
+    let app = temp_dir.path().join("app/icons");
+
+    // Answers a single request; a second download would be refused.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/dot.svg", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).is_ok() && line.trim() != "" {
+            line.clear();
+        }
+        let body = "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg>";
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let output = Command::new(binary_path)
+        .args([
+            "add",
+            "--folder",
+            web.to_str().unwrap(),
+            "--folder",
+            &format!("{}=react", app.display()),
+            "--icon",
+            &url,
+            "--name",
+            "Dot",
+            "--filename",
+            "dot",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    server.join().unwrap();
+
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout)
+            .matches("Fetching icon from")
+            .count(),
+        1
+    );
+    assert!(web.join("dot.svg").exists());
+    assert!(app.join("dot.tsx").exists());
+}
+
+#[test]
+fn test_add_command_writes_and_keeps_index_header_and_footer() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{
+  "index_header": "AUTO-GENERATED by iconmate — do not edit",
+  "index_footer": "end of icons",
+  "index_eslint_disable": true
+}"#,
+    )
+    .unwrap();
+
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    for name in ["First", "Second"] {
+        let output = Command::new(binary_path)
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                name,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "/* eslint-disable */\n\
+         // AUTO-GENERATED by iconmate — do not edit\n\
+         export { default as IconFirst } from './first.svg';\n\
+         export { default as IconSecond } from './second.svg';\n\
+         // end of icons\n"
+    );
+}
+
+#[test]
+fn test_add_command_applies_code_style_to_component_and_export() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "code_style": { "quotes": "double", "semicolons": false, "indent": 4 } }"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "react",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+            "--name",
+            "Dot",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconDot } from \"./dot\"\n"
+    );
+    let component = std::fs::read_to_string(test_folder.join("dot.tsx")).unwrap();
+    assert!(component.starts_with("import type { SVGProps } from \"react\"\n"));
+    assert!(component.contains("\n    return (\n"));
+    assert!(component.ends_with("\n    )\n}\n"));
+}
+
+#[test]
+fn test_add_and_delete_keep_icon_names_dts_in_sync() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "icon_names_dts": true }"#,
+    )
+    .unwrap();
+
+    for name in ["Dot", "Ring"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                name,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let dts = std::fs::read_to_string(test_folder.join("icon-names.d.ts")).unwrap();
+    assert!(dts.ends_with("export type IconName =\n  | 'dot'\n  | 'ring';\n"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "delete",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--name",
+            "IconDot",
+            "--yes",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let dts = std::fs::read_to_string(test_folder.join("icon-names.d.ts")).unwrap();
+    assert!(dts.ends_with("export type IconName =\n  | 'ring';\n"));
+}
+
+#[test]
+fn test_add_command_appends_import_query() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+            "--name",
+            "Dot",
+            "--import-query",
+            "react",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconDot } from './dot.svg?react';\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "react",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+            "--name",
+            "Ring",
+            "--import-query",
+            "raw",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--import-query needs an .svg output")
+    );
+}
+
+#[test]
+fn test_init_auto_writes_detected_preset_and_import_query() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    std::fs::write(
+        temp_dir.path().join("package.json"),
+        r#"{ "dependencies": { "react": "^19.0.0" }, "devDependencies": { "vite": "^6.0.0", "vite-plugin-svgr": "^4.0.0" } }"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["init", "--auto"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("React + Vite (vite-plugin-svgr)"));
+
+    let config = std::fs::read_to_string(temp_dir.path().join("iconmate.config.jsonc")).unwrap();
+    assert!(config.contains("\"preset\": \"normal\""));
+    assert!(config.contains("\"import_query\": \"react\""));
+}
+
+#[test]
+fn test_add_command_svgr_compat_and_replace_keep_svgr_shape() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "react",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\"><path stroke-width=\"2\"/></svg>",
+            "--name",
+            "Heart",
+            "--filename",
+            "heart",
+            "--svgr-compat",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let component = std::fs::read_to_string(test_folder.join("heart.tsx")).unwrap();
+    assert!(component.contains("const SvgHeart = (\n"));
+    assert!(component.contains(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width={24} ref={ref} aria-labelledby={titleId} {...props}>{title ? <title id={titleId}>{title}</title> : null}<path strokeWidth={2}/></svg>"
+    ));
+    assert!(component.ends_with(
+        "const ForwardRef = forwardRef(SvgHeart);\nconst Memo = memo(ForwardRef);\nexport default Memo;\n"
+    ));
+
+    // No flag or config key: the existing component's shape decides.
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "--preset",
+            "react",
+            "replace",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--name",
+            "IconHeart",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><circle r=\"4\"/></svg>",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let component = std::fs::read_to_string(test_folder.join("heart.tsx")).unwrap();
+    assert!(component.contains("<circle r={4}/></svg>"));
+    assert!(component.contains("forwardRef(SvgHeart)"));
+}
+
+#[test]
+fn test_icon_registry_tracks_adds_and_deletes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "preset": "react", "icon_registry": true }"#,
+    )
+    .unwrap();
+
+    for name in ["Dot", "Ring"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                name,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let registry = std::fs::read_to_string(test_folder.join("icon-registry.ts")).unwrap();
+    assert!(registry.contains("  'dot': () => import('./dot"));
+    assert!(registry.contains("  'ring': () => import('./ring"));
+    assert!(test_folder.join("Icon.tsx").exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "delete",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--name",
+            "IconDot",
+            "--yes",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let registry = std::fs::read_to_string(test_folder.join("icon-registry.ts")).unwrap();
+    assert!(!registry.contains("'dot'"));
+    assert!(registry.contains("'ring'"));
+
+    // `Icon.tsx` isn't an icon `sync` should pick up.
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["sync", "--folder", test_folder.to_str().unwrap()])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Icon.tsx"));
+}
+
+#[test]
+fn test_add_command_lazy_exports() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+
+    let add = |preset: &str, name: &str| {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--preset",
+                preset,
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                name,
+                "--lazy",
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+    for name in ["Dot", "Ring"] {
+        let output = add("vue", name);
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let index = std::fs::read_to_string(test_folder.join("index.ts")).unwrap();
+    assert_eq!(
+        index,
+        "import { defineAsyncComponent } from 'vue';\nexport const IconDot = defineAsyncComponent(() => import('./dot.vue'));\nexport const IconRing = defineAsyncComponent(() => import('./ring.vue'));\n"
+    );
+
+    let output = add("svelte", "Star");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--lazy needs --preset react"));
+}
+
+#[test]
+fn test_check_command_flags_tree_shaking_problems() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    std::fs::write(
+        temp_dir.path().join("package.json"),
+        r#"{ "name": "app", "sideEffects": false }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\n",
+    )
+    .unwrap();
+
+    let check = || {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args(["check", "--folder", test_folder.to_str().unwrap()])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+    let output = check();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No problems found"));
+
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\nexport * from './social';\n",
+    )
+    .unwrap();
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("index.ts:2: `export * from './social'`"));
+    assert!(stdout.contains("1 problem(s) found."));
+}
+
+#[test]
+fn test_add_command_enforces_max_icon_size() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "max_icon_size": 256 }"#,
+    )
+    .unwrap();
+    let large_svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"{}\"/></svg>",
+        "M0 0h1".repeat(100)
+    );
+
+    let add = |name: &str, icon: &str, force: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_iconmate"));
+        command.args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "normal",
+            "--icon",
+            icon,
+            "--name",
+            name,
+        ]);
+        if force {
+            command.arg("--force");
+        }
+        command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = add(
+        "Small",
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+        false,
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let output = add("Hero", &large_svg, false);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("hero.svg is 6") && stderr.contains("over the 256 B max_icon_size"),
+        "{stderr}"
+    );
+    assert!(!test_folder.join("hero.svg").exists());
+
+    let output = add("Hero", &large_svg, true);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Warning: hero.svg is"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["check", "--folder", test_folder.to_str().unwrap()])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("hero.svg: 6"), "{stdout}");
+    assert!(!stdout.contains("small.svg"), "{stdout}");
+}
+
+#[test]
+fn test_dedupe_command_merges_identical_icons() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M1 1h22\"/></svg>\n";
+    std::fs::write(test_folder.join("heart.svg"), svg).unwrap();
+    std::fs::write(test_folder.join("love.svg"), svg).unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\nexport { default as IconLove } from './love.svg';\n",
+    )
+    .unwrap();
+
+    let dedupe = |apply: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_iconmate"));
+        command.args(["dedupe", "--folder", test_folder.to_str().unwrap()]);
+        if apply {
+            command.arg("--apply");
+        }
+        command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = dedupe(false);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+export { default as IconLove } from './heart.svg';"));
+    assert!(stdout.contains("- love.svg"));
+    assert!(test_folder.join("love.svg").exists());
+
+    let output = dedupe(true);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!test_folder.join("love.svg").exists());
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconHeart } from './heart.svg';\nexport { default as IconLove } from './heart.svg';\n"
+    );
+}
+
+#[test]
+fn test_normalize_command_renames_files_and_imports() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    std::fs::write(temp_dir.path().join("package.json"), r#"{ "name": "app" }"#).unwrap();
+    std::fs::write(
+        test_folder.join("mdi:heart.svg"),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+    )
+    .unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './mdi:heart.svg';\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("src/App.tsx"),
+        "import heartUrl from './icons/mdi:heart.svg';\n",
+    )
+    .unwrap();
+
+    let normalize = |apply: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_iconmate"));
+        command.args([
+            "normalize",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--rewrite-imports",
+        ]);
+        if apply {
+            command.arg("--apply");
+        }
+        command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = normalize(false);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("mdi:heart.svg → mdi-heart.svg"), "{stdout}");
+    assert!(test_folder.join("mdi:heart.svg").exists());
+
+    let output = normalize(true);
+    assert!(output.status.success(), "{output:?}");
+    assert!(test_folder.join("mdi-heart.svg").exists());
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconHeart } from './mdi-heart.svg';\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("src/App.tsx")).unwrap(),
+        "import heartUrl from './icons/mdi-heart.svg';\n"
+    );
+}
+
+#[test]
+fn test_adopt_command_writes_index_and_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("legacy-icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+    std::fs::write(test_folder.join("HeartIcon.svg"), svg).unwrap();
+    std::fs::write(test_folder.join("ic_close.svg"), svg).unwrap();
+    std::fs::write(test_folder.join("logo.png"), [0u8; 4]).unwrap();
+
+    let adopt = |apply: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_iconmate"));
+        command.args(["adopt", "--folder", "legacy-icons"]);
+        if apply {
+            command.arg("--apply");
+        }
+        command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = adopt(false);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+ IconHeart ← ./HeartIcon.svg"), "{stdout}");
+    assert!(stdout.contains("! logo.png"), "{stdout}");
+    assert!(!test_folder.join("index.ts").exists());
+
+    let output = adopt(true);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconHeart } from './HeartIcon.svg';\nexport { default as IconClose } from './ic_close.svg';\n"
+    );
+    let config = std::fs::read_to_string(temp_dir.path().join("iconmate.config.jsonc")).unwrap();
+    assert!(config.contains("\"folder\": \"legacy-icons\""), "{config}");
+    assert!(config.contains("\"preset\": \"normal\""), "{config}");
+
+    let output = adopt(false);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_log_command_shows_adds_and_deletes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args(args)
+            .env("ICONMATE_USER", "alice")
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&[
+        "add",
+        "--folder",
+        "icons",
+        "--preset",
+        "normal",
+        "--icon",
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+        "--name",
+        "Heart",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let output = run(&["delete", "--folder", "icons", "--name", "IconHeart", "-y"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let log = std::fs::read_to_string(temp_dir.path().join(".iconmate/log.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 2, "{log}");
+    assert!(log.contains("\"source\":\"inline svg\""), "{log}");
+
+    let output = run(&["log"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(
+        lines[0].contains("alice  delete  IconHeart  icons/heart.svg"),
+        "{stdout}"
+    );
+    assert!(lines[1].contains("alice  add     IconHeart  icons/heart.svg  (inline svg)"));
+
+    let output = run(&["log", "-n", "1", "--json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"op\":\"delete\""), "{stdout}");
+    assert_eq!(stdout.lines().count(), 1);
+}
+
+#[test]
+fn test_changelog_command_summarizes_changes_since_ref() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to run git");
+        assert!(output.status.success(), "{output:?}");
+    };
+    let svg =
+        |d: &str| format!("<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"{d}\"/></svg>");
+    std::fs::write(test_folder.join("heart.svg"), svg("M1 1")).unwrap();
+    std::fs::write(test_folder.join("close.svg"), svg("M2 2")).unwrap();
+    std::fs::write(test_folder.join("star.svg"), svg("M3 3")).unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconFav } from './heart.svg';\nexport { default as IconClose } from './close.svg';\nexport { default as IconStar } from './star.svg';\n",
+    )
+    .unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "-A"]);
+    git(&["commit", "-q", "-m", "v1"]);
+    git(&["tag", "v1.0.0"]);
+
+    std::fs::remove_file(test_folder.join("close.svg")).unwrap();
+    std::fs::write(test_folder.join("star.svg"), svg("M4 4")).unwrap();
+    std::fs::write(test_folder.join("bell.svg"), svg("M5 5")).unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\nexport { default as IconStar } from './star.svg';\nexport { default as IconBell } from './bell.svg';\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["changelog", "--folder", "icons", "--since", "v1.0.0"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "## Icons\n\nChanges since `v1.0.0`.\n\n**Breaking:** 2 exports removed or renamed.\n\n### Added\n\n- `IconBell`\n\n### Renamed\n\n- `IconFav` → `IconHeart`\n\n### Removed\n\n- `IconClose`\n\n### Updated\n\n- `IconStar`\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["changelog", "--folder", "icons", "--since", "v9"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_package_command_writes_publishable_package() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    std::fs::write(
+        temp_dir.path().join("package.json"),
+        r#"{ "name": "@acme/icons", "version": "2.1.0", "scripts": {} }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        test_folder.join("heart.svg"),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M1 1\"/></svg>",
+    )
+    .unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["package", "--folder", "icons", "--out", "dist"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Packaged 1 export as @acme/icons@2.1.0")
+    );
+
+    let dist = temp_dir.path().join("dist");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dist.join("package.json")).unwrap()).unwrap();
+    assert_eq!(manifest["version"], "2.1.0");
+    assert_eq!(manifest["sideEffects"], false);
+    assert_eq!(manifest["exports"]["."]["types"], "./index.d.ts");
+    assert!(
+        std::fs::read_to_string(dist.join("index.js"))
+            .unwrap()
+            .contains("export { default as IconHeart } from \"./icons/heart.js\";")
+    );
+    assert!(
+        std::fs::read_to_string(dist.join("icons/heart.js"))
+            .unwrap()
+            .starts_with("export default \"<svg")
+    );
+    assert!(dist.join("icons/heart.d.ts").exists());
+    assert!(dist.join("svg/heart.svg").exists());
+
+    // Rebuilding drops files of icons that are gone.
+    std::fs::write(test_folder.join("index.ts"), "").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["package", "--folder", "icons", "--out", "dist"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "{output:?}");
+    assert!(!dist.join("icons/heart.js").exists());
+
+    // A folder iconmate didn't write is left alone.
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["package", "--folder", "icons", "--out", "icons"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(test_folder.join("heart.svg").exists());
+}
+
+#[test]
+fn test_check_workspace_flags_divergent_aliases() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for (package, d) in [("web", "M1 1"), ("admin", "M2 2")] {
+        let root = temp_dir.path().join("packages").join(package);
+        let icons = root.join("src/icons");
+        std::fs::create_dir_all(&icons).unwrap();
+        std::fs::write(
+            root.join("iconmate.config.json"),
+            r#"{ "folder": "src/icons", "preset": "normal" }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            icons.join("close.svg"),
+            format!("<svg viewBox=\"0 0 24 24\"><path d=\"{d}\"/></svg>"),
+        )
+        .unwrap();
+        std::fs::write(
+            icons.join("index.ts"),
+            "export { default as IconClose } from './close.svg';\n",
+        )
+        .unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["check", "--workspace"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "IconClose has different artwork across packages: packages/admin/src/icons/close.svg, packages/web/src/icons/close.svg"
+    ));
+
+    std::fs::copy(
+        temp_dir.path().join("packages/web/src/icons/close.svg"),
+        temp_dir.path().join("packages/admin/src/icons/close.svg"),
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["check", "--workspace"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("across 2 icon folder(s)"));
+}
+
+#[test]
+fn test_replace_diff_previews_before_overwriting() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&test_folder).expect("Failed to create icons folder");
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\n",
+    )
+    .unwrap();
+    let old = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M1 1\"/></svg>\n";
+    std::fs::write(test_folder.join("heart.svg"), old).unwrap();
+
+    let new =
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><path d="M2 2"/></svg>"#;
+    let run = |flags: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args(["replace", "--name", "IconHeart", "--icon", new])
+            .args(flags)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("-<path d=\"M1 1\"/>\n+<path d=\"M2 2\"/>\n"),
+        "{stdout}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("heart.svg")).unwrap(),
+        old
+    );
+
+    // Not a terminal: --diff alone refuses to guess.
+    let output = run(&["--diff"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --yes"));
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("heart.svg")).unwrap(),
+        old
+    );
+
+    let output = run(&["--diff", "--yes"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        std::fs::read_to_string(test_folder.join("heart.svg"))
+            .unwrap()
+            .contains("M2 2")
+    );
+
+    let output = run(&["--dry-run"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("nothing to replace"));
+}
+
+#[test]
+fn test_mock_api_serves_iconify_from_fixtures() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+    let fixtures = ICONIFY_FIXTURES;
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+
+    let output = Command::new(binary_path)
+        .args([
+            "--mock-api",
+            fixtures,
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "normal",
+            "--icon",
+            "heroicons:heart",
+            "--name",
+            "Heart",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = std::fs::read_to_string(test_folder.join("heroicons_heart.svg")).unwrap();
+    assert!(svg.contains("M21 8.25c0-2.485"));
+
+    let output = Command::new(binary_path)
+        .args(["iconify", "search", "heart", "--mock-api", fixtures])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "heroicons:heart\nmdi:heart\n"
+    );
+
+    let output = Command::new(binary_path)
+        .args(["--mock-api", fixtures, "iconify", "get", "mdi:star"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_add_resolves_a_renamed_iconify_alias() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/assets/icons");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "--mock-api",
+            ICONIFY_FIXTURES,
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "normal",
+            "--icon",
+            "mdi:house",
+            "--name",
+            "House",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Warning: mdi:house is a deprecated alias of mdi:home")
+    );
+    assert!(
+        std::fs::read_to_string(test_folder.join("mdi_home.svg"))
+            .unwrap()
+            .contains("M10 20v-6h4v6h5v-8h3L12 3L2 12h3v8z")
+    );
+    let log = std::fs::read_to_string(temp_dir.path().join(".iconmate/log.jsonl")).unwrap();
+    assert!(log.contains("\"source\":\"mdi:home\""), "{log}");
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_headless_script_adds_icon_through_add_popup() {
+    use std::io::Write;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let icons_folder = temp_dir.path().join("src/assets/icons");
+    std::fs::create_dir_all(&icons_folder).unwrap();
+    std::fs::write(icons_folder.join("index.ts"), "").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["--folder", "src/assets/icons", "headless", "-"])
+        .env("ICONMATE_MOCK_API", ICONIFY_FIXTURES)
+        .current_dir(temp_dir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            b"# add heroicons:heart from the add popup\n\
+              press a tab\n\
+              type heroicons:heart\n\
+              expect heroicons_heart.svg\n\
+              press tab tab tab enter\n\
+              wait Added Heart\n\
+              press esc\n",
+        )
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let screen = String::from_utf8_lossy(&output.stdout);
+    assert!(screen.starts_with("--- end ---\n"));
+    assert!(screen.contains("IconHeart"));
+    assert_eq!(
+        std::fs::read_to_string(icons_folder.join("index.ts")).unwrap(),
+        "export { default as IconHeart } from './heroicons_heart.svg';\n"
+    );
+}
+
+#[test]
+fn test_add_command_group_writes_nested_index_and_reexport() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    let binary_path = env!("CARGO_BIN_EXE_iconmate");
+    let add = |name: &str, group: Option<&str>| {
+        let mut command = Command::new(binary_path);
+        command.args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>",
+            "--name",
+            name,
+        ]);
+        if let Some(group) = group {
+            command.args(["--group", group]);
+        }
+        command
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    for (name, group) in [
+        ("Home", None),
+        ("X", Some("social")),
+        ("Github", Some("social/")),
+    ] {
+        let output = add(name, group);
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    assert!(test_folder.join("social/x.svg").exists());
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("social/index.ts")).unwrap(),
+        "export { default as IconX } from './x.svg';\nexport { default as IconGithub } from './github.svg';\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("index.ts")).unwrap(),
+        "export { default as IconHome } from './home.svg';\nexport * from './social';\n"
+    );
+
+    // `export *` would make a second IconX ambiguous, whichever barrel it's in.
+    let output = add("X", None);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("social"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(binary_path)
+        .args(["list", "--folder", test_folder.to_str().unwrap()])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("./social/github.svg"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_add_command_rust_index_strategy_writes_mod_rs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "index_strategy": "rust" }"#,
+    )
+    .unwrap();
+
+    let add = |preset: &str| {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--preset",
+                preset,
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                "Dot",
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+    let output = add("normal");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("mod.rs")).unwrap(),
+        "pub const ICON_DOT: &str = include_str!(\"dot.svg\");\n"
+    );
+    assert!(test_folder.join("dot.svg").exists());
+    assert!(!test_folder.join("index.ts").exists());
+
+    let output = add("react");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("embeds .svg files"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["list", "--folder", test_folder.to_str().unwrap()])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ICON_DOT\t./dot.svg"));
+}
+
+#[test]
+fn test_add_command_rust_preset_appends_to_mod_rs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("src/icons");
+
+    for name in ["Heart", "Star"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args([
+                "add",
+                "--folder",
+                test_folder.to_str().unwrap(),
+                "--preset",
+                "rust",
+                "--icon",
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                "--name",
+                name,
+            ])
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("mod.rs")).unwrap(),
+        "pub const HEART: &str = include_str!(\"heart.svg\");\n\
+         pub const STAR: &str = include_str!(\"star.svg\");\n"
+    );
+    assert!(test_folder.join("heart.svg").exists());
+    assert!(!test_folder.join("index.ts").exists());
+}
+
+#[test]
+fn test_add_command_html_preset_writes_partial_and_manifest() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("templates/icons");
+    std::fs::write(
+        temp_dir.path().join("iconmate.config.json"),
+        r#"{ "html_class_hook": "{{ class }}" }"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "add",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--preset",
+            "html",
+            "--icon",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><path/></svg>",
+            "--name",
+            "Heart",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("_heart.svg.html")).unwrap(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"{{ class }}\"><path/></svg>\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("partials.json")).unwrap(),
+        "{\n  \"heart\": \"_heart.svg.html\"\n}\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "delete",
+            "--folder",
+            test_folder.to_str().unwrap(),
+            "--name",
+            "heart",
+            "--yes",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!test_folder.join("_heart.svg.html").exists());
+    assert_eq!(
+        std::fs::read_to_string(test_folder.join("partials.json")).unwrap(),
+        "{}\n"
+    );
+}
+
+#[test]
+fn test_css_command_writes_background_stylesheet_kept_in_sync() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    std::fs::write(test_folder.join("heart.svg"), "<svg></svg>").unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconHeart } from './heart.svg';\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Command failed with stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    run(&["css", "--folder", test_folder.to_str().unwrap()]);
+    let stylesheet = test_folder.join("icons-bg.css");
+    let css = std::fs::read_to_string(&stylesheet).unwrap();
+    assert!(css.contains(
+        ".icon-heart {\n  display: inline-block;\n  width: 1em;\n  height: 1em;\n  background-image: url(\"data:image/svg+xml,%3Csvg%3E%3C/svg%3E\");\n"
+    ));
+
+    run(&[
+        "add",
+        "--folder",
+        test_folder.to_str().unwrap(),
+        "--icon",
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+        "--name",
+        "Dot",
+    ]);
+    assert!(
+        std::fs::read_to_string(&stylesheet)
+            .unwrap()
+            .contains(".icon-dot {")
+    );
+
+    run(&[
+        "delete",
+        "--folder",
+        test_folder.to_str().unwrap(),
+        "--name",
+        "IconHeart",
+        "--yes",
+    ]);
+    assert!(
+        !std::fs::read_to_string(&stylesheet)
+            .unwrap()
+            .contains(".icon-heart {")
+    );
+}
+
+#[test]
+fn test_open_command_rejects_missing_folder() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let missing = temp_dir.path().join("icons");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args(["open", "--folder", missing.to_str().unwrap()])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Icons folder not found"));
+}
+
+#[test]
+fn test_preview_command_prints_icon_in_terminal() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let test_folder = temp_dir.path().join("icons");
+    std::fs::create_dir_all(&test_folder).unwrap();
+    std::fs::write(
+        test_folder.join("square.svg"),
+        r#"<svg viewBox="0 0 8 8"><path d="M0 0h8v8H0z"/></svg>"#,
+    )
+    .unwrap();
+    std::fs::write(
+        test_folder.join("index.ts"),
+        "export { default as IconSquare } from './square.svg';\n",
+    )
+    .unwrap();
+
+    let preview = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_iconmate"))
+            .arg("preview")
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = preview(&[
+        "iconsquare",
+        "--folder",
+        test_folder.to_str().unwrap(),
+        "--size",
+        "4",
+    ]);
+    assert!(
+        output.status.success(),
+        "Command failed with stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "⣿⣿⣿⣿\n⣿⣿⣿⣿\n");
+
+    let output = preview(&[test_folder.join("square.svg").to_str().unwrap(), "--sixel"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("\x1bP0;1;0q\"1;1;128;128"));
+
+    let output = preview(&["IconMissing", "--folder", test_folder.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No icon named 'IconMissing'"));
+}
+
+#[test]
+fn test_template_test_command_renders_sample_and_warns() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "--preset",
+            "svelte",
+            "template",
+            "test",
+            "export { default as Icon%name% } from './%filename%';",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "export { default as IconHeart } from './mdi_heart';\n"
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("%filename% without %ext%: svelte files end in .svelte")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_iconmate"))
+        .args([
+            "template",
+            "test",
+            "%prefix|upper%-%nme%",
+            "--icon",
+            "lucide:x",
+        ])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "LUCIDE-%nme%\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown variable %nme%"));
+}